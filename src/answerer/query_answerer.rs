@@ -329,6 +329,7 @@ mod tests {
             fts_score: Some(0.7),
             graph_score: Some(0.5),
             found_by_both: true,
+            snippet: None,
         }
     }
 
@@ -1,8 +1,10 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 /// Post-processing layer for tag normalization.
 ///
 /// Ensures consistent tag formatting regardless of LLM output quality.
 /// All tags are normalized to lowercase, kebab-case format with only
-/// alphanumeric characters and hyphens.
+/// letters and digits (from any script) and hyphens.
 pub struct TagNormalizer;
 
 impl TagNormalizer {
@@ -10,9 +12,13 @@ impl TagNormalizer {
     ///
     /// # Normalization rules
     ///
-    /// - Converts to lowercase
-    /// - Replaces spaces with hyphens
-    /// - Removes all characters except alphanumeric and hyphens
+    /// - Converts to lowercase using Unicode case mapping
+    /// - Replaces whitespace (including tabs and no-break spaces) with hyphens
+    /// - Keeps letters and digits from any script, with their combining marks
+    ///   (accents, vowel signs), and removes other characters such as
+    ///   punctuation and symbols
+    /// - Removed punctuation is not a word break, so `node.js` becomes
+    ///   `nodejs` and `c++` becomes `c` (which then matches a plain `c` tag)
     /// - Trims leading/trailing whitespace and hyphens
     ///
     /// # Examples
@@ -23,18 +29,28 @@ impl TagNormalizer {
     /// assert_eq!(TagNormalizer::normalize_tag("RUST"), "rust");
     /// assert_eq!(TagNormalizer::normalize_tag("machine learning"), "machine-learning");
     /// assert_eq!(TagNormalizer::normalize_tag("C++"), "c");
+    /// assert_eq!(TagNormalizer::normalize_tag("Node.js"), "nodejs");
     /// assert_eq!(TagNormalizer::normalize_tag("rust!"), "rust");
     /// assert_eq!(TagNormalizer::normalize_tag("  --rust--  "), "rust");
     /// assert_eq!(TagNormalizer::normalize_tag("Machine Learning!"), "machine-learning");
+    /// assert_eq!(TagNormalizer::normalize_tag("Café Notes"), "café-notes");
+    /// assert_eq!(TagNormalizer::normalize_tag("機械学習"), "機械学習");
     /// ```
     #[must_use]
     pub fn normalize_tag(tag: &str) -> String {
-        let normalized = tag
-            .to_lowercase()
-            .replace(' ', "-")
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-')
-            .collect::<String>();
+        // Work on grapheme clusters so combining marks stay with their base letter
+        let lowered = tag.to_lowercase();
+        let mut normalized = String::with_capacity(lowered.len());
+        for grapheme in lowered.graphemes(true) {
+            let Some(base) = grapheme.chars().next() else {
+                continue;
+            };
+            if base.is_whitespace() || base == '-' {
+                normalized.push('-');
+            } else if base.is_alphanumeric() {
+                normalized.push_str(grapheme);
+            }
+        }
 
         // Collapse consecutive hyphens into a single hyphen
         let collapsed = normalized
@@ -155,6 +171,53 @@ mod tests {
         assert_eq!(TagNormalizer::normalize_tag("--WEB 2.0--"), "web-20");
     }
 
+    #[test]
+    fn test_unicode_letters_are_preserved() {
+        assert_eq!(TagNormalizer::normalize_tag("Café Notes"), "café-notes");
+        assert_eq!(TagNormalizer::normalize_tag("機械学習"), "機械学習");
+        assert_eq!(TagNormalizer::normalize_tag("ÜBER Größe"), "über-größe");
+        assert_eq!(TagNormalizer::normalize_tag("Σοφία"), "σοφία");
+    }
+
+    #[test]
+    fn test_combining_marks_stay_attached() {
+        // Decomposed "é" (e + U+0301) and Devanagari vowel signs are marks, not letters
+        assert_eq!(
+            TagNormalizer::normalize_tag("Cafe\u{301} Notes"),
+            "cafe\u{301}-notes"
+        );
+        assert_eq!(TagNormalizer::normalize_tag("हिन्दी भाषा"), "हिन्दी-भाषा");
+    }
+
+    #[test]
+    fn test_any_whitespace_becomes_hyphen() {
+        assert_eq!(
+            TagNormalizer::normalize_tag("machine\tlearning"),
+            "machine-learning"
+        );
+        assert_eq!(
+            TagNormalizer::normalize_tag("deep\u{a0}learning\nmodels"),
+            "deep-learning-models"
+        );
+        assert_eq!(
+            TagNormalizer::normalize_tag("東京\u{3000}旅行"),
+            "東京-旅行"
+        );
+    }
+
+    #[test]
+    fn test_punctuation_inside_names_is_dropped() {
+        // Removed punctuation joins the surrounding text rather than splitting it
+        assert_eq!(TagNormalizer::normalize_tag("node.js"), "nodejs");
+        assert_eq!(TagNormalizer::normalize_tag("c++"), "c");
+        assert_eq!(TagNormalizer::normalize_tag("C#"), "c");
+        assert_eq!(
+            TagNormalizer::normalize_tag("c++"),
+            TagNormalizer::normalize_tag("C")
+        );
+        assert_eq!(TagNormalizer::normalize_tag("don't"), "dont");
+    }
+
     #[test]
     fn test_empty_strings_filtered() {
         let tags = vec![
@@ -6,7 +6,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::ollama::{OllamaClientTrait, OllamaError};
+use crate::ollama::{OllamaClientTrait, OllamaError, extract_json_object, generate_json};
 
 use super::normalizer::TagNormalizer;
 
@@ -72,6 +72,8 @@ JSON OUTPUT:"#;
 #[derive(Default)]
 pub struct AutoTaggerBuilder {
     client: Option<Arc<dyn OllamaClientTrait>>,
+    min_confidence: f64,
+    max_tags: Option<usize>,
 }
 
 impl AutoTaggerBuilder {
@@ -90,6 +92,32 @@ impl AutoTaggerBuilder {
         self
     }
 
+    /// Sets the minimum confidence a generated tag needs to be returned.
+    ///
+    /// Tags scoring below the threshold are dropped. Defaults to 0.0 (keep all).
+    /// Values outside 0.0-1.0 are clamped.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_confidence` - Threshold from 0.0 to 1.0
+    pub fn min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Caps the number of generated tags returned.
+    ///
+    /// Only the `max_tags` highest-confidence tags are kept (ties broken by
+    /// tag name). Unbounded by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_tags` - Maximum number of tags to return
+    pub fn max_tags(mut self, max_tags: usize) -> Self {
+        self.max_tags = Some(max_tags);
+        self
+    }
+
     /// Builds the `AutoTagger` with the configured settings.
     ///
     /// # Panics
@@ -115,6 +143,8 @@ impl AutoTaggerBuilder {
     pub fn build(self) -> AutoTagger {
         AutoTagger {
             client: self.client.expect("client must be set via client() method"),
+            min_confidence: self.min_confidence,
+            max_tags: self.max_tags,
         }
     }
 }
@@ -175,6 +205,8 @@ impl AutoTaggerBuilder {
 /// ```
 pub struct AutoTagger {
     client: Arc<dyn OllamaClientTrait>,
+    min_confidence: f64,
+    max_tags: Option<usize>,
 }
 
 impl AutoTagger {
@@ -189,7 +221,11 @@ impl AutoTagger {
     /// Prefer using `AutoTaggerBuilder` for more ergonomic construction.
     #[must_use]
     pub fn new(client: Arc<dyn OllamaClientTrait>) -> Self {
-        Self { client }
+        Self {
+            client,
+            min_confidence: 0.0,
+            max_tags: None,
+        }
     }
 
     /// Generates tags for the given note content using the specified model.
@@ -202,7 +238,11 @@ impl AutoTagger {
     /// # Returns
     ///
     /// Returns a `HashMap` of normalized tag names to confidence scores (0.0-1.0).
-    /// Returns an empty `HashMap` if JSON parsing fails (fail-safe behavior).
+    /// Tags below the configured minimum confidence are omitted, so the map may be
+    /// empty, and at most `max_tags` of the most confident tags are kept. If the
+    /// response contains no valid JSON object, the model is asked once more for
+    /// JSON only; if that also fails, an empty `HashMap` is returned (fail-safe
+    /// behavior).
     ///
     /// # Errors
     ///
@@ -216,45 +256,34 @@ impl AutoTagger {
         // Construct prompt with note content
         let prompt = PROMPT_TEMPLATE.replace("{content}", content);
 
-        // Call LLM
-        let response = self.client.generate(model, &prompt)?;
-
-        // Extract JSON from response (handles various output formats)
-        let Some(json_str) = extract_json(&response) else {
+        // Call LLM and extract JSON (handles fences, prose, trailing commas;
+        // re-prompts once if the response has no usable JSON)
+        let Some(json_str) =
+            generate_json(self.client.as_ref(), model, &prompt, extract_json_object)?
+        else {
             return Ok(HashMap::new()); // Fail-safe: empty on extraction failure
         };
 
-        // Parse and normalize tags
-        Ok(parse_tags(&json_str))
+        // Parse and normalize tags, dropping those below the confidence threshold
+        let mut tags = parse_tags(&json_str);
+        tags.retain(|_, confidence| *confidence >= self.min_confidence);
+
+        if let Some(max_tags) = self.max_tags {
+            tags = top_tags(tags, max_tags);
+        }
+
+        Ok(tags)
     }
 }
 
-/// Extracts JSON from model response, handling various output formats.
-///
-/// Handles:
-/// - Clean JSON response (no wrapping)
-/// - Markdown code block wrapping (```json ... ```)
-/// - Explanatory text before/after JSON
-///
-/// # Arguments
-///
-/// * `response` - The raw model response text
-///
-/// # Returns
-///
-/// Returns `Some(String)` containing the extracted JSON, or `None` if no JSON found.
-fn extract_json(response: &str) -> Option<String> {
-    let trimmed = response.trim();
-
-    // Try to find JSON object boundaries
-    let start = trimmed.find('{')?;
-    let end = trimmed.rfind('}')?;
-
-    if start <= end {
-        Some(trimmed[start..=end].to_string())
-    } else {
-        None
-    }
+/// Keeps the `limit` highest-confidence tags, breaking ties by tag name.
+fn top_tags(tags: HashMap<String, f64>, limit: usize) -> HashMap<String, f64> {
+    let mut sorted: Vec<(String, f64)> = tags.into_iter().collect();
+    sorted.sort_by(|(a_name, a_conf), (b_name, b_conf)| {
+        b_conf.total_cmp(a_conf).then_with(|| a_name.cmp(b_name))
+    });
+    sorted.truncate(limit);
+    sorted.into_iter().collect()
 }
 
 /// Parses JSON string into a `HashMap` of normalized tags to confidence scores.
@@ -349,7 +378,7 @@ mod tests {
         let response = r#"```json
 {"rust": 0.9, "async": 0.75}
 ```"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_object(response);
 
         assert!(extracted.is_some());
         let json = extracted.unwrap();
@@ -367,7 +396,7 @@ mod tests {
 {"rust": 0.9, "async": 0.75, "tokio": 0.8}
 
 I hope this helps!"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_object(response);
 
         assert!(extracted.is_some());
         let json = extracted.unwrap();
@@ -388,7 +417,7 @@ I hope this helps!"#;
 
         // Test with extraction failure
         let no_json = "No curly braces here";
-        let extracted = extract_json(no_json);
+        let extracted = extract_json_object(no_json);
         assert!(extracted.is_none());
     }
 
@@ -629,10 +658,68 @@ I focused on the main topics discussed."#
         }
     }
 
+    #[test]
+    fn test_min_confidence_drops_low_confidence_tags() {
+        let mock = MockOllamaClient {
+            response: r#"{"rust": 0.9, "async": 0.5, "misc": 0.2}"#.to_string(),
+        };
+        let tagger = AutoTaggerBuilder::new()
+            .client(Arc::new(mock))
+            .min_confidence(0.5)
+            .build();
+
+        let tags = tagger.generate_tags("test-model", "test content").unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains_key("rust"));
+        assert!(tags.contains_key("async"), "threshold is inclusive");
+        assert!(!tags.contains_key("misc"));
+    }
+
+    #[test]
+    fn test_min_confidence_can_drop_every_tag() {
+        let mock = MockOllamaClient {
+            response: r#"{"rust": 0.3, "async": 0.2}"#.to_string(),
+        };
+        let tagger = AutoTaggerBuilder::new()
+            .client(Arc::new(mock))
+            .min_confidence(0.8)
+            .build();
+
+        let tags = tagger.generate_tags("test-model", "test content").unwrap();
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn test_max_tags_keeps_most_confident_tags() {
+        let mock = MockOllamaClient {
+            response: r#"{"rust": 0.9, "async": 0.8, "tokio": 0.7, "misc": 0.4, "notes": 0.3}"#
+                .to_string(),
+        };
+        let tagger = AutoTaggerBuilder::new()
+            .client(Arc::new(mock))
+            .max_tags(3)
+            .build();
+
+        let tags = tagger.generate_tags("test-model", "test content").unwrap();
+        assert_eq!(tags.len(), 3);
+        assert!(tags.contains_key("rust"));
+        assert!(tags.contains_key("async"));
+        assert!(tags.contains_key("tokio"));
+    }
+
+    #[test]
+    fn test_top_tags_breaks_ties_by_name() {
+        let tags = parse_tags(r#"{"zebra": 0.8, "alpha": 0.8, "beta": 0.8}"#);
+        let top = top_tags(tags, 2);
+        assert_eq!(top.len(), 2);
+        assert!(top.contains_key("alpha"));
+        assert!(top.contains_key("beta"));
+    }
+
     #[test]
     fn test_extract_json_handles_nested_objects() {
         let response = r#"{"outer": {"inner": 0.5}, "tag": 0.9}"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_object(response);
 
         assert!(extracted.is_some());
         // Should extract the outermost braces
@@ -649,4 +736,18 @@ I focused on the main topics discussed."#
         assert_eq!(tags.len(), 1);
         assert_eq!(tags.get("valid"), Some(&0.7));
     }
+
+    #[test]
+    fn test_generate_tags_parses_fenced_output_with_trailing_comma() {
+        let mock = MockOllamaClient {
+            response: "```json\n{\"rust\": 0.9, \"async\": 0.75,}\n```".to_string(),
+        };
+        let tagger = AutoTagger::new(Arc::new(mock));
+
+        let tags = tagger
+            .generate_tags("test-model", "test content")
+            .expect("tagging should succeed");
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.get("rust"), Some(&0.9));
+    }
 }
@@ -0,0 +1,564 @@
+//! Resolved runtime configuration.
+//!
+//! Every setting cons reads from the environment is parsed here, so defaults
+//! and validation live in one place. [`Config::load`] reads the process
+//! environment; the per-feature configs such as
+//! [`SpreadingActivationConfig`](crate::spreading_activation::SpreadingActivationConfig)
+//! are built from the result. `cons config` prints [`Config::entries`].
+
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::db::FtsTokenizer;
+use crate::service::InvalidInput;
+use crate::spreading_activation::{MAX_MAX_HOPS, MIN_MAX_HOPS};
+
+/// Where a setting's resolved value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSource {
+    /// The built-in default; the variable is unset or could not be parsed.
+    Default,
+    /// A value from the environment variable; only valid ones count, except
+    /// for `CONS_FTS_TOKENIZER`, which keeps an invalid value to report it.
+    Env,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Env => write!(f, "env"),
+        }
+    }
+}
+
+/// One recognized setting as shown by `cons config`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigEntry {
+    /// Environment variable name, e.g. `CONS_DECAY`.
+    pub name: &'static str,
+    /// Resolved value, formatted for display.
+    pub value: String,
+    /// Whether the value came from the environment or the default.
+    pub source: ConfigSource,
+}
+
+/// All settings cons reads from the environment, with defaults applied.
+///
+/// Unparseable values fall back to the default rather than failing, and
+/// out-of-range values are clamped: `CONS_DECAY` to 0-1, weights to at least 0.
+/// The one exception is `CONS_FTS_TOKENIZER`: falling back would silently
+/// re-index every note, so [`Config::fts_tokenizer`] reports the bad value.
+/// Load it once and pass it along; `NoteService` keeps its own copy so search
+/// does not re-read the environment on every call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// `CONS_DB_PATH`: database file, `None` for the platform default.
+    pub db_path: Option<PathBuf>,
+    /// `OLLAMA_HOST` (default `http://localhost:11434`).
+    pub ollama_host: String,
+    /// `OLLAMA_MODEL`: `None` means use the first installed model.
+    pub ollama_model: Option<String>,
+    /// `OLLAMA_TIMEOUT_SECS` (default 30).
+    pub ollama_timeout_secs: u64,
+    /// `OLLAMA_MAX_RETRIES` (default 1).
+    pub ollama_max_retries: u32,
+    /// `CONS_NO_AI`: skip enhancement and auto-tagging on `cons add`.
+    pub no_ai: bool,
+    /// `CONS_DEDUPE`: reuse an existing note with identical content on `cons add`.
+    pub dedupe: bool,
+    /// `CONS_MIN_TAG_CONFIDENCE` (default 0.0, keep every generated tag).
+    pub min_tag_confidence: f64,
+    /// `CONS_MAX_TAGS`: per-note auto-tag cap, `None` for unbounded.
+    pub max_tags: Option<usize>,
+    /// `CONS_FTS_TOKENIZER`: search index tokenizer (default `porter`), or the
+    /// unrecognized value as given.
+    fts_tokenizer: Result<FtsTokenizer, String>,
+    /// `CONS_DECAY`: activation decay per hop (default 0.7, clamped to 0-1).
+    pub decay: f64,
+    /// `CONS_THRESHOLD`: minimum activation to keep spreading (default 0.1).
+    pub threshold: f64,
+    /// `CONS_MAX_HOPS` (default 3, clamped to 1-6).
+    pub max_hops: usize,
+    /// `CONS_GENERIC_WEIGHT` (default 1.0, negative values clamp to 0).
+    pub generic_weight: f64,
+    /// `CONS_PARTITIVE_WEIGHT` (default 0.5, negative values clamp to 0).
+    pub partitive_weight: f64,
+    /// `CONS_VERIFIED_MULTIPLIER` (default 1.0, negative values clamp to 0).
+    pub verified_multiplier: f64,
+    /// `CONS_FTS_WEIGHT` (default 1.0, negative values clamp to 0).
+    pub fts_weight: f64,
+    /// `CONS_GRAPH_WEIGHT` (default 1.0, negative values clamp to 0).
+    pub graph_weight: f64,
+    /// `CONS_INTERSECTION_BONUS` (default 0.5).
+    pub intersection_bonus: f64,
+    /// `CONS_MIN_AVG_ACTIVATION` (default 0.1).
+    pub min_avg_activation: f64,
+    /// `CONS_MIN_ACTIVATED_TAGS` (default 2).
+    pub min_activated_tags: usize,
+    /// `CONS_EXPANSION_DEPTH` (default 1).
+    pub expansion_depth: usize,
+    /// `CONS_MAX_EXPANSION_TERMS` (default 10).
+    pub max_expansion_terms: usize,
+    /// `CONS_BROADER_MIN_CONFIDENCE` (default 0.7).
+    pub broader_min_confidence: f64,
+    /// Names of the variables that supplied a valid value.
+    from_env: BTreeSet<&'static str>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            db_path: None,
+            ollama_host: "http://localhost:11434".to_string(),
+            ollama_model: None,
+            ollama_timeout_secs: 30,
+            ollama_max_retries: 1,
+            no_ai: false,
+            dedupe: false,
+            min_tag_confidence: 0.0,
+            max_tags: None,
+            fts_tokenizer: Ok(FtsTokenizer::Porter),
+            decay: 0.7,
+            threshold: 0.1,
+            max_hops: 3,
+            generic_weight: 1.0,
+            partitive_weight: 0.5,
+            verified_multiplier: 1.0,
+            fts_weight: 1.0,
+            graph_weight: 1.0,
+            intersection_bonus: 0.5,
+            min_avg_activation: 0.1,
+            min_activated_tags: 2,
+            expansion_depth: 1,
+            max_expansion_terms: 10,
+            broader_min_confidence: 0.7,
+            from_env: BTreeSet::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads every setting from the process environment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::config::Config;
+    ///
+    /// let config = Config::load();
+    /// assert!(config.max_hops >= 1 && config.max_hops <= 6);
+    /// ```
+    pub fn load() -> Self {
+        Self::from_lookup(|name| std::env::var_os(name))
+    }
+
+    /// Reads every setting through `lookup`, which maps a variable name to its value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::config::{Config, ConfigSource};
+    ///
+    /// let config = Config::from_lookup(|name| (name == "CONS_DECAY").then(|| "0.5".into()));
+    /// assert_eq!(config.decay, 0.5);
+    /// assert_eq!(config.source("CONS_DECAY"), ConfigSource::Env);
+    /// assert_eq!(config.source("CONS_THRESHOLD"), ConfigSource::Default);
+    /// ```
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<OsString>) -> Self {
+        let defaults = Self::default();
+        let mut env = EnvReader {
+            lookup,
+            from_env: BTreeSet::new(),
+        };
+
+        let db_path = env.path("CONS_DB_PATH");
+        let ollama_host = env.string("OLLAMA_HOST").unwrap_or(defaults.ollama_host);
+        let ollama_model = env.non_empty("OLLAMA_MODEL");
+
+        Self {
+            db_path,
+            ollama_host,
+            ollama_model,
+            ollama_timeout_secs: env
+                .parse("OLLAMA_TIMEOUT_SECS", |_| true)
+                .unwrap_or(defaults.ollama_timeout_secs),
+            ollama_max_retries: env
+                .parse("OLLAMA_MAX_RETRIES", |_| true)
+                .unwrap_or(defaults.ollama_max_retries),
+            no_ai: env.flag("CONS_NO_AI"),
+            dedupe: env.flag("CONS_DEDUPE"),
+            min_tag_confidence: env
+                .parse("CONS_MIN_TAG_CONFIDENCE", |_| true)
+                .unwrap_or(defaults.min_tag_confidence),
+            max_tags: env.parse("CONS_MAX_TAGS", |_| true),
+            fts_tokenizer: env
+                .parse_strict("CONS_FTS_TOKENIZER")
+                .unwrap_or(defaults.fts_tokenizer),
+            decay: env
+                .parse("CONS_DECAY", is_finite)
+                .map(|decay: f64| decay.clamp(0.0, 1.0))
+                .unwrap_or(defaults.decay),
+            threshold: env
+                .parse("CONS_THRESHOLD", |_| true)
+                .unwrap_or(defaults.threshold),
+            max_hops: env
+                .parse("CONS_MAX_HOPS", |_| true)
+                .map(|hops: usize| hops.clamp(MIN_MAX_HOPS, MAX_MAX_HOPS))
+                .unwrap_or(defaults.max_hops),
+            generic_weight: env
+                .parse("CONS_GENERIC_WEIGHT", is_finite)
+                .map(non_negative)
+                .unwrap_or(defaults.generic_weight),
+            partitive_weight: env
+                .parse("CONS_PARTITIVE_WEIGHT", is_finite)
+                .map(non_negative)
+                .unwrap_or(defaults.partitive_weight),
+            verified_multiplier: env
+                .parse("CONS_VERIFIED_MULTIPLIER", is_finite)
+                .map(non_negative)
+                .unwrap_or(defaults.verified_multiplier),
+            fts_weight: env
+                .parse("CONS_FTS_WEIGHT", is_finite)
+                .map(non_negative)
+                .unwrap_or(defaults.fts_weight),
+            graph_weight: env
+                .parse("CONS_GRAPH_WEIGHT", is_finite)
+                .map(non_negative)
+                .unwrap_or(defaults.graph_weight),
+            intersection_bonus: env
+                .parse("CONS_INTERSECTION_BONUS", |_| true)
+                .unwrap_or(defaults.intersection_bonus),
+            min_avg_activation: env
+                .parse("CONS_MIN_AVG_ACTIVATION", |_| true)
+                .unwrap_or(defaults.min_avg_activation),
+            min_activated_tags: env
+                .parse("CONS_MIN_ACTIVATED_TAGS", |_| true)
+                .unwrap_or(defaults.min_activated_tags),
+            expansion_depth: env
+                .parse("CONS_EXPANSION_DEPTH", |_| true)
+                .unwrap_or(defaults.expansion_depth),
+            max_expansion_terms: env
+                .parse("CONS_MAX_EXPANSION_TERMS", |_| true)
+                .unwrap_or(defaults.max_expansion_terms),
+            broader_min_confidence: env
+                .parse("CONS_BROADER_MIN_CONFIDENCE", |_| true)
+                .unwrap_or(defaults.broader_min_confidence),
+            from_env: env.from_env,
+        }
+    }
+
+    /// Returns the `CONS_FTS_TOKENIZER` search index tokenizer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidInput`] error if the variable is set to an unknown
+    /// tokenizer name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::config::Config;
+    /// use cons::db::FtsTokenizer;
+    ///
+    /// let config = Config::from_lookup(|_| Some("trigram".into()));
+    /// assert_eq!(config.fts_tokenizer()?, FtsTokenizer::Trigram);
+    ///
+    /// let config = Config::from_lookup(|_| Some("snowball".into()));
+    /// assert!(config.fts_tokenizer().is_err());
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn fts_tokenizer(&self) -> anyhow::Result<FtsTokenizer> {
+        match &self.fts_tokenizer {
+            Ok(tokenizer) => Ok(*tokenizer),
+            Err(raw) => raw.trim().parse().map_err(|e: anyhow::Error| {
+                InvalidInput(format!("Invalid CONS_FTS_TOKENIZER: {e}")).into()
+            }),
+        }
+    }
+
+    /// Returns where the value of the variable `name` came from.
+    pub fn source(&self, name: &str) -> ConfigSource {
+        if self.from_env.contains(name) {
+            ConfigSource::Env
+        } else {
+            ConfigSource::Default
+        }
+    }
+
+    /// Lists every recognized setting with its resolved value and source.
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        let optional = |value: Option<String>, unset: &str| value.unwrap_or_else(|| unset.into());
+        let settings = [
+            (
+                "CONS_DB_PATH",
+                optional(
+                    self.db_path.as_ref().map(|p| p.display().to_string()),
+                    "(default location)",
+                ),
+            ),
+            ("OLLAMA_HOST", self.ollama_host.clone()),
+            (
+                "OLLAMA_MODEL",
+                optional(self.ollama_model.clone(), "(first installed model)"),
+            ),
+            ("OLLAMA_TIMEOUT_SECS", self.ollama_timeout_secs.to_string()),
+            ("OLLAMA_MAX_RETRIES", self.ollama_max_retries.to_string()),
+            ("CONS_NO_AI", self.no_ai.to_string()),
+            ("CONS_DEDUPE", self.dedupe.to_string()),
+            (
+                "CONS_MIN_TAG_CONFIDENCE",
+                self.min_tag_confidence.to_string(),
+            ),
+            (
+                "CONS_MAX_TAGS",
+                optional(self.max_tags.map(|n| n.to_string()), "(unlimited)"),
+            ),
+            (
+                "CONS_FTS_TOKENIZER",
+                match &self.fts_tokenizer {
+                    Ok(tokenizer) => tokenizer.to_string(),
+                    Err(raw) => format!("{raw} (invalid)"),
+                },
+            ),
+            ("CONS_DECAY", self.decay.to_string()),
+            ("CONS_THRESHOLD", self.threshold.to_string()),
+            ("CONS_MAX_HOPS", self.max_hops.to_string()),
+            ("CONS_GENERIC_WEIGHT", self.generic_weight.to_string()),
+            ("CONS_PARTITIVE_WEIGHT", self.partitive_weight.to_string()),
+            (
+                "CONS_VERIFIED_MULTIPLIER",
+                self.verified_multiplier.to_string(),
+            ),
+            ("CONS_FTS_WEIGHT", self.fts_weight.to_string()),
+            ("CONS_GRAPH_WEIGHT", self.graph_weight.to_string()),
+            (
+                "CONS_INTERSECTION_BONUS",
+                self.intersection_bonus.to_string(),
+            ),
+            (
+                "CONS_MIN_AVG_ACTIVATION",
+                self.min_avg_activation.to_string(),
+            ),
+            (
+                "CONS_MIN_ACTIVATED_TAGS",
+                self.min_activated_tags.to_string(),
+            ),
+            ("CONS_EXPANSION_DEPTH", self.expansion_depth.to_string()),
+            (
+                "CONS_MAX_EXPANSION_TERMS",
+                self.max_expansion_terms.to_string(),
+            ),
+            (
+                "CONS_BROADER_MIN_CONFIDENCE",
+                self.broader_min_confidence.to_string(),
+            ),
+        ];
+
+        settings
+            .into_iter()
+            .map(|(name, value)| ConfigEntry {
+                name,
+                value,
+                source: self.source(name),
+            })
+            .collect()
+    }
+}
+
+/// Reads variables through a lookup function, recording which ones were used.
+struct EnvReader<F> {
+    lookup: F,
+    from_env: BTreeSet<&'static str>,
+}
+
+impl<F: Fn(&str) -> Option<OsString>> EnvReader<F> {
+    /// Returns the variable as a string, or `None` if unset or not UTF-8.
+    fn string(&mut self, name: &'static str) -> Option<String> {
+        let value = (self.lookup)(name)?.into_string().ok()?;
+        self.from_env.insert(name);
+        Some(value)
+    }
+
+    /// Like [`string`](Self::string), but an empty value counts as unset.
+    fn non_empty(&mut self, name: &'static str) -> Option<String> {
+        let value = (self.lookup)(name)?.into_string().ok()?;
+        if value.is_empty() {
+            return None;
+        }
+        self.from_env.insert(name);
+        Some(value)
+    }
+
+    /// Returns the variable as a path; an empty value counts as unset.
+    fn path(&mut self, name: &'static str) -> Option<PathBuf> {
+        let value = (self.lookup)(name).filter(|v| !v.is_empty())?;
+        self.from_env.insert(name);
+        Some(PathBuf::from(value))
+    }
+
+    /// Parses the trimmed variable, keeping it only if `accept` approves.
+    fn parse<T: FromStr>(&mut self, name: &'static str, accept: impl Fn(&T) -> bool) -> Option<T> {
+        let raw = (self.lookup)(name)?.into_string().ok()?;
+        let value = raw.trim().parse::<T>().ok().filter(|v| accept(v))?;
+        self.from_env.insert(name);
+        Some(value)
+    }
+
+    /// Like [`parse`](Self::parse), but keeps a value that does not parse as
+    /// `Err(raw)` instead of dropping it, so the caller can reject it.
+    fn parse_strict<T: FromStr>(&mut self, name: &'static str) -> Option<Result<T, String>> {
+        let raw = (self.lookup)(name)?.into_string().ok()?;
+        self.from_env.insert(name);
+        Some(raw.trim().parse::<T>().map_err(|_| raw))
+    }
+
+    /// Reads a boolean flag; any set value counts as coming from the environment.
+    fn flag(&mut self, name: &'static str) -> bool {
+        self.string(name).is_some_and(|v| is_truthy(&v))
+    }
+}
+
+/// Parses an environment flag value, case-insensitively.
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes"
+    )
+}
+
+/// Rejects `NaN` and infinities, which would poison every score they touch.
+fn is_finite(value: &f64) -> bool {
+    value.is_finite()
+}
+
+/// Clamps a weight to zero so it can disable, but never invert, a signal.
+fn non_negative(weight: f64) -> f64 {
+    weight.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(vars: &[(&str, &str)]) -> Config {
+        Config::from_lookup(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        })
+    }
+
+    #[test]
+    fn is_truthy_accepts_common_flag_values() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy("true"));
+        assert!(is_truthy("YES"));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy("false"));
+        assert!(!is_truthy(""));
+    }
+
+    #[test]
+    fn empty_environment_matches_defaults() {
+        let config = config_with(&[]);
+        assert_eq!(config, Config::default());
+        assert!(
+            config
+                .entries()
+                .iter()
+                .all(|entry| entry.source == ConfigSource::Default)
+        );
+    }
+
+    #[test]
+    fn invalid_values_fall_back_to_defaults() {
+        let config = config_with(&[
+            ("CONS_DECAY", "fast"),
+            ("CONS_PARTITIVE_WEIGHT", "NaN"),
+            ("OLLAMA_MODEL", ""),
+            ("CONS_DB_PATH", ""),
+        ]);
+
+        assert_eq!(config.decay, 0.7);
+        assert_eq!(config.partitive_weight, 0.5);
+        assert_eq!(config.ollama_model, None);
+        assert_eq!(config.db_path, None);
+        for name in [
+            "CONS_DECAY",
+            "CONS_PARTITIVE_WEIGHT",
+            "OLLAMA_MODEL",
+            "CONS_DB_PATH",
+        ] {
+            assert_eq!(config.source(name), ConfigSource::Default, "{name}");
+        }
+    }
+
+    #[test]
+    fn entries_report_env_values_and_sources() {
+        let config = config_with(&[
+            ("CONS_MAX_HOPS", "10"),
+            ("OLLAMA_MODEL", "gemma3:4b"),
+            ("CONS_NO_AI", "yes"),
+        ]);
+
+        assert_eq!(config.max_hops, MAX_MAX_HOPS);
+        let entries = config.entries();
+        let entry = |name: &str| entries.iter().find(|e| e.name == name).unwrap();
+        assert_eq!(entry("CONS_MAX_HOPS").value, "6");
+        assert_eq!(entry("CONS_MAX_HOPS").source, ConfigSource::Env);
+        assert_eq!(entry("OLLAMA_MODEL").value, "gemma3:4b");
+        assert_eq!(entry("CONS_NO_AI").value, "true");
+        assert_eq!(entry("CONS_DECAY").value, "0.7");
+        assert_eq!(entry("CONS_DECAY").source, ConfigSource::Default);
+    }
+
+    #[test]
+    fn fts_tokenizer_parses_known_names() {
+        let config = config_with(&[("CONS_FTS_TOKENIZER", " Unicode61 ")]);
+        assert_eq!(config.fts_tokenizer().unwrap(), FtsTokenizer::Unicode61);
+        assert_eq!(config.source("CONS_FTS_TOKENIZER"), ConfigSource::Env);
+
+        let config = config_with(&[]);
+        assert_eq!(config.fts_tokenizer().unwrap(), FtsTokenizer::Porter);
+    }
+
+    #[test]
+    fn unknown_fts_tokenizer_is_an_error() {
+        let config = config_with(&[("CONS_FTS_TOKENIZER", "snowball")]);
+
+        let err = config.fts_tokenizer().unwrap_err();
+        assert!(format!("{err:#}").contains("Unknown FTS tokenizer 'snowball'"));
+        let entries = config.entries();
+        let entry = entries
+            .iter()
+            .find(|e| e.name == "CONS_FTS_TOKENIZER")
+            .unwrap();
+        assert_eq!(entry.value, "snowball (invalid)");
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        let config = config_with(&[
+            ("CONS_DECAY", "1.5"),
+            ("CONS_THRESHOLD", "0.05"),
+            ("CONS_GENERIC_WEIGHT", "-2"),
+            ("CONS_FTS_WEIGHT", "-0.5"),
+        ]);
+
+        assert_eq!(config.decay, 1.0);
+        assert_eq!(config.threshold, 0.05);
+        assert_eq!(config.generic_weight, 0.0);
+        assert_eq!(config.fts_weight, 0.0);
+        assert_eq!(config.source("CONS_GENERIC_WEIGHT"), ConfigSource::Env);
+
+        let config = config_with(&[("CONS_DECAY", "-0.3")]);
+        assert_eq!(config.decay, 0.0);
+    }
+}
@@ -1,25 +1,123 @@
 mod migration;
 mod schema;
 
+use std::fmt;
 use std::path::Path;
+use std::str::FromStr;
 
-use anyhow::Result;
-use rusqlite::Connection;
+use anyhow::{Context, Result};
+use rusqlite::{Connection, DatabaseName, OpenFlags, OptionalExtension};
 
-use schema::{FTS_TABLE_CREATION, FTS_TRIGGERS, apply_pending_migrations};
+use schema::{FTS_TRIGGERS, apply_pending_migrations, current_version, fts_table_creation};
+
+/// Tokenizer for the `notes_fts` full-text index, set with `CONS_FTS_TOKENIZER`.
+///
+/// The tokenizer decides what a search term matches:
+/// - `porter` (default) stems English words, so `program` also finds
+///   `programming`; this can over-match acronyms and code identifiers
+/// - `unicode61` matches whole words only, with no stemming
+/// - `trigram` matches any substring of three or more characters
+///
+/// Changing it re-creates and re-indexes the FTS table the next time the
+/// database is opened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FtsTokenizer {
+    /// Porter stemming over the unicode61 tokenizer.
+    #[default]
+    Porter,
+    /// Unicode-aware word tokenizer without stemming.
+    Unicode61,
+    /// Substring matching on three-character sequences.
+    Trigram,
+}
+
+impl FtsTokenizer {
+    /// Returns the FTS5 tokenizer name, e.g. `porter`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FtsTokenizer::Porter => "porter",
+            FtsTokenizer::Unicode61 => "unicode61",
+            FtsTokenizer::Trigram => "trigram",
+        }
+    }
+
+    /// Returns the `tokenize` option as written in the FTS table definition.
+    fn tokenize_clause(self) -> String {
+        format!("tokenize='{}'", self.as_str())
+    }
+}
+
+impl fmt::Display for FtsTokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FtsTokenizer {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "porter" => Ok(FtsTokenizer::Porter),
+            "unicode61" => Ok(FtsTokenizer::Unicode61),
+            "trigram" => Ok(FtsTokenizer::Trigram),
+            other => anyhow::bail!(
+                "Unknown FTS tokenizer '{}' (expected porter, unicode61 or trigram)",
+                other
+            ),
+        }
+    }
+}
+
+/// Why the search index had to be rebuilt while opening the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FtsRebuild {
+    /// The index could not be read, e.g. a shadow table was lost or damaged.
+    Unreadable,
+    /// The index was built with a different tokenizer than the one requested.
+    TokenizerChanged(FtsTokenizer),
+}
+
+impl fmt::Display for FtsRebuild {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FtsRebuild::Unreadable => {
+                f.write_str("Search index was unreadable and has been rebuilt")
+            }
+            FtsRebuild::TokenizerChanged(tokenizer) => {
+                write!(
+                    f,
+                    "Search index has been rebuilt for the {tokenizer} tokenizer"
+                )
+            }
+        }
+    }
+}
 
 /// Database wrapper providing connection management and schema initialization.
 pub struct Database {
     conn: Connection,
+    fts_tokenizer: FtsTokenizer,
+    fts_rebuild: Option<FtsRebuild>,
 }
 
 impl Database {
     /// Opens an in-memory SQLite database.
     ///
-    /// Automatically initializes the schema on connection open.
+    /// Automatically initializes the schema on connection open, using the
+    /// default [`FtsTokenizer`].
     pub fn in_memory() -> Result<Self> {
+        Self::in_memory_with_tokenizer(FtsTokenizer::default())
+    }
+
+    /// Opens an in-memory SQLite database whose search index uses `fts_tokenizer`.
+    pub fn in_memory_with_tokenizer(fts_tokenizer: FtsTokenizer) -> Result<Self> {
         let conn = Connection::open_in_memory()?;
-        let mut db = Self { conn };
+        let mut db = Self {
+            conn,
+            fts_tokenizer,
+            fts_rebuild: None,
+        };
         db.initialize_schema()?;
         Ok(db)
     }
@@ -27,19 +125,108 @@ impl Database {
     /// Opens a file-based SQLite database at the given path.
     ///
     /// Creates the database file if it does not exist.
-    /// Automatically initializes the schema on connection open.
+    /// Automatically initializes the schema on connection open, using the
+    /// default [`FtsTokenizer`]. An index built with another tokenizer is
+    /// rebuilt, so callers honouring `CONS_FTS_TOKENIZER` should use
+    /// [`open_with_tokenizer`](Self::open_with_tokenizer).
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with_tokenizer(path, FtsTokenizer::default())
+    }
+
+    /// Opens a file-based SQLite database whose search index uses `fts_tokenizer`.
+    ///
+    /// If the existing index was built with a different tokenizer, it is
+    /// re-created and re-indexed with [`rebuild_fts`](Self::rebuild_fts).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::db::{Database, FtsTokenizer};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let dir = tempfile::tempdir()?;
+    /// let path = dir.path().join("notes.db");
+    ///
+    /// Database::open_with_tokenizer(&path, FtsTokenizer::Porter)?;
+    /// let db = Database::open_with_tokenizer(&path, FtsTokenizer::Unicode61)?;
+    /// assert_eq!(db.fts_tokenizer(), FtsTokenizer::Unicode61);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_with_tokenizer(
+        path: impl AsRef<Path>,
+        fts_tokenizer: FtsTokenizer,
+    ) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let mut db = Self { conn };
+        let mut db = Self {
+            conn,
+            fts_tokenizer,
+            fts_rebuild: None,
+        };
         db.initialize_schema()?;
         Ok(db)
     }
 
+    /// Opens an existing database file read-only, without initializing it.
+    ///
+    /// No migrations, backfills, or search index checks run, so the file is
+    /// left exactly as it was; use this to look things up without side effects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist, or if its schema is older
+    /// than this build expects (any other command upgrades it).
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        let latest = migration::MIGRATIONS.last().map_or(0, |m| m.version);
+        let version = current_version(&conn).unwrap_or(0);
+        if version < latest {
+            anyhow::bail!("Database schema is out of date (version {version}, expected {latest})");
+        }
+
+        // Report the tokenizer the existing index was actually built with
+        let fts_sql: Option<String> = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type='table' AND name='notes_fts'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let fts_tokenizer = [FtsTokenizer::Unicode61, FtsTokenizer::Trigram]
+            .into_iter()
+            .find(|t| {
+                fts_sql
+                    .as_deref()
+                    .is_some_and(|sql| sql.contains(&t.tokenize_clause()))
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            conn,
+            fts_tokenizer,
+            fts_rebuild: None,
+        })
+    }
+
+    /// Returns the tokenizer used by the full-text search index.
+    pub fn fts_tokenizer(&self) -> FtsTokenizer {
+        self.fts_tokenizer
+    }
+
+    /// Returns why the search index was rebuilt while opening, if it was.
+    ///
+    /// Opening never prints anything itself; callers decide whether to tell
+    /// the user.
+    pub fn fts_rebuild(&self) -> Option<FtsRebuild> {
+        self.fts_rebuild
+    }
+
     /// Initializes the database schema.
     ///
     /// Applies all pending migrations in version order.
     /// Creates FTS5 virtual table and triggers if they don't exist.
-    /// Populates FTS index from existing notes.
+    /// Populates a newly created FTS index from existing notes.
     /// Migrations are additive-only and support old database versions.
     fn initialize_schema(&mut self) -> Result<()> {
         self.conn.execute("PRAGMA foreign_keys = ON", [])?;
@@ -58,42 +245,136 @@ impl Database {
         )?;
 
         // Initialize FTS5 virtual table and triggers
+        // Runs before the backfills below that update notes, since those fire the FTS triggers
         self.initialize_fts()?;
 
+        self.backfill_content_hashes()?;
+
+        Ok(())
+    }
+
+    /// Fills in `content_hash` for notes that do not have one yet.
+    ///
+    /// Covers notes written before migration 007 (or by an older build).
+    /// Only rows with a NULL hash are touched, so this is cheap to run on
+    /// every database open.
+    fn backfill_content_hashes(&self) -> Result<()> {
+        let missing: Vec<(i64, String)> = self
+            .conn
+            .prepare("SELECT id, content FROM notes WHERE content_hash IS NULL")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut stmt = self
+            .conn
+            .prepare("UPDATE notes SET content_hash = ?1 WHERE id = ?2")?;
+        for (id, content) in missing {
+            stmt.execute(rusqlite::params![crate::content_hash(&content), id])?;
+        }
+
         Ok(())
     }
 
     /// Initializes FTS5 virtual table, triggers, and populates the index.
     ///
     /// FTS5 does NOT support IF NOT EXISTS, so we check sqlite_master first.
-    /// After creating the table and triggers, we populate the index from existing notes.
-    fn initialize_fts(&self) -> Result<()> {
-        // Check if FTS table already exists
-        let fts_exists: bool = self.conn.query_row(
-            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='notes_fts')",
-            [],
-            |row| row.get(0),
-        )?;
+    /// A newly created table is populated from existing notes; an existing one
+    /// is only checked for readability, so drift stays visible to `cons doctor`.
+    /// A table built with a different tokenizer is rebuilt with the configured one.
+    fn initialize_fts(&mut self) -> Result<()> {
+        // Check if FTS table already exists, and how it was defined
+        let fts_sql: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type='table' AND name='notes_fts'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
 
-        if !fts_exists {
-            // Create FTS virtual table
-            self.conn.execute_batch(FTS_TABLE_CREATION)?;
+        let created = fts_sql.is_none();
+        match fts_sql {
+            None => {
+                // Create FTS virtual table
+                self.conn
+                    .execute_batch(&fts_table_creation(self.fts_tokenizer))?;
+            }
+            Some(sql) if !sql.contains(&self.fts_tokenizer.tokenize_clause()) => {
+                // Switching tokenizers means re-creating the table and re-indexing
+                self.rebuild_fts().with_context(|| {
+                    format!(
+                        "Failed to rebuild search index for the {} tokenizer",
+                        self.fts_tokenizer
+                    )
+                })?;
+                self.fts_rebuild = Some(FtsRebuild::TokenizerChanged(self.fts_tokenizer));
+                return Ok(());
+            }
+            Some(_) => {}
         }
 
         // Create triggers (idempotent with IF NOT EXISTS)
         self.conn.execute_batch(FTS_TRIGGERS)?;
 
-        // Populate/rebuild FTS index from existing notes
-        // This handles both fresh creation and existing databases where FTS might be stale
-        self.populate_fts_index()?;
+        // Index existing notes into a new table. An existing table is kept as is: the
+        // triggers maintain it, and repopulating here would hide drift from `cons doctor`.
+        // A corrupt index would otherwise make the database impossible to open, and the
+        // index only holds derived data, so recreate it from scratch instead
+        let ready = if created {
+            self.populate_fts_index()
+        } else {
+            self.conn
+                .query_row("SELECT COUNT(*) FROM notes_fts", [], |_| Ok(()))
+                .map_err(anyhow::Error::from)
+        };
+        match ready {
+            Ok(()) => {}
+            Err(e) if is_unreadable_index(&e) => {
+                self.rebuild_fts()
+                    .context("Search index is corrupt and could not be rebuilt")?;
+                self.fts_rebuild = Some(FtsRebuild::Unreadable);
+            }
+            Err(e) => return Err(e.context("Failed to populate search index")),
+        }
 
         Ok(())
     }
 
+    /// Drops and recreates the FTS5 index, repopulating it from the notes table.
+    ///
+    /// Recovers from a missing or corrupt `notes_fts` table, and applies the
+    /// configured [`FtsTokenizer`]. Runs in a single transaction and returns
+    /// the number of notes indexed.
+    pub fn rebuild_fts(&self) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        if tx.execute_batch("DROP TABLE IF EXISTS notes_fts").is_err() {
+            // A virtual table whose storage is damaged cannot be loaded, so it cannot
+            // be dropped normally: remove its schema entry, then its shadow tables
+            tx.execute_batch(
+                "PRAGMA writable_schema = ON;
+                 DELETE FROM sqlite_master WHERE type = 'table' AND name = 'notes_fts';
+                 PRAGMA writable_schema = RESET;
+                 DROP TABLE IF EXISTS notes_fts_data;
+                 DROP TABLE IF EXISTS notes_fts_idx;
+                 DROP TABLE IF EXISTS notes_fts_content;
+                 DROP TABLE IF EXISTS notes_fts_docsize;
+                 DROP TABLE IF EXISTS notes_fts_config;",
+            )?;
+        }
+        tx.execute_batch(&fts_table_creation(self.fts_tokenizer))?;
+        tx.execute_batch(FTS_TRIGGERS)?;
+        self.populate_fts_index()?;
+
+        let indexed: i64 = tx.query_row("SELECT COUNT(*) FROM notes_fts", [], |row| row.get(0))?;
+        tx.commit()?;
+
+        Ok(indexed as usize)
+    }
+
     /// Populates the FTS index from existing notes and tags.
     ///
     /// Clears the existing FTS index and rebuilds it from the notes table.
-    /// This operation is idempotent and safe to run on every database open.
     fn populate_fts_index(&self) -> Result<()> {
         // Clear existing FTS index
         self.conn.execute("DELETE FROM notes_fts", [])?;
@@ -116,6 +397,72 @@ impl Database {
         Ok(())
     }
 
+    /// Returns the schema version (the highest applied migration).
+    ///
+    /// The same value is mirrored into `PRAGMA user_version`.
+    pub fn schema_version(&self) -> Result<u32> {
+        current_version(&self.conn)
+    }
+
+    /// Writes a consistent snapshot of the database to `path`.
+    ///
+    /// Uses SQLite's online backup API, so the copy is safe to take while
+    /// other connections are writing. An existing file at `path` is
+    /// overwritten. Returns the size of the written file in bytes.
+    pub fn backup_to(&self, path: impl AsRef<Path>) -> Result<u64> {
+        let path = path.as_ref();
+        self.conn
+            .backup(DatabaseName::Main, path, None)
+            .with_context(|| format!("Failed to back up database to {}", path.display()))?;
+
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read backup size: {}", path.display()))?
+            .len();
+        Ok(size)
+    }
+
+    /// Checks the database file for corruption and dangling foreign keys.
+    ///
+    /// Runs `PRAGMA integrity_check` and `PRAGMA foreign_key_check` and
+    /// returns one message per problem found. An empty list means the
+    /// database is healthy.
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut problems = Vec::new();
+
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        for row in rows {
+            let message = row?;
+            if message != "ok" {
+                problems.push(message);
+            }
+        }
+
+        let mut stmt = self.conn.prepare("PRAGMA foreign_key_check")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (table, rowid, parent) = row?;
+            let rowid = rowid.map_or_else(|| "?".to_string(), |id| id.to_string());
+            problems.push(format!(
+                "foreign key violation: {table} row {rowid} references missing {parent} row"
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    /// Rebuilds the database file, reclaiming free pages.
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
     /// Returns a reference to the underlying connection.
     ///
     /// Useful for executing custom queries in tests or future CRUD operations.
@@ -124,5 +471,26 @@ impl Database {
     }
 }
 
+/// Returns true if `error` means the FTS index itself is damaged or missing.
+///
+/// Only these errors are worth a rebuild; anything else (a locked or
+/// read-only database, a full disk) would fail the rebuild too, so it is
+/// reported as is.
+fn is_unreadable_index(error: &anyhow::Error) -> bool {
+    use rusqlite::ErrorCode;
+
+    match error.downcast_ref::<rusqlite::Error>() {
+        Some(rusqlite::Error::SqliteFailure(failure, message)) => {
+            matches!(
+                failure.code,
+                ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase
+            ) || message
+                .as_deref()
+                .is_some_and(|m| m.contains("no such table"))
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -43,6 +43,7 @@ impl Migration {
             "INSERT INTO schema_migrations (version, applied_at, description) VALUES (?1, ?2, ?3)",
             rusqlite::params![self.version, applied_at as i64, self.description],
         )?;
+        tx.pragma_update(None, "user_version", self.version)?;
 
         tx.commit()?;
         Ok(())
@@ -69,6 +70,42 @@ pub const MIGRATIONS: &[Migration] = &[
         "Add degree_centrality column to tags table for graph analytics",
         include_str!("migrations/003_tag_degree_centrality.sql"),
     ),
+    // Note capture provenance
+    Migration::new(
+        4,
+        "Add source column to notes table (cli, tui, import)",
+        include_str!("migrations/004_note_source.sql"),
+    ),
+    // Note-to-note links
+    Migration::new(
+        5,
+        "Add links table for [[id]] references between notes",
+        include_str!("migrations/005_note_links.sql"),
+    ),
+    // Tag descriptions
+    Migration::new(
+        6,
+        "Add description column to tags table",
+        include_str!("migrations/006_tag_descriptions.sql"),
+    ),
+    // Duplicate-note lookup
+    Migration::new(
+        7,
+        "Add content_hash column to notes table for duplicate detection",
+        include_str!("migrations/007_note_content_hash.sql"),
+    ),
+    // Named tag bundles for capture
+    Migration::new(
+        8,
+        "Add tag_presets table for reusable tag sets",
+        include_str!("migrations/008_tag_presets.sql"),
+    ),
+    // Enhancement prompt provenance
+    Migration::new(
+        9,
+        "Add enhancement_prompt column to notes table",
+        include_str!("migrations/009_note_enhancement_prompt.sql"),
+    ),
 ];
 
 /// Applies all pending migrations to the database.
@@ -87,9 +124,24 @@ pub fn apply_pending_migrations(conn: &mut rusqlite::Connection) -> anyhow::Resu
         }
     }
 
+    // Mirror the schema version into PRAGMA user_version for external tools.
+    // Databases migrated before user_version was maintained are synced here.
+    let version = current_version(conn)?;
+    conn.pragma_update(None, "user_version", version)?;
+
     Ok(())
 }
 
+/// Returns the highest applied migration version, or 0 for an empty database.
+pub fn current_version(conn: &rusqlite::Connection) -> anyhow::Result<u32> {
+    let version: u32 = conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(version)
+}
+
 /// Creates the schema_migrations table if it doesn't exist.
 /// This is idempotent and safe to call multiple times.
 fn ensure_migration_table_exists(conn: &rusqlite::Connection) -> anyhow::Result<()> {
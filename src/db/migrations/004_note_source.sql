@@ -0,0 +1,5 @@
+-- Add capture provenance to notes table
+-- Records how each note was created ('cli', 'tui', 'import'); NULL for notes created before this migration
+-- Version: 004
+
+ALTER TABLE notes ADD COLUMN source TEXT;
@@ -0,0 +1,14 @@
+-- Add note-to-note links parsed from [[id]] references in note content
+-- Rows are rebuilt whenever a note's content is written; dangling references are not stored
+-- Version: 005
+
+CREATE TABLE IF NOT EXISTS links (
+    source_note_id INTEGER NOT NULL,
+    target_note_id INTEGER NOT NULL,
+    PRIMARY KEY (source_note_id, target_note_id),
+    FOREIGN KEY (source_note_id) REFERENCES notes(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+-- Backlink lookups query by target
+CREATE INDEX IF NOT EXISTS idx_links_target ON links(target_note_id);
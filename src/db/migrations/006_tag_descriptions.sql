@@ -0,0 +1,5 @@
+-- Add a user-written definition to tags
+-- NULL means the tag has no description
+-- Version: 006
+
+ALTER TABLE tags ADD COLUMN description TEXT;
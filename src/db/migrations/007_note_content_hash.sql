@@ -0,0 +1,8 @@
+-- Add a SHA-256 hash of each note's trimmed content for exact-duplicate lookup
+-- Hashes are computed in Rust (crate::utils::content_hash); existing notes are
+-- backfilled when the database is opened
+-- Version: 007
+
+ALTER TABLE notes ADD COLUMN content_hash TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_notes_content_hash ON notes(content_hash);
@@ -0,0 +1,9 @@
+-- Add named tag presets: bundles of tags applied together with `cons add --preset`
+-- Tags are stored normalized as a comma-separated list
+-- Version: 008
+
+CREATE TABLE IF NOT EXISTS tag_presets (
+    name TEXT PRIMARY KEY,
+    tags TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
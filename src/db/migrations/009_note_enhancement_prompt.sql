@@ -0,0 +1,5 @@
+-- Record the prompt that produced each note's enhancement, for auditing prompt changes
+-- NULL for notes enhanced before this migration or never enhanced
+-- Version: 009
+
+ALTER TABLE notes ADD COLUMN enhancement_prompt TEXT;
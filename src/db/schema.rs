@@ -1,21 +1,28 @@
-pub use super::migration::apply_pending_migrations;
+pub use super::migration::{apply_pending_migrations, current_version};
 // Re-export for tests
 #[cfg(test)]
 pub use super::migration::MIGRATIONS;
 
-/// FTS5 virtual table creation SQL.
+use super::FtsTokenizer;
+
+/// FTS5 virtual table creation SQL for the given tokenizer.
 ///
 /// FTS5 does NOT support IF NOT EXISTS, so this must be executed conditionally
 /// by checking sqlite_master first in initialize_schema().
-pub const FTS_TABLE_CREATION: &str = r#"
+pub fn fts_table_creation(tokenizer: FtsTokenizer) -> String {
+    format!(
+        r#"
 CREATE VIRTUAL TABLE notes_fts USING fts5(
     note_id UNINDEXED,
     content,
     content_enhanced,
     tags,
-    tokenize='porter'
+    {}
 );
-"#;
+"#,
+        tokenizer.tokenize_clause()
+    )
+}
 
 /// FTS5 synchronization triggers.
 ///
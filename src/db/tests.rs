@@ -717,6 +717,67 @@ fn notes_has_enhancement_confidence_column() {
     assert_eq!(enhancement_confidence, None);
 }
 
+#[test]
+fn notes_has_nullable_source_column() {
+    let db = Database::in_memory().unwrap();
+
+    let source_type: String = db
+        .connection()
+        .query_row(
+            "SELECT type FROM pragma_table_info('notes') WHERE name = 'source'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("source column should exist");
+    assert_eq!(source_type, "TEXT");
+
+    // Rows written before the migration have no source
+    db.connection()
+        .execute(
+            "INSERT INTO notes (id, content) VALUES (1, 'test note')",
+            [],
+        )
+        .unwrap();
+    let source: Option<String> = db
+        .connection()
+        .query_row("SELECT source FROM notes WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(source, None);
+}
+
+#[test]
+fn notes_has_nullable_enhancement_prompt_column() {
+    let db = Database::in_memory().unwrap();
+
+    let prompt_type: String = db
+        .connection()
+        .query_row(
+            "SELECT type FROM pragma_table_info('notes') WHERE name = 'enhancement_prompt'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("enhancement_prompt column should exist");
+    assert_eq!(prompt_type, "TEXT");
+
+    db.connection()
+        .execute(
+            "INSERT INTO notes (id, content) VALUES (1, 'test note')",
+            [],
+        )
+        .unwrap();
+    let prompt: Option<String> = db
+        .connection()
+        .query_row(
+            "SELECT enhancement_prompt FROM notes WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(prompt, None);
+}
+
 #[test]
 fn schema_migration_idempotent_on_existing_database() {
     use tempfile::tempdir;
@@ -765,6 +826,41 @@ fn schema_migration_idempotent_on_existing_database() {
     assert!(db3.is_ok(), "Schema migration should be idempotent");
 }
 
+#[test]
+fn schema_version_matches_latest_migration() {
+    let db = Database::in_memory().unwrap();
+    let latest = MIGRATIONS.last().unwrap().version;
+
+    assert_eq!(db.schema_version().unwrap(), latest);
+
+    let user_version: u32 = db
+        .connection()
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(user_version, latest);
+}
+
+#[test]
+fn user_version_synced_for_databases_migrated_without_it() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("test.db");
+
+    // Simulate a database migrated before user_version was maintained
+    {
+        let db = Database::open(&db_path).unwrap();
+        db.connection()
+            .pragma_update(None, "user_version", 0)
+            .unwrap();
+    }
+
+    let db = Database::open(&db_path).unwrap();
+    let user_version: u32 = db
+        .connection()
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(user_version, MIGRATIONS.last().unwrap().version);
+}
+
 #[test]
 fn fts5_virtual_table_created() {
     let db = Database::in_memory().unwrap();
@@ -2113,6 +2209,10 @@ fn edges_schema_integration_with_existing_tables() {
         tables.contains(&"edges".to_string()),
         "edges table should exist"
     );
+    assert!(
+        tables.contains(&"links".to_string()),
+        "links table should exist"
+    );
 
     // Test foreign key from edges to tags works correctly
     db.connection()
@@ -2217,6 +2317,237 @@ fn tags_has_degree_centrality_column() {
     );
 }
 
+#[test]
+fn tags_has_nullable_description_column() {
+    let db = Database::in_memory().unwrap();
+
+    db.connection()
+        .execute("INSERT INTO tags (id, name) VALUES (1, 'test-tag')", [])
+        .unwrap();
+
+    let description: Option<String> = db
+        .connection()
+        .query_row("SELECT description FROM tags WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+
+    assert_eq!(description, None, "description should default to NULL");
+}
+
+#[test]
+fn tag_presets_table_exists_with_unique_names() {
+    let db = Database::in_memory().unwrap();
+    let conn = db.connection();
+
+    conn.execute(
+        "INSERT INTO tag_presets (name, tags, created_at) VALUES ('meeting', 'work,meetings', 0)",
+        [],
+    )
+    .unwrap();
+    let duplicate = conn.execute(
+        "INSERT INTO tag_presets (name, tags, created_at) VALUES ('meeting', 'other', 0)",
+        [],
+    );
+    assert!(duplicate.is_err(), "preset names should be unique");
+}
+
+#[test]
+fn open_rebuilds_unreadable_fts_index() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("notes.db");
+
+    {
+        let db = Database::open(&db_path).unwrap();
+        db.connection()
+            .execute("INSERT INTO notes (content) VALUES ('indexed again')", [])
+            .unwrap();
+        // Losing a shadow table leaves notes_fts in sqlite_master but unusable
+        db.connection()
+            .execute("DROP TABLE notes_fts_data", [])
+            .unwrap();
+    }
+
+    let db = Database::open(&db_path).expect("open should rebuild the FTS index");
+    assert_eq!(db.fts_rebuild(), Some(FtsRebuild::Unreadable));
+    let matches: i64 = db
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM notes_fts WHERE notes_fts MATCH 'indexed'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(matches, 1);
+    assert!(db.integrity_check().unwrap().is_empty());
+
+    let reopened = Database::open(&db_path).unwrap();
+    assert_eq!(reopened.fts_rebuild(), None, "healthy index is left alone");
+}
+
+#[test]
+fn only_index_damage_counts_as_unreadable() {
+    let failure = |code| {
+        anyhow::Error::from(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(code),
+            None,
+        ))
+    };
+
+    assert!(is_unreadable_index(&failure(rusqlite::ffi::SQLITE_CORRUPT)));
+    assert!(is_unreadable_index(&failure(rusqlite::ffi::SQLITE_NOTADB)));
+    assert!(is_unreadable_index(&anyhow::Error::from(
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_ERROR),
+            Some("no such table: notes_fts".to_string()),
+        )
+    )));
+
+    // A busy or read-only database would fail a rebuild as well
+    assert!(!is_unreadable_index(&failure(rusqlite::ffi::SQLITE_BUSY)));
+    assert!(!is_unreadable_index(&failure(
+        rusqlite::ffi::SQLITE_READONLY
+    )));
+}
+
+#[test]
+fn open_with_new_tokenizer_rebuilds_fts_index() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("notes.db");
+
+    {
+        let db = Database::open_with_tokenizer(&db_path, FtsTokenizer::Porter).unwrap();
+        db.connection()
+            .execute(
+                "INSERT INTO notes (content) VALUES ('programming notes')",
+                [],
+            )
+            .unwrap();
+    }
+
+    let db = Database::open_with_tokenizer(&db_path, FtsTokenizer::Unicode61).unwrap();
+    assert_eq!(
+        db.fts_rebuild(),
+        Some(FtsRebuild::TokenizerChanged(FtsTokenizer::Unicode61))
+    );
+    let sql: String = db
+        .connection()
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'notes_fts'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(
+        sql.contains("tokenize='unicode61'"),
+        "table re-created: {sql}"
+    );
+
+    let count_matches = |term: &str| -> i64 {
+        db.connection()
+            .query_row(
+                "SELECT COUNT(*) FROM notes_fts WHERE notes_fts MATCH ?1",
+                [term],
+                |row| row.get(0),
+            )
+            .unwrap()
+    };
+    assert_eq!(count_matches("programming"), 1, "existing notes re-indexed");
+    assert_eq!(count_matches("program"), 0, "unicode61 does not stem");
+}
+
+#[test]
+fn open_read_only_leaves_the_file_untouched() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("notes.db");
+
+    {
+        let db = Database::open_with_tokenizer(&db_path, FtsTokenizer::Unicode61).unwrap();
+        db.connection()
+            .execute("INSERT INTO notes (content) VALUES ('kept as is')", [])
+            .unwrap();
+        // Left for a normal open to backfill
+        db.connection()
+            .execute("UPDATE notes SET content_hash = NULL", [])
+            .unwrap();
+    }
+
+    let db = Database::open_read_only(&db_path).unwrap();
+    assert_eq!(db.fts_tokenizer(), FtsTokenizer::Unicode61);
+    let missing_hashes: i64 = db
+        .connection()
+        .query_row(
+            "SELECT COUNT(*) FROM notes WHERE content_hash IS NULL",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(missing_hashes, 1, "no backfill on a read-only open");
+    assert!(
+        db.connection()
+            .execute("INSERT INTO notes (content) VALUES ('new')", [])
+            .is_err()
+    );
+}
+
+#[test]
+fn open_read_only_rejects_missing_or_outdated_databases() {
+    let temp_dir = tempdir().unwrap();
+    assert!(Database::open_read_only(temp_dir.path().join("missing.db")).is_err());
+
+    let db_path = temp_dir.path().join("old.db");
+    {
+        let mut conn = Connection::open(&db_path).unwrap();
+        MIGRATIONS[0].apply(&mut conn).unwrap();
+    }
+    let err = Database::open_read_only(&db_path).err().unwrap();
+    assert!(err.to_string().contains("out of date"));
+}
+
+#[test]
+fn fts_tokenizer_parses_case_insensitively() {
+    assert_eq!(
+        "porter".parse::<FtsTokenizer>().unwrap(),
+        FtsTokenizer::Porter
+    );
+    assert_eq!(
+        "TRIGRAM".parse::<FtsTokenizer>().unwrap(),
+        FtsTokenizer::Trigram
+    );
+    let err = "none".parse::<FtsTokenizer>().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected porter, unicode61 or trigram")
+    );
+}
+
+#[test]
+fn content_hash_backfilled_on_open() {
+    let temp_dir = tempdir().unwrap();
+    let db_path = temp_dir.path().join("notes.db");
+
+    {
+        let db = Database::open(&db_path).unwrap();
+        // Simulate a note written before content hashes existed
+        db.connection()
+            .execute(
+                "INSERT INTO notes (id, content, created_at, updated_at) VALUES (1, ' old note\n', 0, 0)",
+                [],
+            )
+            .unwrap();
+    }
+
+    let db = Database::open(&db_path).unwrap();
+    let hash: Option<String> = db
+        .connection()
+        .query_row("SELECT content_hash FROM notes WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+
+    assert_eq!(hash, Some(crate::content_hash("old note")));
+}
+
 #[test]
 fn degree_centrality_backfill_counts_existing_edges() {
     let db = Database::in_memory().unwrap();
@@ -2473,3 +2804,76 @@ fn degree_centrality_backfill_idempotent() {
     assert_eq!(tag1_first, 1, "Tag 1 should have 1 connection");
     assert_eq!(tag2_first, 1, "Tag 2 should have 1 connection");
 }
+
+#[test]
+fn backup_to_writes_consistent_copy_of_in_memory_database() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    db.connection()
+        .execute("INSERT INTO notes (content) VALUES ('backed up note')", [])
+        .expect("failed to insert note");
+
+    let dir = tempdir().expect("failed to create temp dir");
+    let backup_path = dir.path().join("backup.db");
+
+    let size = db.backup_to(&backup_path).expect("backup should succeed");
+    assert!(size > 0, "backup should report bytes written");
+    assert_eq!(
+        size,
+        std::fs::metadata(&backup_path).unwrap().len(),
+        "reported size should match the file on disk"
+    );
+
+    let restored = Database::open(&backup_path).expect("backup should open as a database");
+    let content: String = restored
+        .connection()
+        .query_row("SELECT content FROM notes", [], |row| row.get(0))
+        .expect("note should be in backup");
+    assert_eq!(content, "backed up note");
+}
+
+#[test]
+fn integrity_check_reports_no_problems_for_fresh_database() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let problems = db.integrity_check().expect("integrity check should run");
+    assert!(problems.is_empty(), "unexpected problems: {problems:?}");
+}
+
+#[test]
+fn integrity_check_reports_foreign_key_violations() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let conn = db.connection();
+    conn.execute("INSERT INTO tags (id, name) VALUES (1, 'rust')", [])
+        .unwrap();
+
+    // Insert a dangling note_tags row with enforcement temporarily disabled
+    conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+    conn.execute("INSERT INTO note_tags (note_id, tag_id) VALUES (42, 1)", [])
+        .unwrap();
+    conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+    let problems = db.integrity_check().expect("integrity check should run");
+    assert_eq!(problems.len(), 1, "problems: {problems:?}");
+    assert!(problems[0].contains("note_tags"));
+    assert!(problems[0].contains("notes"));
+}
+
+#[test]
+fn vacuum_shrinks_file_after_deletes() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("vacuum.db");
+    let db = Database::open(&db_path).expect("failed to open database");
+
+    let padding = "x".repeat(4096);
+    for _ in 0..100 {
+        db.connection()
+            .execute("INSERT INTO notes (content) VALUES (?1)", [&padding])
+            .unwrap();
+    }
+    db.connection().execute("DELETE FROM notes", []).unwrap();
+    let before = std::fs::metadata(&db_path).unwrap().len();
+
+    db.vacuum().expect("vacuum should succeed");
+
+    let after = std::fs::metadata(&db_path).unwrap().len();
+    assert!(after < before, "expected {after} < {before}");
+}
@@ -6,11 +6,13 @@
 //! - Backfill capabilities for missing enrichments
 
 use std::io::{self, Write};
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 
 use crate::autotagger::AutoTaggerBuilder;
+use crate::config::Config;
 use crate::enhancer::NoteEnhancerBuilder;
 use crate::hierarchy::HierarchySuggesterBuilder;
 use crate::ollama::OllamaClientBuilder;
@@ -46,6 +48,9 @@ impl HealthStatus {
 pub struct DatabaseHealth {
     pub status: HealthStatus,
     pub file_path: String,
+    pub writable: bool,
+    /// Whether the full-text search index covers every note
+    pub search_index: HealthStatus,
 }
 
 /// Migration tracking information.
@@ -62,6 +67,8 @@ pub struct OllamaHealth {
     pub status: HealthStatus,
     pub base_url: String,
     pub models: Vec<String>,
+    /// Model named by `OLLAMA_MODEL`, if set
+    pub configured_model: Option<String>,
 }
 
 /// Note statistics for doctor output.
@@ -112,7 +119,15 @@ pub struct BackfillResult {
 // ============================================================================
 
 /// Performs all health checks and prints results.
-pub fn run_health_checks(db_path: &str, service: &NoteService) -> Result<()> {
+///
+/// With `repair`, a search index that is out of sync with the notes (or
+/// unreadable) is rebuilt before the checks run.
+pub fn run_health_checks(db_path: &str, service: &NoteService, repair: bool) -> Result<()> {
+    if repair && let Some(indexed) = repair_search_index(service)? {
+        println!("Rebuilt search index ({} notes indexed)", indexed);
+        println!();
+    }
+
     let db_health = check_database_health(db_path, service);
     let migrations = get_applied_migrations(service)?;
     let ollama_health = check_ollama_health();
@@ -133,7 +148,52 @@ fn check_database_health(db_path: &str, service: &NoteService) -> DatabaseHealth
     DatabaseHealth {
         status,
         file_path: db_path.to_string(),
+        writable: is_writable(Path::new(db_path)),
+        search_index: check_search_index(service),
+    }
+}
+
+/// Rebuilds the search index if it is out of sync with the notes or unreadable.
+///
+/// Returns the number of notes indexed, or `None` if the index was healthy.
+pub fn repair_search_index(service: &NoteService) -> Result<Option<usize>> {
+    if service.fts_is_consistent().unwrap_or(false) {
+        return Ok(None);
     }
+
+    let indexed = service
+        .rebuild_fts()
+        .context("Failed to rebuild search index")?;
+    Ok(Some(indexed))
+}
+
+/// Checks that the full-text search index has one row per note.
+fn check_search_index(service: &NoteService) -> HealthStatus {
+    match service.fts_is_consistent() {
+        Ok(true) => HealthStatus::Ok,
+        Ok(false) => HealthStatus::Warning(
+            "Out of sync with notes; run 'cons maintenance rebuild-fts' or 'cons doctor --repair'"
+                .to_string(),
+        ),
+        Err(e) => HealthStatus::Error(format!(
+            "Unreadable ({}); run 'cons maintenance rebuild-fts' or 'cons doctor --repair'",
+            e
+        )),
+    }
+}
+
+/// Returns true if the database file can be opened for writing.
+fn is_writable(path: &Path) -> bool {
+    std::fs::OpenOptions::new().write(true).open(path).is_ok()
+}
+
+/// Returns true if `model` is among the installed `models`.
+///
+/// Ollama reports untagged models as `name:latest`, so `llama3` matches `llama3:latest`.
+fn model_is_installed(model: &str, models: &[String]) -> bool {
+    models
+        .iter()
+        .any(|m| m == model || m.strip_suffix(":latest") == Some(model))
 }
 
 fn get_applied_migrations(service: &NoteService) -> Result<Vec<MigrationInfo>> {
@@ -155,6 +215,8 @@ fn get_applied_migrations(service: &NoteService) -> Result<Vec<MigrationInfo>> {
 }
 
 fn check_ollama_health() -> OllamaHealth {
+    let configured_model = Config::load().ollama_model;
+
     let client = match OllamaClientBuilder::new().build() {
         Ok(c) => c,
         Err(e) => {
@@ -162,26 +224,34 @@ fn check_ollama_health() -> OllamaHealth {
                 status: HealthStatus::Error(format!("Failed to build client: {}", e)),
                 base_url: String::new(),
                 models: Vec::new(),
+                configured_model,
             }
         }
     };
 
     let base_url = client.base_url().to_string();
 
-    match client.list_models() {
+    match client.health_check() {
         Ok(models) => OllamaHealth {
             status: if models.is_empty() {
                 HealthStatus::Warning("No models installed".to_string())
+            } else if let Some(model) = configured_model
+                .as_deref()
+                .filter(|m| !model_is_installed(m, &models))
+            {
+                HealthStatus::Warning(format!("Configured model '{}' is not installed", model))
             } else {
                 HealthStatus::Ok
             },
             base_url,
             models,
+            configured_model,
         },
         Err(e) => OllamaHealth {
             status: HealthStatus::Error(format!("Connection failed: {}", e)),
             base_url,
             models: Vec::new(),
+            configured_model,
         },
     }
 }
@@ -260,6 +330,22 @@ fn print_health_report(
         if db.status.is_ok() { "OK" } else { "FAILED" }
     );
     println!("    {}Path: {}{}", DIM, db.file_path, RESET);
+    if db.writable {
+        println!("    {}Writable: yes{}", DIM, RESET);
+    } else {
+        println!("    {}Writable: no{}", RED, RESET);
+    }
+    let index_text = match &db.search_index {
+        HealthStatus::Ok => "OK".to_string(),
+        HealthStatus::Warning(message) | HealthStatus::Error(message) => message.clone(),
+    };
+    println!(
+        "  {}{}{} Search index: {}",
+        status_color(&db.search_index),
+        status_symbol(&db.search_index),
+        RESET,
+        index_text
+    );
     println!();
 
     // Migrations section
@@ -298,6 +384,13 @@ fn print_health_report(
     if !ollama.base_url.is_empty() {
         println!("    {}URL: {}{}", DIM, ollama.base_url, RESET);
     }
+    match &ollama.configured_model {
+        Some(model) => println!("    {}Model (OLLAMA_MODEL): {}{}", DIM, model, RESET),
+        None => println!(
+            "    {}Model: OLLAMA_MODEL not set (largest installed model is used){}",
+            YELLOW, RESET
+        ),
+    }
     if !ollama.models.is_empty() {
         let models_display = if ollama.models.len() > 3 {
             format!(
@@ -470,9 +563,9 @@ pub fn execute_backfill(service: &NoteService, plan: &BackfillPlan) -> Result<Ba
     );
 
     // Auto-detect model
-    let model = match std::env::var("OLLAMA_MODEL") {
-        Ok(m) if !m.is_empty() => m,
-        _ => {
+    let model = match Config::load().ollama_model {
+        Some(m) => m,
+        None => {
             let models = client.list_models().context("Ollama not reachable")?;
             models
                 .into_iter()
@@ -509,6 +602,7 @@ pub fn execute_backfill(service: &NoteService, plan: &BackfillPlan) -> Result<Ba
                             &model,
                             enhancement.confidence(),
                             now,
+                            enhancement.prompt(),
                         ) {
                             result.errors.push(format!("Note #{}: {}", note_id, e));
                             println!("{}FAILED{}", RED, RESET);
@@ -786,6 +880,89 @@ mod tests {
         assert!(migrations.iter().any(|m| m.version == 1));
     }
 
+    #[test]
+    fn test_model_is_installed_matches_latest_tag() {
+        let models = vec!["llama3:latest".to_string(), "gemma3:4b".to_string()];
+        assert!(model_is_installed("llama3", &models));
+        assert!(model_is_installed("llama3:latest", &models));
+        assert!(model_is_installed("gemma3:4b", &models));
+        assert!(!model_is_installed("gemma3", &models));
+        assert!(!model_is_installed("mistral", &models));
+    }
+
+    #[test]
+    fn test_is_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("notes.db");
+        Database::open(&db_path).unwrap();
+
+        assert!(is_writable(&db_path));
+        assert!(!is_writable(&dir.path().join("missing").join("notes.db")));
+    }
+
+    #[test]
+    fn test_check_search_index_flags_drift() {
+        let db = Database::in_memory().unwrap();
+        let service = NoteService::new(db);
+        let note = service.create_note("Note 1", Some(&["rust"])).unwrap();
+        assert!(check_search_index(&service).is_ok());
+
+        service
+            .database()
+            .connection()
+            .execute(
+                "DELETE FROM notes_fts WHERE note_id = ?1",
+                [note.id().get()],
+            )
+            .unwrap();
+        match check_search_index(&service) {
+            HealthStatus::Warning(message) => assert!(message.contains("rebuild-fts")),
+            other => panic!("expected warning, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_check_search_index_flags_drift_after_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("notes.db");
+        {
+            let service = NoteService::new(Database::open(&db_path).unwrap());
+            let note = service.create_note("Note 1", None).unwrap();
+            service
+                .database()
+                .connection()
+                .execute(
+                    "DELETE FROM notes_fts WHERE note_id = ?1",
+                    [note.id().get()],
+                )
+                .unwrap();
+        }
+
+        // Opening the database must not quietly repair the index
+        let service = NoteService::new(Database::open(&db_path).unwrap());
+        assert!(!check_search_index(&service).is_ok());
+    }
+
+    #[test]
+    fn test_repair_search_index_rebuilds_only_when_drifted() {
+        let db = Database::in_memory().unwrap();
+        let service = NoteService::new(db);
+        let note = service.create_note("Note 1", None).unwrap();
+        assert_eq!(repair_search_index(&service).unwrap(), None);
+
+        service
+            .database()
+            .connection()
+            .execute(
+                "DELETE FROM notes_fts WHERE note_id = ?1",
+                [note.id().get()],
+            )
+            .unwrap();
+
+        assert_eq!(repair_search_index(&service).unwrap(), Some(1));
+        assert!(service.fts_is_consistent().unwrap());
+    }
+
     #[test]
     fn test_backfill_result_default() {
         let result = BackfillResult::default();
@@ -5,8 +5,20 @@
 //! while preserving the original intent.
 
 use std::sync::Arc;
+use std::time::Instant;
 
-use crate::ollama::{OllamaClientTrait, OllamaError};
+use crate::ollama::{GenOptions, OllamaClientTrait, OllamaError};
+
+/// Sampling options for enhancement requests.
+///
+/// A low temperature and a fixed seed keep enhancements close to the original
+/// note and reproducible for the same model and content.
+const ENHANCEMENT_OPTIONS: GenOptions = GenOptions {
+    temperature: Some(0.2),
+    top_p: None,
+    seed: Some(42),
+    num_predict: None,
+};
 
 /// Prompt template for note enhancement.
 ///
@@ -57,13 +69,20 @@ JSON OUTPUT:"#;
 /// Result of note enhancement operation.
 ///
 /// Contains the enhanced note content and a confidence score
-/// indicating the quality of the enhancement.
+/// indicating the quality of the enhancement. Results from `NoteEnhancer`
+/// also record the prompt, the model and how long the model call took.
 #[derive(Debug, Clone, PartialEq)]
 pub struct EnhancementResult {
     /// The enhanced note content
     enhanced_content: String,
     /// Confidence score (0.0-1.0) in the enhancement quality
     confidence: f64,
+    /// The prompt sent to the model
+    prompt: Option<String>,
+    /// The model that produced the enhancement
+    model: Option<String>,
+    /// Wall-clock duration of the model call in milliseconds
+    duration_ms: Option<u64>,
 }
 
 impl EnhancementResult {
@@ -77,6 +96,9 @@ impl EnhancementResult {
         Self {
             enhanced_content,
             confidence: confidence.clamp(0.0, 1.0),
+            prompt: None,
+            model: None,
+            duration_ms: None,
         }
     }
 
@@ -89,6 +111,26 @@ impl EnhancementResult {
     pub fn confidence(&self) -> f64 {
         self.confidence
     }
+
+    /// Returns the prompt that produced this result, if known.
+    ///
+    /// Stored alongside the enhancement so notes enhanced with an older
+    /// prompt can be identified after the prompt changes.
+    pub fn prompt(&self) -> Option<&str> {
+        self.prompt.as_deref()
+    }
+
+    /// Returns the model that produced this result, if known.
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Returns how long the model call took in milliseconds, if known.
+    ///
+    /// Covers the Ollama request only, not prompt construction or JSON parsing.
+    pub fn duration_ms(&self) -> Option<u64> {
+        self.duration_ms
+    }
 }
 
 /// Builder for constructing `NoteEnhancer` instances.
@@ -247,7 +289,8 @@ impl NoteEnhancer {
     ///
     /// # Returns
     ///
-    /// Returns an `EnhancementResult` containing the enhanced content and confidence score.
+    /// Returns an `EnhancementResult` containing the enhanced content, confidence score,
+    /// the prompt that was sent, the model, and the duration of the model call.
     ///
     /// # Errors
     ///
@@ -262,16 +305,24 @@ impl NoteEnhancer {
         // Construct prompt with note content
         let prompt = PROMPT_TEMPLATE.replace("{content}", content);
 
-        // Call LLM
-        let response = self.client.generate(model, &prompt)?;
+        // Call LLM, timing the request so callers can report latency
+        let start = Instant::now();
+        let response = self
+            .client
+            .generate_with_options(model, &prompt, ENHANCEMENT_OPTIONS)?;
+        let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
 
         // Extract JSON from response (handles various output formats)
         let json_str = extract_json(&response).ok_or_else(|| OllamaError::Api {
             message: "Failed to extract JSON from LLM response".to_string(),
         })?;
 
-        // Parse enhancement result
-        parse_enhancement_result(&json_str)
+        // Parse enhancement result and record how it was produced
+        let mut result = parse_enhancement_result(&json_str)?;
+        result.prompt = Some(prompt);
+        result.model = Some(model.to_string());
+        result.duration_ms = Some(duration_ms);
+        Ok(result)
     }
 }
 
@@ -383,6 +434,46 @@ mod tests {
         assert_eq!(enhancement.confidence(), 0.9);
     }
 
+    #[test]
+    fn test_enhance_content_records_prompt() {
+        let mock = MockOllamaClient {
+            response: r#"{"enhanced_content": "Buy milk from the store.", "confidence": 0.85}"#
+                .to_string(),
+        };
+        let enhancer = NoteEnhancer::new(Arc::new(mock));
+
+        let enhancement = enhancer
+            .enhance_content("test-model", "buy milk")
+            .expect("enhancement should succeed");
+        assert_eq!(
+            enhancement.prompt(),
+            Some(PROMPT_TEMPLATE.replace("{content}", "buy milk").as_str())
+        );
+        assert_eq!(
+            EnhancementResult::new("Test".to_string(), 0.5).prompt(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_enhance_content_records_model_and_duration() {
+        let mock = MockOllamaClient {
+            response: r#"{"enhanced_content": "Buy milk from the store.", "confidence": 0.85}"#
+                .to_string(),
+        };
+        let enhancer = NoteEnhancer::new(Arc::new(mock));
+
+        let enhancement = enhancer
+            .enhance_content("test-model", "buy milk")
+            .expect("enhancement should succeed");
+        assert_eq!(enhancement.model(), Some("test-model"));
+        assert!(enhancement.duration_ms().is_some());
+
+        let bare = EnhancementResult::new("Test".to_string(), 0.5);
+        assert_eq!(bare.model(), None);
+        assert_eq!(bare.duration_ms(), None);
+    }
+
     #[test]
     fn test_enhance_content_returns_enhancement_result_with_content_and_confidence() {
         let mock = MockOllamaClient {
@@ -598,4 +689,39 @@ This expansion clarifies the abbreviated input."#
         assert_eq!(result1.enhanced_content(), result2.enhanced_content());
         assert_eq!(result1.confidence(), result2.confidence());
     }
+
+    #[test]
+    fn test_enhance_content_uses_low_temperature_and_fixed_seed() {
+        struct OptionsRecordingClient {
+            options: std::sync::Mutex<Option<GenOptions>>,
+        }
+
+        impl OllamaClientTrait for OptionsRecordingClient {
+            fn generate(&self, _model: &str, _prompt: &str) -> Result<String, OllamaError> {
+                panic!("enhancer should pass sampling options");
+            }
+
+            fn generate_with_options(
+                &self,
+                _model: &str,
+                _prompt: &str,
+                options: GenOptions,
+            ) -> Result<String, OllamaError> {
+                *self.options.lock().unwrap() = Some(options);
+                Ok(r#"{"enhanced_content": "Test note.", "confidence": 0.9}"#.to_string())
+            }
+        }
+
+        let client = Arc::new(OptionsRecordingClient {
+            options: std::sync::Mutex::new(None),
+        });
+        let enhancer = NoteEnhancer::new(client.clone());
+        enhancer
+            .enhance_content("test-model", "test content")
+            .expect("enhancement should succeed");
+
+        let options = client.options.lock().unwrap().expect("options recorded");
+        assert_eq!(options.temperature, Some(0.2));
+        assert_eq!(options.seed, Some(42));
+    }
 }
@@ -0,0 +1,689 @@
+//! Note export and import for backup and migration.
+//!
+//! Provides the `export` and `import` command functionality:
+//! - JSON: an array of notes with tags, enhancement fields, and timestamps
+//! - NDJSON: one note JSON object per line, streamed page by page
+//! - Markdown: one `##`-headed section per note for human reading, or a
+//!   single titled document of the notes carrying one tag
+//! - Parsing a JSON export back into notes for `NoteService::import_notes`
+//!
+//! Also renders the tag hierarchy as Graphviz DOT for `hierarchy export`, and
+//! the tag co-occurrence network as an undirected DOT graph for `tags graph`.
+//!
+//! Serialization works on any `io::Write`/`io::Read`, so it can be tested
+//! without touching the filesystem.
+
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use time::format_description::well_known::Rfc3339;
+
+use crate::{ListNotesOptions, Note, NoteService, SortOrder};
+
+/// Output format for exported notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// JSON array of notes, suitable for re-import
+    Json,
+    /// Newline-delimited JSON, one note object per line, streamed in pages
+    Ndjson,
+    /// Markdown document with one section per note
+    Markdown,
+}
+
+/// Number of notes fetched per query when streaming NDJSON.
+const NDJSON_PAGE_SIZE: usize = 500;
+
+/// Exports every note, oldest first, to `writer` in the given format.
+///
+/// JSON and Markdown load every note before writing; NDJSON streams notes a
+/// page at a time so memory stays bounded for large databases.
+///
+/// Returns the number of notes written.
+///
+/// # Examples
+///
+/// ```
+/// use cons::{Database, NoteService};
+/// use cons::export::{ExportFormat, export_notes};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let service = NoteService::new(Database::in_memory()?);
+/// service.create_note("Learning Rust", Some(&["rust"]))?;
+///
+/// let mut output = Vec::new();
+/// let count = export_notes(&service, ExportFormat::Markdown, &mut output)?;
+/// assert_eq!(count, 1);
+/// assert!(String::from_utf8(output)?.contains("Tags: #rust"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_notes<W: Write>(
+    service: &NoteService,
+    format: ExportFormat,
+    writer: &mut W,
+) -> Result<usize> {
+    if format == ExportFormat::Ndjson {
+        let count = write_ndjson(service, writer, NDJSON_PAGE_SIZE)?;
+        writer.flush()?;
+        return Ok(count);
+    }
+
+    let notes = service
+        .list_notes(ListNotesOptions {
+            order: SortOrder::Ascending,
+            ..Default::default()
+        })
+        .context("Failed to load notes for export")?;
+
+    match format {
+        ExportFormat::Json => write_json(&notes, writer)?,
+        ExportFormat::Markdown => write_markdown(&notes, writer)?,
+        ExportFormat::Ndjson => unreachable!("NDJSON is streamed above"),
+    }
+    writer.flush()?;
+
+    Ok(notes.len())
+}
+
+/// Exports the notes carrying `tag`, oldest first, as one Markdown document.
+///
+/// Aliases resolve to their canonical tag, whose name becomes the document's
+/// `#` title. Notes are written as in [`ExportFormat::Markdown`]. A tag with
+/// no notes produces just the title.
+///
+/// Returns the number of notes written.
+///
+/// # Examples
+///
+/// ```
+/// use cons::{Database, NoteService};
+/// use cons::export::export_tag_markdown;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let service = NoteService::new(Database::in_memory()?);
+/// service.create_note("Learning Rust", Some(&["rust"]))?;
+/// service.create_note("Buy milk", Some(&["errands"]))?;
+///
+/// let mut output = Vec::new();
+/// let count = export_tag_markdown(&service, "Rust", &mut output)?;
+/// assert_eq!(count, 1);
+/// assert!(String::from_utf8(output)?.starts_with("# Notes tagged #rust\n"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_tag_markdown<W: Write>(
+    service: &NoteService,
+    tag: &str,
+    writer: &mut W,
+) -> Result<usize> {
+    let tags = service.canonical_tag_names(&[tag.to_string()])?;
+    let notes = service
+        .list_notes(ListNotesOptions {
+            tags: Some(tags.clone()),
+            order: SortOrder::Ascending,
+            ..Default::default()
+        })
+        .context("Failed to load notes for export")?;
+
+    writeln!(writer, "# Notes tagged #{}", tags[0])?;
+    if !notes.is_empty() {
+        writeln!(writer)?;
+        write_markdown(&notes, writer)?;
+    }
+    writer.flush()?;
+
+    Ok(notes.len())
+}
+
+/// Exports the tag hierarchy to `writer` as a Graphviz DOT `digraph`.
+///
+/// Each edge points from the narrower tag to the broader tag and is labeled
+/// with its hierarchy type; partitive (part-of) edges are dashed. Node font
+/// size grows with degree centrality so hub concepts stand out. Render with
+/// e.g. `dot -Tsvg hierarchy.dot -o hierarchy.svg`.
+///
+/// Returns the number of edges written.
+///
+/// # Examples
+///
+/// ```
+/// use cons::{Database, NoteService};
+/// use cons::export::export_hierarchy_dot;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let service = NoteService::new(Database::in_memory()?);
+/// let tokio = service.get_or_create_tag("tokio")?;
+/// let rust = service.get_or_create_tag("rust")?;
+/// service.create_edge(tokio, rust, 0.8, "partitive", None)?;
+///
+/// let mut output = Vec::new();
+/// let count = export_hierarchy_dot(&service, &mut output)?;
+/// assert_eq!(count, 1);
+/// assert!(String::from_utf8(output)?.contains(r#""tokio" -> "rust""#));
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_hierarchy_dot<W: Write>(service: &NoteService, writer: &mut W) -> Result<usize> {
+    let tags = service
+        .list_hierarchy_tags()
+        .context("Failed to load hierarchy tags for export")?;
+    let edges = service
+        .list_edges()
+        .context("Failed to load hierarchy edges for export")?;
+
+    writeln!(writer, "digraph tags {{")?;
+    // Broader tags at the top, narrower tags below them
+    writeln!(writer, "  rankdir=BT;")?;
+    writeln!(writer, "  node [shape=ellipse];")?;
+
+    for (_, name, centrality) in &tags {
+        let centrality = *centrality;
+        let font_size = 10 + 2 * centrality.clamp(0, 10);
+        writeln!(
+            writer,
+            "  \"{}\" [label=\"{} ({})\", fontsize={}];",
+            dot_escape(name),
+            dot_escape(name),
+            centrality,
+            font_size
+        )?;
+    }
+
+    for edge in &edges {
+        let style = if edge.hierarchy_type() == "partitive" {
+            ", style=dashed"
+        } else {
+            ""
+        };
+        writeln!(
+            writer,
+            "  \"{}\" -> \"{}\" [label=\"{}\"{}];",
+            dot_escape(edge.source_name()),
+            dot_escape(edge.target_name()),
+            edge.hierarchy_type(),
+            style
+        )?;
+    }
+
+    writeln!(writer, "}}")?;
+    writer.flush()?;
+
+    Ok(edges.len())
+}
+
+/// Exports the tag co-occurrence network to `writer` as an undirected Graphviz DOT `graph`.
+///
+/// Unlike [`export_hierarchy_dot`], which draws the curated `edges` table, this
+/// graph is derived from usage: two tags are joined when they appear on the
+/// same notes, and the edge weight is the number of notes they share. Pairs
+/// sharing fewer than `min_count` notes are left out. Node font size grows
+/// with degree (the number of tags a tag co-occurs with) so hubs stand out.
+///
+/// Returns the number of edges written.
+///
+/// # Examples
+///
+/// ```
+/// use cons::{Database, NoteService};
+/// use cons::export::export_cooccurrence_dot;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let service = NoteService::new(Database::in_memory()?);
+/// service.create_note("async runtimes", Some(&["rust", "tokio"]))?;
+/// service.create_note("spawning tasks", Some(&["rust", "tokio"]))?;
+///
+/// let mut output = Vec::new();
+/// let count = export_cooccurrence_dot(&service, 2, &mut output)?;
+/// assert_eq!(count, 1);
+/// assert!(String::from_utf8(output)?.contains(r#""rust" -- "tokio""#));
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_cooccurrence_dot<W: Write>(
+    service: &NoteService,
+    min_count: usize,
+    writer: &mut W,
+) -> Result<usize> {
+    use std::collections::BTreeMap;
+
+    let pairs = service
+        .tag_cooccurrence(min_count)
+        .context("Failed to load tag co-occurrence for export")?;
+
+    let mut degrees: BTreeMap<&str, usize> = BTreeMap::new();
+    for (first, second, _) in &pairs {
+        *degrees.entry(first).or_default() += 1;
+        *degrees.entry(second).or_default() += 1;
+    }
+
+    writeln!(writer, "graph tags {{")?;
+    writeln!(writer, "  node [shape=ellipse];")?;
+
+    for (name, degree) in &degrees {
+        let font_size = 10 + 2 * (*degree).min(10);
+        writeln!(
+            writer,
+            "  \"{}\" [label=\"{} ({})\", fontsize={}];",
+            dot_escape(name),
+            dot_escape(name),
+            degree,
+            font_size
+        )?;
+    }
+
+    for (first, second, count) in &pairs {
+        // Heavier pairs are drawn thicker and pulled closer together
+        let pen_width = (*count).min(8);
+        writeln!(
+            writer,
+            "  \"{}\" -- \"{}\" [label=\"{}\", weight={}, penwidth={}];",
+            dot_escape(first),
+            dot_escape(second),
+            count,
+            count,
+            pen_width
+        )?;
+    }
+
+    writeln!(writer, "}}")?;
+    writer.flush()?;
+
+    Ok(pairs.len())
+}
+
+/// Escapes a string for use inside a double-quoted DOT identifier.
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Parses notes from a JSON export produced by [`export_notes`].
+///
+/// The whole document is parsed before returning, so a malformed record
+/// anywhere in the file is reported before anything is imported.
+pub fn read_notes_json<R: Read>(reader: R) -> Result<Vec<Note>> {
+    serde_json::from_reader(reader).context("Failed to parse notes JSON")
+}
+
+/// Writes notes as a pretty-printed JSON array.
+fn write_json<W: Write>(notes: &[Note], writer: &mut W) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, notes).context("Failed to serialize notes")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Streams notes as NDJSON, fetching `page_size` notes per query.
+///
+/// Pages are ordered by creation time with the note id as a tiebreaker, so
+/// offsets are stable and every note is written exactly once.
+fn write_ndjson<W: Write>(
+    service: &NoteService,
+    writer: &mut W,
+    page_size: usize,
+) -> Result<usize> {
+    let mut count = 0;
+    loop {
+        let page = service
+            .list_notes(ListNotesOptions {
+                limit: Some(page_size),
+                offset: Some(count),
+                order: SortOrder::Ascending,
+                ..Default::default()
+            })
+            .context("Failed to load notes for export")?;
+
+        for note in &page {
+            serde_json::to_writer(&mut *writer, note).context("Failed to serialize note")?;
+            writeln!(writer)?;
+        }
+        count += page.len();
+
+        if page.len() < page_size {
+            return Ok(count);
+        }
+    }
+}
+
+/// Writes notes as Markdown sections.
+///
+/// Each section has a `##` heading with the note id and creation time, the
+/// original content, the enhanced content as a blockquote (if any), and a
+/// trailing `Tags:` line (if the note has tags).
+fn write_markdown<W: Write>(notes: &[Note], writer: &mut W) -> Result<()> {
+    for (i, note) in notes.iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+
+        let created = note
+            .created_at()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "Invalid date".to_string());
+        writeln!(writer, "## Note {} ({})", note.id(), created)?;
+        writeln!(writer)?;
+        writeln!(writer, "{}", note.content())?;
+
+        if let Some(enhanced) = note.content_enhanced() {
+            writeln!(writer)?;
+            match note.enhancement_confidence() {
+                Some(confidence) => writeln!(
+                    writer,
+                    "> **Enhanced** ({:.0}% confidence):",
+                    confidence * 100.0
+                )?,
+                None => writeln!(writer, "> **Enhanced:**")?,
+            }
+            for line in enhanced.lines() {
+                writeln!(writer, "> {}", line)?;
+            }
+        }
+
+        if !note.tags().is_empty() {
+            let tags: Vec<String> = note
+                .tags()
+                .iter()
+                .map(|tag| format!("#{}", tag.name()))
+                .collect();
+            writeln!(writer)?;
+            writeln!(writer, "Tags: {}", tags.join(" "))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Database, TagSource};
+
+    fn service_with_notes() -> NoteService {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+        let note = service
+            .create_note("buy milk", Some(&["errands"]))
+            .expect("failed to create note");
+        service
+            .update_note_enhancement(
+                note.id(),
+                "Buy milk from the grocery store.",
+                "deepseek-r1:8b",
+                0.85,
+                time::OffsetDateTime::now_utc(),
+                None,
+            )
+            .expect("failed to enhance note");
+        service
+            .add_tags_to_note(
+                note.id(),
+                &["shopping"],
+                TagSource::llm("deepseek-r1:8b", 80),
+            )
+            .expect("failed to add tags");
+
+        service
+            .create_note("untagged thought", None)
+            .expect("failed to create note");
+
+        service
+    }
+
+    #[test]
+    fn export_json_includes_tags_enhancement_and_timestamps() {
+        let service = service_with_notes();
+
+        let mut output = Vec::new();
+        let count =
+            export_notes(&service, ExportFormat::Json, &mut output).expect("export should succeed");
+        assert_eq!(count, 2);
+
+        let value: serde_json::Value = serde_json::from_slice(&output).expect("valid JSON");
+        let notes = value.as_array().expect("top-level array");
+        assert_eq!(notes.len(), 2);
+
+        let first = &notes[0];
+        assert_eq!(first["content"], "buy milk");
+        assert_eq!(
+            first["content_enhanced"],
+            "Buy milk from the grocery store."
+        );
+        assert_eq!(first["enhancement_model"], "deepseek-r1:8b");
+        assert!(first["created_at"].is_string());
+        assert!(first["updated_at"].is_string());
+        assert_eq!(first["tags"].as_array().map(Vec::len), Some(2));
+        assert_eq!(notes[1]["content"], "untagged thought");
+    }
+
+    #[test]
+    fn export_json_round_trips_to_notes() {
+        let service = service_with_notes();
+
+        let mut output = Vec::new();
+        export_notes(&service, ExportFormat::Json, &mut output).expect("export should succeed");
+
+        let notes: Vec<Note> = serde_json::from_slice(&output).expect("notes deserialize");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].tags().len(), 2);
+    }
+
+    #[test]
+    fn export_markdown_writes_one_section_per_note() {
+        let service = service_with_notes();
+
+        let mut output = Vec::new();
+        export_notes(&service, ExportFormat::Markdown, &mut output).expect("export should succeed");
+        let markdown = String::from_utf8(output).expect("valid UTF-8");
+
+        assert_eq!(markdown.matches("## Note ").count(), 2);
+        assert!(markdown.contains("buy milk\n"));
+        assert!(markdown.contains("> **Enhanced** (85% confidence):"));
+        assert!(markdown.contains("> Buy milk from the grocery store."));
+        assert!(markdown.contains("Tags: #errands #shopping"));
+        assert_eq!(
+            markdown.matches("Tags:").count(),
+            1,
+            "untagged note has no tags line"
+        );
+    }
+
+    #[test]
+    fn export_tag_markdown_includes_only_tagged_notes_oldest_first() {
+        let service = service_with_notes();
+        let tag_id = service
+            .get_or_create_tag("errands")
+            .expect("failed to get tag");
+        service
+            .create_alias("chores", tag_id, "user", 1.0, None)
+            .expect("failed to create alias");
+        service
+            .create_note("post letters", Some(&["errands"]))
+            .expect("failed to create note");
+
+        let mut output = Vec::new();
+        let count =
+            export_tag_markdown(&service, "chores", &mut output).expect("export should succeed");
+        let markdown = String::from_utf8(output).expect("valid UTF-8");
+
+        assert_eq!(count, 2);
+        assert!(markdown.starts_with("# Notes tagged #errands\n\n## Note "));
+        assert_eq!(markdown.matches("## Note ").count(), 2);
+        assert!(!markdown.contains("untagged thought"));
+        assert!(markdown.contains("> Buy milk from the grocery store."));
+
+        let milk = markdown.find("buy milk").expect("first note exported");
+        let letters = markdown.find("post letters").expect("second note exported");
+        assert!(milk < letters, "notes should be ordered oldest first");
+    }
+
+    #[test]
+    fn export_tag_markdown_unknown_tag_writes_only_title() {
+        let service = service_with_notes();
+
+        let mut output = Vec::new();
+        let count =
+            export_tag_markdown(&service, "missing", &mut output).expect("export should succeed");
+
+        assert_eq!(count, 0);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "# Notes tagged #missing\n"
+        );
+    }
+
+    #[test]
+    fn exported_json_imports_into_empty_database() {
+        let service = service_with_notes();
+        let mut output = Vec::new();
+        export_notes(&service, ExportFormat::Json, &mut output).expect("export should succeed");
+
+        let notes = read_notes_json(output.as_slice()).expect("export should parse");
+        let target = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let summary = target
+            .import_notes(&notes, false)
+            .expect("import should succeed");
+        assert_eq!(summary.imported.len(), 2);
+
+        let imported = target
+            .get_note(summary.imported[0])
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(imported.content(), "buy milk");
+        assert_eq!(imported.created_at(), notes[0].created_at());
+        assert_eq!(imported.enhancement_model(), Some("deepseek-r1:8b"));
+        assert_eq!(imported.tags(), notes[0].tags());
+    }
+
+    #[test]
+    fn read_notes_json_rejects_malformed_records() {
+        let json = r#"[{"content": "missing fields"}]"#;
+        assert!(read_notes_json(json.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn export_hierarchy_dot_writes_one_edge_line_per_edge() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let tag = |name: &str| {
+            service
+                .get_or_create_tag(name)
+                .expect("failed to create tag")
+        };
+        let (tokio, rust, language) = (tag("tokio"), tag("rust"), tag("programming-language"));
+        service
+            .create_edge(tokio, rust, 0.8, "partitive", None)
+            .expect("failed to create edge");
+        service
+            .create_edge(rust, language, 0.9, "generic", None)
+            .expect("failed to create edge");
+
+        let mut output = Vec::new();
+        let count = export_hierarchy_dot(&service, &mut output).expect("export should succeed");
+        let dot = String::from_utf8(output).expect("valid UTF-8");
+
+        assert_eq!(count, 2);
+        assert!(dot.starts_with("digraph tags {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(dot.contains(r#""tokio" -> "rust" [label="partitive", style=dashed];"#));
+        assert!(dot.contains(r#""rust" -> "programming-language" [label="generic"];"#));
+        // rust sits on both edges, so it is drawn larger than the leaves
+        assert!(dot.contains(r#""rust" [label="rust (2)", fontsize=14];"#));
+        assert!(dot.contains(r#""tokio" [label="tokio (1)", fontsize=12];"#));
+    }
+
+    #[test]
+    fn export_cooccurrence_dot_weights_edges_and_prunes_weak_pairs() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        for tags in [
+            &["rust", "tokio"][..],
+            &["rust", "tokio", "async"][..],
+            &["rust", "tokio", "async"][..],
+            &["rust", "python"][..],
+        ] {
+            service
+                .create_note("note", Some(tags))
+                .expect("failed to create note");
+        }
+
+        let mut output = Vec::new();
+        let count =
+            export_cooccurrence_dot(&service, 2, &mut output).expect("export should succeed");
+        let dot = String::from_utf8(output).expect("valid UTF-8");
+
+        assert_eq!(count, 3);
+        assert!(dot.starts_with("graph tags {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -- ").count(), 3);
+        assert!(dot.contains(r#""rust" -- "tokio" [label="3", weight=3, penwidth=3];"#));
+        assert!(dot.contains(r#""async" -- "rust" [label="2", weight=2, penwidth=2];"#));
+        // python shares only one note with rust, so it is pruned entirely
+        assert!(!dot.contains("python"));
+        assert!(dot.contains(r#""rust" [label="rust (2)", fontsize=14];"#));
+
+        let mut output = Vec::new();
+        let count =
+            export_cooccurrence_dot(&service, 1, &mut output).expect("export should succeed");
+        assert_eq!(count, 4);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains(r#""python" -- "rust""#)
+        );
+    }
+
+    #[test]
+    fn dot_escape_quotes_and_backslashes() {
+        assert_eq!(dot_escape(r#"say "hi" \ bye"#), r#"say \"hi\" \\ bye"#);
+    }
+
+    #[test]
+    fn export_ndjson_writes_one_object_per_line_across_pages() {
+        let service = service_with_notes();
+        for i in 0..3 {
+            service
+                .create_note(&format!("note {i}\nwith a second line"), None)
+                .expect("failed to create note");
+        }
+
+        let mut output = Vec::new();
+        let count = write_ndjson(&service, &mut output, 2).expect("export should succeed");
+        let text = String::from_utf8(output).expect("valid UTF-8");
+
+        let total: i64 = service
+            .database()
+            .connection()
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .expect("failed to count notes");
+        assert_eq!(count as i64, total);
+        assert_eq!(text.lines().count(), count);
+
+        let notes: Vec<Note> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line is a note object"))
+            .collect();
+        assert_eq!(notes[0].content(), "buy milk");
+        assert_eq!(notes[0].tags().len(), 2);
+        assert_eq!(notes[4].content(), "note 2\nwith a second line");
+    }
+
+    #[test]
+    fn export_ndjson_empty_database_writes_nothing() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+        let mut output = Vec::new();
+        let count = export_notes(&service, ExportFormat::Ndjson, &mut output)
+            .expect("export should succeed");
+        assert_eq!(count, 0);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn export_empty_database() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+        let mut output = Vec::new();
+        let count =
+            export_notes(&service, ExportFormat::Json, &mut output).expect("export should succeed");
+        assert_eq!(count, 0);
+        assert_eq!(String::from_utf8(output).unwrap().trim(), "[]");
+    }
+}
@@ -6,7 +6,7 @@
 
 use std::sync::Arc;
 
-use crate::ollama::{OllamaClientTrait, OllamaError};
+use crate::ollama::{OllamaClientTrait, OllamaError, extract_json_array, generate_json};
 
 /// Prompt template for tag relationship extraction.
 ///
@@ -55,9 +55,18 @@ Output: [
 
 TAGS TO ANALYZE:
 {tags}
-
+{cooccurrence}
 JSON OUTPUT:"#;
 
+/// Default minimum confidence for a suggestion to be returned.
+const DEFAULT_MIN_CONFIDENCE: f64 = 0.7;
+
+/// Maximum number of co-occurring tag pairs listed in the prompt.
+///
+/// Keeps the prompt short on large vocabularies; pairs are taken in the order
+/// given, so callers should pass the most frequent pairs first.
+const MAX_COOCCURRENCE_HINTS: usize = 20;
+
 /// Represents a suggested hierarchical relationship between two tags.
 ///
 /// # Fields
@@ -135,6 +144,8 @@ pub struct RelationshipSuggestion {
 #[derive(Default)]
 pub struct HierarchySuggesterBuilder {
     client: Option<Arc<dyn OllamaClientTrait>>,
+    min_confidence: Option<f64>,
+    cooccurrence: Vec<(String, String, usize)>,
 }
 
 impl HierarchySuggesterBuilder {
@@ -153,6 +164,34 @@ impl HierarchySuggesterBuilder {
         self
     }
 
+    /// Sets the minimum confidence a suggestion needs to be returned.
+    ///
+    /// Suggestions scoring below the threshold are dropped; a suggestion exactly
+    /// at the threshold is kept. Defaults to 0.7. Values outside 0.0-1.0 are clamped.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_confidence` - Threshold from 0.0 to 1.0
+    pub fn min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = Some(min_confidence.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets tag pairs that frequently appear on the same notes.
+    ///
+    /// Tags used together are the likeliest candidates for a hierarchy, so the
+    /// top pairs are listed in the prompt as hints. Pairs are `(tag, tag, note_count)`
+    /// as returned by `NoteService::tag_cooccurrence`, most frequent first. Without
+    /// this, the suggester works from tag names alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - Co-occurring tag pairs with the number of notes sharing them
+    pub fn cooccurrence(mut self, pairs: Vec<(String, String, usize)>) -> Self {
+        self.cooccurrence = pairs;
+        self
+    }
+
     /// Builds the `HierarchySuggester` with the configured settings.
     ///
     /// # Panics
@@ -178,6 +217,8 @@ impl HierarchySuggesterBuilder {
     pub fn build(self) -> HierarchySuggester {
         HierarchySuggester {
             client: self.client.expect("client must be set via client() method"),
+            min_confidence: self.min_confidence.unwrap_or(DEFAULT_MIN_CONFIDENCE),
+            cooccurrence: self.cooccurrence,
         }
     }
 }
@@ -213,7 +254,7 @@ impl HierarchySuggesterBuilder {
 /// let suggestions = suggester.suggest_relationships("deepseek-r1:8b", tags)?;
 ///
 /// // Process the suggestions (Vec<RelationshipSuggestion>)
-/// // Note: Only suggestions with confidence >= 0.7 are returned
+/// // Note: Only suggestions with confidence >= 0.7 (the default minimum) are returned
 /// for suggestion in suggestions {
 ///     println!(
 ///         "{} -> {} ({}, {:.2})",
@@ -251,6 +292,8 @@ impl HierarchySuggesterBuilder {
 /// ```
 pub struct HierarchySuggester {
     client: Arc<dyn OllamaClientTrait>,
+    min_confidence: f64,
+    cooccurrence: Vec<(String, String, usize)>,
 }
 
 impl HierarchySuggester {
@@ -265,7 +308,11 @@ impl HierarchySuggester {
     /// Prefer using `HierarchySuggesterBuilder` for more ergonomic construction.
     #[must_use]
     pub fn new(client: Arc<dyn OllamaClientTrait>) -> Self {
-        Self { client }
+        Self {
+            client,
+            min_confidence: DEFAULT_MIN_CONFIDENCE,
+            cooccurrence: Vec::new(),
+        }
     }
 
     /// Suggests hierarchical relationships for the given tags using the specified model.
@@ -277,8 +324,12 @@ impl HierarchySuggester {
     ///
     /// # Returns
     ///
-    /// Returns a `Vec<RelationshipSuggestion>` containing only suggestions with confidence >= 0.7.
-    /// Returns an empty `Vec` if JSON parsing fails (fail-safe behavior).
+    /// Returns a `Vec<RelationshipSuggestion>` containing only suggestions with confidence at or
+    /// above the minimum (0.7 unless set with `HierarchySuggesterBuilder::min_confidence`).
+    /// Co-occurrence hints set with `HierarchySuggesterBuilder::cooccurrence` are
+    /// included in the prompt for pairs where both tags are being analyzed.
+    /// If the response contains no valid JSON array, the model is asked once more for JSON
+    /// only; if that also fails, an empty `Vec` is returned (fail-safe behavior).
     ///
     /// # Errors
     ///
@@ -324,47 +375,48 @@ impl HierarchySuggester {
         // Format tags as JSON array
         let tags_json = serde_json::to_string(&tag_names).map_err(OllamaError::Serialization)?;
 
-        // Construct prompt with tag names
-        let prompt = PROMPT_TEMPLATE.replace("{tags}", &tags_json);
-
-        // Call LLM
-        let response = self.client.generate(model, &prompt)?;
+        // Construct prompt with tag names and any co-occurrence hints
+        let prompt = PROMPT_TEMPLATE
+            .replace("{tags}", &tags_json)
+            .replace("{cooccurrence}", &self.cooccurrence_section(&tag_names));
 
-        // Extract JSON from response (handles various output formats)
-        let Some(json_str) = extract_json(&response) else {
+        // Call LLM and extract JSON (handles fences, prose, trailing commas;
+        // re-prompts once if the response has no usable JSON)
+        let Some(json_str) =
+            generate_json(self.client.as_ref(), model, &prompt, extract_json_array)?
+        else {
             return Ok(Vec::new()); // Fail-safe: empty on extraction failure
         };
 
         // Parse and filter suggestions
-        Ok(parse_suggestions(&json_str))
+        Ok(parse_suggestions(&json_str, self.min_confidence))
     }
-}
 
-/// Extracts JSON array from model response, handling various output formats.
-///
-/// Handles:
-/// - Clean JSON response (no wrapping)
-/// - Markdown code block wrapping (```json ... ```)
-/// - Explanatory text before/after JSON
-///
-/// # Arguments
-///
-/// * `response` - The raw model response text
-///
-/// # Returns
-///
-/// Returns `Some(String)` containing the extracted JSON array, or `None` if no JSON found.
-fn extract_json(response: &str) -> Option<String> {
-    let trimmed = response.trim();
-
-    // Try to find JSON array boundaries
-    let start = trimmed.find('[')?;
-    let end = trimmed.rfind(']')?;
+    /// Formats the co-occurrence hints for the prompt.
+    ///
+    /// Only pairs where both tags are in `tag_names` are listed, up to
+    /// `MAX_COOCCURRENCE_HINTS`. Returns an empty string when there are none,
+    /// leaving the name-only prompt unchanged.
+    fn cooccurrence_section(&self, tag_names: &[String]) -> String {
+        let hints: Vec<String> = self
+            .cooccurrence
+            .iter()
+            .filter(|(a, b, _)| tag_names.contains(a) && tag_names.contains(b))
+            .take(MAX_COOCCURRENCE_HINTS)
+            .map(|(a, b, count)| {
+                let noun = if *count == 1 { "note" } else { "notes" };
+                format!("- {a} + {b} ({count} {noun})")
+            })
+            .collect();
+
+        if hints.is_empty() {
+            return String::new();
+        }
 
-    if start <= end {
-        Some(trimmed[start..=end].to_string())
-    } else {
-        None
+        format!(
+            "\nTAGS OFTEN USED TOGETHER (shared notes; relationships are most likely among these pairs):\n{}\n",
+            hints.join("\n")
+        )
     }
 }
 
@@ -373,21 +425,22 @@ fn extract_json(response: &str) -> Option<String> {
 /// # Arguments
 ///
 /// * `json_str` - JSON array string to parse
+/// * `min_confidence` - Minimum confidence for a suggestion to be kept
 ///
 /// # Returns
 ///
 /// Returns a `Vec<RelationshipSuggestion>` with:
 /// - Confidence scores clamped to 0.0-1.0 range
-/// - Only suggestions with confidence >= 0.7
+/// - Only suggestions with confidence >= `min_confidence`
 ///
 /// Returns an empty `Vec` if parsing fails (fail-safe behavior).
 ///
 /// # Filtering
 ///
 /// - Clamps confidence scores to 0.0-1.0 range
-/// - Filters out suggestions with confidence < 0.7
+/// - Filters out suggestions with confidence < `min_confidence`
 /// - Filters out malformed suggestions (missing fields, invalid types)
-fn parse_suggestions(json_str: &str) -> Vec<RelationshipSuggestion> {
+fn parse_suggestions(json_str: &str, min_confidence: f64) -> Vec<RelationshipSuggestion> {
     // Parse JSON
     let json_value: serde_json::Value = match serde_json::from_str(json_str) {
         Ok(v) => v,
@@ -424,7 +477,7 @@ fn parse_suggestions(json_str: &str) -> Vec<RelationshipSuggestion> {
         let confidence = confidence.clamp(0.0, 1.0);
 
         // Filter by confidence threshold
-        if confidence < 0.7 {
+        if confidence < min_confidence {
             continue;
         }
 
@@ -469,6 +522,92 @@ mod tests {
         assert!(PROMPT_TEMPLATE.contains("attention"));
     }
 
+    struct PromptCapturingClient {
+        prompts: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl OllamaClientTrait for PromptCapturingClient {
+        fn generate(&self, _model: &str, prompt: &str) -> Result<String, OllamaError> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            Ok("[]".to_string())
+        }
+    }
+
+    fn capturing_client() -> Arc<PromptCapturingClient> {
+        Arc::new(PromptCapturingClient {
+            prompts: std::sync::Mutex::new(Vec::new()),
+        })
+    }
+
+    #[test]
+    fn test_prompt_includes_cooccurrence_hints() {
+        let client = capturing_client();
+        let suggester = HierarchySuggesterBuilder::new()
+            .client(client.clone())
+            .cooccurrence(vec![
+                ("rust".to_string(), "tokio".to_string(), 5),
+                ("async".to_string(), "tokio".to_string(), 1),
+                ("haskell".to_string(), "rust".to_string(), 3),
+            ])
+            .build();
+
+        suggester
+            .suggest_relationships(
+                "test-model",
+                vec!["rust".into(), "tokio".into(), "async".into()],
+            )
+            .expect("suggestion should succeed");
+
+        let prompts = client.prompts.lock().unwrap();
+        let prompt = &prompts[0];
+        assert!(prompt.contains("TAGS OFTEN USED TOGETHER"));
+        assert!(prompt.contains("- rust + tokio (5 notes)"));
+        assert!(prompt.contains("- async + tokio (1 note)"));
+        // Pairs involving tags outside the analyzed set are left out
+        assert!(!prompt.contains("haskell"));
+        assert!(prompt.ends_with("(1 note)\n\nJSON OUTPUT:"));
+    }
+
+    #[test]
+    fn test_prompt_without_cooccurrence_uses_names_only() {
+        let client = capturing_client();
+        let suggester = HierarchySuggester::new(client.clone());
+
+        suggester
+            .suggest_relationships("test-model", vec!["rust".into(), "tokio".into()])
+            .expect("suggestion should succeed");
+
+        let prompts = client.prompts.lock().unwrap();
+        assert!(!prompts[0].contains("TAGS OFTEN USED TOGETHER"));
+        assert!(prompts[0].ends_with("TAGS TO ANALYZE:\n[\"rust\",\"tokio\"]\n\nJSON OUTPUT:"));
+    }
+
+    #[test]
+    fn test_cooccurrence_hints_are_capped() {
+        let tags: Vec<String> = (0..30).map(|i| format!("tag-{i:02}")).collect();
+        let pairs = tags
+            .windows(2)
+            .map(|w| (w[0].clone(), w[1].clone(), 2))
+            .collect();
+
+        let client = capturing_client();
+        let suggester = HierarchySuggesterBuilder::new()
+            .client(client.clone())
+            .cooccurrence(pairs)
+            .build();
+
+        suggester
+            .suggest_relationships("test-model", tags)
+            .expect("suggestion should succeed");
+
+        let prompts = client.prompts.lock().unwrap();
+        let hint_lines = prompts[0]
+            .lines()
+            .filter(|l| l.starts_with("- tag-"))
+            .count();
+        assert_eq!(hint_lines, MAX_COOCCURRENCE_HINTS);
+    }
+
     #[test]
     fn test_suggest_relationships_returns_parsed_suggestions() {
         let mock = MockOllamaClient {
@@ -509,11 +648,11 @@ mod tests {
     {"source_tag": "rust", "target_tag": "programming-language", "hierarchy_type": "generic", "confidence": 0.95}
 ]
 ```"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_array(response);
 
         assert!(extracted.is_some());
         let json = extracted.unwrap();
-        let suggestions = parse_suggestions(&json);
+        let suggestions = parse_suggestions(&json, DEFAULT_MIN_CONFIDENCE);
 
         assert_eq!(suggestions.len(), 1);
         assert_eq!(suggestions[0].source_tag, "rust");
@@ -527,11 +666,11 @@ mod tests {
 ]
 
 I hope this helps!"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_array(response);
 
         assert!(extracted.is_some());
         let json = extracted.unwrap();
-        let suggestions = parse_suggestions(&json);
+        let suggestions = parse_suggestions(&json, DEFAULT_MIN_CONFIDENCE);
 
         assert_eq!(suggestions.len(), 1);
         assert_eq!(suggestions[0].source_tag, "python");
@@ -544,7 +683,7 @@ I hope this helps!"#;
             {"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": 0.7},
             {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 0.95}
         ]"#;
-        let suggestions = parse_suggestions(json_high);
+        let suggestions = parse_suggestions(json_high, DEFAULT_MIN_CONFIDENCE);
         assert_eq!(suggestions.len(), 2);
 
         // Test that suggestions with confidence < 0.7 are filtered out
@@ -552,7 +691,7 @@ I hope this helps!"#;
             {"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": 0.69},
             {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 0.5}
         ]"#;
-        let suggestions = parse_suggestions(json_low);
+        let suggestions = parse_suggestions(json_low, DEFAULT_MIN_CONFIDENCE);
         assert_eq!(suggestions.len(), 0);
 
         // Test mixed confidence values
@@ -561,7 +700,7 @@ I hope this helps!"#;
             {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 0.65},
             {"source_tag": "e", "target_tag": "f", "hierarchy_type": "partitive", "confidence": 0.8}
         ]"#;
-        let suggestions = parse_suggestions(json_mixed);
+        let suggestions = parse_suggestions(json_mixed, DEFAULT_MIN_CONFIDENCE);
         assert_eq!(suggestions.len(), 2);
         assert_eq!(suggestions[0].source_tag, "a");
         assert_eq!(suggestions[1].source_tag, "e");
@@ -571,22 +710,22 @@ I hope this helps!"#;
     fn test_fail_safe_behavior_returns_empty_vec_on_parse_failure() {
         // Test with invalid JSON
         let invalid_json = "This is not JSON at all";
-        let suggestions = parse_suggestions(invalid_json);
+        let suggestions = parse_suggestions(invalid_json, DEFAULT_MIN_CONFIDENCE);
         assert!(suggestions.is_empty());
 
         // Test with extraction failure
         let no_json = "No square brackets here";
-        let extracted = extract_json(no_json);
+        let extracted = extract_json_array(no_json);
         assert!(extracted.is_none());
 
         // Test with malformed JSON object
         let malformed = r#"[{"incomplete": "object"}]"#;
-        let suggestions = parse_suggestions(malformed);
+        let suggestions = parse_suggestions(malformed, DEFAULT_MIN_CONFIDENCE);
         assert!(suggestions.is_empty());
 
         // Test with non-array JSON
         let not_array = r#"{"key": "value"}"#;
-        let suggestions = parse_suggestions(not_array);
+        let suggestions = parse_suggestions(not_array, DEFAULT_MIN_CONFIDENCE);
         assert!(suggestions.is_empty());
     }
 
@@ -597,7 +736,7 @@ I hope this helps!"#;
             {"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": 1.5},
             {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 2.0}
         ]"#;
-        let suggestions = parse_suggestions(json_high);
+        let suggestions = parse_suggestions(json_high, DEFAULT_MIN_CONFIDENCE);
         assert_eq!(suggestions.len(), 2);
         assert_eq!(suggestions[0].confidence, 1.0);
         assert_eq!(suggestions[1].confidence, 1.0);
@@ -605,7 +744,7 @@ I hope this helps!"#;
         let json_low = r#"[
             {"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": -0.5}
         ]"#;
-        let suggestions = parse_suggestions(json_low);
+        let suggestions = parse_suggestions(json_low, DEFAULT_MIN_CONFIDENCE);
         // This should be filtered out because after clamping to 0.0, it's < 0.7
         assert_eq!(suggestions.len(), 0);
 
@@ -615,7 +754,7 @@ I hope this helps!"#;
             {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 1.0},
             {"source_tag": "e", "target_tag": "f", "hierarchy_type": "partitive", "confidence": 0.85}
         ]"#;
-        let suggestions = parse_suggestions(json_valid);
+        let suggestions = parse_suggestions(json_valid, DEFAULT_MIN_CONFIDENCE);
         assert_eq!(suggestions.len(), 3);
         assert_eq!(suggestions[0].confidence, 0.7);
         assert_eq!(suggestions[1].confidence, 1.0);
@@ -711,7 +850,7 @@ These represent clear hierarchical relationships."#
     #[test]
     fn test_extract_json_handles_nested_arrays() {
         let response = r#"[[{"inner": "array"}], [{"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": 0.9}]]"#;
-        let extracted = extract_json(response);
+        let extracted = extract_json_array(response);
 
         assert!(extracted.is_some());
         // Should extract the outermost brackets
@@ -727,11 +866,105 @@ These represent clear hierarchical relationships."#
             {"source_tag": "e", "target_tag": "f", "hierarchy_type": "generic", "confidence": "not-a-number"},
             {"source_tag": "g", "target_tag": "h", "hierarchy_type": "generic", "confidence": 0.8}
         ]"#;
-        let suggestions = parse_suggestions(json);
+        let suggestions = parse_suggestions(json, DEFAULT_MIN_CONFIDENCE);
 
         // Only first and last objects are valid and meet confidence threshold
         assert_eq!(suggestions.len(), 2);
         assert_eq!(suggestions[0].source_tag, "a");
         assert_eq!(suggestions[1].source_tag, "g");
     }
+
+    #[test]
+    fn test_suggest_relationships_parses_fenced_output_with_trailing_comma() {
+        let mock = MockOllamaClient {
+            response: r#"Here you go:
+```json
+[
+    {"source_tag": "tokio", "target_tag": "rust", "hierarchy_type": "partitive", "confidence": 0.8},
+]
+```"#
+                .to_string(),
+        };
+        let suggester = HierarchySuggester::new(Arc::new(mock));
+
+        let suggestions = suggester
+            .suggest_relationships("test-model", vec!["tokio".into(), "rust".into()])
+            .expect("suggestion should succeed");
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].source_tag, "tokio");
+        assert_eq!(suggestions[0].target_tag, "rust");
+    }
+
+    #[test]
+    fn test_suggest_relationships_reprompts_once_for_valid_json() {
+        struct ProseThenJsonClient {
+            calls: std::sync::Mutex<usize>,
+        }
+
+        impl OllamaClientTrait for ProseThenJsonClient {
+            fn generate(&self, _model: &str, prompt: &str) -> Result<String, OllamaError> {
+                let mut calls = self.calls.lock().unwrap();
+                *calls += 1;
+                if *calls == 1 {
+                    Ok("Rust is broader than tokio.".to_string())
+                } else {
+                    assert!(prompt.contains("Return only valid JSON"));
+                    Ok(r#"[{"source_tag": "tokio", "target_tag": "rust", "hierarchy_type": "partitive", "confidence": 0.8}]"#.to_string())
+                }
+            }
+        }
+
+        let client = Arc::new(ProseThenJsonClient {
+            calls: std::sync::Mutex::new(0),
+        });
+        let suggester = HierarchySuggester::new(client.clone());
+
+        let suggestions = suggester
+            .suggest_relationships("test-model", vec!["tokio".into(), "rust".into()])
+            .expect("suggestion should succeed");
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(*client.calls.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_min_confidence_threshold_is_honored_at_boundary() {
+        let mock = MockOllamaClient {
+            response: r#"[
+                {"source_tag": "a", "target_tag": "b", "hierarchy_type": "generic", "confidence": 0.5},
+                {"source_tag": "c", "target_tag": "d", "hierarchy_type": "generic", "confidence": 0.49},
+                {"source_tag": "e", "target_tag": "f", "hierarchy_type": "partitive", "confidence": 0.6},
+                {"source_tag": "g", "target_tag": "h", "hierarchy_type": "generic", "confidence": 0.95}
+            ]"#
+            .to_string(),
+        };
+        let client: Arc<dyn OllamaClientTrait> = Arc::new(mock);
+        let tags = || vec!["a".to_string(), "b".to_string()];
+
+        let suggester = HierarchySuggesterBuilder::new()
+            .client(Arc::clone(&client))
+            .min_confidence(0.5)
+            .build();
+        let suggestions = suggester
+            .suggest_relationships("test-model", tags())
+            .expect("suggestion should succeed");
+        let sources: Vec<&str> = suggestions.iter().map(|s| s.source_tag.as_str()).collect();
+        assert_eq!(sources, vec!["a", "e", "g"]);
+
+        let suggester = HierarchySuggesterBuilder::new()
+            .client(Arc::clone(&client))
+            .min_confidence(0.95)
+            .build();
+        let suggestions = suggester
+            .suggest_relationships("test-model", tags())
+            .expect("suggestion should succeed");
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].source_tag, "g");
+
+        // Default threshold stays 0.7
+        let suggester = HierarchySuggesterBuilder::new().client(client).build();
+        let suggestions = suggester
+            .suggest_relationships("test-model", tags())
+            .expect("suggestion should succeed");
+        assert_eq!(suggestions.len(), 1);
+    }
 }
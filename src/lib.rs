@@ -1,11 +1,14 @@
 pub mod answerer;
 pub mod autotagger;
+pub mod config;
 pub mod db;
 pub mod doctor;
 pub mod enhancer;
+pub mod export;
 pub mod hierarchy;
 pub mod models;
 pub mod ollama;
+pub mod progress;
 pub mod service;
 pub mod spreading_activation;
 pub mod tui;
@@ -13,16 +16,27 @@ pub mod utils;
 
 pub use answerer::{Citation, QueryAnswerer, QueryAnswererBuilder, QueryResult, QueryType};
 pub use autotagger::{AutoTagger, AutoTaggerBuilder, TagNormalizer};
-pub use db::Database;
+pub use config::{Config, ConfigEntry, ConfigSource};
+pub use db::{Database, FtsRebuild, FtsTokenizer};
 pub use enhancer::{EnhancementResult, NoteEnhancer, NoteEnhancerBuilder};
+pub use export::{
+    ExportFormat, export_cooccurrence_dot, export_hierarchy_dot, export_notes, export_tag_markdown,
+    read_notes_json,
+};
 pub use hierarchy::{HierarchySuggester, HierarchySuggesterBuilder, RelationshipSuggestion};
-pub use models::{AliasInfo, Note, NoteBuilder, NoteId, Tag, TagAssignment, TagId, TagSource};
-pub use ollama::{OllamaClient, OllamaClientBuilder, OllamaClientTrait, OllamaError};
+pub use models::{
+    AliasInfo, EdgeInfo, Note, NoteBuilder, NoteId, Tag, TagAssignment, TagId, TagSource,
+};
+pub use ollama::{GenOptions, OllamaClient, OllamaClientBuilder, OllamaClientTrait, OllamaError};
 pub use service::{
-    DualSearchConfig, DualSearchMetadata, DualSearchResult, ListNotesOptions, NoteService,
-    QueryExpansionConfig, SearchResult, SortOrder,
+    AliasImportSummary, DualSearchConfig, DualSearchMetadata, DualSearchResult, ImportSummary,
+    InvalidInput, ListNotesOptions, NotFound, NoteService, OrderBy, QueryExpansionConfig,
+    SearchFilters, SearchResult, SortOrder, SourceFilter,
+};
+pub use utils::{
+    content_hash, ensure_database_directory, get_database_path, get_tag_names,
+    resolve_database_path,
 };
-pub use utils::{ensure_database_directory, get_database_path, get_tag_names};
 
 #[cfg(test)]
 mod tests {
@@ -1,12 +1,18 @@
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use cons::{
-    Database, NoteId, NoteService, TagId, TagSource, answerer::QueryAnswererBuilder,
-    autotagger::AutoTaggerBuilder, enhancer::NoteEnhancerBuilder, ensure_database_directory,
-    get_database_path, get_tag_names, hierarchy::HierarchySuggesterBuilder,
-    ollama::OllamaClientBuilder,
+    Config, Database, EnhancementResult, ExportFormat, InvalidInput, NotFound, NoteId, NoteService,
+    OrderBy, SourceFilter, TagSource,
+    answerer::QueryAnswererBuilder,
+    autotagger::AutoTaggerBuilder,
+    enhancer::NoteEnhancerBuilder,
+    ensure_database_directory, get_tag_names,
+    hierarchy::HierarchySuggesterBuilder,
+    ollama::{OllamaClient, OllamaClientBuilder, OllamaClientTrait},
+    progress::Progress,
+    resolve_database_path,
 };
 
 /// cons - structure-last personal knowledge management CLI
@@ -15,6 +21,14 @@ use cons::{
 #[command(about = "A structure-last personal knowledge management tool")]
 #[command(version)]
 struct Cli {
+    /// Print machine-readable JSON instead of human-readable output
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Database file to use (overrides CONS_DB_PATH and the default location)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -24,60 +38,309 @@ struct Cli {
 enum Commands {
     /// Add a new note with optional tags
     Add(AddCommand),
+    /// Edit the content of an existing note
+    Edit(EditCommand),
+    /// Regenerate LLM tags for an existing note
+    Retag(RetagCommand),
+    /// Remove a single tag from a note
+    Untag(UntagCommand),
+    /// Generate (or regenerate) enhanced content for a note
+    Reenhance(ReenhanceCommand),
+    /// Delete a note by ID
+    Delete(DeleteCommand),
+    /// Fold one note into another, then delete it
+    Merge(MergeCommand),
+    /// Show full details of a single note
+    Show(ShowCommand),
     /// List notes with optional filtering and pagination
     List(ListCommand),
     /// Search notes by content, enhanced content, and tags
     Search(SearchCommand),
     /// Search notes using graph-based spreading activation
     GraphSearch(GraphSearchCommand),
+    /// Find notes related to a note through the tag graph
+    Related(RelatedCommand),
     /// Ask a natural language question about your notes
     Ask(AskCommand),
     /// Manage tags
+    #[command(alias = "tag")]
     Tags(TagsCommand),
     /// Manage tag aliases
     TagAlias(TagAliasCommand),
+    /// Manage named tag sets for `cons add --preset`
+    TagPreset(TagPresetCommand),
     /// Manage tag hierarchy
     Hierarchy(HierarchyCommand),
+    /// Export all notes to JSON, NDJSON, or Markdown
+    Export(ExportCommand),
+    /// Import notes from a JSON export
+    Import(ImportCommand),
+    /// Write a consistent snapshot of the database
+    Backup(BackupCommand),
+    /// Check or compact the database file
+    Maintenance(MaintenanceCommand),
     /// Launch interactive terminal UI
     Tui,
     /// Health check and maintenance utilities
     Doctor(DoctorCommand),
+    /// Show every setting read from the environment and where its value came from
+    Config,
+    /// Print a shell completion script to stdout
+    Completions(CompletionsCommand),
 }
 
 /// Add a new note
 #[derive(Parser)]
 struct AddCommand {
-    /// The content of the note (opens $EDITOR if not provided)
+    /// The content of the note (opens $EDITOR if not provided and stdin is a terminal,
+    /// reads stdin if `-` or piped)
+    ///
+    /// To save a note whose content is literally `-`, pipe it in: `echo - | cons add -`
     #[arg(value_name = "CONTENT")]
     content: Option<String>,
 
+    /// Read the note from a UTF-8 text file instead (tagged with the file name
+    /// stem unless --tags is given)
+    #[arg(long, value_name = "PATH", conflicts_with = "content")]
+    from_file: Option<std::path::PathBuf>,
+
     /// Comma-separated tags to apply to the note
     #[arg(short, long, value_name = "TAGS")]
     tags: Option<String>,
+
+    /// Apply the tags of a saved preset (see `cons tag-preset`), combined with any --tags
+    #[arg(long, value_name = "NAME")]
+    preset: Option<String>,
+
+    /// Skip LLM enhancement and auto-tagging (default from CONS_NO_AI=1)
+    #[arg(long)]
+    no_ai: bool,
+
+    /// Skip creation if a note with the same trimmed content exists (default from CONS_DEDUPE=1)
+    #[arg(long)]
+    dedupe: bool,
+
+    /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+    #[arg(long, value_name = "MODEL")]
+    model: Option<String>,
+
+    /// Show the enhancement and tags the AI would produce without saving anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
-/// List notes with optional filtering
+/// Edit the content of an existing note
+#[derive(Parser)]
+struct EditCommand {
+    /// The ID of the note to edit
+    #[arg(value_name = "ID")]
+    id: i64,
+
+    /// The new content of the note
+    #[arg(value_name = "CONTENT")]
+    content: String,
+}
+
+/// Regenerate LLM tags for an existing note
+#[derive(Parser)]
+struct RetagCommand {
+    /// The ID of the note to retag
+    #[arg(
+        value_name = "ID",
+        required_unless_present = "all",
+        conflicts_with = "all"
+    )]
+    id: Option<i64>,
+
+    /// Retag every note that has no tags
+    #[arg(long)]
+    all: bool,
+
+    /// Remove existing LLM tags before retagging (user tags are always kept)
+    #[arg(long)]
+    replace: bool,
+
+    /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+    #[arg(long, value_name = "MODEL")]
+    model: Option<String>,
+}
+
+/// Remove a single tag from a note
+#[derive(Parser)]
+struct UntagCommand {
+    /// The ID of the note to untag
+    #[arg(value_name = "ID")]
+    id: i64,
+
+    /// The tag to remove (aliases resolve to their canonical tag)
+    #[arg(value_name = "TAG")]
+    tag: String,
+}
+
+/// Generate (or regenerate) enhanced content for a note
+#[derive(Parser)]
+struct ReenhanceCommand {
+    /// The ID of the note to enhance
+    #[arg(
+        value_name = "ID",
+        required_unless_present = "all_unenhanced",
+        conflicts_with = "all_unenhanced"
+    )]
+    id: Option<i64>,
+
+    /// Enhance every note that has no enhanced content yet
+    #[arg(long)]
+    all_unenhanced: bool,
+
+    /// Re-enhance the note even if it already has enhanced content
+    #[arg(long, conflicts_with = "all_unenhanced")]
+    force: bool,
+
+    /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+    #[arg(long, value_name = "MODEL")]
+    model: Option<String>,
+}
+
+/// Delete a note by ID
+#[derive(Parser)]
+struct DeleteCommand {
+    /// The ID of the note to delete
+    #[arg(value_name = "ID")]
+    id: i64,
+
+    /// Skip the confirmation prompt
+    #[arg(short, long)]
+    yes: bool,
+}
+
+/// Fold one note into another
 #[derive(Parser)]
+struct MergeCommand {
+    /// The ID of the note to keep
+    #[arg(value_name = "KEEP")]
+    keep: i64,
+
+    /// The ID of the note to fold in and delete
+    #[arg(value_name = "MERGE")]
+    merge: i64,
+}
+
+/// Show full details of a single note
+#[derive(Parser)]
+struct ShowCommand {
+    /// The ID of the note to show
+    #[arg(value_name = "ID")]
+    id: i64,
+
+    /// Also show the prompt that produced the enhancement
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// List notes with optional filtering
+#[derive(Parser, Default)]
 struct ListCommand {
-    /// Maximum number of notes to display
+    /// Maximum number of notes to display (default: 10, 0 for no limit)
     #[arg(short, long, value_name = "LIMIT")]
     limit: Option<usize>,
 
+    /// Show every matching note (same as --limit 0)
+    #[arg(long, conflicts_with_all = ["limit", "page"])]
+    all: bool,
+
     /// Filter by comma-separated tags (AND logic)
     #[arg(short, long, value_name = "TAGS")]
     tags: Option<String>,
+
+    /// Filter by notes with any tag containing this text, case-insensitive
+    /// (cannot be combined with --tags)
+    #[arg(long, value_name = "PATTERN", conflicts_with = "tags")]
+    tag_like: Option<String>,
+
+    /// Only show notes created on or after this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only show notes created on or before this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Only show notes by tag source: all, user (no LLM tags), or llm (any LLM tag)
+    #[arg(long, value_enum, value_name = "SOURCE", default_value_t = SourceArg::All)]
+    source: SourceArg,
+
+    /// Number of results to skip (for paging through older results)
+    #[arg(long, value_name = "OFFSET", conflicts_with = "page")]
+    offset: Option<usize>,
+
+    /// Page of results to show, starting at 1 (sugar for --offset (PAGE-1)*LIMIT)
+    #[arg(long, value_name = "PAGE", value_parser = clap::value_parser!(u64).range(1..))]
+    page: Option<u64>,
+
+    /// Only show notes with LLM tags you have not verified yet (see `cons tags verify`)
+    #[arg(long)]
+    unverified_only: bool,
+
+    /// Only show notes with no tags (candidates for `cons retag --all`)
+    #[arg(long, conflicts_with_all = ["tags", "tag_like", "unverified_only"])]
+    untagged: bool,
+
+    /// Only show notes listed after the note with this ID (pass the last ID of
+    /// the previous page to fetch the next one, unaffected by newly added notes)
+    #[arg(long, value_name = "ID")]
+    before: Option<i64>,
+
+    /// Order notes by when they were created or last edited
+    #[arg(long, value_enum, value_name = "FIELD", default_value_t = OrderByArg::Created)]
+    by: OrderByArg,
+
+    /// Show full note content instead of an excerpt
+    #[arg(long, conflicts_with = "truncate")]
+    full: bool,
+
+    /// Shorten note content to N characters, plus "..." when cut (default: 200)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    truncate: Option<u64>,
 }
 
 /// Search notes by content, enhanced content, and tags
-#[derive(Parser)]
+#[derive(Parser, Default)]
 struct SearchCommand {
-    /// The search query
+    /// The search query (wrap in double quotes, e.g. '"rust programming"', for an exact phrase)
     #[arg(value_name = "QUERY")]
     query: String,
 
     /// Maximum number of results to display (default: 10)
     #[arg(short, long, value_name = "LIMIT")]
     limit: Option<usize>,
+
+    /// Only show notes created on or after this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only show notes created on or before this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Only show notes by tag source: all, user (no LLM tags), or llm (any LLM tag)
+    #[arg(long, value_enum, value_name = "SOURCE", default_value_t = SourceArg::All)]
+    source: SourceArg,
+
+    /// Number of results to skip (for paging through older results)
+    #[arg(long, value_name = "OFFSET", conflicts_with = "page")]
+    offset: Option<usize>,
+
+    /// Page of results to show, starting at 1 (sugar for --offset (PAGE-1)*LIMIT)
+    #[arg(long, value_name = "PAGE", value_parser = clap::value_parser!(u64).range(1..))]
+    page: Option<u64>,
+
+    /// Use graph-based spreading activation instead of keyword search
+    #[arg(long)]
+    graph: bool,
+
+    /// Only show notes carrying all of these comma-separated tags
+    #[arg(short, long, value_name = "TAGS")]
+    tags: Option<String>,
 }
 
 /// Search notes using graph-based spreading activation
@@ -90,6 +353,22 @@ struct GraphSearchCommand {
     /// Maximum number of results to display (default: 10)
     #[arg(short, long, value_name = "LIMIT")]
     limit: Option<usize>,
+
+    /// Show the activation paths from the query's tags that surfaced each note
+    #[arg(long)]
+    explain: bool,
+}
+
+/// Find notes related to a note through the tag graph
+#[derive(Parser)]
+struct RelatedCommand {
+    /// The ID of the note to start from
+    #[arg(value_name = "ID")]
+    id: i64,
+
+    /// Maximum number of results to display (default: 10)
+    #[arg(short, long, value_name = "LIMIT")]
+    limit: Option<usize>,
 }
 
 /// Ask a natural language question about your notes
@@ -100,19 +379,91 @@ struct AskCommand {
     query: String,
 
     /// Maximum number of notes to retrieve for context (default: 10)
-    #[arg(short = 'k', long, value_name = "TOP_K", default_value = "10")]
+    #[arg(
+        short = 'k',
+        long,
+        visible_alias = "limit",
+        value_name = "TOP_K",
+        default_value = "10"
+    )]
     top_k: usize,
 
     /// Include detailed citation information in output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+    #[arg(long, value_name = "MODEL")]
+    model: Option<String>,
 }
 
 /// Manage tags
 #[derive(Parser)]
 struct TagsCommand {
     #[command(subcommand)]
-    command: TagsCommands,
+    command: Option<TagsCommands>,
+
+    /// Sort order when listing tags with counts
+    #[arg(short, long, value_enum, default_value_t = TagSort::Count)]
+    sort: TagSort,
+
+    /// Maximum number of tags to display
+    #[arg(short, long, value_name = "LIMIT")]
+    limit: Option<usize>,
+
+    /// Show each tag's description
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+/// Sort order for the tags listing
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum TagSort {
+    /// Most-used tags first
+    Count,
+    /// Alphabetical by tag name
+    Name,
+}
+
+/// Tag source filter for list and search
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+enum SourceArg {
+    /// Notes regardless of tag source
+    #[default]
+    All,
+    /// Notes whose tags are all user-assigned
+    User,
+    /// Notes with at least one LLM-assigned tag
+    Llm,
+}
+
+impl From<SourceArg> for SourceFilter {
+    fn from(arg: SourceArg) -> Self {
+        match arg {
+            SourceArg::All => SourceFilter::All,
+            SourceArg::User => SourceFilter::User,
+            SourceArg::Llm => SourceFilter::Llm,
+        }
+    }
+}
+
+/// Timestamp to order `list` output by
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+enum OrderByArg {
+    /// When the note was captured
+    #[default]
+    Created,
+    /// When the note's content was last edited
+    Updated,
+}
+
+impl From<OrderByArg> for OrderBy {
+    fn from(arg: OrderByArg) -> Self {
+        match arg {
+            OrderByArg::Created => OrderBy::CreatedAt,
+            OrderByArg::Updated => OrderBy::UpdatedAt,
+        }
+    }
 }
 
 /// Tag subcommands
@@ -120,6 +471,106 @@ struct TagsCommand {
 enum TagsCommands {
     /// List all tags with statistics
     List,
+    /// Rename a tag, merging it into an existing tag if the new name is taken
+    Rename {
+        /// The current tag name
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// The new tag name
+        #[arg(value_name = "NEW")]
+        new: String,
+    },
+    /// Preview the tags the model would suggest for some text, without saving anything
+    Suggest {
+        /// The text to tag (reads stdin if `-`)
+        #[arg(value_name = "TEXT")]
+        text: String,
+
+        /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+    },
+    /// Mark a tag on a note as reviewed and correct
+    Verify {
+        /// The ID of the note
+        #[arg(value_name = "ID")]
+        id: i64,
+
+        /// The tag to verify (aliases resolve to their canonical tag)
+        #[arg(value_name = "TAG")]
+        tag: String,
+    },
+    /// Attach a description to a tag (an empty description clears it)
+    Describe {
+        /// The tag to describe (aliases resolve to their canonical tag)
+        #[arg(value_name = "TAG")]
+        tag: String,
+
+        /// The description text
+        #[arg(value_name = "TEXT")]
+        text: String,
+    },
+    /// Add tags to every note that already carries another tag
+    AddBulk {
+        /// Comma-separated tags to add
+        #[arg(long, value_name = "TAGS")]
+        tags: String,
+
+        /// Only notes carrying this tag are updated (aliases resolve to their canonical tag)
+        #[arg(long, value_name = "TAG")]
+        where_tag: String,
+    },
+    /// Show pairs of tags that are often used together on the same note
+    Cooccur {
+        /// Only show pairs that share at least this many notes
+        #[arg(long, value_name = "COUNT", default_value_t = 2)]
+        min: usize,
+    },
+    /// Export the tag co-occurrence network as a weighted graph
+    Graph {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = GraphExportFormatArg::Dot)]
+        format: GraphExportFormatArg,
+
+        /// Only draw edges between tags sharing at least this many notes
+        #[arg(long, value_name = "COUNT", default_value_t = 2)]
+        min: usize,
+
+        /// File to write to (defaults to stdout)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Manage named tag sets
+#[derive(Parser)]
+struct TagPresetCommand {
+    #[command(subcommand)]
+    command: TagPresetCommands,
+}
+
+/// Tag preset subcommands
+#[derive(Subcommand)]
+enum TagPresetCommands {
+    /// Save a preset, replacing any existing preset with the same name
+    Add {
+        /// The preset name
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Comma-separated tags in the preset
+        #[arg(value_name = "TAGS")]
+        tags: String,
+    },
+    /// List all tag presets
+    List,
+    /// Remove a tag preset
+    Remove {
+        /// The preset to remove
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
 }
 
 /// Manage tag aliases
@@ -150,6 +601,32 @@ enum TagAliasCommands {
         #[arg(value_name = "ALIAS")]
         alias: String,
     },
+    /// Create aliases from a file of `alias = canonical` lines
+    Import {
+        /// Text file with one `alias = canonical` mapping per line (`#` starts a comment)
+        #[arg(value_name = "PATH")]
+        path: std::path::PathBuf,
+    },
+    /// Point an existing alias at a different canonical tag
+    Retarget {
+        /// The existing alias
+        #[arg(value_name = "ALIAS")]
+        alias: String,
+
+        /// The new canonical tag name
+        #[arg(value_name = "CANONICAL")]
+        canonical: String,
+    },
+    /// Fix the spelling of an alias, keeping its canonical tag
+    Rename {
+        /// The existing alias
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// The new alias name
+        #[arg(value_name = "NEW")]
+        new: String,
+    },
 }
 
 /// Manage tag hierarchy
@@ -163,7 +640,198 @@ struct HierarchyCommand {
 #[derive(Subcommand)]
 enum HierarchyCommands {
     /// Suggest hierarchical relationships between tags using LLM analysis
-    Suggest,
+    Suggest {
+        /// Ollama model to use for this invocation (overrides OLLAMA_MODEL)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Minimum confidence (0.0-1.0) for a suggestion to be applied (default 0.7)
+        #[arg(long, value_name = "CONFIDENCE", value_parser = parse_confidence)]
+        min_confidence: Option<f64>,
+    },
+    /// Show the tag hierarchy as a tree, broadest tags first
+    Show {
+        /// Only show edges you have not verified yet (see `cons hierarchy verify`)
+        #[arg(long)]
+        unverified: bool,
+    },
+    /// Add a broader/narrower relationship between two tags
+    Add {
+        /// The narrower (source) tag name
+        #[arg(value_name = "SOURCE")]
+        source: String,
+
+        /// The broader (target) tag name
+        #[arg(value_name = "TARGET")]
+        target: String,
+
+        /// Relationship type
+        #[arg(long = "type", value_enum, default_value_t = HierarchyTypeArg::Generic)]
+        hierarchy_type: HierarchyTypeArg,
+
+        /// Confidence score from 0.0 to 1.0
+        #[arg(long, default_value_t = 1.0, value_parser = parse_confidence)]
+        confidence: f64,
+    },
+    /// Remove a broader/narrower relationship between two tags
+    Remove {
+        /// The narrower (source) tag name
+        #[arg(value_name = "SOURCE")]
+        source: String,
+
+        /// The broader (target) tag name
+        #[arg(value_name = "TARGET")]
+        target: String,
+    },
+    /// Mark a broader/narrower relationship as verified
+    Verify {
+        /// The narrower (source) tag name
+        #[arg(value_name = "SOURCE")]
+        source: String,
+
+        /// The broader (target) tag name
+        #[arg(value_name = "TARGET")]
+        target: String,
+    },
+    /// Remove hierarchy edges below a confidence threshold
+    Prune {
+        /// Edges with confidence below this value (0.0 to 1.0) are removed
+        #[arg(long, default_value_t = 0.7, value_parser = parse_confidence)]
+        min_confidence: f64,
+    },
+    /// Export the tag hierarchy for visualization
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = GraphExportFormatArg::Dot)]
+        format: GraphExportFormatArg,
+
+        /// File to write to (defaults to stdout)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Export format for `hierarchy export` and `tags graph`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum GraphExportFormatArg {
+    /// Graphviz DOT (render with e.g. `dot -Tsvg`)
+    Dot,
+}
+
+/// Hierarchy relationship type for `hierarchy add`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum HierarchyTypeArg {
+    /// Is-a relationship (e.g. rust is a programming language)
+    Generic,
+    /// Part-of relationship (e.g. tokio is part of the rust ecosystem)
+    Partitive,
+}
+
+impl HierarchyTypeArg {
+    /// Returns the value stored in `edges.hierarchy_type`.
+    fn as_str(self) -> &'static str {
+        match self {
+            HierarchyTypeArg::Generic => "generic",
+            HierarchyTypeArg::Partitive => "partitive",
+        }
+    }
+}
+
+/// Parses a confidence score, rejecting values outside 0.0-1.0.
+fn parse_confidence(value: &str) -> Result<f64, String> {
+    let confidence: f64 = value
+        .parse()
+        .map_err(|_| format!("'{value}' is not a number"))?;
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err(format!(
+            "confidence must be between 0.0 and 1.0, got {value}"
+        ));
+    }
+    Ok(confidence)
+}
+
+/// Export all notes to JSON, NDJSON, or Markdown
+#[derive(Parser)]
+struct ExportCommand {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ExportFormatArg::Json)]
+    format: ExportFormatArg,
+
+    /// File to write to (defaults to stdout)
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<std::path::PathBuf>,
+
+    /// Only export notes with this tag (or alias), as one titled Markdown
+    /// document; requires --format markdown
+    #[arg(long, value_name = "TAG")]
+    tag: Option<String>,
+}
+
+/// Import notes from a JSON export
+#[derive(Parser)]
+struct ImportCommand {
+    /// Path to a JSON file produced by `cons export`
+    #[arg(value_name = "PATH")]
+    path: std::path::PathBuf,
+
+    /// Run imported notes without an enhancement through the enhancer
+    #[arg(long, overrides_with = "no_enhance")]
+    enhance: bool,
+
+    /// Do not enhance imported notes (default)
+    #[arg(long, overrides_with = "enhance")]
+    no_enhance: bool,
+
+    /// Skip notes whose content already exists verbatim
+    #[arg(long)]
+    skip_duplicates: bool,
+}
+
+/// Write a consistent snapshot of the database
+#[derive(Parser)]
+struct BackupCommand {
+    /// File to write the snapshot to (defaults to the backups directory next to the database)
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<std::path::PathBuf>,
+}
+
+/// Check or compact the database file
+#[derive(Parser)]
+struct MaintenanceCommand {
+    #[command(subcommand)]
+    command: MaintenanceCommands,
+}
+
+/// Maintenance subcommands
+#[derive(Subcommand)]
+enum MaintenanceCommands {
+    /// Verify database integrity and foreign keys
+    Check,
+    /// Compact the database file, reclaiming free pages
+    Vacuum,
+    /// Recreate the full-text search index from the notes table
+    RebuildFts,
+}
+
+/// Export format for the export command
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum ExportFormatArg {
+    /// JSON array of notes with tags, enhancement fields, and timestamps
+    Json,
+    /// One note JSON object per line, streamed for large databases
+    Ndjson,
+    /// One Markdown section per note
+    Markdown,
+}
+
+impl From<ExportFormatArg> for ExportFormat {
+    fn from(arg: ExportFormatArg) -> Self {
+        match arg {
+            ExportFormatArg::Json => ExportFormat::Json,
+            ExportFormatArg::Ndjson => ExportFormat::Ndjson,
+            ExportFormatArg::Markdown => ExportFormat::Markdown,
+        }
+    }
 }
 
 /// Health check and maintenance utilities
@@ -171,6 +839,10 @@ enum HierarchyCommands {
 struct DoctorCommand {
     #[command(subcommand)]
     command: Option<DoctorSubcommand>,
+
+    /// Rebuild the search index if it is out of sync with the notes
+    #[arg(long)]
+    repair: bool,
 }
 
 /// Doctor subcommands
@@ -180,6 +852,14 @@ enum DoctorSubcommand {
     Enhance,
 }
 
+/// Print a shell completion script to stdout
+#[derive(Parser)]
+struct CompletionsCommand {
+    /// Shell to generate completions for (bash, zsh, fish, powershell)
+    #[arg(value_enum, value_name = "SHELL")]
+    shell: clap_complete::Shell,
+}
+
 fn main() {
     // Load environment variables from .env file if it exists
     // This is a no-op if .env doesn't exist, so it's safe to call unconditionally
@@ -187,18 +867,33 @@ fn main() {
 
     let cli = Cli::parse();
 
-    let result = match &cli.command {
-        Commands::Add(cmd) => handle_add(cmd),
-        Commands::List(cmd) => handle_list(cmd),
-        Commands::Search(cmd) => handle_search(cmd),
-        Commands::GraphSearch(cmd) => handle_graph_search(cmd),
-        Commands::Ask(cmd) => handle_ask(cmd),
-        Commands::Tags(cmd) => handle_tags(cmd),
-        Commands::TagAlias(cmd) => handle_tag_alias(cmd),
-        Commands::Hierarchy(cmd) => handle_hierarchy(cmd),
-        Commands::Tui => handle_tui(),
-        Commands::Doctor(cmd) => handle_doctor(cmd),
-    };
+    let result = resolve_database_path(cli.db.as_deref()).and_then(|db_path| match &cli.command {
+        Commands::Add(cmd) => handle_add(cmd, &db_path),
+        Commands::Edit(cmd) => handle_edit(cmd, &db_path),
+        Commands::Retag(cmd) => handle_retag(cmd, &db_path),
+        Commands::Untag(cmd) => handle_untag(cmd, &db_path),
+        Commands::Reenhance(cmd) => handle_reenhance(cmd, &db_path),
+        Commands::Delete(cmd) => handle_delete(cmd, &db_path),
+        Commands::Merge(cmd) => handle_merge(cmd, &db_path),
+        Commands::Show(cmd) => handle_show(cmd, &db_path),
+        Commands::List(cmd) => handle_list(cmd, cli.json, &db_path),
+        Commands::Search(cmd) => handle_search(cmd, cli.json, &db_path),
+        Commands::GraphSearch(cmd) => handle_graph_search(cmd, &db_path),
+        Commands::Related(cmd) => handle_related(cmd, &db_path),
+        Commands::Ask(cmd) => handle_ask(cmd, &db_path),
+        Commands::Tags(cmd) => handle_tags(cmd, cli.json, &db_path),
+        Commands::TagAlias(cmd) => handle_tag_alias(cmd, &db_path),
+        Commands::TagPreset(cmd) => handle_tag_preset(cmd, &db_path),
+        Commands::Hierarchy(cmd) => handle_hierarchy(cmd, &db_path),
+        Commands::Export(cmd) => handle_export(cmd, &db_path),
+        Commands::Import(cmd) => handle_import(cmd, &db_path),
+        Commands::Backup(cmd) => handle_backup(cmd, &db_path),
+        Commands::Maintenance(cmd) => handle_maintenance(cmd, &db_path),
+        Commands::Tui => handle_tui(&db_path),
+        Commands::Doctor(cmd) => handle_doctor(cmd, &db_path),
+        Commands::Config => handle_config(cli.json),
+        Commands::Completions(cmd) => handle_completions(cmd),
+    });
 
     if let Err(e) = result {
         // Determine exit code based on error type
@@ -210,20 +905,56 @@ fn main() {
 
 /// Determines if an error is a user error (vs internal error).
 ///
-/// User errors include validation failures like empty content.
+/// User errors include validation failures like empty content, requests that
+/// are invalid as given ([`InvalidInput`]), and references to notes, tags, or
+/// aliases that do not exist ([`NotFound`]).
 /// Internal errors include database failures and I/O errors.
 fn is_user_error(error: &anyhow::Error) -> bool {
+    // Typed user errors count wherever they are wrapped in context
+    if error
+        .chain()
+        .any(|cause| cause.is::<NotFound>() || cause.is::<InvalidInput>())
+    {
+        return true;
+    }
+
     // Check if the error message indicates a user error
     let error_msg = error.to_string();
-    error_msg.contains("cannot be empty")
+    error_msg.contains("cannot be empty") || error_msg.starts_with("Invalid date")
+}
+
+/// Opens the database at `db_path` with the configured search tokenizer,
+/// telling the user if the search index had to be rebuilt on the way.
+fn open_database(db_path: &std::path::Path) -> Result<Database> {
+    let tokenizer = Config::load().fts_tokenizer()?;
+    let db =
+        Database::open_with_tokenizer(db_path, tokenizer).context("Failed to open database")?;
+    if let Some(rebuild) = db.fts_rebuild() {
+        eprintln!("{rebuild}");
+    }
+    Ok(db)
 }
 
 /// Handles the add command by creating a new note.
-fn handle_add(cmd: &AddCommand) -> Result<()> {
-    // Get content from argument or open editor
-    let content = match &cmd.content {
-        Some(c) => c.clone(),
-        None => open_editor_for_note()?,
+fn handle_add(cmd: &AddCommand, db_path: &std::path::Path) -> Result<()> {
+    // Get content from a file, argument, stdin (`-` or a pipe), or open editor
+    let mut tags = cmd.tags.clone();
+    let content = match (&cmd.from_file, cmd.content.as_deref()) {
+        (Some(path), _) => {
+            let content = read_note_from_file(path)?;
+            if tags.is_none() {
+                tags = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned());
+            }
+            content
+        }
+        (None, Some("-")) => read_note_from_reader(std::io::stdin())?,
+        (None, Some(c)) => c.to_string(),
+        (None, None) if std::io::IsTerminal::is_terminal(&std::io::stdin()) => {
+            open_editor_for_note()?
+        }
+        (None, None) => read_note_from_reader(std::io::stdin())?,
     };
 
     // Validate content is not empty or whitespace-only
@@ -231,21 +962,118 @@ fn handle_add(cmd: &AddCommand) -> Result<()> {
         anyhow::bail!("Note content cannot be empty");
     }
 
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+    let config = Config::load();
+    let no_ai = cmd.no_ai || config.no_ai;
+    let (ai, ai_error) = if no_ai {
+        (None, None)
+    } else {
+        match AiPipeline::connect(cmd.model.as_deref()) {
+            Ok(ai) => (Some(ai), None),
+            Err(e) => (None, Some(e)),
+        }
+    };
+    let dedupe = cmd.dedupe || config.dedupe;
+
+    if cmd.dry_run {
+        // Read presets, duplicates, and aliases from an existing database, but never
+        // create or modify one: without a database file there is nothing to look up
+        let db = if db_path.exists() {
+            Database::open_read_only(db_path).context("Failed to open database")?
+        } else {
+            Database::in_memory().context("Failed to open database")?
+        };
+        return execute_add_dry_run(
+            &content,
+            tags.as_deref(),
+            cmd.preset.as_deref(),
+            ai.as_ref(),
+            ai_error.as_ref(),
+            dedupe,
+            db,
+        );
+    }
+
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
+
+    execute_add(
+        &content,
+        tags.as_deref(),
+        cmd.preset.as_deref(),
+        ai.as_ref(),
+        ai_error.as_ref(),
+        dedupe,
+        db,
+    )
+}
+
+/// Reads note content from a reader (stdin for `cons add -`) until EOF.
+///
+/// Trailing whitespace (such as the final newline from piped output) is trimmed.
+fn read_note_from_reader(reader: impl std::io::Read) -> Result<String> {
+    let content =
+        std::io::read_to_string(reader).context("Failed to read note content from stdin")?;
+    Ok(content.trim_end().to_string())
+}
+
+/// Reads note content from a text file (`cons add --from-file`).
+///
+/// Trailing whitespace is trimmed as for stdin. A missing file and non-UTF-8
+/// content (rejected rather than converted lossily) are both user errors.
+fn read_note_from_file(path: &std::path::Path) -> Result<String> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(NotFound(format!("File '{}' does not exist", path.display())).into());
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
 
-    execute_add(&content, cmd.tags.as_deref(), db)
+    let content = String::from_utf8(bytes)
+        .map_err(|_| InvalidInput(format!("File '{}' is not valid UTF-8 text", path.display())))?;
+    Ok(content.trim_end().to_string())
 }
 
 /// Opens the user's preferred editor to compose a note.
 ///
-/// Uses $EDITOR, falls back to $VISUAL, then to common editors.
+/// Lines starting with `<!--` (the placeholder hint) are removed from the result.
 fn open_editor_for_note() -> Result<String> {
-    use std::io::{Read, Write};
+    let content =
+        launch_editor("<!-- Enter your note below. Lines starting with <!-- are removed. -->\n")?;
+
+    // Remove HTML comment lines
+    let content: String = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("<!--"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(content.trim().to_string())
+}
+
+/// Opens `$EDITOR` (falling back to `$VISUAL`) on a temp file seeded with `initial`
+/// and returns the file's contents once the editor exits.
+///
+/// The editor value may include arguments (e.g. `code --wait`). Errors if neither
+/// variable is set, telling the user how to provide the note instead.
+fn launch_editor(initial: &str) -> Result<String> {
+    use std::io::Write;
+
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .context(
+            "No editor configured: set $EDITOR, pass the note as an argument \
+             (cons add \"...\"), or pipe it on stdin",
+        )?;
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
 
     // Create temp file with .md extension for editor syntax highlighting
     let mut temp_file = tempfile::Builder::new()
@@ -253,24 +1081,12 @@ fn open_editor_for_note() -> Result<String> {
         .suffix(".md")
         .tempfile()
         .context("Failed to create temporary file")?;
-
-    // Write placeholder comment
-    writeln!(
-        temp_file,
-        "<!-- Enter your note below. Lines starting with <!-- are removed. -->"
-    )?;
+    temp_file.write_all(initial.as_bytes())?;
     temp_file.flush()?;
 
-    let temp_path = temp_file.path().to_path_buf();
-
-    // Determine editor
-    let editor = std::env::var("EDITOR")
-        .or_else(|_| std::env::var("VISUAL"))
-        .unwrap_or_else(|_| "vi".to_string());
-
-    // Open editor
-    let status = std::process::Command::new(&editor)
-        .arg(&temp_path)
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(temp_file.path())
         .status()
         .with_context(|| format!("Failed to open editor: {editor}"))?;
 
@@ -278,30 +1094,38 @@ fn open_editor_for_note() -> Result<String> {
         anyhow::bail!("Editor exited with non-zero status");
     }
 
-    // Read content back
-    let mut content = String::new();
-    std::fs::File::open(&temp_path)
-        .context("Failed to read temp file")?
-        .read_to_string(&mut content)?;
-
-    // Remove HTML comment lines
-    let content: String = content
-        .lines()
-        .filter(|line| !line.trim_start().starts_with("<!--"))
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    Ok(content.trim().to_string())
+    std::fs::read_to_string(temp_file.path()).context("Failed to read temp file")
 }
 
 /// Executes the add command logic with a provided database.
 ///
 /// This function is separated from `handle_add` to allow testing with in-memory databases.
-fn execute_add(content: &str, tags: Option<&str>, db: Database) -> Result<()> {
+/// With `ai`, the note is enhanced and auto-tagged by that pipeline, which tests can build
+/// around a mock client. Without it only explicit tags are applied: `ai_error` holds why the
+/// Ollama pipeline could not be set up (logged, the note is still saved), or is `None` for
+/// `--no-ai`.
+/// With `dedupe`, nothing is created when a note with the same trimmed content already exists.
+/// A `preset` contributes its saved tags ahead of any explicit `tags`.
+fn execute_add(
+    content: &str,
+    tags: Option<&str>,
+    preset: Option<&str>,
+    ai: Option<&AiPipeline>,
+    ai_error: Option<&anyhow::Error>,
+    dedupe: bool,
+    db: Database,
+) -> Result<()> {
     let service = NoteService::new(db);
+    let parsed_tags = resolve_add_tags(&service, tags, preset)?;
 
-    // Parse tags if provided
-    let parsed_tags = tags.map(parse_tags);
+    if dedupe
+        && let Some(existing) = service
+            .find_note_by_content(content)
+            .context("Failed to check for duplicate notes")?
+    {
+        println!("Duplicate of id {} (not created)", existing.id());
+        return Ok(());
+    }
 
     // Create note with optional tags
     let note = if let Some(ref tags) = parsed_tags {
@@ -321,151 +1145,692 @@ fn execute_add(content: &str, tags: Option<&str>, db: Database) -> Result<()> {
     }
     println!();
 
-    // Enhance note content (fail-safe: errors logged but don't fail command)
-    // Enhancement runs AFTER save (original preserved) but BEFORE tagging (tag original intent)
-    if let Err(e) = enhance_note(&service, note.id(), content) {
-        eprintln!("Enhancement skipped: {e:#}");
+    let Some(ai) = ai else {
+        report_ai_skipped(ai_error);
+        return Ok(());
+    };
+
+    // Enhancement and tags are computed AFTER save (original preserved), both from the
+    // original content, then persisted (fail-safe: errors logged but don't fail command)
+    let artifacts = ai.compute_artifacts(content);
+
+    match artifacts.enhancement {
+        Ok(result) => {
+            if let Err(e) = store_enhancement(&service, note.id(), &ai.model, &result) {
+                eprintln!("Enhancement skipped: {e:#}");
+            }
+        }
+        Err(e) => eprintln!("Enhancement skipped: {e:#}"),
     }
 
-    // Auto-tag synchronously (fail-safe: errors logged but don't fail command)
-    if let Err(e) = auto_tag_note(&service, note.id(), content) {
-        eprintln!("Auto-tagging skipped: {e}");
+    if let Err(e) = artifacts
+        .tags
+        .and_then(|tags| apply_llm_tags(&service, note.id(), &ai.model, &tags, false))
+    {
+        eprintln!("Auto-tagging skipped: {e:#}");
     }
 
     Ok(())
 }
 
-/// Detects if a suggested tag should be an alias for an existing canonical tag.
-///
-/// Uses a simple heuristic to detect common abbreviation patterns:
-/// - Short tags (2-3 characters) that could be abbreviations
-/// - Existing longer tags where each word's first letter matches the abbreviation
+/// Reports why `cons add` skipped enhancement and auto-tagging.
 ///
-/// Returns the canonical TagId if an alias opportunity is detected, None otherwise.
-///
-/// # Examples
+/// `ai_error` is the pipeline setup failure, or `None` when AI was disabled with `--no-ai`.
+fn report_ai_skipped(ai_error: Option<&anyhow::Error>) {
+    match ai_error {
+        Some(e) => {
+            eprintln!("Enhancement skipped: {e:#}");
+            eprintln!("Auto-tagging skipped: {e:#}");
+        }
+        None => println!("AI enhancement and auto-tagging skipped (--no-ai)"),
+    }
+}
+
+/// Executes `cons add --dry-run`: previews what `execute_add` would do without saving.
 ///
-/// - "ml" → finds "machine-learning" (m-l) → returns Some(tag_id)
-/// - "ai" → finds "artificial-intelligence" (a-i) → returns Some(tag_id)
-/// - "quantum-computing" → no shorter tag exists → returns None
-fn find_alias_opportunity(service: &NoteService, suggested_tag: &str) -> Option<TagId> {
-    use cons::TagNormalizer;
+/// Runs the same preset lookup, duplicate check, enhancement, and tag generation, then
+/// prints the proposed enhanced text, its confidence, and the tags. Nothing is written:
+/// no note is created and alias opportunities are reported, not created.
+fn execute_add_dry_run(
+    content: &str,
+    tags: Option<&str>,
+    preset: Option<&str>,
+    ai: Option<&AiPipeline>,
+    ai_error: Option<&anyhow::Error>,
+    dedupe: bool,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+    let parsed_tags = resolve_add_tags(&service, tags, preset)?;
 
-    // Normalize the suggested tag
-    let normalized_suggested = TagNormalizer::normalize_tag(suggested_tag);
+    if dedupe
+        && let Some(existing) = service
+            .find_note_by_content(content)
+            .context("Failed to check for duplicate notes")?
+    {
+        println!("Duplicate of id {} (would not be created)", existing.id());
+        return Ok(());
+    }
 
-    // Only consider short tags (2-3 characters) as potential abbreviations
-    if normalized_suggested.len() > 3 {
-        return None;
+    println!("Dry run: nothing will be saved");
+    if let Some(tags) = parsed_tags
+        && !tags.is_empty()
+    {
+        println!("Tags: {}", tags.join(", "));
     }
 
-    // Query all existing tags from the database
-    let conn = service.database().connection();
-    let mut stmt = conn.prepare("SELECT id, name FROM tags").ok()?;
-    let tag_rows = stmt
-        .query_map([], |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-        })
-        .ok()?;
+    let Some(ai) = ai else {
+        report_ai_skipped(ai_error);
+        return Ok(());
+    };
 
-    // Look for a longer tag that could be the canonical form
-    for (tag_id, tag_name) in tag_rows.flatten() {
-        // Skip if this is the same tag
-        if tag_name == normalized_suggested {
-            continue;
+    let artifacts = ai.compute_artifacts(content);
+
+    match artifacts.enhancement {
+        Ok(result) => {
+            println!(
+                "Enhanced ({:.0}% confidence, model: {}):",
+                result.confidence() * 100.0,
+                ai.model
+            );
+            println!("  {}", result.enhanced_content());
         }
+        Err(e) => eprintln!("Enhancement skipped: {e:#}"),
+    }
 
-        // Check if this could be an acronym of a hyphenated tag
-        // e.g., "ml" could be an acronym for "machine-learning"
-        if tag_name.len() >= normalized_suggested.len() * 2 && tag_name.contains('-') {
-            let parts: Vec<&str> = tag_name.split('-').collect();
+    match artifacts.tags {
+        Ok(tags) if tags.is_empty() => println!("No tags suggested (model: {}).", ai.model),
+        Ok(tags) => {
+            println!("Suggested tags (model: {}):", ai.model);
+            for line in format_tag_suggestions(&service, &tags)? {
+                println!("  {line}");
+            }
+        }
+        Err(e) => eprintln!("Auto-tagging skipped: {e:#}"),
+    }
 
-            // Check if the abbreviation matches the first letters of each part
-            if parts.len() == normalized_suggested.len() {
-                let matches_acronym = parts
-                    .iter()
-                    .zip(normalized_suggested.chars())
-                    .all(|(part, ch)| part.starts_with(ch));
+    Ok(())
+}
 
-                if matches_acronym {
-                    return Some(TagId::new(tag_id));
-                }
+/// Combines a preset's saved tags with explicit `--tags` for `cons add`.
+///
+/// Preset tags come first; explicit tags not already in the preset follow. Errors if
+/// the preset does not exist.
+fn resolve_add_tags(
+    service: &NoteService,
+    tags: Option<&str>,
+    preset: Option<&str>,
+) -> Result<Option<Vec<String>>> {
+    let preset_tags = match preset {
+        Some(name) => match service
+            .get_tag_preset(name)
+            .context("Failed to look up tag preset")?
+        {
+            Some(preset_tags) => Some(preset_tags),
+            None => {
+                return Err(NotFound(format!(
+                    "Tag preset '{}' does not exist",
+                    cons::TagNormalizer::normalize_tag(name)
+                ))
+                .into());
             }
+        },
+        None => None,
+    };
 
-            // Also check for simple prefix matches on any part
-            for part in &parts {
-                if part.starts_with(&normalized_suggested)
-                    && part.len() >= normalized_suggested.len() * 2
-                {
-                    return Some(TagId::new(tag_id));
+    // Parse tags if provided, after any preset tags
+    Ok(match (preset_tags, tags.map(parse_tags)) {
+        (Some(mut combined), Some(explicit)) => {
+            for tag in explicit {
+                if !combined.contains(&tag) {
+                    combined.push(tag);
                 }
             }
+            Some(combined)
         }
+        (preset_tags, explicit) => preset_tags.or(explicit),
+    })
+}
 
-        // Check direct prefix match (e.g., "ai" matches "aimodel")
-        if tag_name.starts_with(&normalized_suggested)
-            && tag_name.len() >= normalized_suggested.len() * 2
-        {
-            return Some(TagId::new(tag_id));
+/// Handles the edit command by updating a note's content in place.
+fn handle_edit(cmd: &EditCommand, db_path: &std::path::Path) -> Result<()> {
+    // Validate content is not empty or whitespace-only
+    if cmd.content.trim().is_empty() {
+        anyhow::bail!("Note content cannot be empty");
+    }
+
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_edit(NoteId::new(cmd.id), &cmd.content, db)
+}
+
+/// Executes the edit command logic with a provided database.
+///
+/// This function is separated from `handle_edit` to allow testing with in-memory databases.
+/// Existing tags are preserved. The previous enhancement is cleared by the update and
+/// re-generated from the new content (fail-safe: errors logged but don't fail command).
+fn execute_edit(note_id: NoteId, content: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    service
+        .update_note_content(note_id, content)
+        .context("Failed to update note")?;
+
+    println!("Note updated (id: {note_id})");
+
+    // Re-enhance the new content (fail-safe: errors logged but don't fail command)
+    if let Err(e) = enhance_note(&service, note_id, content, None) {
+        eprintln!("Enhancement skipped: {e:#}");
+    }
+
+    Ok(())
+}
+
+/// Handles the retag command by regenerating LLM tags.
+fn handle_retag(cmd: &RetagCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_retag(
+        cmd.id.map(NoteId::new),
+        cmd.replace,
+        cmd.model.as_deref(),
+        db,
+    )
+}
+
+/// Executes the retag command logic with a provided database.
+///
+/// This function is separated from `handle_retag` to allow testing with in-memory databases.
+/// With a note id, tagging errors are returned. With `None`, every untagged note is retagged
+/// through one shared pipeline and per-note failures are logged without stopping the run. When `replace` is set, prior LLM
+/// tags are removed only after new tags were generated, so an Ollama outage loses nothing.
+/// `model` overrides `OLLAMA_MODEL` for this run.
+fn execute_retag(
+    note_id: Option<NoteId>,
+    replace: bool,
+    model: Option<&str>,
+    db: Database,
+) -> Result<()> {
+    use cons::{ListNotesOptions, Note, SortOrder};
+
+    let service = NoteService::new(db);
+
+    let Some(note_id) = note_id else {
+        let untagged: Vec<Note> = service
+            .notes_without_tags(ListNotesOptions {
+                order: SortOrder::Ascending,
+                ..Default::default()
+            })
+            .context("Failed to list untagged notes")?;
+
+        if untagged.is_empty() {
+            println!("No untagged notes to retag.");
+            return Ok(());
+        }
+
+        let ai = AiPipeline::connect(model)?;
+        let mut retagged = 0;
+        let mut progress = Progress::stderr(untagged.len());
+        for note in &untagged {
+            progress.step(format_args!("processing note {}", note.id()));
+            match tag_note_with_llm(&ai, &service, note.id(), note.content(), replace) {
+                Ok(()) => retagged += 1,
+                Err(e) => progress.fail(format_args!("Retagging note {} failed: {e:#}", note.id())),
+            }
         }
+        progress.finish();
+        println!("Retagged {retagged} of {} untagged notes", untagged.len());
+        return Ok(());
+    };
+
+    let note = service
+        .get_note(note_id)
+        .context("Failed to get note")?
+        .ok_or_else(|| NotFound(format!("Note with id {} does not exist", note_id)))?;
+
+    AiPipeline::connect(model)
+        .and_then(|ai| tag_note_with_llm(&ai, &service, note_id, note.content(), replace))
+        .with_context(|| format!("Failed to retag note {note_id}"))?;
+    println!("Note retagged (id: {note_id})");
+
+    Ok(())
+}
+
+/// Handles the untag command by removing one tag from a note.
+fn handle_untag(cmd: &UntagCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_untag(NoteId::new(cmd.id), &cmd.tag, db)
+}
+
+/// Executes the untag command logic with a provided database.
+///
+/// Removing a tag the note does not have succeeds (untag is idempotent); the tag
+/// itself is kept for any other notes that use it.
+fn execute_untag(note_id: NoteId, tag: &str, db: Database) -> Result<()> {
+    if tag.trim().is_empty() {
+        anyhow::bail!("Tag cannot be empty");
+    }
+
+    let service = NoteService::new(db);
+
+    if service
+        .get_note(note_id)
+        .context("Failed to get note")?
+        .is_none()
+    {
+        return Err(NotFound(format!("Note with id {} does not exist", note_id)).into());
+    }
+
+    let removed = service
+        .remove_tag_from_note(note_id, tag)
+        .context("Failed to remove tag")?;
+
+    if removed {
+        println!("Removed tag '{}' from note {}", tag.trim(), note_id);
+    } else {
+        println!("Note {} does not have tag '{}'", note_id, tag.trim());
     }
 
-    None
+    Ok(())
+}
+
+/// Handles the reenhance command by regenerating enhanced content.
+fn handle_reenhance(cmd: &ReenhanceCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_reenhance(cmd.id.map(NoteId::new), cmd.force, cmd.model.as_deref(), db)
 }
 
-/// Auto-tags a note using the configured Ollama model.
+/// Executes the reenhance command logic with a provided database.
 ///
-/// Reuses the provided NoteService to avoid opening a second database connection.
-/// Returns an error if tagging fails; caller decides whether to propagate or log.
+/// This function is separated from `handle_reenhance` to allow testing with in-memory databases.
+/// With a note id, an already-enhanced note is skipped unless `force` is set and enhancement
+/// errors are returned. With `None`, every unenhanced note is processed and per-note failures
+/// are logged without stopping the run. Prints the number enhanced and their average confidence.
+/// `model` overrides `OLLAMA_MODEL` for this run.
+fn execute_reenhance(
+    note_id: Option<NoteId>,
+    force: bool,
+    model: Option<&str>,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let Some(note_id) = note_id else {
+        let notes = service
+            .list_unenhanced_notes()
+            .context("Failed to list unenhanced notes")?;
+
+        if notes.is_empty() {
+            println!("No unenhanced notes.");
+            return Ok(());
+        }
+
+        let mut confidences = Vec::new();
+        let mut progress = Progress::stderr(notes.len());
+        for note in &notes {
+            progress.step(format_args!("processing note {}", note.id()));
+            match enhance_note(&service, note.id(), note.content(), model) {
+                Ok(confidence) => confidences.push(confidence),
+                Err(e) => progress.fail(format_args!("Enhancing note {} failed: {e:#}", note.id())),
+            }
+        }
+        progress.finish();
+        println!(
+            "{}",
+            format_enhancement_summary(confidences.len(), notes.len(), &confidences)
+        );
+        return Ok(());
+    };
+
+    let note = service
+        .get_note(note_id)
+        .context("Failed to get note")?
+        .ok_or_else(|| NotFound(format!("Note with id {} does not exist", note_id)))?;
+
+    if note.content_enhanced().is_some() && !force {
+        println!("Note {note_id} is already enhanced (use --force to re-enhance)");
+        return Ok(());
+    }
+
+    let confidence = enhance_note(&service, note_id, note.content(), model)
+        .with_context(|| format!("Failed to enhance note {note_id}"))?;
+    println!("{}", format_enhancement_summary(1, 1, &[confidence]));
+
+    Ok(())
+}
+
+/// Formats the reenhance summary line, e.g. `Enhanced 2 of 3 notes (average confidence 85%)`.
+fn format_enhancement_summary(enhanced: usize, total: usize, confidences: &[f64]) -> String {
+    let noun = if total == 1 { "note" } else { "notes" };
+    if confidences.is_empty() {
+        return format!("Enhanced {enhanced} of {total} {noun}");
+    }
+    let average = confidences.iter().sum::<f64>() / confidences.len() as f64;
+    format!(
+        "Enhanced {enhanced} of {total} {noun} (average confidence {:.0}%)",
+        average * 100.0
+    )
+}
+
+/// Handles the delete command by removing a note.
+fn handle_delete(cmd: &DeleteCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_delete(NoteId::new(cmd.id), cmd.yes, db)
+}
+
+/// Executes the delete command logic with a provided database.
 ///
-/// Automatically creates LLM-suggested aliases when appropriate:
-/// - Detects when the LLM suggests a tag that could be an alias for an existing tag
-/// - Creates alias mapping with source='llm', confidence from tagger, model_version from OLLAMA_MODEL
-/// - Alias creation is fail-safe: errors are logged but don't block note capture
-fn auto_tag_note(service: &NoteService, note_id: NoteId, content: &str) -> Result<()> {
-    let client = Arc::new(
-        OllamaClientBuilder::new()
-            .build()
-            .context("Failed to build Ollama client")?,
-    );
+/// This function is separated from `handle_delete` to allow testing with in-memory databases.
+/// Deleting a non-existent note is not an error (delete is idempotent). Unless `skip_confirm`
+/// is set, the user is prompted on stdin before anything is removed.
+fn execute_delete(note_id: NoteId, skip_confirm: bool, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
 
-    // Try OLLAMA_MODEL env var first, then auto-detect from Ollama
-    let model = match std::env::var("OLLAMA_MODEL") {
-        Ok(m) if !m.is_empty() => m,
-        _ => {
-            // Auto-detect: fetch available models from Ollama
-            let models = client.list_models().context(
-                "Ollama not reachable. Is it running? Try: ollama serve",
-            )?;
-
-            models.into_iter().next().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No models installed in Ollama. Install one with: ollama pull gemma3:4b"
-                )
-            })?
+    let Some(note) = service.get_note(note_id).context("Failed to get note")? else {
+        println!("No note with id {note_id}");
+        return Ok(());
+    };
+
+    if !skip_confirm {
+        println!("ID: {}", note.id());
+        println!("Content: {}", note.content());
+        if !confirm_delete() {
+            println!("Delete cancelled.");
+            return Ok(());
+        }
+    }
+
+    service
+        .delete_note(note_id)
+        .context("Failed to delete note")?;
+
+    println!("Note deleted (id: {note_id})");
+
+    Ok(())
+}
+
+/// Handles the merge command by folding one note into another.
+fn handle_merge(cmd: &MergeCommand, db_path: &std::path::Path) -> Result<()> {
+    ensure_database_directory(db_path)?;
+    let db = open_database(db_path)?;
+
+    execute_merge(NoteId::new(cmd.keep), NoteId::new(cmd.merge), db)
+}
+
+/// Executes the merge command logic with a provided database.
+fn execute_merge(keep: NoteId, merge: NoteId, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let note = service.merge_notes(keep, merge)?;
+
+    println!("Merged note {merge} into note {keep}");
+    let tag_names = get_tag_names(service.database(), note.tags())?;
+    if !tag_names.is_empty() {
+        println!("Tags: {}", tag_names.join(" "));
+    }
+
+    Ok(())
+}
+
+/// Handles the show command by printing full details of one note.
+fn handle_show(cmd: &ShowCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_show(NoteId::new(cmd.id), cmd.verbose, db)
+}
+
+/// Executes the show command logic with a provided database.
+///
+/// With `verbose`, the stored enhancement prompt is printed after the details.
+/// Returns a user error if no note has the given id.
+fn execute_show(note_id: NoteId, verbose: bool, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let Some(note) = service.get_note(note_id).context("Failed to get note")? else {
+        return Err(NotFound(format!("Note with id {} does not exist", note_id)).into());
+    };
+
+    let tag_names = get_tag_names(service.database(), note.tags())?;
+    print!("{}", format_note_details(&note, &tag_names));
+    if verbose {
+        print!("{}", format_enhancement_prompt(&note));
+    }
+
+    let descriptions = tag_names
+        .iter()
+        .map(|name| service.get_tag_description(name))
+        .collect::<Result<Vec<_>>>()
+        .context("Failed to get tag descriptions")?;
+    print!("{}", format_tag_descriptions(&tag_names, &descriptions));
+
+    let links = service.get_links(note_id).context("Failed to get links")?;
+    let backlinks = service
+        .get_backlinks(note_id)
+        .context("Failed to get backlinks")?;
+    print!("{}", format_note_links(&links, &backlinks));
+
+    Ok(())
+}
+
+/// Formats the prompt that produced a note's enhancement for `show --verbose`.
+///
+/// Prompt lines are indented under a heading. Enhanced notes without a stored
+/// prompt (enhanced before prompts were recorded) say so; unenhanced notes
+/// print nothing.
+fn format_enhancement_prompt(note: &cons::Note) -> String {
+    if note.content_enhanced().is_none() {
+        return String::new();
+    }
+
+    let Some(prompt) = note.enhancement_prompt() else {
+        return "Enhancement prompt: not recorded\n".to_string();
+    };
+
+    let mut output = "Enhancement prompt:\n".to_string();
+    for line in prompt.lines() {
+        output.push_str(&format!("  {line}\n"));
+    }
+    output
+}
+
+/// Formats the descriptions of a note's tags for the show command.
+///
+/// `descriptions` must be in the same order as `tag_names`; tags without a
+/// description are skipped and nothing is returned if none have one.
+fn format_tag_descriptions(tag_names: &[String], descriptions: &[Option<String>]) -> String {
+    let described: Vec<_> = tag_names
+        .iter()
+        .zip(descriptions)
+        .filter_map(|(name, description)| Some((name, description.as_ref()?)))
+        .collect();
+    if described.is_empty() {
+        return String::new();
+    }
+
+    let mut output = "Tag descriptions:\n".to_string();
+    for (name, description) in described {
+        output.push_str(&format!("  #{name}: {description}\n"));
+    }
+    output
+}
+
+/// Formats a note's outgoing `[[id]]` links and backlinks for the show command.
+///
+/// Each linked note is listed with the first line of its content; sections
+/// with no notes are omitted.
+fn format_note_links(links: &[cons::Note], backlinks: &[cons::Note]) -> String {
+    let mut output = String::new();
+
+    for (label, notes) in [("Links", links), ("Backlinks", backlinks)] {
+        if notes.is_empty() {
+            continue;
         }
+        output.push_str(&format!("{label}:\n"));
+        for note in notes {
+            let first_line = note.content().lines().next().unwrap_or_default();
+            output.push_str(&format!("  [[{}]] {}\n", note.id(), first_line));
+        }
+    }
+
+    output
+}
+
+/// Formats every stored detail of a note for the show command.
+///
+/// `tag_names` must be in the same order as `note.tags()`.
+fn format_note_details(note: &cons::Note, tag_names: &[String]) -> String {
+    use time::macros::format_description;
+
+    let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+    let format_time = |t: time::OffsetDateTime| {
+        t.format(&format)
+            .unwrap_or_else(|_| "Invalid date".to_string())
     };
 
-    let tagger = AutoTaggerBuilder::new().client(client).build();
+    let mut output = String::new();
+    output.push_str(&format!("ID: {}\n", note.id()));
+    output.push_str(&format!("Created: {}\n", format_time(note.created_at())));
+    output.push_str(&format!("Updated: {}\n", format_time(note.updated_at())));
+    output.push_str(&format!("Source: {}\n", note.source()));
+    output.push_str(&format_note_content(note, None));
+
+    match (note.content_enhanced(), note.enhancement_model()) {
+        (Some(_), Some(model)) => output.push_str(&format!("Enhancement model: {}\n", model)),
+        (Some(_), None) => {}
+        (None, _) => output.push_str("Enhancement: none\n"),
+    }
+    if let Some(enhanced_at) = note.enhanced_at() {
+        output.push_str(&format!("Enhanced at: {}\n", format_time(enhanced_at)));
+    }
+
+    if note.tags().is_empty() {
+        output.push_str("Tags: none\n");
+    } else {
+        output.push_str("Tags:\n");
+        for (tag, name) in note.tags().iter().zip(tag_names) {
+            let source = match (tag.model(), tag.source().import_source()) {
+                (Some(model), _) => format!("llm, {}", model),
+                (None, Some(source_name)) => format!("import, {}", source_name),
+                (None, None) => "user".to_string(),
+            };
+            let verified = if tag.verified() { " ✓" } else { "" };
+            output.push_str(&format!(
+                "  #{} ({}, {}% confidence){}\n",
+                name,
+                source,
+                tag.confidence(),
+                verified
+            ));
+        }
+    }
+
+    output
+}
+
+/// Prompts the user to confirm a note deletion.
+///
+/// Returns false on read errors or anything other than "y"/"yes".
+fn confirm_delete() -> bool {
+    use std::io::Write;
+
+    print!("\nDelete this note? [y/N] ");
+    if std::io::stdout().flush().is_err() {
+        return false;
+    }
+
+    let mut input = String::new();
+    if std::io::stdin().read_line(&mut input).is_err() {
+        return false;
+    }
+
+    matches!(input.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Generates LLM tags for a note and applies them.
+///
+/// Reuses the provided NoteService to avoid opening a second database connection.
+/// When `replace_llm_tags` is set, existing LLM tags are removed after generation succeeds
+/// and before the new tags are applied. User tags are never touched.
+///
+/// Automatically creates LLM-suggested aliases when appropriate:
+/// - Detects when the LLM suggests a tag that could be an alias for an existing tag
+/// - Creates alias mapping with source='llm', confidence from tagger, model_version from the pipeline
+/// - Alias creation is fail-safe: errors are logged but don't block note capture
+fn tag_note_with_llm(
+    ai: &AiPipeline,
+    service: &NoteService,
+    note_id: NoteId,
+    content: &str,
+    replace_llm_tags: bool,
+) -> Result<()> {
+    let tags = ai.generate_tags(content)?;
+    apply_llm_tags(service, note_id, &ai.model, &tags, replace_llm_tags)
+}
 
-    let tags = tagger
-        .generate_tags(&model, content)
-        .context("Failed to generate tags")?;
+/// Applies generated LLM tags to a note, creating aliases where appropriate.
+///
+/// This is the persist half of [`tag_note_with_llm`]; see it for alias and
+/// `replace_llm_tags` behavior.
+fn apply_llm_tags(
+    service: &NoteService,
+    note_id: NoteId,
+    model: &str,
+    tags: &std::collections::HashMap<String, f64>,
+    replace_llm_tags: bool,
+) -> Result<()> {
+    if replace_llm_tags {
+        service
+            .remove_llm_tags(note_id)
+            .context("Failed to remove previous LLM tags")?;
+    }
 
     if tags.is_empty() {
         return Ok(());
     }
 
     // Process each suggested tag
-    for (tag_name, confidence) in &tags {
+    for (tag_name, confidence) in tags {
         let confidence_u8 = (*confidence * 100.0).round() as u8;
 
         // Check if this tag should be an alias for an existing canonical tag
         // This detects common abbreviation patterns (e.g., "ml" → "machine-learning")
-        if let Some(canonical_tag_id) = find_alias_opportunity(service, tag_name) {
+        let alias_target = service
+            .detect_alias_opportunity(tag_name)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to check alias for '{}': {}", tag_name, e);
+                None
+            });
+        if let Some(canonical_tag_id) = alias_target {
             // Create the alias mapping (fail-safe: log errors but don't fail)
             if let Err(e) =
-                service.create_alias(tag_name, canonical_tag_id, "llm", *confidence, Some(&model))
+                service.create_alias(tag_name, canonical_tag_id, "llm", *confidence, Some(model))
             {
                 eprintln!("Failed to create alias '{}': {}", tag_name, e);
             } else {
@@ -473,7 +1838,7 @@ fn auto_tag_note(service: &NoteService, note_id: NoteId, content: &str) -> Resul
             }
 
             // Use the canonical tag for tagging the note
-            let source = TagSource::llm(model.clone(), confidence_u8);
+            let source = TagSource::llm(model, confidence_u8);
             // Get canonical tag name to use in add_tags_to_note
             let canonical_name: String = service
                 .database()
@@ -495,7 +1860,7 @@ fn auto_tag_note(service: &NoteService, note_id: NoteId, content: &str) -> Resul
                 .with_context(|| format!("Failed to add canonical tag '{}'", canonical_name))?;
         } else {
             // No alias opportunity detected - add the tag as-is
-            let source = TagSource::llm(model.clone(), confidence_u8);
+            let source = TagSource::llm(model, confidence_u8);
             service
                 .add_tags_to_note(note_id, &[tag_name.as_str()], source)
                 .with_context(|| format!("Failed to add tag '{tag_name}'"))?;
@@ -508,103 +1873,265 @@ fn auto_tag_note(service: &NoteService, note_id: NoteId, content: &str) -> Resul
     Ok(())
 }
 
-/// Enhances a note using the configured Ollama model.
+/// Enhancement and tags computed for note content before anything is stored.
 ///
-/// Reuses the provided NoteService to avoid opening a second database connection.
-/// Returns an error if enhancement fails; caller decides whether to propagate or log.
+/// Each step fails independently, so an enhancement error does not discard the tags.
+struct AiArtifacts {
+    enhancement: Result<EnhancementResult>,
+    tags: Result<std::collections::HashMap<String, f64>>,
+}
+
+/// Ollama client and model used for enhancement and auto-tagging.
 ///
-/// Enhancement expands abbreviated notes, completes fragments, and clarifies implicit
-/// context while preserving the original intent. The original content is never modified.
-fn enhance_note(service: &NoteService, note_id: NoteId, content: &str) -> Result<()> {
-    let client = Arc::new(
-        OllamaClientBuilder::new()
+/// `connect` builds the real client; tests construct one around a mock
+/// `OllamaClientTrait` so the add pipeline can run without a live server.
+struct AiPipeline {
+    client: Arc<dyn OllamaClientTrait>,
+    model: String,
+}
+
+impl AiPipeline {
+    /// Builds the Ollama client and resolves the model to use.
+    ///
+    /// See [`resolve_model`] for how `model` (the `--model` flag) is resolved.
+    fn connect(model: Option<&str>) -> Result<Self> {
+        let client = OllamaClientBuilder::new()
             .build()
-            .context("Failed to build Ollama client")?,
-    );
+            .context("Failed to build Ollama client")?;
+        let model = resolve_model(&client, model)?;
 
-    // Try OLLAMA_MODEL env var first, then auto-detect from Ollama
-    let model = match std::env::var("OLLAMA_MODEL") {
-        Ok(m) if !m.is_empty() => m,
-        _ => {
-            let models = client.list_models().context(
-                "Ollama not reachable. Is it running? Try: ollama serve",
-            )?;
-
-            models.into_iter().next().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No models installed in Ollama. Install one with: ollama pull gemma3:4b"
-                )
-            })?
-        }
-    };
+        Ok(Self {
+            client: Arc::new(client),
+            model,
+        })
+    }
 
-    let enhancer = NoteEnhancerBuilder::new().client(client).build();
+    /// Generates an enhanced version of `content`. Does not touch the database.
+    fn enhance(&self, content: &str) -> Result<EnhancementResult> {
+        let enhancer = NoteEnhancerBuilder::new()
+            .client(Arc::clone(&self.client))
+            .build();
 
-    let result = enhancer
-        .enhance_content(&model, content)
-        .context("Failed to enhance content")?;
+        enhancer
+            .enhance_content(&self.model, content)
+            .context("Failed to enhance content")
+    }
 
-    // Update note with enhancement result
-    let now = time::OffsetDateTime::now_utc();
-    service
-        .update_note_enhancement(
-            note_id,
-            result.enhanced_content(),
-            &model,
-            result.confidence(),
-            now,
-        )
-        .context("Failed to update note with enhancement")?;
+    /// Runs enhancement, then tag generation, on `content`. Does not touch the database.
+    fn compute_artifacts(&self, content: &str) -> AiArtifacts {
+        AiArtifacts {
+            enhancement: self.enhance(content),
+            tags: self.generate_tags(content),
+        }
+    }
 
-    eprintln!(
+    /// Generates auto-tags for `content`.
+    ///
+    /// Applies the `CONS_MIN_TAG_CONFIDENCE` threshold and `CONS_MAX_TAGS` cap.
+    /// Does not touch the database.
+    fn generate_tags(&self, content: &str) -> Result<std::collections::HashMap<String, f64>> {
+        let config = Config::load();
+        let mut tagger = AutoTaggerBuilder::new()
+            .client(Arc::clone(&self.client))
+            .min_confidence(config.min_tag_confidence);
+        if let Some(max_tags) = config.max_tags {
+            tagger = tagger.max_tags(max_tags);
+        }
+        let tagger = tagger.build();
+
+        tagger
+            .generate_tags(&self.model, content)
+            .context("Failed to generate tags")
+    }
+}
+
+/// Resolves the Ollama model for one invocation.
+///
+/// Uses the `--model` override when given, then `OLLAMA_MODEL` when set, and
+/// otherwise the first model installed in Ollama.
+fn resolve_model(client: &OllamaClient, model: Option<&str>) -> Result<String> {
+    if let Some(model) = model.map(str::trim).filter(|m| !m.is_empty()) {
+        return Ok(model.to_string());
+    }
+
+    if let Some(model) = Config::load().ollama_model {
+        return Ok(model);
+    }
+
+    // Auto-detect: fetch available models from Ollama
+    let models = client
+        .list_models()
+        .context("Ollama not reachable. Is it running? Try: ollama serve")?;
+
+    models.into_iter().next().ok_or_else(|| {
+        anyhow::anyhow!("No models installed in Ollama. Install one with: ollama pull gemma3:4b")
+    })
+}
+
+/// Enhances a note using `model`, or the configured Ollama model when `None`.
+///
+/// Reuses the provided NoteService to avoid opening a second database connection.
+/// Returns the enhancement confidence on success, or an error if enhancement fails;
+/// caller decides whether to propagate or log.
+fn enhance_note(
+    service: &NoteService,
+    note_id: NoteId,
+    content: &str,
+    model: Option<&str>,
+) -> Result<f64> {
+    enhance_note_with(&AiPipeline::connect(model)?, service, note_id, content)
+}
+
+/// Enhances a note with the given pipeline and stores the result.
+///
+/// Enhancement expands abbreviated notes, completes fragments, and clarifies implicit
+/// context while preserving the original intent. The original content is never modified.
+fn enhance_note_with(
+    ai: &AiPipeline,
+    service: &NoteService,
+    note_id: NoteId,
+    content: &str,
+) -> Result<f64> {
+    let result = ai.enhance(content)?;
+    store_enhancement(service, note_id, &ai.model, &result)
+}
+
+/// Stores an enhancement result on a note, returning its confidence.
+fn store_enhancement(
+    service: &NoteService,
+    note_id: NoteId,
+    model: &str,
+    result: &EnhancementResult,
+) -> Result<f64> {
+    // Update note with enhancement result
+    let now = time::OffsetDateTime::now_utc();
+    service
+        .update_note_enhancement(
+            note_id,
+            result.enhanced_content(),
+            model,
+            result.confidence(),
+            now,
+            result.prompt(),
+        )
+        .context("Failed to update note with enhancement")?;
+
+    eprintln!("{}", format_enhancement_status(result));
+
+    Ok(result.confidence())
+}
+
+/// Formats the stderr line reported after an enhancement is stored.
+///
+/// Includes the model call's duration when the enhancer measured it, e.g.
+/// `Enhanced with 85% confidence in 1203ms`.
+fn format_enhancement_status(result: &EnhancementResult) -> String {
+    let summary = format!(
         "Enhanced with {:.0}% confidence",
         result.confidence() * 100.0
     );
-
-    Ok(())
+    match result.duration_ms() {
+        Some(ms) => format!("{summary} in {ms}ms"),
+        None => summary,
+    }
 }
 
 // Database path utilities moved to src/utils.rs for reuse across CLI and TUI
 
 /// Handles the list command by displaying notes.
-fn handle_list(cmd: &ListCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+fn handle_list(cmd: &ListCommand, json: bool, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
     let service = NoteService::new(db);
 
-    execute_list(cmd.limit, cmd.tags.as_deref(), service)
+    execute_list(cmd, json, service)
 }
 
-/// Executes the list command logic with a provided NoteService.
+/// Resolves `--offset`/`--page` into a row offset.
 ///
-/// This function is separated from `handle_list` to allow testing with in-memory databases.
-fn execute_list(limit: Option<usize>, tags: Option<&str>, service: NoteService) -> Result<()> {
-    use time::macros::format_description;
+/// `--page N` is sugar for `--offset (N-1) * limit`; clap rejects using both.
+/// A `limit` of 0 means no limit, so there are no pages and `--page` is an error,
+/// as is a page whose offset does not fit in `usize`.
+fn resolve_offset(offset: Option<usize>, page: Option<u64>, limit: usize) -> Result<Option<usize>> {
+    if page.is_some() && limit == 0 {
+        return Err(InvalidInput("--page cannot be combined with --limit 0".to_string()).into());
+    }
+    if offset.is_some() {
+        return Ok(offset);
+    }
+    page.map(|page| {
+        usize::try_from(page - 1)
+            .ok()
+            .and_then(|pages| pages.checked_mul(limit))
+            .ok_or_else(|| InvalidInput(format!("Page {page} is out of range")).into())
+    })
+    .transpose()
+}
 
-    // Apply default limit of 10 when not specified
-    let limit = limit.unwrap_or(10);
+/// Fetches the notes for `cons list` in display order, plus the total match count.
+///
+/// Notes are fetched newest-first and reversed, so the result reads oldest to
+/// newest. `--all` or `--limit 0` removes the cap, returning every match.
+fn fetch_list_notes(cmd: &ListCommand, service: &NoteService) -> Result<(Vec<cons::Note>, usize)> {
+    // Apply default limit of 10 when not specified; 0 means no limit
+    let limit = if cmd.all || cmd.limit == Some(0) {
+        None
+    } else {
+        Some(cmd.limit.unwrap_or(10))
+    };
+
+    // Parse date bounds (inclusive on both ends)
+    let created_after = cmd
+        .since
+        .as_deref()
+        .map(|s| parse_date_bound(s, false))
+        .transpose()?;
+    let created_before = cmd
+        .until
+        .as_deref()
+        .map(|s| parse_date_bound(s, true))
+        .transpose()?;
 
     // Parse tags if provided, converting empty to None
-    let parsed_tags = tags.map(parse_tags);
+    let parsed_tags = cmd.tags.as_deref().map(parse_tags);
     let tags_option = match parsed_tags {
         Some(ref tags) if tags.is_empty() => None,
         other => other,
     };
 
+    let tag_like = match cmd.tag_like.as_deref().map(str::trim) {
+        Some("") => anyhow::bail!("Tag pattern cannot be empty"),
+        other => other.map(str::to_string),
+    };
+
     // Use DESC ordering to get the newest N notes, then reverse for chronological display
     // (oldest first, newest last within the result set)
     use cons::{ListNotesOptions, SortOrder};
     let options = ListNotesOptions {
-        limit: Some(limit),
+        limit,
         tags: tags_option,
+        tag_like,
         order: SortOrder::Descending,
+        order_by: cmd.by.into(),
+        created_after,
+        created_before,
+        source: cmd.source.into(),
+        // An unbounded list is a single page
+        offset: resolve_offset(cmd.offset, cmd.page, limit.unwrap_or(0))?,
+        unverified_only: cmd.unverified_only,
+        untagged_only: cmd.untagged,
+        before: cmd.before,
     };
 
-    // Fetch newest N notes
+    // Count all matches (ignoring limit/offset) for the footer
+    let total = service
+        .count_notes(&options)
+        .context("Failed to count notes")?;
+
+    // Fetch the newest notes
     let mut notes = service
         .list_notes(options)
         .context("Failed to list notes")?;
@@ -612,6 +2139,28 @@ fn execute_list(limit: Option<usize>, tags: Option<&str>, service: NoteService)
     // Reverse to display oldest-first (newest last)
     notes.reverse();
 
+    Ok((notes, total))
+}
+
+/// Characters of note content `cons list` shows without `--full` or `--truncate`.
+const DEFAULT_LIST_EXCERPT_CHARS: usize = 200;
+
+/// Executes the list command logic with a provided NoteService.
+///
+/// This function is separated from `handle_list` to allow testing with in-memory databases.
+fn execute_list(cmd: &ListCommand, json: bool, service: NoteService) -> Result<()> {
+    use time::macros::format_description;
+
+    let (notes, total) = fetch_list_notes(cmd, &service)?;
+
+    if json {
+        let json_notes = notes
+            .iter()
+            .map(|note| JsonNote::new(note, None, &service))
+            .collect::<Result<Vec<_>>>()?;
+        return print_json(&json_notes);
+    }
+
     // Handle empty results
     if notes.is_empty() {
         println!("No notes found");
@@ -621,6 +2170,15 @@ fn execute_list(limit: Option<usize>, tags: Option<&str>, service: NoteService)
     // Format descriptor for "YYYY-MM-DD HH:MM"
     let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
 
+    let excerpt_chars = if cmd.full {
+        None
+    } else {
+        Some(
+            cmd.truncate
+                .map_or(DEFAULT_LIST_EXCERPT_CHARS, |n| n as usize),
+        )
+    };
+
     // Display each note
     for note in &notes {
         // Format timestamp as "YYYY-MM-DD HH:MM"
@@ -641,7 +2199,7 @@ fn execute_list(limit: Option<usize>, tags: Option<&str>, service: NoteService)
         println!("Created: {}", timestamp);
 
         // Display content using stacked format (original + enhanced if available)
-        print!("{}", format_note_content(note));
+        print!("{}", format_note_content(note, excerpt_chars));
 
         if !tag_names.is_empty() {
             println!("Tags: {}", tag_names.join(" "));
@@ -649,35 +2207,96 @@ fn execute_list(limit: Option<usize>, tags: Option<&str>, service: NoteService)
         println!(); // Blank line separator
     }
 
+    println!("Showing {} of {} notes", notes.len(), total);
+
     Ok(())
 }
 
 /// Handles the search command by searching notes.
-fn handle_search(cmd: &SearchCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+fn handle_search(cmd: &SearchCommand, json: bool, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
     let service = NoteService::new(db);
 
-    execute_search(&cmd.query, cmd.limit, service)
+    execute_search(cmd, json, service)
 }
 
 /// Executes the search command logic with a provided NoteService.
 ///
 /// This function is separated from `handle_search` to allow testing with in-memory databases.
-fn execute_search(query: &str, limit: Option<usize>, service: NoteService) -> Result<()> {
+fn execute_search(cmd: &SearchCommand, json: bool, service: NoteService) -> Result<()> {
     use time::macros::format_description;
 
     // Apply default limit of 10 when not specified
-    let limit = limit.unwrap_or(10);
+    let limit = cmd.limit.unwrap_or(10);
+
+    // Parse date bounds (inclusive on both ends)
+    let filters = cons::SearchFilters {
+        created_after: cmd
+            .since
+            .as_deref()
+            .map(|s| parse_date_bound(s, false))
+            .transpose()?,
+        created_before: cmd
+            .until
+            .as_deref()
+            .map(|s| parse_date_bound(s, true))
+            .transpose()?,
+        source: cmd.source.into(),
+        offset: resolve_offset(cmd.offset, cmd.page, limit)?,
+        // Resolved up front because the graph branch filters with `matches`
+        tags: cmd
+            .tags
+            .as_deref()
+            .map(|tags| service.canonical_tag_names(&parse_tags(tags)))
+            .transpose()
+            .context("Failed to resolve tags")?,
+    };
 
-    // Call service dual_search method - returns tuple of (Vec<DualSearchResult>, DualSearchMetadata)
-    let (results, metadata) = service
-        .dual_search(query, Some(limit))
-        .context("Failed to search notes")?;
+    // Collect (note, score, snippet) from the selected retrieval mode so both display alike
+    let (results, metadata) = if cmd.graph {
+        if cmd.query.trim().is_empty() {
+            anyhow::bail!("Search query cannot be empty");
+        }
+
+        // Graph search has no native filters, so filter and page over the full ranking
+        let results: Vec<(cons::Note, f64, Option<String>)> = service
+            .graph_search(&cmd.query, None)
+            .context("Failed to perform graph search")?
+            .into_iter()
+            .filter(|result| filters.matches(&result.note))
+            .skip(filters.offset.unwrap_or(0))
+            .take(limit)
+            .map(|result| (result.note, result.relevance_score, result.snippet))
+            .collect();
+        (results, None)
+    } else {
+        // Call service dual_search method - returns tuple of (Vec<DualSearchResult>, DualSearchMetadata)
+        let (results, metadata) = service
+            .dual_search_filtered(&cmd.query, Some(limit), &filters)
+            .context("Failed to search notes")?;
+        let results = results
+            .into_iter()
+            .map(|result| (result.note, result.final_score, result.snippet))
+            .collect();
+        (results, Some(metadata))
+    };
+
+    if json {
+        let json_notes = results
+            .iter()
+            .map(|(note, score, _)| JsonNote::new(note, Some(*score), &service))
+            .collect::<Result<Vec<_>>>()?;
+        return print_json(&json_notes);
+    }
+
+    if cmd.graph {
+        println!("Graph search (spreading activation through tag hierarchy)");
+        println!();
+    }
 
     // Handle empty results
     if results.is_empty() {
@@ -689,10 +2308,7 @@ fn execute_search(query: &str, limit: Option<usize>, service: NoteService) -> Re
     let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
 
     // Display each note (using same format as list command)
-    // Extract .note from DualSearchResult
-    for result in &results {
-        let note = &result.note;
-
+    for (note, score, snippet) in &results {
         // Format timestamp as "YYYY-MM-DD HH:MM"
         let timestamp = note
             .created_at()
@@ -709,9 +2325,14 @@ fn execute_search(query: &str, limit: Option<usize>, service: NoteService) -> Re
         // Display note information
         println!("ID: {}", note.id().get());
         println!("Created: {}", timestamp);
+        // Dual-search scores are weighted sums and can exceed 1.0, so show the raw score
+        println!("Relevance: {:.2}", score);
 
-        // Display content using stacked format (original + enhanced if available)
-        print!("{}", format_note_content(note));
+        // Show the highlighted excerpt when the body matched; otherwise the full content
+        match snippet {
+            Some(snippet) => println!("Match: {}", snippet),
+            None => print!("{}", format_note_content(note, None)),
+        }
 
         if !tag_names.is_empty() {
             println!("Tags: {}", tag_names.join(" "));
@@ -720,48 +2341,58 @@ fn execute_search(query: &str, limit: Option<usize>, service: NoteService) -> Re
     }
 
     // Display search metadata
-    println!("---");
-    println!("Query expansion: {}", metadata.expanded_fts_query);
-    println!(
-        "Results: {} from FTS, {} from graph{}",
-        metadata.fts_result_count,
-        metadata.graph_result_count,
-        if metadata.graph_skipped {
-            " (graph skipped: sparse activation)"
-        } else {
-            ""
-        }
-    );
+    if let Some(metadata) = metadata {
+        println!("---");
+        println!("Query expansion: {}", metadata.expanded_fts_query);
+        println!(
+            "Results: {} from FTS, {} from graph{}",
+            metadata.fts_result_count,
+            metadata.graph_result_count,
+            if metadata.graph_skipped {
+                " (graph skipped: sparse activation)"
+            } else {
+                ""
+            }
+        );
+    }
 
     Ok(())
 }
 
 /// Handles the graph-search command by searching notes using spreading activation.
-fn handle_graph_search(cmd: &GraphSearchCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+fn handle_graph_search(cmd: &GraphSearchCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
     let service = NoteService::new(db);
 
-    execute_graph_search(&cmd.query, cmd.limit, service)
+    execute_graph_search(&cmd.query, cmd.limit, cmd.explain, service)
 }
 
 /// Executes the graph-search command logic with a provided NoteService.
 ///
 /// This function is separated from `handle_graph_search` to allow testing with in-memory databases.
-fn execute_graph_search(query: &str, limit: Option<usize>, service: NoteService) -> Result<()> {
+/// With `explain`, each result is followed by the activation paths that reached it.
+fn execute_graph_search(
+    query: &str,
+    limit: Option<usize>,
+    explain: bool,
+    service: NoteService,
+) -> Result<()> {
     use time::macros::format_description;
 
     // Apply default limit of 10 when not specified
     let limit = limit.unwrap_or(10);
 
     // Call service graph_search method - returns SearchResult with note and relevance_score
-    let results = service
-        .graph_search(query, Some(limit))
-        .context("Failed to perform graph search")?;
+    let results = if explain {
+        service.graph_search_explained(query, Some(limit))
+    } else {
+        service.graph_search(query, Some(limit))
+    }
+    .context("Failed to perform graph search")?;
 
     // Handle empty results
     if results.is_empty() {
@@ -795,7 +2426,98 @@ fn execute_graph_search(query: &str, limit: Option<usize>, service: NoteService)
         println!("Created: {}", timestamp);
 
         // Display content using stacked format (original + enhanced if available)
-        print!("{}", format_note_content(note));
+        print!("{}", format_note_content(note, None));
+
+        if !tag_names.is_empty() {
+            println!("Tags: {}", tag_names.join(" "));
+        }
+        if let Some(paths) = &result.explanation {
+            print!("{}", format_activation_paths(paths));
+        }
+        println!(); // Blank line separator
+    }
+
+    Ok(())
+}
+
+/// Formats graph search activation paths, one indented line per path.
+fn format_activation_paths(paths: &[cons::spreading_activation::ActivationPath]) -> String {
+    let mut output = String::from("Why:\n");
+    for path in paths {
+        output.push_str(&format!("  {}\n", path));
+    }
+    output
+}
+
+/// Handles the related command by finding notes connected to a seed note.
+fn handle_related(cmd: &RelatedCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database and create service
+    let db = open_database(db_path)?;
+    let service = NoteService::new(db);
+
+    execute_related(NoteId::new(cmd.id), cmd.limit, service)
+}
+
+/// Executes the related command logic with a provided NoteService.
+///
+/// This function is separated from `handle_related` to allow testing with in-memory databases.
+/// Results come from `graph_search_from_note` (which excludes the seed note) and are printed
+/// in the search format, most relevant first, with the relevance score as a percentage.
+fn execute_related(note_id: NoteId, limit: Option<usize>, service: NoteService) -> Result<()> {
+    use time::macros::format_description;
+
+    if service
+        .get_note(note_id)
+        .context("Failed to get note")?
+        .is_none()
+    {
+        return Err(NotFound(format!("Note with id {} does not exist", note_id)).into());
+    }
+
+    // Apply default limit of 10 when not specified
+    let limit = limit.unwrap_or(10);
+
+    let mut results = service
+        .graph_search_from_note(note_id, Some(limit))
+        .context("Failed to find related notes")?;
+    results.sort_by(|a, b| {
+        b.relevance_score
+            .total_cmp(&a.relevance_score)
+            .then_with(|| a.note.id().get().cmp(&b.note.id().get()))
+    });
+
+    if results.is_empty() {
+        println!("No related notes found for note {note_id}");
+        return Ok(());
+    }
+
+    // Format descriptor for "YYYY-MM-DD HH:MM"
+    let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+
+    for result in &results {
+        let note = &result.note;
+
+        // Format timestamp as "YYYY-MM-DD HH:MM"
+        let timestamp = note
+            .created_at()
+            .format(&format)
+            .unwrap_or_else(|_| "Invalid date".to_string());
+
+        // Get tag names using batch query
+        let tag_names: Vec<String> = get_tag_names(service.database(), note.tags())?
+            .into_iter()
+            .map(|name| format!("#{}", name))
+            .collect();
+
+        println!("ID: {}", note.id().get());
+        println!("Created: {}", timestamp);
+        println!("Relevance: {:.0}%", result.relevance_score * 100.0);
+
+        // Display content using stacked format (original + enhanced if available)
+        print!("{}", format_note_content(note, None));
 
         if !tag_names.is_empty() {
             println!("Tags: {}", tag_names.join(" "));
@@ -806,6 +2528,48 @@ fn execute_graph_search(query: &str, limit: Option<usize>, service: NoteService)
     Ok(())
 }
 
+/// JSON representation of a note for `--json` output.
+///
+/// Timestamps are RFC3339 and tags are flattened to their display names.
+/// `relevance_score` is only present for search results.
+#[derive(serde::Serialize)]
+struct JsonNote {
+    id: i64,
+    content: String,
+    content_enhanced: Option<String>,
+    enhancement_confidence: Option<f64>,
+    created_at: String,
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    relevance_score: Option<f64>,
+}
+
+impl JsonNote {
+    fn new(note: &cons::Note, relevance_score: Option<f64>, service: &NoteService) -> Result<Self> {
+        use time::format_description::well_known::Rfc3339;
+
+        Ok(Self {
+            id: note.id().get(),
+            content: note.content().to_string(),
+            content_enhanced: note.content_enhanced().map(String::from),
+            enhancement_confidence: note.enhancement_confidence(),
+            created_at: note
+                .created_at()
+                .format(&Rfc3339)
+                .context("Failed to format timestamp")?,
+            tags: get_tag_names(service.database(), note.tags())?,
+            relevance_score,
+        })
+    }
+}
+
+/// Prints a value as pretty-printed JSON to stdout.
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    let output = serde_json::to_string_pretty(value).context("Failed to serialize JSON")?;
+    println!("{output}");
+    Ok(())
+}
+
 /// Formats note content for display using stacked format.
 ///
 /// Returns a formatted string with:
@@ -815,19 +2579,26 @@ fn execute_graph_search(query: &str, limit: Option<usize>, service: NoteService)
 /// - Confidence displayed as percentage: `(enhanced: 85% confidence)`
 ///
 /// When no enhancement is available, returns only the original content.
-fn format_note_content(note: &cons::Note) -> String {
+/// With `max_chars`, both contents are shortened with [`cons::Note::excerpt`].
+fn format_note_content(note: &cons::Note, max_chars: Option<usize>) -> String {
     let mut output = String::new();
 
     // Display original content first
     output.push_str("Content: ");
-    output.push_str(note.content());
+    match max_chars {
+        Some(max_chars) => output.push_str(&note.excerpt(max_chars)),
+        None => output.push_str(note.content()),
+    }
     output.push('\n');
 
     // Display enhanced content if available
     if let Some(enhanced) = note.content_enhanced() {
         output.push_str("---\n");
         output.push_str("Enhanced: ");
-        output.push_str(enhanced);
+        match max_chars.and_then(|max_chars| note.enhanced_excerpt(max_chars)) {
+            Some(excerpt) => output.push_str(&excerpt),
+            None => output.push_str(enhanced),
+        }
         output.push('\n');
 
         // Show confidence as percentage
@@ -842,16 +2613,21 @@ fn format_note_content(note: &cons::Note) -> String {
 // get_tag_names moved to src/utils.rs for reuse across CLI and TUI
 
 /// Handles the ask command.
-fn handle_ask(cmd: &AskCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+fn handle_ask(cmd: &AskCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
     let service = NoteService::new(db);
 
-    execute_ask(&cmd.query, cmd.top_k, cmd.verbose, service)
+    execute_ask(
+        &cmd.query,
+        cmd.top_k,
+        cmd.verbose,
+        cmd.model.as_deref(),
+        service,
+    )
 }
 
 /// Extracts keywords from a natural language query by removing common stop words.
@@ -899,10 +2675,12 @@ fn extract_search_keywords(query: &str) -> String {
 /// Executes the ask command logic with a provided NoteService.
 ///
 /// This function is separated from `handle_ask` to allow testing with in-memory databases.
+/// `model` overrides `OLLAMA_MODEL` for this question.
 fn execute_ask(
     query: &str,
     top_k: usize,
     verbose: bool,
+    model: Option<&str>,
     service: NoteService,
 ) -> Result<()> {
     // Validate query
@@ -927,20 +2705,14 @@ fn execute_ask(
     }
 
     // Create Ollama client and QueryAnswerer
-    let ollama_client = OllamaClientBuilder::new()
-        .build()
-        .context("Failed to create Ollama client")?;
-
-    let model = ollama_client.model().to_string();
-    let model = if model.is_empty() {
-        "deepseek-r1:8b".to_string()
-    } else {
-        model
-    };
+    let client = Arc::new(
+        OllamaClientBuilder::new()
+            .build()
+            .context("Failed to create Ollama client")?,
+    );
+    let model = resolve_model(&client, model)?;
 
-    let answerer = QueryAnswererBuilder::new()
-        .client(Arc::new(ollama_client))
-        .build();
+    let answerer = QueryAnswererBuilder::new().client(client).build();
 
     // Generate answer with citations
     let result = answerer
@@ -994,23 +2766,112 @@ fn display_query_result(result: &cons::QueryResult, verbose: bool) -> Result<()>
 }
 
 /// Handles the tags command by dispatching to subcommand handlers.
-fn handle_tags(cmd: &TagsCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+fn handle_tags(cmd: &TagsCommand, json: bool, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
 
     // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let db = open_database(db_path)?;
 
     match &cmd.command {
-        TagsCommands::List => execute_tags_list(db),
+        None => execute_tags(cmd.sort, cmd.limit, cmd.verbose, json, db),
+        Some(TagsCommands::List) => execute_tags_list(json, db),
+        Some(TagsCommands::Rename { old, new }) => execute_tags_rename(old, new, db),
+        Some(TagsCommands::Suggest { text, model }) => {
+            let text = if text == "-" {
+                read_note_from_reader(std::io::stdin())?
+            } else {
+                text.clone()
+            };
+            execute_tags_suggest(&text, model.as_deref(), db)
+        }
+        Some(TagsCommands::Verify { id, tag }) => execute_tags_verify(NoteId::new(*id), tag, db),
+        Some(TagsCommands::Describe { tag, text }) => execute_tags_describe(tag, text, db),
+        Some(TagsCommands::AddBulk { tags, where_tag }) => {
+            execute_tags_add_bulk(tags, where_tag, db)
+        }
+        Some(TagsCommands::Cooccur { min }) => execute_tags_cooccur(*min, json, db),
+        Some(TagsCommands::Graph {
+            format,
+            min,
+            output,
+        }) => execute_tags_graph(*format, *min, output.as_deref(), db),
+    }
+}
+
+/// Executes the bare tags command, listing every tag with its note count.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+/// Orphan tags (no notes) are included so unused tags can be found and cleaned up.
+/// With `verbose`, each tag's description is shown as well.
+fn execute_tags(
+    sort: TagSort,
+    limit: Option<usize>,
+    verbose: bool,
+    json: bool,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let mut tags = service
+        .list_tags_with_counts()
+        .context("Failed to list tags")?;
+
+    // Service returns tags ordered by name; count order keeps name as tiebreaker
+    if sort == TagSort::Count {
+        tags.sort_by(|a, b| b.2.cmp(&a.2));
+    }
+
+    if let Some(limit) = limit {
+        tags.truncate(limit);
+    }
+
+    let descriptions = if verbose {
+        tags.iter()
+            .map(|(_, name, _)| service.get_tag_description(name))
+            .collect::<Result<Vec<_>>>()
+            .context("Failed to get tag descriptions")?
+    } else {
+        vec![None; tags.len()]
+    };
+
+    if json {
+        let json_tags: Vec<serde_json::Value> = tags
+            .iter()
+            .zip(&descriptions)
+            .map(|((_, name, note_count), description)| {
+                let mut value = serde_json::json!({
+                    "name": name,
+                    "note_count": note_count,
+                });
+                if verbose {
+                    value["description"] = serde_json::json!(description);
+                }
+                value
+            })
+            .collect();
+        return print_json(&json_tags);
+    }
+
+    if tags.is_empty() {
+        println!("No tags found");
+        return Ok(());
+    }
+
+    for ((_, name, note_count), description) in tags.iter().zip(&descriptions) {
+        println!("#{name} ({note_count})");
+        if let Some(description) = description {
+            println!("    {description}");
+        }
     }
+
+    Ok(())
 }
 
 /// Executes the tags list command logic with a provided database.
 ///
 /// This function is separated from `handle_tags` to allow testing with in-memory databases.
-fn execute_tags_list(db: Database) -> Result<()> {
+fn execute_tags_list(json: bool, db: Database) -> Result<()> {
     let service = NoteService::new(db);
 
     // Fetch all tags with statistics
@@ -1018,6 +2879,20 @@ fn execute_tags_list(db: Database) -> Result<()> {
         .get_tags_with_stats()
         .context("Failed to get tags with stats")?;
 
+    if json {
+        let json_tags: Vec<serde_json::Value> = tags
+            .iter()
+            .map(|(_, name, note_count, degree_centrality)| {
+                serde_json::json!({
+                    "name": name,
+                    "note_count": note_count,
+                    "degree_centrality": degree_centrality,
+                })
+            })
+            .collect();
+        return print_json(&json_tags);
+    }
+
     if tags.is_empty() {
         println!("No tags found");
         return Ok(());
@@ -1042,41 +2917,304 @@ fn execute_tags_list(db: Database) -> Result<()> {
     Ok(())
 }
 
-/// Handles the tag-alias command by dispatching to subcommand handlers.
-fn handle_tag_alias(cmd: &TagAliasCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+/// Executes the tags rename command logic with a provided database.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+fn execute_tags_rename(old: &str, new: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
 
-    // Open database and create service
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let service = NoteService::new(db);
 
-    match &cmd.command {
-        TagAliasCommands::Add { alias, canonical } => execute_tag_alias_add(alias, canonical, db),
-        TagAliasCommands::List => execute_tag_alias_list(db),
-        TagAliasCommands::Remove { alias } => execute_tag_alias_remove(alias, db),
-    }
+    service
+        .rename_tag(old, new)
+        .with_context(|| format!("Failed to rename tag '{old}'"))?;
+
+    println!(
+        "Tag renamed: '{}' -> '{}'",
+        TagNormalizer::normalize_tag(old),
+        TagNormalizer::normalize_tag(new)
+    );
+
+    Ok(())
 }
 
-/// Handles the hierarchy command by dispatching to subcommand handlers.
-fn handle_hierarchy(cmd: &HierarchyCommand) -> Result<()> {
-    // Get database path and ensure directory exists
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
+/// Executes the tags describe command logic with a provided database.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+fn execute_tags_describe(tag: &str, text: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
 
-    // Open database
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    service.set_tag_description(tag, text)?;
 
-    match &cmd.command {
-        HierarchyCommands::Suggest => execute_hierarchy_suggest(db),
+    let name = cons::TagNormalizer::normalize_tag(tag);
+    if text.trim().is_empty() {
+        println!("Cleared description of tag '{name}'");
+    } else {
+        println!("Described tag '{name}'");
     }
+
+    Ok(())
 }
 
-/// Executes the tag-alias add command logic with a provided database.
+/// Executes the tags add-bulk command logic with a provided database.
 ///
-/// This function is separated from `handle_tag_alias` to allow testing with in-memory databases.
-fn execute_tag_alias_add(alias: &str, canonical: &str, db: Database) -> Result<()> {
-    use cons::TagNormalizer;
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+fn execute_tags_add_bulk(tags: &str, where_tag: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let tags = parse_tags(tags);
+    if tags.is_empty() {
+        anyhow::bail!("Tags cannot be empty");
+    }
+
+    let filter = cons::TagNormalizer::normalize_tag(where_tag);
+    for tag in &tags {
+        let added = service.add_tag_to_notes_with_tag(tag, where_tag, TagSource::User)?;
+        let name = cons::TagNormalizer::normalize_tag(tag);
+        println!("Added '{name}' to {added} note(s) tagged '{filter}'");
+    }
+
+    Ok(())
+}
+
+/// Executes the tags cooccur command logic with a provided database.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+fn execute_tags_cooccur(min_count: usize, json: bool, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let pairs = service
+        .tag_cooccurrence(min_count)
+        .context("Failed to count tag co-occurrence")?;
+
+    if json {
+        let json_pairs: Vec<serde_json::Value> = pairs
+            .iter()
+            .map(|(first, second, count)| {
+                serde_json::json!({
+                    "tags": [first, second],
+                    "note_count": count,
+                })
+            })
+            .collect();
+        return print_json(&json_pairs);
+    }
+
+    if pairs.is_empty() {
+        println!("No tag pairs share at least {} notes", min_count);
+        return Ok(());
+    }
+
+    for (first, second, count) in &pairs {
+        let note_word = if *count == 1 { "note" } else { "notes" };
+        println!("{} + {} ({} {})", first, second, count, note_word);
+    }
+
+    Ok(())
+}
+
+/// Executes the tags graph command logic with a provided database.
+///
+/// Writes to `output` when given (reporting the edge count), otherwise to stdout.
+fn execute_tags_graph(
+    format: GraphExportFormatArg,
+    min_count: usize,
+    output: Option<&std::path::Path>,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+
+    match (format, output) {
+        (GraphExportFormatArg::Dot, Some(path)) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut writer = std::io::BufWriter::new(file);
+            let count = cons::export_cooccurrence_dot(&service, min_count, &mut writer)?;
+            println!("Exported {} edges to {}", count, path.display());
+        }
+        (GraphExportFormatArg::Dot, None) => {
+            let mut stdout = std::io::stdout().lock();
+            cons::export_cooccurrence_dot(&service, min_count, &mut stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes the tags verify command logic with a provided database.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+fn execute_tags_verify(note_id: NoteId, tag: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    if service
+        .get_note(note_id)
+        .context("Failed to get note")?
+        .is_none()
+    {
+        return Err(NotFound(format!("Note with id {} does not exist", note_id)).into());
+    }
+
+    service.verify_tag(note_id, tag)?;
+
+    println!(
+        "Verified tag '{}' on note {}",
+        cons::TagNormalizer::normalize_tag(tag),
+        note_id
+    );
+
+    Ok(())
+}
+
+/// Executes the tags suggest command, previewing auto-tags for arbitrary text.
+///
+/// This function is separated from `handle_tags` to allow testing with in-memory databases.
+/// Uses the same tagger configuration as capture (confidence threshold, tag cap), but never
+/// writes to the database: alias opportunities are reported, not created.
+fn execute_tags_suggest(text: &str, model: Option<&str>, db: Database) -> Result<()> {
+    if text.trim().is_empty() {
+        anyhow::bail!("Text cannot be empty");
+    }
+
+    let service = NoteService::new(db);
+    let ai = AiPipeline::connect(model)?;
+    let model = &ai.model;
+    let tags = ai.generate_tags(text)?;
+
+    if tags.is_empty() {
+        println!("No tags suggested (model: {model}).");
+        return Ok(());
+    }
+
+    println!("Suggested tags (model: {model}):");
+    for line in format_tag_suggestions(&service, &tags)? {
+        println!("  {line}");
+    }
+
+    Ok(())
+}
+
+/// Formats suggested tags, most confident first (ties by name), one line per tag.
+///
+/// Tags that `NoteService::detect_alias_opportunity` would map to an existing canonical tag are
+/// annotated with that tag's name. Read-only.
+fn format_tag_suggestions(
+    service: &NoteService,
+    tags: &std::collections::HashMap<String, f64>,
+) -> Result<Vec<String>> {
+    let mut sorted: Vec<(&String, &f64)> = tags.iter().collect();
+    sorted.sort_by(|(a_name, a_conf), (b_name, b_conf)| {
+        b_conf.total_cmp(a_conf).then_with(|| a_name.cmp(b_name))
+    });
+
+    let mut lines = Vec::with_capacity(sorted.len());
+    for (name, confidence) in sorted {
+        let mut line = format!("{} ({:.0}%)", name, confidence * 100.0);
+        if let Some(canonical_tag_id) = service.detect_alias_opportunity(name)? {
+            let canonical_name: String = service
+                .database()
+                .connection()
+                .query_row(
+                    "SELECT name FROM tags WHERE id = ?1",
+                    [canonical_tag_id.get()],
+                    |row| row.get(0),
+                )
+                .with_context(|| {
+                    format!("Failed to get canonical tag name for id {canonical_tag_id}")
+                })?;
+            line.push_str(&format!(" -> alias for existing tag '{canonical_name}'"));
+        }
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+/// Handles the tag-alias command by dispatching to subcommand handlers.
+fn handle_tag_alias(cmd: &TagAliasCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database and create service
+    let db = open_database(db_path)?;
+
+    match &cmd.command {
+        TagAliasCommands::Add { alias, canonical } => execute_tag_alias_add(alias, canonical, db),
+        TagAliasCommands::List => execute_tag_alias_list(db),
+        TagAliasCommands::Remove { alias } => execute_tag_alias_remove(alias, db),
+        TagAliasCommands::Retarget { alias, canonical } => {
+            execute_tag_alias_retarget(alias, canonical, db)
+        }
+        TagAliasCommands::Rename { old, new } => execute_tag_alias_rename(old, new, db),
+        TagAliasCommands::Import { path } => execute_tag_alias_import(path, db),
+    }
+}
+
+/// Handles the hierarchy command by dispatching to subcommand handlers.
+fn handle_hierarchy(cmd: &HierarchyCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    match &cmd.command {
+        HierarchyCommands::Suggest {
+            model,
+            min_confidence,
+        } => execute_hierarchy_suggest(model.as_deref(), *min_confidence, db),
+        HierarchyCommands::Show { unverified } => execute_hierarchy_show(*unverified, db),
+        HierarchyCommands::Add {
+            source,
+            target,
+            hierarchy_type,
+            confidence,
+        } => execute_hierarchy_add(source, target, *hierarchy_type, *confidence, db),
+        HierarchyCommands::Remove { source, target } => {
+            execute_hierarchy_remove(source, target, db)
+        }
+        HierarchyCommands::Verify { source, target } => {
+            execute_hierarchy_verify(source, target, db)
+        }
+        HierarchyCommands::Prune { min_confidence } => execute_hierarchy_prune(*min_confidence, db),
+        HierarchyCommands::Export { format, output } => {
+            execute_hierarchy_export(*format, output.as_deref(), db)
+        }
+    }
+}
+
+/// Executes the hierarchy export command logic with a provided database.
+///
+/// Writes to `output` when given (reporting the edge count), otherwise to stdout.
+fn execute_hierarchy_export(
+    format: GraphExportFormatArg,
+    output: Option<&std::path::Path>,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+
+    match (format, output) {
+        (GraphExportFormatArg::Dot, Some(path)) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut writer = std::io::BufWriter::new(file);
+            let count = cons::export_hierarchy_dot(&service, &mut writer)?;
+            println!("Exported {} edges to {}", count, path.display());
+        }
+        (GraphExportFormatArg::Dot, None) => {
+            let mut stdout = std::io::stdout().lock();
+            cons::export_hierarchy_dot(&service, &mut stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes the tag-alias add command logic with a provided database.
+///
+/// This function is separated from `handle_tag_alias` to allow testing with in-memory databases.
+fn execute_tag_alias_add(alias: &str, canonical: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
 
     // Normalize both alias and canonical before processing
     let normalized_alias = TagNormalizer::normalize_tag(alias);
@@ -1189,18 +3327,222 @@ fn execute_tag_alias_remove(alias: &str, db: Database) -> Result<()> {
     Ok(())
 }
 
+/// Executes the tag-alias retarget command logic with a provided database.
+///
+/// This function is separated from `handle_tag_alias` to allow testing with in-memory databases.
+/// The alias must already exist; the new canonical tag is created if needed.
+fn execute_tag_alias_retarget(alias: &str, canonical: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
+
+    // Normalize both alias and canonical before processing
+    let normalized_alias = TagNormalizer::normalize_tag(alias);
+    let normalized_canonical = TagNormalizer::normalize_tag(canonical);
+
+    let service = NoteService::new(db);
+
+    // Check the alias first so a typo does not leave a stray canonical tag behind
+    if service
+        .resolve_alias(&normalized_alias)
+        .context("Failed to resolve alias")?
+        .is_none()
+    {
+        return Err(NotFound(format!("Alias '{}' does not exist", normalized_alias)).into());
+    }
+
+    let canonical_tag_id = service
+        .get_or_create_tag(&normalized_canonical)
+        .context("Failed to get or create canonical tag")?;
+
+    service
+        .update_alias_canonical(&normalized_alias, canonical_tag_id)
+        .with_context(|| {
+            format!(
+                "Failed to retarget alias '{}' -> '{}'",
+                normalized_alias, normalized_canonical
+            )
+        })?;
+
+    println!(
+        "Alias retargeted: '{}' -> '{}'",
+        normalized_alias, normalized_canonical
+    );
+
+    Ok(())
+}
+
+/// Executes the tag-alias rename command logic with a provided database.
+///
+/// This function is separated from `handle_tag_alias` to allow testing with in-memory databases.
+fn execute_tag_alias_rename(old: &str, new: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
+
+    let service = NoteService::new(db);
+    service.rename_alias(old, new)?;
+
+    println!(
+        "Alias renamed: '{}' -> '{}'",
+        TagNormalizer::normalize_tag(old),
+        TagNormalizer::normalize_tag(new)
+    );
+
+    Ok(())
+}
+
+/// Executes the tag-alias import command logic with a provided database.
+///
+/// This function is separated from `handle_tag_alias` to allow testing with in-memory databases.
+/// All aliases are created in one transaction; mappings that would form an alias chain are
+/// skipped with a warning.
+fn execute_tag_alias_import(path: &std::path::Path, db: Database) -> Result<()> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open alias file {}", path.display()))?;
+    let mappings = parse_alias_mappings(std::io::BufReader::new(file))?;
+
+    let service = NoteService::new(db);
+    let summary = service
+        .import_aliases(&mappings)
+        .context("Failed to import aliases")?;
+
+    for (alias, canonical) in &summary.skipped {
+        eprintln!("Warning: skipped '{alias} = {canonical}': it would form an alias chain");
+    }
+
+    let noun = if summary.created == 1 {
+        "alias"
+    } else {
+        "aliases"
+    };
+    println!("Imported {} {noun}", summary.created);
+    if !summary.skipped.is_empty() {
+        println!("Skipped {} (see warnings)", summary.skipped.len());
+    }
+
+    Ok(())
+}
+
+/// Handles the tag-preset command by dispatching to subcommand handlers.
+fn handle_tag_preset(cmd: &TagPresetCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    match &cmd.command {
+        TagPresetCommands::Add { name, tags } => execute_tag_preset_add(name, tags, db),
+        TagPresetCommands::List => execute_tag_preset_list(db),
+        TagPresetCommands::Remove { name } => execute_tag_preset_remove(name, db),
+    }
+}
+
+/// Executes the tag-preset add command logic with a provided database.
+///
+/// This function is separated from `handle_tag_preset` to allow testing with in-memory databases.
+fn execute_tag_preset_add(name: &str, tags: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let parsed = parse_tags(tags);
+    let tag_refs: Vec<&str> = parsed.iter().map(String::as_str).collect();
+    let saved = service.set_tag_preset(name, &tag_refs)?;
+
+    println!(
+        "Preset saved: '{}' -> {}",
+        cons::TagNormalizer::normalize_tag(name),
+        saved.join(", ")
+    );
+
+    Ok(())
+}
+
+/// Executes the tag-preset list command logic with a provided database.
+///
+/// This function is separated from `handle_tag_preset` to allow testing with in-memory databases.
+fn execute_tag_preset_list(db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let presets = service
+        .list_tag_presets()
+        .context("Failed to list tag presets")?;
+
+    if presets.is_empty() {
+        println!("No tag presets found");
+        return Ok(());
+    }
+
+    for (name, tags) in presets {
+        println!("{}: {}", name, tags.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Executes the tag-preset remove command logic with a provided database.
+///
+/// This function is separated from `handle_tag_preset` to allow testing with in-memory databases.
+fn execute_tag_preset_remove(name: &str, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+    let name = cons::TagNormalizer::normalize_tag(name);
+
+    if !service
+        .remove_tag_preset(&name)
+        .context("Failed to remove tag preset")?
+    {
+        return Err(NotFound(format!("Tag preset '{}' does not exist", name)).into());
+    }
+
+    println!("Preset removed: '{}'", name);
+
+    Ok(())
+}
+
+/// Parses `alias = canonical` lines, ignoring blank lines and `#` comments.
+///
+/// Returns the raw (not yet normalized) pairs in file order.
+fn parse_alias_mappings(reader: impl std::io::BufRead) -> Result<Vec<(String, String)>> {
+    let mut mappings = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read alias file")?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parsed = line
+            .split_once('=')
+            .map(|(alias, canonical)| (alias.trim(), canonical.trim()))
+            .filter(|(alias, canonical)| !alias.is_empty() && !canonical.is_empty());
+        let Some((alias, canonical)) = parsed else {
+            anyhow::bail!(
+                "Invalid alias mapping on line {}: expected 'alias = canonical'",
+                index + 1
+            );
+        };
+
+        mappings.push((alias.to_string(), canonical.to_string()));
+    }
+
+    Ok(mappings)
+}
+
 /// Executes the hierarchy suggest command logic with a provided database.
 ///
 /// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
 /// Uses LLM to analyze existing tags and automatically populate the edges table with
-/// broader/narrower relationships (generic and partitive).
+/// broader/narrower relationships (generic and partitive). Tag pairs that share at
+/// least two notes are passed to the suggester as co-occurrence hints.
 ///
 /// # Fail-Safe Behavior
 ///
-/// - Auto-detects model from Ollama if OLLAMA_MODEL not set
+/// - Uses `model` (the `--model` flag) when given, else OLLAMA_MODEL
+/// - Auto-detects model from Ollama if neither is set
 /// - Returns early with message if no tags exist
 /// - Returns clear error if Ollama not reachable or no models installed
-fn execute_hierarchy_suggest(db: Database) -> Result<()> {
+fn execute_hierarchy_suggest(
+    model: Option<&str>,
+    min_confidence: Option<f64>,
+    db: Database,
+) -> Result<()> {
     let service = NoteService::new(db);
 
     // Get all tags that have at least one associated note
@@ -1230,26 +3572,23 @@ fn execute_hierarchy_suggest(db: Database) -> Result<()> {
             .context("Failed to build Ollama client")?,
     );
 
-    // Try OLLAMA_MODEL env var first, then auto-detect from Ollama
-    let model = match std::env::var("OLLAMA_MODEL") {
-        Ok(m) if !m.is_empty() => m,
-        _ => {
-            let models = client.list_models().context(
-                "Ollama not reachable. Is it running? Try: ollama serve",
-            )?;
-
-            models.into_iter().next().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No models installed in Ollama. Install one with: ollama pull gemma3:4b"
-                )
-            })?
-        }
-    };
+    let model = resolve_model(&client, model)?;
+
+    // Tags that share notes are the likeliest hierarchy candidates; pass them as hints
+    let cooccurrence = service
+        .tag_cooccurrence(2)
+        .context("Failed to compute tag co-occurrence")?;
 
-    let suggester = HierarchySuggesterBuilder::new().client(client).build();
+    let mut suggester = HierarchySuggesterBuilder::new()
+        .client(client)
+        .cooccurrence(cooccurrence);
+    if let Some(min_confidence) = min_confidence {
+        suggester = suggester.min_confidence(min_confidence);
+    }
+    let suggester = suggester.build();
 
     // Call suggest_relationships (returns Vec<RelationshipSuggestion>)
-    // Already filtered to confidence >= 0.7 by HierarchySuggester
+    // Already filtered to the minimum confidence (0.7 by default) by HierarchySuggester
     let suggestions = suggester
         .suggest_relationships(&model, tag_names)
         .context("Failed to suggest relationships")?;
@@ -1262,7 +3601,13 @@ fn execute_hierarchy_suggest(db: Database) -> Result<()> {
     // Build edges for batch creation
     // Need to resolve tag names to TagIds
     let mut edges = Vec::new();
+    let mut progress = Progress::stderr(suggestions.len());
     for suggestion in &suggestions {
+        progress.step(format_args!(
+            "resolving tags {} -> {}",
+            suggestion.source_tag, suggestion.target_tag
+        ));
+
         // Resolve source and target tag names to IDs
         let source_tag_id = service
             .get_or_create_tag(&suggestion.source_tag)
@@ -1280,6 +3625,7 @@ fn execute_hierarchy_suggest(db: Database) -> Result<()> {
             Some(model.as_str()),
         ));
     }
+    progress.finish();
 
     // Create edges in batch (atomic transaction)
     let created_count = service
@@ -1303,30 +3649,543 @@ fn execute_hierarchy_suggest(db: Database) -> Result<()> {
     Ok(())
 }
 
-/// Handles the tui command by launching the interactive terminal UI.
+/// Executes the hierarchy remove command logic with a provided database.
 ///
-/// Calls the `tui::run()` function to initialize the TUI and start the event loop.
-/// Terminal state is always restored on exit, even on error.
-fn handle_tui() -> Result<()> {
-    cons::tui::run().context("Failed to run TUI")
-}
+/// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
+/// Removing an edge that does not exist is a no-op.
+fn execute_hierarchy_remove(source: &str, target: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
 
-/// Handles the doctor command by dispatching to health check or enhance subcommand.
-fn handle_doctor(cmd: &DoctorCommand) -> Result<()> {
-    let db_path = get_database_path()?;
-    ensure_database_directory(&db_path)?;
-    let db = Database::open(&db_path).context("Failed to open database")?;
+    let service = NoteService::new(db);
 
-    match &cmd.command {
-        None => execute_doctor_health(&db_path.to_string_lossy(), db),
-        Some(DoctorSubcommand::Enhance) => execute_doctor_enhance(db),
-    }
-}
+    // Resolve tag names (normalization and alias resolution happen here)
+    let source_tag_id = service
+        .get_or_create_tag(source)
+        .with_context(|| format!("Failed to resolve tag '{source}'"))?;
+    let target_tag_id = service
+        .get_or_create_tag(target)
+        .with_context(|| format!("Failed to resolve tag '{target}'"))?;
 
-/// Executes the doctor health check command.
-fn execute_doctor_health(db_path: &str, db: Database) -> Result<()> {
-    let service = NoteService::new(db);
-    cons::doctor::run_health_checks(db_path, &service)
+    service
+        .delete_edge(source_tag_id, target_tag_id)
+        .context("Failed to remove edge")?;
+
+    println!(
+        "Edge removed: '{}' -> '{}'",
+        TagNormalizer::normalize_tag(source),
+        TagNormalizer::normalize_tag(target)
+    );
+
+    Ok(())
+}
+
+/// Executes the hierarchy verify command logic with a provided database.
+///
+/// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
+/// Both tag names are normalized (and aliases resolved) before the edge is looked up.
+/// Unknown tags are reported as errors rather than created.
+fn execute_hierarchy_verify(source: &str, target: &str, db: Database) -> Result<()> {
+    use cons::TagNormalizer;
+
+    let service = NoteService::new(db);
+
+    let find = |name: &str| -> Result<cons::TagId> {
+        service
+            .find_tag(name)
+            .with_context(|| format!("Failed to resolve tag '{name}'"))?
+            .ok_or_else(|| {
+                NotFound(format!(
+                    "Tag '{}' does not exist",
+                    TagNormalizer::normalize_tag(name)
+                ))
+                .into()
+            })
+    };
+    let source_tag_id = find(source)?;
+    let target_tag_id = find(target)?;
+
+    service.verify_edge(source_tag_id, target_tag_id)?;
+
+    println!(
+        "Verified edge '{}' -> '{}'",
+        TagNormalizer::normalize_tag(source),
+        TagNormalizer::normalize_tag(target)
+    );
+
+    Ok(())
+}
+
+/// Executes the hierarchy prune command logic with a provided database.
+///
+/// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
+fn execute_hierarchy_prune(min_confidence: f64, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let removed = service
+        .prune_edges(min_confidence)
+        .context("Failed to prune edges")?;
+
+    let noun = if removed == 1 { "edge" } else { "edges" };
+    println!("Pruned {removed} {noun} with confidence below {min_confidence}");
+
+    Ok(())
+}
+
+/// Executes the hierarchy add command logic with a provided database.
+///
+/// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
+/// Both tag names are normalized (and aliases resolved) before the user edge is created.
+fn execute_hierarchy_add(
+    source: &str,
+    target: &str,
+    hierarchy_type: HierarchyTypeArg,
+    confidence: f64,
+    db: Database,
+) -> Result<()> {
+    use cons::TagNormalizer;
+
+    let source_name = TagNormalizer::normalize_tag(source);
+    let target_name = TagNormalizer::normalize_tag(target);
+    if source_name.is_empty() || target_name.is_empty() {
+        anyhow::bail!("Tag name cannot be empty");
+    }
+
+    let service = NoteService::new(db);
+
+    let source_tag_id = service
+        .get_or_create_tag(&source_name)
+        .with_context(|| format!("Failed to resolve tag '{source}'"))?;
+    let target_tag_id = service
+        .get_or_create_tag(&target_name)
+        .with_context(|| format!("Failed to resolve tag '{target}'"))?;
+
+    service
+        .create_user_edge(
+            source_tag_id,
+            target_tag_id,
+            confidence,
+            hierarchy_type.as_str(),
+        )
+        .with_context(|| format!("Failed to add edge '{source_name}' -> '{target_name}'"))?;
+
+    println!(
+        "Edge added: '{}' -> '{}' ({}, {:.0}%)",
+        source_name,
+        target_name,
+        hierarchy_type.as_str(),
+        confidence * 100.0
+    );
+
+    Ok(())
+}
+
+/// Executes the hierarchy show command logic with a provided database.
+///
+/// This function is separated from `handle_hierarchy` to allow testing with in-memory databases.
+/// With `unverified`, only edges the user has not verified are shown.
+fn execute_hierarchy_show(unverified: bool, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let mut edges = service.list_edges().context("Failed to list edges")?;
+
+    if edges.is_empty() {
+        println!("No hierarchy edges yet. Run 'cons hierarchy suggest' to generate some.");
+        return Ok(());
+    }
+
+    if unverified {
+        edges.retain(|edge| !edge.verified());
+        if edges.is_empty() {
+            println!("All hierarchy edges are verified.");
+            return Ok(());
+        }
+    }
+
+    for line in format_hierarchy_tree(&edges) {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Renders hierarchy edges as an indented text tree.
+///
+/// Roots are the broadest tags (targets with no broader edge of their own), sorted by
+/// name. Each narrower tag is listed under its broader tag with the edge's type and
+/// confidence. A tag with several broader tags appears under each of them. Cycles are
+/// cut where a tag reappears on its own path (marked `(cycle)`), and tags only
+/// reachable through a cycle are still printed from an arbitrary member.
+fn format_hierarchy_tree(edges: &[cons::EdgeInfo]) -> Vec<String> {
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+    // Narrower edges grouped under their broader tag (BTreeMap for stable output)
+    let mut children: BTreeMap<&str, Vec<&cons::EdgeInfo>> = BTreeMap::new();
+    for edge in edges {
+        children.entry(edge.target_name()).or_default().push(edge);
+    }
+    for group in children.values_mut() {
+        group.sort_by(|a, b| a.source_name().cmp(b.source_name()));
+    }
+
+    let has_broader: HashSet<&str> = edges.iter().map(|e| e.source_name()).collect();
+    let mut pending: BTreeSet<&str> = children.keys().copied().collect();
+    let mut roots: Vec<&str> = pending
+        .iter()
+        .copied()
+        .filter(|name| !has_broader.contains(name))
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut printed: HashSet<&str> = HashSet::new();
+
+    fn walk<'a>(
+        name: &'a str,
+        depth: usize,
+        children: &BTreeMap<&'a str, Vec<&'a cons::EdgeInfo>>,
+        path: &mut Vec<&'a str>,
+        printed: &mut HashSet<&'a str>,
+        lines: &mut Vec<String>,
+    ) {
+        path.push(name);
+        printed.insert(name);
+        for edge in children.get(name).into_iter().flatten() {
+            let child = edge.source_name();
+            let indent = "  ".repeat(depth + 1);
+            let verified = if edge.verified() { " ✓" } else { "" };
+            let label = format!(
+                "{indent}{child} ({}, {:.0}%){verified}",
+                edge.hierarchy_type(),
+                edge.confidence() * 100.0
+            );
+            if path.contains(&child) {
+                lines.push(format!("{label} (cycle)"));
+            } else {
+                lines.push(label);
+                walk(child, depth + 1, children, path, printed, lines);
+            }
+        }
+        path.pop();
+    }
+
+    loop {
+        for root in roots.drain(..) {
+            lines.push(root.to_string());
+            walk(
+                root,
+                0,
+                &children,
+                &mut Vec::new(),
+                &mut printed,
+                &mut lines,
+            );
+        }
+        // Anything left is only reachable through a cycle
+        pending.retain(|name| !printed.contains(name));
+        match pending.first() {
+            Some(&name) => roots.push(name),
+            None => break,
+        }
+    }
+
+    lines
+}
+
+/// Handles the tui command by launching the interactive terminal UI.
+///
+/// Calls the `tui::run()` function to initialize the TUI and start the event loop.
+/// Terminal state is always restored on exit, even on error.
+fn handle_tui(db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database before the terminal is taken over, so rebuild notices stay visible
+    let db = open_database(db_path)?;
+
+    cons::tui::run(db).context("Failed to run TUI")
+}
+
+/// Handles the export command by writing all notes to a file or stdout.
+fn handle_export(cmd: &ExportCommand, db_path: &std::path::Path) -> Result<()> {
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_export(
+        cmd.format.into(),
+        cmd.tag.as_deref(),
+        cmd.output.as_deref(),
+        db,
+    )
+}
+
+/// Executes the export command logic with a provided database.
+///
+/// With `tag`, exports only that tag's notes as a Markdown document.
+/// Writes to `output` when given (reporting the count), otherwise to stdout.
+fn execute_export(
+    format: ExportFormat,
+    tag: Option<&str>,
+    output: Option<&std::path::Path>,
+    db: Database,
+) -> Result<()> {
+    if tag.is_some() && format != ExportFormat::Markdown {
+        anyhow::bail!("--tag is only supported with --format markdown");
+    }
+
+    let service = NoteService::new(db);
+    let export = |mut writer: &mut dyn std::io::Write| match tag {
+        Some(tag) => cons::export_tag_markdown(&service, tag, &mut writer),
+        None => cons::export_notes(&service, format, &mut writer),
+    };
+
+    match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let mut writer = std::io::BufWriter::new(file);
+            let count = export(&mut writer)?;
+            println!("Exported {} notes to {}", count, path.display());
+        }
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            export(&mut stdout)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the import command by loading notes from a JSON export.
+fn handle_import(cmd: &ImportCommand, db_path: &std::path::Path) -> Result<()> {
+    let file = std::fs::File::open(&cmd.path)
+        .with_context(|| format!("Failed to open {}", cmd.path.display()))?;
+    let notes = cons::read_notes_json(std::io::BufReader::new(file))?;
+
+    // Ensure database directory exists
+    ensure_database_directory(db_path)?;
+
+    // Open database
+    let db = open_database(db_path)?;
+
+    execute_import(
+        &notes,
+        cmd.enhance && !cmd.no_enhance,
+        cmd.skip_duplicates,
+        db,
+    )
+}
+
+/// Executes the import command logic with a provided database.
+///
+/// All notes are inserted in one transaction. Enhancement runs afterwards and
+/// is fail-safe, so enhancement errors never undo the import.
+fn execute_import(
+    notes: &[cons::Note],
+    enhance: bool,
+    skip_duplicates: bool,
+    db: Database,
+) -> Result<()> {
+    let service = NoteService::new(db);
+
+    let summary = service
+        .import_notes(notes, skip_duplicates)
+        .context("Failed to import notes")?;
+
+    println!(
+        "Imported {} notes ({} skipped)",
+        summary.imported.len(),
+        summary.skipped
+    );
+
+    if enhance {
+        for note_id in &summary.imported {
+            let Some(note) = service.get_note(*note_id)? else {
+                continue;
+            };
+            if note.content_enhanced().is_some() {
+                continue;
+            }
+            if let Err(e) = enhance_note(&service, *note_id, note.content(), None) {
+                eprintln!("Enhancement skipped for note {note_id}: {e:#}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the backup command by snapshotting the open database.
+fn handle_backup(cmd: &BackupCommand, db_path: &std::path::Path) -> Result<()> {
+    ensure_database_directory(db_path)?;
+    let db = open_database(db_path)?;
+
+    let output = match &cmd.output {
+        Some(path) => path.clone(),
+        None => default_backup_path(db_path, time::OffsetDateTime::now_utc())?,
+    };
+
+    execute_backup(&output, &db)
+}
+
+/// Returns `{db_dir}/backups/notes-{timestamp}.db` for the given database path.
+fn default_backup_path(
+    db_path: &std::path::Path,
+    now: time::OffsetDateTime,
+) -> Result<std::path::PathBuf> {
+    use time::macros::format_description;
+
+    let format = format_description!("[year][month][day]-[hour][minute][second]");
+    let timestamp = now.format(&format).context("Failed to format timestamp")?;
+    let dir = db_path
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."))
+        .join("backups");
+
+    Ok(dir.join(format!("notes-{timestamp}.db")))
+}
+
+/// Executes the backup command logic with a provided database.
+///
+/// Creates the output's parent directory if needed and reports the bytes written.
+fn execute_backup(output: &std::path::Path, db: &Database) -> Result<()> {
+    ensure_database_directory(output)?;
+    let size = db.backup_to(output)?;
+    println!(
+        "Backed up database to {} ({} bytes)",
+        output.display(),
+        size
+    );
+    Ok(())
+}
+
+/// Handles maintenance subcommands against the database file.
+fn handle_maintenance(cmd: &MaintenanceCommand, db_path: &std::path::Path) -> Result<()> {
+    ensure_database_directory(db_path)?;
+    let db = open_database(db_path)?;
+
+    match &cmd.command {
+        MaintenanceCommands::Check => execute_maintenance_check(&db),
+        MaintenanceCommands::Vacuum => execute_maintenance_vacuum(db_path, &db),
+        MaintenanceCommands::RebuildFts => execute_maintenance_rebuild_fts(&db),
+    }
+}
+
+/// Executes the integrity check, failing when any problem is found.
+fn execute_maintenance_check(db: &Database) -> Result<()> {
+    let problems = db.integrity_check()?;
+    if problems.is_empty() {
+        println!("Database integrity: ok");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+    anyhow::bail!(
+        "Integrity check found {} problem{}",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    );
+}
+
+/// Executes VACUUM and reports the database file size before and after.
+fn execute_maintenance_vacuum(db_path: &std::path::Path, db: &Database) -> Result<()> {
+    let file_size = || -> Result<u64> {
+        Ok(std::fs::metadata(db_path)
+            .with_context(|| format!("Failed to read size of {}", db_path.display()))?
+            .len())
+    };
+
+    let before = file_size()?;
+    db.vacuum().context("Failed to vacuum database")?;
+    let after = file_size()?;
+
+    println!(
+        "Vacuumed database: {} bytes -> {} bytes ({} bytes reclaimed)",
+        before,
+        after,
+        before.saturating_sub(after)
+    );
+    Ok(())
+}
+
+/// Recreates the FTS index and reports how many notes were indexed.
+fn execute_maintenance_rebuild_fts(db: &Database) -> Result<()> {
+    let indexed = db.rebuild_fts().context("Failed to rebuild search index")?;
+    let noun = if indexed == 1 { "note" } else { "notes" };
+    println!("Rebuilt search index: {indexed} {noun} indexed");
+    Ok(())
+}
+
+/// Handles the completions command by printing the script to stdout.
+fn handle_completions(cmd: &CompletionsCommand) -> Result<()> {
+    execute_completions(cmd.shell, &mut std::io::stdout())
+}
+
+/// Writes the completion script for `shell` to `writer`.
+///
+/// The script is generated from the clap definition, so new subcommands and
+/// flags are picked up without changes here.
+fn execute_completions(
+    shell: clap_complete::Shell,
+    writer: &mut impl std::io::Write,
+) -> Result<()> {
+    use clap::CommandFactory;
+
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, writer);
+    writer.flush()?;
+    Ok(())
+}
+
+/// Handles the config command by printing the settings resolved from the environment.
+fn handle_config(json: bool) -> Result<()> {
+    execute_config(&Config::load(), json, &mut std::io::stdout())
+}
+
+/// Writes each setting's name, resolved value, and source to `writer`.
+///
+/// Text output aligns the columns; `--json` emits an array of
+/// `{name, value, source}` objects.
+fn execute_config(config: &Config, json: bool, writer: &mut impl std::io::Write) -> Result<()> {
+    let entries = config.entries();
+
+    if json {
+        let output = serde_json::to_string_pretty(&entries).context("Failed to serialize JSON")?;
+        writeln!(writer, "{output}")?;
+        return Ok(());
+    }
+
+    let name_width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    let value_width = entries.iter().map(|e| e.value.len()).max().unwrap_or(0);
+    for entry in &entries {
+        writeln!(
+            writer,
+            "{:<name_width$}  {:<value_width$}  ({})",
+            entry.name, entry.value, entry.source
+        )?;
+    }
+    Ok(())
+}
+
+/// Handles the doctor command by dispatching to health check or enhance subcommand.
+fn handle_doctor(cmd: &DoctorCommand, db_path: &std::path::Path) -> Result<()> {
+    ensure_database_directory(db_path)?;
+    let db = open_database(db_path)?;
+
+    match &cmd.command {
+        None => execute_doctor_health(&db_path.to_string_lossy(), cmd.repair, db),
+        Some(DoctorSubcommand::Enhance) => execute_doctor_enhance(db),
+    }
+}
+
+/// Executes the doctor health check command, repairing the search index first if asked.
+fn execute_doctor_health(db_path: &str, repair: bool, db: Database) -> Result<()> {
+    let service = NoteService::new(db);
+    cons::doctor::run_health_checks(db_path, &service, repair)
 }
 
 /// Executes the doctor enhance (backfill) command.
@@ -1359,6 +4218,34 @@ fn execute_doctor_enhance(db: Database) -> Result<()> {
     Ok(())
 }
 
+/// Parses a `--since`/`--until` value into a Unix timestamp.
+///
+/// Accepts either a calendar date (`YYYY-MM-DD`, interpreted in UTC) or a full
+/// RFC3339 timestamp. A bare date expands to the start of that day, or to the
+/// last second of that day when `end_of_day` is set, so both bounds are inclusive.
+fn parse_date_bound(value: &str, end_of_day: bool) -> Result<i64> {
+    use time::format_description::well_known::Rfc3339;
+    use time::macros::format_description;
+    use time::{Date, OffsetDateTime, Time};
+
+    let value = value.trim();
+
+    if let Ok(datetime) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Ok(datetime.unix_timestamp());
+    }
+
+    if let Ok(date) = Date::parse(value, format_description!("[year]-[month]-[day]")) {
+        let time = if end_of_day {
+            Time::from_hms(23, 59, 59).expect("valid time")
+        } else {
+            Time::MIDNIGHT
+        };
+        return Ok(date.with_time(time).assume_utc().unix_timestamp());
+    }
+
+    anyhow::bail!("Invalid date '{value}': expected YYYY-MM-DD or RFC3339")
+}
+
 /// Parses comma-separated tags from a string.
 ///
 /// Splits on commas, trims whitespace from each tag, and filters out empty strings.
@@ -1382,6 +4269,7 @@ fn parse_tags(input: &str) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cons::TagId;
     use serial_test::serial;
 
     #[test]
@@ -1424,9 +4312,15 @@ mod tests {
     fn content_validation_rejects_empty_string() {
         let cmd = AddCommand {
             content: Some(String::new()),
+            from_file: None,
             tags: None,
+            preset: None,
+            no_ai: false,
+            model: None,
+            dedupe: false,
+            dry_run: false,
         };
-        let result = handle_add(&cmd);
+        let result = handle_add(&cmd, std::path::Path::new("unused.db"));
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("cannot be empty"));
     }
@@ -1435,1192 +4329,4228 @@ mod tests {
     fn content_validation_rejects_whitespace_only() {
         let cmd = AddCommand {
             content: Some("   \n\t  ".to_string()),
+            from_file: None,
             tags: None,
+            preset: None,
+            no_ai: false,
+            model: None,
+            dedupe: false,
+            dry_run: false,
         };
-        let result = handle_add(&cmd);
+        let result = handle_add(&cmd, std::path::Path::new("unused.db"));
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("cannot be empty"));
     }
 
-    // --- Auto-Tagging Tests (Task Group 3) ---
+    // --- Stdin Add Tests ---
 
     #[test]
-    fn note_creation_succeeds_even_if_ollama_unavailable() {
-        // Test that note creation succeeds even if Ollama is unavailable
-        // (auto_tag_note errors are caught and logged, not propagated)
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let result = execute_add("Test note", None, db);
-        // Note creation should succeed regardless of Ollama availability
-        assert!(result.is_ok());
+    fn add_command_accepts_dash_for_stdin() {
+        let cli = Cli::try_parse_from(vec!["cons", "add", "-", "-t", "clip"])
+            .expect("failed to parse add with stdin marker");
+        match cli.command {
+            Commands::Add(cmd) => {
+                assert_eq!(cmd.content.as_deref(), Some("-"));
+                assert_eq!(cmd.tags.as_deref(), Some("clip"));
+            }
+            _ => panic!("expected add command"),
+        }
     }
 
     #[test]
-    fn execute_add_creates_note_and_attempts_auto_tagging() {
-        // Test that execute_add creates the note and attempts auto-tagging
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let result = execute_add("Test note", None, db);
-        // Note creation should succeed (auto-tag errors are logged, not propagated)
-        assert!(result.is_ok());
+    fn read_note_from_reader_reads_to_eof_and_trims_trailing_newline() {
+        let input = "line one\n  indented line two\n";
+        let content = read_note_from_reader(input.as_bytes()).expect("failed to read");
+        assert_eq!(content, "line one\n  indented line two");
     }
 
     #[test]
-    fn manual_and_auto_generated_tags_coexist_on_same_note() {
-        // Test that manual tags and auto-generated tags can both exist on a note
-        // This is tested at the NoteService level - both tag sources are supported
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-
-        // Create note with manual tags
-        let note = service
-            .create_note("Test note", Some(&["manual-tag"]))
-            .expect("failed to create note");
+    fn read_note_from_reader_whitespace_only_yields_empty_content() {
+        let content = read_note_from_reader("  \n\t\n".as_bytes()).expect("failed to read");
+        assert!(content.trim().is_empty());
+    }
 
-        // Add auto-generated tags (simulating background task)
-        let llm_source = TagSource::llm("test-model", 85);
-        service
-            .add_tags_to_note(note.id(), &["auto-tag"], llm_source)
-            .expect("failed to add auto-generated tags");
+    // --- Add From File Tests ---
 
-        // Retrieve note and verify both tag types exist
-        let retrieved = service
-            .get_note(note.id())
-            .expect("failed to get note")
-            .expect("note should exist");
+    #[test]
+    fn add_from_file_conflicts_with_content() {
+        use clap::CommandFactory;
 
-        assert_eq!(retrieved.tags().len(), 2, "note should have 2 tags");
-        // Verify both user and LLM tags are present
-        let has_user_tag = retrieved.tags().iter().any(|ta| ta.source().is_user());
-        let has_llm_tag = retrieved.tags().iter().any(|ta| ta.source().is_llm());
-        assert!(has_user_tag, "note should have user tag");
-        assert!(has_llm_tag, "note should have LLM tag");
+        let result =
+            Cli::command().try_get_matches_from(vec!["cons", "add", "--from-file", "note.md"]);
+        assert!(result.is_ok());
+        for content in ["inline", "-"] {
+            let result = Cli::command().try_get_matches_from(vec![
+                "cons",
+                "add",
+                content,
+                "--from-file",
+                "note.md",
+            ]);
+            assert!(result.is_err(), "--from-file and {content:?} are exclusive");
+        }
     }
 
-    // --- Test Review & Gap Analysis Tests (Task Group 4) ---
-
     #[test]
-    fn confidence_score_conversion_f64_to_u8_works_correctly() {
-        // Test that confidence scores are converted correctly from f64 (0.0-1.0) to u8 (0-100)
-        let test_cases: Vec<(f64, u8)> = vec![
-            (0.0, 0u8),
-            (0.5, 50u8),
-            (0.85, 85u8),
-            (1.0, 100u8),
-            (0.955, 96u8), // Test rounding
-        ];
+    fn read_note_from_file_reports_missing_and_non_utf8_files() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+
+        let missing = temp_dir.path().join("missing.md");
+        let err = read_note_from_file(&missing).expect_err("missing file should error");
+        assert!(is_user_error(&err));
+        assert!(err.to_string().contains("missing.md"));
+
+        let binary = temp_dir.path().join("binary.txt");
+        std::fs::write(&binary, [0xff, 0xfe, 0x00]).expect("failed to write file");
+        let err = read_note_from_file(&binary).expect_err("non-UTF-8 file should error");
+        assert!(is_user_error(&err));
+        assert!(err.to_string().contains("not valid UTF-8"));
+
+        let text = temp_dir.path().join("text.md");
+        std::fs::write(&text, "# Scratch\n\nbody\n\n").expect("failed to write file");
+        let content = read_note_from_file(&text).expect("failed to read file");
+        assert_eq!(content, "# Scratch\n\nbody");
+    }
 
-        for (f64_val, expected_u8) in test_cases {
-            let actual_u8 = (f64_val * 100.0_f64).round() as u8;
-            assert_eq!(
-                actual_u8, expected_u8,
-                "f64 {} should convert to u8 {}",
-                f64_val, expected_u8
-            );
+    #[test]
+    fn handle_add_from_file_tags_with_file_stem_unless_tags_given() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let path = temp_dir.path().join("rust-scratch.md");
+        std::fs::write(&path, "Lifetimes tie borrows to scopes\n").expect("failed to write file");
+
+        let add = |tags: Option<&str>| {
+            let cmd = AddCommand {
+                content: None,
+                from_file: Some(path.clone()),
+                tags: tags.map(str::to_string),
+                preset: None,
+                no_ai: true,
+                model: None,
+                dedupe: false,
+                dry_run: false,
+            };
+            handle_add(&cmd, &db_path).expect("add from file should succeed");
+        };
+        add(None);
+        add(Some("lifetimes"));
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions {
+                order: cons::SortOrder::Ascending,
+                ..Default::default()
+            })
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].content(), "Lifetimes tie borrows to scopes");
+
+        let tag_names = |note: &cons::Note| -> Vec<String> {
+            note.tags().iter().map(|t| t.name().to_string()).collect()
+        };
+        assert_eq!(tag_names(&notes[0]), vec!["rust-scratch"]);
+        assert_eq!(tag_names(&notes[1]), vec!["lifetimes"]);
+    }
+
+    // --- Editor Tests ---
+
+    /// Runs `f` with `$EDITOR` and `$VISUAL` set as given, restoring them afterwards.
+    fn with_editor_env<T>(editor: Option<&str>, visual: Option<&str>, f: impl FnOnce() -> T) -> T {
+        let old_editor = std::env::var("EDITOR").ok();
+        let old_visual = std::env::var("VISUAL").ok();
+        // SAFETY: Callers run serially
+        unsafe {
+            for (key, value) in [("EDITOR", editor), ("VISUAL", visual)] {
+                match value {
+                    Some(v) => std::env::set_var(key, v),
+                    None => std::env::remove_var(key),
+                }
+            }
+        }
+
+        let result = f();
+
+        unsafe {
+            for (key, value) in [("EDITOR", old_editor), ("VISUAL", old_visual)] {
+                match value {
+                    Some(v) => std::env::set_var(key, v),
+                    None => std::env::remove_var(key),
+                }
+            }
         }
+        result
     }
 
     #[test]
-    fn model_name_stored_in_tag_source_llm_variant() {
-        // Test that model name from OLLAMA_MODEL env var is stored in TagSource::Llm
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    #[serial]
+    fn launch_editor_returns_file_contents_after_editor_exits() {
+        let content = with_editor_env(Some("true"), None, || launch_editor("seeded text\n"))
+            .expect("editor should succeed");
+        assert_eq!(content, "seeded text\n");
+    }
 
-        let note = service
-            .create_note("Test note", None)
-            .expect("failed to create note");
+    #[test]
+    #[serial]
+    fn launch_editor_passes_editor_arguments_before_path() {
+        let content = with_editor_env(Some("sed -i s/draft/final/"), None, || {
+            launch_editor("draft note\n")
+        })
+        .expect("editor should succeed");
+        assert_eq!(content, "final note\n");
+    }
 
-        // Add tags with specific model name
-        let model_name = "gemma3:4b";
-        let source = TagSource::llm(model_name, 85);
-        service
-            .add_tags_to_note(note.id(), &["test-tag"], source)
-            .expect("failed to add tags");
+    #[test]
+    #[serial]
+    fn launch_editor_falls_back_to_visual() {
+        let content = with_editor_env(None, Some("true"), || launch_editor("from visual"))
+            .expect("VISUAL should be used when EDITOR is unset");
+        assert_eq!(content, "from visual");
+    }
 
-        // Retrieve note and verify model name is stored
-        let retrieved = service
-            .get_note(note.id())
-            .expect("failed to get note")
-            .expect("note should exist");
+    #[test]
+    #[serial]
+    fn launch_editor_without_editor_explains_alternatives() {
+        let err = with_editor_env(None, None, || launch_editor(""))
+            .expect_err("launching without an editor should fail");
+        let message = err.to_string();
+        assert!(message.contains("$EDITOR"));
+        assert!(message.contains("cons add"));
+    }
 
-        let llm_tags: Vec<_> = retrieved
-            .tags()
-            .iter()
-            .filter(|ta| ta.source().is_llm())
-            .collect();
+    #[test]
+    #[serial]
+    fn launch_editor_reports_non_zero_exit() {
+        let err = with_editor_env(Some("false"), None, || launch_editor(""))
+            .expect_err("failing editor should be reported");
+        assert!(err.to_string().contains("non-zero status"));
+    }
 
-        assert_eq!(llm_tags.len(), 1, "should have one LLM tag");
-        assert_eq!(
-            llm_tags[0].model(),
-            Some(model_name),
-            "model name should be stored in TagSource"
+    // --- Edit Command Tests ---
+
+    #[test]
+    fn edit_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        let cmd = Cli::command();
+        let matches = cmd.try_get_matches_from(vec!["cons", "edit", "42", "Fixed content"]);
+        assert!(matches.is_ok(), "edit command should parse id and content");
+
+        let cmd = Cli::command();
+        let matches = cmd.try_get_matches_from(vec!["cons", "edit", "not-a-number", "content"]);
+        assert!(
+            matches.is_err(),
+            "edit command should reject non-numeric ids"
         );
     }
 
     #[test]
-    #[serial]
-    fn auto_tag_returns_error_when_ollama_not_reachable() {
-        // Test that auto_tag_note returns a helpful error when Ollama is not reachable
-        // and OLLAMA_MODEL is not set (triggering auto-detection)
+    fn edit_content_validation_rejects_whitespace_only() {
+        let cmd = EditCommand {
+            id: 1,
+            content: "   \n\t  ".to_string(),
+        };
+        let result = handle_edit(&cmd, std::path::Path::new("unused.db"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("cannot be empty"));
+    }
 
-        // Save current env vars
-        let old_host = std::env::var("OLLAMA_HOST").ok();
-        let old_model = std::env::var("OLLAMA_MODEL").ok();
+    #[test]
+    fn execute_edit_updates_note_content_and_keeps_tags() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Point to a non-existent Ollama instance and clear OLLAMA_MODEL
-        // SAFETY: This test runs serially
-        unsafe {
-            std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:99999");
-            std::env::remove_var("OLLAMA_MODEL");
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            service
+                .create_note("Orignal content", Some(&["rust"]))
+                .expect("failed to create note")
+                .id()
         };
 
+        let db = Database::open(&db_path).expect("failed to open database");
+        let result = execute_edit(note_id, "Original content", db);
+        assert!(
+            result.is_ok(),
+            "edit should succeed even if enhancement fails"
+        );
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .get_note(note_id)
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(note.content(), "Original content");
+        assert_eq!(note.tags().len(), 1);
+    }
+
+    #[test]
+    fn execute_edit_fails_for_non_existent_note() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-        let note_id = NoteId::new(1);
+        let result = execute_edit(NoteId::new(999), "New content", db);
+        assert!(result.is_err(), "editing a missing note should fail");
+    }
 
-        let result = auto_tag_note(&service, note_id, "Test note");
+    // --- Retag Command Tests ---
+
+    #[test]
+    fn execute_retag_fails_for_non_existent_note() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let err = execute_retag(Some(NoteId::new(999)), false, None, db)
+            .expect_err("retagging a missing note should fail");
+        assert!(format!("{err:#}").contains("does not exist"));
+    }
+
+    #[test]
+    #[serial]
+    fn execute_retag_replace_keeps_tags_when_ollama_unreachable() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let note = service
+                .create_note("tokio select timeouts", Some(&["rust"]))
+                .expect("failed to create note");
+            service
+                .add_tags_to_note(note.id(), &["async"], TagSource::llm("test-model", 80))
+                .expect("failed to add tags");
+            note.id()
+        };
+
+        let old_host = std::env::var("OLLAMA_HOST").ok();
+        // SAFETY: This test runs serially
+        unsafe { std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:9") };
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let result = execute_retag(Some(note_id), true, None, db);
 
-        // Restore env vars
         unsafe {
             match old_host {
                 Some(v) => std::env::set_var("OLLAMA_HOST", v),
                 None => std::env::remove_var("OLLAMA_HOST"),
             }
-            match old_model {
-                Some(v) => std::env::set_var("OLLAMA_MODEL", v),
-                None => std::env::remove_var("OLLAMA_MODEL"),
-            }
         };
 
-        assert!(
-            result.is_err(),
-            "should return error when Ollama not reachable"
-        );
+        assert!(result.is_err(), "retag should report the tagging failure");
 
-        let error_msg = result.unwrap_err().to_string();
-        // Should mention Ollama or provide helpful guidance
-        assert!(
-            error_msg.contains("Ollama") || error_msg.contains("ollama"),
-            "error should mention Ollama: {error_msg}"
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .get_note(note_id)
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(
+            note.tags().len(),
+            2,
+            "no tags removed when generation fails"
         );
     }
 
     #[test]
-    fn tag_source_llm_constructor_accepts_model_and_confidence() {
-        // Test that TagSource::llm() constructor works correctly
-        let source = TagSource::llm("test-model", 75);
-        assert!(source.is_llm());
-        assert_eq!(source.confidence(), 75);
-        assert_eq!(source.model(), Some("test-model"));
+    fn execute_retag_all_with_no_untagged_notes_succeeds() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        service
+            .create_note("tagged", Some(&["rust"]))
+            .expect("failed to create note");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let result = execute_retag(None, false, None, db);
+        assert!(result.is_ok(), "nothing to retag is not an error");
     }
 
-    // --- List Command Tests (Task Group 1) ---
+    // --- Untag Command Tests ---
 
     #[test]
-    fn list_command_struct_parsing_with_clap() {
-        use clap::CommandFactory;
+    fn execute_untag_fails_for_non_existent_note() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let err =
+            execute_untag(NoteId::new(999), "rust", db).expect_err("missing note should fail");
+        assert!(format!("{err:#}").contains("does not exist"));
+    }
 
-        // Test parsing with short flags
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "list", "-l", "5", "-t", "rust,programming"])
-            .expect("failed to parse list command");
+    #[test]
+    fn execute_untag_removes_tag_and_is_idempotent() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Verify command is recognized
-        assert!(matches.subcommand_matches("list").is_some());
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .create_note("tagged", Some(&["rust", "wrong"]))
+            .expect("failed to create note");
+
+        for _ in 0..2 {
+            let db = Database::open(&db_path).expect("failed to open database");
+            execute_untag(note.id(), "wrong", db).expect("untag should succeed");
+        }
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(note.tags().len(), 1);
     }
 
+    // --- Tags Verify Command Tests ---
+
     #[test]
-    fn execute_list_with_in_memory_database_returns_notes() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn execute_tags_verify_marks_tag_and_rejects_missing_tag() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Create some notes
-        service
-            .create_note("First note", Some(&["rust"]))
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .create_note("tagged", None)
             .expect("failed to create note");
         service
-            .create_note("Second note", Some(&["rust", "programming"]))
-            .expect("failed to create note");
+            .add_tags_to_note(
+                note.id(),
+                &["rust"],
+                cons::TagSource::llm("deepseek-r1:8b", 80),
+            )
+            .expect("failed to add tags");
 
-        // Create a new database with a test note
-        let db2 = Database::in_memory().expect("failed to create in-memory database");
-        let service2 = NoteService::new(db2);
-        service2
-            .create_note("Test note", None)
-            .expect("failed to create note");
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tags_verify(note.id(), "Rust", db).expect("verify should succeed");
 
-        // Test execute_list function (accepts Database)
-        let db3 = Database::in_memory().expect("failed to create in-memory database");
-        let service3 = NoteService::new(db3);
-        service3
-            .create_note("List test note", None)
-            .expect("failed to create note");
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err =
+            execute_tags_verify(note.id(), "python", db).expect_err("missing tag should fail");
+        assert!(is_user_error(&err));
 
-        let result = execute_list(Some(10), None, service3);
-        assert!(result.is_ok());
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert!(note.tags()[0].verified());
     }
 
-    #[test]
-    fn execute_list_with_empty_database_shows_no_notes_found() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-        let result = execute_list(Some(10), None, service);
-        assert!(result.is_ok());
-    }
+    // --- Tags Describe Command Tests ---
 
     #[test]
-    fn execute_list_with_tags_filter_applies_correctly() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn execute_tags_describe_sets_description_and_rejects_missing_tag() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Create notes with different tags
-        service
-            .create_note("Rust note", Some(&["rust"]))
-            .expect("failed to create note");
-        service
-            .create_note("Programming note", Some(&["programming"]))
-            .expect("failed to create note");
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
         service
-            .create_note("Rust programming note", Some(&["rust", "programming"]))
+            .create_note("tagged", Some(&["rust"]))
             .expect("failed to create note");
 
-        // Filter by tags
-        let result = execute_list(Some(10), Some("rust,programming"), service);
-        assert!(result.is_ok());
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tags_describe("Rust", "Systems language", db).expect("describe should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err =
+            execute_tags_describe("python", "Scripting", db).expect_err("missing tag should fail");
+        assert!(is_user_error(&err));
+
+        assert_eq!(
+            service.get_tag_description("rust").unwrap().as_deref(),
+            Some("Systems language")
+        );
     }
 
-    // --- Output Formatting Tests (Task Group 2) ---
+    // --- Tags Add-Bulk Command Tests ---
 
     #[test]
-    fn get_tag_names_resolves_tag_ids_to_display_names() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn tags_add_bulk_parses_flags() {
+        let cli = Cli::try_parse_from([
+            "cons",
+            "tags",
+            "add-bulk",
+            "--tags",
+            "programming,systems",
+            "--where-tag",
+            "rust",
+        ])
+        .expect("add-bulk should parse");
+        match cli.command {
+            Commands::Tags(TagsCommand {
+                command: Some(TagsCommands::AddBulk { tags, where_tag }),
+                ..
+            }) => {
+                assert_eq!(tags, "programming,systems");
+                assert_eq!(where_tag, "rust");
+            }
+            _ => panic!("expected tags add-bulk"),
+        }
 
-        // Create a note with tags to ensure tags exist in database
-        let note = service
-            .create_note("Test note", Some(&["rust", "programming"]))
-            .expect("failed to create note");
+        assert!(Cli::try_parse_from(["cons", "tags", "add-bulk", "--tags", "x"]).is_err());
+    }
 
-        // Test batch tag name resolution
-        let tag_names =
-            get_tag_names(service.database(), note.tags()).expect("failed to get tag names");
+    #[test]
+    fn tags_cooccur_parses_min_with_default() {
+        let cli = Cli::try_parse_from(["cons", "tags", "cooccur", "--min", "3"])
+            .expect("cooccur should parse");
+        match cli.command {
+            Commands::Tags(TagsCommand {
+                command: Some(TagsCommands::Cooccur { min }),
+                ..
+            }) => assert_eq!(min, 3),
+            _ => panic!("expected tags cooccur"),
+        }
 
-        assert_eq!(tag_names.len(), 2, "should have 2 tags");
-        assert!(
-            tag_names.contains(&"rust".to_string()),
-            "should contain rust"
-        );
-        assert!(
-            tag_names.contains(&"programming".to_string()),
-            "should contain programming"
-        );
+        let cli = Cli::try_parse_from(["cons", "tags", "cooccur"]).expect("cooccur should parse");
+        match cli.command {
+            Commands::Tags(TagsCommand {
+                command: Some(TagsCommands::Cooccur { min }),
+                ..
+            }) => assert_eq!(min, 2),
+            _ => panic!("expected tags cooccur"),
+        }
     }
 
     #[test]
-    fn get_tag_names_returns_empty_for_empty_assignments() {
+    fn execute_tags_cooccur_succeeds_with_and_without_pairs() {
         let db = Database::in_memory().expect("failed to create in-memory database");
+        assert!(execute_tags_cooccur(2, false, db).is_ok());
 
-        // Query with empty tag assignments
-        let tag_names =
-            get_tag_names(&db, &[]).expect("get_tag_names should not error for empty assignments");
-
-        assert!(
-            tag_names.is_empty(),
-            "should return empty vec for empty assignments"
-        );
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        NoteService::new(Database::open(&db_path).expect("failed to open database"))
+            .create_note("Async Rust", Some(&["rust", "tokio"]))
+            .expect("failed to create note");
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_tags_cooccur(1, true, db).is_ok());
     }
 
     #[test]
-    fn timestamp_formats_as_yyyy_mm_dd_hh_mm() {
-        use time::macros::format_description;
+    fn execute_tags_add_bulk_tags_matching_notes() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let rust = service.create_note("Lifetimes", Some(&["rust"])).unwrap();
+        let other = service.create_note("Bread", Some(&["baking"])).unwrap();
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tags_add_bulk("programming, systems", "Rust", db).expect("add-bulk should succeed");
+
+        let tags = |id| {
+            let note = service.get_note(id).unwrap().unwrap();
+            let mut names: Vec<String> = note.tags().iter().map(|t| t.name().to_string()).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(tags(rust.id()), vec!["programming", "rust", "systems"]);
+        assert_eq!(tags(other.id()), vec!["baking"]);
 
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_tags_add_bulk("x", "missing", db).unwrap_err();
+        assert!(is_user_error(&err));
+    }
 
-        // Create a note
-        let note = service
-            .create_note("Timestamp test", None)
-            .expect("failed to create note");
+    #[test]
+    fn tags_describe_and_verbose_parse() {
+        let cli = Cli::try_parse_from(["cons", "tags", "describe", "rust", "Systems language"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Tags(TagsCommand {
+                command: Some(TagsCommands::Describe { tag, text }),
+                ..
+            }) => {
+                assert_eq!(tag, "rust");
+                assert_eq!(text, "Systems language");
+            }
+            _ => panic!("expected tags describe command"),
+        }
 
-        // Format timestamp using the same format as execute_list
-        let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
-        let timestamp = note
-            .created_at()
-            .format(&format)
-            .expect("failed to format timestamp");
+        let cli = Cli::try_parse_from(["cons", "tags", "--verbose"]).expect("should parse");
+        match cli.command {
+            Commands::Tags(cmd) => assert!(cmd.verbose),
+            _ => panic!("expected tags command"),
+        }
+    }
 
-        // Verify format matches expected pattern (YYYY-MM-DD HH:MM)
-        // Example: "2025-12-23 14:30"
-        assert_eq!(timestamp.len(), 16, "timestamp should be 16 characters");
-        assert_eq!(
-            &timestamp[4..5],
-            "-",
-            "character at position 4 should be '-'"
-        );
-        assert_eq!(
-            &timestamp[7..8],
-            "-",
-            "character at position 7 should be '-'"
-        );
-        assert_eq!(
-            &timestamp[10..11],
-            " ",
-            "character at position 10 should be space"
-        );
+    #[test]
+    fn format_tag_descriptions_lists_only_described_tags() {
+        let names = vec!["rust".to_string(), "cli".to_string()];
+
+        assert_eq!(format_tag_descriptions(&names, &[None, None]), "");
         assert_eq!(
-            &timestamp[13..14],
-            ":",
-            "character at position 13 should be ':'"
+            format_tag_descriptions(&names, &[Some("Systems language".to_string()), None]),
+            "Tag descriptions:\n  #rust: Systems language\n"
         );
     }
 
+    // --- Reenhance Command Tests ---
+
     #[test]
-    fn note_display_with_multiple_tags_shows_hashtag_format() {
+    fn execute_reenhance_fails_for_non_existent_note() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let err = execute_reenhance(Some(NoteId::new(999)), false, None, db)
+            .expect_err("enhancing a missing note should fail");
+        assert!(format!("{err:#}").contains("does not exist"));
+    }
 
-        // Create a note with multiple tags
-        let note = service
-            .create_note("Test note", Some(&["rust", "programming", "tutorial"]))
-            .expect("failed to create note");
+    #[test]
+    fn execute_reenhance_skips_enhanced_note_without_force() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let note = service
+                .create_note("buy milk", None)
+                .expect("failed to create note");
+            service
+                .update_note_enhancement(
+                    note.id(),
+                    "Buy milk from the store.",
+                    "test-model",
+                    0.9,
+                    time::OffsetDateTime::now_utc(),
+                    None,
+                )
+                .expect("failed to enhance note");
+            note.id()
+        };
 
-        // Collect tag names in hashtag format (simulating execute_list behavior)
-        let tag_names: Vec<String> = get_tag_names(service.database(), note.tags())
-            .expect("failed to get tag names")
-            .into_iter()
-            .map(|name| format!("#{}", name))
-            .collect();
+        // Without --force no Ollama call is made, so this succeeds offline
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_reenhance(Some(note_id), false, None, db).expect("skip should succeed");
 
-        // Verify all tags are present in hashtag format
-        assert_eq!(tag_names.len(), 3, "should have 3 tags");
-        assert!(
-            tag_names.contains(&"#rust".to_string()),
-            "should contain #rust"
-        );
-        assert!(
-            tag_names.contains(&"#programming".to_string()),
-            "should contain #programming"
-        );
-        assert!(
-            tag_names.contains(&"#tutorial".to_string()),
-            "should contain #tutorial"
-        );
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let note = service
+            .get_note(note_id)
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(note.content_enhanced(), Some("Buy milk from the store."));
+    }
 
-        // Verify joined output (as it appears in execute_list)
-        let tags_display = tag_names.join(" ");
-        assert!(
-            tags_display.contains("#rust"),
-            "joined output should contain #rust"
-        );
-        assert!(
-            tags_display.contains("#programming"),
-            "joined output should contain #programming"
+    #[test]
+    fn format_enhancement_summary_reports_average_confidence() {
+        assert_eq!(
+            format_enhancement_summary(2, 3, &[0.8, 0.9]),
+            "Enhanced 2 of 3 notes (average confidence 85%)"
         );
-        assert!(
-            tags_display.contains("#tutorial"),
-            "joined output should contain #tutorial"
+        assert_eq!(
+            format_enhancement_summary(0, 1, &[]),
+            "Enhanced 0 of 1 note"
         );
     }
 
-    // --- Tag Alias CLI Tests (Task Group 3) ---
+    // --- Delete Command Tests ---
 
     #[test]
-    fn tag_alias_add_creates_alias_correctly() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let result = execute_tag_alias_add("ml", "machine-learning", db);
-        assert!(result.is_ok());
+    fn delete_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        let cmd = Cli::command();
+        let matches = cmd.try_get_matches_from(vec!["cons", "delete", "7"]);
+        assert!(matches.is_ok(), "delete command should parse id");
+
+        let cmd = Cli::command();
+        let matches = cmd.try_get_matches_from(vec!["cons", "delete", "7", "--yes"]);
+        assert!(matches.is_ok(), "delete command should accept --yes");
+
+        let cmd = Cli::command();
+        let matches = cmd.try_get_matches_from(vec!["cons", "delete"]);
+        assert!(matches.is_err(), "delete command should require an id");
     }
 
     #[test]
-    fn tag_alias_add_with_non_existent_canonical_creates_tag_first() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
+    fn execute_delete_removes_note_when_confirmed() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Add alias with non-existent canonical tag (this should auto-create the tag)
-        let result = execute_tag_alias_add("ai", "artificial-intelligence", db);
-        assert!(result.is_ok());
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            service
+                .create_note("Delete me", Some(&["rust"]))
+                .expect("failed to create note")
+                .id()
+        };
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_delete(note_id, true, db).expect("delete should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        assert_eq!(service.get_note(note_id).expect("failed to get note"), None);
     }
 
     #[test]
-    fn tag_alias_list_displays_aliases_grouped_by_canonical() {
-        // Create database and add multiple aliases
+    fn execute_delete_non_existent_note_is_not_an_error() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let result = execute_delete(NoteId::new(999), true, db);
+        assert!(result.is_ok(), "deleting a missing note should not error");
+    }
 
-        // Create multiple aliases for different canonical tags
-        let ml_tag = service
-            .get_or_create_tag("machine-learning")
-            .expect("failed to create tag");
-        service
-            .create_alias("ml", ml_tag, "user", 1.0, None)
-            .expect("failed to add ml alias");
+    // --- Merge Command Tests ---
 
-        let ai_tag = service
-            .get_or_create_tag("artificial-intelligence")
-            .expect("failed to create tag");
-        service
-            .create_alias("ai", ai_tag, "user", 1.0, None)
-            .expect("failed to add ai alias");
+    #[test]
+    fn merge_command_requires_both_ids() {
+        use clap::CommandFactory;
 
-        let dl_tag = service
-            .get_or_create_tag("deep-learning")
-            .expect("failed to create tag");
-        service
-            .create_alias("dl", dl_tag, "user", 1.0, None)
-            .expect("failed to add dl alias");
+        let result = Cli::command().try_get_matches_from(vec!["cons", "merge", "1", "2"]);
+        assert!(result.is_ok());
 
-        // Now test the list command with the same database
-        let db2 = Database::in_memory().expect("failed to create in-memory database");
-        let service2 = NoteService::new(db2);
+        let result = Cli::command().try_get_matches_from(vec!["cons", "merge", "1"]);
+        assert!(result.is_err(), "merge should require the note to fold in");
+    }
 
-        // Recreate one alias to test display
-        let test_tag = service2
-            .get_or_create_tag("test-tag")
-            .expect("failed to create tag");
-        service2
-            .create_alias("t", test_tag, "user", 1.0, None)
-            .expect("failed to add test alias");
+    #[test]
+    fn execute_merge_folds_note_and_deletes_it() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let (keep, merge) = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let keep = service.create_note("First", Some(&["rust"])).unwrap();
+            let merge = service.create_note("Second", Some(&["async"])).unwrap();
+            (keep.id(), merge.id())
+        };
 
-        // Get the database from service2
-        // Since we can't get db back from service, we'll create a new db for the execute function
-        let db3 = Database::in_memory().expect("failed to create in-memory database");
-        let service3 = NoteService::new(db3);
-        let test_tag3 = service3
-            .get_or_create_tag("example")
-            .expect("failed to create tag");
-        service3
-            .create_alias("ex", test_tag3, "user", 1.0, None)
-            .expect("failed to add alias");
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_merge(keep, merge, db).expect("merge should succeed");
 
-        let aliases = service3.list_aliases().expect("failed to list aliases");
-        assert_eq!(aliases.len(), 1);
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        assert_eq!(service.get_note(merge).unwrap(), None);
+        let kept = service.get_note(keep).unwrap().expect("kept note exists");
+        assert_eq!(kept.content(), "First\n\nSecond");
+        assert_eq!(kept.tags().len(), 2);
     }
 
     #[test]
-    fn tag_alias_remove_deletes_alias() {
+    fn execute_merge_missing_note_is_user_error() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let err = execute_merge(NoteId::new(1), NoteId::new(2), db).unwrap_err();
+        assert!(is_user_error(&err));
+    }
 
-        // Create an alias
-        let ml_tag = service
-            .get_or_create_tag("machine-learning")
-            .expect("failed to create tag");
-        service
-            .create_alias("ml", ml_tag, "user", 1.0, None)
-            .expect("failed to add alias");
+    // --- Show Command Tests ---
 
-        // Verify it exists
-        let resolved_before = service
-            .resolve_alias("ml")
-            .expect("failed to resolve alias");
-        assert!(resolved_before.is_some());
+    #[test]
+    fn show_command_parses_id() {
+        use clap::CommandFactory;
 
-        // Remove the alias
-        service.remove_alias("ml").expect("failed to remove alias");
+        let result = Cli::command().try_get_matches_from(vec!["cons", "show", "42"]);
+        assert!(result.is_ok());
 
-        // Verify it's gone
-        let resolved_after = service
-            .resolve_alias("ml")
-            .expect("failed to resolve alias");
-        assert_eq!(resolved_after, None);
+        let result = Cli::command().try_get_matches_from(vec!["cons", "show"]);
+        assert!(result.is_err(), "id is required");
     }
 
     #[test]
-    fn tag_alias_command_parsing_with_clap() {
-        use clap::CommandFactory;
+    fn execute_show_missing_note_is_user_error() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let err = execute_show(NoteId::new(999), false, db).expect_err("missing note should error");
+        assert!(is_user_error(&err));
+        assert!(err.to_string().contains("999"));
+    }
 
-        // Test parsing tag-alias add
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "tag-alias", "add", "ml", "machine-learning"])
-            .expect("failed to parse tag-alias add command");
+    #[test]
+    fn is_user_error_relies_on_not_found_type_not_message() {
+        let wrapped = anyhow::Error::new(NotFound("Note with id 7 does not exist".to_string()))
+            .context("failed to show note");
+        assert!(is_user_error(&wrapped));
 
-        assert!(matches.subcommand_matches("tag-alias").is_some());
+        let sqlite_like = anyhow::anyhow!("database table does not exist");
+        assert!(!is_user_error(&sqlite_like));
+    }
 
-        // Test parsing tag-alias list
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "tag-alias", "list"])
-            .expect("failed to parse tag-alias list command");
+    #[test]
+    fn merging_a_note_into_itself_is_user_error() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let id = NoteId::new(5);
 
-        assert!(matches.subcommand_matches("tag-alias").is_some());
+        let err = execute_merge(id, id, db).expect_err("self-merge should fail");
+        assert!(err.to_string().contains("into itself"));
+        assert!(is_user_error(&err));
 
-        // Test parsing tag-alias remove
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "tag-alias", "remove", "ml"])
-            .expect("failed to parse tag-alias remove command");
+        let wrapped = anyhow::Error::new(InvalidInput("Cannot merge note 5 into itself".into()))
+            .context("failed to merge notes");
+        assert!(is_user_error(&wrapped));
+    }
 
-        assert!(matches.subcommand_matches("tag-alias").is_some());
+    #[test]
+    fn unknown_fts_tokenizer_is_user_error() {
+        let config = Config::from_lookup(|_| Some("snowball".into()));
+
+        let err = config
+            .fts_tokenizer()
+            .expect_err("unknown tokenizer should fail");
+        assert!(err.to_string().contains("Unknown FTS tokenizer 'snowball'"));
+        assert!(is_user_error(&err));
     }
 
     #[test]
-    fn tag_alias_add_normalizes_both_alias_and_canonical() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
+    fn execute_show_existing_note_succeeds() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Add alias with non-normalized names
-        let result = execute_tag_alias_add("ML!", "Machine Learning", db);
-        assert!(result.is_ok());
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            service
+                .create_note("Show me", Some(&["rust"]))
+                .expect("failed to create note")
+                .id()
+        };
 
-        // Verify normalization worked by checking in a new database instance
-        let db2 = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db2);
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_show(note_id, false, db).is_ok());
+    }
 
-        // Create the same alias again to test normalization
-        let tag = service
-            .get_or_create_tag("machine-learning")
-            .expect("failed to create tag");
-        service
-            .create_alias("ml", tag, "user", 1.0, None)
-            .expect("failed to create alias");
+    #[test]
+    fn execute_show_verbose_succeeds_for_enhanced_note() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let note_id = {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let note_id = service
+                .create_note("Show me", Some(&["rust"]))
+                .expect("failed to create note")
+                .id();
+            service
+                .update_note_enhancement(
+                    note_id,
+                    "Show me, enhanced",
+                    "mock-model",
+                    0.9,
+                    time::OffsetDateTime::now_utc(),
+                    Some("Expand this note"),
+                )
+                .expect("failed to enhance note");
+            note_id
+        };
 
-        let resolved = service
-            .resolve_alias("ml")
-            .expect("failed to resolve alias");
-        assert!(
-            resolved.is_some(),
-            "alias should be normalized to 'ml' (lowercase, no punctuation)"
-        );
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_show(note_id, true, db).is_ok());
     }
 
-    // --- AutoTagger Alias Integration Tests (Task Group 4) ---
-
     #[test]
-    fn auto_tagging_creates_alias_when_llm_suggests_existing_tag_variant() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn format_note_details_includes_enhancement_and_tag_sources() {
+        use cons::{NoteBuilder, TagAssignment};
 
-        // Pre-create a canonical tag
-        let canonical_tag_id = service
-            .get_or_create_tag("machine-learning")
-            .expect("failed to create canonical tag");
+        let now = time::OffsetDateTime::now_utc();
+        let note = NoteBuilder::new()
+            .id(NoteId::new(7))
+            .content("buy milk")
+            .created_at(now)
+            .updated_at(now)
+            .content_enhanced("Buy milk from the grocery store.")
+            .enhanced_at(now)
+            .enhancement_model("deepseek-r1:8b")
+            .enhancement_confidence(0.75)
+            .source("cli")
+            .tags(vec![
+                TagAssignment::user(TagId::new(1), "errands", now),
+                TagAssignment::llm(TagId::new(2), "shopping", "deepseek-r1:8b", 80, now),
+            ])
+            .build();
 
-        // Simulate LLM suggesting "ml" as a tag
-        // In real scenario, auto_tag_note would detect "ml" normalizes differently from "machine-learning"
-        // and create an alias mapping
+        let details = format_note_details(&note, &["errands".to_string(), "shopping".to_string()]);
+        assert!(details.contains("ID: 7"));
+        assert!(details.contains("Updated: "));
+        assert!(details.contains("Source: cli\n"));
+        assert!(details.contains("Enhanced: Buy milk from the grocery store."));
+        assert!(details.contains("Enhancement model: deepseek-r1:8b"));
+        assert!(details.contains("#errands (user, 100% confidence)"));
+        assert!(details.contains("#shopping (llm, deepseek-r1:8b, 80% confidence)"));
+    }
 
-        // For now, manually create the alias as auto_tag_note will do
-        service
-            .create_alias("ml", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))
-            .expect("failed to create alias");
+    #[test]
+    fn format_note_details_marks_verified_tags() {
+        use cons::{NoteBuilder, TagAssignment};
 
-        // Verify alias was created
-        let resolved = service
-            .resolve_alias("ml")
-            .expect("failed to resolve alias");
+        let now = time::OffsetDateTime::now_utc();
+        let mut verified = TagAssignment::llm(TagId::new(1), "rust", "deepseek-r1:8b", 90, now);
+        verified.verify();
+        let note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("ownership")
+            .created_at(now)
+            .updated_at(now)
+            .tags(vec![
+                verified,
+                TagAssignment::llm(TagId::new(2), "memory", "deepseek-r1:8b", 70, now),
+            ])
+            .build();
+
+        let details = format_note_details(&note, &["rust".to_string(), "memory".to_string()]);
+        assert!(details.contains("#rust (llm, deepseek-r1:8b, 90% confidence) ✓"));
+        assert!(details.contains("#memory (llm, deepseek-r1:8b, 70% confidence)\n"));
+    }
+
+    #[test]
+    fn format_note_details_says_when_not_enhanced() {
+        use cons::NoteBuilder;
+
+        let now = time::OffsetDateTime::now_utc();
+        let note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("plain")
+            .created_at(now)
+            .updated_at(now)
+            .build();
+
+        let details = format_note_details(&note, &[]);
+        assert!(details.contains("Enhancement: none"));
+        assert!(details.contains("Tags: none"));
+    }
+
+    #[test]
+    fn format_enhancement_prompt_indents_stored_prompt() {
+        use cons::NoteBuilder;
+
+        let note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("buy milk")
+            .content_enhanced("Buy milk from the store.")
+            .enhancement_prompt("Expand this note.\n\nNOTE:\nbuy milk")
+            .build();
         assert_eq!(
-            resolved,
-            Some(canonical_tag_id),
-            "alias should resolve to canonical tag"
+            format_enhancement_prompt(&note),
+            "Enhancement prompt:\n  Expand this note.\n  \n  NOTE:\n  buy milk\n"
         );
 
-        // Verify alias has correct metadata
-        let aliases = service.list_aliases().expect("failed to list aliases");
-        assert_eq!(aliases.len(), 1, "should have one alias");
-        let alias_info = &aliases[0];
-        assert_eq!(alias_info.alias(), "ml");
-        assert_eq!(alias_info.canonical_tag_id(), canonical_tag_id);
-        assert_eq!(alias_info.source(), "llm");
-        assert_eq!(alias_info.confidence(), 0.85);
-        assert_eq!(alias_info.model_version(), Some("deepseek-r1:8b"));
+        let legacy = NoteBuilder::new()
+            .id(NoteId::new(2))
+            .content("buy milk")
+            .content_enhanced("Buy milk from the store.")
+            .build();
+        assert_eq!(
+            format_enhancement_prompt(&legacy),
+            "Enhancement prompt: not recorded\n"
+        );
+
+        let plain = NoteBuilder::new()
+            .id(NoteId::new(3))
+            .content("plain")
+            .build();
+        assert_eq!(format_enhancement_prompt(&plain), "");
     }
 
     #[test]
-    fn alias_stored_with_source_llm_and_correct_confidence() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn doctor_repair_parses() {
+        let cli = Cli::try_parse_from(["cons", "doctor", "--repair"]).expect("should parse");
+        match cli.command {
+            Commands::Doctor(cmd) => {
+                assert!(cmd.repair);
+                assert!(cmd.command.is_none());
+            }
+            _ => panic!("expected doctor command"),
+        }
+    }
 
-        // Create canonical tag
-        let canonical_tag_id = service
-            .get_or_create_tag("artificial-intelligence")
-            .expect("failed to create canonical tag");
+    #[test]
+    fn show_verbose_parses() {
+        let cli = Cli::try_parse_from(["cons", "show", "7", "--verbose"]).expect("should parse");
+        match cli.command {
+            Commands::Show(cmd) => {
+                assert_eq!(cmd.id, 7);
+                assert!(cmd.verbose);
+            }
+            _ => panic!("expected show command"),
+        }
+    }
 
-        // Create LLM alias with specific confidence
-        let confidence = 0.92;
-        service
-            .create_alias("ai", canonical_tag_id, "llm", confidence, Some("gemma3:4b"))
-            .expect("failed to create alias");
+    #[test]
+    fn format_note_links_lists_links_and_backlinks() {
+        use cons::NoteBuilder;
 
-        // Verify alias metadata
-        let aliases = service.list_aliases().expect("failed to list aliases");
-        assert_eq!(aliases.len(), 1);
-        let alias_info = &aliases[0];
-        assert_eq!(alias_info.source(), "llm");
-        assert_eq!(alias_info.confidence(), confidence);
+        let now = time::OffsetDateTime::now_utc();
+        let note = |id: i64, content: &str| {
+            NoteBuilder::new()
+                .id(NoteId::new(id))
+                .content(content)
+                .created_at(now)
+                .updated_at(now)
+                .build()
+        };
+
+        let output = format_note_links(
+            &[note(2, "Ownership basics\nmore detail")],
+            &[note(5, "Borrowing builds on [[1]]")],
+        );
+        assert_eq!(
+            output,
+            "Links:\n  [[2]] Ownership basics\nBacklinks:\n  [[5]] Borrowing builds on [[1]]\n"
+        );
+        assert!(format_note_links(&[], &[]).is_empty());
     }
 
-    #[test]
-    fn model_version_from_ollama_model_stored_in_alias() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    // --- Export Command Tests ---
 
-        // Create canonical tag
-        let canonical_tag_id = service
-            .get_or_create_tag("deep-learning")
-            .expect("failed to create canonical tag");
+    #[test]
+    fn export_command_parses_format_and_output() {
+        use clap::CommandFactory;
 
-        // Create alias with specific model version
-        let model_version = "deepseek-r1:8b";
-        service
-            .create_alias("dl", canonical_tag_id, "llm", 0.88, Some(model_version))
-            .expect("failed to create alias");
+        let matches = Cli::command()
+            .try_get_matches_from(vec![
+                "cons", "export", "--format", "markdown", "--output", "notes.md",
+            ])
+            .expect("export should parse");
+        let (_, export_matches) = matches.subcommand().unwrap();
+        assert_eq!(
+            export_matches.get_one::<ExportFormatArg>("format").copied(),
+            Some(ExportFormatArg::Markdown)
+        );
 
-        // Verify model version is stored
-        let aliases = service.list_aliases().expect("failed to list aliases");
-        assert_eq!(aliases.len(), 1);
-        let alias_info = &aliases[0];
-        assert_eq!(alias_info.model_version(), Some(model_version));
+        let result = Cli::command().try_get_matches_from(vec!["cons", "export", "-f", "xml"]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn no_alias_created_for_genuinely_new_tags() {
+    fn execute_export_writes_file() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let output = temp_dir.path().join("notes.json");
+        execute_export(ExportFormat::Json, None, Some(&output), db).expect("export should succeed");
 
-        // Create a tag directly (simulating LLM suggesting a new tag)
-        let new_tag_id = service
-            .get_or_create_tag("quantum-computing")
-            .expect("failed to create new tag");
+        let written = std::fs::read_to_string(&output).expect("export file should exist");
+        assert_eq!(written.trim(), "[]");
+    }
 
-        // Verify no alias exists for this tag
-        let resolved = service
-            .resolve_alias("quantum-computing")
-            .expect("failed to resolve alias");
-        assert_eq!(resolved, None, "new tag should not have alias");
+    #[test]
+    fn execute_export_with_tag_writes_tag_document() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        service
+            .create_note("Learning Rust", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Buy milk", Some(&["errands"]))
+            .expect("failed to create note");
 
-        // Verify aliases list is empty
-        let aliases = service.list_aliases().expect("failed to list aliases");
-        assert_eq!(aliases.len(), 0, "no aliases should exist");
+        let output = temp_dir.path().join("rust.md");
+        execute_export(
+            ExportFormat::Markdown,
+            Some("rust"),
+            Some(&output),
+            Database::open(&db_path).expect("failed to open database"),
+        )
+        .expect("export should succeed");
 
-        // Verify the tag was actually created
-        let conn = service.database().connection();
-        let tag_exists: bool = conn
-            .query_row(
-                "SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)",
-                [new_tag_id.get()],
-                |row| row.get(0),
-            )
-            .expect("failed to check tag existence");
-        assert!(tag_exists, "tag should exist in database");
+        let written = std::fs::read_to_string(&output).expect("export file should exist");
+        assert!(written.starts_with("# Notes tagged #rust"));
+        assert!(written.contains("Learning Rust"));
+        assert!(!written.contains("Buy milk"));
     }
 
     #[test]
-    fn alias_creation_is_fail_safe_does_not_block_note_capture() {
+    fn execute_export_with_tag_requires_markdown() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        let err = execute_export(ExportFormat::Json, Some("rust"), None, db).unwrap_err();
+        assert!(err.to_string().contains("--format markdown"));
+    }
 
-        // Create a note - this simulates the note capture flow
-        let note = service
-            .create_note("Test note about AI", None)
-            .expect("note creation should succeed");
+    // --- Import Command Tests ---
 
-        // Simulate alias creation failure (e.g., invalid canonical tag ID)
-        let invalid_tag_id = TagId::new(999999);
-        let alias_result =
-            service.create_alias("ai", invalid_tag_id, "llm", 0.85, Some("test-model"));
+    #[test]
+    fn import_command_parses_flags() {
+        use clap::CommandFactory;
 
-        // Alias creation should fail (canonical tag doesn't exist)
+        let result = Cli::command().try_get_matches_from(vec![
+            "cons",
+            "import",
+            "notes.json",
+            "--enhance",
+            "--no-enhance",
+            "--skip-duplicates",
+        ]);
         assert!(
-            alias_result.is_err(),
-            "alias creation should fail with invalid canonical tag"
+            result.is_ok(),
+            "--enhance and --no-enhance override each other"
         );
 
-        // But the note should still exist and be retrievable
-        let retrieved_note = service
-            .get_note(note.id())
-            .expect("failed to get note")
-            .expect("note should exist");
-        assert_eq!(retrieved_note.content(), "Test note about AI");
+        let result = Cli::command().try_get_matches_from(vec!["cons", "import"]);
+        assert!(result.is_err(), "path is required");
     }
 
     #[test]
-    fn alias_creation_error_logged_but_does_not_propagate() {
-        // This test verifies that auto_tag_note's error handling is fail-safe
-        // We'll test this by simulating the workflow without actually calling auto_tag_note
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn execute_import_skips_duplicates_and_keeps_tags() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let source = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let notes = vec![
+            source
+                .create_note("Existing note", None)
+                .expect("failed to create note"),
+            source
+                .create_note("New note", Some(&["rust"]))
+                .expect("failed to create note"),
+        ];
 
-        // Create a note successfully
-        let note = service
-            .create_note("Learning Rust async patterns", None)
-            .expect("note creation should succeed");
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            service
+                .create_note("Existing note", None)
+                .expect("failed to create note");
+        }
 
-        // Verify note exists even if we don't attempt auto-tagging
-        let retrieved = service.get_note(note.id()).expect("failed to get note");
-        assert!(retrieved.is_some(), "note should exist");
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_import(&notes, false, true, db).expect("import should succeed");
 
-        // The actual auto_tag_note function catches errors and logs them
-        // without propagating, so note capture always succeeds
-        // This is verified by the execute_add tests which show that
-        // auto_tag_note errors don't cause execute_add to fail
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let all = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(all.len(), 2);
+        let imported = all
+            .iter()
+            .find(|n| n.content() == "New note")
+            .expect("new note imported");
+        assert_eq!(imported.tags().len(), 1);
     }
 
     #[test]
-    fn find_alias_opportunity_detects_abbreviations() {
-        // Test the find_alias_opportunity helper function
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-
-        // Create a canonical tag
-        service
-            .get_or_create_tag("machine-learning")
-            .expect("failed to create canonical tag");
+    fn execute_import_rolls_back_on_invalid_record() {
+        use cons::NoteBuilder;
 
-        // Test abbreviation detection
-        let result = find_alias_opportunity(&service, "ml");
-        assert!(
-            result.is_some(),
-            "should detect 'ml' as abbreviation of 'machine-learning'"
-        );
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Test that longer tags don't create aliases
-        let result = find_alias_opportunity(&service, "quantum-computing");
-        assert_eq!(
-            result, None,
-            "should not detect alias opportunity for long tag"
-        );
+        let now = time::OffsetDateTime::now_utc();
+        let notes = vec![
+            NoteBuilder::new()
+                .id(NoteId::new(1))
+                .content("Valid note")
+                .created_at(now)
+                .updated_at(now)
+                .build(),
+            NoteBuilder::new()
+                .id(NoteId::new(2))
+                .content("   ")
+                .created_at(now)
+                .updated_at(now)
+                .build(),
+        ];
 
-        // Test another common abbreviation pattern
-        service
-            .get_or_create_tag("artificial-intelligence")
-            .expect("failed to create canonical tag");
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_import(&notes, false, false, db).expect_err("empty record should fail");
+        assert!(format!("{err:#}").contains("cannot be empty"));
 
-        let result = find_alias_opportunity(&service, "ai");
-        assert!(
-            result.is_some(),
-            "should detect 'ai' as abbreviation of 'artificial-intelligence'"
-        );
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let all = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert!(all.is_empty(), "nothing should be imported");
     }
 
-    // --- CLI Enhancement Integration Tests (Task Group 4) ---
+    // --- Backup Command Tests ---
 
     #[test]
-    fn execute_add_calls_enhancement_after_note_save() {
-        // Test that execute_add attempts enhancement after note is saved
-        // Enhancement may fail (no Ollama), but note creation should succeed
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn default_backup_path_uses_timestamped_file_in_backups_dir() {
+        let db_path = std::path::Path::new("/data/cons/notes.db");
+        let now = time::macros::datetime!(2026-03-04 05:06:07 UTC);
 
-        // Create note directly to test the flow
-        let note = service
-            .create_note("quick thought", None)
-            .expect("note creation should succeed");
+        let path = default_backup_path(db_path, now).expect("path should build");
+        assert_eq!(
+            path,
+            std::path::PathBuf::from("/data/cons/backups/notes-20260304-050607.db")
+        );
+    }
 
-        // Verify note exists with original content
-        let retrieved = service
-            .get_note(note.id())
-            .expect("failed to get note")
-            .expect("note should exist");
-        assert_eq!(retrieved.content(), "quick thought");
+    #[test]
+    fn execute_backup_creates_missing_directory() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let output = temp_dir.path().join("backups").join("snapshot.db");
 
-        // Enhancement fields should be None if Ollama is unavailable
-        // (This is the fail-safe behavior we're testing)
-        // Note: In real scenario, enhance_note would be called after create_note
+        execute_backup(&output, &db).expect("backup should succeed");
+        assert!(output.exists(), "backup file should be written");
     }
 
+    // --- Maintenance Command Tests ---
+
     #[test]
-    fn enhancement_failure_does_not_block_note_capture() {
-        // Test that note creation succeeds even if enhancement fails
-        // This verifies the fail-safe pattern in execute_add
+    fn maintenance_command_requires_subcommand() {
+        use clap::CommandFactory;
+
+        let result = Cli::command().try_get_matches_from(vec!["cons", "maintenance", "check"]);
+        assert!(result.is_ok());
+        let result = Cli::command().try_get_matches_from(vec!["cons", "maintenance"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn execute_maintenance_check_fails_on_dangling_rows() {
         let db = Database::in_memory().expect("failed to create in-memory database");
+        execute_maintenance_check(&db).expect("fresh database should pass");
 
-        // Call execute_add - it should succeed even without Ollama
-        let result = execute_add("test note", None, db);
+        let conn = db.connection();
+        conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        conn.execute("INSERT INTO note_tags (note_id, tag_id) VALUES (7, 7)", [])
+            .unwrap();
+        conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
 
-        // Note creation should succeed (enhancement errors are caught)
+        let err = execute_maintenance_check(&db).expect_err("violation should fail the check");
+        assert!(err.to_string().contains("Integrity check found"));
+    }
+
+    #[test]
+    fn execute_maintenance_vacuum_on_file_database() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let db = Database::open(&db_path).expect("failed to open database");
+
+        execute_maintenance_vacuum(&db_path, &db).expect("vacuum should succeed");
+    }
+
+    #[test]
+    fn execute_maintenance_rebuild_fts_reindexes_notes() {
+        let cli = Cli::try_parse_from(vec!["cons", "maintenance", "rebuild-fts"])
+            .expect("failed to parse maintenance rebuild-fts");
+        assert!(matches!(
+            cli.command,
+            Commands::Maintenance(MaintenanceCommand {
+                command: MaintenanceCommands::RebuildFts
+            })
+        ));
+
+        let service =
+            NoteService::new(Database::in_memory().expect("failed to create in-memory database"));
+        service
+            .create_note("reindex me", None)
+            .expect("failed to create note");
+        service
+            .database()
+            .connection()
+            .execute("DELETE FROM notes_fts", [])
+            .expect("failed to clear FTS index");
+        assert!(service.search_notes("reindex", None).unwrap().is_empty());
+
+        execute_maintenance_rebuild_fts(service.database()).expect("rebuild should succeed");
+        assert_eq!(
+            service.search_notes("reindex", None).unwrap().len(),
+            1,
+            "rebuilt index should find the note"
+        );
+    }
+
+    // --- Completions Command Tests ---
+
+    #[test]
+    fn completions_command_accepts_supported_shells() {
+        use clap::CommandFactory;
+
+        for shell in ["bash", "zsh", "fish", "powershell"] {
+            let result = Cli::command().try_get_matches_from(vec!["cons", "completions", shell]);
+            assert!(result.is_ok(), "{shell} should be accepted");
+        }
+        let result = Cli::command().try_get_matches_from(vec!["cons", "completions", "tcsh"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn execute_completions_writes_script_with_subcommands() {
+        let mut output = Vec::new();
+        execute_completions(clap_complete::Shell::Bash, &mut output)
+            .expect("completion generation should succeed");
+        let script = String::from_utf8(output).expect("valid UTF-8");
+
+        assert!(script.contains("_cons()"));
+        assert!(script.contains("graph-search"));
+        assert!(script.contains("--db"));
+    }
+
+    // --- Config Tests ---
+
+    #[test]
+    fn config_command_parses() {
+        let cli = Cli::try_parse_from(["cons", "config"]).expect("config should parse");
+        assert!(matches!(cli.command, Commands::Config));
+    }
+
+    #[test]
+    fn execute_config_lists_values_with_sources() {
+        let config = Config::from_lookup(|name| (name == "CONS_DECAY").then(|| "0.5".into()));
+
+        let mut output = Vec::new();
+        execute_config(&config, false, &mut output).expect("config should print");
+        let text = String::from_utf8(output).expect("valid UTF-8");
+
+        let decay = text
+            .lines()
+            .find(|line| line.starts_with("CONS_DECAY "))
+            .expect("CONS_DECAY listed");
+        assert!(decay.contains("0.5") && decay.ends_with("(env)"));
+        let host = text
+            .lines()
+            .find(|line| line.starts_with("OLLAMA_HOST "))
+            .expect("OLLAMA_HOST listed");
+        assert!(host.contains("http://localhost:11434") && host.ends_with("(default)"));
+    }
+
+    #[test]
+    fn execute_config_json_emits_entries() {
+        let mut output = Vec::new();
+        execute_config(&Config::default(), true, &mut output).expect("config should print");
+        let entries: Vec<serde_json::Value> = serde_json::from_slice(&output).expect("valid JSON");
+
+        let threshold = entries
+            .iter()
+            .find(|e| e["name"] == "CONS_THRESHOLD")
+            .expect("CONS_THRESHOLD listed");
+        assert_eq!(threshold["value"], "0.1");
+        assert_eq!(threshold["source"], "default");
+    }
+
+    // --- No-AI Add Tests ---
+
+    #[test]
+    fn execute_add_with_no_ai_applies_only_explicit_tags() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add(
+            "offline thought",
+            Some("rust,cli"),
+            None,
+            None,
+            None,
+            false,
+            db,
+        )
+        .expect("add should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 1);
+
+        let note = &notes[0];
+        assert_eq!(note.content(), "offline thought");
+        assert!(note.content_enhanced().is_none());
+        assert_eq!(note.tags().len(), 2);
+        assert!(note.tags().iter().all(|tag| tag.source().is_user()));
+    }
+
+    #[test]
+    fn execute_add_with_dedupe_skips_existing_content() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add("pasted snippet", None, None, None, None, true, db)
+            .expect("add should succeed");
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add("pasted snippet\n", Some("rust"), None, None, None, true, db)
+            .expect("duplicate add should succeed without creating");
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add("pasted snippet", None, None, None, None, false, db)
+            .expect("add should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 2, "only the deduped add is skipped");
+        assert!(notes.iter().all(|note| note.tags().is_empty()));
+    }
+
+    // --- Auto-Tagging Tests (Task Group 3) ---
+
+    #[test]
+    fn execute_add_with_preset_combines_preset_and_explicit_tags() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_preset_add("Meeting", "work, meetings", db).expect("preset add should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add(
+            "sprint planning",
+            Some("planning,work"),
+            Some("meeting"),
+            None,
+            None,
+            false,
+            db,
+        )
+        .expect("add should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_add("orphan", None, Some("missing"), None, None, false, db)
+            .expect_err("unknown preset should fail");
+        assert!(is_user_error(&err));
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 1, "nothing is created for an unknown preset");
+
+        let mut tags: Vec<String> = notes[0]
+            .tags()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+        tags.sort();
+        assert_eq!(tags, vec!["meetings", "planning", "work"]);
+    }
+
+    #[test]
+    fn execute_tag_preset_remove_rejects_missing_preset() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_preset_add("meeting", "work", db).expect("preset add should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_tag_preset_list(db).is_ok());
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_preset_remove("Meeting", db).expect("remove should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_tag_preset_remove("meeting", db).expect_err("second remove should fail");
+        assert!(is_user_error(&err));
+    }
+
+    #[test]
+    fn note_creation_succeeds_even_if_ollama_unavailable() {
+        // Test that note creation succeeds even if Ollama is unavailable
+        // (pipeline errors are caught and logged, not propagated)
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let ai = AiPipeline::connect(None);
+        let result = execute_add(
+            "Test note",
+            None,
+            None,
+            ai.as_ref().ok(),
+            ai.as_ref().err(),
+            false,
+            db,
+        );
+        // Note creation should succeed regardless of Ollama availability
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_add_creates_note_and_attempts_auto_tagging() {
+        // Test that execute_add creates the note and attempts auto-tagging
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let ai = AiPipeline::connect(None);
+        let result = execute_add(
+            "Test note",
+            None,
+            None,
+            ai.as_ref().ok(),
+            ai.as_ref().err(),
+            false,
+            db,
+        );
+        // Note creation should succeed (auto-tag errors are logged, not propagated)
+        assert!(result.is_ok());
+    }
+
+    /// Mock Ollama client answering enhancement and tagging prompts with fixed JSON.
+    struct FixedResponseClient;
+
+    impl OllamaClientTrait for FixedResponseClient {
+        fn generate(&self, _model: &str, prompt: &str) -> Result<String, cons::OllamaError> {
+            if prompt.contains("note enhancement assistant") {
+                Ok(
+                    r#"{"enhanced_content": "Learning Rust ownership rules.", "confidence": 0.8}"#
+                        .to_string(),
+                )
+            } else {
+                Ok(r#"{"rust": 0.9, "ownership": 0.7}"#.to_string())
+            }
+        }
+    }
+
+    #[test]
+    fn format_enhancement_status_includes_duration_when_measured() {
+        let unmeasured = EnhancementResult::new("Buy milk.".to_string(), 0.85);
+        assert_eq!(
+            format_enhancement_status(&unmeasured),
+            "Enhanced with 85% confidence"
+        );
+
+        let ai = AiPipeline {
+            client: Arc::new(FixedResponseClient),
+            model: "mock-model".to_string(),
+        };
+        let measured = ai
+            .enhance("rust ownership")
+            .expect("enhance should succeed");
+        let summary = format_enhancement_status(&measured);
+        assert!(summary.starts_with("Enhanced with 80% confidence in "));
+        assert!(summary.ends_with("ms"));
+    }
+
+    #[test]
+    #[serial]
+    fn execute_add_with_mock_pipeline_enhances_and_tags_note() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let ai = AiPipeline {
+            client: Arc::new(FixedResponseClient),
+            model: "mock-model".to_string(),
+        };
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_add(
+            "rust ownership",
+            Some("learning"),
+            None,
+            Some(&ai),
+            None,
+            false,
+            db,
+        )
+        .expect("add should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 1);
+        let note = &notes[0];
+
+        assert_eq!(note.content(), "rust ownership");
+        assert_eq!(
+            note.content_enhanced(),
+            Some("Learning Rust ownership rules.")
+        );
+        assert_eq!(note.enhancement_model(), Some("mock-model"));
+
+        let mut llm_tags: Vec<(&str, u8)> = note
+            .tags()
+            .iter()
+            .filter(|tag| tag.model() == Some("mock-model"))
+            .map(|tag| (tag.name(), tag.confidence()))
+            .collect();
+        llm_tags.sort();
+        assert_eq!(llm_tags, vec![("ownership", 70), ("rust", 90)]);
         assert!(
-            result.is_ok(),
-            "note capture should succeed even if enhancement fails"
+            note.tags()
+                .iter()
+                .any(|tag| tag.name() == "learning" && tag.source().is_user())
         );
     }
 
     #[test]
-    fn enhancement_runs_after_save_before_tagging() {
-        // Test workflow order: save -> enhance -> tag
+    fn execute_add_dry_run_computes_ai_artifacts_without_saving() {
+        let ai = AiPipeline {
+            client: Arc::new(FixedResponseClient),
+            model: "mock-model".to_string(),
+        };
+        let artifacts = ai.compute_artifacts("rust ownership");
+        let enhancement = artifacts.enhancement.expect("enhancement should succeed");
+        assert_eq!(
+            enhancement.enhanced_content(),
+            "Learning Rust ownership rules."
+        );
+        assert_eq!(artifacts.tags.expect("tags should succeed").len(), 2);
+
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        NoteService::new(Database::open(&db_path).expect("failed to open database"))
+            .create_note("existing", Some(&["learning"]))
+            .expect("failed to create note");
+
+        execute_add_dry_run(
+            "rust ownership",
+            Some("learning"),
+            None,
+            Some(&ai),
+            None,
+            false,
+            Database::open_read_only(&db_path).expect("failed to open database"),
+        )
+        .expect("dry run should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let notes = service
+            .list_notes(cons::ListNotesOptions::default())
+            .expect("failed to list notes");
+        assert_eq!(notes.len(), 1, "dry run must not create a note");
+        assert_eq!(notes[0].content(), "existing");
+        assert!(notes[0].content_enhanced().is_none());
+        let tag_count: i64 = service
+            .database()
+            .connection()
+            .query_row("SELECT COUNT(*) FROM tags", [], |row| row.get(0))
+            .expect("failed to count tags");
+        assert_eq!(tag_count, 1, "dry run must not create suggested tags");
+    }
+
+    #[test]
+    fn execute_add_dry_run_rejects_unknown_preset() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let err = execute_add_dry_run("note", None, Some("missing"), None, None, false, db)
+            .expect_err("unknown preset should fail");
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn handle_add_dry_run_does_not_create_database() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("nested").join("notes.db");
+        let cmd = AddCommand {
+            content: Some("preview only".to_string()),
+            from_file: None,
+            tags: Some("draft".to_string()),
+            preset: None,
+            no_ai: true,
+            model: None,
+            dedupe: false,
+            dry_run: true,
+        };
+
+        handle_add(&cmd, &db_path).expect("dry run should succeed");
+        assert!(!db_path.exists());
+        assert!(!temp_dir.path().join("nested").exists());
+    }
+
+    #[test]
+    fn manual_and_auto_generated_tags_coexist_on_same_note() {
+        // Test that manual tags and auto-generated tags can both exist on a note
+        // This is tested at the NoteService level - both tag sources are supported
         let db = Database::in_memory().expect("failed to create in-memory database");
         let service = NoteService::new(db);
 
-        // Create note (step 1: save)
+        // Create note with manual tags
         let note = service
-            .create_note("workflow test", None)
-            .expect("note creation should succeed");
+            .create_note("Test note", Some(&["manual-tag"]))
+            .expect("failed to create note");
 
-        // At this point, note is saved with original content
-        let after_save = service
+        // Add auto-generated tags (simulating background task)
+        let llm_source = TagSource::llm("test-model", 85);
+        service
+            .add_tags_to_note(note.id(), &["auto-tag"], llm_source)
+            .expect("failed to add auto-generated tags");
+
+        // Retrieve note and verify both tag types exist
+        let retrieved = service
             .get_note(note.id())
             .expect("failed to get note")
             .expect("note should exist");
-        assert_eq!(after_save.content(), "workflow test");
-        assert_eq!(after_save.content_enhanced(), None);
 
-        // Step 2: Enhancement would happen here (simulated)
-        // In real flow, enhance_note is called here
+        assert_eq!(retrieved.tags().len(), 2, "note should have 2 tags");
+        // Verify both user and LLM tags are present
+        let has_user_tag = retrieved.tags().iter().any(|ta| ta.source().is_user());
+        let has_llm_tag = retrieved.tags().iter().any(|ta| ta.source().is_llm());
+        assert!(has_user_tag, "note should have user tag");
+        assert!(has_llm_tag, "note should have LLM tag");
+    }
 
-        // Step 3: Tagging happens on ORIGINAL content
-        // This ensures tags reflect user's original intent, not AI expansion
-        let source = TagSource::llm("test-model", 85);
-        service
-            .add_tags_to_note(note.id(), &["test-tag"], source)
-            .expect("tagging should succeed");
+    // --- Test Review & Gap Analysis Tests (Task Group 4) ---
 
-        let after_tag = service
-            .get_note(note.id())
-            .expect("failed to get note")
-            .expect("note should exist");
-        assert_eq!(after_tag.tags().len(), 1);
+    #[test]
+    fn confidence_score_conversion_f64_to_u8_works_correctly() {
+        // Test that confidence scores are converted correctly from f64 (0.0-1.0) to u8 (0-100)
+        let test_cases: Vec<(f64, u8)> = vec![
+            (0.0, 0u8),
+            (0.5, 50u8),
+            (0.85, 85u8),
+            (1.0, 100u8),
+            (0.955, 96u8), // Test rounding
+        ];
+
+        for (f64_val, expected_u8) in test_cases {
+            let actual_u8 = (f64_val * 100.0_f64).round() as u8;
+            assert_eq!(
+                actual_u8, expected_u8,
+                "f64 {} should convert to u8 {}",
+                f64_val, expected_u8
+            );
+        }
     }
 
     #[test]
-    fn list_command_displays_original_and_enhanced_content() {
-        // Test that execute_list shows both original and enhanced content
+    fn model_name_stored_in_tag_source_llm_variant() {
+        // Test that model name from OLLAMA_MODEL env var is stored in TagSource::Llm
         let db = Database::in_memory().expect("failed to create in-memory database");
         let service = NoteService::new(db);
 
-        // Create note with enhancement data
         let note = service
-            .create_note("quick thought", None)
+            .create_note("Test note", None)
             .expect("failed to create note");
 
-        // Simulate enhancement update
-        let now = time::OffsetDateTime::now_utc();
+        // Add tags with specific model name
+        let model_name = "gemma3:4b";
+        let source = TagSource::llm(model_name, 85);
         service
-            .update_note_enhancement(
-                note.id(),
-                "This is a quick thought about something important.",
-                "test-model",
-                0.85,
-                now,
-            )
-            .expect("failed to update enhancement");
+            .add_tags_to_note(note.id(), &["test-tag"], source)
+            .expect("failed to add tags");
 
-        // Test the display format
+        // Retrieve note and verify model name is stored
         let retrieved = service
             .get_note(note.id())
             .expect("failed to get note")
             .expect("note should exist");
 
-        let formatted = format_note_content(&retrieved);
+        let llm_tags: Vec<_> = retrieved
+            .tags()
+            .iter()
+            .filter(|ta| ta.source().is_llm())
+            .collect();
 
-        // Verify formatted output contains original content
-        assert!(
-            formatted.contains("quick thought"),
-            "formatted output should contain original content"
+        assert_eq!(llm_tags.len(), 1, "should have one LLM tag");
+        assert_eq!(
+            llm_tags[0].model(),
+            Some(model_name),
+            "model name should be stored in TagSource"
         );
+    }
 
-        // Verify formatted output contains separator
-        assert!(
-            formatted.contains("---"),
+    #[test]
+    #[serial]
+    fn ai_pipeline_returns_error_when_ollama_not_reachable() {
+        // Test that building the AI pipeline returns a helpful error when Ollama is not reachable
+        // and OLLAMA_MODEL is not set (triggering auto-detection)
+
+        // Save current env vars
+        let old_host = std::env::var("OLLAMA_HOST").ok();
+        let old_model = std::env::var("OLLAMA_MODEL").ok();
+
+        // Point to a non-existent Ollama instance and clear OLLAMA_MODEL
+        // SAFETY: This test runs serially
+        unsafe {
+            std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:99999");
+            std::env::remove_var("OLLAMA_MODEL");
+        };
+
+        let result = AiPipeline::connect(None).map(|ai| ai.model);
+
+        // Restore env vars
+        unsafe {
+            match old_host {
+                Some(v) => std::env::set_var("OLLAMA_HOST", v),
+                None => std::env::remove_var("OLLAMA_HOST"),
+            }
+            match old_model {
+                Some(v) => std::env::set_var("OLLAMA_MODEL", v),
+                None => std::env::remove_var("OLLAMA_MODEL"),
+            }
+        };
+
+        assert!(
+            result.is_err(),
+            "should return error when Ollama not reachable"
+        );
+
+        let error_msg = result.unwrap_err().to_string();
+        // Should mention Ollama or provide helpful guidance
+        assert!(
+            error_msg.contains("Ollama") || error_msg.contains("ollama"),
+            "error should mention Ollama: {error_msg}"
+        );
+    }
+
+    #[test]
+    fn tag_source_llm_constructor_accepts_model_and_confidence() {
+        // Test that TagSource::llm() constructor works correctly
+        let source = TagSource::llm("test-model", 75);
+        assert!(source.is_llm());
+        assert_eq!(source.confidence(), 75);
+        assert_eq!(source.model(), Some("test-model"));
+    }
+
+    // --- List Command Tests (Task Group 1) ---
+
+    #[test]
+    fn list_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing with short flags
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "list", "-l", "5", "-t", "rust,programming"])
+            .expect("failed to parse list command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("list").is_some());
+    }
+
+    #[test]
+    fn execute_list_with_in_memory_database_returns_notes() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create some notes
+        service
+            .create_note("First note", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Second note", Some(&["rust", "programming"]))
+            .expect("failed to create note");
+
+        // Create a new database with a test note
+        let db2 = Database::in_memory().expect("failed to create in-memory database");
+        let service2 = NoteService::new(db2);
+        service2
+            .create_note("Test note", None)
+            .expect("failed to create note");
+
+        // Test execute_list function (accepts Database)
+        let db3 = Database::in_memory().expect("failed to create in-memory database");
+        let service3 = NoteService::new(db3);
+        service3
+            .create_note("List test note", None)
+            .expect("failed to create note");
+
+        let result = execute_list(
+            &ListCommand {
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service3,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_list_with_empty_database_shows_no_notes_found() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        let result = execute_list(
+            &ListCommand {
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_list_with_tags_filter_applies_correctly() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with different tags
+        service
+            .create_note("Rust note", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Programming note", Some(&["programming"]))
+            .expect("failed to create note");
+        service
+            .create_note("Rust programming note", Some(&["rust", "programming"]))
+            .expect("failed to create note");
+
+        // Filter by tags
+        let result = execute_list(
+            &ListCommand {
+                limit: Some(10),
+                tags: Some("rust,programming".to_string()),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_ok());
+    }
+
+    // --- Output Formatting Tests (Task Group 2) ---
+
+    #[test]
+    fn get_tag_names_resolves_tag_ids_to_display_names() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a note with tags to ensure tags exist in database
+        let note = service
+            .create_note("Test note", Some(&["rust", "programming"]))
+            .expect("failed to create note");
+
+        // Test batch tag name resolution
+        let tag_names =
+            get_tag_names(service.database(), note.tags()).expect("failed to get tag names");
+
+        assert_eq!(tag_names.len(), 2, "should have 2 tags");
+        assert!(
+            tag_names.contains(&"rust".to_string()),
+            "should contain rust"
+        );
+        assert!(
+            tag_names.contains(&"programming".to_string()),
+            "should contain programming"
+        );
+    }
+
+    #[test]
+    fn get_tag_names_returns_empty_for_empty_assignments() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Query with empty tag assignments
+        let tag_names =
+            get_tag_names(&db, &[]).expect("get_tag_names should not error for empty assignments");
+
+        assert!(
+            tag_names.is_empty(),
+            "should return empty vec for empty assignments"
+        );
+    }
+
+    #[test]
+    fn timestamp_formats_as_yyyy_mm_dd_hh_mm() {
+        use time::macros::format_description;
+
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a note
+        let note = service
+            .create_note("Timestamp test", None)
+            .expect("failed to create note");
+
+        // Format timestamp using the same format as execute_list
+        let format = format_description!("[year]-[month]-[day] [hour]:[minute]");
+        let timestamp = note
+            .created_at()
+            .format(&format)
+            .expect("failed to format timestamp");
+
+        // Verify format matches expected pattern (YYYY-MM-DD HH:MM)
+        // Example: "2025-12-23 14:30"
+        assert_eq!(timestamp.len(), 16, "timestamp should be 16 characters");
+        assert_eq!(
+            &timestamp[4..5],
+            "-",
+            "character at position 4 should be '-'"
+        );
+        assert_eq!(
+            &timestamp[7..8],
+            "-",
+            "character at position 7 should be '-'"
+        );
+        assert_eq!(
+            &timestamp[10..11],
+            " ",
+            "character at position 10 should be space"
+        );
+        assert_eq!(
+            &timestamp[13..14],
+            ":",
+            "character at position 13 should be ':'"
+        );
+    }
+
+    #[test]
+    fn note_display_with_multiple_tags_shows_hashtag_format() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a note with multiple tags
+        let note = service
+            .create_note("Test note", Some(&["rust", "programming", "tutorial"]))
+            .expect("failed to create note");
+
+        // Collect tag names in hashtag format (simulating execute_list behavior)
+        let tag_names: Vec<String> = get_tag_names(service.database(), note.tags())
+            .expect("failed to get tag names")
+            .into_iter()
+            .map(|name| format!("#{}", name))
+            .collect();
+
+        // Verify all tags are present in hashtag format
+        assert_eq!(tag_names.len(), 3, "should have 3 tags");
+        assert!(
+            tag_names.contains(&"#rust".to_string()),
+            "should contain #rust"
+        );
+        assert!(
+            tag_names.contains(&"#programming".to_string()),
+            "should contain #programming"
+        );
+        assert!(
+            tag_names.contains(&"#tutorial".to_string()),
+            "should contain #tutorial"
+        );
+
+        // Verify joined output (as it appears in execute_list)
+        let tags_display = tag_names.join(" ");
+        assert!(
+            tags_display.contains("#rust"),
+            "joined output should contain #rust"
+        );
+        assert!(
+            tags_display.contains("#programming"),
+            "joined output should contain #programming"
+        );
+        assert!(
+            tags_display.contains("#tutorial"),
+            "joined output should contain #tutorial"
+        );
+    }
+
+    // --- Tag Alias CLI Tests (Task Group 3) ---
+
+    #[test]
+    fn tag_alias_add_creates_alias_correctly() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let result = execute_tag_alias_add("ml", "machine-learning", db);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn tag_alias_add_with_non_existent_canonical_creates_tag_first() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Add alias with non-existent canonical tag (this should auto-create the tag)
+        let result = execute_tag_alias_add("ai", "artificial-intelligence", db);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn tag_alias_list_displays_aliases_grouped_by_canonical() {
+        // Create database and add multiple aliases
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create multiple aliases for different canonical tags
+        let ml_tag = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create tag");
+        service
+            .create_alias("ml", ml_tag, "user", 1.0, None)
+            .expect("failed to add ml alias");
+
+        let ai_tag = service
+            .get_or_create_tag("artificial-intelligence")
+            .expect("failed to create tag");
+        service
+            .create_alias("ai", ai_tag, "user", 1.0, None)
+            .expect("failed to add ai alias");
+
+        let dl_tag = service
+            .get_or_create_tag("deep-learning")
+            .expect("failed to create tag");
+        service
+            .create_alias("dl", dl_tag, "user", 1.0, None)
+            .expect("failed to add dl alias");
+
+        // Now test the list command with the same database
+        let db2 = Database::in_memory().expect("failed to create in-memory database");
+        let service2 = NoteService::new(db2);
+
+        // Recreate one alias to test display
+        let test_tag = service2
+            .get_or_create_tag("test-tag")
+            .expect("failed to create tag");
+        service2
+            .create_alias("t", test_tag, "user", 1.0, None)
+            .expect("failed to add test alias");
+
+        // Get the database from service2
+        // Since we can't get db back from service, we'll create a new db for the execute function
+        let db3 = Database::in_memory().expect("failed to create in-memory database");
+        let service3 = NoteService::new(db3);
+        let test_tag3 = service3
+            .get_or_create_tag("example")
+            .expect("failed to create tag");
+        service3
+            .create_alias("ex", test_tag3, "user", 1.0, None)
+            .expect("failed to add alias");
+
+        let aliases = service3.list_aliases().expect("failed to list aliases");
+        assert_eq!(aliases.len(), 1);
+    }
+
+    #[test]
+    fn tag_alias_remove_deletes_alias() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create an alias
+        let ml_tag = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create tag");
+        service
+            .create_alias("ml", ml_tag, "user", 1.0, None)
+            .expect("failed to add alias");
+
+        // Verify it exists
+        let resolved_before = service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias");
+        assert!(resolved_before.is_some());
+
+        // Remove the alias
+        service.remove_alias("ml").expect("failed to remove alias");
+
+        // Verify it's gone
+        let resolved_after = service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias");
+        assert_eq!(resolved_after, None);
+    }
+
+    #[test]
+    fn tag_alias_command_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing tag-alias add
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tag-alias", "add", "ml", "machine-learning"])
+            .expect("failed to parse tag-alias add command");
+
+        assert!(matches.subcommand_matches("tag-alias").is_some());
+
+        // Test parsing tag-alias list
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tag-alias", "list"])
+            .expect("failed to parse tag-alias list command");
+
+        assert!(matches.subcommand_matches("tag-alias").is_some());
+
+        // Test parsing tag-alias remove
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tag-alias", "remove", "ml"])
+            .expect("failed to parse tag-alias remove command");
+
+        assert!(matches.subcommand_matches("tag-alias").is_some());
+    }
+
+    #[test]
+    fn parse_alias_mappings_skips_comments_and_blank_lines() {
+        let input = "# team vocabulary\n\nml = machine-learning\n  AI=Artificial Intelligence  \n";
+        let mappings = parse_alias_mappings(input.as_bytes()).expect("failed to parse");
+        assert_eq!(
+            mappings,
+            vec![
+                ("ml".to_string(), "machine-learning".to_string()),
+                ("AI".to_string(), "Artificial Intelligence".to_string()),
+            ]
+        );
+
+        let err = parse_alias_mappings("ml = machine-learning\nbroken line\n".as_bytes())
+            .expect_err("line without '=' should fail");
+        assert!(err.to_string().contains("line 2"));
+        assert!(parse_alias_mappings("ml =\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn tag_alias_import_creates_aliases_and_skips_chains() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let alias_path = temp_dir.path().join("aliases.txt");
+        std::fs::write(
+            &alias_path,
+            "ml = Machine Learning\nai = artificial-intelligence\n# chain\nmlearn = ml\n",
+        )
+        .expect("failed to write alias file");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_alias_import(&alias_path, db).expect("import should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let machine_learning = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to get tag");
+        assert_eq!(
+            service
+                .resolve_alias("ml")
+                .expect("failed to resolve alias"),
+            Some(machine_learning)
+        );
+        assert!(
+            service
+                .resolve_alias("ai")
+                .expect("failed to resolve alias")
+                .is_some()
+        );
+        assert_eq!(
+            service
+                .resolve_alias("mlearn")
+                .expect("failed to resolve alias"),
+            None,
+            "chained alias should be skipped"
+        );
+        let aliases = service.list_aliases().expect("failed to list aliases");
+        assert!(aliases.iter().all(|alias| alias.source() == "user"));
+    }
+
+    #[test]
+    fn tag_alias_retarget_moves_existing_alias() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_alias_add("ml", "machine-learning", db).expect("failed to add alias");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_alias_retarget("ml", "Meta Learning", db).expect("failed to retarget alias");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let meta_learning = service
+            .get_or_create_tag("meta-learning")
+            .expect("failed to get tag");
+        assert_eq!(
+            service
+                .resolve_alias("ml")
+                .expect("failed to resolve alias"),
+            Some(meta_learning)
+        );
+    }
+
+    #[test]
+    fn tag_alias_retarget_fails_for_missing_alias_without_creating_tag() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_tag_alias_retarget("ml", "meta-learning", db)
+            .expect_err("missing alias should fail");
+        assert!(is_user_error(&err));
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let tag_count: i64 = db
+            .connection()
+            .query_row("SELECT COUNT(*) FROM tags", [], |row| row.get(0))
+            .expect("failed to count tags");
+        assert_eq!(tag_count, 0);
+    }
+
+    #[test]
+    fn tag_alias_rename_fixes_alias_spelling() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_alias_add("machien-learning", "machine-learning", db)
+            .expect("failed to add alias");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tag_alias_rename("machien-learning", "mach-learning", db)
+            .expect("failed to rename alias");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let machine_learning = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to get tag");
+        assert_eq!(
+            service
+                .resolve_alias("mach-learning")
+                .expect("failed to resolve alias"),
+            Some(machine_learning)
+        );
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_tag_alias_rename("missing", "other", db)
+            .expect_err("missing alias should fail");
+        assert!(is_user_error(&err));
+    }
+
+    #[test]
+    fn tag_alias_add_normalizes_both_alias_and_canonical() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Add alias with non-normalized names
+        let result = execute_tag_alias_add("ML!", "Machine Learning", db);
+        assert!(result.is_ok());
+
+        // Verify normalization worked by checking in a new database instance
+        let db2 = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db2);
+
+        // Create the same alias again to test normalization
+        let tag = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create tag");
+        service
+            .create_alias("ml", tag, "user", 1.0, None)
+            .expect("failed to create alias");
+
+        let resolved = service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias");
+        assert!(
+            resolved.is_some(),
+            "alias should be normalized to 'ml' (lowercase, no punctuation)"
+        );
+    }
+
+    // --- AutoTagger Alias Integration Tests (Task Group 4) ---
+
+    #[test]
+    fn auto_tagging_creates_alias_when_llm_suggests_existing_tag_variant() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Pre-create a canonical tag
+        let canonical_tag_id = service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create canonical tag");
+
+        // Simulate LLM suggesting "ml" as a tag
+        // In real scenario, tag_note_with_llm would detect "ml" normalizes differently from "machine-learning"
+        // and create an alias mapping
+
+        // For now, manually create the alias as tag_note_with_llm will do
+        service
+            .create_alias("ml", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))
+            .expect("failed to create alias");
+
+        // Verify alias was created
+        let resolved = service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias");
+        assert_eq!(
+            resolved,
+            Some(canonical_tag_id),
+            "alias should resolve to canonical tag"
+        );
+
+        // Verify alias has correct metadata
+        let aliases = service.list_aliases().expect("failed to list aliases");
+        assert_eq!(aliases.len(), 1, "should have one alias");
+        let alias_info = &aliases[0];
+        assert_eq!(alias_info.alias(), "ml");
+        assert_eq!(alias_info.canonical_tag_id(), canonical_tag_id);
+        assert_eq!(alias_info.source(), "llm");
+        assert_eq!(alias_info.confidence(), 0.85);
+        assert_eq!(alias_info.model_version(), Some("deepseek-r1:8b"));
+    }
+
+    #[test]
+    fn alias_stored_with_source_llm_and_correct_confidence() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create canonical tag
+        let canonical_tag_id = service
+            .get_or_create_tag("artificial-intelligence")
+            .expect("failed to create canonical tag");
+
+        // Create LLM alias with specific confidence
+        let confidence = 0.92;
+        service
+            .create_alias("ai", canonical_tag_id, "llm", confidence, Some("gemma3:4b"))
+            .expect("failed to create alias");
+
+        // Verify alias metadata
+        let aliases = service.list_aliases().expect("failed to list aliases");
+        assert_eq!(aliases.len(), 1);
+        let alias_info = &aliases[0];
+        assert_eq!(alias_info.source(), "llm");
+        assert_eq!(alias_info.confidence(), confidence);
+    }
+
+    #[test]
+    fn model_version_from_ollama_model_stored_in_alias() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create canonical tag
+        let canonical_tag_id = service
+            .get_or_create_tag("deep-learning")
+            .expect("failed to create canonical tag");
+
+        // Create alias with specific model version
+        let model_version = "deepseek-r1:8b";
+        service
+            .create_alias("dl", canonical_tag_id, "llm", 0.88, Some(model_version))
+            .expect("failed to create alias");
+
+        // Verify model version is stored
+        let aliases = service.list_aliases().expect("failed to list aliases");
+        assert_eq!(aliases.len(), 1);
+        let alias_info = &aliases[0];
+        assert_eq!(alias_info.model_version(), Some(model_version));
+    }
+
+    #[test]
+    fn no_alias_created_for_genuinely_new_tags() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a tag directly (simulating LLM suggesting a new tag)
+        let new_tag_id = service
+            .get_or_create_tag("quantum-computing")
+            .expect("failed to create new tag");
+
+        // Verify no alias exists for this tag
+        let resolved = service
+            .resolve_alias("quantum-computing")
+            .expect("failed to resolve alias");
+        assert_eq!(resolved, None, "new tag should not have alias");
+
+        // Verify aliases list is empty
+        let aliases = service.list_aliases().expect("failed to list aliases");
+        assert_eq!(aliases.len(), 0, "no aliases should exist");
+
+        // Verify the tag was actually created
+        let conn = service.database().connection();
+        let tag_exists: bool = conn
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)",
+                [new_tag_id.get()],
+                |row| row.get(0),
+            )
+            .expect("failed to check tag existence");
+        assert!(tag_exists, "tag should exist in database");
+    }
+
+    #[test]
+    fn alias_creation_is_fail_safe_does_not_block_note_capture() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a note - this simulates the note capture flow
+        let note = service
+            .create_note("Test note about AI", None)
+            .expect("note creation should succeed");
+
+        // Simulate alias creation failure (e.g., invalid canonical tag ID)
+        let invalid_tag_id = TagId::new(999999);
+        let alias_result =
+            service.create_alias("ai", invalid_tag_id, "llm", 0.85, Some("test-model"));
+
+        // Alias creation should fail (canonical tag doesn't exist)
+        assert!(
+            alias_result.is_err(),
+            "alias creation should fail with invalid canonical tag"
+        );
+
+        // But the note should still exist and be retrievable
+        let retrieved_note = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(retrieved_note.content(), "Test note about AI");
+    }
+
+    #[test]
+    fn alias_creation_error_logged_but_does_not_propagate() {
+        // This test verifies that tag_note_with_llm's error handling is fail-safe
+        // We'll test this by simulating the workflow without actually calling tag_note_with_llm
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a note successfully
+        let note = service
+            .create_note("Learning Rust async patterns", None)
+            .expect("note creation should succeed");
+
+        // Verify note exists even if we don't attempt auto-tagging
+        let retrieved = service.get_note(note.id()).expect("failed to get note");
+        assert!(retrieved.is_some(), "note should exist");
+
+        // The actual tag_note_with_llm function catches errors and logs them
+        // without propagating, so note capture always succeeds
+        // This is verified by the execute_add tests which show that
+        // tag_note_with_llm errors don't cause execute_add to fail
+    }
+
+    #[test]
+    fn detect_alias_opportunity_detects_abbreviations() {
+        // Test the alias detection used when applying LLM tags
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create a canonical tag
+        service
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create canonical tag");
+
+        // Test abbreviation detection
+        let result = service
+            .detect_alias_opportunity("ml")
+            .expect("alias detection should succeed");
+        assert!(
+            result.is_some(),
+            "should detect 'ml' as abbreviation of 'machine-learning'"
+        );
+
+        // Test that longer tags don't create aliases
+        let result = service
+            .detect_alias_opportunity("quantum-computing")
+            .expect("alias detection should succeed");
+        assert_eq!(
+            result, None,
+            "should not detect alias opportunity for long tag"
+        );
+
+        // Test another common abbreviation pattern
+        service
+            .get_or_create_tag("artificial-intelligence")
+            .expect("failed to create canonical tag");
+
+        let result = service
+            .detect_alias_opportunity("ai")
+            .expect("alias detection should succeed");
+        assert!(
+            result.is_some(),
+            "should detect 'ai' as abbreviation of 'artificial-intelligence'"
+        );
+    }
+
+    // --- CLI Enhancement Integration Tests (Task Group 4) ---
+
+    #[test]
+    fn execute_add_calls_enhancement_after_note_save() {
+        // Test that execute_add attempts enhancement after note is saved
+        // Enhancement may fail (no Ollama), but note creation should succeed
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create note directly to test the flow
+        let note = service
+            .create_note("quick thought", None)
+            .expect("note creation should succeed");
+
+        // Verify note exists with original content
+        let retrieved = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(retrieved.content(), "quick thought");
+
+        // Enhancement fields should be None if Ollama is unavailable
+        // (This is the fail-safe behavior we're testing)
+        // Note: In real scenario, enhance_note would be called after create_note
+    }
+
+    #[test]
+    fn enhancement_failure_does_not_block_note_capture() {
+        // Test that note creation succeeds even if enhancement fails
+        // This verifies the fail-safe pattern in execute_add
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Call execute_add - it should succeed even without Ollama
+        let ai = AiPipeline::connect(None);
+        let result = execute_add(
+            "test note",
+            None,
+            None,
+            ai.as_ref().ok(),
+            ai.as_ref().err(),
+            false,
+            db,
+        );
+
+        // Note creation should succeed (enhancement errors are caught)
+        assert!(
+            result.is_ok(),
+            "note capture should succeed even if enhancement fails"
+        );
+    }
+
+    #[test]
+    fn enhancement_runs_after_save_before_tagging() {
+        // Test workflow order: save -> enhance -> tag
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create note (step 1: save)
+        let note = service
+            .create_note("workflow test", None)
+            .expect("note creation should succeed");
+
+        // At this point, note is saved with original content
+        let after_save = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(after_save.content(), "workflow test");
+        assert_eq!(after_save.content_enhanced(), None);
+
+        // Step 2: Enhancement would happen here (simulated)
+        // In real flow, enhance_note is called here
+
+        // Step 3: Tagging happens on ORIGINAL content
+        // This ensures tags reflect user's original intent, not AI expansion
+        let source = TagSource::llm("test-model", 85);
+        service
+            .add_tags_to_note(note.id(), &["test-tag"], source)
+            .expect("tagging should succeed");
+
+        let after_tag = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(after_tag.tags().len(), 1);
+    }
+
+    #[test]
+    fn list_command_displays_original_and_enhanced_content() {
+        // Test that execute_list shows both original and enhanced content
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create note with enhancement data
+        let note = service
+            .create_note("quick thought", None)
+            .expect("failed to create note");
+
+        // Simulate enhancement update
+        let now = time::OffsetDateTime::now_utc();
+        service
+            .update_note_enhancement(
+                note.id(),
+                "This is a quick thought about something important.",
+                "test-model",
+                0.85,
+                now,
+                None,
+            )
+            .expect("failed to update enhancement");
+
+        // Test the display format
+        let retrieved = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+
+        let formatted = format_note_content(&retrieved, None);
+
+        // Verify formatted output contains original content
+        assert!(
+            formatted.contains("quick thought"),
+            "formatted output should contain original content"
+        );
+
+        // Verify formatted output contains separator
+        assert!(
+            formatted.contains("---"),
             "formatted output should contain separator"
         );
 
-        // Verify formatted output contains enhanced content
+        // Verify formatted output contains enhanced content
+        assert!(
+            formatted.contains("This is a quick thought about something important."),
+            "formatted output should contain enhanced content"
+        );
+
+        // Verify formatted output contains confidence
+        assert!(
+            formatted.contains("85% confidence"),
+            "formatted output should show confidence percentage"
+        );
+    }
+
+    #[test]
+    fn format_note_content_shows_stacked_format_with_separator() {
+        // Test the stacked display format helper function
+        use cons::NoteBuilder;
+
+        let now = time::OffsetDateTime::now_utc();
+
+        // Test note WITH enhancement
+        let enhanced_note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("buy milk")
+            .created_at(now)
+            .updated_at(now)
+            .content_enhanced("Buy milk from the grocery store.")
+            .enhancement_confidence(0.75)
+            .build();
+
+        let formatted = format_note_content(&enhanced_note, None);
+
+        assert!(
+            formatted.contains("Content: buy milk"),
+            "should show original content first"
+        );
+        assert!(formatted.contains("---"), "should have separator");
+        assert!(
+            formatted.contains("Buy milk from the grocery store."),
+            "should show enhanced content"
+        );
+        assert!(
+            formatted.contains("75% confidence"),
+            "should show confidence percentage"
+        );
+
+        // Test note WITHOUT enhancement
+        let plain_note = NoteBuilder::new()
+            .id(NoteId::new(2))
+            .content("already complete thought")
+            .created_at(now)
+            .updated_at(now)
+            .build();
+
+        let formatted_plain = format_note_content(&plain_note, None);
+
+        assert!(
+            formatted_plain.contains("Content: already complete thought"),
+            "should show original content"
+        );
+        assert!(
+            !formatted_plain.contains("---"),
+            "should NOT have separator when no enhancement"
+        );
+    }
+
+    #[test]
+    fn confidence_percentage_display_format() {
+        // Test that confidence is displayed as integer percentage
+        use cons::NoteBuilder;
+
+        let now = time::OffsetDateTime::now_utc();
+
+        let test_cases = vec![
+            (0.0, "0% confidence"),
+            (0.5, "50% confidence"),
+            (0.85, "85% confidence"),
+            (1.0, "100% confidence"),
+            (0.955, "96% confidence"), // Test rounding
+        ];
+
+        for (confidence_f64, expected_str) in test_cases {
+            let note = NoteBuilder::new()
+                .id(NoteId::new(1))
+                .content("test")
+                .created_at(now)
+                .updated_at(now)
+                .content_enhanced("enhanced test")
+                .enhancement_confidence(confidence_f64)
+                .build();
+
+            let formatted = format_note_content(&note, None);
+
+            assert!(
+                formatted.contains(expected_str),
+                "confidence {} should display as '{}', got: {}",
+                confidence_f64,
+                expected_str,
+                formatted
+            );
+        }
+    }
+
+    // --- Search Command Tests (Task Group 3) ---
+
+    #[test]
+    fn search_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing with positional query and --limit flag
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "search", "rust programming", "-l", "5"])
+            .expect("failed to parse search command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("search").is_some());
+    }
+
+    #[test]
+    fn execute_search_with_in_memory_database_returns_results() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with searchable content
+        service
+            .create_note("Learning Rust programming", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Python programming tutorial", Some(&["python"]))
+            .expect("failed to create note");
+
+        // Search for Rust-related notes
+        let result = execute_search(
+            &SearchCommand {
+                query: "rust".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_search_with_empty_database_shows_no_notes_found() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Search in empty database
+        let result = execute_search(
+            &SearchCommand {
+                query: "rust".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_ok());
+        // The function should complete successfully and print "No notes found matching query"
+    }
+
+    // --- Dual-Channel Search CLI Tests (Task Group 3) ---
+
+    #[test]
+    fn dual_search_command_parses_correctly() {
+        use clap::CommandFactory;
+
+        // Test that `cons search` command parses correctly with dual-channel search
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "search", "machine learning", "-l", "10"])
+            .expect("failed to parse search command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("search").is_some());
+
+        // Verify the search subcommand has query and limit
+        let search_matches = matches.subcommand_matches("search").unwrap();
+        assert!(search_matches.contains_id("query"));
+        assert!(search_matches.contains_id("limit"));
+    }
+
+    #[test]
+    fn execute_search_calls_dual_search_and_formats_output() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with tags to test dual-channel search
+        service
+            .create_note("Learning Rust programming", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Advanced Rust patterns", Some(&["rust", "advanced"]))
+            .expect("failed to create note");
+
+        // Execute search which should call dual_search internally
+        let result = execute_search(
+            &SearchCommand {
+                query: "rust".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+
+        // Verify the search completes successfully
+        assert!(result.is_ok());
+        // The function should format and display results from dual_search
+    }
+
+    #[test]
+    fn execute_search_displays_graph_skipped_notice() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes without tags or edges (cold-start scenario)
+        service
+            .create_note("Simple note without tags", None)
+            .expect("failed to create note");
+
+        // Execute search - should trigger graph skip due to sparse activation
+        let result = execute_search(
+            &SearchCommand {
+                query: "simple".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+
+        // Verify the search completes successfully
+        assert!(result.is_ok());
+        // The function should print "Note: Graph search skipped (sparse activation)"
+        // when metadata.graph_skipped is true
+    }
+
+    #[test]
+    fn execute_search_with_empty_query_returns_error() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Test empty string
+        let result = execute_search(
+            &SearchCommand {
+                query: "".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        let error_msg = format!("{:#}", error); // Use alternate format to show chain
+        eprintln!("Actual error message: '{}'", error_msg);
+        assert!(
+            error_msg.contains("Search query cannot be empty")
+                || error_msg.contains("cannot be empty"),
+            "Error message '{}' should contain 'cannot be empty'",
+            error_msg
+        );
+    }
+
+    #[test]
+    fn execute_search_with_whitespace_only_query_returns_error() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Test whitespace-only query
+        let result = execute_search(
+            &SearchCommand {
+                query: "   \n\t  ".to_string(),
+                limit: Some(10),
+                ..Default::default()
+            },
+            false,
+            service,
+        );
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        let error_msg = format!("{:#}", error); // Use alternate format to show chain
+        eprintln!("Actual error message: '{}'", error_msg);
+        assert!(
+            error_msg.contains("Search query cannot be empty")
+                || error_msg.contains("cannot be empty"),
+            "Error message '{}' should contain 'cannot be empty'",
+            error_msg
+        );
+    }
+
+    // --- Hierarchy CLI Command Tests (Task Group 3) ---
+
+    #[test]
+    fn hierarchy_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing of `cons hierarchy suggest`
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "hierarchy", "suggest"])
+            .expect("failed to parse hierarchy suggest command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("hierarchy").is_some());
+    }
+
+    #[test]
+    fn hierarchy_remove_command_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "hierarchy", "remove", "rust", "programming"])
+            .expect("failed to parse hierarchy remove command");
+        assert!(matches.subcommand_matches("hierarchy").is_some());
+
+        let result =
+            Cli::command().try_get_matches_from(vec!["cons", "hierarchy", "remove", "rust"]);
+        assert!(result.is_err(), "hierarchy remove should require both tags");
+    }
+
+    #[test]
+    fn execute_hierarchy_remove_deletes_edge() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let rust = service
+                .get_or_create_tag("rust")
+                .expect("failed to create tag");
+            let programming = service
+                .get_or_create_tag("programming")
+                .expect("failed to create tag");
+            service
+                .create_edge(rust, programming, 0.9, "generic", Some("test-model"))
+                .expect("failed to create edge");
+        }
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_remove("Rust", "programming", db).expect("remove should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let edge_count: i64 = db
+            .connection()
+            .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+            .expect("failed to count edges");
+        assert_eq!(edge_count, 0, "edge should be removed");
+    }
+
+    #[test]
+    fn execute_hierarchy_prune_defaults_to_suggester_threshold() {
+        let matches = Cli::try_parse_from(vec!["cons", "hierarchy", "prune"])
+            .expect("failed to parse hierarchy prune");
+        match matches.command {
+            Commands::Hierarchy(HierarchyCommand {
+                command: HierarchyCommands::Prune { min_confidence },
+            }) => assert_eq!(min_confidence, 0.7),
+            _ => panic!("expected hierarchy prune command"),
+        }
+        assert!(
+            Cli::try_parse_from(vec![
+                "cons",
+                "hierarchy",
+                "prune",
+                "--min-confidence",
+                "1.5"
+            ])
+            .is_err(),
+            "min-confidence outside 0.0-1.0 should be rejected"
+        );
+
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let rust = service
+                .get_or_create_tag("rust")
+                .expect("failed to create tag");
+            let programming = service
+                .get_or_create_tag("programming")
+                .expect("failed to create tag");
+            let music = service
+                .get_or_create_tag("music")
+                .expect("failed to create tag");
+            service
+                .create_edge(rust, programming, 0.9, "generic", Some("test-model"))
+                .expect("failed to create edge");
+            service
+                .create_edge(rust, music, 0.4, "generic", Some("test-model"))
+                .expect("failed to create edge");
+        }
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_prune(0.7, db).expect("prune should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let edges = service.list_edges().expect("failed to list edges");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].target_name(), "programming");
+    }
+
+    #[test]
+    fn execute_tags_graph_writes_dot_file() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let output = temp_dir.path().join("cooccurrence.dot");
+
+        let cli = Cli::try_parse_from(["cons", "tags", "graph", "--min", "3"])
+            .expect("failed to parse tags graph");
+        match cli.command {
+            Commands::Tags(TagsCommand {
+                command:
+                    Some(TagsCommands::Graph {
+                        format,
+                        min,
+                        output: None,
+                    }),
+                ..
+            }) => {
+                assert_eq!(format, GraphExportFormatArg::Dot);
+                assert_eq!(min, 3);
+            }
+            _ => panic!("expected tags graph"),
+        }
+
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            for _ in 0..2 {
+                service
+                    .create_note("async runtimes", Some(&["rust", "tokio"]))
+                    .expect("failed to create note");
+            }
+        }
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_tags_graph(GraphExportFormatArg::Dot, 2, Some(&output), db)
+            .expect("export should succeed");
+
+        let dot = std::fs::read_to_string(&output).expect("failed to read output");
+        assert!(dot.starts_with("graph tags {"));
+        assert!(dot.contains(r#""rust" -- "tokio" [label="2""#));
+    }
+
+    #[test]
+    fn execute_hierarchy_export_writes_dot_file() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+        let output = temp_dir.path().join("hierarchy.dot");
+
+        let matches = Cli::try_parse_from(vec!["cons", "hierarchy", "export", "--format", "dot"])
+            .expect("failed to parse hierarchy export");
+        assert!(matches!(
+            matches.command,
+            Commands::Hierarchy(HierarchyCommand {
+                command: HierarchyCommands::Export { output: None, .. }
+            })
+        ));
+
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let tokio = service
+                .get_or_create_tag("tokio")
+                .expect("failed to create tag");
+            let rust = service
+                .get_or_create_tag("rust")
+                .expect("failed to create tag");
+            service
+                .create_edge(tokio, rust, 0.8, "partitive", None)
+                .expect("failed to create edge");
+        }
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_export(GraphExportFormatArg::Dot, Some(&output), db)
+            .expect("export should succeed");
+
+        let dot = std::fs::read_to_string(&output).expect("failed to read output");
+        assert!(dot.contains(r#""tokio" -> "rust""#));
+    }
+
+    fn edge(source: &str, target: &str, kind: &str, confidence: f64) -> cons::EdgeInfo {
+        cons::EdgeInfo::new(
+            TagId::new(0),
+            source,
+            TagId::new(0),
+            target,
+            kind,
+            confidence,
+            "llm",
+        )
+    }
+
+    #[test]
+    fn format_hierarchy_tree_nests_narrower_under_broader() {
+        let edges = vec![
+            edge("rust", "programming", "generic", 0.9),
+            edge("python", "programming", "generic", 0.85),
+            edge("tokio", "rust", "partitive", 0.8),
+            edge("jazz", "music", "generic", 0.7),
+        ];
+
+        assert_eq!(
+            format_hierarchy_tree(&edges),
+            vec![
+                "music",
+                "  jazz (generic, 70%)",
+                "programming",
+                "  python (generic, 85%)",
+                "  rust (generic, 90%)",
+                "    tokio (partitive, 80%)",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_hierarchy_tree_marks_verified_edges() {
+        let mut verified = edge("rust", "programming", "generic", 0.9);
+        verified.verify();
+        let edges = vec![verified, edge("python", "programming", "generic", 0.85)];
+
+        assert_eq!(
+            format_hierarchy_tree(&edges),
+            vec![
+                "programming",
+                "  python (generic, 85%)",
+                "  rust (generic, 90%) ✓",
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_hierarchy_verify_marks_edge_and_rejects_missing_edge() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            let rust = service
+                .get_or_create_tag("rust")
+                .expect("failed to create tag");
+            let programming = service
+                .get_or_create_tag("programming")
+                .expect("failed to create tag");
+            service
+                .create_edge(rust, programming, 0.9, "generic", Some("test-model"))
+                .expect("failed to create edge");
+        }
+
+        let matches = Cli::try_parse_from(vec!["cons", "hierarchy", "show", "--unverified"])
+            .expect("failed to parse hierarchy show");
+        assert!(matches!(
+            matches.command,
+            Commands::Hierarchy(HierarchyCommand {
+                command: HierarchyCommands::Show { unverified: true }
+            })
+        ));
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_verify("Rust", "programming", db).expect("verify should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_hierarchy_verify("programming", "rust", db)
+            .expect_err("missing edge should fail");
+        assert!(is_user_error(&err));
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err =
+            execute_hierarchy_verify("rust", "golang", db).expect_err("unknown tag should fail");
+        assert!(is_user_error(&err));
+        assert!(err.to_string().contains("'golang' does not exist"));
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        assert_eq!(
+            service.find_tag("golang").expect("lookup should succeed"),
+            None,
+            "verify must not create tags"
+        );
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let edges = service.list_edges().expect("failed to list edges");
+        assert!(edges[0].verified());
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_hierarchy_show(true, db).is_ok());
+    }
+
+    #[test]
+    fn format_hierarchy_tree_terminates_on_cycles() {
+        let edges = vec![
+            edge("a", "b", "generic", 0.9),
+            edge("b", "a", "generic", 0.9),
+        ];
+
+        assert_eq!(
+            format_hierarchy_tree(&edges),
+            vec!["a", "  b (generic, 90%)", "    a (generic, 90%) (cycle)"]
+        );
+    }
+
+    #[test]
+    fn execute_hierarchy_add_creates_user_edge() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_add("Tokio", "rust", HierarchyTypeArg::Partitive, 0.9, db)
+            .expect("add should succeed");
+
+        let service = NoteService::new(Database::open(&db_path).expect("failed to open database"));
+        let edges = service.list_edges().expect("failed to list edges");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].source_name(), "tokio");
+        assert_eq!(edges[0].target_name(), "rust");
+        assert_eq!(edges[0].hierarchy_type(), "partitive");
+        assert_eq!(edges[0].confidence(), 0.9);
+        assert_eq!(edges[0].source(), "user");
+    }
+
+    #[test]
+    fn execute_hierarchy_add_rejects_self_edge() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let err = execute_hierarchy_add("Rust", "rust", HierarchyTypeArg::Generic, 1.0, db)
+            .expect_err("self edge should fail");
+        assert!(format!("{err:#}").contains("cannot connect a tag to itself"));
+        assert!(is_user_error(&err));
+    }
+
+    #[test]
+    fn execute_hierarchy_add_cycle_is_user_error() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        execute_hierarchy_add("tokio", "rust", HierarchyTypeArg::Partitive, 0.9, db)
+            .expect("add should succeed");
+
+        let db = Database::open(&db_path).expect("failed to open database");
+        let err = execute_hierarchy_add("rust", "tokio", HierarchyTypeArg::Generic, 0.9, db)
+            .expect_err("cycle should be rejected");
+        assert!(
+            format!("{err:#}")
+                .contains("Cannot add edge: it would create a cycle: rust -> tokio -> rust")
+        );
+        assert!(is_user_error(&err));
+    }
+
+    #[test]
+    fn hierarchy_add_parses_defaults_and_validates_confidence() {
+        let cli = Cli::try_parse_from(["cons", "hierarchy", "add", "tokio", "rust"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Hierarchy(HierarchyCommand {
+                command:
+                    HierarchyCommands::Add {
+                        hierarchy_type,
+                        confidence,
+                        ..
+                    },
+            }) => {
+                assert_eq!(hierarchy_type, HierarchyTypeArg::Generic);
+                assert_eq!(confidence, 1.0);
+            }
+            _ => panic!("expected hierarchy add"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "cons",
+                "hierarchy",
+                "add",
+                "tokio",
+                "rust",
+                "--confidence",
+                "1.5"
+            ])
+            .is_err()
+        );
+        assert!(
+            Cli::try_parse_from([
+                "cons",
+                "hierarchy",
+                "add",
+                "tokio",
+                "rust",
+                "--type",
+                "related"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn execute_hierarchy_show_with_no_edges_succeeds() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        assert!(execute_hierarchy_show(false, db).is_ok());
+    }
+
+    #[test]
+    fn execute_hierarchy_suggest_with_in_memory_database() {
+        // Create database and populate it with notes+tags
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Populate database in a scope, then pass it to execute function
+        {
+            let service = NoteService::new(Database::in_memory().expect("db"));
+            service
+                .create_note("Test note", Some(&["transformer", "neural-network"]))
+                .expect("failed to create note");
+            // service drops here, db is not consumed
+        }
+
+        // Now test execute_hierarchy_suggest with the database
+        // (will return early with "No tags found" since we used a different db above)
+        let result = execute_hierarchy_suggest(None, None, db);
+
+        // Function should complete (either success or graceful error handling)
+        // We don't assert Ok because OLLAMA_MODEL might not be set in test environment
+        // The function is designed to handle missing OLLAMA_MODEL gracefully
+        drop(result);
+    }
+
+    #[test]
+    #[serial]
+    fn execute_hierarchy_suggest_handles_ollama_not_reachable() {
+        // Save current env vars
+        let old_host = std::env::var("OLLAMA_HOST").ok();
+        let old_model = std::env::var("OLLAMA_MODEL").ok();
+
+        // Point to a non-existent Ollama instance and clear OLLAMA_MODEL
+        unsafe {
+            std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:99999");
+            std::env::remove_var("OLLAMA_MODEL");
+        };
+
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Insert a note and tag directly so execute_hierarchy_suggest doesn't return early
+        db.connection()
+            .execute("INSERT INTO notes (id, content) VALUES (1, 'Test note')", [])
+            .expect("failed to insert note");
+        db.connection()
+            .execute("INSERT INTO tags (id, name) VALUES (1, 'test-tag')", [])
+            .expect("failed to insert tag");
+        db.connection()
+            .execute("INSERT INTO note_tags (note_id, tag_id) VALUES (1, 1)", [])
+            .expect("failed to insert note_tag");
+
+        // This should fail because Ollama is not reachable for auto-detection
+        let result = execute_hierarchy_suggest(None, None, db);
+
+        // Restore env vars
+        unsafe {
+            match old_host {
+                Some(v) => std::env::set_var("OLLAMA_HOST", v),
+                None => std::env::remove_var("OLLAMA_HOST"),
+            }
+            match old_model {
+                Some(v) => std::env::set_var("OLLAMA_MODEL", v),
+                None => std::env::remove_var("OLLAMA_MODEL"),
+            }
+        };
+
+        assert!(
+            result.is_err(),
+            "should return error when Ollama not reachable"
+        );
+
+        let error_msg = result.unwrap_err().to_string();
+        assert!(
+            error_msg.contains("Ollama") || error_msg.contains("ollama"),
+            "error should mention Ollama: {error_msg}"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn execute_hierarchy_suggest_handles_empty_tag_set() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // No notes created, so no tags exist
+
+        // Set OLLAMA_MODEL immediately before the call to minimize race conditions with other tests
+        // SAFETY: Test runs in isolation, though parallel tests may interfere with env vars
+        unsafe { std::env::set_var("OLLAMA_MODEL", "test-model") };
+
+        // This should complete successfully without calling LLM
+        // (Returns early with message about no tags)
+        let result = execute_hierarchy_suggest(None, None, db);
+
+        // Should succeed (doesn't make LLM call for empty tag set)
+        if let Err(e) = &result {
+            eprintln!("Test failed with error: {:#}", e);
+        }
+        assert!(result.is_ok(), "Expected Ok but got: {:?}", result);
+    }
+
+    #[test]
+    fn execute_hierarchy_suggest_fail_safe_on_llm_error() {
+        // This test verifies that LLM errors don't crash the command
+        // We can't easily test this without mocking, but we verify the structure exists
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with tags
+        service
+            .create_note("Test", Some(&["tag1", "tag2"]))
+            .expect("failed to create note");
+
+        // The execute_hierarchy_suggest function should handle LLM errors gracefully
+        // (either by catching them or by having them not propagate to exit code)
+        // This is verified by the implementation pattern we'll use
+    }
+
+    // --- Graph Search CLI Command Tests (Task Group 3) ---
+
+    #[test]
+    fn graph_search_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing with positional query and --limit flag
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "graph-search", "machine learning", "-l", "5"])
+            .expect("failed to parse graph-search command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("graph-search").is_some());
+    }
+
+    #[test]
+    fn execute_graph_search_with_in_memory_database_returns_results() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with tags that will be used for graph search
+        service
+            .create_note(
+                "Learning machine learning basics",
+                Some(&["machine-learning"]),
+            )
+            .expect("failed to create note");
+        service
+            .create_note("Neural networks tutorial", Some(&["neural-network"]))
+            .expect("failed to create note");
+
+        // Execute graph search
+        let result = execute_graph_search("machine learning", Some(10), false, service);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn execute_graph_search_with_empty_database_shows_no_notes_found() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Execute graph search in empty database
+        let result = execute_graph_search("machine learning", Some(10), false, service);
+        assert!(result.is_ok());
+        // Should complete successfully and print "No notes found via graph search"
+    }
+
+    #[test]
+    fn execute_graph_search_limit_flag_restricts_result_count() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create multiple notes
+        for i in 1..=5 {
+            service
+                .create_note(&format!("Test note {}", i), Some(&["test"]))
+                .expect("failed to create note");
+        }
+
+        // Execute with limit of 3
+        let result = execute_graph_search("test", Some(3), false, service);
+        assert!(result.is_ok());
+        // The limit is applied at the service layer, verified by service tests
+    }
+
+    #[test]
+    fn execute_graph_search_with_explain_succeeds() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let tokio = service
+            .get_or_create_tag("tokio")
+            .expect("failed to create tag");
+        let rust = service
+            .get_or_create_tag("rust")
+            .expect("failed to create tag");
+        service
+            .create_edge(tokio, rust, 1.0, "generic", None)
+            .expect("failed to create edge");
+        service
+            .create_note("Ownership rules", Some(&["rust"]))
+            .expect("failed to create note");
+
+        let cli = Cli::try_parse_from(vec!["cons", "graph-search", "tokio", "--explain"])
+            .expect("failed to parse --explain");
+        match cli.command {
+            Commands::GraphSearch(cmd) => assert!(cmd.explain),
+            _ => panic!("expected graph-search command"),
+        }
+
+        let result = execute_graph_search("tokio", Some(10), true, service);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn format_activation_paths_indents_each_path() {
+        use cons::spreading_activation::{ActivationPath, ActivationStep};
+
+        let step = |id, name: &str, activation| ActivationStep {
+            tag_id: TagId::new(id),
+            tag_name: name.to_string(),
+            activation,
+        };
+        let paths = vec![ActivationPath {
+            steps: vec![step(1, "tokio", 1.0), step(2, "rust", 0.7)],
+        }];
+
+        assert_eq!(
+            format_activation_paths(&paths),
+            "Why:\n  tokio (1.00) -> rust (0.70)\n"
+        );
+    }
+
+    // --- Tags List CLI Command Tests (Task Group 4) ---
+
+    #[test]
+    fn tags_list_command_struct_parsing_with_clap() {
+        use clap::CommandFactory;
+
+        // Test parsing of `cons tags list`
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tags", "list"])
+            .expect("failed to parse tags list command");
+
+        // Verify command is recognized
+        assert!(matches.subcommand_matches("tags").is_some());
+    }
+
+    #[test]
+    fn execute_tags_list_shows_degree_centrality() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with tags
+        service
+            .create_note("Rust note", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Programming note", Some(&["programming"]))
+            .expect("failed to create note");
+
+        // Get tags with stats to verify degree centrality is included
+        let tags = service
+            .get_tags_with_stats()
+            .expect("failed to get tags with stats");
+
+        // Verify we have 2 tags with degree centrality
+        assert_eq!(tags.len(), 2);
+
+        // Verify all tags have degree_centrality (should be 0 since no edges created yet)
+        for (_, _, _, degree_centrality) in &tags {
+            assert_eq!(*degree_centrality, 0);
+        }
+
+        // Test the execute_tags_list function with a new database
+        let db2 = Database::in_memory().expect("failed to create in-memory database");
+        let service2 = NoteService::new(db2);
+        service2
+            .create_note("Test note", Some(&["test"]))
+            .expect("failed to create note");
+
+        let tags2 = service2
+            .get_tags_with_stats()
+            .expect("failed to get tags with stats");
+        assert_eq!(tags2.len(), 1);
+        let (_, name, note_count, degree_centrality) = &tags2[0];
+        assert_eq!(name, "test");
+        assert_eq!(*note_count, 1);
+        assert_eq!(*degree_centrality, 0);
+    }
+
+    #[test]
+    fn execute_tags_list_with_empty_database_shows_no_tags_found() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+
+        // Execute tags list in empty database
+        let result = execute_tags_list(false, db);
+        assert!(result.is_ok());
+        // Should complete successfully and print "No tags found"
+    }
+
+    #[test]
+    fn execute_tags_list_output_format_includes_note_count_and_connections() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+
+        // Create notes with tags
+        service
+            .create_note("First rust note", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Second rust note", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Python note", Some(&["python"]))
+            .expect("failed to create note");
+
+        // Get tags with stats to verify the service method works correctly
+        let tags = service
+            .get_tags_with_stats()
+            .expect("failed to get tags with stats");
+
+        // Verify we have 2 tags
+        assert_eq!(tags.len(), 2);
+
+        // Find the rust tag
+        let rust_tag = tags.iter().find(|(_, name, _, _)| name == "rust");
+        assert!(rust_tag.is_some());
+        let (_, name, note_count, degree_centrality) = rust_tag.unwrap();
+        assert_eq!(name, "rust");
+        assert_eq!(*note_count, 2);
+        assert_eq!(*degree_centrality, 0); // No edges created yet
+
+        // Find the python tag
+        let python_tag = tags.iter().find(|(_, name, _, _)| name == "python");
+        assert!(python_tag.is_some());
+        let (_, name, note_count, degree_centrality) = python_tag.unwrap();
+        assert_eq!(name, "python");
+        assert_eq!(*note_count, 1);
+        assert_eq!(*degree_centrality, 0); // No edges created yet
+    }
+
+    // --- JSON Output Tests ---
+
+    #[test]
+    fn json_flag_is_global() {
+        use clap::CommandFactory;
+
+        for args in [
+            vec!["cons", "--json", "list"],
+            vec!["cons", "list", "--json"],
+            vec!["cons", "search", "rust", "--json"],
+            vec!["cons", "tags", "--json"],
+        ] {
+            let result = Cli::command().try_get_matches_from(args.clone());
+            assert!(result.is_ok(), "failed to parse {args:?}");
+        }
+    }
+
+    #[test]
+    fn json_note_serializes_expected_fields() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        let note = service
+            .create_note("Learning Rust", Some(&["rust"]))
+            .expect("failed to create note");
+
+        let value = serde_json::to_value(
+            JsonNote::new(&note, None, &service).expect("failed to build json note"),
+        )
+        .expect("failed to serialize");
+        assert_eq!(value["id"], note.id().get());
+        assert_eq!(value["content"], "Learning Rust");
+        assert!(value["content_enhanced"].is_null());
+        assert!(value["enhancement_confidence"].is_null());
+        assert_eq!(value["tags"], serde_json::json!(["rust"]));
         assert!(
-            formatted.contains("This is a quick thought about something important."),
-            "formatted output should contain enhanced content"
+            value.get("relevance_score").is_none(),
+            "relevance_score should be omitted outside search"
         );
 
-        // Verify formatted output contains confidence
+        // created_at must round-trip as RFC3339
+        let created_at = value["created_at"].as_str().expect("created_at string");
+        time::OffsetDateTime::parse(created_at, &time::format_description::well_known::Rfc3339)
+            .expect("created_at should be RFC3339");
+
+        let value = serde_json::to_value(
+            JsonNote::new(&note, Some(0.75), &service).expect("failed to build json note"),
+        )
+        .expect("failed to serialize");
+        assert_eq!(value["relevance_score"], 0.75);
+    }
+
+    #[test]
+    fn execute_list_and_search_json_with_empty_database_succeed() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        assert!(execute_list(&ListCommand::default(), true, NoteService::new(db)).is_ok());
+
+        let db = Database::in_memory().expect("failed to create in-memory database");
         assert!(
-            formatted.contains("85% confidence"),
-            "formatted output should show confidence percentage"
+            execute_search(
+                &SearchCommand {
+                    query: "rust".to_string(),
+                    limit: Some(10),
+                    ..Default::default()
+                },
+                true,
+                NoteService::new(db)
+            )
+            .is_ok()
+        );
+
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        assert!(execute_tags(TagSort::Count, None, false, true, db).is_ok());
+    }
+
+    // --- Date Range Filter CLI Tests ---
+
+    #[test]
+    fn list_and_search_commands_parse_since_and_until() {
+        use clap::CommandFactory;
+
+        let result = Cli::command().try_get_matches_from(vec![
+            "cons",
+            "list",
+            "--since",
+            "2024-01-01",
+            "--until",
+            "2024-12-31",
+        ]);
+        assert!(result.is_ok());
+
+        let result = Cli::command().try_get_matches_from(vec![
+            "cons",
+            "search",
+            "rust",
+            "--since",
+            "2024-01-01T00:00:00Z",
+        ]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn parse_date_bound_accepts_dates_and_rfc3339() {
+        // 2024-01-01T00:00:00Z
+        assert_eq!(
+            parse_date_bound("2024-01-01", false).unwrap(),
+            1_704_067_200
+        );
+        // End-of-day bound is inclusive of the whole day
+        assert_eq!(
+            parse_date_bound("2024-01-01", true).unwrap(),
+            1_704_067_200 + 86_399
+        );
+        assert_eq!(
+            parse_date_bound("2024-01-01T12:00:00+02:00", false).unwrap(),
+            1_704_067_200 + 10 * 3600
         );
     }
 
     #[test]
-    fn format_note_content_shows_stacked_format_with_separator() {
-        // Test the stacked display format helper function
-        use cons::NoteBuilder;
+    fn parse_date_bound_rejects_invalid_values_as_user_error() {
+        let err = parse_date_bound("last tuesday", false).unwrap_err();
+        assert!(err.to_string().contains("'last tuesday'"));
+        assert!(is_user_error(&err), "invalid dates should exit with code 1");
+    }
+
+    #[test]
+    fn execute_list_with_invalid_since_returns_error() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let cmd = ListCommand {
+            since: Some("2024-13-01".to_string()),
+            ..Default::default()
+        };
+        let result = execute_list(&cmd, false, NoteService::new(db));
+        assert!(result.is_err());
+        assert!(is_user_error(&result.unwrap_err()));
+    }
+
+    #[test]
+    fn list_command_parses_before_cursor() {
+        let cli = Cli::try_parse_from(vec!["cons", "list", "--before", "42", "-l", "5"])
+            .expect("failed to parse list with --before");
+        match cli.command {
+            Commands::List(cmd) => {
+                assert_eq!(cmd.before, Some(42));
+                assert_eq!(cmd.limit, Some(5));
+            }
+            _ => panic!("expected list command"),
+        }
+    }
+
+    #[test]
+    fn list_command_parses_excerpt_flags() {
+        let cli = Cli::try_parse_from(vec!["cons", "list", "--truncate", "80"])
+            .expect("failed to parse list with --truncate");
+        match cli.command {
+            Commands::List(cmd) => {
+                assert_eq!(cmd.truncate, Some(80));
+                assert!(!cmd.full);
+            }
+            _ => panic!("expected list command"),
+        }
 
-        let now = time::OffsetDateTime::now_utc();
+        let result = Cli::try_parse_from(vec!["cons", "list", "--full", "--truncate", "80"]);
+        assert!(result.is_err(), "--full and --truncate should conflict");
 
-        // Test note WITH enhancement
-        let enhanced_note = NoteBuilder::new()
+        let result = Cli::try_parse_from(vec!["cons", "list", "--truncate", "0"]);
+        assert!(result.is_err(), "--truncate must be at least 1");
+    }
+
+    #[test]
+    fn format_note_content_shortens_both_contents_to_excerpt() {
+        let note = cons::NoteBuilder::new()
             .id(NoteId::new(1))
-            .content("buy milk")
-            .created_at(now)
-            .updated_at(now)
-            .content_enhanced("Buy milk from the grocery store.")
-            .enhancement_confidence(0.75)
+            .content("the quick brown fox")
+            .content_enhanced("The quick brown fox jumps over the lazy dog.")
             .build();
 
-        let formatted = format_note_content(&enhanced_note);
+        let formatted = format_note_content(&note, Some(10));
+        assert!(formatted.contains("Content: the quick...\n"));
+        assert!(formatted.contains("Enhanced: The quick...\n"));
 
-        assert!(
-            formatted.contains("Content: buy milk"),
-            "should show original content first"
-        );
-        assert!(formatted.contains("---"), "should have separator");
-        assert!(
-            formatted.contains("Buy milk from the grocery store."),
-            "should show enhanced content"
-        );
-        assert!(
-            formatted.contains("75% confidence"),
-            "should show confidence percentage"
-        );
+        let full = format_note_content(&note, None);
+        assert!(full.contains("Content: the quick brown fox\n"));
+        assert!(full.contains("jumps over the lazy dog."));
+    }
 
-        // Test note WITHOUT enhancement
-        let plain_note = NoteBuilder::new()
-            .id(NoteId::new(2))
-            .content("already complete thought")
-            .created_at(now)
-            .updated_at(now)
-            .build();
+    #[test]
+    fn list_command_parses_order_by_field() {
+        let cli = Cli::try_parse_from(vec!["cons", "list", "--by", "updated"])
+            .expect("failed to parse list with --by");
+        match cli.command {
+            Commands::List(cmd) => assert_eq!(cmd.by, OrderByArg::Updated),
+            _ => panic!("expected list command"),
+        }
 
-        let formatted_plain = format_note_content(&plain_note);
+        let cli = Cli::try_parse_from(vec!["cons", "list"]).expect("failed to parse list");
+        match cli.command {
+            Commands::List(cmd) => assert_eq!(OrderBy::from(cmd.by), OrderBy::CreatedAt),
+            _ => panic!("expected list command"),
+        }
 
-        assert!(
-            formatted_plain.contains("Content: already complete thought"),
-            "should show original content"
-        );
-        assert!(
-            !formatted_plain.contains("---"),
-            "should NOT have separator when no enhancement"
-        );
+        assert!(Cli::try_parse_from(vec!["cons", "list", "--by", "title"]).is_err());
     }
 
     #[test]
-    fn confidence_percentage_display_format() {
-        // Test that confidence is displayed as integer percentage
-        use cons::NoteBuilder;
-
-        let now = time::OffsetDateTime::now_utc();
+    fn list_command_tag_like_conflicts_with_tags() {
+        use clap::CommandFactory;
 
-        let test_cases = vec![
-            (0.0, "0% confidence"),
-            (0.5, "50% confidence"),
-            (0.85, "85% confidence"),
-            (1.0, "100% confidence"),
-            (0.955, "96% confidence"), // Test rounding
-        ];
+        let result =
+            Cli::command().try_get_matches_from(vec!["cons", "list", "--tag-like", "learn"]);
+        assert!(result.is_ok());
+        let result = Cli::command().try_get_matches_from(vec![
+            "cons",
+            "list",
+            "--tags",
+            "rust",
+            "--tag-like",
+            "learn",
+        ]);
+        assert!(
+            result.is_err(),
+            "--tags and --tag-like are mutually exclusive"
+        );
+    }
 
-        for (confidence_f64, expected_str) in test_cases {
-            let note = NoteBuilder::new()
-                .id(NoteId::new(1))
-                .content("test")
-                .created_at(now)
-                .updated_at(now)
-                .content_enhanced("enhanced test")
-                .enhancement_confidence(confidence_f64)
-                .build();
+    #[test]
+    fn execute_list_with_tag_like_finds_partial_matches() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        service
+            .create_note("gradient descent", Some(&["machine-learning"]))
+            .expect("failed to create note");
 
-            let formatted = format_note_content(&note);
+        let cmd = ListCommand {
+            tag_like: Some("learn".to_string()),
+            ..Default::default()
+        };
+        assert!(execute_list(&cmd, false, service).is_ok());
 
-            assert!(
-                formatted.contains(expected_str),
-                "confidence {} should display as '{}', got: {}",
-                confidence_f64,
-                expected_str,
-                formatted
-            );
-        }
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let cmd = ListCommand {
+            tag_like: Some("  ".to_string()),
+            ..Default::default()
+        };
+        let result = execute_list(&cmd, false, NoteService::new(db));
+        assert!(is_user_error(&result.unwrap_err()));
     }
 
-    // --- Search Command Tests (Task Group 3) ---
+    // --- Tag Source Filter CLI Tests ---
 
     #[test]
-    fn search_command_struct_parsing_with_clap() {
+    fn list_and_search_commands_parse_source() {
         use clap::CommandFactory;
 
-        // Test parsing with positional query and --limit flag
         let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "search", "rust programming", "-l", "5"])
-            .expect("failed to parse search command");
+            .try_get_matches_from(vec!["cons", "list", "--source", "llm"])
+            .expect("list --source llm should parse");
+        let (_, list_matches) = matches.subcommand().unwrap();
+        assert_eq!(
+            list_matches.get_one::<SourceArg>("source").copied(),
+            Some(SourceArg::Llm)
+        );
 
-        // Verify command is recognized
-        assert!(matches.subcommand_matches("search").is_some());
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "search", "rust"])
+            .expect("search without --source should parse");
+        let (_, search_matches) = matches.subcommand().unwrap();
+        assert_eq!(
+            search_matches.get_one::<SourceArg>("source").copied(),
+            Some(SourceArg::All)
+        );
+
+        let result = Cli::command().try_get_matches_from(vec!["cons", "list", "--source", "robot"]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn execute_search_with_in_memory_database_returns_results() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
+    fn execute_list_with_source_filter_succeeds() {
+        let db = Database::in_memory().expect("Failed to create in-memory database");
         let service = NoteService::new(db);
-
-        // Create notes with searchable content
         service
-            .create_note("Learning Rust programming", Some(&["rust"]))
-            .expect("failed to create note");
+            .create_note("user tagged", Some(&["rust"]))
+            .expect("Failed to create note");
+        let cmd = ListCommand {
+            source: SourceArg::Llm,
+            ..Default::default()
+        };
+        assert!(execute_list(&cmd, false, service).is_ok());
+    }
+
+    // --- Graph Mode Search CLI Tests ---
+
+    #[test]
+    fn execute_search_graph_mode_succeeds() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
         service
-            .create_note("Python programming tutorial", Some(&["python"]))
+            .create_note("ownership and borrowing", Some(&["rust"]))
             .expect("failed to create note");
 
-        // Search for Rust-related notes
-        let result = execute_search("rust", Some(10), service);
+        let result = execute_search(
+            &SearchCommand {
+                query: "rust".to_string(),
+                graph: true,
+                ..Default::default()
+            },
+            true,
+            service,
+        );
         assert!(result.is_ok());
     }
 
     #[test]
-    fn execute_search_with_empty_database_shows_no_notes_found() {
+    fn execute_search_graph_mode_rejects_empty_query() {
         let db = Database::in_memory().expect("failed to create in-memory database");
         let service = NoteService::new(db);
 
-        // Search in empty database
-        let result = execute_search("rust", Some(10), service);
-        assert!(result.is_ok());
-        // The function should complete successfully and print "No notes found matching query"
+        let err = execute_search(
+            &SearchCommand {
+                query: "  ".to_string(),
+                graph: true,
+                ..Default::default()
+            },
+            false,
+            service,
+        )
+        .expect_err("empty query should fail");
+        assert!(err.to_string().contains("cannot be empty"));
     }
 
-    // --- Dual-Channel Search CLI Tests (Task Group 3) ---
+    #[test]
+    fn search_graph_flag_parses() {
+        let cli = Cli::try_parse_from(["cons", "search", "rust", "--graph"]).expect("should parse");
+        match cli.command {
+            Commands::Search(cmd) => assert!(cmd.graph),
+            _ => panic!("expected search command"),
+        }
+    }
 
     #[test]
-    fn dual_search_command_parses_correctly() {
-        use clap::CommandFactory;
+    fn search_tags_flag_parses() {
+        let cli = Cli::try_parse_from(["cons", "search", "async", "--tags", "rust,tokio"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Search(cmd) => assert_eq!(cmd.tags.as_deref(), Some("rust,tokio")),
+            _ => panic!("expected search command"),
+        }
+    }
 
-        // Test that `cons search` command parses correctly with dual-channel search
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "search", "machine learning", "-l", "10"])
-            .expect("failed to parse search command");
+    #[test]
+    fn execute_search_with_tags_succeeds_in_both_modes() {
+        for graph in [false, true] {
+            let db = Database::in_memory().expect("failed to create in-memory database");
+            let service = NoteService::new(db);
+            service
+                .create_note("async runtimes", Some(&["rust"]))
+                .expect("failed to create note");
 
-        // Verify command is recognized
-        assert!(matches.subcommand_matches("search").is_some());
+            let result = execute_search(
+                &SearchCommand {
+                    query: "async".to_string(),
+                    tags: Some("Rust".to_string()),
+                    graph,
+                    ..Default::default()
+                },
+                false,
+                service,
+            );
+            assert!(result.is_ok());
+        }
+    }
 
-        // Verify the search subcommand has query and limit
-        let search_matches = matches.subcommand_matches("search").unwrap();
-        assert!(search_matches.contains_id("query"));
-        assert!(search_matches.contains_id("limit"));
+    // --- Pagination CLI Tests ---
+
+    #[test]
+    fn resolve_offset_converts_page_to_offset() {
+        assert_eq!(resolve_offset(None, None, 10).unwrap(), None);
+        assert_eq!(resolve_offset(Some(7), None, 10).unwrap(), Some(7));
+        assert_eq!(resolve_offset(None, Some(1), 10).unwrap(), Some(0));
+        assert_eq!(resolve_offset(None, Some(3), 10).unwrap(), Some(20));
+        assert_eq!(resolve_offset(Some(7), None, 0).unwrap(), Some(7));
     }
 
     #[test]
-    fn execute_search_calls_dual_search_and_formats_output() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn resolve_offset_rejects_pages_past_the_offset_range() {
+        let err = resolve_offset(None, Some(u64::MAX), 10).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+        assert!(is_user_error(&err));
 
-        // Create notes with tags to test dual-channel search
-        service
-            .create_note("Learning Rust programming", Some(&["rust"]))
-            .expect("failed to create note");
+        assert!(Cli::try_parse_from(["cons", "list", "--page", "18446744073709551615"]).is_ok());
+    }
+
+    #[test]
+    fn resolve_offset_rejects_page_without_a_limit() {
+        let err = resolve_offset(None, Some(2), 0).unwrap_err();
+        assert!(err.to_string().contains("--limit 0"));
+        assert!(is_user_error(&err));
+
+        let cmd = ListCommand {
+            limit: Some(0),
+            page: Some(2),
+            ..Default::default()
+        };
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        assert!(fetch_list_notes(&cmd, &service).is_err());
+    }
+
+    #[test]
+    fn fetch_list_notes_untagged_composes_with_limit() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let stray: Vec<NoteId> = (1..=3)
+            .map(|i| {
+                service
+                    .create_note(&format!("Stray {i}"), None)
+                    .unwrap()
+                    .id()
+            })
+            .collect();
         service
-            .create_note("Advanced Rust patterns", Some(&["rust", "advanced"]))
+            .create_note("Tagged", Some(&["rust"]))
             .expect("failed to create note");
 
-        // Execute search which should call dual_search internally
-        let result = execute_search("rust", Some(10), service);
+        let cmd = ListCommand {
+            untagged: true,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let (notes, total) = fetch_list_notes(&cmd, &service).expect("list should succeed");
+        assert_eq!(total, 3);
+        assert_eq!(
+            notes.iter().map(|n| n.id()).collect::<Vec<_>>(),
+            stray[1..].to_vec()
+        );
 
-        // Verify the search completes successfully
-        assert!(result.is_ok());
-        // The function should format and display results from dual_search
+        let cli = Cli::try_parse_from(["cons", "list", "--untagged"]).expect("should parse");
+        assert!(matches!(
+            cli.command,
+            Commands::List(ListCommand { untagged: true, .. })
+        ));
+        assert!(Cli::try_parse_from(["cons", "list", "--untagged", "--tags", "rust"]).is_err());
     }
 
     #[test]
-    fn execute_search_displays_graph_skipped_notice() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn fetch_list_notes_with_all_returns_every_note_chronologically() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let ids: Vec<NoteId> = (1..=30)
+            .map(|i| {
+                service
+                    .create_note(&format!("Note {i}"), None)
+                    .unwrap()
+                    .id()
+            })
+            .collect();
 
-        // Create notes without tags or edges (cold-start scenario)
-        service
-            .create_note("Simple note without tags", None)
-            .expect("failed to create note");
+        let all = ListCommand {
+            all: true,
+            ..Default::default()
+        };
+        let (notes, total) = fetch_list_notes(&all, &service).expect("list should succeed");
+        assert_eq!(total, 30);
+        assert_eq!(notes.iter().map(|n| n.id()).collect::<Vec<_>>(), ids);
 
-        // Execute search - should trigger graph skip due to sparse activation
-        let result = execute_search("simple", Some(10), service);
+        let zero = ListCommand {
+            limit: Some(0),
+            ..Default::default()
+        };
+        let (notes, _) = fetch_list_notes(&zero, &service).expect("list should succeed");
+        assert_eq!(notes.len(), 30);
 
-        // Verify the search completes successfully
-        assert!(result.is_ok());
-        // The function should print "Note: Graph search skipped (sparse activation)"
-        // when metadata.graph_skipped is true
+        let (notes, _) =
+            fetch_list_notes(&ListCommand::default(), &service).expect("list should succeed");
+        assert_eq!(notes.len(), 10, "default cap still applies");
+        assert_eq!(notes.last().unwrap().id(), ids[29]);
     }
 
     #[test]
-    fn execute_search_with_empty_query_returns_error() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn list_all_conflicts_with_limit_and_page() {
+        assert!(Cli::try_parse_from(["cons", "list", "--all"]).is_ok());
+        assert!(Cli::try_parse_from(["cons", "list", "--all", "--limit", "5"]).is_err());
+        assert!(Cli::try_parse_from(["cons", "list", "--all", "--page", "2"]).is_err());
+    }
 
-        // Test empty string
-        let result = execute_search("", Some(10), service);
+    #[test]
+    fn list_and_search_commands_parse_offset_and_page() {
+        use clap::CommandFactory;
+
+        let result = Cli::command().try_get_matches_from(vec!["cons", "list", "--offset", "20"]);
+        assert!(result.is_ok());
+
+        let result =
+            Cli::command().try_get_matches_from(vec!["cons", "search", "rust", "--page", "2"]);
+        assert!(result.is_ok());
+
+        // --page starts at 1 and cannot be combined with --offset
+        let result = Cli::command().try_get_matches_from(vec!["cons", "list", "--page", "0"]);
+        assert!(result.is_err());
+        let result = Cli::command()
+            .try_get_matches_from(vec!["cons", "list", "--page", "2", "--offset", "5"]);
         assert!(result.is_err());
-        let error = result.unwrap_err();
-        let error_msg = format!("{:#}", error); // Use alternate format to show chain
-        eprintln!("Actual error message: '{}'", error_msg);
-        assert!(
-            error_msg.contains("Search query cannot be empty")
-                || error_msg.contains("cannot be empty"),
-            "Error message '{}' should contain 'cannot be empty'",
-            error_msg
-        );
     }
 
+    // --- Related Command Tests ---
+
     #[test]
-    fn execute_search_with_whitespace_only_query_returns_error() {
+    fn execute_related_fails_for_non_existent_note() {
         let db = Database::in_memory().expect("failed to create in-memory database");
         let service = NoteService::new(db);
 
-        // Test whitespace-only query
-        let result = execute_search("   \n\t  ", Some(10), service);
-        assert!(result.is_err());
-        let error = result.unwrap_err();
-        let error_msg = format!("{:#}", error); // Use alternate format to show chain
-        eprintln!("Actual error message: '{}'", error_msg);
-        assert!(
-            error_msg.contains("Search query cannot be empty")
-                || error_msg.contains("cannot be empty"),
-            "Error message '{}' should contain 'cannot be empty'",
-            error_msg
-        );
+        let err = execute_related(NoteId::new(999), None, service)
+            .expect_err("missing seed note should fail");
+        assert!(err.to_string().contains("does not exist"));
     }
 
-    // --- Hierarchy CLI Command Tests (Task Group 3) ---
-
     #[test]
-    fn hierarchy_command_struct_parsing_with_clap() {
-        use clap::CommandFactory;
+    fn execute_related_excludes_seed_note() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
 
-        // Test parsing of `cons hierarchy suggest`
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "hierarchy", "suggest"])
-            .expect("failed to parse hierarchy suggest command");
+        let seed = service
+            .create_note("tokio select", Some(&["rust", "async"]))
+            .expect("failed to create note");
+        service
+            .create_note("ownership rules", Some(&["rust"]))
+            .expect("failed to create note");
 
-        // Verify command is recognized
-        assert!(matches.subcommand_matches("hierarchy").is_some());
+        let results = service
+            .graph_search_from_note(seed.id(), None)
+            .expect("graph search should succeed");
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.note.id() != seed.id()));
+
+        assert!(execute_related(seed.id(), Some(5), service).is_ok());
     }
 
     #[test]
-    fn execute_hierarchy_suggest_with_in_memory_database() {
-        // Create database and populate it with notes+tags
+    fn execute_related_for_untagged_note_succeeds() {
         let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
 
-        // Populate database in a scope, then pass it to execute function
-        {
-            let service = NoteService::new(Database::in_memory().expect("db"));
-            service
-                .create_note("Test note", Some(&["transformer", "neural-network"]))
-                .expect("failed to create note");
-            // service drops here, db is not consumed
+        // A note without tags has no graph neighbours, which is not an error
+        let note = service
+            .create_note("loose thought", None)
+            .expect("failed to create note");
+        assert!(execute_related(note.id(), None, service).is_ok());
+    }
+
+    // --- Ask Command Tests ---
+
+    #[test]
+    fn ask_accepts_limit_as_alias_for_top_k() {
+        let cli = Cli::try_parse_from(["cons", "ask", "what is tokio", "--limit", "3"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Ask(cmd) => {
+                assert_eq!(cmd.query, "what is tokio");
+                assert_eq!(cmd.top_k, 3);
+            }
+            _ => panic!("expected ask command"),
         }
+    }
 
-        // Now test execute_hierarchy_suggest with the database
-        // (will return early with "No tags found" since we used a different db above)
-        let result = execute_hierarchy_suggest(db);
+    #[test]
+    fn execute_ask_with_no_matching_notes_succeeds_without_ollama() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
 
-        // Function should complete (either success or graceful error handling)
-        // We don't assert Ok because OLLAMA_MODEL might not be set in test environment
-        // The function is designed to handle missing OLLAMA_MODEL gracefully
-        drop(result);
+        let result = execute_ask("what is tokio?", 5, false, None, service);
+        assert!(result.is_ok(), "no context means no LLM call");
     }
 
     #[test]
     #[serial]
-    fn execute_hierarchy_suggest_handles_ollama_not_reachable() {
-        // Save current env vars
+    fn execute_ask_reports_unreachable_ollama_when_model_unset() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        service
+            .create_note("tokio is an async runtime", Some(&["rust"]))
+            .expect("failed to create note");
+
         let old_host = std::env::var("OLLAMA_HOST").ok();
         let old_model = std::env::var("OLLAMA_MODEL").ok();
-
-        // Point to a non-existent Ollama instance and clear OLLAMA_MODEL
+        // SAFETY: This test runs serially
         unsafe {
-            std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:99999");
+            std::env::set_var("OLLAMA_HOST", "http://127.0.0.1:9");
             std::env::remove_var("OLLAMA_MODEL");
         };
 
-        let db = Database::in_memory().expect("failed to create in-memory database");
-
-        // Insert a note and tag directly so execute_hierarchy_suggest doesn't return early
-        db.connection()
-            .execute("INSERT INTO notes (id, content) VALUES (1, 'Test note')", [])
-            .expect("failed to insert note");
-        db.connection()
-            .execute("INSERT INTO tags (id, name) VALUES (1, 'test-tag')", [])
-            .expect("failed to insert tag");
-        db.connection()
-            .execute("INSERT INTO note_tags (note_id, tag_id) VALUES (1, 1)", [])
-            .expect("failed to insert note_tag");
-
-        // This should fail because Ollama is not reachable for auto-detection
-        let result = execute_hierarchy_suggest(db);
+        let result = execute_ask("what is tokio?", 5, false, None, service);
 
-        // Restore env vars
         unsafe {
             match old_host {
                 Some(v) => std::env::set_var("OLLAMA_HOST", v),
@@ -2632,228 +8562,213 @@ mod tests {
             }
         };
 
-        assert!(
-            result.is_err(),
-            "should return error when Ollama not reachable"
-        );
-
-        let error_msg = result.unwrap_err().to_string();
-        assert!(
-            error_msg.contains("Ollama") || error_msg.contains("ollama"),
-            "error should mention Ollama: {error_msg}"
-        );
+        let err = result.expect_err("ask should fail without a reachable model");
+        assert!(format!("{err:#}").contains("Ollama not reachable"));
     }
 
-    #[test]
-    #[serial]
-    fn execute_hierarchy_suggest_handles_empty_tag_set() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-
-        // No notes created, so no tags exist
-
-        // Set OLLAMA_MODEL immediately before the call to minimize race conditions with other tests
-        // SAFETY: Test runs in isolation, though parallel tests may interfere with env vars
-        unsafe { std::env::set_var("OLLAMA_MODEL", "test-model") };
+    // --- Model Override Tests ---
 
-        // This should complete successfully without calling LLM
-        // (Returns early with message about no tags)
-        let result = execute_hierarchy_suggest(db);
+    #[test]
+    fn hierarchy_suggest_min_confidence_is_validated() {
+        let cli = Cli::try_parse_from(["cons", "hierarchy", "suggest", "--min-confidence", "0.5"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Hierarchy(HierarchyCommand {
+                command: HierarchyCommands::Suggest { min_confidence, .. },
+            }) => assert_eq!(min_confidence, Some(0.5)),
+            _ => panic!("expected hierarchy suggest command"),
+        }
 
-        // Should succeed (doesn't make LLM call for empty tag set)
-        if let Err(e) = &result {
-            eprintln!("Test failed with error: {:#}", e);
+        for value in ["1.5", "-0.1", "high"] {
+            assert!(
+                Cli::try_parse_from(["cons", "hierarchy", "suggest", "--min-confidence", value])
+                    .is_err(),
+                "{value} should be rejected"
+            );
         }
-        assert!(result.is_ok(), "Expected Ok but got: {:?}", result);
     }
 
     #[test]
-    fn execute_hierarchy_suggest_fail_safe_on_llm_error() {
-        // This test verifies that LLM errors don't crash the command
-        // We can't easily test this without mocking, but we verify the structure exists
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+    fn model_flag_parses_on_llm_backed_commands() {
+        let cli = Cli::try_parse_from(["cons", "add", "note", "--model", "llama3:8b"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Add(cmd) => assert_eq!(cmd.model.as_deref(), Some("llama3:8b")),
+            _ => panic!("expected add command"),
+        }
 
-        // Create notes with tags
-        service
-            .create_note("Test", Some(&["tag1", "tag2"]))
-            .expect("failed to create note");
+        let cli = Cli::try_parse_from(["cons", "hierarchy", "suggest", "--model", "qwen3"])
+            .expect("should parse");
+        match cli.command {
+            Commands::Hierarchy(HierarchyCommand {
+                command: HierarchyCommands::Suggest { model, .. },
+            }) => assert_eq!(model.as_deref(), Some("qwen3")),
+            _ => panic!("expected hierarchy suggest command"),
+        }
 
-        // The execute_hierarchy_suggest function should handle LLM errors gracefully
-        // (either by catching them or by having them not propagate to exit code)
-        // This is verified by the implementation pattern we'll use
+        for args in [
+            vec!["cons", "retag", "1", "--model", "m"],
+            vec!["cons", "reenhance", "--all-unenhanced", "--model", "m"],
+            vec!["cons", "ask", "what is tokio", "--model", "m"],
+            vec!["cons", "tags", "suggest", "text", "--model", "m"],
+        ] {
+            assert!(Cli::try_parse_from(&args).is_ok(), "{args:?} should parse");
+        }
     }
 
-    // --- Graph Search CLI Command Tests (Task Group 3) ---
-
     #[test]
-    fn graph_search_command_struct_parsing_with_clap() {
-        use clap::CommandFactory;
+    #[serial]
+    fn resolve_model_prefers_override_over_env() {
+        let client = OllamaClientBuilder::new()
+            .base_url("http://127.0.0.1:9")
+            .build()
+            .expect("failed to build client");
 
-        // Test parsing with positional query and --limit flag
-        let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "graph-search", "machine learning", "-l", "5"])
-            .expect("failed to parse graph-search command");
+        let old_model = std::env::var("OLLAMA_MODEL").ok();
+        // SAFETY: This test runs serially
+        unsafe { std::env::set_var("OLLAMA_MODEL", "env-model") };
 
-        // Verify command is recognized
-        assert!(matches.subcommand_matches("graph-search").is_some());
+        let overridden = resolve_model(&client, Some("flag-model"));
+        let blank = resolve_model(&client, Some("  "));
+        let unset = resolve_model(&client, None);
+
+        unsafe {
+            match old_model {
+                Some(v) => std::env::set_var("OLLAMA_MODEL", v),
+                None => std::env::remove_var("OLLAMA_MODEL"),
+            }
+        };
+
+        assert_eq!(overridden.unwrap(), "flag-model");
+        assert_eq!(blank.unwrap(), "env-model", "blank override falls back");
+        assert_eq!(unset.unwrap(), "env-model");
     }
 
+    // --- Tag Suggest CLI Tests ---
+
     #[test]
-    fn execute_graph_search_with_in_memory_database_returns_results() {
+    fn format_tag_suggestions_sorts_and_reports_alias_opportunities() {
         let db = Database::in_memory().expect("failed to create in-memory database");
         let service = NoteService::new(db);
-
-        // Create notes with tags that will be used for graph search
-        service
-            .create_note(
-                "Learning machine learning basics",
-                Some(&["machine-learning"]),
-            )
-            .expect("failed to create note");
         service
-            .create_note("Neural networks tutorial", Some(&["neural-network"]))
-            .expect("failed to create note");
+            .get_or_create_tag("machine-learning")
+            .expect("failed to create canonical tag");
 
-        // Execute graph search
-        let result = execute_graph_search("machine learning", Some(10), service);
-        assert!(result.is_ok());
-    }
+        let tags = std::collections::HashMap::from([
+            ("rust".to_string(), 0.8),
+            ("ml".to_string(), 0.9),
+            ("async".to_string(), 0.8),
+        ]);
+        let lines = format_tag_suggestions(&service, &tags).expect("formatting should succeed");
 
-    #[test]
-    fn execute_graph_search_with_empty_database_shows_no_notes_found() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
+        assert_eq!(
+            lines,
+            vec![
+                "ml (90%) -> alias for existing tag 'machine-learning'",
+                "async (80%)",
+                "rust (80%)",
+            ]
+        );
 
-        // Execute graph search in empty database
-        let result = execute_graph_search("machine learning", Some(10), service);
-        assert!(result.is_ok());
-        // Should complete successfully and print "No notes found via graph search"
+        // Previewing must not create aliases or tags
+        assert!(
+            service
+                .list_aliases()
+                .expect("failed to list aliases")
+                .is_empty()
+        );
+        assert_eq!(
+            service
+                .list_tags_with_counts()
+                .expect("failed to list tags")
+                .len(),
+            1
+        );
     }
 
     #[test]
-    fn execute_graph_search_limit_flag_restricts_result_count() {
+    fn execute_tags_suggest_rejects_empty_text() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-
-        // Create multiple notes
-        for i in 1..=5 {
-            service
-                .create_note(&format!("Test note {}", i), Some(&["test"]))
-                .expect("failed to create note");
-        }
-
-        // Execute with limit of 3
-        let result = execute_graph_search("test", Some(3), service);
-        assert!(result.is_ok());
-        // The limit is applied at the service layer, verified by service tests
+        let err = execute_tags_suggest("   ", None, db).expect_err("empty text should fail");
+        assert!(err.to_string().contains("cannot be empty"));
     }
 
-    // --- Tags List CLI Command Tests (Task Group 4) ---
+    // --- Tags With Counts CLI Tests ---
 
     #[test]
-    fn tags_list_command_struct_parsing_with_clap() {
+    fn tags_command_parses_without_subcommand() {
         use clap::CommandFactory;
 
-        // Test parsing of `cons tags list`
         let matches = Cli::command()
-            .try_get_matches_from(vec!["cons", "tags", "list"])
-            .expect("failed to parse tags list command");
-
-        // Verify command is recognized
+            .try_get_matches_from(vec!["cons", "tags"])
+            .expect("failed to parse bare tags command");
         assert!(matches.subcommand_matches("tags").is_some());
-    }
-
-    #[test]
-    fn execute_tags_list_shows_degree_centrality() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
 
-        // Create notes with tags
-        service
-            .create_note("Rust note", Some(&["rust"]))
-            .expect("failed to create note");
-        service
-            .create_note("Programming note", Some(&["programming"]))
-            .expect("failed to create note");
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tags", "--sort", "name", "--limit", "5"])
+            .expect("failed to parse tags command with sort and limit");
+        let tags_matches = matches.subcommand_matches("tags").unwrap();
+        assert_eq!(
+            tags_matches.get_one::<TagSort>("sort").copied(),
+            Some(TagSort::Name)
+        );
+        assert_eq!(tags_matches.get_one::<usize>("limit").copied(), Some(5));
 
-        // Get tags with stats to verify degree centrality is included
-        let tags = service
-            .get_tags_with_stats()
-            .expect("failed to get tags with stats");
+        let result = Cli::command().try_get_matches_from(vec!["cons", "tags", "--sort", "size"]);
+        assert!(result.is_err(), "unknown sort order should be rejected");
+    }
 
-        // Verify we have 2 tags with degree centrality
-        assert_eq!(tags.len(), 2);
+    #[test]
+    fn execute_tags_lists_tags_including_orphans() {
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let db_path = temp_dir.path().join("notes.db");
 
-        // Verify all tags have degree_centrality (should be 0 since no edges created yet)
-        for (_, _, _, degree_centrality) in &tags {
-            assert_eq!(*degree_centrality, 0);
+        {
+            let service =
+                NoteService::new(Database::open(&db_path).expect("failed to open database"));
+            service
+                .create_note("Rust note", Some(&["rust"]))
+                .expect("failed to create note");
+            service
+                .get_or_create_tag("orphan")
+                .expect("failed to create tag");
         }
 
-        // Test the execute_tags_list function with a new database
-        let db2 = Database::in_memory().expect("failed to create in-memory database");
-        let service2 = NoteService::new(db2);
-        service2
-            .create_note("Test note", Some(&["test"]))
-            .expect("failed to create note");
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_tags(TagSort::Count, None, false, false, db).is_ok());
 
-        let tags2 = service2
-            .get_tags_with_stats()
-            .expect("failed to get tags with stats");
-        assert_eq!(tags2.len(), 1);
-        let (_, name, note_count, degree_centrality) = &tags2[0];
-        assert_eq!(name, "test");
-        assert_eq!(*note_count, 1);
-        assert_eq!(*degree_centrality, 0);
+        let db = Database::open(&db_path).expect("failed to open database");
+        assert!(execute_tags(TagSort::Name, Some(1), true, false, db).is_ok());
     }
 
     #[test]
-    fn execute_tags_list_with_empty_database_shows_no_tags_found() {
+    fn execute_tags_with_empty_database_shows_no_tags_found() {
         let db = Database::in_memory().expect("failed to create in-memory database");
-
-        // Execute tags list in empty database
-        let result = execute_tags_list(db);
-        assert!(result.is_ok());
-        // Should complete successfully and print "No tags found"
+        assert!(execute_tags(TagSort::Count, None, false, false, db).is_ok());
     }
 
-    #[test]
-    fn execute_tags_list_output_format_includes_note_count_and_connections() {
-        let db = Database::in_memory().expect("failed to create in-memory database");
-        let service = NoteService::new(db);
-
-        // Create notes with tags
-        service
-            .create_note("First rust note", Some(&["rust"]))
-            .expect("failed to create note");
-        service
-            .create_note("Second rust note", Some(&["rust"]))
-            .expect("failed to create note");
-        service
-            .create_note("Python note", Some(&["python"]))
-            .expect("failed to create note");
+    // --- Tag Rename CLI Tests ---
 
-        // Get tags with stats to verify the service method works correctly
-        let tags = service
-            .get_tags_with_stats()
-            .expect("failed to get tags with stats");
+    #[test]
+    fn tags_rename_command_parsing_with_clap() {
+        use clap::CommandFactory;
 
-        // Verify we have 2 tags
-        assert_eq!(tags.len(), 2);
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tags", "rename", "ml", "machine-learning"])
+            .expect("failed to parse tags rename command");
+        assert!(matches.subcommand_matches("tags").is_some());
 
-        // Find the rust tag
-        let rust_tag = tags.iter().find(|(_, name, _, _)| name == "rust");
-        assert!(rust_tag.is_some());
-        let (_, name, note_count, degree_centrality) = rust_tag.unwrap();
-        assert_eq!(name, "rust");
-        assert_eq!(*note_count, 2);
-        assert_eq!(*degree_centrality, 0); // No edges created yet
+        // `tag` is accepted as an alias for `tags`
+        let matches = Cli::command()
+            .try_get_matches_from(vec!["cons", "tag", "rename", "ml", "machine-learning"])
+            .expect("failed to parse tag rename command");
+        assert!(matches.subcommand_matches("tags").is_some());
+    }
 
-        // Find the python tag
-        let python_tag = tags.iter().find(|(_, name, _, _)| name == "python");
-        assert!(python_tag.is_some());
-        let (_, name, note_count, degree_centrality) = python_tag.unwrap();
-        assert_eq!(name, "python");
-        assert_eq!(*note_count, 1);
-        assert_eq!(*degree_centrality, 0); // No edges created yet
+    #[test]
+    fn execute_tags_rename_fails_for_non_existent_tag() {
+        let db = Database::in_memory().expect("failed to create in-memory database");
+        let result = execute_tags_rename("missing", "other", db);
+        assert!(result.is_err());
     }
 }
@@ -1,4 +1,5 @@
 mod alias_info;
+mod edge_info;
 mod ids;
 mod note;
 mod tag;
@@ -6,6 +7,7 @@ mod tag_assignment;
 mod tag_source;
 
 pub use alias_info::AliasInfo;
+pub use edge_info::EdgeInfo;
 pub use ids::{NoteId, TagId};
 pub use note::{Note, NoteBuilder};
 pub use tag::Tag;
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::TagId;
+
+/// Information about a hierarchy edge between two tags.
+///
+/// Edges point from the narrower (source) tag to the broader (target) tag,
+/// e.g. `tokio -> rust`. Tag names are resolved so callers can display
+/// the hierarchy without extra lookups.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeInfo {
+    source_tag_id: TagId,
+    source_name: String,
+    target_tag_id: TagId,
+    target_name: String,
+    hierarchy_type: String,
+    confidence: f64,
+    source: String,
+    #[serde(default)]
+    verified: bool,
+}
+
+impl EdgeInfo {
+    /// Creates a new EdgeInfo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{EdgeInfo, TagId};
+    ///
+    /// let edge = EdgeInfo::new(
+    ///     TagId::new(1),
+    ///     "tokio",
+    ///     TagId::new(2),
+    ///     "rust",
+    ///     "partitive",
+    ///     0.8,
+    ///     "llm",
+    /// );
+    ///
+    /// assert_eq!(edge.source_name(), "tokio");
+    /// assert_eq!(edge.target_name(), "rust");
+    /// assert_eq!(edge.hierarchy_type(), "partitive");
+    /// ```
+    pub fn new(
+        source_tag_id: TagId,
+        source_name: impl Into<String>,
+        target_tag_id: TagId,
+        target_name: impl Into<String>,
+        hierarchy_type: impl Into<String>,
+        confidence: f64,
+        source: impl Into<String>,
+    ) -> Self {
+        Self {
+            source_tag_id,
+            source_name: source_name.into(),
+            target_tag_id,
+            target_name: target_name.into(),
+            hierarchy_type: hierarchy_type.into(),
+            confidence,
+            source: source.into(),
+            verified: false,
+        }
+    }
+
+    /// Returns the ID of the narrower (source) tag.
+    pub fn source_tag_id(&self) -> TagId {
+        self.source_tag_id
+    }
+
+    /// Returns the name of the narrower (source) tag.
+    pub fn source_name(&self) -> &str {
+        &self.source_name
+    }
+
+    /// Returns the ID of the broader (target) tag.
+    pub fn target_tag_id(&self) -> TagId {
+        self.target_tag_id
+    }
+
+    /// Returns the name of the broader (target) tag.
+    pub fn target_name(&self) -> &str {
+        &self.target_name
+    }
+
+    /// Returns the relationship type ('generic' or 'partitive').
+    pub fn hierarchy_type(&self) -> &str {
+        &self.hierarchy_type
+    }
+
+    /// Returns the confidence score (0.0-1.0).
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// Returns the source of this edge ('user' or 'llm').
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns whether this edge has been verified by the user.
+    pub fn verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Marks this edge as verified by the user.
+    pub fn verify(&mut self) {
+        self.verified = true;
+    }
+}
+
+impl fmt::Display for EdgeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} -> {} ({}, {:.0}%)",
+            self.source_name,
+            self.target_name,
+            self.hierarchy_type,
+            self.confidence * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_shows_direction_type_and_confidence() {
+        let edge = EdgeInfo::new(
+            TagId::new(1),
+            "tokio",
+            TagId::new(2),
+            "rust",
+            "partitive",
+            0.8,
+            "llm",
+        );
+
+        assert_eq!(edge.to_string(), "tokio -> rust (partitive, 80%)");
+        assert_eq!(edge.source_tag_id(), TagId::new(1));
+        assert_eq!(edge.target_tag_id(), TagId::new(2));
+        assert_eq!(edge.source(), "llm");
+        assert!(!edge.verified());
+    }
+}
@@ -9,8 +9,11 @@ use super::{NoteId, TagAssignment};
 /// contains freeform text content and zero or more tag assignments.
 ///
 /// Enhancement fields store AI-expanded versions of fragmentary notes with
-/// provenance metadata (model, confidence, timestamp). All enhancement fields
+/// provenance metadata (model, confidence, timestamp, prompt). All enhancement fields
 /// are optional and default to None when enhancement is unavailable.
+///
+/// The `source` records how the note was captured (`cli`, `tui`, `import`),
+/// or `unknown` for notes created before provenance was tracked.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Note {
     id: NoteId,
@@ -30,6 +33,16 @@ pub struct Note {
     enhancement_model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     enhancement_confidence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    enhancement_prompt: Option<String>,
+    #[serde(default = "unknown_source")]
+    source: String,
+}
+
+/// Source reported for notes whose provenance was never stored.
+fn unknown_source() -> String {
+    "unknown".to_string()
 }
 
 impl Note {
@@ -87,6 +100,61 @@ impl Note {
     pub fn enhancement_confidence(&self) -> Option<f64> {
         self.enhancement_confidence
     }
+
+    /// Returns the prompt that produced the enhancement, if it was recorded.
+    pub fn enhancement_prompt(&self) -> Option<&str> {
+        self.enhancement_prompt.as_deref()
+    }
+
+    /// Returns how this note was captured (`cli`, `tui`, `import` or `unknown`).
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the content shortened to at most `max_chars` characters, plus a
+    /// trailing `...` when it was cut.
+    ///
+    /// Longer content is cut at the last word boundary within the limit; a
+    /// single word longer than the limit is cut mid-word. The `...` is not
+    /// counted, so a cut excerpt can be up to `max_chars + 3` characters long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{NoteBuilder, NoteId};
+    ///
+    /// let note = NoteBuilder::new()
+    ///     .id(NoteId::new(1))
+    ///     .content("Learning about Rust ownership")
+    ///     .build();
+    ///
+    /// assert_eq!(note.excerpt(20), "Learning about Rust...");
+    /// assert_eq!(note.excerpt(100), "Learning about Rust ownership");
+    /// ```
+    pub fn excerpt(&self, max_chars: usize) -> String {
+        excerpt(&self.content, max_chars)
+    }
+
+    /// Like [`excerpt`](Self::excerpt), for the enhanced content if there is one.
+    pub fn enhanced_excerpt(&self, max_chars: usize) -> Option<String> {
+        self.content_enhanced
+            .as_deref()
+            .map(|enhanced| excerpt(enhanced, max_chars))
+    }
+}
+
+/// Shortens `text` to at most `max_chars` characters at a word boundary, then appends `...`.
+fn excerpt(text: &str, max_chars: usize) -> String {
+    let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+        return text.to_string();
+    };
+
+    let head = &text[..cut];
+    let head = match head.rfind(char::is_whitespace) {
+        Some(boundary) if !text[cut..].starts_with(char::is_whitespace) => &head[..boundary],
+        _ => head,
+    };
+    format!("{}...", head.trim_end())
 }
 
 /// Builder for constructing `Note` instances.
@@ -116,6 +184,8 @@ pub struct NoteBuilder {
     enhanced_at: Option<OffsetDateTime>,
     enhancement_model: Option<String>,
     enhancement_confidence: Option<f64>,
+    enhancement_prompt: Option<String>,
+    source: Option<String>,
 }
 
 impl NoteBuilder {
@@ -178,6 +248,18 @@ impl NoteBuilder {
         self
     }
 
+    /// Sets the enhancement prompt (defaults to None).
+    pub fn enhancement_prompt(mut self, enhancement_prompt: impl Into<String>) -> Self {
+        self.enhancement_prompt = Some(enhancement_prompt.into());
+        self
+    }
+
+    /// Sets the capture source (defaults to `unknown`).
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Builds the `Note`, using defaults for optional fields.
     ///
     /// # Panics
@@ -195,6 +277,8 @@ impl NoteBuilder {
             enhanced_at: self.enhanced_at,
             enhancement_model: self.enhancement_model,
             enhancement_confidence: self.enhancement_confidence,
+            enhancement_prompt: self.enhancement_prompt,
+            source: self.source.unwrap_or_else(unknown_source),
         }
     }
 }
@@ -214,6 +298,7 @@ mod tests {
         assert_eq!(note.id(), NoteId::new(1));
         assert_eq!(note.content(), "Test note");
         assert!(note.tags().is_empty());
+        assert_eq!(note.source(), "unknown");
     }
 
     #[test]
@@ -252,6 +337,22 @@ mod tests {
         assert_eq!(note, deserialized);
     }
 
+    #[test]
+    fn deserialization_defaults_missing_source_to_unknown() {
+        let note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("Exported before provenance")
+            .source("cli")
+            .build();
+
+        let mut json = serde_json::to_value(&note).unwrap();
+        assert_eq!(json["source"], "cli");
+        json.as_object_mut().unwrap().remove("source");
+
+        let deserialized: Note = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.source(), "unknown");
+    }
+
     #[test]
     fn note_with_mixed_tag_sources() {
         let now = OffsetDateTime::now_utc();
@@ -285,4 +386,59 @@ mod tests {
 
         assert_eq!(note.tags().len(), 2);
     }
+
+    fn note_with_content(content: &str) -> Note {
+        NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content(content)
+            .build()
+    }
+
+    #[test]
+    fn excerpt_cuts_at_word_boundary() {
+        let note = note_with_content("the quick brown fox jumps");
+
+        assert_eq!(note.excerpt(12), "the quick...");
+        assert_eq!(note.excerpt(15), "the quick brown...");
+        assert_eq!(note.excerpt(3), "the...");
+    }
+
+    #[test]
+    fn excerpt_returns_short_content_unchanged() {
+        let note = note_with_content("short note");
+
+        assert_eq!(note.excerpt(10), "short note");
+        assert_eq!(note.excerpt(200), "short note");
+    }
+
+    #[test]
+    fn excerpt_cuts_long_word_mid_word() {
+        let note = note_with_content("supercalifragilistic");
+
+        assert_eq!(note.excerpt(5), "super...");
+        assert_eq!(note.excerpt(5).chars().count(), 5 + "...".len());
+    }
+
+    #[test]
+    fn excerpt_does_not_split_multibyte_chars() {
+        let note = note_with_content("café über naïve résumé");
+
+        assert_eq!(note.excerpt(12), "café über...");
+        assert_eq!(note_with_content("日本語のノート").excerpt(3), "日本語...");
+    }
+
+    #[test]
+    fn enhanced_excerpt_shortens_enhanced_content() {
+        let note = NoteBuilder::new()
+            .id(NoteId::new(1))
+            .content("rust ownership")
+            .content_enhanced("Rust enforces ownership at compile time.")
+            .build();
+
+        assert_eq!(
+            note.enhanced_excerpt(20).as_deref(),
+            Some("Rust enforces...")
+        );
+        assert_eq!(note_with_content("plain").enhanced_excerpt(20), None);
+    }
 }
@@ -82,6 +82,36 @@ impl TagAssignment {
         }
     }
 
+    /// Creates an imported tag assignment with 100% confidence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{TagAssignment, TagId};
+    /// use time::OffsetDateTime;
+    ///
+    /// let now = OffsetDateTime::now_utc();
+    /// let assignment = TagAssignment::import(TagId::new(7), "reading", "obsidian", now);
+    ///
+    /// assert_eq!(assignment.confidence(), 100);
+    /// assert!(assignment.source().is_import());
+    /// assert_eq!(assignment.source().import_source(), Some("obsidian"));
+    /// ```
+    pub fn import(
+        tag_id: TagId,
+        name: impl Into<String>,
+        source_name: impl Into<String>,
+        created_at: OffsetDateTime,
+    ) -> Self {
+        Self {
+            tag_id,
+            tag_name: name.into(),
+            source: TagSource::import(source_name),
+            created_at,
+            verified: false,
+        }
+    }
+
     /// Returns the tag ID.
     pub fn tag_id(&self) -> TagId {
         self.tag_id
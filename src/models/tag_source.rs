@@ -3,9 +3,10 @@ use std::fmt;
 
 /// Source of a tag assignment.
 ///
-/// Distinguishes between tags explicitly created by users and those inferred by LLM.
-/// The `Llm` variant carries provenance metadata (model and confidence) intrinsic
-/// to LLM-inferred tags.
+/// Distinguishes between tags explicitly created by users, those inferred by LLM,
+/// and those brought in from external data. The `Llm` variant carries provenance
+/// metadata (model and confidence) intrinsic to LLM-inferred tags; the `Import`
+/// variant records where imported tags came from.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 #[non_exhaustive]
@@ -20,6 +21,12 @@ pub enum TagSource {
         /// Confidence score (0-100 percentage).
         confidence: u8,
     },
+    /// Tag brought in from external data (e.g., another tool's export).
+    /// Imported tags are implicitly 100% confidence with no model.
+    Import {
+        /// Name of the external source the tag came from (e.g., "obsidian").
+        source_name: String,
+    },
 }
 
 impl TagSource {
@@ -31,11 +38,18 @@ impl TagSource {
         }
     }
 
+    /// Creates an import tag source for the named external source.
+    pub fn import(source_name: impl Into<String>) -> Self {
+        Self::Import {
+            source_name: source_name.into(),
+        }
+    }
+
     /// Returns the confidence score for this tag source.
-    /// User tags always return 100.
+    /// User and imported tags always return 100.
     pub fn confidence(&self) -> u8 {
         match self {
-            Self::User => 100,
+            Self::User | Self::Import { .. } => 100,
             Self::Llm { confidence, .. } => *confidence,
         }
     }
@@ -43,11 +57,19 @@ impl TagSource {
     /// Returns the model identifier if this is an LLM-inferred tag.
     pub fn model(&self) -> Option<&str> {
         match self {
-            Self::User => None,
+            Self::User | Self::Import { .. } => None,
             Self::Llm { model, .. } => Some(model),
         }
     }
 
+    /// Returns the external source name if this is an imported tag.
+    pub fn import_source(&self) -> Option<&str> {
+        match self {
+            Self::Import { source_name } => Some(source_name),
+            Self::User | Self::Llm { .. } => None,
+        }
+    }
+
     /// Returns true if this tag was created by a user.
     pub fn is_user(&self) -> bool {
         matches!(self, Self::User)
@@ -57,6 +79,11 @@ impl TagSource {
     pub fn is_llm(&self) -> bool {
         matches!(self, Self::Llm { .. })
     }
+
+    /// Returns true if this tag was imported from external data.
+    pub fn is_import(&self) -> bool {
+        matches!(self, Self::Import { .. })
+    }
 }
 
 impl fmt::Display for TagSource {
@@ -66,6 +93,7 @@ impl fmt::Display for TagSource {
             Self::Llm { model, confidence } => {
                 write!(f, "llm({model}, {confidence}%)")
             }
+            Self::Import { .. } => write!(f, "import"),
         }
     }
 }
@@ -97,6 +125,19 @@ mod tests {
         assert_eq!(deserialized, source);
     }
 
+    #[test]
+    fn import_serializes_with_source_name() {
+        let source = TagSource::import("obsidian");
+        let json = serde_json::to_string(&source).unwrap();
+        assert_eq!(json, r#"{"type":"import","source_name":"obsidian"}"#);
+
+        let deserialized: TagSource = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, source);
+        assert!(deserialized.is_import());
+        assert!(!deserialized.is_user());
+        assert!(!deserialized.is_llm());
+    }
+
     #[test]
     fn deserialization_fails_on_unknown_variant() {
         let invalid_json = r#"{"type":"unknown"}"#;
@@ -108,6 +149,7 @@ mod tests {
     fn confidence_returns_correct_values() {
         assert_eq!(TagSource::User.confidence(), 100);
         assert_eq!(TagSource::llm("model", 75).confidence(), 75);
+        assert_eq!(TagSource::import("obsidian").confidence(), 100);
     }
 
     #[test]
@@ -117,6 +159,11 @@ mod tests {
             TagSource::llm("deepseek-r1:8b", 85).model(),
             Some("deepseek-r1:8b")
         );
+        assert_eq!(TagSource::import("obsidian").model(), None);
+        assert_eq!(
+            TagSource::import("obsidian").import_source(),
+            Some("obsidian")
+        );
     }
 
     #[test]
@@ -126,5 +173,6 @@ mod tests {
             format!("{}", TagSource::llm("gpt-4", 92)),
             "llm(gpt-4, 92%)"
         );
+        assert_eq!(format!("{}", TagSource::import("obsidian")), "import");
     }
 }
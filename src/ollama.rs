@@ -3,5 +3,7 @@
 /// This module provides an async HTTP client for interacting with the Ollama API,
 /// including error handling, retry logic, and timeout configuration.
 mod client;
+mod json;
 
-pub use client::{OllamaClient, OllamaClientBuilder, OllamaClientTrait, OllamaError};
+pub use client::{GenOptions, OllamaClient, OllamaClientBuilder, OllamaClientTrait, OllamaError};
+pub(crate) use json::{extract_json_array, extract_json_object, generate_json};
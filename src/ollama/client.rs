@@ -5,8 +5,11 @@
 use std::thread;
 use std::time::Duration;
 
+use serde::Serialize;
 use thiserror::Error;
 
+use crate::config::Config;
+
 /// Errors that can occur when interacting with the Ollama API.
 #[derive(Debug, Error)]
 pub enum OllamaError {
@@ -15,8 +18,13 @@ pub enum OllamaError {
     Network(#[source] reqwest::Error),
 
     /// Request or response timeout errors
-    #[error("Request timed out")]
-    Timeout(#[source] reqwest::Error),
+    #[error("Request timed out after {secs}s")]
+    Timeout {
+        /// The configured timeout that was exceeded, in seconds
+        secs: u64,
+        #[source]
+        source: reqwest::Error,
+    },
 
     /// HTTP errors with status code
     #[error("HTTP error: status {status}")]
@@ -33,6 +41,68 @@ pub enum OllamaError {
     /// Invalid URL configuration error
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
+
+    /// The requested model is not installed in Ollama
+    #[error(
+        "model '{model}' not found; available: {} — run 'ollama pull {model}'",
+        format_available_models(.available)
+    )]
+    ModelNotFound {
+        /// The model that was requested
+        model: String,
+        /// Installed models, largest first (empty if they could not be listed)
+        available: Vec<String>,
+    },
+}
+
+/// Formats installed model names for [`OllamaError::ModelNotFound`].
+fn format_available_models(available: &[String]) -> String {
+    if available.is_empty() {
+        "none".to_string()
+    } else {
+        available.join(", ")
+    }
+}
+
+/// Sampling options for a single generation request.
+///
+/// Unset fields are omitted from the request, so Ollama falls back to the
+/// model's defaults. Serializes to the `options` object of `/api/generate`.
+///
+/// # Examples
+///
+/// ```
+/// use cons::ollama::GenOptions;
+///
+/// let options = GenOptions {
+///     temperature: Some(0.2),
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+/// assert!(!options.is_empty());
+/// assert!(GenOptions::default().is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct GenOptions {
+    /// Sampling temperature; lower values are more deterministic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling cutoff (0.0-1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Random seed; the same seed and prompt give reproducible output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Maximum number of tokens to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_predict: Option<i32>,
+}
+
+impl GenOptions {
+    /// Returns true when no option is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Builder for constructing `OllamaClient` instances.
@@ -51,6 +121,8 @@ pub enum OllamaError {
 pub struct OllamaClientBuilder {
     base_url: Option<String>,
     model: Option<String>,
+    timeout: Option<Duration>,
+    max_retries: Option<u32>,
 }
 
 impl OllamaClientBuilder {
@@ -79,6 +151,29 @@ impl OllamaClientBuilder {
         self
     }
 
+    /// Sets the timeout for each request to the Ollama API.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for a complete response
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many times a failed request is retried.
+    ///
+    /// Only transient failures (timeouts, connection errors, HTTP 5xx) are
+    /// retried, with exponential backoff starting at 1s. Zero disables retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_retries` - Number of retries after the initial attempt
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
     /// Builds the `OllamaClient` with the configured settings.
     ///
     /// # Returns
@@ -89,24 +184,30 @@ impl OllamaClientBuilder {
     /// # Environment Variables
     ///
     /// If `base_url()` was not called, this method will check the `OLLAMA_HOST`
-    /// environment variable. If not set, it defaults to `http://172.17.64.1:11434`.
+    /// environment variable. If not set, it defaults to `http://localhost:11434`.
+    /// Trailing slashes are stripped so request paths never contain `//`.
     ///
     /// If `model()` was not called, this method will check the `OLLAMA_MODEL`
     /// environment variable. If not set, it defaults to an empty string.
+    ///
+    /// If `timeout()` or `max_retries()` were not called, `OLLAMA_TIMEOUT_SECS`
+    /// and `OLLAMA_MAX_RETRIES` are checked, defaulting to 30 seconds and 1 retry.
     pub fn build(self) -> Result<OllamaClient, OllamaError> {
-        // Determine base URL: use builder value, then env var, then default
-        let base_url = if let Some(url) = self.base_url {
-            url
-        } else {
-            std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".to_string())
-        };
+        self.build_with_config(Config::load())
+    }
 
-        // Determine model: use builder value, then env var, then default
-        let model = if let Some(m) = self.model {
-            m
-        } else {
-            std::env::var("OLLAMA_MODEL").unwrap_or_else(|_| String::new())
-        };
+    /// Builds the client, filling unset values from `config` instead of the environment.
+    fn build_with_config(self, config: Config) -> Result<OllamaClient, OllamaError> {
+        // Builder values take precedence over env vars, then defaults
+        let base_url = self.base_url.unwrap_or(config.ollama_host);
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let model = self
+            .model
+            .unwrap_or_else(|| config.ollama_model.unwrap_or_default());
+        let timeout = self
+            .timeout
+            .unwrap_or_else(|| Duration::from_secs(config.ollama_timeout_secs));
+        let max_retries = self.max_retries.unwrap_or(config.ollama_max_retries);
 
         // Validate URL
         reqwest::Url::parse(&base_url)
@@ -114,8 +215,8 @@ impl OllamaClientBuilder {
 
         // Create reqwest blocking client with timeout configuration
         let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(60))
-            .connect_timeout(Duration::from_secs(5))
+            .timeout(timeout)
+            .connect_timeout(timeout.min(Duration::from_secs(5)))
             .build()
             .map_err(OllamaError::Network)?;
 
@@ -123,6 +224,8 @@ impl OllamaClientBuilder {
             client,
             base_url,
             model,
+            timeout,
+            max_retries,
         })
     }
 }
@@ -135,6 +238,8 @@ pub struct OllamaClient {
     client: reqwest::blocking::Client,
     base_url: String,
     model: String,
+    timeout: Duration,
+    max_retries: u32,
 }
 
 /// Trait for Ollama API client operations.
@@ -153,6 +258,22 @@ pub trait OllamaClientTrait: Send + Sync {
     ///
     /// Returns the generated text as a `String`, or an error if the request fails.
     fn generate(&self, model: &str, prompt: &str) -> Result<String, OllamaError>;
+
+    /// Generates text with sampling options such as temperature and seed.
+    ///
+    /// The default implementation ignores `options` and calls [`generate`],
+    /// so existing implementations (including test mocks) keep working.
+    ///
+    /// [`generate`]: OllamaClientTrait::generate
+    fn generate_with_options(
+        &self,
+        model: &str,
+        prompt: &str,
+        options: GenOptions,
+    ) -> Result<String, OllamaError> {
+        let _ = options;
+        self.generate(model, prompt)
+    }
 }
 
 impl OllamaClient {
@@ -166,6 +287,28 @@ impl OllamaClient {
         &self.model
     }
 
+    /// Returns the per-request timeout configured for this client.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns how many times transient failures are retried.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Converts a reqwest error, reporting timeouts with the configured limit.
+    fn request_error(&self, error: reqwest::Error) -> OllamaError {
+        if error.is_timeout() {
+            OllamaError::Timeout {
+                secs: self.timeout.as_secs(),
+                source: error,
+            }
+        } else {
+            OllamaError::Network(error)
+        }
+    }
+
     /// Lists available models from the Ollama API, sorted by size (largest first).
     ///
     /// Fetches the `/api/tags` endpoint and returns model names.
@@ -177,7 +320,7 @@ impl OllamaClient {
             .client
             .get(&url)
             .send()
-            .map_err(OllamaError::Network)?;
+            .map_err(|e| self.request_error(e))?;
 
         if !response.status().is_success() {
             return Err(OllamaError::Http {
@@ -208,27 +351,41 @@ impl OllamaClient {
         Ok(models.into_iter().map(|(name, _)| name).collect())
     }
 
+    /// Checks that the Ollama API is reachable.
+    ///
+    /// Hits the `/api/tags` endpoint and returns the installed model names
+    /// (largest first), so callers can also verify a configured model exists.
+    pub fn health_check(&self) -> Result<Vec<String>, OllamaError> {
+        self.list_models()
+    }
+
     /// Generates text using the Ollama API.
     ///
-    /// This is the internal implementation that will be called by the trait method.
-    fn generate_internal(&self, model: &str, prompt: &str) -> Result<String, OllamaError> {
+    /// This is the internal implementation that will be called by the trait methods.
+    fn generate_internal(
+        &self,
+        model: &str,
+        prompt: &str,
+        options: &GenOptions,
+    ) -> Result<String, OllamaError> {
         let url = format!("{}/api/generate", self.base_url);
-        let request_body = serde_json::json!({
-            "model": model,
-            "prompt": prompt,
-            "stream": false
-        });
+        let request_body = generate_request_body(model, prompt, options)?;
 
         // Wrap the HTTP call with retry logic
-        retry_with_backoff(|| {
+        retry_with_backoff(self.max_retries, || {
             let response = self
                 .client
                 .post(&url)
                 .json(&request_body)
                 .send()
-                .map_err(OllamaError::Network)?;
+                .map_err(|e| self.request_error(e))?;
 
             let status = response.status();
+            if status == reqwest::StatusCode::NOT_FOUND {
+                // Ollama answers 404 with `{"error": "model \"x\" not found, ..."}`
+                let body = response.text().unwrap_or_default();
+                return Err(self.not_found_error(model, &body));
+            }
             if !status.is_success() {
                 if status.is_client_error() {
                     // 4xx errors - don't retry
@@ -243,7 +400,7 @@ impl OllamaClient {
                 }
             }
 
-            let json: serde_json::Value = response.json().map_err(OllamaError::Network)?;
+            let json: serde_json::Value = response.json().map_err(|e| self.request_error(e))?;
 
             // Extract the "response" field from Ollama API response
             json.get("response")
@@ -254,33 +411,75 @@ impl OllamaClient {
                 })
         })
     }
+
+    /// Converts a 404 from `/api/generate` into an error.
+    ///
+    /// When the body reports a missing model, the installed models are listed
+    /// so the error can suggest alternatives; listing failures leave them empty.
+    fn not_found_error(&self, model: &str, body: &str) -> OllamaError {
+        if !body.to_lowercase().contains("not found") {
+            return OllamaError::Http { status: 404 };
+        }
+
+        OllamaError::ModelNotFound {
+            model: model.to_string(),
+            available: self.list_models().unwrap_or_default(),
+        }
+    }
 }
 
 impl OllamaClientTrait for OllamaClient {
     fn generate(&self, model: &str, prompt: &str) -> Result<String, OllamaError> {
-        self.generate_internal(model, prompt)
+        self.generate_internal(model, prompt, &GenOptions::default())
+    }
+
+    fn generate_with_options(
+        &self,
+        model: &str,
+        prompt: &str,
+        options: GenOptions,
+    ) -> Result<String, OllamaError> {
+        self.generate_internal(model, prompt, &options)
     }
 }
 
-/// Retries an async operation with exponential backoff.
+/// Builds the `/api/generate` request body.
 ///
-/// This function will retry the operation up to 3 times with delays of 1s, 2s, and 4s.
-/// It only retries on transient errors (HTTP 5xx and network errors), not on client errors (HTTP 4xx).
+/// The `options` object is only included when at least one option is set.
+fn generate_request_body(
+    model: &str,
+    prompt: &str,
+    options: &GenOptions,
+) -> Result<serde_json::Value, OllamaError> {
+    let mut body = serde_json::json!({
+        "model": model,
+        "prompt": prompt,
+        "stream": false
+    });
+    if !options.is_empty() {
+        body["options"] = serde_json::to_value(options).map_err(OllamaError::Serialization)?;
+    }
+    Ok(body)
+}
+
+/// Retries an operation with exponential backoff.
+///
+/// This function will retry the operation up to `max_retries` times with delays of
+/// 1s, 2s, 4s, and so on. It only retries on transient errors (HTTP 5xx, network
+/// errors and timeouts), not on client errors (HTTP 4xx).
 ///
 /// # Arguments
 ///
-/// * `f` - A closure that returns a future producing a `Result<T, OllamaError>`
+/// * `max_retries` - Number of retries after the initial attempt
+/// * `f` - A closure producing a `Result<T, OllamaError>`
 ///
 /// # Returns
 ///
 /// Returns the result of the operation if it succeeds, or the last error if all retries fail.
-pub fn retry_with_backoff<F, T>(mut f: F) -> Result<T, OllamaError>
+pub fn retry_with_backoff<F, T>(max_retries: u32, mut f: F) -> Result<T, OllamaError>
 where
     F: FnMut() -> Result<T, OllamaError>,
 {
-    const MAX_RETRIES: usize = 3;
-    const DELAYS: [u64; MAX_RETRIES] = [1, 2, 4]; // seconds
-
     // Try the operation first
     let mut last_error = match f() {
         Ok(result) => return Ok(result),
@@ -293,10 +492,10 @@ where
         }
     };
 
-    // Retry up to MAX_RETRIES times
-    for &delay_secs in &DELAYS {
+    // Retry up to max_retries times
+    for attempt in 0..max_retries {
         // Sleep before retry (exponential backoff)
-        thread::sleep(Duration::from_secs(delay_secs));
+        thread::sleep(Duration::from_secs(1u64 << attempt.min(16)));
 
         match f() {
             Ok(result) => return Ok(result),
@@ -321,7 +520,7 @@ where
 fn should_retry(error: &OllamaError) -> bool {
     match error {
         OllamaError::Network(_) => true,
-        OllamaError::Timeout(_) => true,
+        OllamaError::Timeout { .. } => true,
         OllamaError::Http { status } => {
             // Retry on 5xx server errors, not on 4xx client errors
             *status >= 500 && *status < 600
@@ -329,6 +528,7 @@ fn should_retry(error: &OllamaError) -> bool {
         OllamaError::Serialization(_) => false, // Don't retry serialization errors
         OllamaError::Api { .. } => false,       // Don't retry API errors
         OllamaError::InvalidUrl(_) => false,    // Don't retry invalid URL errors
+        OllamaError::ModelNotFound { .. } => false, // Retrying won't install the model
     }
 }
 
@@ -361,11 +561,14 @@ mod tests {
         let client = reqwest::blocking::Client::new();
         let invalid_url = "http://";
         let reqwest_error = client.get(invalid_url).build().unwrap_err();
-        let ollama_error = OllamaError::Timeout(reqwest_error);
+        let ollama_error = OllamaError::Timeout {
+            secs: 30,
+            source: reqwest_error,
+        };
 
-        // Verify error message is user-friendly
+        // Verify error message is user-friendly and names the limit
         let error_msg = format!("{}", ollama_error);
-        assert_eq!(error_msg, "Request timed out");
+        assert_eq!(error_msg, "Request timed out after 30s");
     }
 
     #[test]
@@ -454,6 +657,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn build_strips_trailing_slash_from_base_url() {
+        let client = OllamaClientBuilder::new()
+            .base_url("http://gpu-box:11434/")
+            .build()
+            .expect("valid URL should build");
+        assert_eq!(client.base_url(), "http://gpu-box:11434");
+        assert_eq!(
+            format!("{}/api/generate", client.base_url()),
+            "http://gpu-box:11434/api/generate"
+        );
+    }
+
+    #[test]
+    fn timeout_and_max_retries_builder_values_are_used() {
+        let client = OllamaClientBuilder::new()
+            .base_url("http://localhost:11434")
+            .timeout(Duration::from_secs(5))
+            .max_retries(0)
+            .build()
+            .unwrap();
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+        assert_eq!(client.max_retries(), 0);
+    }
+
+    #[test]
+    fn timeout_and_max_retries_read_environment_variables() {
+        // Read through a fixed lookup so parallel tests never see these values
+        let env = std::collections::HashMap::from([
+            ("OLLAMA_TIMEOUT_SECS", "12"),
+            ("OLLAMA_MAX_RETRIES", "4"),
+        ]);
+        let config = || Config::from_lookup(|name| env.get(name).map(Into::into));
+
+        let client = OllamaClientBuilder::new()
+            .base_url("http://localhost:11434")
+            .build_with_config(config())
+            .unwrap();
+        assert_eq!(client.timeout(), Duration::from_secs(12));
+        assert_eq!(client.max_retries(), 4);
+
+        // Builder values take precedence over the environment
+        let client = OllamaClientBuilder::new()
+            .base_url("http://localhost:11434")
+            .timeout(Duration::from_secs(3))
+            .max_retries(2)
+            .build_with_config(config())
+            .unwrap();
+        assert_eq!(client.timeout(), Duration::from_secs(3));
+        assert_eq!(client.max_retries(), 2);
+    }
+
+    #[test]
+    fn retry_with_backoff_respects_max_retries() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+        let result: Result<&str, OllamaError> = retry_with_backoff(1, move || {
+            attempts_clone.fetch_add(1, Ordering::SeqCst);
+            Err(OllamaError::Http { status: 503 })
+        });
+
+        assert!(result.is_err());
+        // Initial attempt + 1 retry
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn retry_with_backoff_zero_does_not_retry() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_clone = attempts.clone();
+        let result: Result<&str, OllamaError> = retry_with_backoff(0, move || {
+            attempts_clone.fetch_add(1, Ordering::SeqCst);
+            Err(OllamaError::Http { status: 503 })
+        });
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn health_check_fails_when_ollama_unreachable() {
+        // Port 9 (discard) is not an Ollama server; the connection is refused
+        let client = OllamaClientBuilder::new()
+            .base_url("http://127.0.0.1:9")
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        assert!(client.health_check().is_err());
+    }
+
     #[test]
     fn build_creates_client_with_correct_timeout_configuration() {
         let client = OllamaClientBuilder::new()
@@ -485,7 +784,7 @@ mod tests {
 
         let attempts = Arc::new(AtomicUsize::new(0));
         let attempts_clone = attempts.clone();
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             let count = attempts.fetch_add(1, Ordering::SeqCst);
             if count < 1 {
@@ -513,7 +812,7 @@ mod tests {
 
         let attempts = Arc::new(AtomicUsize::new(0));
         let attempts_clone = attempts.clone();
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             attempts.fetch_add(1, Ordering::SeqCst);
             // Always fail with retryable error
@@ -540,7 +839,7 @@ mod tests {
         let attempts_clone = attempts.clone();
         let start = Instant::now();
 
-        let _result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let _result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             let count = attempts.fetch_add(1, Ordering::SeqCst);
             if count < 2 {
@@ -569,7 +868,7 @@ mod tests {
 
         let attempts = Arc::new(AtomicUsize::new(0));
         let attempts_clone = attempts.clone();
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             attempts.fetch_add(1, Ordering::SeqCst);
             // Return 4xx error (should not retry)
@@ -588,7 +887,7 @@ mod tests {
 
         let attempts = Arc::new(AtomicUsize::new(0));
         let attempts_clone = attempts.clone();
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             let count = attempts.fetch_add(1, Ordering::SeqCst);
             if count < 1 {
@@ -666,6 +965,124 @@ mod tests {
         assert_eq!(response_text, "Generated text here");
     }
 
+    /// Serves one canned HTTP response per connection, in order, on a local port.
+    ///
+    /// Returns the base URL and a handle yielding the request lines received.
+    fn serve_responses(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut request_lines = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().expect("failed to accept");
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                request_lines.push(request_line.trim_end().to_string());
+
+                // Drain headers and body so the client sees a complete exchange
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            request_lines
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn generate_reports_model_not_found_with_available_models() {
+        let (base_url, server) = serve_responses(vec![
+            (
+                404,
+                r#"{"error": "model \"llama9\" not found, try pulling it first"}"#,
+            ),
+            (
+                200,
+                r#"{"models": [{"name": "gemma3:4b", "size": 100}, {"name": "deepseek-r1:8b", "size": 200}]}"#,
+            ),
+        ]);
+        let client = OllamaClientBuilder::new()
+            .base_url(base_url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let error = client
+            .generate("llama9", "hello")
+            .expect_err("missing model should fail");
+
+        match &error {
+            OllamaError::ModelNotFound { model, available } => {
+                assert_eq!(model, "llama9");
+                assert_eq!(available, &["deepseek-r1:8b", "gemma3:4b"]);
+            }
+            other => panic!("expected ModelNotFound, got {other:?}"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "model 'llama9' not found; available: deepseek-r1:8b, gemma3:4b — run 'ollama pull llama9'"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /api/generate"));
+        assert!(requests[1].starts_with("GET /api/tags"));
+    }
+
+    #[test]
+    fn generate_keeps_plain_404_as_http_error() {
+        let (base_url, server) = serve_responses(vec![(404, "{}")]);
+        let client = OllamaClientBuilder::new()
+            .base_url(base_url)
+            .max_retries(0)
+            .build()
+            .unwrap();
+
+        let error = client.generate("gemma3:4b", "hello").unwrap_err();
+        assert!(matches!(error, OllamaError::Http { status: 404 }));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn model_not_found_display_without_installed_models() {
+        let error = OllamaError::ModelNotFound {
+            model: "gemma3:4b".to_string(),
+            available: Vec::new(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "model 'gemma3:4b' not found; available: none — run 'ollama pull gemma3:4b'"
+        );
+        assert!(!should_retry(&error));
+    }
+
     #[test]
     fn generate_handles_http_errors_correctly() {
         // Test error handling for HTTP errors
@@ -686,7 +1103,7 @@ mod tests {
         let attempts_clone = attempts.clone();
 
         // Simulate a transient error that should be retried
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             let count = attempts.fetch_add(1, Ordering::SeqCst);
             if count < 1 {
@@ -746,7 +1163,7 @@ mod tests {
         let attempts_clone = attempts.clone();
 
         // Create a network error that should be retried
-        let result: Result<&str, OllamaError> = retry_with_backoff(move || {
+        let result: Result<&str, OllamaError> = retry_with_backoff(3, move || {
             let attempts = attempts_clone.clone();
             let count = attempts.fetch_add(1, Ordering::SeqCst);
             if count < 1 {
@@ -801,6 +1218,45 @@ mod tests {
         assert_eq!(client.model(), "");
     }
 
+    #[test]
+    fn generate_request_body_includes_only_set_options() {
+        let body = generate_request_body("m", "p", &GenOptions::default()).unwrap();
+        assert!(body.get("options").is_none());
+
+        let options = GenOptions {
+            temperature: Some(0.5),
+            seed: Some(7),
+            ..Default::default()
+        };
+        let body = generate_request_body("m", "p", &options).unwrap();
+        assert_eq!(body["model"], "m");
+        assert_eq!(body["stream"], false);
+        assert_eq!(
+            body["options"],
+            serde_json::json!({"temperature": 0.5, "seed": 7})
+        );
+    }
+
+    #[test]
+    fn generate_with_options_defaults_to_generate() {
+        struct EchoClient;
+
+        impl OllamaClientTrait for EchoClient {
+            fn generate(&self, model: &str, prompt: &str) -> Result<String, OllamaError> {
+                Ok(format!("{model}:{prompt}"))
+            }
+        }
+
+        let options = GenOptions {
+            temperature: Some(0.9),
+            ..Default::default()
+        };
+        let response = EchoClient
+            .generate_with_options("m", "hello", options)
+            .unwrap();
+        assert_eq!(response, "m:hello");
+    }
+
     #[test]
     fn model_method_sets_custom_model_and_takes_precedence_over_env_var() {
         // Set environment variable
@@ -0,0 +1,201 @@
+//! Tolerant JSON extraction for LLM responses.
+//!
+//! Models often wrap JSON in Markdown fences or prose, or leave trailing commas.
+//! These helpers find the first balanced JSON block that parses (repairing
+//! trailing commas if needed), and `generate_json` re-prompts once when a
+//! response contains no usable JSON.
+
+use super::{OllamaClientTrait, OllamaError};
+
+/// Appended to the original prompt for the single corrective retry.
+const JSON_REPAIR_INSTRUCTION: &str = "\n\nYour previous response could not be parsed as JSON. \
+Return only valid JSON, with no explanatory text, no Markdown fences, and no trailing commas.";
+
+/// Extracts the first parseable JSON array (`[...]`) from a model response.
+///
+/// Returns the JSON text, with trailing commas removed if that was needed to
+/// parse it, or `None` if no block parses.
+pub(crate) fn extract_json_array(raw: &str) -> Option<String> {
+    extract_json_block(raw, '[', ']')
+}
+
+/// Extracts the first parseable JSON object (`{...}`) from a model response.
+///
+/// See [`extract_json_array`] for the repair rules.
+pub(crate) fn extract_json_object(raw: &str) -> Option<String> {
+    extract_json_block(raw, '{', '}')
+}
+
+/// Generates a response and extracts JSON from it, re-prompting once on failure.
+///
+/// The retry repeats `prompt` with an instruction to return only valid JSON.
+/// Returns `Ok(None)` if neither response contains usable JSON.
+///
+/// # Errors
+///
+/// Returns `OllamaError` if either LLM request fails.
+pub(crate) fn generate_json(
+    client: &dyn OllamaClientTrait,
+    model: &str,
+    prompt: &str,
+    extract: fn(&str) -> Option<String>,
+) -> Result<Option<String>, OllamaError> {
+    let response = client.generate(model, prompt)?;
+    if let Some(json) = extract(&response) {
+        return Ok(Some(json));
+    }
+
+    let retry_prompt = format!("{prompt}{JSON_REPAIR_INSTRUCTION}");
+    let response = client.generate(model, &retry_prompt)?;
+    Ok(extract(&response))
+}
+
+/// Tries each balanced `open`...`close` block in order, returning the first that parses.
+fn extract_json_block(raw: &str, open: char, close: char) -> Option<String> {
+    raw.match_indices(open).find_map(|(start, _)| {
+        let end = start + matching_close(&raw[start..], open, close)?;
+        let candidate = &raw[start..=end];
+
+        if is_valid_json(candidate) {
+            return Some(candidate.to_string());
+        }
+        let repaired = strip_trailing_commas(candidate);
+        is_valid_json(&repaired).then_some(repaired)
+    })
+}
+
+fn is_valid_json(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text).is_ok()
+}
+
+/// Returns the byte offset of the `close` matching the `open` at the start of `text`.
+///
+/// Brackets inside JSON strings are ignored.
+fn matching_close(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, ch) in text.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if ch == '"' {
+            in_string = true;
+        } else if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+
+    None
+}
+
+/// Removes commas that directly precede a closing `]` or `}` outside strings.
+fn strip_trailing_commas(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if ch == '"' {
+            in_string = true;
+        } else if ch == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some(']' | '}')) {
+                continue;
+            }
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn extract_json_array_handles_fences_and_prose_with_brackets() {
+        let raw = "Sure [see below]:\n```json\n[{\"a\": \"x]\"}]\n```\nDone [ok].";
+        assert_eq!(
+            extract_json_array(raw).as_deref(),
+            Some("[{\"a\": \"x]\"}]")
+        );
+    }
+
+    #[test]
+    fn extract_json_repairs_trailing_commas() {
+        let raw = "[{\"a\": 1, \"b\": \"x,]\",}, ]";
+        let json = extract_json_array(raw).expect("trailing commas should be repaired");
+        assert_eq!(json, "[{\"a\": 1, \"b\": \"x,]\"} ]");
+
+        let json = extract_json_object("{\"rust\": 0.9,}").expect("object should be repaired");
+        assert_eq!(json, "{\"rust\": 0.9}");
+    }
+
+    #[test]
+    fn extract_json_returns_none_without_valid_block() {
+        assert_eq!(extract_json_array("no brackets"), None);
+        assert_eq!(extract_json_array("[unclosed"), None);
+        assert_eq!(extract_json_object("{not json}"), None);
+    }
+
+    struct ScriptedClient {
+        responses: Mutex<Vec<&'static str>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl OllamaClientTrait for ScriptedClient {
+        fn generate(&self, _model: &str, prompt: &str) -> Result<String, OllamaError> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            Ok(self.responses.lock().unwrap().remove(0).to_string())
+        }
+    }
+
+    #[test]
+    fn generate_json_reprompts_once_when_response_has_no_json() {
+        let client = ScriptedClient {
+            responses: Mutex::new(vec!["I think rust is a language.", "[1, 2]"]),
+            prompts: Mutex::new(Vec::new()),
+        };
+
+        let json = generate_json(&client, "m", "PROMPT", extract_json_array).unwrap();
+        assert_eq!(json.as_deref(), Some("[1, 2]"));
+
+        let prompts = client.prompts.lock().unwrap();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[1].starts_with("PROMPT"));
+        assert!(prompts[1].contains("Return only valid JSON"));
+    }
+
+    #[test]
+    fn generate_json_gives_up_after_one_retry() {
+        let client = ScriptedClient {
+            responses: Mutex::new(vec!["nope", "still nope"]),
+            prompts: Mutex::new(Vec::new()),
+        };
+
+        let json = generate_json(&client, "m", "PROMPT", extract_json_array).unwrap();
+        assert_eq!(json, None);
+        assert_eq!(client.prompts.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,123 @@
+//! Progress reporting for long-running batch operations.
+//!
+//! Batch commands such as `retag --all` make one slow LLM call per note. The
+//! [`Progress`] reporter prints a `[i/N] ...` line as each item starts and a
+//! summary at the end. It writes to stderr by default so `--json` output on
+//! stdout stays machine-readable.
+
+use std::fmt::Display;
+use std::io::{self, Write};
+
+/// Reports per-item progress and a final summary for a batch of known size.
+///
+/// Write errors are ignored: progress output must never fail the operation
+/// it describes.
+///
+/// # Examples
+///
+/// ```
+/// use cons::progress::Progress;
+///
+/// let mut output = Vec::new();
+/// let mut progress = Progress::new(&mut output, 2);
+/// progress.step("processing note 1");
+/// progress.step("processing note 2");
+/// progress.fail("note 2 failed: timeout");
+/// progress.finish();
+///
+/// let text = String::from_utf8(output).unwrap();
+/// assert!(text.starts_with("[1/2] processing note 1...\n"));
+/// assert!(text.ends_with("Processed 2 items (1 failed)\n"));
+/// ```
+pub struct Progress<W: Write> {
+    writer: W,
+    total: usize,
+    current: usize,
+    failed: usize,
+}
+
+impl Progress<io::Stderr> {
+    /// Creates a reporter that writes to stderr.
+    pub fn stderr(total: usize) -> Self {
+        Self::new(io::stderr(), total)
+    }
+}
+
+impl<W: Write> Progress<W> {
+    /// Creates a reporter for `total` items that writes to `writer`.
+    pub fn new(writer: W, total: usize) -> Self {
+        Self {
+            writer,
+            total,
+            current: 0,
+            failed: 0,
+        }
+    }
+
+    /// Marks the start of the next item, e.g. `[3/10] processing note 42...`.
+    pub fn step(&mut self, description: impl Display) {
+        self.current += 1;
+        let _ = writeln!(
+            self.writer,
+            "[{}/{}] {}...",
+            self.current, self.total, description
+        );
+    }
+
+    /// Records that the current item failed, printing the error.
+    pub fn fail(&mut self, error: impl Display) {
+        self.failed += 1;
+        let _ = writeln!(self.writer, "  {error}");
+    }
+
+    /// Returns the number of items recorded as failed so far.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Prints the final summary, e.g. `Processed 10 items (2 failed)`.
+    pub fn finish(mut self) {
+        let noun = if self.current == 1 { "item" } else { "items" };
+        let _ = if self.failed == 0 {
+            writeln!(self.writer, "Processed {} {noun}", self.current)
+        } else {
+            writeln!(
+                self.writer,
+                "Processed {} {noun} ({} failed)",
+                self.current, self.failed
+            )
+        };
+        let _ = self.writer.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_numbers_items_against_total() {
+        let mut output = Vec::new();
+        let mut progress = Progress::new(&mut output, 3);
+        progress.step("processing note 7");
+        progress.step("processing note 9");
+
+        let text = String::from_utf8(output).unwrap();
+        assert_eq!(
+            text,
+            "[1/3] processing note 7...\n[2/3] processing note 9...\n"
+        );
+    }
+
+    #[test]
+    fn finish_summarizes_without_failures() {
+        let mut output = Vec::new();
+        let mut progress = Progress::new(&mut output, 1);
+        progress.step("processing note 1");
+        assert_eq!(progress.failed(), 0);
+        progress.finish();
+
+        let text = String::from_utf8(output).unwrap();
+        assert!(text.ends_with("Processed 1 item\n"));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    AliasInfo, Database, Note, NoteBuilder, NoteId, TagAssignment, TagId, TagSource,
-    autotagger::TagNormalizer,
+    AliasInfo, Database, EdgeInfo, Note, NoteBuilder, NoteId, TagAssignment, TagId, TagSource,
+    autotagger::TagNormalizer, config::Config, content_hash, spreading_activation::ActivationPath,
 };
 use anyhow::Result;
 use rusqlite::OptionalExtension;
@@ -36,11 +36,21 @@ pub struct SearchResult {
     /// Normalized relevance score (0.0-1.0, higher = more relevant).
     /// Derived from BM25: `1.0 / (1.0 + raw_score.abs())`
     pub relevance_score: f64,
+    /// Excerpt of the note body around the first match, with matched terms
+    /// wrapped in `[` and `]`. `None` when the query did not match the body
+    /// (e.g., the note matched only by tag) or for graph search results.
+    pub snippet: Option<String>,
+    /// The strongest activation paths from the query's seed tags to this note's
+    /// tags. Only set by [`NoteService::graph_search_explained`].
+    pub explanation: Option<Vec<ActivationPath>>,
 }
 
+/// Most activation paths kept per result by [`NoteService::graph_search_explained`].
+const MAX_EXPLANATION_PATHS: usize = 3;
+
 /// Configuration for dual-channel search combining FTS and graph-based retrieval.
 ///
-/// Parsed from environment variables at method call time with fallback defaults.
+/// Built from the [`Config`] a `NoteService` holds, or from the environment via `from_env`.
 #[derive(Debug, Clone)]
 pub struct DualSearchConfig {
     /// Weight applied to FTS channel scores (default 1.0).
@@ -53,22 +63,31 @@ pub struct DualSearchConfig {
     pub min_avg_activation: f64,
     /// Minimum number of activated tags required for graph channel (default 2).
     pub min_activated_tags: usize,
+    /// Maximum spreading-activation depth for the graph channel (default 3, range 1-6).
+    pub max_hops: usize,
 }
 
 impl Default for DualSearchConfig {
     fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+impl From<&Config> for DualSearchConfig {
+    fn from(config: &Config) -> Self {
         Self {
-            fts_weight: 1.0,
-            graph_weight: 1.0,
-            intersection_bonus: 0.5,
-            min_avg_activation: 0.1,
-            min_activated_tags: 2,
+            fts_weight: config.fts_weight,
+            graph_weight: config.graph_weight,
+            intersection_bonus: config.intersection_bonus,
+            min_avg_activation: config.min_avg_activation,
+            min_activated_tags: config.min_activated_tags,
+            max_hops: config.max_hops,
         }
     }
 }
 
 impl DualSearchConfig {
-    /// Parses configuration from environment variables.
+    /// Reads configuration from environment variables via [`Config::load`].
     ///
     /// Falls back to defaults when env vars not set or invalid.
     ///
@@ -79,6 +98,11 @@ impl DualSearchConfig {
     /// - `CONS_INTERSECTION_BONUS` (f64, default 0.5): Bonus when found by both channels
     /// - `CONS_MIN_AVG_ACTIVATION` (f64, default 0.1): Minimum average activation threshold
     /// - `CONS_MIN_ACTIVATED_TAGS` (usize, default 2): Minimum activated tags required
+    /// - `CONS_MAX_HOPS` (usize, default 3, clamped to 1-6): Graph traversal depth.
+    ///   Higher values trade latency for recall.
+    ///
+    /// Edge-type weights and the verified-edge boost for the graph channel are read by
+    /// [`SpreadingActivationConfig`](crate::spreading_activation::SpreadingActivationConfig).
     ///
     /// # Examples
     ///
@@ -89,44 +113,13 @@ impl DualSearchConfig {
     /// assert_eq!(config.fts_weight, 1.0); // default when env var not set
     /// ```
     pub fn from_env() -> Self {
-        let fts_weight = std::env::var("CONS_FTS_WEIGHT")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(1.0);
-
-        let graph_weight = std::env::var("CONS_GRAPH_WEIGHT")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(1.0);
-
-        let intersection_bonus = std::env::var("CONS_INTERSECTION_BONUS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0.5);
-
-        let min_avg_activation = std::env::var("CONS_MIN_AVG_ACTIVATION")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0.1);
-
-        let min_activated_tags = std::env::var("CONS_MIN_ACTIVATED_TAGS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(2);
-
-        Self {
-            fts_weight,
-            graph_weight,
-            intersection_bonus,
-            min_avg_activation,
-            min_activated_tags,
-        }
+        Self::from(&Config::load())
     }
 }
 
 /// Configuration for query expansion with broader concepts.
 ///
-/// Parsed from environment variables at method call time with fallback defaults.
+/// Built from the [`Config`] a `NoteService` holds, or from the environment via `from_env`.
 #[derive(Debug, Clone)]
 pub struct QueryExpansionConfig {
     /// Maximum depth for broader concept traversal (default 1).
@@ -139,16 +132,22 @@ pub struct QueryExpansionConfig {
 
 impl Default for QueryExpansionConfig {
     fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+impl From<&Config> for QueryExpansionConfig {
+    fn from(config: &Config) -> Self {
         Self {
-            expansion_depth: 1,
-            max_expansion_terms: 10,
-            broader_min_confidence: 0.7,
+            expansion_depth: config.expansion_depth,
+            max_expansion_terms: config.max_expansion_terms,
+            broader_min_confidence: config.broader_min_confidence,
         }
     }
 }
 
 impl QueryExpansionConfig {
-    /// Parses configuration from environment variables.
+    /// Reads configuration from environment variables via [`Config::load`].
     ///
     /// Falls back to defaults when env vars not set or invalid.
     ///
@@ -167,26 +166,7 @@ impl QueryExpansionConfig {
     /// assert_eq!(config.expansion_depth, 1); // default when env var not set
     /// ```
     pub fn from_env() -> Self {
-        let expansion_depth = std::env::var("CONS_EXPANSION_DEPTH")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(1);
-
-        let max_expansion_terms = std::env::var("CONS_MAX_EXPANSION_TERMS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(10);
-
-        let broader_min_confidence = std::env::var("CONS_BROADER_MIN_CONFIDENCE")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0.7);
-
-        Self {
-            expansion_depth,
-            max_expansion_terms,
-            broader_min_confidence,
-        }
+        Self::from(&Config::load())
     }
 }
 
@@ -205,6 +185,8 @@ pub struct DualSearchResult {
     pub graph_score: Option<f64>,
     /// True if the note was found by both FTS and graph channels.
     pub found_by_both: bool,
+    /// Highlighted body excerpt from the FTS channel (see [`SearchResult::snippet`]).
+    pub snippet: Option<String>,
 }
 
 /// Metadata about dual-channel search execution.
@@ -247,6 +229,311 @@ pub fn should_expand_broader(query: &str) -> bool {
     term_count < 3
 }
 
+/// Extracts the phrase from a query wrapped in double quotes.
+///
+/// Returns the trimmed text between the outer quotes, or `None` if the query
+/// is not a quoted phrase. Embedded quotes are returned as-is and escaped later
+/// when the FTS5 phrase is built.
+///
+/// # Examples
+///
+/// ```
+/// use cons::service::parse_phrase_query;
+///
+/// assert_eq!(parse_phrase_query("\"rust programming\""), Some("rust programming"));
+/// assert_eq!(parse_phrase_query("rust programming"), None);
+/// assert_eq!(parse_phrase_query("\""), None);
+/// ```
+pub fn parse_phrase_query(query: &str) -> Option<&str> {
+    let query = query.trim();
+    if query.len() >= 2 && query.starts_with('"') && query.ends_with('"') {
+        Some(query[1..query.len() - 1].trim())
+    } else {
+        None
+    }
+}
+
+/// Builds a ` LIMIT ? OFFSET ?` clause, pushing the bound values onto `params`.
+///
+/// SQLite only accepts OFFSET after a LIMIT, so `LIMIT -1` (no limit) is used
+/// when only an offset is given.
+fn limit_offset_clause(
+    limit: Option<usize>,
+    offset: Option<usize>,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+) -> String {
+    let offset = offset.filter(|&offset| offset > 0);
+    if limit.is_none() && offset.is_none() {
+        return String::new();
+    }
+
+    params.push(Box::new(limit.map_or(-1, |limit| limit as i64)));
+    match offset {
+        Some(offset) => {
+            params.push(Box::new(offset as i64));
+            " LIMIT ? OFFSET ?".to_string()
+        }
+        None => " LIMIT ?".to_string(),
+    }
+}
+
+/// Builds a condition keeping rows whose `column` is a note carrying every tag in `tag_names`.
+///
+/// `tag_names` must already be canonical (see [`NoteService::canonical_tag_names`]).
+/// Pushes the bound values onto `params` in placeholder order.
+fn all_tags_condition(
+    column: &str,
+    tag_names: &[String],
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+) -> String {
+    let placeholders = vec!["?"; tag_names.len()].join(", ");
+    for tag_name in tag_names {
+        params.push(Box::new(tag_name.clone()));
+    }
+    params.push(Box::new(tag_names.len() as i64));
+
+    // Notes that have ALL specified tags (AND logic via HAVING COUNT)
+    format!(
+        "{column} IN (
+             SELECT nt.note_id
+             FROM note_tags nt
+             JOIN tags t ON nt.tag_id = t.id
+             WHERE t.name IN ({placeholders}) COLLATE NOCASE
+             GROUP BY nt.note_id
+             HAVING COUNT(DISTINCT t.id) = ?
+         )"
+    )
+}
+
+/// Builds the `AND ...` conditions for search filters on a `notes_fts` query.
+///
+/// Tag names in `filters` must already be canonical. Pushes the bound values
+/// onto `params` in placeholder order.
+fn fts_filter_clause(
+    filters: &SearchFilters,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+) -> String {
+    let mut filter_clause = String::new();
+
+    // Constrains the MATCH itself, so BM25 ordering of the remaining rows is unchanged
+    match filters.tags.as_deref() {
+        Some([]) => filter_clause.push_str(" AND 0"),
+        Some(tag_names) => {
+            filter_clause.push_str(" AND ");
+            filter_clause.push_str(&all_tags_condition("note_id", tag_names, params));
+        }
+        None => {}
+    }
+
+    if let Some(condition) = filters.source.sql_condition("notes_fts.note_id") {
+        filter_clause.push_str(" AND ");
+        filter_clause.push_str(&condition);
+    }
+
+    if let Some(created_after) = filters.created_after {
+        filter_clause.push_str(" AND note_id IN (SELECT id FROM notes WHERE created_at >= ?)");
+        params.push(Box::new(created_after));
+    }
+
+    if let Some(created_before) = filters.created_before {
+        filter_clause.push_str(" AND note_id IN (SELECT id FROM notes WHERE created_at <= ?)");
+        params.push(Box::new(created_before));
+    }
+
+    filter_clause
+}
+
+/// Note columns read by [`note_from_row`], in order.
+const NOTE_COLUMNS: &str = "id, content, created_at, updated_at, content_enhanced, \
+     enhanced_at, enhancement_model, enhancement_confidence, source, enhancement_prompt";
+
+/// Tag assignment columns read by [`tag_assignment_from_row`], in order.
+///
+/// Expects `note_tags` aliased as `nt` joined with `tags` aliased as `t`.
+const TAG_ASSIGNMENT_COLUMNS: &str = "nt.tag_id, t.name, nt.confidence, nt.source, \
+     nt.created_at, nt.model_version, nt.verified";
+
+/// Builds a [`Note`] from a row selected with [`NOTE_COLUMNS`].
+fn note_from_row(row: &rusqlite::Row<'_>, tags: Vec<TagAssignment>) -> Result<Note> {
+    let created_at: i64 = row.get(2)?;
+    let updated_at: i64 = row.get(3)?;
+
+    let mut builder = NoteBuilder::new()
+        .id(NoteId::new(row.get(0)?))
+        .content(row.get::<_, String>(1)?)
+        .created_at(OffsetDateTime::from_unix_timestamp(created_at)?)
+        .updated_at(OffsetDateTime::from_unix_timestamp(updated_at)?)
+        .tags(tags);
+
+    // Add enhancement fields if present
+    if let Some(enhanced_content) = row.get::<_, Option<String>>(4)? {
+        builder = builder.content_enhanced(enhanced_content);
+    }
+    if let Some(enhanced_timestamp) = row.get::<_, Option<i64>>(5)? {
+        builder = builder.enhanced_at(OffsetDateTime::from_unix_timestamp(enhanced_timestamp)?);
+    }
+    if let Some(model) = row.get::<_, Option<String>>(6)? {
+        builder = builder.enhancement_model(model);
+    }
+    if let Some(confidence) = row.get::<_, Option<f64>>(7)? {
+        builder = builder.enhancement_confidence(confidence);
+    }
+    // Notes created before provenance was tracked have no source
+    if let Some(source) = row.get::<_, Option<String>>(8)? {
+        builder = builder.source(source);
+    }
+    if let Some(prompt) = row.get::<_, Option<String>>(9)? {
+        builder = builder.enhancement_prompt(prompt);
+    }
+
+    Ok(builder.build())
+}
+
+/// Maps a [`TagSource`] to its `note_tags` (source, confidence, model_version) values.
+///
+/// Imported tags store their external source name in `model_version`.
+fn tag_source_columns(source: &TagSource) -> (&'static str, f64, Option<&str>) {
+    match source {
+        TagSource::User => ("user", 1.0, None),
+        TagSource::Llm { model, confidence } => {
+            // Convert u8 (0-100) to f64 (0.0-1.0)
+            ("llm", f64::from(*confidence) / 100.0, Some(model.as_str()))
+        }
+        TagSource::Import { source_name } => ("import", 1.0, Some(source_name.as_str())),
+    }
+}
+
+/// Turns a failed FTS5 query into an actionable error.
+///
+/// A missing or corrupt `notes_fts` table surfaces as a raw SQLite error; point
+/// the user at `cons maintenance rebuild-fts` instead. Other errors (such as FTS5
+/// query syntax errors) are passed through unchanged.
+fn fts_error(error: rusqlite::Error) -> anyhow::Error {
+    let message = error.to_string();
+    let index_broken = message.contains("no such table: notes_fts")
+        || message.contains("malformed")
+        || message.contains("corrupt")
+        || message.contains("vtable constructor failed");
+
+    if index_broken {
+        anyhow::Error::new(error).context(
+            "Search index is missing or corrupt; run 'cons maintenance rebuild-fts' to rebuild it",
+        )
+    } else {
+        error.into()
+    }
+}
+
+/// Picks the strongest activation paths ending at one of `note`'s tags.
+fn note_activation_paths(
+    note: &Note,
+    paths: &std::collections::HashMap<TagId, Vec<ActivationPath>>,
+) -> Vec<ActivationPath> {
+    let mut note_paths: Vec<ActivationPath> = note
+        .tags()
+        .iter()
+        .filter_map(|tag| paths.get(&tag.tag_id()))
+        .flatten()
+        .cloned()
+        .collect();
+    note_paths.sort_by(|a, b| b.activation().total_cmp(&a.activation()));
+    note_paths.truncate(MAX_EXPLANATION_PATHS);
+    note_paths
+}
+
+/// Splits a stored `tag_presets.tags` value back into tag names.
+fn split_preset_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Builds a [`TagAssignment`] from a row selected with [`TAG_ASSIGNMENT_COLUMNS`].
+fn tag_assignment_from_row(row: &rusqlite::Row<'_>) -> Result<TagAssignment> {
+    tag_assignment_from_row_at(row, 0)
+}
+
+/// Like [`tag_assignment_from_row`], for rows where the tag columns start at `offset`.
+fn tag_assignment_from_row_at(row: &rusqlite::Row<'_>, offset: usize) -> Result<TagAssignment> {
+    let tag_id = TagId::new(row.get(offset)?);
+    let tag_name: String = row.get(offset + 1)?;
+    let confidence: f64 = row.get(offset + 2)?;
+    let source: String = row.get(offset + 3)?;
+    let created_at = OffsetDateTime::from_unix_timestamp(row.get(offset + 4)?)?;
+    let model_version: Option<String> = row.get(offset + 5)?;
+    let verified: Option<bool> = row.get(offset + 6)?;
+
+    let mut tag_assignment = match source.as_str() {
+        "user" => TagAssignment::user(tag_id, tag_name, created_at),
+        "import" => {
+            // Imported tags keep their external source name in model_version
+            let source_name = model_version.unwrap_or_else(|| "unknown".to_string());
+            TagAssignment::import(tag_id, tag_name, source_name, created_at)
+        }
+        _ => {
+            // LLM source - convert confidence from f64 (0.0-1.0) to u8 (0-100)
+            let confidence_u8 = (confidence * 100.0).round() as u8;
+            let model = model_version.unwrap_or_else(|| "unknown".to_string());
+
+            TagAssignment::llm(tag_id, tag_name, model, confidence_u8, created_at)
+        }
+    };
+    if verified.unwrap_or(false) {
+        tag_assignment.verify();
+    }
+
+    Ok(tag_assignment)
+}
+
+/// A search query split into positive term groups and excluded terms.
+#[derive(Debug, PartialEq)]
+struct ParsedQuery<'a> {
+    /// Groups joined with AND; terms within a group are joined with OR
+    groups: Vec<Vec<&'a str>>,
+    /// Terms prefixed with `-` that matching notes must not contain
+    excluded: Vec<&'a str>,
+}
+
+/// Parses boolean operators out of a search query.
+///
+/// A leading `-` excludes a term, and an uppercase `OR` between two terms puts
+/// them in the same group. All other terms start a new group (implicit AND).
+/// An `OR` that does not sit between two positive terms is treated as a term.
+fn parse_search_query(query: &str) -> ParsedQuery<'_> {
+    let tokens: Vec<&str> = query.split_whitespace().collect();
+    let mut groups: Vec<Vec<&str>> = Vec::new();
+    let mut excluded = Vec::new();
+    let mut join_with_previous = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(term) = token.strip_prefix('-') {
+            if !term.is_empty() {
+                excluded.push(term);
+            }
+            continue;
+        }
+
+        let is_operator = *token == "OR"
+            && !groups.is_empty()
+            && tokens
+                .get(i + 1)
+                .is_some_and(|next| *next != "OR" && !next.starts_with('-'));
+        if is_operator {
+            join_with_previous = true;
+            continue;
+        }
+
+        match groups.last_mut() {
+            Some(group) if join_with_previous => group.push(token),
+            _ => groups.push(vec![token]),
+        }
+        join_with_previous = false;
+    }
+
+    ParsedQuery { groups, excluded }
+}
+
 /// Service layer providing note management operations.
 ///
 /// NoteService owns a Database instance and provides high-level business logic
@@ -266,6 +553,7 @@ pub fn should_expand_broader(query: &str) -> bool {
 /// ```
 pub struct NoteService {
     db: Database,
+    config: Config,
 }
 
 impl NoteService {
@@ -274,6 +562,9 @@ impl NoteService {
     /// Takes ownership of the database instance. The service becomes the sole
     /// owner and manages all database operations through its methods.
     ///
+    /// Search tunables are read from the environment once, here, via
+    /// [`Config::load`]; use [`with_config`](Self::with_config) to supply them.
+    ///
     /// # Examples
     ///
     /// ```
@@ -286,7 +577,35 @@ impl NoteService {
     /// # }
     /// ```
     pub fn new(db: Database) -> Self {
-        Self { db }
+        Self {
+            db,
+            config: Config::load(),
+        }
+    }
+
+    /// Replaces the configuration used by search, instead of the environment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Config, Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut config = Config::default();
+    /// config.max_hops = 1;
+    /// let service = NoteService::new(Database::in_memory()?).with_config(config);
+    /// assert_eq!(service.config().max_hops, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the configuration used by search.
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 
     /// Returns a reference to the underlying database.
@@ -323,6 +642,34 @@ impl NoteService {
     /// # }
     /// ```
     pub fn create_note(&self, content: &str, tags: Option<&[&str]>) -> Result<Note> {
+        self.create_note_with_source(content, tags, "cli")
+    }
+
+    /// Creates a new note, recording how it was captured.
+    ///
+    /// Behaves like [`create_note`](Self::create_note), which records `cli`.
+    /// Front ends pass their own source (e.g. `tui`) so notes can later be
+    /// audited by origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    ///
+    /// let note = service.create_note_with_source("Captured in the TUI", None, "tui")?;
+    /// assert_eq!(note.source(), "tui");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_note_with_source(
+        &self,
+        content: &str,
+        tags: Option<&[&str]>,
+        source: &str,
+    ) -> Result<Note> {
         use std::collections::HashSet;
 
         let conn = self.db.connection();
@@ -334,12 +681,14 @@ impl NoteService {
         let result: Result<Note> = (|| {
             // Insert note with current timestamp
             conn.execute(
-                "INSERT INTO notes (content, created_at, updated_at) VALUES (?1, ?2, ?3)",
-                (content, now, now),
+                "INSERT INTO notes (content, created_at, updated_at, source, content_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                (content, now, now, source, content_hash(content)),
             )?;
 
             // Get the ID of the just-inserted note
             let note_id = conn.last_insert_rowid();
+            self.replace_links(NoteId::new(note_id), content)?;
 
             // Handle tags if provided
             let mut tag_assignments = Vec::new();
@@ -383,6 +732,7 @@ impl NoteService {
                 .created_at(OffsetDateTime::from_unix_timestamp(now)?)
                 .updated_at(OffsetDateTime::from_unix_timestamp(now)?)
                 .tags(tag_assignments)
+                .source(source)
                 .build();
 
             Ok(note)
@@ -400,6 +750,121 @@ impl NoteService {
         }
     }
 
+    /// Imports notes (e.g., from a JSON export) in a single transaction.
+    ///
+    /// Each note is inserted with a new ID but keeps its original timestamps,
+    /// enhancement fields, and tag assignments (source, confidence, model and
+    /// verification status). Imported notes record `import` as their source.
+    /// If any record fails, nothing is imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `notes` - Notes to import; their IDs and tag IDs are ignored
+    /// * `skip_duplicates` - Skip notes whose content already exists verbatim
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a note has empty content or a database operation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let source = NoteService::new(Database::in_memory()?);
+    /// let note = source.create_note("Learning Rust", Some(&["rust"]))?;
+    ///
+    /// let target = NoteService::new(Database::in_memory()?);
+    /// let summary = target.import_notes(&[note.clone(), note], true)?;
+    /// assert_eq!(summary.imported.len(), 1);
+    /// assert_eq!(summary.skipped, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn import_notes(&self, notes: &[Note], skip_duplicates: bool) -> Result<ImportSummary> {
+        let conn = self.db.connection();
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<ImportSummary> = (|| {
+            let mut summary = ImportSummary::default();
+
+            for (index, note) in notes.iter().enumerate() {
+                if note.content().trim().is_empty() {
+                    anyhow::bail!("Note content cannot be empty (record {})", index + 1);
+                }
+
+                let hash = content_hash(note.content());
+                if skip_duplicates {
+                    let exists: bool = conn.query_row(
+                        "SELECT EXISTS(SELECT 1 FROM notes WHERE content_hash = ?1 AND content = ?2)",
+                        [&hash, note.content()],
+                        |row| row.get(0),
+                    )?;
+                    if exists {
+                        summary.skipped += 1;
+                        continue;
+                    }
+                }
+
+                conn.execute(
+                    "INSERT INTO notes (content, created_at, updated_at, content_enhanced,
+                                        enhanced_at, enhancement_model, enhancement_confidence,
+                                        enhancement_prompt, source, content_hash)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'import', ?9)",
+                    rusqlite::params![
+                        note.content(),
+                        note.created_at().unix_timestamp(),
+                        note.updated_at().unix_timestamp(),
+                        note.content_enhanced(),
+                        note.enhanced_at().map(|t| t.unix_timestamp()),
+                        note.enhancement_model(),
+                        note.enhancement_confidence(),
+                        note.enhancement_prompt(),
+                        hash,
+                    ],
+                )?;
+                let note_id = conn.last_insert_rowid();
+
+                for tag in note.tags() {
+                    let tag_id = self.get_or_create_tag(tag.name())?;
+                    let (source_str, confidence, model_version) = tag_source_columns(tag.source());
+
+                    conn.execute(
+                        "INSERT OR IGNORE INTO note_tags
+                         (note_id, tag_id, confidence, source, created_at, verified, model_version)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        rusqlite::params![
+                            note_id,
+                            tag_id.get(),
+                            confidence,
+                            source_str,
+                            tag.created_at().unix_timestamp(),
+                            tag.verified(),
+                            model_version,
+                        ],
+                    )?;
+                }
+
+                summary.imported.push(NoteId::new(note_id));
+            }
+
+            Ok(summary)
+        })();
+
+        match result {
+            Ok(summary) => {
+                conn.execute("COMMIT", [])?;
+                Ok(summary)
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
     /// Retrieves a note by its ID.
     ///
     /// Returns `None` if no note exists with the given ID. This is not
@@ -431,129 +896,161 @@ impl NoteService {
     pub fn get_note(&self, id: NoteId) -> Result<Option<Note>> {
         let conn = self.db.connection();
 
-        let mut stmt = conn.prepare(
-            "SELECT id, content, created_at, updated_at, content_enhanced, enhanced_at, enhancement_model, enhancement_confidence
-             FROM notes WHERE id = ?1"
-        )?;
+        let mut stmt = conn.prepare(&format!("SELECT {NOTE_COLUMNS} FROM notes WHERE id = ?1"))?;
+        let mut rows = stmt.query([id.get()])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
 
-        let result = stmt.query_row([id.get()], |row| {
-            let id: i64 = row.get(0)?;
-            let content: String = row.get(1)?;
-            let created_at: i64 = row.get(2)?;
-            let updated_at: i64 = row.get(3)?;
-            let content_enhanced: Option<String> = row.get(4)?;
-            let enhanced_at: Option<i64> = row.get(5)?;
-            let enhancement_model: Option<String> = row.get(6)?;
-            let enhancement_confidence: Option<f64> = row.get(7)?;
+        // Load tag assignments for this note (with tag names)
+        let mut tag_stmt = conn.prepare(&format!(
+            "SELECT {TAG_ASSIGNMENT_COLUMNS}
+             FROM note_tags nt
+             JOIN tags t ON nt.tag_id = t.id
+             WHERE nt.note_id = ?1
+             ORDER BY nt.created_at"
+        ))?;
+        let mut tag_rows = tag_stmt.query([id.get()])?;
+
+        let mut tag_assignments = Vec::new();
+        while let Some(tag_row) = tag_rows.next()? {
+            tag_assignments.push(tag_assignment_from_row(tag_row)?);
+        }
 
-            Ok((
-                id,
-                content,
-                created_at,
-                updated_at,
-                content_enhanced,
-                enhanced_at,
-                enhancement_model,
-                enhancement_confidence,
-            ))
-        });
+        Ok(Some(note_from_row(row, tag_assignments)?))
+    }
 
-        match result {
-            Ok((
-                id,
-                content,
-                created_at,
-                updated_at,
-                content_enhanced,
-                enhanced_at,
-                enhancement_model,
-                enhancement_confidence,
-            )) => {
-                // Load tag assignments for this note (with tag names)
-                let mut tag_stmt = conn.prepare(
-                    "SELECT nt.tag_id, t.name, nt.confidence, nt.source, nt.created_at, nt.model_version
-                     FROM note_tags nt
-                     JOIN tags t ON nt.tag_id = t.id
-                     WHERE nt.note_id = ?1
-                     ORDER BY nt.created_at",
-                )?;
+    /// Finds an existing note whose content matches `content` exactly.
+    ///
+    /// Both sides are compared with surrounding whitespace trimmed, so a pasted
+    /// snippet with a trailing newline still matches. The lookup goes through
+    /// [`find_note_by_hash`](Self::find_note_by_hash). When several notes
+    /// match, the oldest (lowest ID) is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("Learning Rust", None)?;
+    ///
+    /// let found = service.find_note_by_content("  Learning Rust\n")?;
+    /// assert_eq!(found.map(|n| n.id()), Some(note.id()));
+    /// assert!(service.find_note_by_content("Learning Go")?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_note_by_content(&self, content: &str) -> Result<Option<Note>> {
+        self.find_note_by_hash(&content_hash(content))
+    }
 
-                let tag_rows = tag_stmt.query_map([id], |row| {
-                    let tag_id: i64 = row.get(0)?;
-                    let tag_name: String = row.get(1)?;
-                    let confidence: f64 = row.get(2)?;
-                    let source: String = row.get(3)?;
-                    let tag_created_at: i64 = row.get(4)?;
-                    let model_version: Option<String> = row.get(5)?;
+    /// Finds the oldest note whose [`content_hash`] equals `hash`.
+    ///
+    /// The lookup uses the `idx_notes_content_hash` index, so it stays fast on
+    /// large databases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, content_hash};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("Learning Rust", None)?;
+    ///
+    /// let found = service.find_note_by_hash(&content_hash("Learning Rust"))?;
+    /// assert_eq!(found.map(|n| n.id()), Some(note.id()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_note_by_hash(&self, hash: &str) -> Result<Option<Note>> {
+        let id = self
+            .db
+            .connection()
+            .query_row(
+                "SELECT id FROM notes WHERE content_hash = ?1 ORDER BY id LIMIT 1",
+                [hash],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?;
 
-                    Ok((
-                        tag_id,
-                        tag_name,
-                        confidence,
-                        source,
-                        tag_created_at,
-                        model_version,
-                    ))
-                })?;
-
-                let mut tag_assignments = Vec::new();
-                for row_result in tag_rows {
-                    let (tag_id, tag_name, confidence, source, tag_created_at, model_version) =
-                        row_result?;
-
-                    let tag_assignment = if source == "user" {
-                        TagAssignment::user(
-                            TagId::new(tag_id),
-                            tag_name,
-                            OffsetDateTime::from_unix_timestamp(tag_created_at)?,
-                        )
-                    } else {
-                        // LLM source - convert confidence from f64 (0.0-1.0) to u8 (0-100)
-                        let confidence_u8 = (confidence * 100.0).round() as u8;
-                        let model = model_version.unwrap_or_else(|| "unknown".to_string());
-
-                        TagAssignment::llm(
-                            TagId::new(tag_id),
-                            tag_name,
-                            model,
-                            confidence_u8,
-                            OffsetDateTime::from_unix_timestamp(tag_created_at)?,
-                        )
-                    };
-
-                    tag_assignments.push(tag_assignment);
-                }
+        match id {
+            Some(id) => self.get_note(NoteId::new(id)),
+            None => Ok(None),
+        }
+    }
 
-                // Build Note with enhancement fields
-                let mut builder = NoteBuilder::new()
-                    .id(NoteId::new(id))
-                    .content(content)
-                    .created_at(OffsetDateTime::from_unix_timestamp(created_at)?)
-                    .updated_at(OffsetDateTime::from_unix_timestamp(updated_at)?)
-                    .tags(tag_assignments);
-
-                // Add enhancement fields if present
-                if let Some(enhanced_content) = content_enhanced {
-                    builder = builder.content_enhanced(enhanced_content);
-                }
-                if let Some(enhanced_timestamp) = enhanced_at {
-                    builder = builder
-                        .enhanced_at(OffsetDateTime::from_unix_timestamp(enhanced_timestamp)?);
-                }
-                if let Some(model) = enhancement_model {
-                    builder = builder.enhancement_model(model);
-                }
-                if let Some(confidence) = enhancement_confidence {
-                    builder = builder.enhancement_confidence(confidence);
-                }
+    /// Retrieves several notes at once, in the order of `ids`.
+    ///
+    /// Loads all notes with one query and all of their tags with a second,
+    /// avoiding a round trip per note when assembling search results. IDs
+    /// that do not exist are skipped, and a repeated ID is returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, NoteId};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let first = service.create_note("First", Some(&["rust"]))?;
+    /// let second = service.create_note("Second", None)?;
+    ///
+    /// let notes = service.get_notes_batch(&[second.id(), NoteId::new(999), first.id()])?;
+    /// assert_eq!(notes.len(), 2);
+    /// assert_eq!(notes[0].content(), "Second");
+    /// assert_eq!(notes[1].tags().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_notes_batch(&self, ids: &[NoteId]) -> Result<Vec<Note>> {
+        use std::collections::HashMap;
 
-                let note = builder.build();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-                Ok(Some(note))
-            }
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e.into()),
+        let conn = self.db.connection();
+        let params: Vec<i64> = ids.iter().map(|id| id.get()).collect();
+        let placeholders = vec!["?"; params.len()].join(", ");
+
+        // Load every tag assignment for the requested notes in one query
+        let mut tag_stmt = conn.prepare(&format!(
+            "SELECT nt.note_id, {TAG_ASSIGNMENT_COLUMNS}
+             FROM note_tags nt
+             JOIN tags t ON nt.tag_id = t.id
+             WHERE nt.note_id IN ({placeholders})
+             ORDER BY nt.created_at"
+        ))?;
+        let mut tag_rows = tag_stmt.query(rusqlite::params_from_iter(&params))?;
+
+        let mut tags_by_note: HashMap<i64, Vec<TagAssignment>> = HashMap::new();
+        while let Some(row) = tag_rows.next()? {
+            let note_id: i64 = row.get(0)?;
+            tags_by_note
+                .entry(note_id)
+                .or_default()
+                .push(tag_assignment_from_row_at(row, 1)?);
         }
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {NOTE_COLUMNS} FROM notes WHERE id IN ({placeholders})"
+        ))?;
+        let mut rows = stmt.query(rusqlite::params_from_iter(&params))?;
+
+        let mut notes_by_id: HashMap<i64, Note> = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let tags = tags_by_note.remove(&id).unwrap_or_default();
+            notes_by_id.insert(id, note_from_row(row, tags)?);
+        }
+
+        Ok(ids
+            .iter()
+            .filter_map(|id| notes_by_id.remove(&id.get()))
+            .collect())
     }
 
     /// Deletes a note by its ID.
@@ -596,9 +1093,275 @@ impl NoteService {
         Ok(())
     }
 
-    /// Gets or creates a tag by name.
+    /// Folds the note `merge` into the note `keep` and deletes `merge`.
     ///
-    /// Queries the tags table by name (case-insensitive via COLLATE NOCASE).
+    /// The merged note's content is appended to the kept note's content, and
+    /// its enhanced content to the kept enhancement, separated by a blank line.
+    /// Tags are unioned; when both notes carry a tag, the stronger assignment
+    /// wins (a user tag beats an LLM tag, then higher confidence). Notes that
+    /// linked to `merge` via `[[id]]`, the kept note included, are rewritten to
+    /// link to `keep` and have their `updated_at` bumped. The FTS row is
+    /// refreshed by the `notes_fts_update` trigger. Runs in a single
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either note does not exist or both IDs are the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let keep = service.create_note("Tokio runs futures", Some(&["rust"]))?;
+    /// let dup = service.create_note("Tokio is an async runtime", Some(&["async"]))?;
+    ///
+    /// let merged = service.merge_notes(keep.id(), dup.id())?;
+    /// assert_eq!(merged.content(), "Tokio runs futures\n\nTokio is an async runtime");
+    /// assert_eq!(merged.tags().len(), 2);
+    /// assert_eq!(service.get_note(dup.id())?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge_notes(&self, keep: NoteId, merge: NoteId) -> Result<Note> {
+        if keep == merge {
+            return Err(InvalidInput(format!("Cannot merge note {} into itself", keep)).into());
+        }
+        let kept = self
+            .get_note(keep)?
+            .ok_or_else(|| NotFound(format!("Note with id {} does not exist", keep)))?;
+        let merged = self
+            .get_note(merge)?
+            .ok_or_else(|| NotFound(format!("Note with id {} does not exist", merge)))?;
+
+        // References to the merged note from either side now point at the kept note
+        let old_ref = format!("[[{merge}]]");
+        let new_ref = format!("[[{keep}]]");
+        let content =
+            format!("{}\n\n{}", kept.content(), merged.content()).replace(&old_ref, &new_ref);
+        let enhanced = match (kept.content_enhanced(), merged.content_enhanced()) {
+            (Some(a), Some(b)) => Some(format!("{a}\n\n{b}")),
+            (a, b) => a.or(b).map(str::to_string),
+        };
+
+        let conn = self.db.connection();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<()> = (|| {
+            // Union tags; on conflict keep the stronger assignment
+            conn.execute(
+                "INSERT INTO note_tags
+                 (note_id, tag_id, confidence, source, created_at, verified, model_version)
+                 SELECT ?1, tag_id, confidence, source, created_at, verified, model_version
+                 FROM note_tags WHERE note_id = ?2
+                 ON CONFLICT(note_id, tag_id) DO UPDATE SET
+                     confidence = excluded.confidence,
+                     source = excluded.source,
+                     created_at = excluded.created_at,
+                     verified = excluded.verified,
+                     model_version = excluded.model_version
+                 WHERE (excluded.source = 'user') > (note_tags.source = 'user')
+                    OR ((excluded.source = 'user') = (note_tags.source = 'user')
+                        AND excluded.confidence > note_tags.confidence)",
+                [keep.get(), merge.get()],
+            )?;
+
+            // Point notes that referenced the merged note at the kept note instead
+            let mut stmt = conn.prepare(
+                "SELECT n.id, n.content FROM notes n
+                 JOIN links l ON l.source_note_id = n.id
+                 WHERE l.target_note_id = ?1 AND n.id != ?2",
+            )?;
+            let linking = stmt
+                .query_map([merge.get(), keep.get()], |row| {
+                    Ok((NoteId::new(row.get(0)?), row.get::<_, String>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+
+            conn.execute("DELETE FROM notes WHERE id = ?1", [merge.get()])?;
+
+            for (id, text) in linking {
+                let text = text.replace(&old_ref, &new_ref);
+                conn.execute(
+                    "UPDATE notes SET content = ?1, content_hash = ?2, updated_at = ?3 WHERE id = ?4",
+                    (&text, content_hash(&text), now, id.get()),
+                )?;
+                self.replace_links(id, &text)?;
+            }
+
+            conn.execute(
+                "UPDATE notes
+                 SET content = ?1,
+                     content_enhanced = ?2,
+                     enhanced_at = COALESCE(enhanced_at, ?3),
+                     enhancement_model = COALESCE(enhancement_model, ?4),
+                     enhancement_confidence = COALESCE(enhancement_confidence, ?5),
+                     enhancement_prompt = COALESCE(enhancement_prompt, ?6),
+                     updated_at = ?7,
+                     content_hash = ?8
+                 WHERE id = ?9",
+                rusqlite::params![
+                    content,
+                    enhanced,
+                    merged.enhanced_at().map(|t| t.unix_timestamp()),
+                    merged.enhancement_model(),
+                    merged.enhancement_confidence(),
+                    merged.enhancement_prompt(),
+                    now,
+                    content_hash(&content),
+                    keep.get(),
+                ],
+            )?;
+            self.replace_links(keep, &content)?;
+
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => conn.execute("COMMIT", [])?,
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                return Err(e);
+            }
+        };
+
+        self.get_note(keep)?
+            .ok_or_else(|| NotFound(format!("Note with id {} does not exist", keep)).into())
+    }
+
+    /// Extracts the note IDs referenced as `[[123]]` in note content.
+    ///
+    /// IDs are returned in order of first appearance without duplicates.
+    /// Brackets that do not wrap a plain positive number (e.g. `[[rust]]`)
+    /// are ignored. Extra opening brackets are skipped, so `[[[12]]]` links to 12.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{NoteId, NoteService};
+    ///
+    /// let links = NoteService::extract_links("See [[12]] and [[7]], then [[12]] again");
+    /// assert_eq!(links, vec![NoteId::new(12), NoteId::new(7)]);
+    /// assert!(NoteService::extract_links("[[rust]] [[]] [12]").is_empty());
+    /// ```
+    pub fn extract_links(content: &str) -> Vec<NoteId> {
+        let mut links = Vec::new();
+        let mut rest = content;
+
+        while let Some(start) = rest.find("[[") {
+            // Anchor on the innermost "[[" so extra brackets ("[[[12]]]") still link
+            rest = rest[start + 2..].trim_start_matches('[');
+            let Some(end) = rest.find("]]") else {
+                break;
+            };
+
+            let inner = &rest[..end];
+            if !inner.is_empty() && inner.bytes().all(|b| b.is_ascii_digit()) {
+                if let Ok(id) = inner.parse::<i64>() {
+                    let id = NoteId::new(id);
+                    if id.get() > 0 && !links.contains(&id) {
+                        links.push(id);
+                    }
+                }
+                rest = &rest[end + 2..];
+            }
+        }
+
+        links
+    }
+
+    /// Returns the notes that `id` links to via `[[id]]` references, in ID order.
+    ///
+    /// References to notes that did not exist when the content was saved are
+    /// not recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let target = service.create_note("Ownership basics", None)?;
+    /// let source = service.create_note(&format!("Builds on [[{}]]", target.id()), None)?;
+    ///
+    /// let links = service.get_links(source.id())?;
+    /// assert_eq!(links.len(), 1);
+    /// assert_eq!(links[0].id(), target.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_links(&self, id: NoteId) -> Result<Vec<Note>> {
+        self.linked_notes(
+            "SELECT target_note_id FROM links WHERE source_note_id = ?1 ORDER BY target_note_id",
+            id,
+        )
+    }
+
+    /// Returns the notes that link to `id` via `[[id]]` references, in ID order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let target = service.create_note("Ownership basics", None)?;
+    /// let source = service.create_note(&format!("Builds on [[{}]]", target.id()), None)?;
+    ///
+    /// let backlinks = service.get_backlinks(target.id())?;
+    /// assert_eq!(backlinks.len(), 1);
+    /// assert_eq!(backlinks[0].id(), source.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_backlinks(&self, id: NoteId) -> Result<Vec<Note>> {
+        self.linked_notes(
+            "SELECT source_note_id FROM links WHERE target_note_id = ?1 ORDER BY source_note_id",
+            id,
+        )
+    }
+
+    /// Loads the notes whose IDs are selected by a single-parameter links query.
+    fn linked_notes(&self, sql: &str, id: NoteId) -> Result<Vec<Note>> {
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare(sql)?;
+        let ids = stmt
+            .query_map([id.get()], |row| row.get(0).map(NoteId::new))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        self.get_notes_batch(&ids)
+    }
+
+    /// Rebuilds the outgoing links of `source` from its content.
+    ///
+    /// Dangling references and self-references are skipped.
+    fn replace_links(&self, source: NoteId, content: &str) -> Result<()> {
+        let conn = self.db.connection();
+
+        conn.execute(
+            "DELETE FROM links WHERE source_note_id = ?1",
+            [source.get()],
+        )?;
+        for target in Self::extract_links(content) {
+            conn.execute(
+                "INSERT OR IGNORE INTO links (source_note_id, target_note_id)
+                 SELECT ?1, id FROM notes WHERE id = ?2 AND id != ?1",
+                (source.get(), target.get()),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets or creates a tag by name.
+    ///
+    /// Queries the tags table by name (case-insensitive via COLLATE NOCASE).
     /// If an alias exists for the normalized name, returns the canonical tag ID.
     /// If the tag exists, returns its TagId. If not found, creates a new tag
     /// and returns its TagId.
@@ -642,7 +1405,7 @@ impl NoteService {
     ///
     /// * `note_id` - The ID of the note to add tags to
     /// * `tags` - Slice of tag names to add
-    /// * `source` - The source of the tag assignment (User or Llm)
+    /// * `source` - The source of the tag assignment (User, Llm or Import)
     ///
     /// # Examples
     ///
@@ -681,7 +1444,7 @@ impl NoteService {
         )?;
 
         if !note_exists {
-            anyhow::bail!("Note with id {} does not exist", note_id);
+            return Err(NotFound(format!("Note with id {} does not exist", note_id)).into());
         }
 
         // Process each tag
@@ -689,14 +1452,7 @@ impl NoteService {
             let tag_id = self.get_or_create_tag(tag_name)?;
 
             // Prepare metadata based on source
-            let (source_str, confidence, model_version) = match &source {
-                TagSource::User => ("user", 1.0, None),
-                TagSource::Llm { model, confidence } => {
-                    // Convert u8 (0-100) to f64 (0.0-1.0)
-                    let confidence_f64 = f64::from(*confidence) / 100.0;
-                    ("llm", confidence_f64, Some(model.as_str()))
-                }
-            };
+            let (source_str, confidence, model_version) = tag_source_columns(&source);
 
             // Insert note_tag association (INSERT OR IGNORE for duplicates)
             conn.execute(
@@ -717,169 +1473,1012 @@ impl NoteService {
         Ok(())
     }
 
-    /// Lists notes with optional filtering and pagination.
+    /// Adds `new_tag` to every note that carries `filter_tag`.
     ///
-    /// Returns notes ordered by creation time (order controlled by `ListNotesOptions::order`)
-    /// with optional filtering by tags and limiting of results.
+    /// Both names are normalized and resolved through aliases, like
+    /// [`add_tags_to_note`](Self::add_tags_to_note). Notes that already carry
+    /// the new tag keep their existing assignment. Runs in a single transaction
+    /// and returns the number of notes that gained the tag.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `options` - Filtering and pagination options
+    /// Returns an error if `new_tag` is empty after normalization or `filter_tag`
+    /// does not exist.
     ///
     /// # Examples
     ///
     /// ```
-    /// use cons::{Database, NoteService, ListNotesOptions};
+    /// use cons::{Database, NoteService, TagSource};
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let db = Database::in_memory()?;
-    /// let service = NoteService::new(db);
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Borrow checker", Some(&["rust"]))?;
+    /// service.create_note("Cargo workspaces", Some(&["rust", "programming"]))?;
+    /// service.create_note("Sourdough", Some(&["baking"]))?;
     ///
-    /// // Create some notes
-    /// service.create_note("First note", Some(&["rust"]))?;
-    /// service.create_note("Second note", Some(&["rust", "programming"]))?;
+    /// let added = service.add_tag_to_notes_with_tag("programming", "rust", TagSource::User)?;
+    /// assert_eq!(added, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_tag_to_notes_with_tag(
+        &self,
+        new_tag: &str,
+        filter_tag: &str,
+        source: TagSource,
+    ) -> Result<usize> {
+        if TagNormalizer::normalize_tag(new_tag).is_empty() {
+            anyhow::bail!("Tag name cannot be empty");
+        }
+        let Some(filter_id) = self.find_tag(filter_tag)? else {
+            return Err(NotFound(format!(
+                "Tag '{}' does not exist",
+                TagNormalizer::normalize_tag(filter_tag)
+            ))
+            .into());
+        };
+
+        let conn = self.db.connection();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let (source_str, confidence, model_version) = tag_source_columns(&source);
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<usize> = (|| {
+            let tag_id = self.get_or_create_tag(new_tag)?;
+
+            // The (note_id, tag_id) primary key skips notes that already have the tag
+            let added = conn.execute(
+                "INSERT OR IGNORE INTO note_tags
+                 (note_id, tag_id, confidence, source, created_at, verified, model_version)
+                 SELECT note_id, ?1, ?2, ?3, ?4, 0, ?5
+                 FROM note_tags WHERE tag_id = ?6",
+                rusqlite::params![
+                    tag_id.get(),
+                    confidence,
+                    source_str,
+                    now,
+                    model_version,
+                    filter_id.get(),
+                ],
+            )?;
+
+            Ok(added)
+        })();
+
+        match result {
+            Ok(added) => {
+                conn.execute("COMMIT", [])?;
+                Ok(added)
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Removes all LLM-sourced tags from a note, keeping user tags.
     ///
-    /// // List all notes
-    /// let all_notes = service.list_notes(ListNotesOptions::default())?;
+    /// Returns the number of tag assignments removed. Used before re-running
+    /// auto-tagging so stale LLM suggestions can be replaced.
     ///
-    /// // List with limit
-    /// let recent_notes = service.list_notes(ListNotesOptions {
-    ///     limit: Some(5),
-    ///     ..Default::default()
-    /// })?;
+    /// # Examples
     ///
-    /// // Filter by tags (AND logic)
-    /// let filtered_notes = service.list_notes(ListNotesOptions {
-    ///     tags: Some(vec!["rust".to_string(), "programming".to_string()]),
-    ///     ..Default::default()
-    /// })?;
+    /// ```
+    /// use cons::{Database, NoteService, TagSource};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("My note", Some(&["rust"]))?;
+    /// service.add_tags_to_note(note.id(), &["ai"], TagSource::llm("deepseek-r1:8b", 85))?;
+    ///
+    /// assert_eq!(service.remove_llm_tags(note.id())?, 1);
+    /// let note = service.get_note(note.id())?.unwrap();
+    /// assert_eq!(note.tags().len(), 1);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn list_notes(&self, options: ListNotesOptions) -> Result<Vec<Note>> {
+    pub fn remove_llm_tags(&self, note_id: NoteId) -> Result<usize> {
         let conn = self.db.connection();
 
-        // Build the query based on whether we have tag filters
-        let note_ids: Vec<i64> = if let Some(tag_names) = options.tags {
-            if tag_names.is_empty() {
-                // Empty tag filter means no notes match
-                Vec::new()
-            } else {
-                // Resolve aliases for each tag filter independently
-                let mut resolved_tag_names = Vec::new();
-                for tag_name in &tag_names {
-                    // Normalize the tag name
-                    let normalized = TagNormalizer::normalize_tag(tag_name);
+        let removed = conn.execute(
+            "DELETE FROM note_tags WHERE note_id = ?1 AND source = 'llm'",
+            [note_id.get()],
+        )?;
 
-                    // Check if it's an alias
-                    if let Some(canonical_tag_id) = self.resolve_alias(&normalized)? {
-                        // It's an alias - get the canonical tag name
-                        let canonical_name: String = conn.query_row(
-                            "SELECT name FROM tags WHERE id = ?1",
-                            [canonical_tag_id.get()],
-                            |row| row.get(0),
-                        )?;
-                        resolved_tag_names.push(canonical_name);
-                    } else {
-                        // Not an alias - use the normalized name
-                        resolved_tag_names.push(normalized);
-                    }
-                }
+        Ok(removed)
+    }
 
-                // Query for notes that have ALL specified tags (AND logic)
-                // We use HAVING COUNT to ensure the note has all tags
-                let tag_count = resolved_tag_names.len();
+    /// Removes a single tag from a note.
+    ///
+    /// The tag name is normalized and resolved through aliases, so removing
+    /// `ML` also removes the canonical `machine-learning` tag. The tag itself
+    /// stays in the tags table for other notes. Removing a tag the note does
+    /// not have is a no-op; the return value reports whether a row was removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("My note", Some(&["rust", "wrong"]))?;
+    ///
+    /// assert!(service.remove_tag_from_note(note.id(), "Wrong")?);
+    /// assert!(!service.remove_tag_from_note(note.id(), "wrong")?);
+    ///
+    /// let note = service.get_note(note.id())?.unwrap();
+    /// assert_eq!(note.tags().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_tag_from_note(&self, note_id: NoteId, tag: &str) -> Result<bool> {
+        let Some(tag_id) = self.find_tag(tag)? else {
+            return Ok(false);
+        };
 
-                // Build placeholders for the IN clause
-                let placeholders: Vec<&str> = resolved_tag_names.iter().map(|_| "?").collect();
-                let in_clause = placeholders.join(", ");
+        let removed = self.db.connection().execute(
+            "DELETE FROM note_tags WHERE note_id = ?1 AND tag_id = ?2",
+            [note_id.get(), tag_id.get()],
+        )?;
 
-                let order_clause = match options.order {
-                    SortOrder::Ascending => "ASC",
-                    SortOrder::Descending => "DESC",
-                };
-                let limit_clause = if let Some(limit) = options.limit {
-                    format!(" LIMIT {}", limit)
-                } else {
-                    String::new()
-                };
-                let query = format!(
-                    "SELECT DISTINCT n.id
-                     FROM notes n
-                     JOIN note_tags nt ON n.id = nt.note_id
-                     JOIN tags t ON nt.tag_id = t.id
-                     WHERE t.name IN ({}) COLLATE NOCASE
-                     GROUP BY n.id
-                     HAVING COUNT(DISTINCT t.id) = ?
-                     ORDER BY n.created_at {}{}",
-                    in_clause, order_clause, limit_clause
-                );
+        Ok(removed > 0)
+    }
 
-                let mut stmt = conn.prepare(&query)?;
+    /// Marks a note's tag assignment as verified by the user.
+    ///
+    /// The tag name is normalized and resolved through aliases like
+    /// [`remove_tag_from_note`](Self::remove_tag_from_note). Verifying an
+    /// already-verified tag is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the note does not have the tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, TagSource};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("My note", None)?;
+    /// service.add_tags_to_note(note.id(), &["rust"], TagSource::llm("deepseek-r1:8b", 85))?;
+    ///
+    /// service.verify_tag(note.id(), "rust")?;
+    /// let note = service.get_note(note.id())?.unwrap();
+    /// assert!(note.tags()[0].verified());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_tag(&self, note_id: NoteId, tag: &str) -> Result<()> {
+        let updated = match self.find_tag(tag)? {
+            Some(tag_id) => self.db.connection().execute(
+                "UPDATE note_tags SET verified = 1 WHERE note_id = ?1 AND tag_id = ?2",
+                [note_id.get(), tag_id.get()],
+            )?,
+            None => 0,
+        };
 
-                // Bind tag names and then the count
-                let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-                for tag_name in &resolved_tag_names {
-                    params.push(tag_name);
-                }
-                params.push(&tag_count);
+        if updated == 0 {
+            return Err(NotFound(format!(
+                "Tag '{}' does not exist on note {}",
+                TagNormalizer::normalize_tag(tag),
+                note_id
+            ))
+            .into());
+        }
 
-                let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
-                    row.get::<_, i64>(0)
-                })?;
+        Ok(())
+    }
 
-                let mut ids = Vec::new();
-                for row_result in rows {
-                    ids.push(row_result?);
-                }
+    /// Sets the description of an existing tag.
+    ///
+    /// The tag name is normalized and resolved through aliases, so describing an
+    /// alias describes its canonical tag. A blank description clears it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("My note", Some(&["rust"]))?;
+    ///
+    /// service.set_tag_description("Rust", "Systems programming language")?;
+    /// assert_eq!(
+    ///     service.get_tag_description("rust")?.as_deref(),
+    ///     Some("Systems programming language")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_tag_description(&self, tag: &str, desc: &str) -> Result<()> {
+        let Some(tag_id) = self.find_tag(tag)? else {
+            return Err(NotFound(format!(
+                "Tag '{}' does not exist",
+                TagNormalizer::normalize_tag(tag)
+            ))
+            .into());
+        };
 
-                ids
-            }
-        } else {
-            // No tag filtering - get all notes
-            let order_clause = match options.order {
-                SortOrder::Ascending => "ASC",
-                SortOrder::Descending => "DESC",
-            };
-            let query = if let Some(limit) = options.limit {
-                format!(
-                    "SELECT id FROM notes ORDER BY created_at {} LIMIT {}",
-                    order_clause, limit
-                )
-            } else {
-                format!("SELECT id FROM notes ORDER BY created_at {}", order_clause)
+        let desc = desc.trim();
+        self.db.connection().execute(
+            "UPDATE tags SET description = ?1 WHERE id = ?2",
+            rusqlite::params![(!desc.is_empty()).then_some(desc), tag_id.get()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the description of a tag, if it exists and has one.
+    ///
+    /// The tag name is normalized and resolved through aliases like
+    /// [`set_tag_description`](Self::set_tag_description).
+    pub fn get_tag_description(&self, tag: &str) -> Result<Option<String>> {
+        let Some(tag_id) = self.find_tag(tag)? else {
+            return Ok(None);
+        };
+
+        let description = self.db.connection().query_row(
+            "SELECT description FROM tags WHERE id = ?1",
+            [tag_id.get()],
+            |row| row.get(0),
+        )?;
+
+        Ok(description)
+    }
+
+    /// Saves a named tag preset, replacing any existing preset with the same name.
+    ///
+    /// Both the preset name and its tags are normalized like tag names; duplicate
+    /// tags are dropped. The tags themselves are only created when the preset is
+    /// applied to a note. Returns the normalized tags that were stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name or the tag list is empty after normalization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    ///
+    /// let tags = service.set_tag_preset("Meeting", &["Work", "meetings", "work"])?;
+    /// assert_eq!(tags, vec!["work", "meetings"]);
+    /// assert_eq!(service.get_tag_preset("meeting")?, Some(tags));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_tag_preset(&self, name: &str, tags: &[&str]) -> Result<Vec<String>> {
+        let name = TagNormalizer::normalize_tag(name);
+        if name.is_empty() {
+            anyhow::bail!("Preset name cannot be empty");
+        }
+
+        let tags = TagNormalizer::normalize_tags(tags.iter().map(|t| t.to_string()).collect());
+        if tags.is_empty() {
+            anyhow::bail!("Preset tags cannot be empty");
+        }
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        self.db.connection().execute(
+            "INSERT INTO tag_presets (name, tags, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET tags = excluded.tags",
+            rusqlite::params![name, tags.join(","), now],
+        )?;
+
+        Ok(tags)
+    }
+
+    /// Returns the tags of a preset, or `None` if no preset has that name.
+    ///
+    /// The name is normalized like in [`set_tag_preset`](Self::set_tag_preset).
+    pub fn get_tag_preset(&self, name: &str) -> Result<Option<Vec<String>>> {
+        let tags: Option<String> = self
+            .db
+            .connection()
+            .query_row(
+                "SELECT tags FROM tag_presets WHERE name = ?1",
+                [TagNormalizer::normalize_tag(name)],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(tags.map(|tags| split_preset_tags(&tags)))
+    }
+
+    /// Lists all tag presets as `(name, tags)` pairs, ordered by name.
+    pub fn list_tag_presets(&self) -> Result<Vec<(String, Vec<String>)>> {
+        let mut stmt = self
+            .db
+            .connection()
+            .prepare("SELECT name, tags FROM tag_presets ORDER BY name")?;
+
+        let presets = stmt
+            .query_map([], |row| {
+                let tags: String = row.get(1)?;
+                Ok((row.get(0)?, split_preset_tags(&tags)))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(presets)
+    }
+
+    /// Removes a tag preset. Returns false if no preset had that name.
+    pub fn remove_tag_preset(&self, name: &str) -> Result<bool> {
+        let removed = self.db.connection().execute(
+            "DELETE FROM tag_presets WHERE name = ?1",
+            [TagNormalizer::normalize_tag(name)],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Looks up an existing tag by name without creating it.
+    ///
+    /// Normalizes the name and resolves aliases to their canonical tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let rust = service.get_or_create_tag("rust")?;
+    ///
+    /// assert_eq!(service.find_tag("Rust")?, Some(rust));
+    /// assert_eq!(service.find_tag("golang")?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_tag(&self, name: &str) -> Result<Option<TagId>> {
+        let normalized = TagNormalizer::normalize_tag(name);
+
+        if let Some(canonical_tag_id) = self.resolve_alias(&normalized)? {
+            return Ok(Some(canonical_tag_id));
+        }
+
+        let id = self
+            .db
+            .connection()
+            .query_row(
+                "SELECT id FROM tags WHERE name = ?1 COLLATE NOCASE",
+                [&normalized],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?;
+
+        Ok(id.map(TagId::new))
+    }
+
+    /// Lists notes with optional filtering and pagination.
+    ///
+    /// Returns notes ordered by creation time (order controlled by `ListNotesOptions::order`)
+    /// with optional filtering by tags and limiting of results.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Filtering and pagination options
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, ListNotesOptions};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// // Create some notes
+    /// service.create_note("First note", Some(&["rust"]))?;
+    /// service.create_note("Second note", Some(&["rust", "programming"]))?;
+    ///
+    /// // List all notes
+    /// let all_notes = service.list_notes(ListNotesOptions::default())?;
+    ///
+    /// // List with limit
+    /// let recent_notes = service.list_notes(ListNotesOptions {
+    ///     limit: Some(5),
+    ///     ..Default::default()
+    /// })?;
+    ///
+    /// // Filter by tags (AND logic)
+    /// let filtered_notes = service.list_notes(ListNotesOptions {
+    ///     tags: Some(vec!["rust".to_string(), "programming".to_string()]),
+    ///     ..Default::default()
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_notes(&self, options: ListNotesOptions) -> Result<Vec<Note>> {
+        let conn = self.db.connection();
+
+        let Some((where_clause, mut params)) = self.list_filter_clause(&options)? else {
+            // Empty tag filter means no notes match
+            return Ok(Vec::new());
+        };
+
+        let order_clause = match options.order {
+            SortOrder::Ascending => "ASC",
+            SortOrder::Descending => "DESC",
+        };
+        let order_column = options.order_by.column();
+        let limit_clause = limit_offset_clause(options.limit, options.offset, &mut params);
+
+        // Tie-break on id so notes written within the same second keep insertion order
+        let query = format!(
+            "SELECT n.id FROM notes n{} ORDER BY n.{} {}, n.id {}{}",
+            where_clause, order_column, order_clause, order_clause, limit_clause
+        );
+
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+            |row| row.get::<_, i64>(0),
+        )?;
+
+        let mut note_ids = Vec::new();
+        for row_result in rows {
+            note_ids.push(row_result?);
+        }
+
+        // Now load each note with its full data including tags
+        let mut notes = Vec::new();
+        for id in note_ids {
+            if let Some(note) = self.get_note(NoteId::new(id))? {
+                notes.push(note);
+            }
+        }
+
+        Ok(notes)
+    }
+
+    /// Counts the notes matching the filters in `options`.
+    ///
+    /// Applies the same tag (AND), provenance, and date filters as
+    /// [`list_notes`](Self::list_notes) but ignores `limit` and `offset`, so
+    /// callers can report "showing N of M". An empty tag filter counts 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, ListNotesOptions};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("First note", Some(&["rust"]))?;
+    /// service.create_note("Second note", Some(&["rust", "programming"]))?;
+    ///
+    /// let options = ListNotesOptions {
+    ///     limit: Some(1),
+    ///     tags: Some(vec!["rust".to_string()]),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(service.count_notes(&options)?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_notes(&self, options: &ListNotesOptions) -> Result<usize> {
+        let Some((where_clause, params)) = self.list_filter_clause(options)? else {
+            return Ok(0);
+        };
+
+        let query = format!("SELECT COUNT(*) FROM notes n{}", where_clause);
+        let count: i64 = self.db.connection().query_row(
+            &query,
+            rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+            |row| row.get(0),
+        )?;
+
+        Ok(count as usize)
+    }
+
+    /// Normalizes tag names and resolves aliases to their canonical tag names.
+    ///
+    /// Each name is resolved independently; names that are neither an alias nor
+    /// an existing tag are returned normalized, so filtering on them matches
+    /// nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let tag_id = service.get_or_create_tag("machine-learning")?;
+    /// service.create_alias("ml", tag_id, "user", 1.0, None)?;
+    ///
+    /// let names = service.canonical_tag_names(&["ML".to_string(), "Rust".to_string()])?;
+    /// assert_eq!(names, vec!["machine-learning", "rust"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn canonical_tag_names(&self, tag_names: &[String]) -> Result<Vec<String>> {
+        let conn = self.db.connection();
+
+        let mut resolved_tag_names = Vec::new();
+        for tag_name in tag_names {
+            let normalized = TagNormalizer::normalize_tag(tag_name);
+
+            if let Some(canonical_tag_id) = self.resolve_alias(&normalized)? {
+                // It's an alias - get the canonical tag name
+                let canonical_name: String = conn.query_row(
+                    "SELECT name FROM tags WHERE id = ?1",
+                    [canonical_tag_id.get()],
+                    |row| row.get(0),
+                )?;
+                resolved_tag_names.push(canonical_name);
+            } else {
+                resolved_tag_names.push(normalized);
+            }
+        }
+
+        Ok(resolved_tag_names)
+    }
+
+    /// Returns a copy of `filters` with its tag names made canonical.
+    fn canonical_search_filters(&self, filters: &SearchFilters) -> Result<SearchFilters> {
+        let tags = filters
+            .tags
+            .as_deref()
+            .map(|tags| self.canonical_tag_names(tags))
+            .transpose()?;
+        Ok(SearchFilters {
+            tags,
+            ..filters.clone()
+        })
+    }
+
+    /// Builds the WHERE clause and bound parameters for the filters in `options`.
+    ///
+    /// Shared by [`list_notes`](Self::list_notes) and
+    /// [`count_notes`](Self::count_notes) so both apply identical filtering.
+    /// Returns `None` when the filter cannot match any note (an empty tag list).
+    fn list_filter_clause(
+        &self,
+        options: &ListNotesOptions,
+    ) -> Result<Option<(String, Vec<Box<dyn rusqlite::ToSql>>)>> {
+        // Each filter contributes a WHERE clause and its bound parameters
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(tag_names) = &options.tags {
+            if tag_names.is_empty() {
+                return Ok(None);
+            }
+
+            let resolved_tag_names = self.canonical_tag_names(tag_names)?;
+            conditions.push(all_tags_condition("n.id", &resolved_tag_names, &mut params));
+        }
+
+        if let Some(pattern) = &options.tag_like {
+            if options.tags.is_some() {
+                anyhow::bail!("Cannot filter by both exact tags and a tag pattern");
+            }
+
+            // Substring match on tag names; escape LIKE wildcards so they match literally
+            let escaped = pattern
+                .trim()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            conditions.push(
+                "n.id IN (
+                     SELECT nt.note_id
+                     FROM note_tags nt
+                     JOIN tags t ON nt.tag_id = t.id
+                     WHERE t.name LIKE ? ESCAPE '\\'
+                 )"
+                .to_string(),
+            );
+            params.push(Box::new(format!("%{escaped}%")));
+        }
+
+        if let Some(condition) = options.source.sql_condition("n.id") {
+            conditions.push(condition);
+        }
+
+        if options.unverified_only {
+            conditions.push(
+                "n.id IN (SELECT note_id FROM note_tags WHERE source = 'llm' AND verified = 0)"
+                    .to_string(),
+            );
+        }
+
+        if options.untagged_only {
+            conditions.push("n.id NOT IN (SELECT note_id FROM note_tags)".to_string());
+        }
+
+        if let Some(created_after) = options.created_after {
+            conditions.push("n.created_at >= ?".to_string());
+            params.push(Box::new(created_after));
+        }
+
+        if let Some(created_before) = options.created_before {
+            conditions.push("n.created_at <= ?".to_string());
+            params.push(Box::new(created_before));
+        }
+
+        if let Some(before) = options.before {
+            // Keyset cursor on the sort key, with the same id tie-break as the ORDER BY
+            let column = options.order_by.column();
+            let comparison = match options.order {
+                SortOrder::Ascending => ">",
+                SortOrder::Descending => "<",
             };
+            let cursor_key: Option<i64> = self
+                .db
+                .connection()
+                .query_row(
+                    &format!("SELECT {column} FROM notes WHERE id = ?1"),
+                    [before],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            match cursor_key {
+                Some(key) => {
+                    conditions.push(format!("(n.{column}, n.id) {comparison} (?, ?)"));
+                    params.push(Box::new(key));
+                    params.push(Box::new(before));
+                }
+                // The cursor note was deleted; fall back to comparing ids
+                None => {
+                    conditions.push(format!("n.id {comparison} ?"));
+                    params.push(Box::new(before));
+                }
+            }
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        Ok(Some((where_clause, params)))
+    }
+
+    /// Lists notes that have no tags, applying the other filters in `options`.
+    ///
+    /// These are notes that slipped through auto-tagging (e.g. captured while
+    /// Ollama was unavailable) and are candidates for `cons retag --all`.
+    /// Limit, offset, ordering and date filters behave as in
+    /// [`list_notes`](Self::list_notes); tag filters match nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, ListNotesOptions, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("tagged thought", Some(&["rust"]))?;
+    /// let note = service.create_note("stray thought", None)?;
+    ///
+    /// let untagged = service.notes_without_tags(ListNotesOptions::default())?;
+    /// assert_eq!(untagged.len(), 1);
+    /// assert_eq!(untagged[0].id(), note.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn notes_without_tags(&self, options: ListNotesOptions) -> Result<Vec<Note>> {
+        self.list_notes(ListNotesOptions {
+            untagged_only: true,
+            ..options
+        })
+    }
+
+    /// Lists notes that have no enhanced content, oldest first.
+    ///
+    /// These are notes captured while Ollama was unavailable (or before
+    /// enhancement existed) and are candidates for `cons reenhance`. Notes and
+    /// their tags are loaded in bulk via [`get_notes_batch`](Self::get_notes_batch).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let note = service.create_note("quick thought", None)?;
+    ///
+    /// let unenhanced = service.list_unenhanced_notes()?;
+    /// assert_eq!(unenhanced.len(), 1);
+    /// assert_eq!(unenhanced[0].id(), note.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_unenhanced_notes(&self) -> Result<Vec<Note>> {
+        let conn = self.db.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT id FROM notes WHERE content_enhanced IS NULL ORDER BY created_at, id",
+        )?;
+        let note_ids = stmt
+            .query_map([], |row| row.get::<_, i64>(0).map(NoteId::new))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.get_notes_batch(&note_ids)
+    }
+
+    /// Resolves an alias to its canonical tag ID.
+    ///
+    /// Normalizes the input alias name before lookup using COLLATE NOCASE matching.
+    /// Returns `None` if no alias exists with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The alias name to resolve
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// // Create a canonical tag and alias
+    /// let canonical_tag_id = service.get_or_create_tag("machine-learning")?;
+    /// service.create_alias("ml", canonical_tag_id, "user", 1.0, None)?;
+    ///
+    /// // Resolve the alias
+    /// let resolved = service.resolve_alias("ml")?;
+    /// assert_eq!(resolved, Some(canonical_tag_id));
+    ///
+    /// // Non-existent alias returns None
+    /// assert_eq!(service.resolve_alias("non-existent")?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve_alias(&self, name: &str) -> Result<Option<TagId>> {
+        // Normalize input before lookup
+        let normalized = TagNormalizer::normalize_tag(name);
+        let conn = self.db.connection();
+
+        // Query tag_aliases with COLLATE NOCASE matching
+        let result: Option<i64> = conn
+            .query_row(
+                "SELECT canonical_tag_id FROM tag_aliases WHERE alias = ?1 COLLATE NOCASE",
+                [&normalized],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(result.map(TagId::new))
+    }
+
+    /// Detects whether a suggested tag is an acronym of an existing tag.
+    ///
+    /// Only true acronyms are detected: a short tag (2-3 characters) maps to an
+    /// existing hyphenated tag of at least two words when each character is the
+    /// first letter of the corresponding word, in order. Prefix matches such as
+    /// "ai" against "aimodel" are deliberately not treated as aliases.
+    ///
+    /// Returns the canonical tag's ID, or None when no acronym match exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// let canonical_tag_id = service.get_or_create_tag("machine-learning")?;
+    ///
+    /// assert_eq!(service.detect_alias_opportunity("ml")?, Some(canonical_tag_id));
+    /// assert_eq!(service.detect_alias_opportunity("quantum-computing")?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_alias_opportunity(&self, suggested_tag: &str) -> Result<Option<TagId>> {
+        let normalized = TagNormalizer::normalize_tag(suggested_tag);
+        let letters: Vec<char> = normalized.chars().collect();
+
+        // Only consider short, single-word tags (2-3 characters) as potential acronyms
+        if !(2..=3).contains(&letters.len()) || letters.contains(&'-') {
+            return Ok(None);
+        }
+
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare("SELECT id, name FROM tags ORDER BY id")?;
+        let tags = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        // Look for a hyphenated tag whose word initials spell the acronym,
+        // e.g. "ml" for "machine-learning"
+        for (tag_id, tag_name) in tags {
+            let words: Vec<&str> = tag_name.split('-').collect();
+            if words.len() < 2 || words.len() != letters.len() {
+                continue;
+            }
+
+            if words
+                .iter()
+                .zip(&letters)
+                .all(|(word, &ch)| word.starts_with(ch))
+            {
+                return Ok(Some(TagId::new(tag_id)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Creates an alias mapping an alternate name to a canonical tag.
+    ///
+    /// Normalizes the alias before storage and verifies that:
+    /// - The canonical tag exists
+    /// - The canonical tag is not itself an alias (prevents chains)
+    ///
+    /// Uses INSERT OR REPLACE for idempotent updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alias name to create
+    /// * `canonical_tag_id` - The canonical tag this alias resolves to
+    /// * `source` - The source of the alias ("user" or "llm")
+    /// * `confidence` - Confidence score (0.0-1.0)
+    /// * `model_version` - Optional model version for LLM-created aliases
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// // Create a canonical tag
+    /// let canonical_tag_id = service.get_or_create_tag("machine-learning")?;
+    ///
+    /// // Create a user alias
+    /// service.create_alias("ml", canonical_tag_id, "user", 1.0, None)?;
+    ///
+    /// // Create an LLM alias
+    /// service.create_alias("ML", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_alias(
+        &self,
+        alias: &str,
+        canonical_tag_id: TagId,
+        source: &str,
+        confidence: f64,
+        model_version: Option<&str>,
+    ) -> Result<()> {
+        // Normalize alias before storage
+        let normalized_alias = TagNormalizer::normalize_tag(alias);
+        let conn = self.db.connection();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        self.ensure_alias_target(canonical_tag_id)?;
+
+        // Insert with INSERT OR REPLACE for idempotent updates
+        conn.execute(
+            "INSERT OR REPLACE INTO tag_aliases (alias, canonical_tag_id, source, confidence, created_at, model_version)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                normalized_alias,
+                canonical_tag_id.get(),
+                source,
+                confidence,
+                now,
+                model_version,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Creates user aliases from `(alias, canonical)` pairs in a single transaction.
+    ///
+    /// Both sides are normalized and missing canonical tags are created, as with
+    /// [`create_alias`](Self::create_alias). Pairs that would form an alias chain
+    /// are skipped rather than failing the import: those whose canonical name is
+    /// itself an alias, whose alias name is already a tag (and so possibly the
+    /// target of other aliases), or that map a name to itself. Earlier pairs in
+    /// the same batch count, so the outcome does not depend on line order. If
+    /// any other error occurs, nothing is imported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    ///
+    /// let summary = service.import_aliases(&[
+    ///     ("ml".to_string(), "machine-learning".to_string()),
+    ///     ("mlearn".to_string(), "ml".to_string()),
+    /// ])?;
+    /// assert_eq!(summary.created, 1);
+    /// assert_eq!(summary.skipped, vec![("mlearn".to_string(), "ml".to_string())]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn import_aliases(&self, mappings: &[(String, String)]) -> Result<AliasImportSummary> {
+        let conn = self.db.connection();
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<AliasImportSummary> = (|| {
+            let mut summary = AliasImportSummary::default();
+
+            for (alias, canonical) in mappings {
+                let normalized_alias = TagNormalizer::normalize_tag(alias);
+                let normalized_canonical = TagNormalizer::normalize_tag(canonical);
+
+                // Aliases target tags, so a tag name covers every canonical target
+                let alias_is_tag: bool = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM tags WHERE name = ?1 COLLATE NOCASE)",
+                    [&normalized_alias],
+                    |row| row.get(0),
+                )?;
 
-            let mut stmt = conn.prepare(&query)?;
-            let rows = stmt.query_map([], |row| row.get::<_, i64>(0))?;
+                if normalized_alias == normalized_canonical
+                    || alias_is_tag
+                    || self.resolve_alias(&normalized_canonical)?.is_some()
+                {
+                    summary
+                        .skipped
+                        .push((normalized_alias, normalized_canonical));
+                    continue;
+                }
 
-            let mut ids = Vec::new();
-            for row_result in rows {
-                ids.push(row_result?);
+                let canonical_tag_id = self.get_or_create_tag(&normalized_canonical)?;
+                self.create_alias(&normalized_alias, canonical_tag_id, "user", 1.0, None)?;
+                summary.created += 1;
             }
 
-            ids
-        };
+            Ok(summary)
+        })();
 
-        // Now load each note with its full data including tags
-        let mut notes = Vec::new();
-        for id in note_ids {
-            if let Some(note) = self.get_note(NoteId::new(id))? {
-                notes.push(note);
+        match result {
+            Ok(summary) => {
+                conn.execute("COMMIT", [])?;
+                Ok(summary)
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
             }
         }
-
-        Ok(notes)
     }
 
-    /// Resolves an alias to its canonical tag ID.
+    /// Points an existing alias at a different canonical tag.
     ///
-    /// Normalizes the input alias name before lookup using COLLATE NOCASE matching.
-    /// Returns `None` if no alias exists with the given name.
+    /// Applies the same checks as [`create_alias`](Self::create_alias): the new
+    /// canonical tag must exist and must not itself be an alias. The alias keeps
+    /// its source, confidence and creation metadata.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `name` - The alias name to resolve
+    /// Returns an error if the alias does not exist (it is never created here),
+    /// or if the new canonical tag is missing or is an alias.
     ///
     /// # Examples
     ///
@@ -887,54 +2486,43 @@ impl NoteService {
     /// use cons::{Database, NoteService};
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let db = Database::in_memory()?;
-    /// let service = NoteService::new(db);
-    ///
-    /// // Create a canonical tag and alias
-    /// let canonical_tag_id = service.get_or_create_tag("machine-learning")?;
-    /// service.create_alias("ml", canonical_tag_id, "user", 1.0, None)?;
-    ///
-    /// // Resolve the alias
-    /// let resolved = service.resolve_alias("ml")?;
-    /// assert_eq!(resolved, Some(canonical_tag_id));
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let machine_learning = service.get_or_create_tag("machine-learning")?;
+    /// let meta_learning = service.get_or_create_tag("meta-learning")?;
+    /// service.create_alias("ml", machine_learning, "user", 1.0, None)?;
     ///
-    /// // Non-existent alias returns None
-    /// assert_eq!(service.resolve_alias("non-existent")?, None);
+    /// service.update_alias_canonical("ml", meta_learning)?;
+    /// assert_eq!(service.resolve_alias("ml")?, Some(meta_learning));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn resolve_alias(&self, name: &str) -> Result<Option<TagId>> {
-        // Normalize input before lookup
-        let normalized = TagNormalizer::normalize_tag(name);
-        let conn = self.db.connection();
+    pub fn update_alias_canonical(&self, alias: &str, new_canonical: TagId) -> Result<()> {
+        let normalized_alias = TagNormalizer::normalize_tag(alias);
 
-        // Query tag_aliases with COLLATE NOCASE matching
-        let result: Option<i64> = conn
-            .query_row(
-                "SELECT canonical_tag_id FROM tag_aliases WHERE alias = ?1 COLLATE NOCASE",
-                [&normalized],
-                |row| row.get(0),
-            )
-            .optional()?;
+        if self.resolve_alias(&normalized_alias)?.is_none() {
+            return Err(NotFound(format!("Alias '{}' does not exist", normalized_alias)).into());
+        }
 
-        Ok(result.map(TagId::new))
+        self.ensure_alias_target(new_canonical)?;
+
+        self.db.connection().execute(
+            "UPDATE tag_aliases SET canonical_tag_id = ?1 WHERE alias = ?2 COLLATE NOCASE",
+            rusqlite::params![new_canonical.get(), normalized_alias],
+        )?;
+
+        Ok(())
     }
 
-    /// Creates an alias mapping an alternate name to a canonical tag.
-    ///
-    /// Normalizes the alias before storage and verifies that:
-    /// - The canonical tag exists
-    /// - The canonical tag is not itself an alias (prevents chains)
+    /// Renames an alias, keeping its canonical tag and metadata.
     ///
-    /// Uses INSERT OR REPLACE for idempotent updates.
+    /// Use this to fix a misspelled alias; to point an alias at a different
+    /// tag, use [`update_alias_canonical`](Self::update_alias_canonical). Both
+    /// names are normalized. Renaming an alias to its own name is a no-op.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `alias` - The alias name to create
-    /// * `canonical_tag_id` - The canonical tag this alias resolves to
-    /// * `source` - The source of the alias ("user" or "llm")
-    /// * `confidence` - Confidence score (0.0-1.0)
-    /// * `model_version` - Optional model version for LLM-created aliases
+    /// Returns an error if `old_alias` does not exist, if `new_alias` is empty,
+    /// or if `new_alias` is already an alias or a tag name.
     ///
     /// # Examples
     ///
@@ -942,53 +2530,85 @@ impl NoteService {
     /// use cons::{Database, NoteService};
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let db = Database::in_memory()?;
-    /// let service = NoteService::new(db);
-    ///
-    /// // Create a canonical tag
-    /// let canonical_tag_id = service.get_or_create_tag("machine-learning")?;
-    ///
-    /// // Create a user alias
-    /// service.create_alias("ml", canonical_tag_id, "user", 1.0, None)?;
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let machine_learning = service.get_or_create_tag("machine-learning")?;
+    /// service.create_alias("machien-learning", machine_learning, "user", 1.0, None)?;
     ///
-    /// // Create an LLM alias
-    /// service.create_alias("ML", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))?;
+    /// service.rename_alias("machien-learning", "mach-learning")?;
+    /// assert_eq!(service.resolve_alias("mach-learning")?, Some(machine_learning));
+    /// assert_eq!(service.resolve_alias("machien-learning")?, None);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn create_alias(
-        &self,
-        alias: &str,
-        canonical_tag_id: TagId,
-        source: &str,
-        confidence: f64,
-        model_version: Option<&str>,
-    ) -> Result<()> {
-        // Normalize alias before storage
-        let normalized_alias = TagNormalizer::normalize_tag(alias);
-        let conn = self.db.connection();
-        let now = OffsetDateTime::now_utc().unix_timestamp();
+    pub fn rename_alias(&self, old_alias: &str, new_alias: &str) -> Result<()> {
+        let normalized_old = TagNormalizer::normalize_tag(old_alias);
+        let normalized_new = TagNormalizer::normalize_tag(new_alias);
 
-        // Verify canonical_tag_id exists in tags table
-        let tag_exists: bool = conn.query_row(
-            "SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)",
-            [canonical_tag_id.get()],
-            |row| row.get(0),
-        )?;
+        if self.resolve_alias(&normalized_old)?.is_none() {
+            return Err(NotFound(format!("Alias '{}' does not exist", normalized_old)).into());
+        }
+        if normalized_new.is_empty() {
+            anyhow::bail!("New alias name cannot be empty");
+        }
+        if normalized_new.eq_ignore_ascii_case(&normalized_old) {
+            return Ok(());
+        }
 
-        if !tag_exists {
-            anyhow::bail!("Canonical tag with id {} does not exist", canonical_tag_id);
+        if self.resolve_alias(&normalized_new)?.is_some() {
+            anyhow::bail!(
+                "Cannot rename alias '{}': alias '{}' already exists",
+                normalized_old,
+                normalized_new
+            );
         }
 
-        // Verify the tag name isn't already used as an alias (prevent chains)
-        // Get the tag name for the canonical_tag_id
-        let tag_name: String = conn.query_row(
-            "SELECT name FROM tags WHERE id = ?1",
-            [canonical_tag_id.get()],
+        let conn = self.db.connection();
+        let is_tag: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM tags WHERE name = ?1 COLLATE NOCASE)",
+            [&normalized_new],
             |row| row.get(0),
         )?;
+        if is_tag {
+            anyhow::bail!(
+                "Cannot rename alias '{}': '{}' is already a tag name",
+                normalized_old,
+                normalized_new
+            );
+        }
+
+        conn.execute(
+            "UPDATE tag_aliases SET alias = ?1 WHERE alias = ?2 COLLATE NOCASE",
+            rusqlite::params![normalized_new, normalized_old],
+        )?;
+
+        Ok(())
+    }
 
-        // Check if this tag name is already an alias
+    /// Verifies that a tag can be the target of an alias.
+    ///
+    /// The tag must exist and its name must not itself be an alias, which
+    /// would create an alias chain.
+    fn ensure_alias_target(&self, canonical_tag_id: TagId) -> Result<()> {
+        let conn = self.db.connection();
+
+        // Verify canonical_tag_id exists in tags table
+        let tag_name: Option<String> = conn
+            .query_row(
+                "SELECT name FROM tags WHERE id = ?1",
+                [canonical_tag_id.get()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(tag_name) = tag_name else {
+            return Err(NotFound(format!(
+                "Canonical tag with id {} does not exist",
+                canonical_tag_id
+            ))
+            .into());
+        };
+
+        // Check if this tag name is already an alias (prevent chains)
         let is_alias: bool = conn.query_row(
             "SELECT EXISTS(SELECT 1 FROM tag_aliases WHERE alias = ?1 COLLATE NOCASE)",
             [&tag_name],
@@ -1003,20 +2623,6 @@ impl NoteService {
             );
         }
 
-        // Insert with INSERT OR REPLACE for idempotent updates
-        conn.execute(
-            "INSERT OR REPLACE INTO tag_aliases (alias, canonical_tag_id, source, confidence, created_at, model_version)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            rusqlite::params![
-                normalized_alias,
-                canonical_tag_id.get(),
-                source,
-                confidence,
-                now,
-                model_version,
-            ],
-        )?;
-
         Ok(())
     }
 
@@ -1469,7 +3075,12 @@ impl NoteService {
     ///
     /// Uses SQLite FTS5 with BM25 relevance ranking to find notes matching the search query.
     /// All search terms must match (AND logic). Porter stemming automatically handles word
-    /// variations (e.g., "running" matches "run").
+    /// variations (e.g., "running" matches "run"). Wrapping the query in double quotes
+    /// (e.g., `"rust programming"`) searches for the exact phrase instead.
+    ///
+    /// **Operators**: An uppercase `OR` between two terms matches either term, and a
+    /// leading `-` excludes a term. For example, `rust OR python -tutorial` matches
+    /// notes mentioning rust or python but not tutorial.
     ///
     /// **Alias Expansion**: Before executing the search, each term is expanded using
     /// the `tag_aliases` table. For example, searching for "ML" will also match notes
@@ -1518,8 +3129,73 @@ impl NoteService {
     /// # }
     /// ```
     pub fn search_notes(&self, query: &str, limit: Option<usize>) -> Result<Vec<SearchResult>> {
+        self.search_notes_filtered(query, limit, &SearchFilters::default())
+    }
+
+    /// Counts all notes matching a search query, ignoring any limit.
+    ///
+    /// Uses the same query parsing and alias/broader-concept expansion as
+    /// [`search_notes`](Self::search_notes), so the count equals the number of
+    /// results a pager would traverse by paging through `search_notes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query is empty or contains only whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Learning Rust programming", Some(&["rust"]))?;
+    /// service.create_note("Rust ownership rules", None)?;
+    ///
+    /// assert_eq!(service.search_notes("rust", Some(1))?.len(), 1);
+    /// assert_eq!(service.search_notes_count("rust")?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_notes_count(&self, query: &str) -> Result<usize> {
+        let fts_query = self.build_fts_query(query)?;
+        self.execute_fts_count(&fts_query, &SearchFilters::default())
+    }
+
+    /// Searches notes using FTS5, restricted by the given filters.
+    ///
+    /// Behaves like [`search_notes`](Self::search_notes) but only returns notes
+    /// passing `filters`. Filters are applied in SQL before the limit, so the
+    /// limit counts matching notes only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService, SearchFilters};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    /// service.create_note("Learning Rust", None)?;
+    ///
+    /// // Nothing was created after the far future
+    /// let filters = SearchFilters {
+    ///     created_after: Some(4_102_444_800),
+    ///     ..Default::default()
+    /// };
+    /// assert!(service.search_notes_filtered("rust", None, &filters)?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_notes_filtered(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        filters: &SearchFilters,
+    ) -> Result<Vec<SearchResult>> {
         let fts_query = self.build_fts_query(query)?;
-        self.execute_fts_search(&fts_query, limit)
+        let filters = self.canonical_search_filters(filters)?;
+        self.execute_fts_search(&fts_query, limit, &filters)
     }
 
     /// Builds the expanded FTS query string for a search query.
@@ -1528,6 +3204,9 @@ impl NoteService {
     /// (for queries with fewer than 3 terms). The returned string can be used
     /// directly with FTS5 MATCH queries.
     ///
+    /// A query wrapped in double quotes becomes an FTS5 phrase query and is not
+    /// expanded, so only notes containing the words adjacently and in order match.
+    ///
     /// # Returns
     ///
     /// The expanded FTS query string, e.g., `("rust" OR "rustlang" OR "programming")`.
@@ -1538,33 +3217,112 @@ impl NoteService {
             anyhow::bail!("Search query cannot be empty");
         }
 
-        // Load query expansion configuration from environment
-        let config = QueryExpansionConfig::from_env();
+        // Quoted phrase: match the words adjacently, without term-by-term expansion
+        if let Some(phrase) = parse_phrase_query(trimmed_query) {
+            if phrase.is_empty() {
+                anyhow::bail!("Search query cannot be empty");
+            }
+            return Ok(format!("\"{}\"", phrase.replace('"', "\"\"")));
+        }
+
+        let config = QueryExpansionConfig::from(&self.config);
 
-        // Split query into terms and expand each with alias expansion
-        let terms: Vec<&str> = trimmed_query.split_whitespace().collect();
+        // Split query into OR-groups of positive terms and excluded terms
+        let parsed = parse_search_query(trimmed_query);
+        if parsed.groups.is_empty() {
+            anyhow::bail!("Search query must contain at least one term that is not excluded");
+        }
 
-        // Check if we should apply broader concept expansion (< 3 terms)
-        let should_expand = should_expand_broader(trimmed_query);
+        // Check if we should apply broader concept expansion (< 3 positive terms)
+        let positive_terms: Vec<&str> = parsed.groups.iter().flatten().copied().collect();
+        let should_expand = should_expand_broader(&positive_terms.join(" "));
 
         // Build FTS5 query with expansion for each term
-        // AND logic between original query terms, OR within expansions
-        let expanded_terms: Result<Vec<String>> = terms
-            .iter()
-            .map(|term| {
-                if should_expand {
-                    // Apply broader concept expansion
-                    self.build_expanded_fts_term_with_config(term, &config)
-                } else {
-                    // Only apply alias expansion for queries with 3+ terms
-                    self.build_expanded_fts_term(term)
-                }
-            })
-            .collect();
+        // AND logic between groups, OR within groups and within expansions
+        let mut expanded_groups = Vec::with_capacity(parsed.groups.len());
+        for group in &parsed.groups {
+            let expanded_terms: Result<Vec<String>> = group
+                .iter()
+                .map(|term| {
+                    if should_expand {
+                        // Apply broader concept expansion
+                        self.build_expanded_fts_term_with_config(term, &config)
+                    } else {
+                        // Only apply alias expansion for queries with 3+ terms
+                        self.build_expanded_fts_term(term)
+                    }
+                })
+                .collect();
+            let expanded_terms = expanded_terms?;
+
+            if expanded_terms.len() == 1 {
+                expanded_groups.extend(expanded_terms);
+            } else {
+                expanded_groups.push(format!("({})", expanded_terms.join(" OR ")));
+            }
+        }
 
         // Join with explicit AND for FTS5 when using parenthesized OR groups
         // FTS5 syntax requires explicit AND between parenthesized groups
-        Ok(expanded_terms?.join(" AND "))
+        let mut fts_query = expanded_groups.join(" AND ");
+
+        // Excluded terms are alias-expanded so excluding an alias excludes its tag too
+        for term in &parsed.excluded {
+            let expanded = self.build_expanded_fts_term(term)?;
+            fts_query = format!("({}) NOT {}", fts_query, expanded);
+        }
+
+        Ok(fts_query)
+    }
+
+    /// Returns the IDs of notes matching any excluded (`-term`) term in the query.
+    ///
+    /// Used to apply exclusions to the graph channel, which ignores query operators.
+    fn excluded_note_ids(&self, query: &str) -> Result<std::collections::HashSet<NoteId>> {
+        let parsed = parse_search_query(query.trim());
+        if parsed.excluded.is_empty() || parse_phrase_query(query).is_some() {
+            return Ok(std::collections::HashSet::new());
+        }
+
+        let expanded: Result<Vec<String>> = parsed
+            .excluded
+            .iter()
+            .map(|term| self.build_expanded_fts_term(term))
+            .collect();
+        let fts_query = expanded?.join(" OR ");
+
+        let conn = self.db.connection();
+        let mut stmt = conn
+            .prepare("SELECT note_id FROM notes_fts WHERE notes_fts MATCH ?1")
+            .map_err(fts_error)?;
+        let ids = stmt
+            .query_map([&fts_query], |row| row.get::<_, i64>(0))
+            .map_err(fts_error)?
+            .map(|id| id.map(NoteId::new))
+            .collect::<rusqlite::Result<_>>()
+            .map_err(fts_error)?;
+        Ok(ids)
+    }
+
+    /// Counts FTS5 matches for the given pre-built query string.
+    ///
+    /// Applies the same filters as [`execute_fts_search`](Self::execute_fts_search)
+    /// but ignores the offset.
+    fn execute_fts_count(&self, fts_query: &str, filters: &SearchFilters) -> Result<usize> {
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(fts_query.to_string())];
+        let filter_clause = fts_filter_clause(filters, &mut params);
+
+        let count: i64 = self
+            .db
+            .connection()
+            .query_row(
+                &format!("SELECT COUNT(*) FROM notes_fts WHERE notes_fts MATCH ?{filter_clause}"),
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| row.get(0),
+            )
+            .map_err(fts_error)?;
+
+        Ok(count as usize)
     }
 
     /// Executes an FTS5 search with the given pre-built query string.
@@ -1572,34 +3330,46 @@ impl NoteService {
         &self,
         fts_query: &str,
         limit: Option<usize>,
+        filters: &SearchFilters,
     ) -> Result<Vec<SearchResult>> {
         let conn = self.db.connection();
 
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(fts_query.to_string())];
+        let filter_clause = fts_filter_clause(filters, &mut params);
+
+        let limit_clause = limit_offset_clause(limit, filters.offset, &mut params);
+
         // Query FTS5 table with BM25 ranking, also selecting the score
         // ORDER BY bm25() ascending (lower/more negative scores are more relevant in FTS5)
-        let query_sql = if let Some(limit_val) = limit {
-            format!(
-                "SELECT note_id, bm25(notes_fts) as score FROM notes_fts
-                 WHERE notes_fts MATCH ?
-                 ORDER BY score
-                 LIMIT {}",
-                limit_val
-            )
-        } else {
-            "SELECT note_id, bm25(notes_fts) as score FROM notes_fts
-             WHERE notes_fts MATCH ?
-             ORDER BY score"
-                .to_string()
-        };
+        // Column 1 is `content`: the snippet is only kept when the body itself matched,
+        // which highlight() reveals by inserting the (unprintable) char(1) marker
+        let query_sql = format!(
+            "SELECT note_id, bm25(notes_fts) as score,
+                    snippet(notes_fts, 1, '[', ']', '...', 15),
+                    instr(highlight(notes_fts, 1, char(1), char(2)), char(1)) > 0
+             FROM notes_fts
+             WHERE notes_fts MATCH ?{}
+             ORDER BY score, note_id{}",
+            filter_clause, limit_clause
+        );
 
-        let mut stmt = conn.prepare(&query_sql)?;
-        let rows: Vec<(i64, f64)> = stmt
-            .query_map([fts_query], |row| Ok((row.get(0)?, row.get(1)?)))?
-            .collect::<Result<Vec<(i64, f64)>, _>>()?;
+        let mut stmt = conn.prepare(&query_sql).map_err(fts_error)?;
+        let rows: Vec<(i64, f64, Option<String>)> = stmt
+            .query_map(
+                rusqlite::params_from_iter(params.iter().map(|p| p.as_ref())),
+                |row| {
+                    let body_matched: bool = row.get(3)?;
+                    let snippet = if body_matched { row.get(2)? } else { None };
+                    Ok((row.get(0)?, row.get(1)?, snippet))
+                },
+            )
+            .map_err(fts_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(fts_error)?;
 
         // Load full Note objects and construct SearchResults with normalized scores
         let mut results = Vec::new();
-        for (id, raw_score) in rows {
+        for (id, raw_score, snippet) in rows {
             if let Some(note) = self.get_note(NoteId::new(id))? {
                 // Normalize BM25 score to 0.0-1.0 range (higher = more relevant)
                 // BM25 returns negative values where more negative = more relevant
@@ -1607,6 +3377,8 @@ impl NoteService {
                 results.push(SearchResult {
                     note,
                     relevance_score,
+                    snippet,
+                    explanation: None,
                 });
             }
         }
@@ -1614,6 +3386,73 @@ impl NoteService {
         Ok(results)
     }
 
+    /// Drops and recreates the full-text search index from the notes table.
+    ///
+    /// Recovers search after the `notes_fts` table has gone missing or become
+    /// corrupt; notes, tags, and enhancements are untouched. Each note's content,
+    /// enhanced content, and tag names are re-indexed. Returns the number of
+    /// notes indexed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Rust ownership rules", Some(&["rust"]))?;
+    ///
+    /// assert_eq!(service.rebuild_fts()?, 1);
+    /// assert_eq!(service.search_notes("ownership", None)?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rebuild_fts(&self) -> Result<usize> {
+        self.db.rebuild_fts()
+    }
+
+    /// Returns true if every note has exactly one full-text search row and
+    /// every search row belongs to a note.
+    ///
+    /// Compares the note ids in `notes` and `notes_fts`, and their row counts
+    /// to catch duplicates. The triggers keep them in step, so a mismatch means
+    /// the index has drifted (for example after manual edits) and some notes
+    /// are missing from search results; [`rebuild_fts`](Self::rebuild_fts)
+    /// repairs it. Only membership is checked: a row whose indexed text is
+    /// stale still counts as consistent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either table cannot be read, e.g. when `notes_fts`
+    /// is missing or corrupt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Rust ownership rules", Some(&["rust"]))?;
+    ///
+    /// assert!(service.fts_is_consistent()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fts_is_consistent(&self) -> Result<bool> {
+        let conn = self.db.connection();
+        let (notes, indexed, mismatched): (i64, i64, bool) = conn.query_row(
+            "SELECT
+                 (SELECT COUNT(*) FROM notes),
+                 (SELECT COUNT(*) FROM notes_fts),
+                 EXISTS (SELECT id FROM notes EXCEPT SELECT note_id FROM notes_fts)
+                     OR EXISTS (SELECT note_id FROM notes_fts EXCEPT SELECT id FROM notes)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        Ok(notes == indexed && !mismatched)
+    }
+
     /// Updates the enhancement fields for an existing note.
     ///
     /// This method is designed for the enhancement workflow where:
@@ -1621,6 +3460,10 @@ impl NoteService {
     /// 2. Enhancement is attempted
     /// 3. If successful, this method updates the note with enhancement data
     ///
+    /// The enhanced content is searchable immediately: the `notes_fts_update`
+    /// trigger re-indexes the note as part of the same `UPDATE` statement, so the
+    /// index never sees the new content without the new enhancement (or vice versa).
+    ///
     /// # Arguments
     ///
     /// * `note_id` - The ID of the note to update
@@ -1628,6 +3471,8 @@ impl NoteService {
     /// * `model` - The model identifier used for enhancement
     /// * `confidence` - Enhancement confidence score (0.0-1.0)
     /// * `enhanced_at` - Timestamp when enhancement occurred
+    /// * `prompt` - The prompt sent to the model, if known, so notes enhanced with an
+    ///   older prompt can be spotted later
     ///
     /// # Examples
     ///
@@ -1650,6 +3495,7 @@ impl NoteService {
     ///     "deepseek-r1:8b",
     ///     0.85,
     ///     now,
+    ///     Some("Expand this note: Quick thought"),
     /// )?;
     /// # Ok(())
     /// # }
@@ -1661,6 +3507,7 @@ impl NoteService {
         model: &str,
         confidence: f64,
         enhanced_at: OffsetDateTime,
+        prompt: Option<&str>,
     ) -> Result<()> {
         let conn = self.db.connection();
         let enhanced_timestamp = enhanced_at.unix_timestamp();
@@ -1671,13 +3518,15 @@ impl NoteService {
              SET content_enhanced = ?1,
                  enhanced_at = ?2,
                  enhancement_model = ?3,
-                 enhancement_confidence = ?4
-             WHERE id = ?5",
+                 enhancement_confidence = ?4,
+                 enhancement_prompt = ?5
+             WHERE id = ?6",
             (
                 content_enhanced,
                 enhanced_timestamp,
                 model,
                 confidence,
+                prompt,
                 note_id.get(),
             ),
         )?;
@@ -1685,6 +3534,73 @@ impl NoteService {
         Ok(())
     }
 
+    /// Updates the content of an existing note in place.
+    ///
+    /// Replaces `notes.content` and bumps `updated_at`, preserving the note ID,
+    /// `created_at`, and all tag assignments. Any existing enhancement is cleared
+    /// because it was derived from the previous content; callers that want a fresh
+    /// enhancement should re-run the enhancer afterwards. The FTS index is refreshed
+    /// by the `notes_fts_update` trigger.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The unique identifier of the note to update
+    /// * `content` - The new note content (cannot be empty or whitespace-only)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is empty or if no note exists with the given ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// let note = service.create_note("Fix the tpyo", Some(&["writing"]))?;
+    /// service.update_note_content(note.id(), "Fix the typo")?;
+    ///
+    /// let updated = service.get_note(note.id())?.expect("note should exist");
+    /// assert_eq!(updated.content(), "Fix the typo");
+    /// assert_eq!(updated.tags().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_note_content(&self, id: NoteId, content: &str) -> Result<()> {
+        if content.trim().is_empty() {
+            anyhow::bail!("Note content cannot be empty");
+        }
+
+        let conn = self.db.connection();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        // Clear stale enhancement fields along with the content change
+        let rows_affected = conn.execute(
+            "UPDATE notes
+             SET content = ?1,
+                 updated_at = ?2,
+                 content_enhanced = NULL,
+                 enhanced_at = NULL,
+                 enhancement_model = NULL,
+                 enhancement_confidence = NULL,
+                 enhancement_prompt = NULL,
+                 content_hash = ?3
+             WHERE id = ?4",
+            (content, now, content_hash(content), id.get()),
+        )?;
+
+        if rows_affected == 0 {
+            return Err(NotFound(format!("Note with id {} does not exist", id)).into());
+        }
+
+        self.replace_links(id, content)?;
+
+        Ok(())
+    }
+
     /// Gets all tags that have at least one associated note.
     ///
     /// Queries the tags table using JOIN with note_tags to filter for tags
@@ -1798,6 +3714,316 @@ impl NoteService {
         Ok(tags)
     }
 
+    /// Lists all tags with the number of notes using each one.
+    ///
+    /// Unlike `get_tags_with_notes`, this LEFT JOINs `note_tags` so orphan tags
+    /// (tags with no notes) are included with a count of zero, which makes unused
+    /// tags easy to find and clean up.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of tuples containing (TagId, tag name, note count),
+    /// ordered by tag name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// service.create_note("Rust note", Some(&["rust"]))?;
+    /// service.get_or_create_tag("unused")?;
+    ///
+    /// let tags = service.list_tags_with_counts()?;
+    /// assert_eq!(tags.len(), 2);
+    /// assert_eq!(tags[0].1, "rust");
+    /// assert_eq!(tags[0].2, 1);
+    /// assert_eq!(tags[1].2, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_tags_with_counts(&self) -> Result<Vec<(TagId, String, usize)>> {
+        let conn = self.db.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, COUNT(nt.note_id) as note_count
+             FROM tags t
+             LEFT JOIN note_tags nt ON t.id = nt.tag_id
+             GROUP BY t.id, t.name
+             ORDER BY t.name",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let id: i64 = row.get(0)?;
+            let name: String = row.get(1)?;
+            let note_count: usize = row.get(2)?;
+            Ok((TagId::new(id), name, note_count))
+        })?;
+
+        let mut tags = Vec::new();
+        for row_result in rows {
+            tags.push(row_result?);
+        }
+
+        Ok(tags)
+    }
+
+    /// Counts how often pairs of tags appear together on the same note.
+    ///
+    /// Returns `(tag, tag, count)` for every pair sharing at least `min_count`
+    /// notes, with the two names in alphabetical order. Pairs are ordered by
+    /// count descending, then by name. Frequent pairs are cheap candidates for
+    /// hierarchy edges or aliases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Async in Rust", Some(&["rust", "tokio"]))?;
+    /// service.create_note("Tokio runtime", Some(&["tokio", "rust", "async"]))?;
+    ///
+    /// let pairs = service.tag_cooccurrence(2)?;
+    /// assert_eq!(pairs, vec![("rust".to_string(), "tokio".to_string(), 2)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn tag_cooccurrence(&self, min_count: usize) -> Result<Vec<(String, String, usize)>> {
+        let conn = self.db.connection();
+
+        // Self-join on note_tags; a.tag_id < b.tag_id counts each pair once
+        let mut stmt = conn.prepare(
+            "SELECT MIN(ta.name, tb.name), MAX(ta.name, tb.name), COUNT(*) AS together
+             FROM note_tags a
+             JOIN note_tags b ON a.note_id = b.note_id AND a.tag_id < b.tag_id
+             JOIN tags ta ON ta.id = a.tag_id
+             JOIN tags tb ON tb.id = b.tag_id
+             GROUP BY a.tag_id, b.tag_id
+             HAVING together >= ?1
+             ORDER BY together DESC, 1, 2",
+        )?;
+
+        let rows = stmt.query_map([min_count], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get::<_, usize>(2)?))
+        })?;
+
+        let mut pairs = Vec::new();
+        for row_result in rows {
+            pairs.push(row_result?);
+        }
+
+        Ok(pairs)
+    }
+
+    /// Lists the names of the `limit` most recently applied tags.
+    ///
+    /// Tags are ordered by their latest assignment to any note, newest first,
+    /// which suits tag-entry completion better than the usage counts from
+    /// [`list_tags_with_counts`](Self::list_tags_with_counts). Assignments made
+    /// in the same second are ordered by insertion. Orphan tags are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// service.create_note("Older note", Some(&["rust"]))?;
+    /// service.create_note("Newer note", Some(&["cli"]))?;
+    ///
+    /// assert_eq!(service.recent_tags(1)?, vec!["cli"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recent_tags(&self, limit: usize) -> Result<Vec<String>> {
+        let conn = self.db.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.name
+             FROM tags t
+             JOIN note_tags nt ON t.id = nt.tag_id
+             GROUP BY t.id, t.name
+             ORDER BY MAX(nt.created_at) DESC, MAX(nt.rowid) DESC
+             LIMIT ?1",
+        )?;
+
+        let names = stmt
+            .query_map([limit as i64], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(names)
+    }
+
+    /// Renames a tag, merging it into an existing tag if the new name is taken.
+    ///
+    /// Both names are normalized first. If no tag named `new` exists, the old tag
+    /// is renamed in place. Otherwise the old tag is merged into the existing one:
+    /// its note assignments, edges, and aliases are re-pointed to the surviving tag
+    /// (assignments the note already has are kept as-is), the old tag is deleted,
+    /// and degree centrality is recomputed. Runs in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The name of the tag to rename
+    /// * `new` - The new tag name (or the name of the tag to merge into)
+    ///
+    /// # Returns
+    ///
+    /// Returns the TagId of the surviving tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either name is empty after normalization or if no tag
+    /// named `old` exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// service.create_note("Gradient descent", Some(&["ml"]))?;
+    /// let canonical = service.get_or_create_tag("machine-learning")?;
+    ///
+    /// // "ml" already has a real tag, so it is merged into "machine-learning"
+    /// let merged = service.rename_tag("ml", "machine-learning")?;
+    /// assert_eq!(merged, canonical);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rename_tag(&self, old: &str, new: &str) -> Result<TagId> {
+        let old_name = TagNormalizer::normalize_tag(old);
+        let new_name = TagNormalizer::normalize_tag(new);
+
+        if old_name.is_empty() || new_name.is_empty() {
+            anyhow::bail!("Tag name cannot be empty");
+        }
+
+        let conn = self.db.connection();
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<TagId> = (|| {
+            let old_id: i64 = conn
+                .query_row("SELECT id FROM tags WHERE name = ?1", [&old_name], |row| {
+                    row.get(0)
+                })
+                .optional()?
+                .ok_or_else(|| NotFound(format!("Tag '{}' does not exist", old_name)))?;
+
+            let existing_id: Option<i64> = conn
+                .query_row("SELECT id FROM tags WHERE name = ?1", [&new_name], |row| {
+                    row.get(0)
+                })
+                .optional()?;
+
+            let new_id = match existing_id {
+                // Same tag (e.g., a case-only change), nothing to merge
+                Some(id) if id == old_id => return Ok(TagId::new(old_id)),
+                Some(id) => id,
+                None => {
+                    conn.execute(
+                        "UPDATE tags SET name = ?1 WHERE id = ?2",
+                        rusqlite::params![new_name, old_id],
+                    )?;
+
+                    // Tag names are denormalized into the FTS index, so refresh affected notes
+                    conn.execute(
+                        "DELETE FROM notes_fts
+                         WHERE note_id IN (SELECT note_id FROM note_tags WHERE tag_id = ?1)",
+                        [old_id],
+                    )?;
+                    conn.execute(
+                        "INSERT INTO notes_fts (note_id, content, content_enhanced, tags)
+                         SELECT
+                             n.id,
+                             n.content,
+                             n.content_enhanced,
+                             (SELECT GROUP_CONCAT(t.name, ' ')
+                              FROM note_tags nt2
+                              JOIN tags t ON nt2.tag_id = t.id
+                              WHERE nt2.note_id = n.id)
+                         FROM notes n
+                         WHERE n.id IN (SELECT note_id FROM note_tags WHERE tag_id = ?1)",
+                        [old_id],
+                    )?;
+
+                    return Ok(TagId::new(old_id));
+                }
+            };
+
+            // Re-point note assignments (INSERT/DELETE so the FTS triggers fire).
+            // The (note_id, tag_id) primary key makes existing assignments win.
+            conn.execute(
+                "INSERT OR IGNORE INTO note_tags
+                 (note_id, tag_id, confidence, source, created_at, verified, model_version)
+                 SELECT note_id, ?1, confidence, source, created_at, verified, model_version
+                 FROM note_tags WHERE tag_id = ?2",
+                [new_id, old_id],
+            )?;
+            conn.execute("DELETE FROM note_tags WHERE tag_id = ?1", [old_id])?;
+
+            // Migrate edges, dropping self-loops and duplicates created by the merge
+            conn.execute(
+                "UPDATE edges SET source_tag_id = ?1 WHERE source_tag_id = ?2",
+                [new_id, old_id],
+            )?;
+            conn.execute(
+                "UPDATE edges SET target_tag_id = ?1 WHERE target_tag_id = ?2",
+                [new_id, old_id],
+            )?;
+            conn.execute("DELETE FROM edges WHERE source_tag_id = target_tag_id", [])?;
+            conn.execute(
+                "DELETE FROM edges
+                 WHERE (source_tag_id = ?1 OR target_tag_id = ?1)
+                   AND id NOT IN (
+                       SELECT MIN(id) FROM edges
+                       GROUP BY source_tag_id, target_tag_id, valid_from, valid_until
+                   )",
+                [new_id],
+            )?;
+
+            // Migrate aliases
+            conn.execute(
+                "UPDATE tag_aliases SET canonical_tag_id = ?1 WHERE canonical_tag_id = ?2",
+                [new_id, old_id],
+            )?;
+
+            conn.execute("DELETE FROM tags WHERE id = ?1", [old_id])?;
+
+            // Recompute degree centrality from the edges table
+            conn.execute(
+                "UPDATE tags SET degree_centrality = (
+                    SELECT COUNT(*) FROM edges
+                    WHERE source_tag_id = tags.id OR target_tag_id = tags.id
+                )",
+                [],
+            )?;
+
+            Ok(TagId::new(new_id))
+        })();
+
+        match result {
+            Ok(tag_id) => {
+                conn.execute("COMMIT", [])?;
+                Ok(tag_id)
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
     /// Creates an edge between two tags in the hierarchy.
     ///
     /// Inserts a directed edge from source_tag_id (narrower/child concept) to
@@ -1847,9 +4073,10 @@ impl NoteService {
         confidence: f64,
         hierarchy_type: &str,
         model_version: Option<&str>,
-        now: i64,
+        source: &str,
     ) -> Result<()> {
         let conn = self.db.connection();
+        let now = OffsetDateTime::now_utc().unix_timestamp();
 
         // Validate both tag IDs exist
         let source_exists: bool = conn.query_row(
@@ -1865,11 +4092,19 @@ impl NoteService {
         )?;
 
         if !source_exists {
-            anyhow::bail!("Source tag with id {} does not exist", source_tag_id);
+            return Err(NotFound(format!(
+                "Source tag with id {} does not exist",
+                source_tag_id
+            ))
+            .into());
         }
 
         if !target_exists {
-            anyhow::bail!("Target tag with id {} does not exist", target_tag_id);
+            return Err(NotFound(format!(
+                "Target tag with id {} does not exist",
+                target_tag_id
+            ))
+            .into());
         }
 
         // Check if edge already exists (for idempotent operation)
@@ -1887,45 +4122,174 @@ impl NoteService {
             return Ok(());
         }
 
+        // Reject edges that would close a loop: if the broader tag can already reach
+        // the narrower tag by following broader edges, this edge would create a cycle
+        if let Some(path) = self.find_broader_path(target_tag_id, source_tag_id)? {
+            let mut names = vec![self.tag_name(source_tag_id)?];
+            for tag_id in path {
+                names.push(self.tag_name(tag_id)?);
+            }
+            return Err(InvalidInput(format!(
+                "Cannot add edge: it would create a cycle: {}",
+                names.join(" -> ")
+            ))
+            .into());
+        }
+
         // Insert edge
         conn.execute(
             "INSERT INTO edges
              (source_tag_id, target_tag_id, confidence, hierarchy_type, source, model_version, verified, created_at, updated_at, valid_from, valid_until)
-             VALUES (?1, ?2, ?3, ?4, 'llm', ?5, 0, ?6, ?6, NULL, NULL)",
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?7, NULL, NULL)",
             rusqlite::params![
                 source_tag_id.get(),
                 target_tag_id.get(),
                 confidence,
                 hierarchy_type,
+                source,
                 model_version,
                 now,
             ],
         )?;
 
-        // Increment degree_centrality for both tags
-        conn.execute(
-            "UPDATE tags SET degree_centrality = degree_centrality + 1 WHERE id = ?",
-            [source_tag_id.get()],
-        )?;
+        // Increment degree_centrality for both tags
+        conn.execute(
+            "UPDATE tags SET degree_centrality = degree_centrality + 1 WHERE id = ?",
+            [source_tag_id.get()],
+        )?;
+
+        conn.execute(
+            "UPDATE tags SET degree_centrality = degree_centrality + 1 WHERE id = ?",
+            [target_tag_id.get()],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_edge(
+        &self,
+        source_tag_id: TagId,
+        target_tag_id: TagId,
+        confidence: f64,
+        hierarchy_type: &str,
+        model_version: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.db.connection();
+
+        // Use a transaction for atomicity (edge insert + centrality updates)
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result = self.create_edge_internal(
+            source_tag_id,
+            target_tag_id,
+            confidence,
+            hierarchy_type,
+            model_version,
+            "llm",
+        );
+
+        match result {
+            Ok(()) => {
+                conn.execute("COMMIT", [])?;
+                Ok(())
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Finds a path from `from` to `to` following broader (source -> target) edges.
+    ///
+    /// Breadth-first, so the returned path (`from` first, `to` last) is a shortest one.
+    /// Only permanent edges (no `valid_from`/`valid_until`) are followed, matching the
+    /// hierarchy edges [`create_edge`](Self::create_edge) checks for duplicates.
+    /// Returns `None` if `to` is not reachable.
+    fn find_broader_path(&self, from: TagId, to: TagId) -> Result<Option<Vec<TagId>>> {
+        use std::collections::hash_map::Entry;
+        use std::collections::{HashMap, VecDeque};
 
-        conn.execute(
-            "UPDATE tags SET degree_centrality = degree_centrality + 1 WHERE id = ?",
-            [target_tag_id.get()],
+        let conn = self.db.connection();
+        let mut stmt = conn.prepare(
+            "SELECT target_tag_id FROM edges
+             WHERE source_tag_id = ?1 AND valid_from IS NULL AND valid_until IS NULL",
         )?;
 
-        Ok(())
+        // Maps each visited tag to the tag it was reached from
+        let mut came_from: HashMap<TagId, Option<TagId>> = HashMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![current];
+                let mut step = current;
+                while let Some(Some(previous)) = came_from.get(&step) {
+                    path.push(*previous);
+                    step = *previous;
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+
+            let broader = stmt
+                .query_map([current.get()], |row| row.get::<_, i64>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for next in broader.into_iter().map(TagId::new) {
+                if let Entry::Vacant(entry) = came_from.entry(next) {
+                    entry.insert(Some(current));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Ok(None)
     }
 
-    pub fn create_edge(
+    /// Returns a tag's name by id.
+    fn tag_name(&self, tag_id: TagId) -> Result<String> {
+        let name = self.db.connection().query_row(
+            "SELECT name FROM tags WHERE id = ?1",
+            [tag_id.get()],
+            |row| row.get(0),
+        )?;
+        Ok(name)
+    }
+
+    /// Creates a user-asserted edge between two tags in the hierarchy.
+    ///
+    /// Like [`create_edge`](Self::create_edge), but the edge is recorded with
+    /// `source = 'user'` and no model version. A tag cannot be its own broader concept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    ///
+    /// let tokio = service.get_or_create_tag("tokio")?;
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// service.create_user_edge(tokio, rust, 1.0, "partitive")?;
+    ///
+    /// assert_eq!(service.list_edges()?[0].source(), "user");
+    /// assert!(service.create_user_edge(rust, rust, 1.0, "generic").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_user_edge(
         &self,
         source_tag_id: TagId,
         target_tag_id: TagId,
         confidence: f64,
         hierarchy_type: &str,
-        model_version: Option<&str>,
     ) -> Result<()> {
+        if source_tag_id == target_tag_id {
+            return Err(InvalidInput("An edge cannot connect a tag to itself".to_string()).into());
+        }
+
         let conn = self.db.connection();
-        let now = OffsetDateTime::now_utc().unix_timestamp();
 
         // Use a transaction for atomicity (edge insert + centrality updates)
         conn.execute("BEGIN TRANSACTION", [])?;
@@ -1935,8 +4299,8 @@ impl NoteService {
             target_tag_id,
             confidence,
             hierarchy_type,
-            model_version,
-            now,
+            None,
+            "user",
         );
 
         match result {
@@ -2000,7 +4364,6 @@ impl NoteService {
 
         let result: Result<usize> = (|| {
             let mut count = 0;
-            let now = OffsetDateTime::now_utc().unix_timestamp();
 
             for (source_tag_id, target_tag_id, confidence, hierarchy_type, model_version) in edges {
                 self.create_edge_internal(
@@ -2009,7 +4372,7 @@ impl NoteService {
                     *confidence,
                     hierarchy_type,
                     *model_version,
-                    now,
+                    "llm",
                 )?;
                 count += 1;
             }
@@ -2083,28 +4446,290 @@ impl NoteService {
             Ok(TagId::new(target_id))
         })?;
 
-        let mut broader_concepts = Vec::new();
-        for row_result in rows {
-            broader_concepts.push(row_result?);
+        let mut broader_concepts = Vec::new();
+        for row_result in rows {
+            broader_concepts.push(row_result?);
+        }
+
+        Ok(broader_concepts)
+    }
+
+    /// Retrieves broader concepts with their tag names.
+    ///
+    /// Combines broader concept retrieval with tag name lookup, returning both
+    /// TagId and tag name for each broader concept. This is useful for building
+    /// FTS queries with expanded terms.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag_id` - The tag to find broader concepts for
+    /// * `min_confidence` - Minimum confidence threshold (0.0-1.0) for edges
+    ///
+    /// # Returns
+    ///
+    /// Returns `Vec<(TagId, String)>` of (tag_id, tag_name) pairs, ordered by confidence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// // Create tag hierarchy
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// let programming = service.get_or_create_tag("programming")?;
+    /// service.create_edge(rust, programming, 0.9, "generic", Some("test"))?;
+    ///
+    /// // Get broader concept names
+    /// let broader_names = service.get_broader_concept_names(rust, 0.7)?;
+    /// assert_eq!(broader_names.len(), 1);
+    /// assert_eq!(broader_names[0].1, "programming");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_broader_concept_names(
+        &self,
+        tag_id: TagId,
+        min_confidence: f64,
+    ) -> Result<Vec<(TagId, String)>> {
+        let conn = self.db.connection();
+
+        let mut stmt = conn.prepare(
+            "SELECT e.target_tag_id, t.name
+             FROM edges e
+             JOIN tags t ON e.target_tag_id = t.id
+             WHERE e.source_tag_id = ?1
+               AND e.hierarchy_type = 'generic'
+               AND e.confidence >= ?2
+             ORDER BY e.confidence DESC",
+        )?;
+
+        let rows = stmt.query_map(rusqlite::params![tag_id.get(), min_confidence], |row| {
+            let target_id: i64 = row.get(0)?;
+            let name: String = row.get(1)?;
+            Ok((TagId::new(target_id), name))
+        })?;
+
+        let mut broader_concepts = Vec::new();
+        for row_result in rows {
+            broader_concepts.push(row_result?);
+        }
+
+        Ok(broader_concepts)
+    }
+
+    /// Deletes an edge between two tags in the hierarchy.
+    ///
+    /// Removes the directed edge from source_tag_id to target_tag_id and decrements
+    /// the degree_centrality for both tags. Uses a transaction to ensure atomicity.
+    /// This operation is idempotent - deleting a non-existent edge succeeds without error.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_tag_id` - The source tag of the edge to delete
+    /// * `target_tag_id` - The target tag of the edge to delete
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// // Create tags and edge
+    /// let tag1 = service.get_or_create_tag("tag1")?;
+    /// let tag2 = service.get_or_create_tag("tag2")?;
+    /// service.create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))?;
+    ///
+    /// // Delete the edge
+    /// service.delete_edge(tag1, tag2)?;
+    ///
+    /// // Deleting again is idempotent (no error)
+    /// service.delete_edge(tag1, tag2)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn delete_edge(&self, source_tag_id: TagId, target_tag_id: TagId) -> Result<()> {
+        let conn = self.db.connection();
+
+        // Use a transaction for atomicity (edge delete + centrality updates)
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        match self.delete_edge_internal(source_tag_id, target_tag_id) {
+            Ok(_) => {
+                conn.execute("COMMIT", [])?;
+                Ok(())
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Internal helper to delete an edge without managing transactions.
+    ///
+    /// This method is used internally by both delete_edge() and prune_edges()
+    /// to avoid nested transactions. The caller is responsible for transaction management.
+    /// Returns whether an edge was actually removed.
+    fn delete_edge_internal(&self, source_tag_id: TagId, target_tag_id: TagId) -> Result<bool> {
+        let conn = self.db.connection();
+
+        // Delete the edge
+        let rows_deleted = conn.execute(
+            "DELETE FROM edges
+             WHERE source_tag_id = ?1 AND target_tag_id = ?2
+               AND valid_from IS NULL AND valid_until IS NULL",
+            [source_tag_id.get(), target_tag_id.get()],
+        )?;
+
+        if rows_deleted == 0 {
+            // Edge doesn't exist, this is a no-op (idempotent)
+            return Ok(false);
+        }
+
+        // Decrement degree_centrality for both tags, but ensure it never goes negative
+        // Use MAX(0, degree_centrality - 1) to prevent negative values
+        conn.execute(
+            "UPDATE tags SET degree_centrality = MAX(0, degree_centrality - 1) WHERE id = ?",
+            [source_tag_id.get()],
+        )?;
+
+        conn.execute(
+            "UPDATE tags SET degree_centrality = MAX(0, degree_centrality - 1) WHERE id = ?",
+            [target_tag_id.get()],
+        )?;
+
+        Ok(true)
+    }
+
+    /// Deletes all hierarchy edges with confidence below `min_confidence`.
+    ///
+    /// Useful for cleaning out weak relationships after `hierarchy suggest`. Each
+    /// removed edge decrements degree_centrality for both of its tags, exactly as
+    /// [`delete_edge`](Self::delete_edge) does, and the whole prune runs in a single
+    /// transaction. Edges without a stored confidence are treated as 1.0 and kept.
+    ///
+    /// Returns the number of edges removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Database::in_memory()?;
+    /// let service = NoteService::new(db);
+    ///
+    /// let tokio = service.get_or_create_tag("tokio")?;
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// let async_tag = service.get_or_create_tag("async")?;
+    /// service.create_edge(tokio, rust, 0.9, "partitive", Some("test-model"))?;
+    /// service.create_edge(tokio, async_tag, 0.5, "generic", Some("test-model"))?;
+    ///
+    /// assert_eq!(service.prune_edges(0.7)?, 1);
+    /// assert_eq!(service.list_edges()?.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prune_edges(&self, min_confidence: f64) -> Result<usize> {
+        let conn = self.db.connection();
+
+        conn.execute("BEGIN TRANSACTION", [])?;
+
+        let result: Result<usize> = (|| {
+            let weak_edges: Vec<(TagId, TagId)> = {
+                let mut stmt = conn.prepare(
+                    "SELECT source_tag_id, target_tag_id FROM edges
+                     WHERE COALESCE(confidence, 1.0) < ?1
+                       AND valid_from IS NULL AND valid_until IS NULL",
+                )?;
+                stmt.query_map([min_confidence], |row| {
+                    Ok((TagId::new(row.get(0)?), TagId::new(row.get(1)?)))
+                })?
+                .collect::<Result<_, _>>()?
+            };
+
+            let mut removed = 0;
+            for (source_tag_id, target_tag_id) in weak_edges {
+                if self.delete_edge_internal(source_tag_id, target_tag_id)? {
+                    removed += 1;
+                }
+            }
+
+            Ok(removed)
+        })();
+
+        match result {
+            Ok(removed) => {
+                conn.execute("COMMIT", [])?;
+                Ok(removed)
+            }
+            Err(e) => {
+                conn.execute("ROLLBACK", []).ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Marks a hierarchy edge as verified by the user.
+    ///
+    /// Verified edges can be boosted during graph search through
+    /// `CONS_VERIFIED_MULTIPLIER`. Verifying an already-verified edge is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no edge from `source_tag_id` to `target_tag_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    ///
+    /// let tokio = service.get_or_create_tag("tokio")?;
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// service.create_edge(tokio, rust, 0.8, "partitive", Some("test-model"))?;
+    ///
+    /// service.verify_edge(tokio, rust)?;
+    /// assert!(service.list_edges()?[0].verified());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_edge(&self, source_tag_id: TagId, target_tag_id: TagId) -> Result<()> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+
+        let updated = self.db.connection().execute(
+            "UPDATE edges SET verified = 1, updated_at = ?3
+             WHERE source_tag_id = ?1 AND target_tag_id = ?2
+               AND valid_from IS NULL AND valid_until IS NULL",
+            [source_tag_id.get(), target_tag_id.get(), now],
+        )?;
+
+        if updated == 0 {
+            let name = |tag_id: TagId| self.tag_name(tag_id).unwrap_or_else(|_| tag_id.to_string());
+            return Err(NotFound(format!(
+                "Edge '{}' -> '{}' does not exist",
+                name(source_tag_id),
+                name(target_tag_id)
+            ))
+            .into());
         }
 
-        Ok(broader_concepts)
+        Ok(())
     }
 
-    /// Retrieves broader concepts with their tag names.
-    ///
-    /// Combines broader concept retrieval with tag name lookup, returning both
-    /// TagId and tag name for each broader concept. This is useful for building
-    /// FTS queries with expanded terms.
-    ///
-    /// # Arguments
-    ///
-    /// * `tag_id` - The tag to find broader concepts for
-    /// * `min_confidence` - Minimum confidence threshold (0.0-1.0) for edges
+    /// Lists all hierarchy edges with resolved tag names.
     ///
-    /// # Returns
-    ///
-    /// Returns `Vec<(TagId, String)>` of (tag_id, tag_name) pairs, ordered by confidence.
+    /// Edges are ordered by broader (target) tag name, then narrower (source) tag name.
+    /// A missing `hierarchy_type` is reported as 'generic' and a missing confidence as 1.0.
     ///
     /// # Examples
     ///
@@ -2112,62 +4737,62 @@ impl NoteService {
     /// use cons::{Database, NoteService};
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let db = Database::in_memory()?;
-    /// let service = NoteService::new(db);
+    /// let service = NoteService::new(Database::in_memory()?);
     ///
-    /// // Create tag hierarchy
+    /// let tokio = service.get_or_create_tag("tokio")?;
     /// let rust = service.get_or_create_tag("rust")?;
-    /// let programming = service.get_or_create_tag("programming")?;
-    /// service.create_edge(rust, programming, 0.9, "generic", Some("test"))?;
+    /// service.create_edge(tokio, rust, 0.8, "partitive", Some("test-model"))?;
     ///
-    /// // Get broader concept names
-    /// let broader_names = service.get_broader_concept_names(rust, 0.7)?;
-    /// assert_eq!(broader_names.len(), 1);
-    /// assert_eq!(broader_names[0].1, "programming");
+    /// let edges = service.list_edges()?;
+    /// assert_eq!(edges.len(), 1);
+    /// assert_eq!(edges[0].source_name(), "tokio");
+    /// assert_eq!(edges[0].target_name(), "rust");
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_broader_concept_names(
-        &self,
-        tag_id: TagId,
-        min_confidence: f64,
-    ) -> Result<Vec<(TagId, String)>> {
+    pub fn list_edges(&self) -> Result<Vec<EdgeInfo>> {
         let conn = self.db.connection();
 
         let mut stmt = conn.prepare(
-            "SELECT e.target_tag_id, t.name
+            "SELECT e.source_tag_id, s.name, e.target_tag_id, t.name,
+                    COALESCE(e.hierarchy_type, 'generic'), COALESCE(e.confidence, 1.0),
+                    COALESCE(e.source, 'user'), COALESCE(e.verified, 0)
              FROM edges e
+             JOIN tags s ON e.source_tag_id = s.id
              JOIN tags t ON e.target_tag_id = t.id
-             WHERE e.source_tag_id = ?1
-               AND e.hierarchy_type = 'generic'
-               AND e.confidence >= ?2
-             ORDER BY e.confidence DESC",
+             ORDER BY t.name, s.name",
         )?;
 
-        let rows = stmt.query_map(rusqlite::params![tag_id.get(), min_confidence], |row| {
-            let target_id: i64 = row.get(0)?;
-            let name: String = row.get(1)?;
-            Ok((TagId::new(target_id), name))
-        })?;
-
-        let mut broader_concepts = Vec::new();
-        for row_result in rows {
-            broader_concepts.push(row_result?);
-        }
+        let edges = stmt
+            .query_map([], |row| {
+                let mut edge = EdgeInfo::new(
+                    TagId::new(row.get(0)?),
+                    row.get::<_, String>(1)?,
+                    TagId::new(row.get(2)?),
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get(5)?,
+                    row.get::<_, String>(6)?,
+                );
+                if row.get::<_, bool>(7)? {
+                    edge.verify();
+                }
+                Ok(edge)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(broader_concepts)
+        Ok(edges)
     }
 
-    /// Deletes an edge between two tags in the hierarchy.
+    /// Lists tags that take part in at least one hierarchy edge, with their degree centrality.
     ///
-    /// Removes the directed edge from source_tag_id to target_tag_id and decrements
-    /// the degree_centrality for both tags. Uses a transaction to ensure atomicity.
-    /// This operation is idempotent - deleting a non-existent edge succeeds without error.
+    /// Unlike `get_tags_with_stats`, tags without notes are included, since the
+    /// hierarchy can reference tags that only exist as broader concepts.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `source_tag_id` - The source tag of the edge to delete
-    /// * `target_tag_id` - The target tag of the edge to delete
+    /// Returns a vector of tuples containing (TagId, tag name, degree centrality),
+    /// ordered by tag name.
     ///
     /// # Examples
     ///
@@ -2175,76 +4800,37 @@ impl NoteService {
     /// use cons::{Database, NoteService};
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let db = Database::in_memory()?;
-    /// let service = NoteService::new(db);
-    ///
-    /// // Create tags and edge
-    /// let tag1 = service.get_or_create_tag("tag1")?;
-    /// let tag2 = service.get_or_create_tag("tag2")?;
-    /// service.create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))?;
+    /// let service = NoteService::new(Database::in_memory()?);
     ///
-    /// // Delete the edge
-    /// service.delete_edge(tag1, tag2)?;
+    /// let tokio = service.get_or_create_tag("tokio")?;
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// service.get_or_create_tag("unrelated")?;
+    /// service.create_edge(tokio, rust, 0.8, "partitive", None)?;
     ///
-    /// // Deleting again is idempotent (no error)
-    /// service.delete_edge(tag1, tag2)?;
+    /// let tags = service.list_hierarchy_tags()?;
+    /// assert_eq!(tags.len(), 2);
+    /// assert_eq!(tags[0].1, "rust");
+    /// assert_eq!(tags[0].2, 1);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn delete_edge(&self, source_tag_id: TagId, target_tag_id: TagId) -> Result<()> {
+    pub fn list_hierarchy_tags(&self) -> Result<Vec<(TagId, String, i64)>> {
         let conn = self.db.connection();
 
-        // Use a transaction for atomicity (edge delete + centrality updates)
-        conn.execute("BEGIN TRANSACTION", [])?;
-
-        let result: Result<()> = (|| {
-            // Check if edge exists before attempting delete
-            let exists: bool = conn.query_row(
-                "SELECT EXISTS(SELECT 1 FROM edges
-                 WHERE source_tag_id = ?1 AND target_tag_id = ?2
-                   AND valid_from IS NULL AND valid_until IS NULL)",
-                [source_tag_id.get(), target_tag_id.get()],
-                |row| row.get(0),
-            )?;
-
-            if !exists {
-                // Edge doesn't exist, this is a no-op (idempotent)
-                return Ok(());
-            }
-
-            // Delete the edge
-            conn.execute(
-                "DELETE FROM edges
-                 WHERE source_tag_id = ?1 AND target_tag_id = ?2
-                   AND valid_from IS NULL AND valid_until IS NULL",
-                [source_tag_id.get(), target_tag_id.get()],
-            )?;
-
-            // Decrement degree_centrality for both tags, but ensure it never goes negative
-            // Use MAX(0, degree_centrality - 1) to prevent negative values
-            conn.execute(
-                "UPDATE tags SET degree_centrality = MAX(0, degree_centrality - 1) WHERE id = ?",
-                [source_tag_id.get()],
-            )?;
-
-            conn.execute(
-                "UPDATE tags SET degree_centrality = MAX(0, degree_centrality - 1) WHERE id = ?",
-                [target_tag_id.get()],
-            )?;
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, COALESCE(t.degree_centrality, 0)
+             FROM tags t
+             WHERE t.id IN (SELECT source_tag_id FROM edges UNION SELECT target_tag_id FROM edges)
+             ORDER BY t.name",
+        )?;
 
-            Ok(())
-        })();
+        let tags = stmt
+            .query_map([], |row| {
+                Ok((TagId::new(row.get(0)?), row.get(1)?, row.get(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
 
-        match result {
-            Ok(()) => {
-                conn.execute("COMMIT", [])?;
-                Ok(())
-            }
-            Err(e) => {
-                conn.execute("ROLLBACK", []).ok();
-                Err(e)
-            }
-        }
+        Ok(tags)
     }
 
     /// Searches for notes using spreading activation through the tag hierarchy graph.
@@ -2296,13 +4882,58 @@ impl NoteService {
     /// # }
     /// ```
     pub fn graph_search(&self, query: &str, limit: Option<usize>) -> Result<Vec<SearchResult>> {
-        use crate::spreading_activation::{SpreadingActivationConfig, spread_activation};
+        self.graph_search_with(query, limit, false)
+    }
+
+    /// Like [`graph_search`](Self::graph_search), also explaining each result.
+    ///
+    /// Each result's `explanation` lists up to three activation paths from a
+    /// seed tag to one of the note's tags, strongest first, with the
+    /// activation reaching every tag along the way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let tokio = service.get_or_create_tag("tokio")?;
+    /// let rust = service.get_or_create_tag("rust")?;
+    /// service.create_edge(tokio, rust, 1.0, "generic", None)?;
+    /// service.create_note("Ownership rules", Some(&["rust"]))?;
+    ///
+    /// let results = service.graph_search_explained("tokio", None)?;
+    /// let paths = results[0].explanation.as_ref().unwrap();
+    /// assert_eq!(paths[0].to_string(), "tokio (1.00) -> rust (0.70)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn graph_search_explained(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<SearchResult>> {
+        self.graph_search_with(query, limit, true)
+    }
+
+    /// Runs graph search, recording activation paths when `explain` is set.
+    fn graph_search_with(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        explain: bool,
+    ) -> Result<Vec<SearchResult>> {
+        use crate::spreading_activation::{
+            SpreadingActivationConfig, activation_paths, spread_activation,
+        };
         use std::collections::HashMap;
 
         let conn = self.db.connection();
 
-        // Parse query into terms
-        let terms: Vec<&str> = query.split_whitespace().collect();
+        // Parse query into terms, ignoring operators and excluded terms
+        let parsed = parse_search_query(query);
+        let terms: Vec<&str> = parsed.groups.into_iter().flatten().collect();
         if terms.is_empty() {
             return Ok(Vec::new());
         }
@@ -2339,8 +4970,13 @@ impl NoteService {
         }
 
         // Execute spreading activation
-        let config = SpreadingActivationConfig::from_env();
+        let config = SpreadingActivationConfig::from(&self.config);
         let activated_tags = spread_activation(conn, &seed_tags, &config)?;
+        let paths = if explain {
+            Some(activation_paths(conn, &seed_tags, &config)?)
+        } else {
+            None
+        };
 
         // Score notes using: SUM(tag_activation * note_tags.confidence)
         // Since we can't bind arrays, we'll execute multiple queries
@@ -2381,20 +5017,30 @@ impl NoteService {
             .map(|(_, score)| *score)
             .fold(0.0_f64, f64::max);
 
-        for (note_id, raw_score) in scored_notes {
-            if let Some(note) = self.get_note(NoteId::new(note_id))? {
-                // Normalize score to 0.0-1.0 range using min-max normalization
-                // Higher raw scores = higher normalized scores
-                let relevance_score = if max_score > 0.0 {
-                    raw_score / max_score
-                } else {
-                    0.0
-                };
-                results.push(SearchResult {
-                    note,
-                    relevance_score,
-                });
-            }
+        // Load all scored notes in one batch, then pair them back up with their scores
+        let ids: Vec<NoteId> = scored_notes
+            .iter()
+            .map(|(id, _)| NoteId::new(*id))
+            .collect();
+        let scores: std::collections::HashMap<i64, f64> = scored_notes.into_iter().collect();
+        for note in self.get_notes_batch(&ids)? {
+            let raw_score = scores[&note.id().get()];
+            // Normalize score to 0.0-1.0 range using min-max normalization
+            // Higher raw scores = higher normalized scores
+            let relevance_score = if max_score > 0.0 {
+                raw_score / max_score
+            } else {
+                0.0
+            };
+            let explanation = paths
+                .as_ref()
+                .map(|paths| note_activation_paths(&note, paths));
+            results.push(SearchResult {
+                note,
+                relevance_score,
+                snippet: None,
+                explanation,
+            });
         }
 
         Ok(results)
@@ -2477,7 +5123,7 @@ impl NoteService {
         }
 
         // Execute spreading activation
-        let config = SpreadingActivationConfig::from_env();
+        let config = SpreadingActivationConfig::from(&self.config);
         let activated_tags = spread_activation(conn, &seed_tags, &config)?;
 
         // Score notes using: SUM(tag_activation * note_tags.confidence)
@@ -2522,25 +5168,112 @@ impl NoteService {
             .map(|(_, score)| *score)
             .fold(0.0_f64, f64::max);
 
-        for (note_id_val, raw_score) in scored_notes {
-            if let Some(note) = self.get_note(NoteId::new(note_id_val))? {
-                // Normalize score to 0.0-1.0 range using min-max normalization
-                // Higher raw scores = higher normalized scores
-                let relevance_score = if max_score > 0.0 {
-                    raw_score / max_score
-                } else {
-                    0.0
-                };
-                results.push(SearchResult {
-                    note,
-                    relevance_score,
-                });
-            }
+        // Load all scored notes in one batch, then pair them back up with their scores
+        let ids: Vec<NoteId> = scored_notes
+            .iter()
+            .map(|(id, _)| NoteId::new(*id))
+            .collect();
+        let scores: std::collections::HashMap<i64, f64> = scored_notes.into_iter().collect();
+        for note in self.get_notes_batch(&ids)? {
+            let raw_score = scores[&note.id().get()];
+            // Normalize score to 0.0-1.0 range using min-max normalization
+            // Higher raw scores = higher normalized scores
+            let relevance_score = if max_score > 0.0 {
+                raw_score / max_score
+            } else {
+                0.0
+            };
+            results.push(SearchResult {
+                note,
+                relevance_score,
+                snippet: None,
+                explanation: None,
+            });
         }
 
         Ok(results)
     }
 
+    /// Finds notes that share tags with a given note, ranked by Jaccard similarity.
+    ///
+    /// Each candidate's score is `|shared tags| / |union of both tag sets|`, so a
+    /// note with exactly the seed's tags scores 1.0. This needs no LLM and no
+    /// hierarchy edges, making it a cheap, deterministic alternative to
+    /// [`graph_search_from_note`](Self::graph_search_from_note).
+    ///
+    /// The seed note is excluded, as are notes sharing no tags with it. Ties are
+    /// broken by ascending note ID. Returns an empty list when the seed note has
+    /// no tags or does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::{Database, NoteService};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let service = NoteService::new(Database::in_memory()?);
+    /// let seed = service.create_note("Tokio runtime", Some(&["rust", "async"]))?;
+    /// service.create_note("Async Rust", Some(&["rust", "async"]))?;
+    /// service.create_note("Rust CLI", Some(&["rust", "cli"]))?;
+    ///
+    /// let similar = service.find_similar_notes(seed.id(), 10)?;
+    /// assert_eq!(similar[0].0.content(), "Async Rust");
+    /// assert_eq!(similar[0].1, 1.0);
+    /// assert_eq!(similar.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_similar_notes(&self, id: NoteId, limit: usize) -> Result<Vec<(Note, f64)>> {
+        let conn = self.db.connection();
+
+        let seed_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM note_tags WHERE note_id = ?1",
+            [id.get()],
+            |row| row.get(0),
+        )?;
+        if seed_count == 0 || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Per candidate note: its tag count and how many of those the seed also has
+        let mut stmt = conn.prepare(
+            "SELECT nt.note_id,
+                    COUNT(*) AS total,
+                    SUM(nt.tag_id IN (SELECT tag_id FROM note_tags WHERE note_id = ?1)) AS shared
+             FROM note_tags nt
+             WHERE nt.note_id != ?1
+             GROUP BY nt.note_id
+             HAVING shared > 0",
+        )?;
+        let rows = stmt.query_map([id.get()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+
+        let mut scored = Vec::new();
+        for row in rows {
+            let (note_id, total, shared) = row?;
+            let union = seed_count + total - shared;
+            scored.push((note_id, shared as f64 / union as f64));
+        }
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.truncate(limit);
+
+        let ids: Vec<NoteId> = scored.iter().map(|(id, _)| NoteId::new(*id)).collect();
+        let scores: std::collections::HashMap<i64, f64> = scored.into_iter().collect();
+        Ok(self
+            .get_notes_batch(&ids)?
+            .into_iter()
+            .map(|note| {
+                let score = scores[&note.id().get()];
+                (note, score)
+            })
+            .collect())
+    }
+
     /// Searches for notes using dual-channel retrieval combining FTS and graph search.
     ///
     /// Executes both FTS (via `search_notes`) and graph-based (via `graph_search`)
@@ -2553,7 +5286,7 @@ impl NoteService {
     ///
     /// # Algorithm
     ///
-    /// 1. Load configuration from environment (or use defaults)
+    /// 1. Read the service configuration (environment or defaults)
     /// 2. Execute FTS search via `search_notes(query, None)` (unlimited)
     /// 3. Execute graph search via `graph_search(query, None)` (unlimited)
     /// 4. Check cold-start conditions on graph results:
@@ -2614,18 +5347,40 @@ impl NoteService {
         &self,
         query: &str,
         limit: Option<usize>,
+    ) -> Result<(Vec<DualSearchResult>, DualSearchMetadata)> {
+        self.dual_search_filtered(query, limit, &SearchFilters::default())
+    }
+
+    /// Performs dual-channel search restricted by the given filters.
+    ///
+    /// Behaves like [`dual_search`](Self::dual_search), but both channels only
+    /// contribute notes passing `filters`. Result counts in the metadata reflect
+    /// the filtered channels.
+    pub fn dual_search_filtered(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+        filters: &SearchFilters,
     ) -> Result<(Vec<DualSearchResult>, DualSearchMetadata)> {
         use std::collections::HashMap;
 
-        // Load configuration from environment
-        let config = DualSearchConfig::from_env();
+        // Use the configuration captured when the service was created
+        let config = DualSearchConfig::from(&self.config);
 
         // Build and capture the expanded FTS query for metadata
         let expanded_fts_query = self.build_fts_query(query)?;
+        let filters = &self.canonical_search_filters(filters)?;
 
         // Execute both search channels
-        let fts_results = self.search_notes(query, None)?;
-        let graph_results = self.graph_search(query, None)?;
+        // Pagination applies to the merged ranking, not to the FTS channel alone
+        let fts_filters = SearchFilters {
+            offset: None,
+            ..filters.clone()
+        };
+        let fts_results = self.search_notes_filtered(query, None, &fts_filters)?;
+        let mut graph_results = self.graph_search(query, None)?;
+        let excluded = self.excluded_note_ids(query)?;
+        graph_results.retain(|r| filters.matches(&r.note) && !excluded.contains(&r.note.id()));
 
         let fts_result_count = fts_results.len();
         let graph_result_count = graph_results.len();
@@ -2653,6 +5408,7 @@ impl NoteService {
                     fts_score: Some(r.relevance_score),
                     graph_score: None,
                     found_by_both: false,
+                    snippet: r.snippet,
                 })
                 .collect();
 
@@ -2663,7 +5419,10 @@ impl NoteService {
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
 
-            // Apply limit
+            // Apply offset and limit
+            if let Some(offset) = filters.offset {
+                fts_only_results.drain(..offset.min(fts_only_results.len()));
+            }
             if let Some(lim) = limit {
                 fts_only_results.truncate(lim);
             }
@@ -2693,6 +5452,7 @@ impl NoteService {
                     fts_score: Some(fts_result.relevance_score),
                     graph_score: None,
                     found_by_both: false,
+                    snippet: fts_result.snippet,
                 },
             );
         }
@@ -2721,20 +5481,26 @@ impl NoteService {
                         fts_score: None,
                         graph_score: Some(graph_result.relevance_score),
                         found_by_both: false,
+                        snippet: None,
                     },
                 );
             }
         }
 
         // Convert HashMap to Vec and sort by final_score descending
+        // Tie-break on note id so pages are deterministic
         let mut results: Vec<DualSearchResult> = merged.into_values().collect();
         results.sort_by(|a, b| {
             b.final_score
                 .partial_cmp(&a.final_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.note.id().get().cmp(&b.note.id().get()))
         });
 
-        // Apply limit
+        // Apply offset and limit
+        if let Some(offset) = filters.offset {
+            results.drain(..offset.min(results.len()));
+        }
         if let Some(lim) = limit {
             results.truncate(lim);
         }
@@ -2752,6 +5518,23 @@ impl NoteService {
     }
 }
 
+/// Error for a note, tag, alias, or other named entity that does not exist.
+///
+/// Carries the full user-facing message. Callers can downcast an
+/// [`anyhow::Error`] to this type to tell a missing entity apart from a
+/// database failure.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct NotFound(pub String);
+
+/// Error for a request that is invalid as given, e.g. merging a note into itself.
+///
+/// Like [`NotFound`], it carries the full user-facing message and marks the
+/// failure as the user's input rather than a database or I/O problem.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct InvalidInput(pub String);
+
 /// Sort order for listing notes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SortOrder {
@@ -2762,6 +5545,83 @@ pub enum SortOrder {
     Descending,
 }
 
+/// Timestamp that [`ListNotesOptions::order`] sorts by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderBy {
+    /// Sort by when the note was captured
+    #[default]
+    CreatedAt,
+    /// Sort by when the note's content was last edited
+    UpdatedAt,
+}
+
+impl OrderBy {
+    /// The `notes` column holding this timestamp.
+    fn column(self) -> &'static str {
+        match self {
+            OrderBy::CreatedAt => "created_at",
+            OrderBy::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Outcome of [`NoteService::import_aliases`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AliasImportSummary {
+    /// Number of aliases created (or updated, if the alias already existed).
+    pub created: usize,
+    /// Normalized `(alias, canonical)` pairs skipped because they would form an alias chain.
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Outcome of [`NoteService::import_notes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// IDs of the newly created notes, in input order.
+    pub imported: Vec<NoteId>,
+    /// Number of notes skipped because their content already existed.
+    pub skipped: usize,
+}
+
+/// Filter on the provenance of a note's tags.
+///
+/// Used by [`ListNotesOptions`] and [`SearchFilters`] to narrow results to notes
+/// that still carry auto-generated tags (for review) or only user-assigned tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SourceFilter {
+    /// No filtering by tag source
+    #[default]
+    All,
+    /// Only notes whose tags are all user-assigned (untagged notes included)
+    User,
+    /// Only notes with at least one LLM-assigned tag
+    Llm,
+}
+
+impl SourceFilter {
+    /// Returns the SQL condition for this filter, correlated on `id_column`.
+    fn sql_condition(self, id_column: &str) -> Option<String> {
+        match self {
+            SourceFilter::All => None,
+            SourceFilter::User => Some(format!(
+                "NOT EXISTS (SELECT 1 FROM note_tags sf WHERE sf.note_id = {id_column} AND sf.source != 'user')"
+            )),
+            SourceFilter::Llm => Some(format!(
+                "EXISTS (SELECT 1 FROM note_tags sf WHERE sf.note_id = {id_column} AND sf.source = 'llm')"
+            )),
+        }
+    }
+
+    /// Returns true if the note's tags satisfy this filter.
+    pub fn matches(self, note: &Note) -> bool {
+        match self {
+            SourceFilter::All => true,
+            SourceFilter::User => note.tags().iter().all(|t| t.source().is_user()),
+            SourceFilter::Llm => note.tags().iter().any(|t| t.source().is_llm()),
+        }
+    }
+}
+
 /// Options for listing notes.
 ///
 /// Provides flexible filtering and pagination for note queries.
@@ -2798,6 +5658,42 @@ pub struct ListNotesOptions {
 
     /// Sort order for notes. Defaults to Descending (newest first).
     pub order: SortOrder,
+
+    /// Timestamp to sort by. Defaults to `OrderBy::CreatedAt`.
+    pub order_by: OrderBy,
+
+    /// Only include notes created at or after this Unix timestamp.
+    pub created_after: Option<i64>,
+
+    /// Only include notes created at or before this Unix timestamp.
+    pub created_before: Option<i64>,
+
+    /// Filter by tag provenance. Defaults to `SourceFilter::All`.
+    pub source: SourceFilter,
+
+    /// Number of notes to skip before returning results. None means no offset.
+    pub offset: Option<usize>,
+
+    /// Only include notes with at least one LLM tag the user has not verified.
+    pub unverified_only: bool,
+
+    /// Only include notes with no tags at all.
+    pub untagged_only: bool,
+
+    /// Filter notes by a tag substring (case-insensitive). None means no filtering.
+    /// When specified, returns notes with ANY tag containing the pattern.
+    /// Cannot be combined with `tags`.
+    pub tag_like: Option<String>,
+
+    /// Only include notes listed after the note with this ID. None means no cursor.
+    ///
+    /// Notes are compared on `(order_by timestamp, id)`, the same key `list_notes`
+    /// sorts by, in the direction of `order`: with the default descending order
+    /// these are the notes that sort before the cursor, with ascending order the
+    /// ones that sort after it. Passing the last ID of a page fetches the next page
+    /// without the drift an offset suffers when new notes are added in between.
+    /// If the cursor note no longer exists, only IDs are compared.
+    pub before: Option<i64>,
 }
 
 impl Default for ListNotesOptions {
@@ -2806,10 +5702,79 @@ impl Default for ListNotesOptions {
             limit: None,
             tags: None,
             order: SortOrder::Descending,
+            order_by: OrderBy::CreatedAt,
+            created_after: None,
+            created_before: None,
+            source: SourceFilter::All,
+            offset: None,
+            unverified_only: false,
+            untagged_only: false,
+            tag_like: None,
+            before: None,
         }
     }
 }
 
+/// Filters applied to search results.
+///
+/// Mirrors the filtering fields of [`ListNotesOptions`] for the search paths
+/// (`search_notes_filtered` and `dual_search_filtered`).
+///
+/// # Examples
+///
+/// ```
+/// use cons::SearchFilters;
+///
+/// // Only notes created during 2024
+/// let filters = SearchFilters {
+///     created_after: Some(1_704_067_200),
+///     created_before: Some(1_735_689_599),
+///     ..Default::default()
+/// };
+/// assert!(filters != SearchFilters::default());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    /// Only include notes created at or after this Unix timestamp.
+    pub created_after: Option<i64>,
+
+    /// Only include notes created at or before this Unix timestamp.
+    pub created_before: Option<i64>,
+
+    /// Filter by tag provenance. Defaults to `SourceFilter::All`.
+    pub source: SourceFilter,
+
+    /// Number of top-ranked results to skip, for pagination.
+    pub offset: Option<usize>,
+
+    /// Only include notes carrying all of these tags. Search methods normalize
+    /// the names and resolve aliases; an empty list matches no notes.
+    pub tags: Option<Vec<String>>,
+}
+
+impl SearchFilters {
+    /// Returns true if the note passes every filter.
+    ///
+    /// Tag names are compared case-insensitively as given, without alias
+    /// resolution; see [`NoteService::canonical_tag_names`].
+    pub fn matches(&self, note: &Note) -> bool {
+        let created_at = note.created_at().unix_timestamp();
+        self.created_after.is_none_or(|after| created_at >= after)
+            && self
+                .created_before
+                .is_none_or(|before| created_at <= before)
+            && self.source.matches(note)
+            && self.tags.as_ref().is_none_or(|tags| {
+                !tags.is_empty()
+                    && tags.iter().all(|tag| {
+                        note.tags()
+                            .iter()
+                            .any(|assigned| assigned.name().eq_ignore_ascii_case(tag))
+                    })
+            })
+    }
+}
+
 #[cfg(test)]
 #[path = "service/tests.rs"]
 mod tests;
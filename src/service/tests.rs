@@ -1,4 +1,5 @@
 use super::*;
+use crate::FtsTokenizer;
 
 #[test]
 fn note_service_construction_with_in_memory_database() {
@@ -96,6 +97,120 @@ fn get_note_returns_some_note_for_existing_note() {
     assert_eq!(retrieved.updated_at(), created.updated_at());
 }
 
+#[test]
+fn create_note_records_capture_source() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let cli = service
+        .create_note("from the command line", None)
+        .expect("failed to create note");
+    assert_eq!(cli.source(), "cli");
+    let tui = service
+        .create_note_with_source("from the terminal UI", Some(&["rust"]), "tui")
+        .expect("failed to create note");
+    assert_eq!(tui.source(), "tui");
+
+    let load = |id: NoteId| {
+        service
+            .get_note(id)
+            .expect("failed to get note")
+            .expect("note should exist")
+    };
+    assert_eq!(load(cli.id()).source(), "cli");
+    assert_eq!(load(tui.id()).source(), "tui");
+
+    let summary = service
+        .import_notes(&[load(tui.id())], false)
+        .expect("failed to import notes");
+    assert_eq!(load(summary.imported[0]).source(), "import");
+}
+
+#[test]
+fn get_note_treats_missing_source_as_unknown() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    db.connection()
+        .execute(
+            "INSERT INTO notes (id, content, created_at, updated_at) VALUES (1, 'legacy', 0, 0)",
+            [],
+        )
+        .expect("failed to insert legacy note");
+    let service = NoteService::new(db);
+
+    let note = service
+        .get_note(NoteId::new(1))
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(note.source(), "unknown");
+}
+
+#[test]
+fn get_notes_batch_returns_200_notes_in_requested_order() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let mut ids = Vec::new();
+    for i in 0..200 {
+        let tag = format!("group-{}", i % 7);
+        let tags = if i % 3 == 0 {
+            None
+        } else {
+            Some(vec![tag.as_str(), "batch"])
+        };
+        let note = service
+            .create_note(&format!("Batch note {i}"), tags.as_deref())
+            .expect("failed to create note");
+        ids.push(note.id());
+    }
+
+    // Request in an order that differs from insertion, with a missing id mixed in
+    let mut requested: Vec<NoteId> = ids.iter().rev().copied().collect();
+    requested.insert(50, NoteId::new(99_999));
+
+    let notes = service
+        .get_notes_batch(&requested)
+        .expect("failed to batch load notes");
+
+    assert_eq!(notes.len(), 200, "missing id should be skipped");
+    let returned: Vec<NoteId> = notes.iter().map(|note| note.id()).collect();
+    let expected: Vec<NoteId> = ids.iter().rev().copied().collect();
+    assert_eq!(returned, expected, "notes should follow requested order");
+
+    for note in &notes {
+        let single = service
+            .get_note(note.id())
+            .expect("failed to get note")
+            .expect("note should exist");
+        assert_eq!(note, &single, "batch note should match get_note");
+    }
+    assert_eq!(notes[0].content(), "Batch note 199");
+    assert_eq!(notes[0].tags().len(), 2);
+    assert!(notes[199].tags().is_empty(), "note 0 was created untagged");
+}
+
+#[test]
+fn get_notes_batch_handles_empty_and_duplicate_ids() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let note = service
+        .create_note("Only note", Some(&["solo"]))
+        .expect("failed to create note");
+
+    assert!(
+        service
+            .get_notes_batch(&[])
+            .expect("failed to batch load notes")
+            .is_empty()
+    );
+
+    let notes = service
+        .get_notes_batch(&[note.id(), note.id()])
+        .expect("failed to batch load notes");
+    assert_eq!(notes.len(), 1, "duplicate ids should be returned once");
+    assert_eq!(notes[0].tags()[0].name(), "solo");
+}
+
 #[test]
 fn delete_note_is_idempotent() {
     let db = Database::in_memory().expect("failed to create in-memory database");
@@ -276,6 +391,57 @@ fn add_tags_to_note_with_llm_source_includes_model_version_and_confidence() {
     }
 }
 
+#[test]
+fn add_tags_to_note_with_import_source_round_trips() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let note = service
+        .create_note("Note from an Obsidian vault", None)
+        .expect("failed to create note");
+    service
+        .add_tags_to_note(note.id(), &["reading"], TagSource::import("obsidian"))
+        .expect("failed to add imported tags");
+
+    let stored: (String, f64, Option<String>) = service
+        .database()
+        .connection()
+        .query_row(
+            "SELECT source, confidence, model_version FROM note_tags WHERE note_id = ?1",
+            [note.id().get()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .expect("failed to query note_tags");
+    assert_eq!(
+        stored,
+        ("import".to_string(), 1.0, Some("obsidian".to_string()))
+    );
+
+    let retrieved = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    let tag = &retrieved.tags()[0];
+    assert_eq!(tag.source(), &TagSource::import("obsidian"));
+    assert_eq!(tag.confidence(), 100);
+    assert_eq!(tag.model(), None);
+
+    // Export/import keeps the import provenance
+    let target = NoteService::new(Database::in_memory().expect("failed to create database"));
+    let summary = target
+        .import_notes(&[retrieved], false)
+        .expect("failed to import notes");
+    let imported = target
+        .get_note(summary.imported[0])
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert!(imported.tags()[0].source().is_import());
+    assert_eq!(
+        imported.tags()[0].source().import_source(),
+        Some("obsidian")
+    );
+}
+
 // --- Tag Normalization Tests (Task Group 1: Tag Normalization) ---
 
 #[test]
@@ -632,6 +798,87 @@ fn list_notes_with_default_options_returns_notes_in_created_at_desc_order() {
     );
 }
 
+#[test]
+fn list_notes_order_by_updated_at_surfaces_edited_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let old = service
+        .create_note("Old note", None)
+        .expect("failed to create note");
+    let newer = service
+        .create_note("Newer note", None)
+        .expect("failed to create note");
+
+    // Backdate both notes so the edit below lands in a later second
+    service
+        .database()
+        .connection()
+        .execute(
+            "UPDATE notes SET created_at = created_at - 100 + id, updated_at = created_at - 100 + id",
+            [],
+        )
+        .expect("failed to backdate notes");
+
+    service
+        .update_note_content(old.id(), "Old note, revised")
+        .expect("failed to edit note");
+
+    let ids = |order_by: OrderBy| -> Vec<NoteId> {
+        service
+            .list_notes(ListNotesOptions {
+                order_by,
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect()
+    };
+
+    assert_eq!(ids(OrderBy::CreatedAt), vec![newer.id(), old.id()]);
+    assert_eq!(ids(OrderBy::UpdatedAt), vec![old.id(), newer.id()]);
+    assert_eq!(ListNotesOptions::default().order_by, OrderBy::CreatedAt);
+}
+
+#[test]
+fn list_notes_before_cursor_pages_by_updated_at() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let [first, second, third] =
+        create_notes(&service, [("first", &[]), ("second", &[]), ("third", &[])]);
+    service
+        .database()
+        .connection()
+        .execute(
+            "UPDATE notes SET created_at = id * 100, updated_at = id * 100",
+            [],
+        )
+        .expect("failed to backdate notes");
+    // The oldest note was edited last, so it leads the updated order despite its low id
+    service
+        .update_note_content(first, "first, revised")
+        .expect("failed to edit note");
+
+    let page = |before: Option<NoteId>| -> Vec<NoteId> {
+        service
+            .list_notes(ListNotesOptions {
+                limit: Some(2),
+                order_by: OrderBy::UpdatedAt,
+                before: before.map(NoteId::get),
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect()
+    };
+
+    assert_eq!(page(None), vec![first, third]);
+    assert_eq!(page(Some(third)), vec![second]);
+    assert_eq!(page(Some(first)), vec![third, second]);
+}
+
 #[test]
 fn list_notes_with_limit_option_respects_limit() {
     let db = Database::in_memory().expect("failed to create in-memory database");
@@ -723,3470 +970,5954 @@ fn list_notes_with_tags_filter_returns_only_notes_with_all_specified_tags() {
     );
 }
 
-// --- Additional Critical Gap Tests (Task Group 5) ---
+// --- Test Fixtures ---
+
+/// Creates one note per (content, user tags) pair and returns their ids in order.
+fn create_notes<const N: usize>(service: &NoteService, notes: [(&str, &[&str]); N]) -> [NoteId; N] {
+    notes.map(|(content, tags)| {
+        service
+            .create_note(content, Some(tags))
+            .expect("failed to create note")
+            .id()
+    })
+}
+
+/// Collects note ids sorted ascending, for order-independent comparisons.
+fn sorted_ids(ids: impl IntoIterator<Item = NoteId>) -> Vec<NoteId> {
+    let mut ids: Vec<NoteId> = ids.into_iter().collect();
+    ids.sort_by_key(|id| id.get());
+    ids
+}
+
+// --- Date Range Filter Tests ---
+
+/// Creates three notes with created_at set to 1000, 2000, and 3000.
+fn create_notes_at_fixed_times(service: &NoteService) -> [NoteId; 3] {
+    let ids = create_notes(
+        service,
+        [
+            ("early rust note", &["rust"]),
+            ("middle rust note", &["rust"]),
+            ("late rust note", &["rust"]),
+        ],
+    );
+    for (id, timestamp) in ids.iter().zip([1000, 2000, 3000]) {
+        service
+            .database()
+            .connection()
+            .execute(
+                "UPDATE notes SET created_at = ?1 WHERE id = ?2",
+                [timestamp, id.get()],
+            )
+            .expect("failed to backdate note");
+    }
+    ids
+}
 
 #[test]
-fn list_notes_returns_empty_vec_for_empty_database() {
+fn list_notes_with_date_range_returns_only_notes_in_range() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let ids = create_notes_at_fixed_times(&service);
 
     let notes = service
-        .list_notes(ListNotesOptions::default())
+        .list_notes(ListNotesOptions {
+            created_after: Some(2000),
+            created_before: Some(3000),
+            ..Default::default()
+        })
         .expect("failed to list notes");
+    let listed: Vec<NoteId> = notes.iter().map(|n| n.id()).collect();
+    assert_eq!(listed, vec![ids[2], ids[1]], "bounds should be inclusive");
 
-    assert_eq!(notes.len(), 0, "should return empty vec for empty database");
+    let notes = service
+        .list_notes(ListNotesOptions {
+            created_before: Some(1999),
+            ..Default::default()
+        })
+        .expect("failed to list notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id(), ids[0]);
 }
 
 #[test]
-fn add_tags_to_note_fails_for_non_existent_note() {
+fn list_notes_date_range_composes_with_tags_and_limit() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let ids = create_notes_at_fixed_times(&service);
 
-    let result =
-        service.add_tags_to_note(NoteId::new(999), &["rust", "programming"], TagSource::User);
+    let notes = service
+        .list_notes(ListNotesOptions {
+            tags: Some(vec!["rust".to_string()]),
+            created_after: Some(1500),
+            limit: Some(1),
+            ..Default::default()
+        })
+        .expect("failed to list notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id(), ids[2]);
+}
 
-    assert!(
-        result.is_err(),
-        "should return error when adding tags to non-existent note"
-    );
+#[test]
+fn search_notes_filtered_respects_date_range() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let ids = create_notes_at_fixed_times(&service);
 
-    let err_msg = result.unwrap_err().to_string();
-    assert!(
-        err_msg.contains("does not exist"),
-        "error message should indicate note doesn't exist: {}",
-        err_msg
-    );
+    let filters = SearchFilters {
+        created_after: Some(1500),
+        created_before: Some(2500),
+        ..Default::default()
+    };
+    let results = service
+        .search_notes_filtered("rust", None, &filters)
+        .expect("search should succeed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id(), ids[1]);
+
+    let (dual_results, _) = service
+        .dual_search_filtered("rust", None, &filters)
+        .expect("dual search should succeed");
+    assert_eq!(dual_results.len(), 1);
+    assert_eq!(dual_results[0].note.id(), ids[1]);
 }
 
 #[test]
-fn list_notes_with_empty_tags_filter_returns_no_notes() {
+fn search_with_tags_is_stricter_than_text_or_tags_alone() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create some notes
+    let both = service
+        .create_note("async runtimes compared", Some(&["rust", "tokio"]))
+        .expect("failed to create note");
+    // Matches the text but lacks the tag
     service
-        .create_note("Note 1", Some(&["rust"]))
-        .expect("failed to create note 1");
-
+        .create_note("async event loops", Some(&["python"]))
+        .expect("failed to create note");
+    // Has the tag but does not match the text
     service
-        .create_note("Note 2", Some(&["programming"]))
-        .expect("failed to create note 2");
+        .create_note("ownership rules", Some(&["rust"]))
+        .expect("failed to create note");
+    let tag_id = service.get_or_create_tag("rust").unwrap();
+    service
+        .create_alias("rs", tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Filter with empty tags list
-    let options = ListNotesOptions {
-        tags: Some(vec![]),
+    let text_only = service.search_notes("async", None).unwrap();
+    assert_eq!(text_only.len(), 2);
+    let tags_only = service
+        .list_notes(ListNotesOptions {
+            tags: Some(vec!["rust".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(tags_only.len(), 2);
+
+    // Aliases and case are resolved like the list filter
+    let filters = SearchFilters {
+        tags: Some(vec!["RS".to_string()]),
         ..Default::default()
     };
+    let combined = service
+        .search_notes_filtered("async", None, &filters)
+        .expect("search should succeed");
+    let ids: Vec<NoteId> = combined.iter().map(|r| r.note.id()).collect();
+    assert_eq!(ids, vec![both.id()]);
 
-    let notes = service.list_notes(options).expect("failed to list notes");
+    let (dual_results, _) = service
+        .dual_search_filtered("async", None, &filters)
+        .expect("dual search should succeed");
+    let ids: Vec<NoteId> = dual_results.iter().map(|r| r.note.id()).collect();
+    assert_eq!(ids, vec![both.id()]);
 
-    assert_eq!(notes.len(), 0, "empty tags filter should return no notes");
+    // Every listed tag is required
+    let filters = SearchFilters {
+        tags: Some(vec!["rust".to_string(), "python".to_string()]),
+        ..Default::default()
+    };
+    assert!(
+        service
+            .search_notes_filtered("async", None, &filters)
+            .unwrap()
+            .is_empty()
+    );
 }
 
+// --- Remove LLM Tags Tests ---
+
 #[test]
-fn delete_note_cascades_to_note_tags_table() {
+fn remove_llm_tags_keeps_user_tags() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note with tags
     let note = service
-        .create_note("Note with tags", Some(&["rust", "programming"]))
+        .create_note("mixed tags", Some(&["rust"]))
         .expect("failed to create note");
-
-    // Verify tags exist in note_tags table
-    let conn = service.database().connection();
-    let tag_count_before: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM note_tags WHERE note_id = ?1",
-            [note.id().get()],
-            |row| row.get(0),
+    service
+        .add_tags_to_note(
+            note.id(),
+            &["async", "tokio"],
+            TagSource::llm("test-model", 80),
         )
-        .expect("failed to count note_tags");
-
-    assert_eq!(tag_count_before, 2, "note should have 2 tag associations");
+        .expect("failed to add tags");
 
-    // Delete the note
-    service
-        .delete_note(note.id())
-        .expect("failed to delete note");
+    let removed = service
+        .remove_llm_tags(note.id())
+        .expect("failed to remove LLM tags");
+    assert_eq!(removed, 2);
 
-    // Verify note_tags entries are also deleted (cascade)
-    let tag_count_after: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM note_tags WHERE note_id = ?1",
-            [note.id().get()],
-            |row| row.get(0),
-        )
-        .expect("failed to count note_tags");
+    let note = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(note.tags().len(), 1);
+    assert!(note.tags()[0].source().is_user());
 
-    assert_eq!(
-        tag_count_after, 0,
-        "note_tags entries should be deleted via cascade"
-    );
+    // Removed tags no longer match in search
+    let results = service
+        .search_notes("tokio", None)
+        .expect("search should succeed");
+    assert!(results.is_empty());
 }
 
 #[test]
-fn timestamp_conversion_maintains_accuracy() {
+fn remove_llm_tags_on_note_without_llm_tags_removes_nothing() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Capture Unix timestamp before creation (second precision like database)
-    let before_unix = OffsetDateTime::now_utc().unix_timestamp();
-
     let note = service
-        .create_note("Timestamp test", None)
+        .create_note("user only", Some(&["rust"]))
         .expect("failed to create note");
+    assert_eq!(service.remove_llm_tags(note.id()).unwrap(), 0);
+}
 
-    // Capture Unix timestamp after creation
-    let after_unix = OffsetDateTime::now_utc().unix_timestamp();
+// --- Remove Tag From Note Tests ---
 
-    let note_unix = note.created_at().unix_timestamp();
+#[test]
+fn remove_tag_from_note_leaves_other_notes_sharing_the_tag() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    // Verify created_at is within expected range (Unix timestamps are seconds)
-    assert!(
-        note_unix >= before_unix && note_unix <= after_unix,
-        "created_at Unix timestamp should be between before ({}) and after ({}), got {}",
-        before_unix,
-        after_unix,
-        note_unix
-    );
+    let first = service
+        .create_note("first", Some(&["rust", "shared"]))
+        .expect("failed to create note");
+    let second = service
+        .create_note("second", Some(&["shared"]))
+        .expect("failed to create note");
 
-    // Verify created_at equals updated_at on creation
-    assert_eq!(
-        note.created_at(),
-        note.updated_at(),
-        "created_at and updated_at should match on creation"
-    );
+    assert!(service.remove_tag_from_note(first.id(), "Shared").unwrap());
 
-    // Verify timestamp round-trip through database
-    let retrieved = service
-        .get_note(note.id())
-        .expect("failed to get note")
-        .expect("note should exist");
+    let first = service.get_note(first.id()).unwrap().unwrap();
+    let second = service.get_note(second.id()).unwrap().unwrap();
+    assert_eq!(first.tags().len(), 1, "only rust should remain");
+    assert_eq!(second.tags().len(), 1, "other note keeps the shared tag");
 
-    assert_eq!(
-        retrieved.created_at(),
-        note.created_at(),
-        "timestamps should survive database round-trip"
-    );
-    assert_eq!(
-        retrieved.updated_at(),
-        note.updated_at(),
-        "timestamps should survive database round-trip"
-    );
+    let shared_exists: bool = service
+        .database()
+        .connection()
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM tags WHERE name = 'shared')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(shared_exists, "tag row should be kept");
 }
 
-// --- Tag Alias Tests (Task Group 2: Alias Service Methods) ---
-
 #[test]
-fn resolve_alias_returns_canonical_tag_id_for_existing_alias() {
+fn remove_tag_from_note_resolves_alias_and_is_idempotent() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a canonical tag
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-
-    // Create an alias for it
+    let note = service
+        .create_note("gradient descent", Some(&["machine-learning"]))
+        .expect("failed to create note");
+    let canonical = service.get_or_create_tag("machine-learning").unwrap();
     service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
+        .create_alias("ml", canonical, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Resolve the alias
-    let resolved = service
-        .resolve_alias("ml")
-        .expect("failed to resolve alias")
-        .expect("alias should exist");
-
-    assert_eq!(
-        resolved, canonical_tag_id,
-        "alias should resolve to canonical tag ID"
+    assert!(service.remove_tag_from_note(note.id(), "ML").unwrap());
+    assert!(!service.remove_tag_from_note(note.id(), "ml").unwrap());
+    assert!(
+        !service
+            .remove_tag_from_note(note.id(), "unknown-tag")
+            .unwrap()
     );
+
+    let note = service.get_note(note.id()).unwrap().unwrap();
+    assert!(note.tags().is_empty());
 }
 
+// --- Tag Verification Tests ---
+
 #[test]
-fn resolve_alias_returns_none_for_non_existent_alias() {
+fn verify_tag_persists_and_unverified_only_filters_llm_tags() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    let result = service
-        .resolve_alias("non-existent-alias")
-        .expect("failed to resolve alias");
+    let user_only = service
+        .create_note("user tags only", Some(&["rust"]))
+        .expect("failed to create note");
+    let pending = service
+        .create_note("pending review", None)
+        .expect("failed to create note");
+    service
+        .add_tags_to_note(
+            pending.id(),
+            &["rust", "memory"],
+            TagSource::llm("deepseek-r1:8b", 80),
+        )
+        .expect("failed to add tags");
 
-    assert_eq!(result, None, "non-existent alias should return None");
+    let unverified = ListNotesOptions {
+        unverified_only: true,
+        ..Default::default()
+    };
+    let ids: Vec<NoteId> = service
+        .list_notes(unverified.clone())
+        .expect("failed to list notes")
+        .iter()
+        .map(|n| n.id())
+        .collect();
+    assert_eq!(ids, vec![pending.id()]);
+    assert!(!ids.contains(&user_only.id()));
+
+    // Verifying one of two LLM tags still leaves the note in the triage list
+    service
+        .verify_tag(pending.id(), "rust")
+        .expect("verify should succeed");
+    assert_eq!(service.count_notes(&unverified).unwrap(), 1);
+
+    service
+        .verify_tag(pending.id(), "Memory")
+        .expect("verify should succeed");
+    assert_eq!(service.count_notes(&unverified).unwrap(), 0);
+
+    let note = service.get_note(pending.id()).unwrap().unwrap();
+    assert!(note.tags().iter().all(|t| t.verified()));
 }
 
 #[test]
-fn create_alias_with_user_source_stores_correctly() {
+fn verify_tag_fails_when_note_does_not_have_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a canonical tag
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-
-    // Create an alias
+    let note = service
+        .create_note("note", Some(&["rust"]))
+        .expect("failed to create note");
     service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    // Verify it's stored correctly
-    let aliases = service.list_aliases().expect("failed to list aliases");
+        .create_note("other", Some(&["python"]))
+        .expect("failed to create note");
 
-    assert_eq!(aliases.len(), 1, "should have 1 alias");
-    assert_eq!(aliases[0].alias(), "ml");
-    assert_eq!(aliases[0].canonical_tag_id(), canonical_tag_id);
-    assert_eq!(aliases[0].source(), "user");
-    assert_eq!(aliases[0].confidence(), 1.0);
-    assert_eq!(aliases[0].model_version(), None);
+    let err = service
+        .verify_tag(note.id(), "python")
+        .expect_err("tag not on note should fail");
+    assert!(err.to_string().contains("does not exist"));
 }
 
+// --- Tag Description Tests ---
+
 #[test]
-fn create_alias_with_llm_source_includes_model_version() {
+fn tag_description_round_trips_through_normalization_and_aliases() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a canonical tag
-    let canonical_tag_id = service
+    service
+        .create_note("note", Some(&["machine-learning"]))
+        .expect("failed to create note");
+    let tag_id = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-
-    // Create an LLM alias
+        .expect("failed to get tag");
     service
-        .create_alias("ml", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))
+        .create_alias("ml", tag_id, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Verify it's stored correctly
-    let aliases = service.list_aliases().expect("failed to list aliases");
+    assert_eq!(
+        service.get_tag_description("machine-learning").unwrap(),
+        None
+    );
 
-    assert_eq!(aliases.len(), 1, "should have 1 alias");
-    assert_eq!(aliases[0].alias(), "ml");
-    assert_eq!(aliases[0].source(), "llm");
-    assert_eq!(aliases[0].confidence(), 0.85);
-    assert_eq!(aliases[0].model_version(), Some("deepseek-r1:8b"));
+    service
+        .set_tag_description("ML", "  Statistical learning from data  ")
+        .expect("describing an alias should succeed");
+    assert_eq!(
+        service
+            .get_tag_description("Machine-Learning")
+            .unwrap()
+            .as_deref(),
+        Some("Statistical learning from data")
+    );
+
+    // A blank description clears it
+    service
+        .set_tag_description("machine-learning", "   ")
+        .expect("clearing should succeed");
+    assert_eq!(
+        service.get_tag_description("machine-learning").unwrap(),
+        None
+    );
 }
 
 #[test]
-fn create_alias_prevents_alias_to_alias_chains() {
+fn set_tag_description_fails_for_missing_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
-    let conn = service.database().connection();
 
-    // Create two canonical tags
-    let ml_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create ml tag");
-
-    // Create an alias "ml" pointing to machine-learning
-    service
-        .create_alias("ml", ml_tag_id, "user", 1.0, None)
-        .expect("failed to create ml alias");
+    let err = service
+        .set_tag_description("nonexistent", "text")
+        .expect_err("missing tag should fail");
+    assert!(err.to_string().contains("does not exist"));
+    assert_eq!(service.get_tag_description("nonexistent").unwrap(), None);
+}
 
-    // Manually create a tag with the name "ml" in the tags table (bypassing get_or_create_tag)
-    // This simulates a scenario where a tag name conflicts with an alias
-    conn.execute("INSERT INTO tags (name) VALUES (?1)", ["ml"])
-        .expect("failed to insert ml tag");
+// --- Tag Preset Tests ---
 
-    let ml_as_tag_id = conn.last_insert_rowid();
+#[test]
+fn set_tag_preset_normalizes_and_replaces_existing() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    // Try to create an alias where the canonical_tag_id points to a tag whose name is "ml"
-    // which is itself an alias - this should fail
-    let result = service.create_alias(
-        "machine-learning-alias",
-        TagId::new(ml_as_tag_id),
-        "user",
-        1.0,
-        None,
+    let saved = service
+        .set_tag_preset("Weekly Sync", &["Meetings", "Work Notes", "meetings"])
+        .expect("failed to save preset");
+    assert_eq!(saved, vec!["meetings", "work-notes"]);
+    assert_eq!(
+        service.get_tag_preset("weekly-sync").unwrap(),
+        Some(vec!["meetings".to_string(), "work-notes".to_string()])
     );
 
-    assert!(
-        result.is_err(),
-        "creating alias where canonical tag name is itself an alias should fail"
+    service
+        .set_tag_preset("weekly-sync", &["standup"])
+        .expect("failed to replace preset");
+    assert_eq!(
+        service.list_tag_presets().unwrap(),
+        vec![("weekly-sync".to_string(), vec!["standup".to_string()])]
     );
 
-    let err_msg = result.unwrap_err().to_string();
-    assert!(
-        err_msg.contains("is itself an alias"),
-        "error message should indicate tag is an alias: {}",
-        err_msg
-    );
+    let err = service
+        .set_tag_preset("empty", &["!!", " "])
+        .expect_err("preset without tags should fail");
+    assert!(err.to_string().contains("cannot be empty"));
 }
 
 #[test]
-fn list_aliases_returns_all_aliases_grouped_by_canonical_tag() {
+fn remove_tag_preset_reports_whether_it_existed() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tags
-    let ml_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create ml tag");
-    let ai_tag_id = service
-        .get_or_create_tag("artificial-intelligence")
-        .expect("failed to create ai tag");
-
-    // Create aliases
     service
-        .create_alias("ml", ml_tag_id, "user", 1.0, None)
-        .expect("failed to create ml alias");
-    service
-        .create_alias("ai", ai_tag_id, "user", 1.0, None)
-        .expect("failed to create ai alias");
-    service
-        .create_alias(
-            "machine-learning-abbrev",
-            ml_tag_id,
-            "llm",
-            0.9,
-            Some("model"),
-        )
-        .expect("failed to create machine-learning-abbrev alias");
+        .set_tag_preset("meeting", &["work"])
+        .expect("failed to save preset");
 
-    // List all aliases
-    let aliases = service.list_aliases().expect("failed to list aliases");
+    assert!(service.remove_tag_preset("Meeting").unwrap());
+    assert!(!service.remove_tag_preset("meeting").unwrap());
+    assert_eq!(service.get_tag_preset("meeting").unwrap(), None);
+}
 
-    assert_eq!(aliases.len(), 3, "should have 3 aliases");
+// --- Unenhanced Notes Tests ---
 
-    // Verify they're ordered by canonical tag name, then by alias name
-    let alias_names: Vec<&str> = aliases.iter().map(|a| a.alias()).collect();
-    assert!(alias_names.contains(&"ml"), "should contain ml alias");
-    assert!(alias_names.contains(&"ai"), "should contain ai alias");
-    assert!(
-        alias_names.contains(&"machine-learning-abbrev"),
-        "should contain machine-learning-abbrev alias"
-    );
+#[test]
+fn list_unenhanced_notes_excludes_enhanced_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let plain = service
+        .create_note("plain", None)
+        .expect("failed to create note");
+    let enhanced = service
+        .create_note("enhanced", None)
+        .expect("failed to create note");
+    service
+        .update_note_enhancement(
+            enhanced.id(),
+            "An enhanced note.",
+            "test-model",
+            0.8,
+            OffsetDateTime::now_utc(),
+            None,
+        )
+        .expect("failed to enhance note");
+
+    let notes = service
+        .list_unenhanced_notes()
+        .expect("failed to list unenhanced notes");
+    let ids: Vec<NoteId> = notes.iter().map(|n| n.id()).collect();
+    assert_eq!(ids, vec![plain.id()]);
 }
 
 #[test]
-fn remove_alias_deletes_mapping_idempotently() {
+fn list_notes_tag_like_matches_tag_substrings() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a canonical tag and alias
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+    let ml = service
+        .create_note("gradient descent", Some(&["machine-learning"]))
+        .expect("failed to create note");
+    let learning = service
+        .create_note("spaced repetition", Some(&["learning", "memory"]))
+        .expect("failed to create note");
     service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
+        .create_note("borrow checker", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .create_note("untagged thought", None)
+        .expect("failed to create note");
 
-    // Verify alias exists
-    assert!(
-        service
-            .resolve_alias("ml")
-            .expect("failed to resolve")
-            .is_some(),
-        "alias should exist before removal"
+    let ids = |pattern: &str| -> Vec<NoteId> {
+        let mut ids: Vec<NoteId> = service
+            .list_notes(ListNotesOptions {
+                tag_like: Some(pattern.to_string()),
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect();
+        ids.sort_by_key(|id| id.get());
+        ids
+    };
+
+    assert_eq!(ids("learn"), vec![ml.id(), learning.id()]);
+    assert_eq!(
+        ids("LEARN"),
+        vec![ml.id(), learning.id()],
+        "case-insensitive"
     );
+    assert_eq!(ids("machine"), vec![ml.id()]);
+    assert!(ids("%").is_empty(), "LIKE wildcards match literally");
+    assert!(ids("python").is_empty());
 
-    // Remove the alias
-    service.remove_alias("ml").expect("failed to remove alias");
+    let count = service
+        .count_notes(&ListNotesOptions {
+            tag_like: Some("learn".to_string()),
+            ..Default::default()
+        })
+        .expect("failed to count notes");
+    assert_eq!(count, 2);
+}
 
-    // Verify it's gone
-    assert!(
-        service
-            .resolve_alias("ml")
-            .expect("failed to resolve")
-            .is_none(),
-        "alias should not exist after removal"
-    );
+#[test]
+fn list_notes_rejects_tags_combined_with_tag_like() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    // Remove again (idempotent)
-    service
-        .remove_alias("ml")
-        .expect("second remove should succeed (idempotent)");
+    let result = service.list_notes(ListNotesOptions {
+        tags: Some(vec!["rust".to_string()]),
+        tag_like: Some("ru".to_string()),
+        ..Default::default()
+    });
+    assert!(result.is_err());
+}
 
-    // Remove non-existent alias (also idempotent)
+/// Creates a user-tagged note, an LLM-tagged note, a mixed note and an untagged note.
+fn create_notes_with_tag_sources(service: &NoteService) -> [NoteId; 4] {
+    let ids @ [_, llm, mixed, _] = create_notes(
+        service,
+        [
+            ("rust user note", &["rust"]),
+            ("rust llm note", &[]),
+            ("rust mixed note", &["rust"]),
+            ("rust untagged note", &[]),
+        ],
+    );
     service
-        .remove_alias("non-existent")
-        .expect("removing non-existent alias should succeed (idempotent)");
+        .add_tags_to_note(llm, &["rust"], TagSource::llm("deepseek-r1:8b", 80))
+        .expect("failed to add tags");
+    service
+        .add_tags_to_note(mixed, &["systems"], TagSource::llm("deepseek-r1:8b", 70))
+        .expect("failed to add tags");
+    ids
 }
 
 #[test]
-fn alias_lookup_happens_after_normalization() {
+fn list_notes_filters_by_tag_source() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let [user, llm, mixed, untagged] = create_notes_with_tag_sources(&service);
+
+    let ids = |source: SourceFilter| -> Vec<NoteId> {
+        sorted_ids(
+            service
+                .list_notes(ListNotesOptions {
+                    source,
+                    ..Default::default()
+                })
+                .expect("failed to list notes")
+                .iter()
+                .map(|n| n.id()),
+        )
+    };
 
-    // Create a canonical tag
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+    assert_eq!(ids(SourceFilter::All), vec![user, llm, mixed, untagged]);
+    assert_eq!(ids(SourceFilter::Llm), vec![llm, mixed]);
+    assert_eq!(ids(SourceFilter::User), vec![user, untagged]);
+}
 
-    // Create an alias with normalized form
-    service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
+#[test]
+fn list_notes_source_filter_composes_with_tags_and_limit() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let [_, _, mixed, _] = create_notes_with_tag_sources(&service);
 
-    // Resolve using different case (should normalize before lookup)
-    let resolved_lower = service
-        .resolve_alias("ml")
-        .expect("failed to resolve")
-        .expect("should find alias");
+    let notes = service
+        .list_notes(ListNotesOptions {
+            tags: Some(vec!["systems".to_string()]),
+            source: SourceFilter::Llm,
+            limit: Some(1),
+            ..Default::default()
+        })
+        .expect("failed to list notes");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].id(), mixed);
+}
 
-    let resolved_upper = service
-        .resolve_alias("ML")
-        .expect("failed to resolve")
-        .expect("should find alias with different case");
+#[test]
+fn count_notes_matches_list_notes_filters_and_ignores_limit() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    service
+        .create_note("First note", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .create_note("Second note", Some(&["rust", "programming"]))
+        .expect("failed to create note");
+    service
+        .create_note("Third note", Some(&["programming"]))
+        .expect("failed to create note");
+
+    let all = ListNotesOptions {
+        limit: Some(1),
+        offset: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(service.count_notes(&all).expect("count should succeed"), 3);
 
+    // AND semantics: only the note with both tags matches
+    let both = ListNotesOptions {
+        tags: Some(vec!["rust".to_string(), "programming".to_string()]),
+        ..Default::default()
+    };
+    let listed = service
+        .list_notes(both.clone())
+        .expect("failed to list notes");
     assert_eq!(
-        resolved_lower, resolved_upper,
-        "case-insensitive lookup should work"
+        service.count_notes(&both).expect("count should succeed"),
+        listed.len()
     );
+    assert_eq!(listed.len(), 1);
+
+    let empty_tags = ListNotesOptions {
+        tags: Some(Vec::new()),
+        ..Default::default()
+    };
     assert_eq!(
-        resolved_lower, canonical_tag_id,
-        "both should resolve to canonical tag"
+        service
+            .count_notes(&empty_tags)
+            .expect("count should succeed"),
+        0
     );
 }
 
-// --- AutoTagger Alias Integration Tests ---
-
 #[test]
-fn llm_suggested_alias_auto_creation_workflow() {
-    // Integration test simulating auto_tag_note creating an LLM-suggested alias
-    // when LLM suggests a tag that could be an alias for an existing tag
-
+fn search_notes_filtered_respects_tag_source() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let [user, llm, mixed, untagged] = create_notes_with_tag_sources(&service);
 
-    // Arrange: Pre-existing canonical tag "machine-learning" exists
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create canonical tag");
+    let filters = SearchFilters {
+        source: SourceFilter::Llm,
+        ..Default::default()
+    };
+    let results = service
+        .search_notes_filtered("rust", None, &filters)
+        .expect("search should succeed");
+    assert_eq!(
+        sorted_ids(results.iter().map(|r| r.note.id())),
+        vec![llm, mixed]
+    );
 
-    // Act: Simulate LLM suggesting "ml" as a tag (detected as alias opportunity)
-    // This mimics the find_alias_opportunity + create_alias flow in auto_tag_note
-    let suggested_tag = "ml";
-    let model_version = "deepseek-r1:8b";
-    let confidence = 0.85;
+    let filters = SearchFilters {
+        source: SourceFilter::User,
+        ..Default::default()
+    };
+    let (dual_results, _) = service
+        .dual_search_filtered("rust", None, &filters)
+        .expect("dual search should succeed");
+    assert_eq!(
+        sorted_ids(dual_results.iter().map(|r| r.note.id())),
+        vec![user, untagged]
+    );
+}
 
-    // Create the LLM-suggested alias
-    service
-        .create_alias(
-            suggested_tag,
-            canonical_tag_id,
-            "llm",
-            confidence,
-            Some(model_version),
-        )
-        .expect("failed to create LLM alias");
+// --- Pagination Tests ---
 
-    // Assert: Alias was created with correct provenance
-    let alias_info_list = service.list_aliases().expect("failed to list aliases");
-    assert_eq!(alias_info_list.len(), 1, "should have 1 alias");
+#[test]
+fn list_notes_pages_do_not_overlap_and_cover_all_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    for i in 0..5 {
+        service
+            .create_note(&format!("note {i}"), None)
+            .expect("failed to create note");
+    }
 
-    let alias_info = &alias_info_list[0];
-    assert_eq!(alias_info.alias(), "ml");
-    assert_eq!(alias_info.canonical_tag_id(), canonical_tag_id);
-    assert_eq!(alias_info.source(), "llm");
-    assert_eq!(alias_info.confidence(), confidence);
-    assert_eq!(alias_info.model_version(), Some(model_version));
+    let page = |offset: usize| -> Vec<NoteId> {
+        service
+            .list_notes(ListNotesOptions {
+                limit: Some(3),
+                offset: Some(offset),
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect()
+    };
+    let first = page(0);
+    let second = page(3);
+    assert_eq!(first.len(), 3);
+    assert_eq!(second.len(), 2);
+    assert!(first.iter().all(|id| !second.contains(id)));
+
+    let all: Vec<NoteId> = service
+        .list_notes(ListNotesOptions::default())
+        .expect("failed to list notes")
+        .iter()
+        .map(|n| n.id())
+        .collect();
+    assert_eq!([first, second].concat(), all);
 }
 
 #[test]
-fn user_creates_alias_then_adds_note_with_that_tag() {
-    // Integration test: User creates an alias, then adds a note using that alias
-    // Verify the alias resolution works end-to-end
-
+fn list_notes_offset_without_limit_skips_notes() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    for i in 0..3 {
+        service
+            .create_note(&format!("note {i}"), None)
+            .expect("failed to create note");
+    }
 
-    // Arrange: User creates an alias via CLI (cons tag-alias add ml machine-learning)
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create canonical tag");
-    service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
+    let notes = service
+        .list_notes(ListNotesOptions {
+            offset: Some(1),
+            ..Default::default()
+        })
+        .expect("failed to list notes");
+    assert_eq!(notes.len(), 2);
+}
 
-    // Act: User adds a note with the alias (cons add --tags ml "...")
-    let note = service
-        .create_note("Learning about ML", Some(&["ml"]))
+#[test]
+fn list_notes_before_cursor_is_stable_when_notes_are_added() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    for i in 0..5 {
+        service
+            .create_note(&format!("note {i}"), Some(&["rust"]))
+            .expect("failed to create note");
+    }
+    service
+        .create_note("untagged note", None)
         .expect("failed to create note");
 
-    // Assert: Note is tagged with canonical tag, not alias
-    assert_eq!(note.tags().len(), 1, "note should have 1 tag");
+    let page = |before: Option<i64>| -> Vec<NoteId> {
+        service
+            .list_notes(ListNotesOptions {
+                limit: Some(2),
+                tags: Some(vec!["rust".to_string()]),
+                before,
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect()
+    };
 
-    let conn = service.database().connection();
-    let tag_name: String = conn
-        .query_row(
-            "SELECT name FROM tags WHERE id = ?1",
-            [note.tags()[0].tag_id().get()],
-            |row| row.get(0),
-        )
-        .expect("failed to get tag name");
+    let first = page(None);
+    assert_eq!(first.len(), 2);
 
-    assert_eq!(
-        tag_name, "machine-learning",
-        "note should be tagged with canonical form"
+    // A note added between page requests must not shift the next page
+    service
+        .create_note("newest note", Some(&["rust"]))
+        .expect("failed to create note");
+
+    let cursor = first.last().expect("first page should not be empty").get();
+    let second = page(Some(cursor));
+    assert_eq!(second.len(), 2);
+    assert!(
+        second.iter().all(|id| !first.contains(id)),
+        "previously seen notes must not reappear"
     );
+    assert!(second.iter().all(|id| id.get() < cursor));
+
+    let third = page(Some(second.last().unwrap().get()));
+    assert_eq!(third.len(), 1, "only the oldest tagged note remains");
 }
 
 #[test]
-fn alias_removal_then_tag_creation_with_same_name() {
-    // Integration test: After removing an alias, the alias name can be used as a new tag
-    // This verifies alias removal doesn't leave orphaned constraints
-
+fn list_notes_before_cursor_follows_sort_key_not_id() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let ids = create_notes(
+        &service,
+        [("a", &[]), ("b", &[]), ("c", &[]), ("d", &[]), ("e", &[])],
+    );
+    // Imported notes keep their original timestamps, so ids disagree with created_at
+    for (id, timestamp) in ids.iter().zip([300, 100, 500, 200, 400]) {
+        service
+            .database()
+            .connection()
+            .execute(
+                "UPDATE notes SET created_at = ?1 WHERE id = ?2",
+                [timestamp, id.get()],
+            )
+            .expect("failed to backdate note");
+    }
 
-    // Arrange: Create an alias
-    let canonical_tag_id = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create canonical tag");
-    service
-        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    // Act: Remove the alias
-    service.remove_alias("ml").expect("failed to remove alias");
+    let mut seen = Vec::new();
+    let mut before = None;
+    loop {
+        let page: Vec<NoteId> = service
+            .list_notes(ListNotesOptions {
+                limit: Some(2),
+                before,
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect();
+        let Some(last) = page.last() else { break };
+        before = Some(last.get());
+        seen.extend(page);
+    }
 
-    // Now create a new tag with the name "ml" (not an alias, a real tag)
-    let new_ml_tag_id = service
-        .get_or_create_tag("ml")
-        .expect("failed to create new ml tag");
+    assert_eq!(seen, vec![ids[2], ids[4], ids[0], ids[3], ids[1]]);
+}
 
-    // Assert: New tag was created (not resolved to old canonical)
-    assert_ne!(
-        new_ml_tag_id, canonical_tag_id,
-        "ml should be a new tag, not resolved to old canonical"
+#[test]
+fn list_notes_before_cursor_pages_ascending_lists_forward() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let ids = create_notes(
+        &service,
+        [("a", &[]), ("b", &[]), ("c", &[]), ("d", &[]), ("e", &[])],
     );
+    for (id, timestamp) in ids.iter().zip([300, 100, 500, 200, 400]) {
+        service
+            .database()
+            .connection()
+            .execute(
+                "UPDATE notes SET created_at = ?1 WHERE id = ?2",
+                [timestamp, id.get()],
+            )
+            .expect("failed to backdate note");
+    }
 
-    // Verify the new tag exists in tags table
-    let conn = service.database().connection();
-    let count: i64 = conn
-        .query_row("SELECT COUNT(*) FROM tags WHERE name = 'ml'", [], |row| {
-            row.get(0)
-        })
-        .expect("failed to count ml tags");
+    let page = |before: Option<i64>| -> Vec<NoteId> {
+        service
+            .list_notes(ListNotesOptions {
+                limit: Some(2),
+                order: SortOrder::Ascending,
+                before,
+                ..Default::default()
+            })
+            .expect("failed to list notes")
+            .iter()
+            .map(|n| n.id())
+            .collect()
+    };
 
-    assert_eq!(count, 1, "ml tag should exist");
-}
+    let mut seen = Vec::new();
+    let mut before = None;
+    loop {
+        let next = page(before);
+        let Some(last) = next.last() else { break };
+        before = Some(last.get());
+        seen.extend(next);
+    }
+    assert_eq!(seen, vec![ids[1], ids[3], ids[0], ids[4], ids[2]]);
 
-// --- Enhancement Field Tests (Task Group 2: Note Text Enhancement) ---
+    // A deleted cursor falls back to ids, still moving forward
+    service.delete_note(ids[3]).expect("failed to delete note");
+    let after_deleted = page(Some(ids[3].get()));
+    assert!(after_deleted.iter().all(|id| id.get() > ids[3].get()));
+    assert_eq!(after_deleted.len(), 1);
+}
 
 #[test]
-fn note_struct_includes_optional_enhancement_fields() {
+fn search_pages_do_not_overlap_and_cover_all_results() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    for i in 0..5 {
+        service
+            .create_note(&format!("rust note {i}"), None)
+            .expect("failed to create note");
+    }
 
-    // Create note without enhancement
-    let note = service
-        .create_note("Original content", None)
-        .expect("failed to create note");
+    let fts_page = |offset: usize| -> Vec<NoteId> {
+        let filters = SearchFilters {
+            offset: Some(offset),
+            ..Default::default()
+        };
+        service
+            .search_notes_filtered("rust", Some(3), &filters)
+            .expect("search should succeed")
+            .iter()
+            .map(|r| r.note.id())
+            .collect()
+    };
+    let all: Vec<NoteId> = service
+        .search_notes("rust", None)
+        .expect("search should succeed")
+        .iter()
+        .map(|r| r.note.id())
+        .collect();
+    assert_eq!([fts_page(0), fts_page(3)].concat(), all);
 
-    // Verify enhancement fields are None by default
-    assert_eq!(note.content_enhanced(), None);
-    assert_eq!(note.enhanced_at(), None);
-    assert_eq!(note.enhancement_model(), None);
-    assert_eq!(note.enhancement_confidence(), None);
+    let dual_page = |offset: usize| -> Vec<NoteId> {
+        let filters = SearchFilters {
+            offset: Some(offset),
+            ..Default::default()
+        };
+        service
+            .dual_search_filtered("rust", Some(3), &filters)
+            .expect("dual search should succeed")
+            .0
+            .iter()
+            .map(|r| r.note.id())
+            .collect()
+    };
+    let first = dual_page(0);
+    let second = dual_page(3);
+    assert_eq!(first.len(), 3);
+    assert_eq!(second.len(), 2);
+    assert!(first.iter().all(|id| !second.contains(id)));
 }
 
+// --- Additional Critical Gap Tests (Task Group 5) ---
+
 #[test]
-fn note_builder_supports_setting_enhancement_fields() {
-    use time::OffsetDateTime;
+fn list_notes_returns_empty_vec_for_empty_database() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let now = OffsetDateTime::now_utc();
-    let note = NoteBuilder::new()
-        .id(NoteId::new(1))
-        .content("Original content")
-        .content_enhanced("Enhanced content")
-        .enhanced_at(now)
-        .enhancement_model("deepseek-r1:8b")
-        .enhancement_confidence(0.85)
-        .build();
+    let notes = service
+        .list_notes(ListNotesOptions::default())
+        .expect("failed to list notes");
 
-    assert_eq!(note.content_enhanced(), Some("Enhanced content"));
-    assert_eq!(note.enhanced_at(), Some(now));
-    assert_eq!(note.enhancement_model(), Some("deepseek-r1:8b"));
-    assert_eq!(note.enhancement_confidence(), Some(0.85));
+    assert_eq!(notes.len(), 0, "should return empty vec for empty database");
 }
 
 #[test]
-fn note_accessors_return_correct_values_for_enhancement_fields() {
-    use time::OffsetDateTime;
+fn add_tags_to_note_fails_for_non_existent_note() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let enhanced_time = OffsetDateTime::now_utc();
-    let note = NoteBuilder::new()
-        .id(NoteId::new(42))
-        .content("Short note")
-        .content_enhanced("This is a more detailed version of the short note.")
-        .enhanced_at(enhanced_time)
-        .enhancement_model("deepseek-r1:8b")
-        .enhancement_confidence(0.92)
-        .build();
+    let result =
+        service.add_tags_to_note(NoteId::new(999), &["rust", "programming"], TagSource::User);
 
-    // Test all accessors
-    assert_eq!(
-        note.content_enhanced(),
-        Some("This is a more detailed version of the short note.")
+    assert!(
+        result.is_err(),
+        "should return error when adding tags to non-existent note"
     );
-    assert_eq!(note.enhanced_at(), Some(enhanced_time));
-    assert_eq!(note.enhancement_model(), Some("deepseek-r1:8b"));
-    assert_eq!(note.enhancement_confidence(), Some(0.92));
 
-    // Verify None case
-    let plain_note = NoteBuilder::new()
-        .id(NoteId::new(1))
-        .content("Plain note")
-        .build();
+    let err_msg = result.unwrap_err().to_string();
+    assert!(
+        err_msg.contains("does not exist"),
+        "error message should indicate note doesn't exist: {}",
+        err_msg
+    );
+}
 
-    assert_eq!(plain_note.content_enhanced(), None);
-    assert_eq!(plain_note.enhanced_at(), None);
-    assert_eq!(plain_note.enhancement_model(), None);
-    assert_eq!(plain_note.enhancement_confidence(), None);
+#[test]
+fn list_notes_with_empty_tags_filter_returns_no_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create some notes
+    service
+        .create_note("Note 1", Some(&["rust"]))
+        .expect("failed to create note 1");
+
+    service
+        .create_note("Note 2", Some(&["programming"]))
+        .expect("failed to create note 2");
+
+    // Filter with empty tags list
+    let options = ListNotesOptions {
+        tags: Some(vec![]),
+        ..Default::default()
+    };
+
+    let notes = service.list_notes(options).expect("failed to list notes");
+
+    assert_eq!(notes.len(), 0, "empty tags filter should return no notes");
 }
 
 #[test]
-fn note_service_stores_enhancement_data_on_note_creation() {
+fn delete_note_cascades_to_note_tags_table() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note without enhancement (normal flow)
+    // Create note with tags
     let note = service
-        .create_note("Original", None)
+        .create_note("Note with tags", Some(&["rust", "programming"]))
         .expect("failed to create note");
 
-    // Verify enhancement fields are NULL in database
+    // Verify tags exist in note_tags table
     let conn = service.database().connection();
-    let row: (Option<String>, Option<i64>, Option<String>, Option<f64>) = conn
+    let tag_count_before: i64 = conn
         .query_row(
-            "SELECT content_enhanced, enhanced_at, enhancement_model, enhancement_confidence
-             FROM notes WHERE id = ?1",
+            "SELECT COUNT(*) FROM note_tags WHERE note_id = ?1",
             [note.id().get()],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            |row| row.get(0),
         )
-        .expect("failed to query enhancement fields");
+        .expect("failed to count note_tags");
 
-    assert_eq!(row.0, None, "content_enhanced should be NULL");
-    assert_eq!(row.1, None, "enhanced_at should be NULL");
-    assert_eq!(row.2, None, "enhancement_model should be NULL");
-    assert_eq!(row.3, None, "enhancement_confidence should be NULL");
+    assert_eq!(tag_count_before, 2, "note should have 2 tag associations");
+
+    // Delete the note
+    service
+        .delete_note(note.id())
+        .expect("failed to delete note");
+
+    // Verify note_tags entries are also deleted (cascade)
+    let tag_count_after: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM note_tags WHERE note_id = ?1",
+            [note.id().get()],
+            |row| row.get(0),
+        )
+        .expect("failed to count note_tags");
+
+    assert_eq!(
+        tag_count_after, 0,
+        "note_tags entries should be deleted via cascade"
+    );
 }
 
 #[test]
-fn note_service_retrieves_enhancement_data_from_database() {
-    use time::OffsetDateTime;
-
+fn timestamp_conversion_maintains_accuracy() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note and manually insert enhancement data
+    // Capture Unix timestamp before creation (second precision like database)
+    let before_unix = OffsetDateTime::now_utc().unix_timestamp();
+
     let note = service
-        .create_note("Original content", None)
+        .create_note("Timestamp test", None)
         .expect("failed to create note");
 
-    let enhanced_time = OffsetDateTime::now_utc().unix_timestamp();
-    let conn = service.database().connection();
-    conn.execute(
-        "UPDATE notes
-         SET content_enhanced = ?1, enhanced_at = ?2, enhancement_model = ?3, enhancement_confidence = ?4
-         WHERE id = ?5",
-        (
-            "Enhanced version of the content",
-            enhanced_time,
-            "deepseek-r1:8b",
-            0.88,
-            note.id().get(),
-        ),
-    )
-    .expect("failed to update enhancement fields");
+    // Capture Unix timestamp after creation
+    let after_unix = OffsetDateTime::now_utc().unix_timestamp();
 
-    // Retrieve note and verify enhancement fields are loaded
+    let note_unix = note.created_at().unix_timestamp();
+
+    // Verify created_at is within expected range (Unix timestamps are seconds)
+    assert!(
+        note_unix >= before_unix && note_unix <= after_unix,
+        "created_at Unix timestamp should be between before ({}) and after ({}), got {}",
+        before_unix,
+        after_unix,
+        note_unix
+    );
+
+    // Verify created_at equals updated_at on creation
+    assert_eq!(
+        note.created_at(),
+        note.updated_at(),
+        "created_at and updated_at should match on creation"
+    );
+
+    // Verify timestamp round-trip through database
     let retrieved = service
         .get_note(note.id())
         .expect("failed to get note")
         .expect("note should exist");
 
     assert_eq!(
-        retrieved.content_enhanced(),
-        Some("Enhanced version of the content")
+        retrieved.created_at(),
+        note.created_at(),
+        "timestamps should survive database round-trip"
     );
     assert_eq!(
-        retrieved.enhanced_at(),
-        Some(OffsetDateTime::from_unix_timestamp(enhanced_time).unwrap())
+        retrieved.updated_at(),
+        note.updated_at(),
+        "timestamps should survive database round-trip"
     );
-    assert_eq!(retrieved.enhancement_model(), Some("deepseek-r1:8b"));
-    assert_eq!(retrieved.enhancement_confidence(), Some(0.88));
 }
 
-#[test]
-fn update_note_enhancement_method_updates_existing_note() {
-    use time::OffsetDateTime;
+// --- Tag Alias Tests (Task Group 2: Alias Service Methods) ---
 
+#[test]
+fn resolve_alias_returns_canonical_tag_id_for_existing_alias() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note without enhancement
-    let note = service
-        .create_note("Quick thought", None)
-        .expect("failed to create note");
-
-    // Verify no enhancement initially
-    assert_eq!(note.content_enhanced(), None);
+    // Create a canonical tag
+    let canonical_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    // Update enhancement after note creation (simulates LLM enhancement workflow)
-    let enhanced_time = OffsetDateTime::now_utc();
+    // Create an alias for it
     service
-        .update_note_enhancement(
-            note.id(),
-            "This is a quick thought about something important.",
-            "deepseek-r1:8b",
-            0.90,
-            enhanced_time,
-        )
-        .expect("failed to update note enhancement");
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Retrieve and verify enhancement was added
-    let updated = service
-        .get_note(note.id())
-        .expect("failed to get note")
-        .expect("note should exist");
+    // Resolve the alias
+    let resolved = service
+        .resolve_alias("ml")
+        .expect("failed to resolve alias")
+        .expect("alias should exist");
 
     assert_eq!(
-        updated.content_enhanced(),
-        Some("This is a quick thought about something important.")
-    );
-    // Unix timestamp loses sub-second precision, so compare timestamps
-    assert_eq!(
-        updated.enhanced_at().unwrap().unix_timestamp(),
-        enhanced_time.unix_timestamp()
+        resolved, canonical_tag_id,
+        "alias should resolve to canonical tag ID"
     );
-    assert_eq!(updated.enhancement_model(), Some("deepseek-r1:8b"));
-    assert_eq!(updated.enhancement_confidence(), Some(0.90));
-    // Original content should be unchanged
-    assert_eq!(updated.content(), "Quick thought");
 }
 
-// --- Search Tests (Task Group 2: NoteService Search Method) ---
-
 #[test]
-fn search_notes_returns_matching_notes() {
+fn resolve_alias_returns_none_for_non_existent_alias() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with different content
-    service
-        .create_note("Learning Rust programming", Some(&["rust"]))
-        .expect("failed to create note 1");
-    service
-        .create_note("Python scripting tutorial", Some(&["python"]))
-        .expect("failed to create note 2");
-    service
-        .create_note("Rust and Python comparison", Some(&["rust", "python"]))
-        .expect("failed to create note 3");
-
-    // Search for "rust"
-    let results = service
-        .search_notes("rust", None)
-        .expect("search should succeed");
-
-    assert_eq!(results.len(), 2, "should find 2 notes containing rust");
+    let result = service
+        .resolve_alias("non-existent-alias")
+        .expect("failed to resolve alias");
 
-    // Verify results contain correct notes
-    let contents: Vec<&str> = results.iter().map(|r| r.note.content()).collect();
-    assert!(contents.contains(&"Learning Rust programming"));
-    assert!(contents.contains(&"Rust and Python comparison"));
+    assert_eq!(result, None, "non-existent alias should return None");
 }
 
 #[test]
-fn search_notes_with_and_logic_requires_all_terms() {
+fn create_alias_with_user_source_stores_correctly() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with different combinations of terms
+    // Create a canonical tag
+    let canonical_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+
+    // Create an alias
     service
-        .create_note("Rust programming language", None)
-        .expect("failed to create note 1");
-    service
-        .create_note("Python programming language", None)
-        .expect("failed to create note 2");
-    service
-        .create_note("Rust and Python both great", None)
-        .expect("failed to create note 3");
-    service
-        .create_note("Learning Rust", None)
-        .expect("failed to create note 4");
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Search for "rust programming" (both terms required)
-    let results = service
-        .search_notes("rust programming", None)
-        .expect("search should succeed");
+    // Verify it's stored correctly
+    let aliases = service.list_aliases().expect("failed to list aliases");
 
-    // Only notes containing both "rust" AND "programming" should match
-    assert_eq!(
-        results.len(),
-        1,
-        "should find 1 note with both rust and programming"
-    );
-    assert_eq!(results[0].note.content(), "Rust programming language");
+    assert_eq!(aliases.len(), 1, "should have 1 alias");
+    assert_eq!(aliases[0].alias(), "ml");
+    assert_eq!(aliases[0].canonical_tag_id(), canonical_tag_id);
+    assert_eq!(aliases[0].source(), "user");
+    assert_eq!(aliases[0].confidence(), 1.0);
+    assert_eq!(aliases[0].model_version(), None);
 }
 
 #[test]
-fn search_notes_uses_porter_stemming() {
+fn create_alias_with_llm_source_includes_model_version() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with different word forms that stem to the same root
-    // Using "program" which stems: programming -> program, programs -> program
-    let note1 = service
-        .create_note("I love programming in Rust", None)
-        .expect("failed to create note 1");
-    let note2 = service
-        .create_note("Many programs are written in C", None)
-        .expect("failed to create note 2");
+    // Create a canonical tag
+    let canonical_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    // Search using base form "program" should match both variants
-    let results = service
-        .search_notes("program", None)
-        .expect("search should succeed");
+    // Create an LLM alias
+    service
+        .create_alias("ml", canonical_tag_id, "llm", 0.85, Some("deepseek-r1:8b"))
+        .expect("failed to create alias");
 
-    assert_eq!(
-        results.len(),
-        2,
-        "porter stemming should match programming and programs"
-    );
+    // Verify it's stored correctly
+    let aliases = service.list_aliases().expect("failed to list aliases");
 
-    // Verify both notes are in results
-    let result_ids: Vec<_> = results.iter().map(|r| r.note.id()).collect();
-    assert!(result_ids.contains(&note1.id()));
-    assert!(result_ids.contains(&note2.id()));
+    assert_eq!(aliases.len(), 1, "should have 1 alias");
+    assert_eq!(aliases[0].alias(), "ml");
+    assert_eq!(aliases[0].source(), "llm");
+    assert_eq!(aliases[0].confidence(), 0.85);
+    assert_eq!(aliases[0].model_version(), Some("deepseek-r1:8b"));
 }
 
 #[test]
-fn search_notes_searches_content_enhanced_and_tags() {
-    use time::OffsetDateTime;
-
+fn create_alias_prevents_alias_to_alias_chains() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let conn = service.database().connection();
 
-    // Create note with original content
-    let note1 = service
-        .create_note("Quick thought", Some(&["machine-learning"]))
-        .expect("failed to create note 1");
+    // Create two canonical tags
+    let ml_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create ml tag");
 
-    // Add enhanced content
-    let now = OffsetDateTime::now_utc();
+    // Create an alias "ml" pointing to machine-learning
     service
-        .update_note_enhancement(
-            note1.id(),
-            "This is a detailed explanation about artificial intelligence",
-            "deepseek-r1:8b",
-            0.9,
-            now,
-        )
-        .expect("failed to update enhancement");
+        .create_alias("ml", ml_tag_id, "user", 1.0, None)
+        .expect("failed to create ml alias");
 
-    // Create another note with tag only
-    service
-        .create_note("Another note", Some(&["rust"]))
-        .expect("failed to create note 2");
+    // Manually create a tag with the name "ml" in the tags table (bypassing get_or_create_tag)
+    // This simulates a scenario where a tag name conflicts with an alias
+    conn.execute("INSERT INTO tags (name) VALUES (?1)", ["ml"])
+        .expect("failed to insert ml tag");
 
-    // Search for term in enhanced content
-    let results = service
-        .search_notes("artificial", None)
-        .expect("search should succeed");
-    assert_eq!(
-        results.len(),
-        1,
-        "should find note by enhanced content term"
+    let ml_as_tag_id = conn.last_insert_rowid();
+
+    // Try to create an alias where the canonical_tag_id points to a tag whose name is "ml"
+    // which is itself an alias - this should fail
+    let result = service.create_alias(
+        "machine-learning-alias",
+        TagId::new(ml_as_tag_id),
+        "user",
+        1.0,
+        None,
     );
-    assert_eq!(results[0].note.id(), note1.id());
 
-    // Search for tag name
-    let tag_results = service
-        .search_notes("machine-learning", None)
-        .expect("search should succeed");
-    assert_eq!(tag_results.len(), 1, "should find note by tag name");
-    assert_eq!(tag_results[0].note.id(), note1.id());
+    assert!(
+        result.is_err(),
+        "creating alias where canonical tag name is itself an alias should fail"
+    );
+
+    let err_msg = result.unwrap_err().to_string();
+    assert!(
+        err_msg.contains("is itself an alias"),
+        "error message should indicate tag is an alias: {}",
+        err_msg
+    );
 }
 
 #[test]
-fn search_notes_empty_query_returns_error() {
+fn list_aliases_returns_all_aliases_grouped_by_canonical_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Empty query should return error
-    let result = service.search_notes("", None);
-    assert!(result.is_err(), "empty query should return error");
+    // Create canonical tags
+    let ml_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create ml tag");
+    let ai_tag_id = service
+        .get_or_create_tag("artificial-intelligence")
+        .expect("failed to create ai tag");
 
-    let err_msg = result.unwrap_err().to_string();
-    assert!(
-        err_msg.contains("cannot be empty"),
-        "error should mention empty query: {}",
-        err_msg
-    );
+    // Create aliases
+    service
+        .create_alias("ml", ml_tag_id, "user", 1.0, None)
+        .expect("failed to create ml alias");
+    service
+        .create_alias("ai", ai_tag_id, "user", 1.0, None)
+        .expect("failed to create ai alias");
+    service
+        .create_alias(
+            "machine-learning-abbrev",
+            ml_tag_id,
+            "llm",
+            0.9,
+            Some("model"),
+        )
+        .expect("failed to create machine-learning-abbrev alias");
 
-    // Whitespace-only query should also fail
-    let whitespace_result = service.search_notes("   ", None);
+    // List all aliases
+    let aliases = service.list_aliases().expect("failed to list aliases");
+
+    assert_eq!(aliases.len(), 3, "should have 3 aliases");
+
+    // Verify they're ordered by canonical tag name, then by alias name
+    let alias_names: Vec<&str> = aliases.iter().map(|a| a.alias()).collect();
+    assert!(alias_names.contains(&"ml"), "should contain ml alias");
+    assert!(alias_names.contains(&"ai"), "should contain ai alias");
     assert!(
-        whitespace_result.is_err(),
-        "whitespace-only query should return error"
+        alias_names.contains(&"machine-learning-abbrev"),
+        "should contain machine-learning-abbrev alias"
     );
 }
 
 #[test]
-fn search_notes_limit_parameter_restricts_results() {
+fn update_alias_canonical_moves_alias_to_new_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create multiple notes with the same term
-    for i in 1..=5 {
-        service
-            .create_note(&format!("Rust note {}", i), None)
-            .expect("failed to create note");
-        std::thread::sleep(std::time::Duration::from_millis(10));
-    }
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+    let meta_learning = service
+        .get_or_create_tag("meta-learning")
+        .expect("failed to create tag");
+    service
+        .create_alias("ml", machine_learning, "llm", 0.9, Some("deepseek-r1:8b"))
+        .expect("failed to create alias");
 
-    // Search without limit
-    let all_results = service
-        .search_notes("rust", None)
-        .expect("search should succeed");
-    assert_eq!(all_results.len(), 5, "should find all 5 notes");
+    service
+        .update_alias_canonical("ML", meta_learning)
+        .expect("failed to retarget alias");
 
-    // Search with limit of 2
-    let limited_results = service
-        .search_notes("rust", Some(2))
-        .expect("search should succeed");
     assert_eq!(
-        limited_results.len(),
-        2,
-        "should return exactly 2 notes when limited"
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias"),
+        Some(meta_learning)
+    );
+    let aliases = service.list_aliases().expect("failed to list aliases");
+    assert_eq!(
+        aliases.len(),
+        1,
+        "retargeting should not duplicate the alias"
     );
+    assert_eq!(aliases[0].source(), "llm", "alias metadata is kept");
 }
 
 #[test]
-fn search_notes_returns_full_note_objects_with_tags() {
+fn update_alias_canonical_rejects_missing_alias_and_chains() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note with tags
-    let note = service
-        .create_note("Rust tutorial", Some(&["rust", "programming"]))
-        .expect("failed to create note");
-
-    // Search for it
-    let results = service
-        .search_notes("tutorial", None)
-        .expect("search should succeed");
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    assert_eq!(results.len(), 1, "should find 1 note");
+    let err = service
+        .update_alias_canonical("ml", machine_learning)
+        .expect_err("missing alias should fail");
+    assert!(err.to_string().contains("does not exist"));
+    assert_eq!(
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias"),
+        None,
+        "a missing alias is never created"
+    );
 
-    // Verify full Note object is returned with tags
-    let found_note = &results[0].note;
-    assert_eq!(found_note.id(), note.id());
-    assert_eq!(found_note.content(), "Rust tutorial");
-    assert_eq!(found_note.tags().len(), 2, "note should include all tags");
+    // Retargeting onto a tag whose name is itself an alias would form a chain
+    service
+        .create_alias("ml", machine_learning, "user", 1.0, None)
+        .expect("failed to create alias");
+    service
+        .create_alias("ai", machine_learning, "user", 1.0, None)
+        .expect("failed to create alias");
+    let conn = service.database().connection();
+    conn.execute("INSERT INTO tags (name) VALUES ('ai')", [])
+        .expect("failed to insert tag");
+    let ai_tag = TagId::new(conn.last_insert_rowid());
+
+    let err = service
+        .update_alias_canonical("ml", ai_tag)
+        .expect_err("alias chain should fail");
+    assert!(err.to_string().contains("is itself an alias"));
+    assert_eq!(
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias"),
+        Some(machine_learning)
+    );
 }
 
-// --- Additional Strategic Tests (Task Group 4: Test Review) ---
-
 #[test]
-fn search_notes_orders_results_by_bm25_relevance() {
+fn rename_alias_keeps_target_and_metadata() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with different relevance for "rust"
-    // Note 1: "rust" appears once
-    let note1 = service
-        .create_note("learning rust programming", None)
-        .expect("failed to create note 1");
-
-    // Note 2: "rust" appears three times (highest relevance)
-    let note2 = service
-        .create_note("rust rust rust is amazing for systems", None)
-        .expect("failed to create note 2");
-
-    // Note 3: "rust" appears twice
-    let note3 = service
-        .create_note("rust and more rust content", None)
-        .expect("failed to create note 3");
-
-    // Search for "rust"
-    let results = service
-        .search_notes("rust", None)
-        .expect("search should succeed");
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+    service
+        .create_alias(
+            "machien-learning",
+            machine_learning,
+            "llm",
+            0.85,
+            Some("deepseek-r1:8b"),
+        )
+        .expect("failed to create alias");
 
-    assert_eq!(results.len(), 3, "should find all 3 notes");
+    service
+        .rename_alias("Machien Learning", "ML")
+        .expect("failed to rename alias");
 
-    // BM25 orders by ascending score (lower is better), so most relevant should be first
-    // Note 2 (3 occurrences) should be most relevant, then note3 (2), then note1 (1)
-    assert_eq!(
-        results[0].note.id(),
-        note2.id(),
-        "most relevant note (3 occurrences) should be first"
-    );
-    assert_eq!(
-        results[1].note.id(),
-        note3.id(),
-        "second most relevant note (2 occurrences) should be second"
-    );
     assert_eq!(
-        results[2].note.id(),
-        note1.id(),
-        "least relevant note (1 occurrence) should be last"
+        service
+            .resolve_alias("machien-learning")
+            .expect("failed to resolve alias"),
+        None
     );
+    let aliases = service.list_aliases().expect("failed to list aliases");
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias(), "ml");
+    assert_eq!(aliases[0].canonical_tag_id(), machine_learning);
+    assert_eq!(aliases[0].source(), "llm");
+    assert_eq!(aliases[0].confidence(), 0.85);
+    assert_eq!(aliases[0].model_version(), Some("deepseek-r1:8b"));
 }
 
 #[test]
-fn search_result_has_normalized_relevance_score() {
+fn rename_alias_rejects_collisions_and_missing_alias() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with different relevance
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
     service
-        .create_note("rust rust rust is amazing for systems", None)
-        .expect("failed to create note 1");
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
     service
-        .create_note("learning rust programming", None)
-        .expect("failed to create note 2");
-
-    // Search for "rust"
-    let results = service
-        .search_notes("rust", None)
-        .expect("search should succeed");
+        .create_alias("ml", machine_learning, "user", 1.0, None)
+        .expect("failed to create alias");
+    service
+        .create_alias("mlearn", machine_learning, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    assert_eq!(results.len(), 2, "should find 2 notes");
+    let err = service
+        .rename_alias("mlearn", "ML")
+        .expect_err("existing alias should conflict");
+    assert!(err.to_string().contains("alias 'ml' already exists"));
 
-    // Verify all SearchResults have note and score fields
-    for result in &results {
-        // Verify note is accessible
-        assert!(
-            !result.note.content().is_empty(),
-            "note content should be accessible"
-        );
+    let err = service
+        .rename_alias("mlearn", "rust")
+        .expect_err("existing tag should conflict");
+    assert!(err.to_string().contains("already a tag name"));
 
-        // Verify relevance_score is in 0.0-1.0 range
-        assert!(
-            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
-            "relevance score {} should be between 0.0 and 1.0",
-            result.relevance_score
-        );
+    let err = service
+        .rename_alias("missing", "other")
+        .expect_err("missing alias should fail");
+    assert!(err.to_string().contains("does not exist"));
 
-        // Verify score is reasonably high (close to 1.0 for matching results)
-        assert!(
-            result.relevance_score > 0.5,
-            "relevance score {} should be > 0.5 for matching results",
-            result.relevance_score
-        );
-    }
+    assert_eq!(
+        service
+            .list_aliases()
+            .expect("failed to list aliases")
+            .len(),
+        2,
+        "failed renames leave aliases untouched"
+    );
+    assert_eq!(
+        service
+            .resolve_alias("mlearn")
+            .expect("failed to resolve alias"),
+        Some(machine_learning)
+    );
 }
 
 #[test]
-fn list_notes_works_independently_of_fts_functionality() {
-    // Fail-safe test: Verify that list_notes doesn't depend on FTS table
-    // This ensures note access via `cons list` works even if FTS has issues
+fn import_aliases_normalizes_and_rolls_back_on_error() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with tags
-    let note1 = service
-        .create_note("First note", Some(&["rust"]))
-        .expect("failed to create note 1");
-
-    let note2 = service
-        .create_note("Second note", Some(&["python"]))
-        .expect("failed to create note 2");
-
-    // Verify FTS table exists and is populated
-    let conn = service.database().connection();
-    let fts_count_before: i64 = conn
-        .query_row("SELECT COUNT(*) FROM notes_fts", [], |row| row.get(0))
-        .expect("FTS table should exist");
-    assert_eq!(fts_count_before, 2, "FTS should have 2 entries");
+    let summary = service
+        .import_aliases(&[
+            ("ML".to_string(), "Machine Learning".to_string()),
+            ("same".to_string(), "Same".to_string()),
+            ("mlearn".to_string(), "ml".to_string()),
+        ])
+        .expect("failed to import aliases");
+    assert_eq!(summary.created, 1);
+    assert_eq!(
+        summary.skipped,
+        vec![
+            ("same".to_string(), "same".to_string()),
+            ("mlearn".to_string(), "ml".to_string()),
+        ]
+    );
 
-    // Simulate FTS corruption by dropping the FTS table
-    // This tests the fail-safe requirement: "FTS issues don't block note access via cons list"
-    conn.execute("DROP TABLE notes_fts", [])
-        .expect("failed to drop FTS table");
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to get tag");
+    assert_eq!(
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve alias"),
+        Some(machine_learning)
+    );
 
-    // Verify FTS table is gone
-    let fts_exists: bool = conn
-        .query_row(
-            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='notes_fts')",
-            [],
-            |row| row.get(0),
+    // A failure part-way through leaves no aliases from the batch behind
+    service
+        .database()
+        .connection()
+        .execute_batch(
+            "CREATE TRIGGER reject_bad_alias BEFORE INSERT ON tag_aliases
+             WHEN NEW.alias = 'bad' BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
         )
-        .expect("failed to check FTS table existence");
-    assert!(!fts_exists, "FTS table should be dropped");
-
-    // list_notes should still work (doesn't depend on FTS)
-    let notes = service
-        .list_notes(ListNotesOptions::default())
-        .expect("list_notes should succeed even without FTS table");
-
+        .expect("failed to create trigger");
+    let result = service.import_aliases(&[
+        ("dl".to_string(), "deep-learning".to_string()),
+        ("bad".to_string(), "deep-learning".to_string()),
+    ]);
+    assert!(result.is_err());
     assert_eq!(
-        notes.len(),
-        2,
-        "should list all notes despite FTS being gone"
+        service
+            .resolve_alias("dl")
+            .expect("failed to resolve alias"),
+        None
     );
+}
 
-    // Verify we got the correct notes
-    let note_ids: Vec<_> = notes.iter().map(|n| n.id()).collect();
-    assert!(note_ids.contains(&note1.id()), "should include first note");
-    assert!(note_ids.contains(&note2.id()), "should include second note");
+#[test]
+fn import_aliases_skips_chains_in_either_line_order() {
+    let pairs = [
+        ("ml".to_string(), "machine-learning".to_string()),
+        ("machine-learning".to_string(), "ai".to_string()),
+    ];
 
-    // Verify notes have their tags
-    for note in &notes {
+    for order in [[0, 1], [1, 0]] {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        let mappings: Vec<_> = order.iter().map(|&i| pairs[i].clone()).collect();
+
+        let summary = service
+            .import_aliases(&mappings)
+            .expect("failed to import aliases");
+        assert_eq!(summary.created, 1, "order {order:?}");
         assert_eq!(
-            note.tags().len(),
-            1,
-            "notes should include their tags even without FTS"
+            summary.skipped,
+            vec![mappings[1].clone()],
+            "order {order:?}"
         );
+
+        // Whichever alias was created resolves directly to a tag, never via another alias
+        for (alias, _) in &mappings {
+            if let Some(tag_id) = service.resolve_alias(alias).expect("failed to resolve") {
+                let name = service.tag_name(tag_id).expect("failed to get tag name");
+                assert_eq!(
+                    service.resolve_alias(&name).expect("failed to resolve"),
+                    None,
+                    "order {order:?}: '{alias}' forms a chain through '{name}'"
+                );
+            }
+        }
     }
 }
 
-// --- Alias Expansion Tests (Task Group 1: Alias Expansion Logic) ---
-
 #[test]
-fn expand_search_term_no_aliases_returns_only_original_term() {
+fn import_aliases_skips_alias_names_that_are_existing_tags() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
 
-    // No aliases or tags exist
-    let expanded = service
-        .expand_search_term("rust")
-        .expect("expansion should succeed");
-
-    assert_eq!(expanded.len(), 1, "should return only original term");
-    assert!(
-        expanded.contains(&"rust".to_string()),
-        "should contain original term"
+    let summary = service
+        .import_aliases(&[("Rust".to_string(), "rust-lang".to_string())])
+        .expect("failed to import aliases");
+    assert_eq!(summary.created, 0);
+    assert_eq!(
+        summary.skipped,
+        vec![("rust".to_string(), "rust-lang".to_string())]
     );
 }
 
 #[test]
-fn expand_search_term_alias_expands_to_canonical() {
+fn remove_alias_deletes_mapping_idempotently() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
+    // Create a canonical tag and alias
+    let canonical_tag_id = service
         .get_or_create_tag("machine-learning")
         .expect("failed to create tag");
     service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Expand alias
-    let expanded = service
-        .expand_search_term("ml")
-        .expect("expansion should succeed");
-
+    // Verify alias exists
     assert!(
-        expanded.contains(&"ml".to_string()),
-        "should contain original alias"
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve")
+            .is_some(),
+        "alias should exist before removal"
     );
+
+    // Remove the alias
+    service.remove_alias("ml").expect("failed to remove alias");
+
+    // Verify it's gone
     assert!(
-        expanded.contains(&"machine-learning".to_string()),
-        "should contain canonical tag name"
+        service
+            .resolve_alias("ml")
+            .expect("failed to resolve")
+            .is_none(),
+        "alias should not exist after removal"
     );
-}
-
-#[test]
-fn expand_search_term_canonical_expands_to_all_aliases() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
 
-    // Create canonical tag and multiple aliases
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-    service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create ml alias");
+    // Remove again (idempotent)
     service
-        .create_alias("ai-ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create ai-ml alias");
-
-    // Expand canonical tag name
-    let expanded = service
-        .expand_search_term("machine-learning")
-        .expect("expansion should succeed");
+        .remove_alias("ml")
+        .expect("second remove should succeed (idempotent)");
 
-    assert!(
-        expanded.contains(&"machine-learning".to_string()),
-        "should contain canonical tag"
-    );
-    assert!(
-        expanded.contains(&"ml".to_string()),
-        "should contain ml alias"
-    );
-    assert!(
-        expanded.contains(&"ai-ml".to_string()),
-        "should contain ai-ml alias"
-    );
+    // Remove non-existent alias (also idempotent)
+    service
+        .remove_alias("non-existent")
+        .expect("removing non-existent alias should succeed (idempotent)");
 }
 
 #[test]
-fn expand_search_term_user_aliases_always_included() {
+fn alias_lookup_happens_after_normalization() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag
-    let ml_tag = service
+    // Create a canonical tag
+    let canonical_tag_id = service
         .get_or_create_tag("machine-learning")
         .expect("failed to create tag");
 
-    // Create user alias with low confidence (should still be included)
+    // Create an alias with normalized form
     service
-        .create_alias("ml", ml_tag, "user", 0.5, None)
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Expand from canonical
-    let expanded = service
-        .expand_search_term("machine-learning")
-        .expect("expansion should succeed");
-
-    assert!(
-        expanded.contains(&"ml".to_string()),
-        "user alias should be included regardless of confidence"
-    );
-}
-
-#[test]
-fn expand_search_term_llm_alias_high_confidence_included() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
-
-    // Create canonical tag
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-
-    // Create LLM alias with confidence >= 0.8
-    service
-        .create_alias("ml", ml_tag, "llm", 0.85, Some("deepseek-r1:8b"))
-        .expect("failed to create alias");
+    // Resolve using different case (should normalize before lookup)
+    let resolved_lower = service
+        .resolve_alias("ml")
+        .expect("failed to resolve")
+        .expect("should find alias");
 
-    // Expand from canonical
-    let expanded = service
-        .expand_search_term("machine-learning")
-        .expect("expansion should succeed");
+    let resolved_upper = service
+        .resolve_alias("ML")
+        .expect("failed to resolve")
+        .expect("should find alias with different case");
 
-    assert!(
-        expanded.contains(&"ml".to_string()),
-        "LLM alias with confidence >= 0.8 should be included"
+    assert_eq!(
+        resolved_lower, resolved_upper,
+        "case-insensitive lookup should work"
+    );
+    assert_eq!(
+        resolved_lower, canonical_tag_id,
+        "both should resolve to canonical tag"
     );
 }
 
+// --- AutoTagger Alias Integration Tests ---
+
 #[test]
-fn expand_search_term_llm_alias_low_confidence_excluded() {
+fn llm_suggested_alias_auto_creation_workflow() {
+    // Integration test simulating tag_note_with_llm creating an LLM-suggested alias
+    // when LLM suggests a tag that could be an alias for an existing tag
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag
-    let ml_tag = service
+    // Arrange: Pre-existing canonical tag "machine-learning" exists
+    let canonical_tag_id = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+        .expect("failed to create canonical tag");
 
-    // Create LLM alias with confidence < 0.8
+    // Act: Simulate LLM suggesting "ml" as a tag (detected as alias opportunity)
+    // This mimics the find_alias_opportunity + create_alias flow in tag_note_with_llm
+    let suggested_tag = "ml";
+    let model_version = "deepseek-r1:8b";
+    let confidence = 0.85;
+
+    // Create the LLM-suggested alias
     service
-        .create_alias("ml", ml_tag, "llm", 0.75, Some("deepseek-r1:8b"))
-        .expect("failed to create alias");
+        .create_alias(
+            suggested_tag,
+            canonical_tag_id,
+            "llm",
+            confidence,
+            Some(model_version),
+        )
+        .expect("failed to create LLM alias");
 
-    // Expand from canonical
-    let expanded = service
-        .expand_search_term("machine-learning")
-        .expect("expansion should succeed");
+    // Assert: Alias was created with correct provenance
+    let alias_info_list = service.list_aliases().expect("failed to list aliases");
+    assert_eq!(alias_info_list.len(), 1, "should have 1 alias");
 
-    assert!(
-        expanded.contains(&"machine-learning".to_string()),
-        "should contain original canonical term"
-    );
-    assert!(
-        !expanded.contains(&"ml".to_string()),
-        "LLM alias with confidence < 0.8 should be excluded"
-    );
+    let alias_info = &alias_info_list[0];
+    assert_eq!(alias_info.alias(), "ml");
+    assert_eq!(alias_info.canonical_tag_id(), canonical_tag_id);
+    assert_eq!(alias_info.source(), "llm");
+    assert_eq!(alias_info.confidence(), confidence);
+    assert_eq!(alias_info.model_version(), Some(model_version));
 }
 
-// --- Search Integration with Alias Expansion Tests (Task Group 2: Search Integration) ---
-
 #[test]
-fn search_for_alias_term_finds_notes_with_canonical_tag() {
+fn user_creates_alias_then_adds_note_with_that_tag() {
+    // Integration test: User creates an alias, then adds a note using that alias
+    // Verify the alias resolution works end-to-end
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
+    // Arrange: User creates an alias via CLI (cons tag-alias add ml machine-learning)
+    let canonical_tag_id = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+        .expect("failed to create canonical tag");
     service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Create note with canonical tag
+    // Act: User adds a note with the alias (cons add --tags ml "...")
     let note = service
-        .create_note("Deep learning tutorial", Some(&["machine-learning"]))
+        .create_note("Learning about ML", Some(&["ml"]))
         .expect("failed to create note");
 
-    // Search using alias term "ml" - should find note tagged with "machine-learning"
-    let results = service
-        .search_notes("ml", None)
-        .expect("search should succeed");
+    // Assert: Note is tagged with canonical tag, not alias
+    assert_eq!(note.tags().len(), 1, "note should have 1 tag");
+
+    let conn = service.database().connection();
+    let tag_name: String = conn
+        .query_row(
+            "SELECT name FROM tags WHERE id = ?1",
+            [note.tags()[0].tag_id().get()],
+            |row| row.get(0),
+        )
+        .expect("failed to get tag name");
 
     assert_eq!(
-        results.len(),
-        1,
-        "searching for alias 'ml' should find note with 'machine-learning' tag"
+        tag_name, "machine-learning",
+        "note should be tagged with canonical form"
     );
-    assert_eq!(results[0].note.id(), note.id());
 }
 
 #[test]
-fn search_for_canonical_term_finds_notes_with_alias_tags() {
+fn alias_removal_then_tag_creation_with_same_name() {
+    // Integration test: After removing an alias, the alias name can be used as a new tag
+    // This verifies alias removal doesn't leave orphaned constraints
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
+    // Arrange: Create an alias
+    let canonical_tag_id = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+        .expect("failed to create canonical tag");
     service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .create_alias("ml", canonical_tag_id, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Create a note that has "ml" in content (simulating a note where user mentioned the alias)
-    // Note: When user creates note with tag "ml", it gets resolved to "machine-learning"
-    // So we need to test via content search
-    let note = service
-        .create_note("Learning about ML algorithms", Some(&["machine-learning"]))
-        .expect("failed to create note");
+    // Act: Remove the alias
+    service.remove_alias("ml").expect("failed to remove alias");
 
-    // Search for canonical term should find notes
-    let results = service
-        .search_notes("machine-learning", None)
-        .expect("search should succeed");
+    // Now create a new tag with the name "ml" (not an alias, a real tag)
+    let new_ml_tag_id = service
+        .get_or_create_tag("ml")
+        .expect("failed to create new ml tag");
 
-    assert_eq!(
-        results.len(),
-        1,
-        "searching for canonical term should find note"
+    // Assert: New tag was created (not resolved to old canonical)
+    assert_ne!(
+        new_ml_tag_id, canonical_tag_id,
+        "ml should be a new tag, not resolved to old canonical"
     );
-    assert_eq!(results[0].note.id(), note.id());
 
-    // Now test the reverse: search for "ml" finds note with content mentioning ML
-    let alias_results = service
-        .search_notes("ml", None)
-        .expect("search should succeed");
+    // Verify the new tag exists in tags table
+    let conn = service.database().connection();
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM tags WHERE name = 'ml'", [], |row| {
+            row.get(0)
+        })
+        .expect("failed to count ml tags");
 
-    assert!(
-        !alias_results.is_empty(),
-        "searching for 'ml' should find note"
-    );
+    assert_eq!(count, 1, "ml tag should exist");
 }
 
+// --- Enhancement Field Tests (Task Group 2: Note Text Enhancement) ---
+
 #[test]
-fn multi_term_search_expands_each_term_independently() {
+fn note_struct_includes_optional_enhancement_fields() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tags and aliases
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create ml tag");
-    service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create ml alias");
-
-    let nlp_tag = service
-        .get_or_create_tag("natural-language-processing")
-        .expect("failed to create nlp tag");
-    service
-        .create_alias("nlp", nlp_tag, "user", 1.0, None)
-        .expect("failed to create nlp alias");
-
-    // Create note with both canonical tags
+    // Create note without enhancement
     let note = service
-        .create_note(
-            "NLP and ML research",
-            Some(&["machine-learning", "natural-language-processing"]),
-        )
+        .create_note("Original content", None)
         .expect("failed to create note");
 
-    // Create another note with only one tag
-    service
-        .create_note("Just ML stuff", Some(&["machine-learning"]))
-        .expect("failed to create note 2");
-
-    // Search using both alias terms - should use AND logic between expanded groups
-    let results = service
-        .search_notes("ml nlp", None)
-        .expect("search should succeed");
-
-    // Should find only the note with both tags
-    assert_eq!(
-        results.len(),
-        1,
-        "multi-term search should find note with both expanded terms"
-    );
-    assert_eq!(results[0].note.id(), note.id());
+    // Verify enhancement fields are None by default
+    assert_eq!(note.content_enhanced(), None);
+    assert_eq!(note.enhanced_at(), None);
+    assert_eq!(note.enhancement_model(), None);
+    assert_eq!(note.enhancement_confidence(), None);
 }
 
 #[test]
-fn multi_word_alias_handled_as_phrase_match() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
-
-    // Create canonical tag and aliases
-    // Use a canonical tag name that won't conflict with the alias normalization
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+fn note_builder_supports_setting_enhancement_fields() {
+    use time::OffsetDateTime;
 
-    // Create the single-word alias first
-    service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create ml alias");
+    let now = OffsetDateTime::now_utc();
+    let note = NoteBuilder::new()
+        .id(NoteId::new(1))
+        .content("Original content")
+        .content_enhanced("Enhanced content")
+        .enhanced_at(now)
+        .enhancement_model("deepseek-r1:8b")
+        .enhancement_confidence(0.85)
+        .build();
 
-    // Create note with content mentioning "machine learning" (multi-word)
-    let note = service
-        .create_note(
-            "Studies in machine learning are fascinating",
-            Some(&["machine-learning"]),
-        )
-        .expect("failed to create note");
+    assert_eq!(note.content_enhanced(), Some("Enhanced content"));
+    assert_eq!(note.enhanced_at(), Some(now));
+    assert_eq!(note.enhancement_model(), Some("deepseek-r1:8b"));
+    assert_eq!(note.enhancement_confidence(), Some(0.85));
+}
 
-    // Search for single-word alias "ml" should find note via alias expansion
-    let results = service
-        .search_notes("ml", None)
-        .expect("search should succeed");
+#[test]
+fn note_accessors_return_correct_values_for_enhancement_fields() {
+    use time::OffsetDateTime;
 
-    assert!(
-        !results.is_empty(),
-        "search should find note via alias expansion"
+    let enhanced_time = OffsetDateTime::now_utc();
+    let note = NoteBuilder::new()
+        .id(NoteId::new(42))
+        .content("Short note")
+        .content_enhanced("This is a more detailed version of the short note.")
+        .enhanced_at(enhanced_time)
+        .enhancement_model("deepseek-r1:8b")
+        .enhancement_confidence(0.92)
+        .build();
+
+    // Test all accessors
+    assert_eq!(
+        note.content_enhanced(),
+        Some("This is a more detailed version of the short note.")
     );
-    assert_eq!(results[0].note.id(), note.id());
+    assert_eq!(note.enhanced_at(), Some(enhanced_time));
+    assert_eq!(note.enhancement_model(), Some("deepseek-r1:8b"));
+    assert_eq!(note.enhancement_confidence(), Some(0.92));
+
+    // Verify None case
+    let plain_note = NoteBuilder::new()
+        .id(NoteId::new(1))
+        .content("Plain note")
+        .build();
+
+    assert_eq!(plain_note.content_enhanced(), None);
+    assert_eq!(plain_note.enhanced_at(), None);
+    assert_eq!(plain_note.enhancement_model(), None);
+    assert_eq!(plain_note.enhancement_confidence(), None);
 }
 
 #[test]
-fn search_without_aliases_passes_through_unchanged() {
+fn note_service_stores_enhancement_data_on_note_creation() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes without any aliases defined
+    // Create note without enhancement (normal flow)
     let note = service
-        .create_note("Rust programming is great", Some(&["rust"]))
+        .create_note("Original", None)
         .expect("failed to create note");
 
-    // Search for a term that has no aliases
-    let results = service
-        .search_notes("rust", None)
-        .expect("search should succeed");
+    // Verify enhancement fields are NULL in database
+    let conn = service.database().connection();
+    let row: (Option<String>, Option<i64>, Option<String>, Option<f64>) = conn
+        .query_row(
+            "SELECT content_enhanced, enhanced_at, enhancement_model, enhancement_confidence
+             FROM notes WHERE id = ?1",
+            [note.id().get()],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .expect("failed to query enhancement fields");
 
-    assert_eq!(
-        results.len(),
-        1,
-        "search should work normally when no aliases exist"
-    );
-    assert_eq!(results[0].note.id(), note.id());
+    assert_eq!(row.0, None, "content_enhanced should be NULL");
+    assert_eq!(row.1, None, "enhanced_at should be NULL");
+    assert_eq!(row.2, None, "enhancement_model should be NULL");
+    assert_eq!(row.3, None, "enhancement_confidence should be NULL");
 }
 
 #[test]
-fn search_with_alias_expansion_preserves_bm25_scoring() {
+fn note_service_retrieves_enhancement_data_from_database() {
+    use time::OffsetDateTime;
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-    service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    // Create notes with different content
-    service
-        .create_note(
-            "machine-learning machine-learning machine-learning",
-            Some(&["machine-learning"]),
-        )
-        .expect("failed to create highly relevant note");
-
-    service
-        .create_note("Just one mention of ml", Some(&["machine-learning"]))
-        .expect("failed to create less relevant note");
-
-    // Search using alias term
-    let results = service
-        .search_notes("ml", None)
-        .expect("search should succeed");
+    // Create note and manually insert enhancement data
+    let note = service
+        .create_note("Original content", None)
+        .expect("failed to create note");
 
-    assert_eq!(results.len(), 2, "should find both notes");
+    let enhanced_time = OffsetDateTime::now_utc().unix_timestamp();
+    let conn = service.database().connection();
+    conn.execute(
+        "UPDATE notes
+         SET content_enhanced = ?1, enhanced_at = ?2, enhancement_model = ?3, enhancement_confidence = ?4
+         WHERE id = ?5",
+        (
+            "Enhanced version of the content",
+            enhanced_time,
+            "deepseek-r1:8b",
+            0.88,
+            note.id().get(),
+        ),
+    )
+    .expect("failed to update enhancement fields");
 
-    // Verify SearchResult structure is preserved with valid scores
-    for result in &results {
-        assert!(
-            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
-            "relevance score {} should be normalized between 0.0 and 1.0",
-            result.relevance_score
-        );
-        assert!(
-            !result.note.content().is_empty(),
-            "note content should be accessible"
-        );
-    }
+    // Retrieve note and verify enhancement fields are loaded
+    let retrieved = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
 
-    // Verify both notes were found (order may vary due to OR expansion behavior)
-    let contents: Vec<&str> = results.iter().map(|r| r.note.content()).collect();
-    assert!(
-        contents.contains(&"machine-learning machine-learning machine-learning"),
-        "should find note with multiple machine-learning occurrences"
+    assert_eq!(
+        retrieved.content_enhanced(),
+        Some("Enhanced version of the content")
     );
-    assert!(
-        contents.contains(&"Just one mention of ml"),
-        "should find note with ml mention"
+    assert_eq!(
+        retrieved.enhanced_at(),
+        Some(OffsetDateTime::from_unix_timestamp(enhanced_time).unwrap())
     );
+    assert_eq!(retrieved.enhancement_model(), Some("deepseek-r1:8b"));
+    assert_eq!(retrieved.enhancement_confidence(), Some(0.88));
 }
 
-// --- Additional Strategic Tests for Alias-Expanded FTS (Task Group 3: Gap Analysis) ---
-
 #[test]
-fn expand_search_term_case_insensitive_lookup() {
-    // Tests case sensitivity handling in expansion
+fn update_note_enhancement_method_updates_existing_note() {
+    use time::OffsetDateTime;
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+    // Create note without enhancement
+    let note = service
+        .create_note("Quick thought", None)
+        .expect("failed to create note");
+
+    // Verify no enhancement initially
+    assert_eq!(note.content_enhanced(), None);
+
+    // Update enhancement after note creation (simulates LLM enhancement workflow)
+    let enhanced_time = OffsetDateTime::now_utc();
     service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create alias");
+        .update_note_enhancement(
+            note.id(),
+            "This is a quick thought about something important.",
+            "deepseek-r1:8b",
+            0.90,
+            enhanced_time,
+            None,
+        )
+        .expect("failed to update note enhancement");
 
-    // Expand using different case variants
-    let expanded_lower = service
-        .expand_search_term("ml")
-        .expect("expansion should succeed");
-    let expanded_upper = service
-        .expand_search_term("ML")
-        .expect("expansion should succeed");
-    let expanded_mixed = service
-        .expand_search_term("Ml")
-        .expect("expansion should succeed");
+    // Retrieve and verify enhancement was added
+    let updated = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
 
-    // All should produce same expansion (contain both ml and machine-learning)
-    assert!(
-        expanded_lower.contains(&"machine-learning".to_string()),
-        "lowercase should expand to canonical"
-    );
-    assert!(
-        expanded_upper.contains(&"machine-learning".to_string()),
-        "uppercase should expand to canonical"
+    assert_eq!(
+        updated.content_enhanced(),
+        Some("This is a quick thought about something important.")
     );
-    assert!(
-        expanded_mixed.contains(&"machine-learning".to_string()),
-        "mixed case should expand to canonical"
+    // Unix timestamp loses sub-second precision, so compare timestamps
+    assert_eq!(
+        updated.enhanced_at().unwrap().unix_timestamp(),
+        enhanced_time.unix_timestamp()
     );
+    assert_eq!(updated.enhancement_model(), Some("deepseek-r1:8b"));
+    assert_eq!(updated.enhancement_confidence(), Some(0.90));
+    // Original content should be unchanged
+    assert_eq!(updated.content(), "Quick thought");
 }
 
-// --- Edge Creation Tests (Task Group 2: Edge Creation in NoteService) ---
+// --- Note Content Update Tests ---
 
 #[test]
-fn get_tags_with_notes_returns_only_tags_with_associated_notes() {
+fn update_note_content_replaces_content_and_preserves_tags() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags with notes
+    let note = service
+        .create_note("Original contnet", Some(&["rust"]))
+        .expect("failed to create note");
+
+    // Backdate timestamps so the bump to updated_at is observable
     service
-        .create_note("Note about Rust", Some(&["rust"]))
-        .expect("failed to create note 1");
+        .database()
+        .connection()
+        .execute(
+            "UPDATE notes SET created_at = 1000, updated_at = 1000 WHERE id = ?1",
+            [note.id().get()],
+        )
+        .expect("failed to backdate note");
+
     service
-        .create_note("Note about Python", Some(&["python", "programming"]))
-        .expect("failed to create note 2");
-
-    // Create an orphan tag with no notes
-    let conn = service.database().connection();
-    conn.execute("INSERT INTO tags (name) VALUES ('orphan')", [])
-        .expect("failed to insert orphan tag");
-
-    // Get tags with notes
-    let tags_with_notes = service
-        .get_tags_with_notes()
-        .expect("failed to get tags with notes");
+        .update_note_content(note.id(), "Original content")
+        .expect("failed to update note content");
 
-    // Should return 3 tags (rust, python, programming) but NOT orphan
-    assert_eq!(
-        tags_with_notes.len(),
-        3,
-        "should return only tags with associated notes"
-    );
+    let updated = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
 
-    let tag_names: Vec<String> = tags_with_notes
-        .iter()
-        .map(|(_, name)| name.clone())
-        .collect();
-    assert!(tag_names.contains(&"rust".to_string()));
-    assert!(tag_names.contains(&"python".to_string()));
-    assert!(tag_names.contains(&"programming".to_string()));
-    assert!(!tag_names.contains(&"orphan".to_string()));
+    assert_eq!(updated.id(), note.id());
+    assert_eq!(updated.content(), "Original content");
+    assert_eq!(updated.created_at().unix_timestamp(), 1000);
+    assert!(updated.updated_at().unix_timestamp() > 1000);
+    assert_eq!(updated.tags().len(), 1);
+    assert_eq!(updated.tags()[0].name(), "rust");
 }
 
 #[test]
-fn get_tags_with_notes_returns_empty_when_no_tags_exist() {
+fn update_note_content_clears_enhancement() {
+    use time::OffsetDateTime;
+
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // No tags or notes
-    let tags = service
-        .get_tags_with_notes()
-        .expect("failed to get tags with notes");
+    let note = service
+        .create_note("Quick thought", None)
+        .expect("failed to create note");
+    service
+        .update_note_enhancement(
+            note.id(),
+            "An expanded quick thought.",
+            "deepseek-r1:8b",
+            0.9,
+            OffsetDateTime::now_utc(),
+            None,
+        )
+        .expect("failed to update note enhancement");
 
-    assert_eq!(tags.len(), 0, "should return empty vec when no tags exist");
+    service
+        .update_note_content(note.id(), "A different thought")
+        .expect("failed to update note content");
+
+    let updated = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+
+    assert_eq!(updated.content_enhanced(), None);
+    assert_eq!(updated.enhanced_at(), None);
+    assert_eq!(updated.enhancement_model(), None);
+    assert_eq!(updated.enhancement_confidence(), None);
 }
 
 #[test]
-fn create_edge_inserts_edge_with_correct_metadata() {
+fn update_note_content_refreshes_fts_index() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let transformer_tag = service
-        .get_or_create_tag("transformer")
-        .expect("failed to create transformer tag");
-    let neural_network_tag = service
-        .get_or_create_tag("neural-network")
-        .expect("failed to create neural-network tag");
+    let note = service
+        .create_note("Learning about kubernetes", None)
+        .expect("failed to create note");
 
-    // Create edge: transformer (narrower) -> neural-network (broader)
     service
-        .create_edge(
-            transformer_tag,
-            neural_network_tag,
-            0.85,
-            "generic",
-            Some("deepseek-r1:8b"),
-        )
-        .expect("failed to create edge");
+        .update_note_content(note.id(), "Learning about terraform")
+        .expect("failed to update note content");
 
-    // Verify edge was created with correct metadata
-    let conn = service.database().connection();
-    let row: (i64, i64, f64, String, String, i64, Option<i64>, Option<i64>) = conn
-        .query_row(
-            "SELECT source_tag_id, target_tag_id, confidence, hierarchy_type, source, verified, valid_from, valid_until
-             FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [transformer_tag.get(), neural_network_tag.get()],
-            |row| {
-                Ok((
-                    row.get(0)?,
-                    row.get(1)?,
-                    row.get(2)?,
-                    row.get(3)?,
-                    row.get(4)?,
-                    row.get(5)?,
-                    row.get(6)?,
-                    row.get(7)?,
-                ))
-            },
-        )
-        .expect("failed to query edge");
+    let old_results = service
+        .search_notes("kubernetes", None)
+        .expect("search should succeed");
+    assert!(old_results.is_empty(), "old content should not be indexed");
 
-    assert_eq!(row.0, transformer_tag.get(), "source_tag_id should match");
-    assert_eq!(
-        row.1,
-        neural_network_tag.get(),
-        "target_tag_id should match"
-    );
-    assert_eq!(row.2, 0.85, "confidence should match");
-    assert_eq!(row.3, "generic", "hierarchy_type should be generic");
-    assert_eq!(row.4, "llm", "source should be llm");
-    assert_eq!(row.5, 0, "verified should be 0");
-    assert_eq!(row.6, None, "valid_from should be NULL");
-    assert_eq!(row.7, None, "valid_until should be NULL");
+    let new_results = service
+        .search_notes("terraform", None)
+        .expect("search should succeed");
+    assert_eq!(new_results.len(), 1);
+    assert_eq!(new_results[0].note.id(), note.id());
 }
 
 #[test]
-fn create_edge_respects_insert_or_ignore_for_duplicates() {
+fn update_note_content_rejects_empty_content() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let transformer_tag = service
-        .get_or_create_tag("transformer")
-        .expect("failed to create transformer tag");
-    let neural_network_tag = service
-        .get_or_create_tag("neural-network")
-        .expect("failed to create neural-network tag");
-
-    // Create edge first time
-    service
-        .create_edge(
-            transformer_tag,
-            neural_network_tag,
-            0.85,
-            "generic",
-            Some("deepseek-r1:8b"),
-        )
-        .expect("first edge creation should succeed");
-
-    // Create same edge again (should not error due to INSERT OR IGNORE)
-    service
-        .create_edge(
-            transformer_tag,
-            neural_network_tag,
-            0.90,
-            "generic",
-            Some("deepseek-r1:8b"),
-        )
-        .expect("duplicate edge creation should succeed (idempotent)");
+    let note = service
+        .create_note("Keep me", None)
+        .expect("failed to create note");
 
-    // Verify only one edge exists
-    let conn = service.database().connection();
-    let count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [transformer_tag.get(), neural_network_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to count edges");
+    let result = service.update_note_content(note.id(), "   \n\t ");
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("cannot be empty"));
 
-    assert_eq!(count, 1, "should have only 1 edge (duplicate ignored)");
+    let unchanged = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(unchanged.content(), "Keep me");
+}
 
-    // Verify original confidence is preserved (first insert wins)
-    let confidence: f64 = conn
-        .query_row(
-            "SELECT confidence FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [transformer_tag.get(), neural_network_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query confidence");
+#[test]
+fn update_note_content_fails_for_non_existent_note() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    assert_eq!(confidence, 0.85, "original confidence should be preserved");
+    let result = service.update_note_content(NoteId::new(999), "New content");
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("does not exist"));
 }
 
+// --- Duplicate Lookup Tests ---
+
 #[test]
-fn create_edge_stores_correct_hierarchy_type() {
+fn find_note_by_content_matches_trimmed_content_and_prefers_oldest() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let attention_tag = service
-        .get_or_create_tag("attention")
-        .expect("failed to create attention tag");
-    let transformer_tag = service
-        .get_or_create_tag("transformer")
-        .expect("failed to create transformer tag");
-    let neural_network_tag = service
-        .get_or_create_tag("neural-network")
-        .expect("failed to create neural-network tag");
-
-    // Create partitive edge: attention (part) -> transformer (whole)
+    let first = service
+        .create_note("same snippet\n", None)
+        .expect("failed to create note");
     service
-        .create_edge(
-            attention_tag,
-            transformer_tag,
-            0.95,
-            "partitive",
-            Some("deepseek-r1:8b"),
-        )
-        .expect("failed to create partitive edge");
-
-    // Create generic edge: transformer (narrower) -> neural-network (broader)
+        .create_note("same snippet", None)
+        .expect("failed to create note");
     service
-        .create_edge(
-            transformer_tag,
-            neural_network_tag,
-            0.90,
-            "generic",
-            Some("deepseek-r1:8b"),
-        )
-        .expect("failed to create generic edge");
-
-    // Verify hierarchy types
-    let conn = service.database().connection();
+        .create_note("same snippet, longer", None)
+        .expect("failed to create note");
 
-    let partitive_type: String = conn
-        .query_row(
-            "SELECT hierarchy_type FROM edges WHERE source_tag_id = ?1",
-            [attention_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query partitive edge");
-    assert_eq!(partitive_type, "partitive");
+    let found = service
+        .find_note_by_content("\t same snippet  \r\n")
+        .expect("lookup should succeed")
+        .expect("duplicate should be found");
+    assert_eq!(found.id(), first.id());
 
-    let generic_type: String = conn
-        .query_row(
-            "SELECT hierarchy_type FROM edges WHERE source_tag_id = ?1",
-            [transformer_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query generic edge");
-    assert_eq!(generic_type, "generic");
+    assert!(
+        service
+            .find_note_by_content("Same Snippet")
+            .expect("lookup should succeed")
+            .is_none(),
+        "matching is exact apart from surrounding whitespace"
+    );
 }
 
 #[test]
-fn create_edges_batch_uses_transaction_for_atomicity() {
+fn find_note_by_hash_uses_content_hash_index() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
-    let tag3 = service
-        .get_or_create_tag("tag3")
-        .expect("failed to create tag3");
+    let plan: Vec<String> = service
+        .database()
+        .connection()
+        .prepare(
+            "EXPLAIN QUERY PLAN
+             SELECT id FROM notes WHERE content_hash = ?1 ORDER BY id LIMIT 1",
+        )
+        .unwrap()
+        .query_map(["x"], |row| row.get::<_, String>(3))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
 
-    // Prepare edges
-    let edges = vec![
-        (tag1, tag2, 0.9, "generic", Some("deepseek-r1:8b")),
-        (tag2, tag3, 0.85, "partitive", Some("deepseek-r1:8b")),
-    ];
+    assert!(
+        plan.iter()
+            .any(|step| step.contains("idx_notes_content_hash")),
+        "expected index lookup, got {plan:?}"
+    );
+}
 
-    // Create edges in batch
-    let count = service
-        .create_edges_batch(&edges)
-        .expect("failed to create edges batch");
+#[test]
+fn content_hash_follows_create_and_update() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    assert_eq!(count, 2, "should create 2 edges");
+    let note = service
+        .create_note("first draft", None)
+        .expect("failed to create note");
+    let found = service
+        .find_note_by_hash(&content_hash("first draft"))
+        .expect("lookup should succeed");
+    assert_eq!(found.map(|n| n.id()), Some(note.id()));
 
-    // Verify both edges exist
-    let conn = service.database().connection();
-    let total: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
+    service
+        .update_note_content(note.id(), "second draft")
+        .expect("failed to update note");
+    assert!(
+        service
+            .find_note_by_hash(&content_hash("first draft"))
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        service
+            .find_note_by_hash(&content_hash("second draft"))
+            .unwrap()
+            .map(|n| n.id()),
+        Some(note.id())
+    );
+}
 
-    assert_eq!(total, 2, "should have 2 edges in database");
+// --- Note Link Tests ---
+
+#[test]
+fn extract_links_anchors_on_innermost_brackets() {
+    assert_eq!(
+        NoteService::extract_links("see [[[12]]] and [[[[7]]]]"),
+        vec![NoteId::new(12), NoteId::new(7)]
+    );
+    assert_eq!(
+        NoteService::extract_links("[[ [[3]] ]] then [[[rust]]]"),
+        vec![NoteId::new(3)]
+    );
 }
 
 #[test]
-fn create_edges_batch_returns_zero_for_empty_input() {
+fn create_note_records_links_and_skips_dangling_ones() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let first = service
+        .create_note("Ownership basics", None)
+        .expect("failed to create note");
+    let second = service
+        .create_note("Borrowing rules", None)
+        .expect("failed to create note");
 
-    // Create edges batch with empty vec
-    let count = service
-        .create_edges_batch(&[])
-        .expect("failed to create empty batch");
+    let source = service
+        .create_note(
+            &format!("See [[{}]], [[{}]] and [[999]]", second.id(), first.id()),
+            None,
+        )
+        .expect("failed to create note");
 
-    assert_eq!(count, 0, "should return 0 for empty batch");
+    let links: Vec<NoteId> = service
+        .get_links(source.id())
+        .expect("failed to get links")
+        .iter()
+        .map(|n| n.id())
+        .collect();
+    assert_eq!(links, vec![first.id(), second.id()]);
+
+    let backlinks = service
+        .get_backlinks(first.id())
+        .expect("failed to get backlinks");
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0].id(), source.id());
 }
 
 #[test]
-fn expand_search_term_with_special_characters_normalized() {
-    // Tests expansion with special characters in alias names
+fn update_note_content_replaces_links() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let old_target = service
+        .create_note("Old target", None)
+        .expect("failed to create note");
+    let new_target = service
+        .create_note("New target", None)
+        .expect("failed to create note");
+    let source = service
+        .create_note(&format!("Links to [[{}]]", old_target.id()), None)
+        .expect("failed to create note");
 
-    // Create canonical tag
-    let cpp_tag = service
-        .get_or_create_tag("cpp")
-        .expect("failed to create tag");
-
-    // Create alias with special characters (will be normalized)
-    // "c++" normalizes to "c" due to TagNormalizer stripping non-alphanumeric
     service
-        .create_alias("cplusplus", cpp_tag, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    // Expand "cpp" should find the canonical tag and its aliases
-    let expanded = service
-        .expand_search_term("cpp")
-        .expect("expansion should succeed");
+        .update_note_content(
+            source.id(),
+            &format!(
+                "Now links to [[{}]] and itself [[{}]]",
+                new_target.id(),
+                source.id()
+            ),
+        )
+        .expect("failed to update note");
 
+    let links = service.get_links(source.id()).expect("failed to get links");
+    assert_eq!(links.len(), 1, "self-links are not recorded");
+    assert_eq!(links[0].id(), new_target.id());
     assert!(
-        expanded.contains(&"cpp".to_string()),
-        "should contain canonical tag"
+        service
+            .get_backlinks(old_target.id())
+            .expect("failed to get backlinks")
+            .is_empty()
     );
+}
+
+#[test]
+fn delete_note_removes_its_links_and_backlinks() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let target = service
+        .create_note("Target", None)
+        .expect("failed to create note");
+    let source = service
+        .create_note(&format!("Links to [[{}]]", target.id()), None)
+        .expect("failed to create note");
+
+    service
+        .delete_note(target.id())
+        .expect("failed to delete note");
+
     assert!(
-        expanded.contains(&"cplusplus".to_string()),
-        "should contain cplusplus alias"
+        service
+            .get_links(source.id())
+            .expect("failed to get links")
+            .is_empty()
     );
 }
 
-#[test]
-fn search_alias_in_enhanced_content() {
-    // Tests integration with enhanced content search via alias expansion
-    use time::OffsetDateTime;
+// --- Search Tests (Task Group 2: NoteService Search Method) ---
 
+#[test]
+fn search_notes_returns_matching_notes() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag and alias
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
+    // Create notes with different content
     service
-        .create_alias("ml", ml_tag, "user", 1.0, None)
-        .expect("failed to create alias");
+        .create_note("Learning Rust programming", Some(&["rust"]))
+        .expect("failed to create note 1");
+    service
+        .create_note("Python scripting tutorial", Some(&["python"]))
+        .expect("failed to create note 2");
+    service
+        .create_note("Rust and Python comparison", Some(&["rust", "python"]))
+        .expect("failed to create note 3");
 
-    // Create note with original content
-    let note = service
-        .create_note("Quick note", Some(&["machine-learning"]))
-        .expect("failed to create note");
+    // Search for "rust"
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
 
-    // Add enhanced content mentioning the canonical term
-    let now = OffsetDateTime::now_utc();
+    assert_eq!(results.len(), 2, "should find 2 notes containing rust");
+
+    // Verify results contain correct notes
+    let contents: Vec<&str> = results.iter().map(|r| r.note.content()).collect();
+    assert!(contents.contains(&"Learning Rust programming"));
+    assert!(contents.contains(&"Rust and Python comparison"));
+}
+
+#[test]
+fn search_notes_with_and_logic_requires_all_terms() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create notes with different combinations of terms
     service
-        .update_note_enhancement(
-            note.id(),
-            "This is about machine-learning algorithms and neural networks",
-            "deepseek-r1:8b",
-            0.9,
-            now,
-        )
-        .expect("failed to update enhancement");
+        .create_note("Rust programming language", None)
+        .expect("failed to create note 1");
+    service
+        .create_note("Python programming language", None)
+        .expect("failed to create note 2");
+    service
+        .create_note("Rust and Python both great", None)
+        .expect("failed to create note 3");
+    service
+        .create_note("Learning Rust", None)
+        .expect("failed to create note 4");
 
-    // Search using alias "ml" should find note via expansion to "machine-learning"
+    // Search for "rust programming" (both terms required)
     let results = service
-        .search_notes("ml", None)
+        .search_notes("rust programming", None)
         .expect("search should succeed");
 
+    // Only notes containing both "rust" AND "programming" should match
     assert_eq!(
         results.len(),
         1,
-        "alias search should find note via enhanced content expansion"
+        "should find 1 note with both rust and programming"
     );
-    assert_eq!(results[0].note.id(), note.id());
+    assert_eq!(results[0].note.content(), "Rust programming language");
 }
 
 #[test]
-fn expand_search_term_exact_confidence_boundary() {
-    // Tests LLM alias at exactly 0.8 confidence threshold
+fn search_notes_uses_porter_stemming() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag
-    let ml_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create tag");
-
-    // Create LLM alias with exactly 0.8 confidence (should be included)
-    service
-        .create_alias("ml", ml_tag, "llm", 0.8, Some("deepseek-r1:8b"))
-        .expect("failed to create alias");
+    // Create notes with different word forms that stem to the same root
+    // Using "program" which stems: programming -> program, programs -> program
+    let note1 = service
+        .create_note("I love programming in Rust", None)
+        .expect("failed to create note 1");
+    let note2 = service
+        .create_note("Many programs are written in C", None)
+        .expect("failed to create note 2");
 
-    // Expand from canonical - should include the alias at exactly 0.8
-    let expanded = service
-        .expand_search_term("machine-learning")
-        .expect("expansion should succeed");
+    // Search using base form "program" should match both variants
+    let results = service
+        .search_notes("program", None)
+        .expect("search should succeed");
 
-    assert!(
-        expanded.contains(&"ml".to_string()),
-        "LLM alias with confidence exactly 0.8 should be included"
+    assert_eq!(
+        results.len(),
+        2,
+        "porter stemming should match programming and programs"
     );
-}
 
-// --- Hierarchy Population Integration Tests (Task Group 4) ---
+    // Verify both notes are in results
+    let result_ids: Vec<_> = results.iter().map(|r| r.note.id()).collect();
+    assert!(result_ids.contains(&note1.id()));
+    assert!(result_ids.contains(&note2.id()));
+}
 
 #[test]
-fn hierarchy_population_full_end_to_end_workflow() {
-    // Integration test: Full workflow from tags to edges creation
-    use crate::hierarchy::HierarchySuggesterBuilder;
-    use crate::ollama::OllamaClientTrait;
-    use std::sync::Arc;
-
-    struct MockHierarchyClient;
-
-    impl OllamaClientTrait for MockHierarchyClient {
-        fn generate(
-            &self,
-            _model: &str,
-            _prompt: &str,
-        ) -> Result<String, crate::ollama::OllamaError> {
-            Ok(r#"[
-                {"source_tag": "transformer", "target_tag": "neural-network", "hierarchy_type": "generic", "confidence": 0.95},
-                {"source_tag": "attention", "target_tag": "transformer", "hierarchy_type": "partitive", "confidence": 0.85}
-            ]"#.to_string())
-        }
-    }
+fn search_notes_searches_content_enhanced_and_tags() {
+    use time::OffsetDateTime;
 
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with tags to populate tags table
-    service
-        .create_note("About transformers", Some(&["transformer"]))
+    // Create note with original content
+    let note1 = service
+        .create_note("Quick thought", Some(&["machine-learning"]))
         .expect("failed to create note 1");
+
+    // Add enhanced content
+    let now = OffsetDateTime::now_utc();
     service
-        .create_note("About neural networks", Some(&["neural-network"]))
-        .expect("failed to create note 2");
+        .update_note_enhancement(
+            note1.id(),
+            "This is a detailed explanation about artificial intelligence",
+            "deepseek-r1:8b",
+            0.9,
+            now,
+            None,
+        )
+        .expect("failed to update enhancement");
+
+    // Create another note with tag only
     service
-        .create_note("About attention mechanism", Some(&["attention"]))
-        .expect("failed to create note 3");
+        .create_note("Another note", Some(&["rust"]))
+        .expect("failed to create note 2");
 
-    // Step 1: Get tags with notes
-    let tags_with_notes = service
-        .get_tags_with_notes()
-        .expect("failed to get tags with notes");
-    assert_eq!(tags_with_notes.len(), 3, "should have 3 tags with notes");
+    // Search for term in enhanced content
+    let results = service
+        .search_notes("artificial", None)
+        .expect("search should succeed");
+    assert_eq!(
+        results.len(),
+        1,
+        "should find note by enhanced content term"
+    );
+    assert_eq!(results[0].note.id(), note1.id());
 
-    // Step 2: Call HierarchySuggester
-    let suggester = HierarchySuggesterBuilder::new()
-        .client(Arc::new(MockHierarchyClient))
-        .build();
-
-    let tag_names: Vec<String> = tags_with_notes
-        .iter()
-        .map(|(_, name)| name.clone())
-        .collect();
-
-    let suggestions = suggester
-        .suggest_relationships("test-model", tag_names)
-        .expect("failed to suggest relationships");
+    // Search for tag name
+    let tag_results = service
+        .search_notes("machine-learning", None)
+        .expect("search should succeed");
+    assert_eq!(tag_results.len(), 1, "should find note by tag name");
+    assert_eq!(tag_results[0].note.id(), note1.id());
+}
 
-    assert_eq!(suggestions.len(), 2, "should get 2 suggestions");
+#[test]
+fn update_note_enhancement_reindexes_without_rebuild() {
+    use time::OffsetDateTime;
 
-    // Step 3: Create edges from suggestions
-    let mut edges = Vec::new();
-    for suggestion in &suggestions {
-        let source_id = service
-            .get_or_create_tag(&suggestion.source_tag)
-            .expect("failed to resolve source tag");
-        let target_id = service
-            .get_or_create_tag(&suggestion.target_tag)
-            .expect("failed to resolve target tag");
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-        edges.push((
-            source_id,
-            target_id,
-            suggestion.confidence,
-            suggestion.hierarchy_type.as_str(),
-            Some("test-model"),
-        ));
-    }
+    let note = service
+        .create_note("Short jotting", None)
+        .expect("failed to create note");
 
-    let created_count = service
-        .create_edges_batch(&edges)
-        .expect("failed to create edges");
+    service
+        .update_note_enhancement(
+            note.id(),
+            "A jotting about zymurgy and fermentation",
+            "deepseek-r1:8b",
+            0.9,
+            OffsetDateTime::now_utc(),
+            None,
+        )
+        .expect("failed to update enhancement");
 
-    assert_eq!(created_count, 2, "should create 2 edges");
+    let results = service
+        .search_notes("zymurgy", None)
+        .expect("search should succeed");
+    assert_eq!(
+        results.len(),
+        1,
+        "enhancement term should be indexed at once"
+    );
+    assert_eq!(results[0].note.id(), note.id());
 
-    // Step 4: Verify edges in database
-    let conn = service.database().connection();
-    let edge_count: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
+    // Re-enhancing replaces the indexed enhancement rather than appending to it
+    service
+        .update_note_enhancement(
+            note.id(),
+            "A jotting about sourdough starters",
+            "deepseek-r1:8b",
+            0.9,
+            OffsetDateTime::now_utc(),
+            None,
+        )
+        .expect("failed to update enhancement");
 
-    assert_eq!(edge_count, 2, "should have 2 edges in database");
+    assert!(
+        service.search_notes("zymurgy", None).unwrap().is_empty(),
+        "stale enhancement terms should no longer match"
+    );
+    assert_eq!(service.search_notes("sourdough", None).unwrap().len(), 1);
 
-    // Verify edge direction: source = narrower, target = broader
-    let generic_edge: (String, String) = conn
+    let indexed_rows: i64 = service
+        .database()
+        .connection()
         .query_row(
-            "SELECT st.name, tt.name FROM edges e
-             JOIN tags st ON e.source_tag_id = st.id
-             JOIN tags tt ON e.target_tag_id = tt.id
-             WHERE e.hierarchy_type = 'generic'",
-            [],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+            "SELECT COUNT(*) FROM notes_fts WHERE note_id = ?1",
+            [note.id().get()],
+            |row| row.get(0),
         )
-        .expect("failed to query generic edge");
+        .expect("failed to count FTS rows");
+    assert_eq!(indexed_rows, 1, "each note should have exactly one FTS row");
+}
 
-    assert_eq!(
-        generic_edge,
-        ("transformer".to_string(), "neural-network".to_string()),
-        "transformer (narrower) should point to neural-network (broader)"
+#[test]
+fn search_notes_empty_query_returns_error() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Empty query should return error
+    let result = service.search_notes("", None);
+    assert!(result.is_err(), "empty query should return error");
+
+    let err_msg = result.unwrap_err().to_string();
+    assert!(
+        err_msg.contains("cannot be empty"),
+        "error should mention empty query: {}",
+        err_msg
+    );
+
+    // Whitespace-only query should also fail
+    let whitespace_result = service.search_notes("   ", None);
+    assert!(
+        whitespace_result.is_err(),
+        "whitespace-only query should return error"
     );
 }
 
 #[test]
-fn edge_direction_convention_narrower_to_broader() {
-    // Test that edges follow the direction convention: source=narrower, target=broader
+fn search_notes_limit_parameter_restricts_results() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let python_tag = service
-        .get_or_create_tag("python")
-        .expect("failed to create python tag");
-    let programming_language_tag = service
-        .get_or_create_tag("programming-language")
-        .expect("failed to create programming-language tag");
-
-    // Create edge: python (specific/narrower) -> programming-language (general/broader)
-    service
-        .create_edge(
-            python_tag,
-            programming_language_tag,
-            0.95,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
+    // Create multiple notes with the same term
+    for i in 1..=5 {
+        service
+            .create_note(&format!("Rust note {}", i), None)
+            .expect("failed to create note");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
 
-    // Verify edge direction in database
-    let conn = service.database().connection();
-    let (source_name, target_name): (String, String) = conn
-        .query_row(
-            "SELECT st.name, tt.name FROM edges e
-             JOIN tags st ON e.source_tag_id = st.id
-             JOIN tags tt ON e.target_tag_id = tt.id
-             WHERE st.id = ?1 AND tt.id = ?2",
-            [python_tag.get(), programming_language_tag.get()],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        )
-        .expect("failed to query edge");
+    // Search without limit
+    let all_results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
+    assert_eq!(all_results.len(), 5, "should find all 5 notes");
 
+    // Search with limit of 2
+    let limited_results = service
+        .search_notes("rust", Some(2))
+        .expect("search should succeed");
     assert_eq!(
-        source_name, "python",
-        "source should be narrower/specific concept"
-    );
-    assert_eq!(
-        target_name, "programming-language",
-        "target should be broader/general concept"
+        limited_results.len(),
+        2,
+        "should return exactly 2 notes when limited"
     );
+}
 
-    // Verify no reverse edge exists
-    let reverse_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [programming_language_tag.get(), python_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to count reverse edges");
+#[test]
+fn search_notes_count_ignores_limit_and_matches_alias_expansion() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    for i in 1..=20 {
+        service
+            .create_note(&format!("Rust note {i}"), None)
+            .expect("failed to create note");
+    }
+    // Five more notes only reachable through the alias "rustlang" -> "rust"
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    service
+        .create_alias("rustlang", rust_tag, "user", 1.0, None)
+        .expect("failed to create alias");
+    for i in 1..=5 {
+        service
+            .create_note(&format!("Rustlang snippet {i}"), None)
+            .expect("failed to create note");
+    }
+    service
+        .create_note("Python note", None)
+        .expect("failed to create note");
+
+    let page = service
+        .search_notes("rust", Some(10))
+        .expect("search should succeed");
+    assert_eq!(page.len(), 10);
 
+    let count = service
+        .search_notes_count("rust")
+        .expect("count should succeed");
+    assert_eq!(count, 25);
     assert_eq!(
-        reverse_count, 0,
-        "should not have reverse edge (broader -> narrower)"
+        count,
+        service
+            .search_notes("rust", None)
+            .expect("search should succeed")
+            .len()
     );
+
+    assert!(service.search_notes_count("   ").is_err());
 }
 
 #[test]
-fn hierarchy_suggest_idempotency_no_duplicate_edges() {
-    // Test that running suggest twice doesn't duplicate edges
-    use crate::hierarchy::HierarchySuggesterBuilder;
-    use crate::ollama::OllamaClientTrait;
-    use std::sync::Arc;
+fn search_notes_returns_full_note_objects_with_tags() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    struct MockIdempotentClient;
+    // Create note with tags
+    let note = service
+        .create_note("Rust tutorial", Some(&["rust", "programming"]))
+        .expect("failed to create note");
 
-    impl OllamaClientTrait for MockIdempotentClient {
-        fn generate(
-            &self,
-            _model: &str,
-            _prompt: &str,
-        ) -> Result<String, crate::ollama::OllamaError> {
-            Ok(r#"[
-                {"source_tag": "rust", "target_tag": "programming-language", "hierarchy_type": "generic", "confidence": 0.9}
-            ]"#.to_string())
-        }
-    }
+    // Search for it
+    let results = service
+        .search_notes("tutorial", None)
+        .expect("search should succeed");
+
+    assert_eq!(results.len(), 1, "should find 1 note");
+
+    // Verify full Note object is returned with tags
+    let found_note = &results[0].note;
+    assert_eq!(found_note.id(), note.id());
+    assert_eq!(found_note.content(), "Rust tutorial");
+    assert_eq!(found_note.tags().len(), 2, "note should include all tags");
+}
+
+// --- Phrase Search Tests ---
 
+#[test]
+fn search_notes_quoted_query_matches_adjacent_words_only() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create notes with tags
-    service
-        .create_note("Rust programming", Some(&["rust", "programming-language"]))
+    let swapped = service
+        .create_note("programming rust is fun", None)
+        .expect("failed to create note");
+    let adjacent = service
+        .create_note("I enjoy rust programming", None)
         .expect("failed to create note");
 
-    let suggester = HierarchySuggesterBuilder::new()
-        .client(Arc::new(MockIdempotentClient))
-        .build();
+    let results = service
+        .search_notes("\"rust programming\"", None)
+        .expect("phrase search should succeed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id(), adjacent.id());
 
-    // Run suggest first time
-    let tags_with_notes = service.get_tags_with_notes().expect("failed to get tags");
-    let tag_names: Vec<String> = tags_with_notes
-        .iter()
-        .map(|(_, name)| name.clone())
-        .collect();
-
-    let _suggestions1 = suggester
-        .suggest_relationships("test-model", tag_names.clone())
-        .expect("failed to suggest relationships");
-
-    let rust_id = service
-        .get_or_create_tag("rust")
-        .expect("failed to get rust");
-    let pl_id = service
-        .get_or_create_tag("programming-language")
-        .expect("failed to get pl");
-
-    let edges1 = vec![(rust_id, pl_id, 0.9, "generic", Some("test-model"))];
-    service
-        .create_edges_batch(&edges1)
-        .expect("failed to create edges first time");
-
-    // Verify one edge exists
-    let conn = service.database().connection();
-    let count_after_first: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
-    assert_eq!(count_after_first, 1, "should have 1 edge after first run");
+    let results = service
+        .search_notes("rust programming", None)
+        .expect("term search should succeed");
+    let ids: Vec<NoteId> = results.iter().map(|r| r.note.id()).collect();
+    assert!(ids.contains(&swapped.id()));
+    assert!(ids.contains(&adjacent.id()));
+}
 
-    // Run suggest second time (same suggestions)
-    let _suggestions2 = suggester
-        .suggest_relationships("test-model", tag_names)
-        .expect("failed to suggest relationships second time");
+#[test]
+fn build_fts_query_phrase_skips_alias_expansion_and_escapes_quotes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let edges2 = vec![(rust_id, pl_id, 0.9, "generic", Some("test-model"))];
+    let note = service
+        .create_note("Some note", Some(&["machine-learning"]))
+        .expect("failed to create note");
+    let tag_id = note.tags()[0].tag_id();
     service
-        .create_edges_batch(&edges2)
-        .expect("failed to create edges second time");
+        .create_alias("ml", tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Verify still only one edge (INSERT OR IGNORE prevents duplicates)
-    let count_after_second: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
     assert_eq!(
-        count_after_second, 1,
-        "should still have 1 edge after second run (no duplicates)"
+        service.build_fts_query("\"ml basics\"").unwrap(),
+        "\"ml basics\""
     );
-
-    // Verify original edge metadata is preserved
-    let (confidence, hierarchy_type): (f64, String) = conn
-        .query_row(
-            "SELECT confidence, hierarchy_type FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [rust_id.get(), pl_id.get()],
-            |row| Ok((row.get(0)?, row.get(1)?)),
-        )
-        .expect("failed to query edge metadata");
-
-    assert_eq!(confidence, 0.9, "original confidence should be preserved");
     assert_eq!(
-        hierarchy_type, "generic",
-        "original hierarchy type should be preserved"
+        service.build_fts_query("\"say \"hi\" now\"").unwrap(),
+        "\"say \"\"hi\"\" now\""
     );
 }
 
 #[test]
-fn mixed_hierarchy_types_in_single_batch() {
-    // Test creating both generic and partitive edges in a single batch
+fn search_notes_rejects_empty_quoted_phrase() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let attention_tag = service
-        .get_or_create_tag("attention")
-        .expect("failed to create attention");
-    let transformer_tag = service
-        .get_or_create_tag("transformer")
-        .expect("failed to create transformer");
-    let neural_network_tag = service
-        .get_or_create_tag("neural-network")
-        .expect("failed to create neural-network");
-
-    // Create batch with both hierarchy types
-    let edges = vec![
-        // Partitive: attention is part of transformer
-        (
-            attention_tag,
-            transformer_tag,
-            0.9,
-            "partitive",
-            Some("test-model"),
-        ),
-        // Generic: transformer is a type of neural-network
-        (
-            transformer_tag,
-            neural_network_tag,
-            0.95,
-            "generic",
-            Some("test-model"),
-        ),
-    ];
+    let result = service.search_notes("\"  \"", None);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("cannot be empty"));
+}
 
-    let created_count = service
-        .create_edges_batch(&edges)
-        .expect("failed to create mixed batch");
+// --- Boolean Operator Search Tests ---
 
-    assert_eq!(created_count, 2, "should create 2 edges");
+/// Creates notes about rust, python, a rust tutorial, and go.
+fn create_operator_notes(service: &NoteService) -> [NoteId; 4] {
+    create_notes(
+        service,
+        [
+            ("rust ownership rules", &[]),
+            ("python decorators", &[]),
+            ("rust tutorial for beginners", &[]),
+            ("go channels", &[]),
+        ],
+    )
+}
 
-    // Verify both hierarchy types stored correctly
-    let conn = service.database().connection();
+fn search_ids(service: &NoteService, query: &str) -> Vec<NoteId> {
+    let results = service
+        .search_notes(query, None)
+        .expect("search should succeed");
+    sorted_ids(results.iter().map(|r| r.note.id()))
+}
 
-    let partitive_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM edges WHERE hierarchy_type = 'partitive'",
-            [],
-            |row| row.get(0),
-        )
-        .expect("failed to count partitive edges");
-    assert_eq!(partitive_count, 1, "should have 1 partitive edge");
+#[test]
+fn parse_search_query_splits_groups_and_exclusions() {
+    let parsed = parse_search_query("rust OR python web -tutorial");
+    assert_eq!(parsed.groups, vec![vec!["rust", "python"], vec!["web"]]);
+    assert_eq!(parsed.excluded, vec!["tutorial"]);
 
-    let generic_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM edges WHERE hierarchy_type = 'generic'",
-            [],
-            |row| row.get(0),
-        )
-        .expect("failed to count generic edges");
-    assert_eq!(generic_count, 1, "should have 1 generic edge");
+    // Dangling or lowercase OR is an ordinary term
+    let parsed = parse_search_query("OR rust or");
+    assert_eq!(parsed.groups, vec![vec!["OR"], vec!["rust"], vec!["or"]]);
+    assert!(parsed.excluded.is_empty());
+}
 
-    // Verify edge metadata
-    let partitive_edge: (String, String, f64) = conn
-        .query_row(
-            "SELECT st.name, tt.name, e.confidence FROM edges e
-             JOIN tags st ON e.source_tag_id = st.id
-             JOIN tags tt ON e.target_tag_id = tt.id
-             WHERE e.hierarchy_type = 'partitive'",
-            [],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-        )
-        .expect("failed to query partitive edge");
+#[test]
+fn search_notes_or_matches_either_term() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let [rust, python, tutorial, _] = create_operator_notes(&service);
 
     assert_eq!(
-        partitive_edge,
-        ("attention".to_string(), "transformer".to_string(), 0.9),
-        "partitive edge should be attention -> transformer"
+        search_ids(&service, "rust OR python"),
+        vec![rust, python, tutorial]
     );
 }
 
 #[test]
-fn tag_name_resolution_before_edge_creation() {
-    // Test that tag names are properly resolved to IDs before edge creation
+fn search_notes_minus_excludes_term() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let [rust, _, _, _] = create_operator_notes(&service);
 
-    // Create only one of the two tags initially
-    let existing_tag = service
-        .get_or_create_tag("existing-tag")
-        .expect("failed to create existing tag");
+    assert_eq!(search_ids(&service, "rust -tutorial"), vec![rust]);
+}
 
-    // Attempt to create edge with non-existent target tag (should fail validation)
-    let non_existent_id = TagId::new(99999);
+#[test]
+fn search_notes_mixed_or_and_exclusion() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let [rust, python, _, _] = create_operator_notes(&service);
 
-    let result = service.create_edge(
-        existing_tag,
-        non_existent_id,
-        0.9,
-        "generic",
-        Some("test-model"),
+    assert_eq!(
+        search_ids(&service, "rust OR python -tutorial"),
+        vec![rust, python]
     );
+}
 
-    // Should fail because target tag doesn't exist
-    assert!(result.is_err(), "should fail when target tag doesn't exist");
-
-    // Now create both tags and verify edge creation works
-    let source_tag = service
-        .get_or_create_tag("python")
-        .expect("failed to create python");
-    let target_tag = service
-        .get_or_create_tag("programming-language")
-        .expect("failed to create programming-language");
+#[test]
+fn search_notes_exclusion_is_alias_expanded() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let result = service.create_edge(source_tag, target_tag, 0.95, "generic", Some("test-model"));
+    let kept = service
+        .create_note("notes on statistics", None)
+        .expect("failed to create note");
+    let tagged = service
+        .create_note("statistics for models", Some(&["machine-learning"]))
+        .expect("failed to create note");
+    let tag_id = tagged.tags()[0].tag_id();
+    service
+        .create_alias("ml", tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    assert!(
-        result.is_ok(),
-        "should succeed when both tags exist: {:?}",
-        result
-    );
+    assert_eq!(search_ids(&service, "statistics -ml"), vec![kept.id()]);
+}
 
-    // Verify edge was created
-    let conn = service.database().connection();
-    let edge_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
-            [source_tag.get(), target_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to count edges");
+#[test]
+fn search_notes_rejects_only_excluded_terms() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    assert_eq!(edge_count, 1, "should have created 1 edge");
+    let result = service.search_notes("-rust", None);
+    assert!(result.is_err());
 }
 
+// --- Search Snippet Tests ---
+
 #[test]
-fn create_edges_batch_rollback_on_failure() {
-    // Test that batch edge creation rolls back on failure (transaction atomicity)
+fn search_notes_returns_highlighted_snippet_for_body_match() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create valid tags
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
-
-    // Create batch with one invalid edge (non-existent tag)
-    let invalid_tag_id = TagId::new(99999);
-    let edges = vec![
-        (tag1, tag2, 0.9, "generic", Some("test-model")), // Valid
-        (tag1, invalid_tag_id, 0.85, "generic", Some("test-model")), // Invalid - should cause rollback
-    ];
+    service
+        .create_note("Borrowing in rust keeps memory safe", None)
+        .expect("failed to create note");
 
-    let result = service.create_edges_batch(&edges);
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
+    assert_eq!(results.len(), 1);
+    let snippet = results[0]
+        .snippet
+        .as_deref()
+        .expect("body match has snippet");
+    assert!(snippet.contains("[rust]"), "snippet was {snippet:?}");
+}
 
-    // Should fail due to invalid tag
-    assert!(
-        result.is_err(),
-        "batch should fail when one edge is invalid"
-    );
+#[test]
+fn search_notes_snippet_is_none_for_tag_only_match() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    // Verify NO edges were created (transaction rolled back)
-    let conn = service.database().connection();
-    let edge_count: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
+    service
+        .create_note("Ownership and lifetimes", Some(&["rust"]))
+        .expect("failed to create note");
 
-    assert_eq!(
-        edge_count, 0,
-        "no edges should exist after rollback (atomicity)"
-    );
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
+    assert_eq!(results.len(), 1);
+    assert!(results[0].snippet.is_none());
 }
 
-// --- Degree Centrality Edge Operations Tests (Task Group 2: Degree Centrality) ---
+// --- Additional Strategic Tests (Task Group 4: Test Review) ---
 
 #[test]
-fn create_edge_increments_degree_centrality_for_both_tags() {
+fn search_notes_orders_results_by_bm25_relevance() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let programming_tag = service
-        .get_or_create_tag("programming")
-        .expect("failed to create programming tag");
-
-    // Verify both tags start with degree_centrality = 0
-    let conn = service.database().connection();
-    let rust_centrality_before: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [rust_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query rust centrality");
-    let programming_centrality_before: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [programming_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query programming centrality");
+    // Create notes with different relevance for "rust"
+    // Note 1: "rust" appears once
+    let note1 = service
+        .create_note("learning rust programming", None)
+        .expect("failed to create note 1");
 
-    assert_eq!(
-        rust_centrality_before, 0,
-        "rust tag should start with centrality 0"
-    );
-    assert_eq!(
-        programming_centrality_before, 0,
-        "programming tag should start with centrality 0"
-    );
+    // Note 2: "rust" appears three times (highest relevance)
+    let note2 = service
+        .create_note("rust rust rust is amazing for systems", None)
+        .expect("failed to create note 2");
 
-    // Create edge: rust -> programming
-    service
-        .create_edge(
-            rust_tag,
-            programming_tag,
-            0.9,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
+    // Note 3: "rust" appears twice
+    let note3 = service
+        .create_note("rust and more rust content", None)
+        .expect("failed to create note 3");
 
-    // Verify both tags now have degree_centrality = 1
-    let rust_centrality_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [rust_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query rust centrality after");
-    let programming_centrality_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [programming_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query programming centrality after");
+    // Search for "rust"
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
+
+    assert_eq!(results.len(), 3, "should find all 3 notes");
 
+    // BM25 orders by ascending score (lower is better), so most relevant should be first
+    // Note 2 (3 occurrences) should be most relevant, then note3 (2), then note1 (1)
     assert_eq!(
-        rust_centrality_after, 1,
-        "rust tag should have centrality 1 after edge creation"
+        results[0].note.id(),
+        note2.id(),
+        "most relevant note (3 occurrences) should be first"
     );
     assert_eq!(
-        programming_centrality_after, 1,
-        "programming tag should have centrality 1 after edge creation"
+        results[1].note.id(),
+        note3.id(),
+        "second most relevant note (2 occurrences) should be second"
+    );
+    assert_eq!(
+        results[2].note.id(),
+        note1.id(),
+        "least relevant note (1 occurrence) should be last"
     );
 }
 
 #[test]
-fn create_edge_idempotent_does_not_double_increment_centrality() {
+fn search_result_has_normalized_relevance_score() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
-
-    // Create edge first time
+    // Create notes with different relevance
     service
-        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge first time");
+        .create_note("rust rust rust is amazing for systems", None)
+        .expect("failed to create note 1");
+    service
+        .create_note("learning rust programming", None)
+        .expect("failed to create note 2");
 
-    let conn = service.database().connection();
-    let tag1_centrality_first: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality");
-    let tag2_centrality_first: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality");
+    // Search for "rust"
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
 
-    assert_eq!(tag1_centrality_first, 1, "tag1 should have centrality 1");
-    assert_eq!(tag2_centrality_first, 1, "tag2 should have centrality 1");
+    assert_eq!(results.len(), 2, "should find 2 notes");
 
-    // Create same edge again (should be idempotent)
-    service
-        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge second time");
+    // Verify all SearchResults have note and score fields
+    for result in &results {
+        // Verify note is accessible
+        assert!(
+            !result.note.content().is_empty(),
+            "note content should be accessible"
+        );
 
-    // Verify centrality is still 1 (not incremented again)
-    let tag1_centrality_second: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality after second create");
-    let tag2_centrality_second: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality after second create");
+        // Verify relevance_score is in 0.0-1.0 range
+        assert!(
+            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
+            "relevance score {} should be between 0.0 and 1.0",
+            result.relevance_score
+        );
 
-    assert_eq!(
-        tag1_centrality_second, 1,
-        "tag1 centrality should still be 1 (no double increment)"
-    );
-    assert_eq!(
-        tag2_centrality_second, 1,
-        "tag2 centrality should still be 1 (no double increment)"
-    );
+        // Verify score is reasonably high (close to 1.0 for matching results)
+        assert!(
+            result.relevance_score > 0.5,
+            "relevance score {} should be > 0.5 for matching results",
+            result.relevance_score
+        );
+    }
 }
 
-// TODO: Task Group 2 tests - uncomment when delete_edge is implemented
-/*
 #[test]
-fn delete_edge_decrements_degree_centrality_for_both_tags() {
+fn rebuild_fts_restores_search_after_table_dropped() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
+    let note = service
+        .create_note("Borrow checker notes", Some(&["ownership"]))
+        .expect("failed to create note");
+    service
+        .update_note_enhancement(
+            note.id(),
+            "Notes about the Rust borrow checker and lifetimes.",
+            "test-model",
+            0.9,
+            time::OffsetDateTime::now_utc(),
+            None,
+        )
+        .expect("failed to enhance note");
+    service
+        .create_note("Unrelated note", None)
+        .expect("failed to create note");
 
-    // Create edge
     service
-        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
+        .database()
+        .connection()
+        .execute("DROP TABLE notes_fts", [])
+        .expect("failed to drop FTS table");
 
-    let conn = service.database().connection();
-    let tag1_centrality_before: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality before delete");
-    let tag2_centrality_before: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality before delete");
+    let err = service
+        .search_notes("borrow", None)
+        .expect_err("search should fail without the FTS table");
+    assert!(
+        err.to_string().contains("cons maintenance rebuild-fts"),
+        "error should suggest a rebuild: {err:#}"
+    );
 
-    assert_eq!(tag1_centrality_before, 1);
-    assert_eq!(tag2_centrality_before, 1);
+    assert_eq!(service.rebuild_fts().expect("rebuild should succeed"), 2);
 
-    // Delete edge
-    service
-        .delete_edge(tag1, tag2)
-        .expect("failed to delete edge");
+    // Content, enhanced content, and tag names are all searchable again
+    for term in ["borrow", "lifetimes", "ownership"] {
+        let results = service
+            .search_notes(term, None)
+            .expect("search should succeed after rebuild");
+        assert_eq!(results.len(), 1, "'{term}' should match after rebuild");
+        assert_eq!(results[0].note.id(), note.id());
+    }
+}
 
-    // Verify centrality decremented to 0
-    let tag1_centrality_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality after delete");
-    let tag2_centrality_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality after delete");
+#[test]
+fn search_stems_only_with_porter_tokenizer() {
+    let stemmed = NoteService::new(
+        Database::in_memory_with_tokenizer(FtsTokenizer::Porter)
+            .expect("failed to create in-memory database"),
+    );
+    let exact = NoteService::new(
+        Database::in_memory_with_tokenizer(FtsTokenizer::Unicode61)
+            .expect("failed to create in-memory database"),
+    );
+
+    for service in [&stemmed, &exact] {
+        service
+            .create_note("Notes on programming in Rust", None)
+            .expect("failed to create note");
+    }
 
+    let porter_results = stemmed
+        .search_notes("program", None)
+        .expect("search should succeed");
     assert_eq!(
-        tag1_centrality_after, 0,
-        "tag1 centrality should be decremented to 0"
+        porter_results.len(),
+        1,
+        "porter stems programming to program"
+    );
+
+    let unicode_results = exact
+        .search_notes("program", None)
+        .expect("search should succeed");
+    assert!(
+        unicode_results.is_empty(),
+        "unicode61 should not match 'programming' for 'program'"
     );
     assert_eq!(
-        tag2_centrality_after, 0,
-        "tag2 centrality should be decremented to 0"
+        exact
+            .search_notes("programming", None)
+            .expect("search should succeed")
+            .len(),
+        1
     );
 }
 
 #[test]
-fn delete_edge_on_non_existent_edge_is_no_op() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
+fn list_notes_works_independently_of_fts_functionality() {
+    // Fail-safe test: Verify that list_notes doesn't depend on FTS table
+    // This ensures note access via `cons list` works even if FTS has issues
+    let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags but no edge
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
-
-    // Delete non-existent edge (should be idempotent/no-op)
-    let result = service.delete_edge(tag1, tag2);
+    // Create notes with tags
+    let note1 = service
+        .create_note("First note", Some(&["rust"]))
+        .expect("failed to create note 1");
 
-    assert!(
-        result.is_ok(),
-        "delete of non-existent edge should succeed (no-op)"
-    );
+    let note2 = service
+        .create_note("Second note", Some(&["python"]))
+        .expect("failed to create note 2");
 
-    // Verify centrality remains 0
+    // Verify FTS table exists and is populated
     let conn = service.database().connection();
-    let tag1_centrality: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality");
-    let tag2_centrality: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality");
-
-    assert_eq!(tag1_centrality, 0, "tag1 centrality should remain 0");
-    assert_eq!(tag2_centrality, 0, "tag2 centrality should remain 0");
-}
-
-#[test]
-fn degree_centrality_never_goes_negative() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
+    let fts_count_before: i64 = conn
+        .query_row("SELECT COUNT(*) FROM notes_fts", [], |row| row.get(0))
+        .expect("FTS table should exist");
+    assert_eq!(fts_count_before, 2, "FTS should have 2 entries");
 
-    // Create tags
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
+    // Simulate FTS corruption by dropping the FTS table
+    // This tests the fail-safe requirement: "FTS issues don't block note access via cons list"
+    conn.execute("DROP TABLE notes_fts", [])
+        .expect("failed to drop FTS table");
 
-    // Verify both start at 0
-    let conn = service.database().connection();
-    let tag1_start: i32 = conn
+    // Verify FTS table is gone
+    let fts_exists: bool = conn
         .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='notes_fts')",
+            [],
             |row| row.get(0),
         )
-        .expect("failed to query tag1 centrality");
-    assert_eq!(tag1_start, 0);
-
-    // Try to delete edge that doesn't exist multiple times
-    service
-        .delete_edge(tag1, tag2)
-        .expect("first delete should succeed");
-    service
-        .delete_edge(tag1, tag2)
-        .expect("second delete should succeed");
+        .expect("failed to check FTS table existence");
+    assert!(!fts_exists, "FTS table should be dropped");
 
-    // Verify centrality is still 0 (not negative)
-    let tag1_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality after deletes");
-    let tag2_after: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality after deletes");
+    // list_notes should still work (doesn't depend on FTS)
+    let notes = service
+        .list_notes(ListNotesOptions::default())
+        .expect("list_notes should succeed even without FTS table");
 
     assert_eq!(
-        tag1_after, 0,
-        "tag1 centrality should never go negative (remain 0)"
-    );
-    assert_eq!(
-        tag2_after, 0,
-        "tag2 centrality should never go negative (remain 0)"
-    );
-}
-*/
-
-#[test]
-fn edge_and_centrality_update_atomic_transaction() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
-
-    // Create one valid tag and use invalid tag ID to force failure
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let invalid_tag = TagId::new(99999);
-
-    // Try to create edge with invalid tag (should fail)
-    let result = service.create_edge(tag1, invalid_tag, 0.9, "generic", Some("test-model"));
-
-    assert!(
-        result.is_err(),
-        "creating edge with invalid tag should fail"
+        notes.len(),
+        2,
+        "should list all notes despite FTS being gone"
     );
 
-    // Verify no edge was created
-    let conn = service.database().connection();
-    let edge_count: i64 = conn
-        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
-        .expect("failed to count edges");
-    assert_eq!(edge_count, 0, "no edge should be created on failure");
-
-    // Verify centrality was NOT incremented (transaction rolled back)
-    let tag1_centrality: i32 = conn
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality");
+    // Verify we got the correct notes
+    let note_ids: Vec<_> = notes.iter().map(|n| n.id()).collect();
+    assert!(note_ids.contains(&note1.id()), "should include first note");
+    assert!(note_ids.contains(&note2.id()), "should include second note");
 
-    assert_eq!(
-        tag1_centrality, 0,
-        "centrality should remain 0 on failed edge creation (transaction atomicity)"
-    );
+    // Verify notes have their tags
+    for note in &notes {
+        assert_eq!(
+            note.tags().len(),
+            1,
+            "notes should include their tags even without FTS"
+        );
+    }
 }
 
-// --- Graph Search Tests (Task Group 2) ---
+// --- Alias Expansion Tests (Task Group 1: Alias Expansion Logic) ---
 
 #[test]
-fn graph_search_returns_search_results_with_normalized_scores() {
+fn expand_search_term_no_aliases_returns_only_original_term() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags with hierarchy: rust -> programming
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let programming_tag = service
-        .get_or_create_tag("programming")
-        .expect("failed to create programming tag");
-
-    // Create edge: rust specializes programming
-    service
-        .create_edge(
-            rust_tag,
-            programming_tag,
-            0.9,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
-
-    // Create note tagged with rust
-    let note1 = service
-        .create_note("Learning Rust", Some(&["rust"]))
-        .expect("failed to create note");
-
-    // Create note tagged with programming
-    let _note2 = service
-        .create_note("General programming concepts", Some(&["programming"]))
-        .expect("failed to create note");
-
-    // Search for "rust" should find both notes via graph spreading
-    let results = service
-        .graph_search("rust", None)
-        .expect("graph search should succeed");
-
-    assert!(!results.is_empty(), "should find notes via graph search");
-
-    // Verify SearchResult structure
-    for result in &results {
-        assert!(
-            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
-            "relevance score should be normalized to 0.0-1.0 range"
-        );
-        assert!(result.note.id().get() > 0, "note should have valid ID");
-    }
-
-    // Note tagged with rust should score higher (seed tag)
-    let note1_result = results
-        .iter()
-        .find(|r| r.note.id() == note1.id())
-        .expect("note1 should be in results");
+    // No aliases or tags exist
+    let expanded = service
+        .expand_search_term("rust")
+        .expect("expansion should succeed");
 
+    assert_eq!(expanded.len(), 1, "should return only original term");
     assert!(
-        note1_result.relevance_score > 0.0,
-        "note with seed tag should have positive score"
+        expanded.contains(&"rust".to_string()),
+        "should contain original term"
     );
 }
 
 #[test]
-fn graph_search_parses_query_into_seed_tags_via_expand_search_term() {
+fn expand_search_term_alias_expands_to_canonical() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
     // Create canonical tag and alias
     let ml_tag = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create ml tag");
+        .expect("failed to create tag");
     service
         .create_alias("ml", ml_tag, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Create note with canonical tag
-    service
-        .create_note("ML tutorial", Some(&["machine-learning"]))
-        .expect("failed to create note");
-
-    // Search using alias should expand and find note
-    let results = service
-        .graph_search("ml", None)
-        .expect("graph search should succeed");
+    // Expand alias
+    let expanded = service
+        .expand_search_term("ml")
+        .expect("expansion should succeed");
 
     assert!(
-        !results.is_empty(),
-        "alias should expand to canonical tag and find notes"
+        expanded.contains(&"ml".to_string()),
+        "should contain original alias"
+    );
+    assert!(
+        expanded.contains(&"machine-learning".to_string()),
+        "should contain canonical tag name"
     );
 }
 
 #[test]
-fn graph_search_from_note_seeds_from_note_tags_with_confidence_weighting() {
+fn expand_search_term_canonical_expands_to_all_aliases() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let systems_tag = service
-        .get_or_create_tag("systems")
-        .expect("failed to create systems tag");
-
-    // Create edge: rust -> systems
+    // Create canonical tag and multiple aliases
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
     service
-        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
-
-    // Create seed note with rust tag
-    let seed_note = service
-        .create_note("Rust memory safety", Some(&["rust"]))
-        .expect("failed to create seed note");
-
-    // Create related note with systems tag
-    let related_note = service
-        .create_note("Systems programming", Some(&["systems"]))
-        .expect("failed to create related note");
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create ml alias");
+    service
+        .create_alias("ai-ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create ai-ml alias");
 
-    // Find notes related to seed note
-    let results = service
-        .graph_search_from_note(seed_note.id(), None)
-        .expect("graph search from note should succeed");
+    // Expand canonical tag name
+    let expanded = service
+        .expand_search_term("machine-learning")
+        .expect("expansion should succeed");
 
     assert!(
-        !results.is_empty(),
-        "should find related notes via tag graph"
+        expanded.contains(&"machine-learning".to_string()),
+        "should contain canonical tag"
+    );
+    assert!(
+        expanded.contains(&"ml".to_string()),
+        "should contain ml alias"
+    );
+    assert!(
+        expanded.contains(&"ai-ml".to_string()),
+        "should contain ai-ml alias"
     );
-
-    // Verify related note is in results
-    let found_related = results.iter().any(|r| r.note.id() == related_note.id());
-    assert!(found_related, "should find note with related tag");
 }
 
 #[test]
-fn graph_search_cold_start_returns_empty_when_no_matching_tags() {
+fn expand_search_term_user_aliases_always_included() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create note with tag
+    // Create canonical tag
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+
+    // Create user alias with low confidence (should still be included)
     service
-        .create_note("Some note", Some(&["unrelated"]))
-        .expect("failed to create note");
+        .create_alias("ml", ml_tag, "user", 0.5, None)
+        .expect("failed to create alias");
 
-    // Search for non-existent tag
-    let results = service
-        .graph_search("nonexistent", None)
-        .expect("graph search should succeed");
+    // Expand from canonical
+    let expanded = service
+        .expand_search_term("machine-learning")
+        .expect("expansion should succeed");
 
     assert!(
-        results.is_empty(),
-        "cold start should return empty results when no matching tags"
+        expanded.contains(&"ml".to_string()),
+        "user alias should be included regardless of confidence"
     );
 }
 
 #[test]
-fn graph_search_note_scoring_uses_sum_of_tag_activation_times_confidence() {
+fn expand_search_term_llm_alias_high_confidence_included() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags with hierarchy
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let programming_tag = service
-        .get_or_create_tag("programming")
-        .expect("failed to create programming tag");
-    let systems_tag = service
-        .get_or_create_tag("systems")
-        .expect("failed to create systems tag");
+    // Create canonical tag
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    // Create edges: rust -> programming, rust -> systems
-    service
-        .create_edge(
-            rust_tag,
-            programming_tag,
-            0.9,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
+    // Create LLM alias with confidence >= 0.8
     service
-        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
+        .create_alias("ml", ml_tag, "llm", 0.85, Some("deepseek-r1:8b"))
+        .expect("failed to create alias");
 
-    // Create hub note with multiple activated tags
-    let hub_note = service
-        .create_note(
-            "Rust programming systems",
-            Some(&["programming", "systems"]),
-        )
-        .expect("failed to create hub note");
+    // Expand from canonical
+    let expanded = service
+        .expand_search_term("machine-learning")
+        .expect("expansion should succeed");
 
-    // Create single-tag note
-    let single_note = service
-        .create_note("Programming basics", Some(&["programming"]))
-        .expect("failed to create single note");
+    assert!(
+        expanded.contains(&"ml".to_string()),
+        "LLM alias with confidence >= 0.8 should be included"
+    );
+}
 
-    // Search for rust - both programming and systems should activate
-    let results = service
-        .graph_search("rust", Some(10))
-        .expect("graph search should succeed");
+#[test]
+fn expand_search_term_llm_alias_low_confidence_excluded() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    assert!(!results.is_empty(), "should find notes");
+    // Create canonical tag
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    // Hub note with 2 activated tags should score higher than single-tag note
-    let hub_result = results
-        .iter()
-        .find(|r| r.note.id() == hub_note.id())
-        .expect("hub note should be in results");
+    // Create LLM alias with confidence < 0.8
+    service
+        .create_alias("ml", ml_tag, "llm", 0.75, Some("deepseek-r1:8b"))
+        .expect("failed to create alias");
 
-    let single_result = results
-        .iter()
-        .find(|r| r.note.id() == single_note.id())
-        .expect("single note should be in results");
+    // Expand from canonical
+    let expanded = service
+        .expand_search_term("machine-learning")
+        .expect("expansion should succeed");
 
     assert!(
-        hub_result.relevance_score >= single_result.relevance_score,
-        "hub note with multiple activated tags should score higher or equal"
+        expanded.contains(&"machine-learning".to_string()),
+        "should contain original canonical term"
+    );
+    assert!(
+        !expanded.contains(&"ml".to_string()),
+        "LLM alias with confidence < 0.8 should be excluded"
     );
 }
 
+// --- Search Integration with Alias Expansion Tests (Task Group 2: Search Integration) ---
+
 #[test]
-fn graph_search_from_note_excludes_seed_note_from_results() {
+fn search_for_alias_term_finds_notes_with_canonical_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags with hierarchy
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let programming_tag = service
-        .get_or_create_tag("programming")
-        .expect("failed to create programming tag");
-
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
     service
-        .create_edge(
-            rust_tag,
-            programming_tag,
-            0.9,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
-
-    // Create seed note
-    let seed_note = service
-        .create_note("Rust note", Some(&["rust"]))
-        .expect("failed to create seed note");
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Create related note
-    service
-        .create_note("Programming note", Some(&["programming"]))
-        .expect("failed to create related note");
+    // Create note with canonical tag
+    let note = service
+        .create_note("Deep learning tutorial", Some(&["machine-learning"]))
+        .expect("failed to create note");
 
-    // Find notes related to seed note
+    // Search using alias term "ml" - should find note tagged with "machine-learning"
     let results = service
-        .graph_search_from_note(seed_note.id(), None)
-        .expect("graph search from note should succeed");
+        .search_notes("ml", None)
+        .expect("search should succeed");
 
-    // Verify seed note is NOT in results
-    let found_seed = results.iter().any(|r| r.note.id() == seed_note.id());
-    assert!(!found_seed, "seed note should be excluded from results");
+    assert_eq!(
+        results.len(),
+        1,
+        "searching for alias 'ml' should find note with 'machine-learning' tag"
+    );
+    assert_eq!(results[0].note.id(), note.id());
 }
 
-// --- Task Group 4: Strategic Integration Tests ---
-
 #[test]
-fn graph_search_multi_hop_traversal_finds_distantly_related_notes() {
-    // Test end-to-end: query -> 3-hop graph traversal -> distantly related notes
-    // Validates: multi-hop spreading, decay application, distant semantic discovery
+fn search_for_canonical_term_finds_notes_with_alias_tags() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create linear chain: rust -> systems-programming -> operating-systems -> kernel
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let systems_tag = service
-        .get_or_create_tag("systems-programming")
-        .expect("failed to create systems tag");
-    let os_tag = service
-        .get_or_create_tag("operating-systems")
-        .expect("failed to create os tag");
-    let kernel_tag = service
-        .get_or_create_tag("kernel")
-        .expect("failed to create kernel tag");
-
-    // Create edges with high confidence (0.9) to ensure propagation
-    service
-        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
-    service
-        .create_edge(systems_tag, os_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
     service
-        .create_edge(os_tag, kernel_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Create notes at different distances from query term "rust"
-    let rust_note = service
-        .create_note("Rust ownership model", Some(&["rust"]))
-        .expect("failed to create note");
-
-    let systems_note = service
-        .create_note(
-            "Systems programming patterns",
-            Some(&["systems-programming"]),
-        )
-        .expect("failed to create note");
-
-    let kernel_note = service
-        .create_note("Kernel development", Some(&["kernel"]))
+    // Create a note that has "ml" in content (simulating a note where user mentioned the alias)
+    // Note: When user creates note with tag "ml", it gets resolved to "machine-learning"
+    // So we need to test via content search
+    let note = service
+        .create_note("Learning about ML algorithms", Some(&["machine-learning"]))
         .expect("failed to create note");
 
-    // Search for "rust" - should find notes 3 hops away (kernel)
+    // Search for canonical term should find notes
     let results = service
-        .graph_search("rust", Some(10))
-        .expect("graph search should succeed");
-
-    assert!(
-        !results.is_empty(),
-        "should find notes via multi-hop spreading"
-    );
-
-    // Verify all notes are found
-    let found_rust = results.iter().any(|r| r.note.id() == rust_note.id());
-    let found_systems = results.iter().any(|r| r.note.id() == systems_note.id());
-    let found_kernel = results.iter().any(|r| r.note.id() == kernel_note.id());
+        .search_notes("machine-learning", None)
+        .expect("search should succeed");
 
-    assert!(found_rust, "should find note with seed tag");
-    assert!(found_systems, "should find note 1 hop away");
-    assert!(
-        found_kernel,
-        "should find note 3 hops away (distant relation)"
+    assert_eq!(
+        results.len(),
+        1,
+        "searching for canonical term should find note"
     );
+    assert_eq!(results[0].note.id(), note.id());
 
-    // Verify score decay: rust > systems > kernel
-    let rust_score = results
-        .iter()
-        .find(|r| r.note.id() == rust_note.id())
-        .unwrap()
-        .relevance_score;
-    let systems_score = results
-        .iter()
-        .find(|r| r.note.id() == systems_note.id())
-        .unwrap()
-        .relevance_score;
-    let kernel_score = results
-        .iter()
-        .find(|r| r.note.id() == kernel_note.id())
-        .unwrap()
-        .relevance_score;
+    // Now test the reverse: search for "ml" finds note with content mentioning ML
+    let alias_results = service
+        .search_notes("ml", None)
+        .expect("search should succeed");
 
     assert!(
-        rust_score > systems_score,
-        "seed tag note should score higher than 1-hop note"
-    );
-    assert!(
-        systems_score > kernel_score,
-        "1-hop note should score higher than 3-hop note"
+        !alias_results.is_empty(),
+        "searching for 'ml' should find note"
     );
 }
 
 #[test]
-fn graph_search_hub_note_with_multiple_activated_tags_scores_highest() {
-    // Test hub note discovery: query activates multiple tags -> note with ALL tags scores highest
-    // Validates: SUM aggregation, tag convergence scoring
+fn multi_term_search_expands_each_term_independently() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tag hierarchy:
-    //      rust
-    //     /    \
-    //  memory  concurrency
-    let rust_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create rust tag");
-    let memory_tag = service
-        .get_or_create_tag("memory-safety")
-        .expect("failed to create memory tag");
-    let concurrency_tag = service
-        .get_or_create_tag("concurrency")
-        .expect("failed to create concurrency tag");
-
+    // Create canonical tags and aliases
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create ml tag");
     service
-        .create_edge(rust_tag, memory_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create ml alias");
+
+    let nlp_tag = service
+        .get_or_create_tag("natural-language-processing")
+        .expect("failed to create nlp tag");
     service
-        .create_edge(
-            rust_tag,
-            concurrency_tag,
-            0.9,
-            "generic",
-            Some("test-model"),
-        )
-        .expect("failed to create edge");
+        .create_alias("nlp", nlp_tag, "user", 1.0, None)
+        .expect("failed to create nlp alias");
 
-    // Create hub note with BOTH activated tags
-    let hub_note = service
+    // Create note with both canonical tags
+    let note = service
         .create_note(
-            "Rust safe concurrency",
-            Some(&["memory-safety", "concurrency"]),
+            "NLP and ML research",
+            Some(&["machine-learning", "natural-language-processing"]),
         )
-        .expect("failed to create hub note");
-
-    // Create single-tag notes
-    let memory_note = service
-        .create_note("Memory safety basics", Some(&["memory-safety"]))
-        .expect("failed to create memory note");
+        .expect("failed to create note");
 
-    let concurrency_note = service
-        .create_note("Concurrency patterns", Some(&["concurrency"]))
-        .expect("failed to create concurrency note");
+    // Create another note with only one tag
+    service
+        .create_note("Just ML stuff", Some(&["machine-learning"]))
+        .expect("failed to create note 2");
 
-    // Search for "rust" - activates both memory-safety and concurrency
+    // Search using both alias terms - should use AND logic between expanded groups
     let results = service
-        .graph_search("rust", Some(10))
-        .expect("graph search should succeed");
+        .search_notes("ml nlp", None)
+        .expect("search should succeed");
 
-    assert!(!results.is_empty(), "should find notes");
+    // Should find only the note with both tags
+    assert_eq!(
+        results.len(),
+        1,
+        "multi-term search should find note with both expanded terms"
+    );
+    assert_eq!(results[0].note.id(), note.id());
+}
 
-    // Find scores
-    let hub_score = results
-        .iter()
-        .find(|r| r.note.id() == hub_note.id())
-        .expect("hub note should be in results")
-        .relevance_score;
+#[test]
+fn multi_word_alias_handled_as_phrase_match() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let memory_score = results
-        .iter()
-        .find(|r| r.note.id() == memory_note.id())
-        .expect("memory note should be in results")
-        .relevance_score;
+    // Create canonical tag and aliases
+    // Use a canonical tag name that won't conflict with the alias normalization
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
 
-    let concurrency_score = results
-        .iter()
-        .find(|r| r.note.id() == concurrency_note.id())
-        .expect("concurrency note should be in results")
-        .relevance_score;
+    // Create the single-word alias first
+    service
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create ml alias");
 
-    // Hub note should score highest (SUM of both tag activations)
-    assert!(
-        hub_score > memory_score,
-        "hub note with 2 activated tags should score higher than single-tag note (got hub={}, memory={})",
-        hub_score,
-        memory_score
-    );
-    assert!(
-        hub_score > concurrency_score,
-        "hub note with 2 activated tags should score higher than single-tag note (got hub={}, concurrency={})",
-        hub_score,
-        concurrency_score
-    );
+    // Create note with content mentioning "machine learning" (multi-word)
+    let note = service
+        .create_note(
+            "Studies in machine learning are fascinating",
+            Some(&["machine-learning"]),
+        )
+        .expect("failed to create note");
+
+    // Search for single-word alias "ml" should find note via alias expansion
+    let results = service
+        .search_notes("ml", None)
+        .expect("search should succeed");
 
-    // Verify hub score is approximately the sum of individual activations
-    // (allowing for bidirectional traversal effects)
     assert!(
-        hub_score >= memory_score && hub_score >= concurrency_score,
-        "hub note should benefit from multiple activated tags"
+        !results.is_empty(),
+        "search should find note via alias expansion"
     );
+    assert_eq!(results[0].note.id(), note.id());
 }
 
 #[test]
-fn graph_search_environment_variable_override_affects_results() {
-    // Test CONS_DECAY override changes final results
-    // Validates: environment variable configuration, runtime config parsing
-    // NOTE: This test uses serial execution marker to avoid test interference
-
-    // Save original CONS_DECAY value to restore later
-    let original_decay = std::env::var("CONS_DECAY").ok();
-
+fn search_without_aliases_passes_through_unchanged() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create linear chain: tag1 -> tag2 -> tag3
-    let tag1 = service
-        .get_or_create_tag("tag1")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("tag2")
-        .expect("failed to create tag2");
-    let tag3 = service
-        .get_or_create_tag("tag3")
-        .expect("failed to create tag3");
-
-    service
-        .create_edge(tag1, tag2, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge");
-    service
-        .create_edge(tag2, tag3, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge");
-
-    // Create note 2 hops away
-    let distant_note = service
-        .create_note("Tag3 note", Some(&["tag3"]))
+    // Create notes without any aliases defined
+    let note = service
+        .create_note("Rust programming is great", Some(&["rust"]))
         .expect("failed to create note");
 
-    // Test 1: Default decay (0.7) - distant note should be found
-    unsafe { std::env::remove_var("CONS_DECAY") };
-    let results_default = service
-        .graph_search("tag1", Some(10))
-        .expect("graph search should succeed");
+    // Search for a term that has no aliases
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
 
-    let found_default = results_default
-        .iter()
-        .any(|r| r.note.id() == distant_note.id());
-
-    // Test 2: Low decay (0.2) - activation drops quickly, may not reach tag3
-    unsafe { std::env::set_var("CONS_DECAY", "0.2") };
-    let results_low_decay = service
-        .graph_search("tag1", Some(10))
-        .expect("graph search should succeed");
-
-    let found_low_decay = results_low_decay
-        .iter()
-        .any(|r| r.note.id() == distant_note.id());
-
-    // Test 3: No decay (1.0) - activation preserved, should definitely find tag3
-    unsafe { std::env::set_var("CONS_DECAY", "1.0") };
-    let results_high_decay = service
-        .graph_search("tag1", Some(10))
-        .expect("graph search should succeed");
-
-    let found_high_decay = results_high_decay
-        .iter()
-        .any(|r| r.note.id() == distant_note.id());
-
-    // Restore original environment variable state
-    unsafe {
-        match original_decay {
-            Some(val) => std::env::set_var("CONS_DECAY", val),
-            None => std::env::remove_var("CONS_DECAY"),
-        }
-    }
-
-    // Verify CONS_DECAY affects results
-    // With decay=1.0, we should definitely find the distant note
-    assert!(
-        found_high_decay,
-        "with CONS_DECAY=1.0, should find 2-hop distant note"
-    );
-
-    // With decay=0.2, activation decays rapidly (1.0 -> 0.2 -> 0.04)
-    // Threshold is 0.1, so 0.04 gets pruned
-    assert!(
-        !found_low_decay,
-        "with CONS_DECAY=0.2, should NOT find 2-hop note (0.04 < threshold 0.1)"
-    );
-
-    // Verify default behavior
-    assert!(
-        found_default,
-        "with default CONS_DECAY=0.7, should find 2-hop note"
+    assert_eq!(
+        results.len(),
+        1,
+        "search should work normally when no aliases exist"
     );
+    assert_eq!(results[0].note.id(), note.id());
 }
 
 #[test]
-fn graph_search_alias_expansion_then_spreading_activation() {
-    // Test integration: query uses alias -> resolves to canonical -> spreads through edges
-    // Validates: alias resolution + graph spreading pipeline
+fn search_with_alias_expansion_preserves_bm25_scoring() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
     // Create canonical tag and alias
     let ml_tag = service
         .get_or_create_tag("machine-learning")
-        .expect("failed to create ml tag");
+        .expect("failed to create tag");
     service
         .create_alias("ml", ml_tag, "user", 1.0, None)
         .expect("failed to create alias");
 
-    // Create related tag via edge
-    let nn_tag = service
-        .get_or_create_tag("neural-network")
-        .expect("failed to create nn tag");
+    // Create notes with different content
     service
-        .create_edge(ml_tag, nn_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create edge");
-
-    // Create notes
-    let ml_note = service
-        .create_note("ML tutorial", Some(&["machine-learning"]))
-        .expect("failed to create note");
+        .create_note(
+            "machine-learning machine-learning machine-learning",
+            Some(&["machine-learning"]),
+        )
+        .expect("failed to create highly relevant note");
 
-    let nn_note = service
-        .create_note("Neural network basics", Some(&["neural-network"]))
-        .expect("failed to create note");
+    service
+        .create_note("Just one mention of ml", Some(&["machine-learning"]))
+        .expect("failed to create less relevant note");
 
-    // Search using ALIAS "ml" (not canonical "machine-learning")
+    // Search using alias term
     let results = service
-        .graph_search("ml", Some(10))
-        .expect("graph search should succeed");
+        .search_notes("ml", None)
+        .expect("search should succeed");
 
-    assert!(!results.is_empty(), "alias query should find notes");
+    assert_eq!(results.len(), 2, "should find both notes");
 
-    // Verify both notes found: alias resolves -> spreads to related tag
-    let found_ml = results.iter().any(|r| r.note.id() == ml_note.id());
-    let found_nn = results.iter().any(|r| r.note.id() == nn_note.id());
+    // Verify SearchResult structure is preserved with valid scores
+    for result in &results {
+        assert!(
+            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
+            "relevance score {} should be normalized between 0.0 and 1.0",
+            result.relevance_score
+        );
+        assert!(
+            !result.note.content().is_empty(),
+            "note content should be accessible"
+        );
+    }
 
+    // Verify both notes were found (order may vary due to OR expansion behavior)
+    let contents: Vec<&str> = results.iter().map(|r| r.note.content()).collect();
     assert!(
-        found_ml,
-        "should find note with canonical tag via alias resolution"
+        contents.contains(&"machine-learning machine-learning machine-learning"),
+        "should find note with multiple machine-learning occurrences"
     );
     assert!(
-        found_nn,
-        "should find note with related tag via spreading activation after alias resolution"
+        contents.contains(&"Just one mention of ml"),
+        "should find note with ml mention"
     );
 }
 
-#[test]
-fn graph_search_edge_confidence_affects_activation_propagation() {
-    // Test edge confidence weighting: low-confidence edge (0.3) vs high-confidence (0.9)
-    // Validates: confidence multiplier in spreading formula
-    // Clear any environment variables that might affect this test
-    unsafe {
-        std::env::remove_var("CONS_DECAY");
-        std::env::remove_var("CONS_THRESHOLD");
-        std::env::remove_var("CONS_MAX_HOPS");
-    }
+// --- Additional Strategic Tests for Alias-Expanded FTS (Task Group 3: Gap Analysis) ---
 
+#[test]
+fn expand_search_term_case_insensitive_lookup() {
+    // Tests case sensitivity handling in expansion
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create parallel paths with different edge confidences
-    let seed_tag = service
-        .get_or_create_tag("seed")
-        .expect("failed to create seed tag");
-
-    let high_conf_tag = service
-        .get_or_create_tag("high-confidence-target")
-        .expect("failed to create high conf tag");
-
-    let low_conf_tag = service
-        .get_or_create_tag("low-confidence-target")
-        .expect("failed to create low conf tag");
-
-    // High confidence edge (0.9)
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
     service
-        .create_edge(seed_tag, high_conf_tag, 0.9, "generic", Some("test-model"))
-        .expect("failed to create high conf edge");
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Low confidence edge (0.3)
-    service
-        .create_edge(seed_tag, low_conf_tag, 0.3, "generic", Some("test-model"))
-        .expect("failed to create low conf edge");
+    // Expand using different case variants
+    let expanded_lower = service
+        .expand_search_term("ml")
+        .expect("expansion should succeed");
+    let expanded_upper = service
+        .expand_search_term("ML")
+        .expect("expansion should succeed");
+    let expanded_mixed = service
+        .expand_search_term("Ml")
+        .expect("expansion should succeed");
 
-    // Create notes with each target tag
-    let high_conf_note = service
-        .create_note("High confidence note", Some(&["high-confidence-target"]))
-        .expect("failed to create note");
+    // All should produce same expansion (contain both ml and machine-learning)
+    assert!(
+        expanded_lower.contains(&"machine-learning".to_string()),
+        "lowercase should expand to canonical"
+    );
+    assert!(
+        expanded_upper.contains(&"machine-learning".to_string()),
+        "uppercase should expand to canonical"
+    );
+    assert!(
+        expanded_mixed.contains(&"machine-learning".to_string()),
+        "mixed case should expand to canonical"
+    );
+}
 
-    let low_conf_note = service
-        .create_note("Low confidence note", Some(&["low-confidence-target"]))
-        .expect("failed to create note");
+// --- Edge Creation Tests (Task Group 2: Edge Creation in NoteService) ---
 
-    // Search for seed tag
-    let results = service
-        .graph_search("seed", Some(10))
-        .expect("graph search should succeed");
+#[test]
+fn get_tags_with_notes_returns_only_tags_with_associated_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    assert!(!results.is_empty(), "should find notes");
+    // Create tags with notes
+    service
+        .create_note("Note about Rust", Some(&["rust"]))
+        .expect("failed to create note 1");
+    service
+        .create_note("Note about Python", Some(&["python", "programming"]))
+        .expect("failed to create note 2");
 
-    // Get scores
-    let high_conf_score = results
-        .iter()
-        .find(|r| r.note.id() == high_conf_note.id())
-        .expect("high conf note should be in results")
-        .relevance_score;
+    // Create an orphan tag with no notes
+    let conn = service.database().connection();
+    conn.execute("INSERT INTO tags (name) VALUES ('orphan')", [])
+        .expect("failed to insert orphan tag");
 
-    let low_conf_score = results
-        .iter()
-        .find(|r| r.note.id() == low_conf_note.id())
-        .expect("low conf note should be in results")
-        .relevance_score;
+    // Get tags with notes
+    let tags_with_notes = service
+        .get_tags_with_notes()
+        .expect("failed to get tags with notes");
 
-    // High confidence edge should produce higher activation
-    // Formula: activation = 1.0 * confidence * decay * edge_type_multiplier
-    // High: 1.0 * 0.9 * 0.7 * 1.0 = 0.63
-    // Low:  1.0 * 0.3 * 0.7 * 1.0 = 0.21
-    assert!(
-        high_conf_score > low_conf_score,
-        "high confidence edge (0.9) should produce higher activation than low confidence (0.3), got high={}, low={}",
-        high_conf_score,
-        low_conf_score
+    // Should return 3 tags (rust, python, programming) but NOT orphan
+    assert_eq!(
+        tags_with_notes.len(),
+        3,
+        "should return only tags with associated notes"
     );
 
-    // Verify rough ratio (allowing for bidirectional and normalization effects)
-    let ratio = high_conf_score / low_conf_score;
-    assert!(
-        ratio > 1.5,
-        "activation ratio should reflect confidence difference (0.9/0.3 = 3.0), got ratio={}",
-        ratio
-    );
+    let tag_names: Vec<String> = tags_with_notes
+        .iter()
+        .map(|(_, name)| name.clone())
+        .collect();
+    assert!(tag_names.contains(&"rust".to_string()));
+    assert!(tag_names.contains(&"python".to_string()));
+    assert!(tag_names.contains(&"programming".to_string()));
+    assert!(!tag_names.contains(&"orphan".to_string()));
 }
 
 #[test]
-fn graph_search_mixed_edge_types_in_path_applies_both_multipliers() {
-    // Test path with both generic (1.0) and partitive (0.5) edges
-    // Validates: edge type multiplier composition
+fn get_tags_with_notes_returns_empty_when_no_tags_exist() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create chain: seed -> generic_tag -> partitive_tag
-    let seed_tag = service
+    // No tags or notes
+    let tags = service
+        .get_tags_with_notes()
+        .expect("failed to get tags with notes");
+
+    assert_eq!(tags.len(), 0, "should return empty vec when no tags exist");
+}
+
+#[test]
+fn list_tags_with_counts_includes_orphan_tags_with_zero_count() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    service
+        .create_note("First rust note", Some(&["rust", "programming"]))
+        .expect("failed to create note");
+    service
+        .create_note("Second rust note", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .get_or_create_tag("orphan")
+        .expect("failed to create orphan tag");
+
+    let tags = service
+        .list_tags_with_counts()
+        .expect("failed to list tags with counts");
+
+    let counts: Vec<(&str, usize)> = tags
+        .iter()
+        .map(|(_, name, count)| (name.as_str(), *count))
+        .collect();
+    assert_eq!(counts, vec![("orphan", 0), ("programming", 1), ("rust", 2)]);
+}
+
+#[test]
+fn recent_tags_orders_by_latest_assignment() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    service
+        .create_note("first", Some(&["rust", "old"]))
+        .expect("failed to create note");
+    let second = service
+        .create_note("second", Some(&["cli"]))
+        .expect("failed to create note");
+    service
+        .get_or_create_tag("orphan")
+        .expect("failed to create orphan tag");
+
+    // Pin timestamps: rust was re-applied most recently, old is the oldest
+    let conn = service.database().connection();
+    conn.execute("UPDATE note_tags SET created_at = 100", [])
+        .unwrap();
+    conn.execute(
+        "UPDATE note_tags SET created_at = 200 WHERE note_id = ?1",
+        [second.id().get()],
+    )
+    .unwrap();
+    service
+        .add_tags_to_note(second.id(), &["rust"], TagSource::User)
+        .expect("failed to add tag");
+    conn.execute(
+        "UPDATE note_tags SET created_at = 300 WHERE note_id = ?1 AND tag_id =
+             (SELECT id FROM tags WHERE name = 'rust')",
+        [second.id().get()],
+    )
+    .unwrap();
+
+    assert_eq!(service.recent_tags(10).unwrap(), vec!["rust", "cli", "old"]);
+    assert_eq!(service.recent_tags(2).unwrap(), vec!["rust", "cli"]);
+    assert!(service.recent_tags(0).unwrap().is_empty());
+}
+
+#[test]
+fn create_edge_inserts_edge_with_correct_metadata() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let transformer_tag = service
+        .get_or_create_tag("transformer")
+        .expect("failed to create transformer tag");
+    let neural_network_tag = service
+        .get_or_create_tag("neural-network")
+        .expect("failed to create neural-network tag");
+
+    // Create edge: transformer (narrower) -> neural-network (broader)
+    service
+        .create_edge(
+            transformer_tag,
+            neural_network_tag,
+            0.85,
+            "generic",
+            Some("deepseek-r1:8b"),
+        )
+        .expect("failed to create edge");
+
+    // Verify edge was created with correct metadata
+    let conn = service.database().connection();
+    let row: (i64, i64, f64, String, String, i64, Option<i64>, Option<i64>) = conn
+        .query_row(
+            "SELECT source_tag_id, target_tag_id, confidence, hierarchy_type, source, verified, valid_from, valid_until
+             FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [transformer_tag.get(), neural_network_tag.get()],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                ))
+            },
+        )
+        .expect("failed to query edge");
+
+    assert_eq!(row.0, transformer_tag.get(), "source_tag_id should match");
+    assert_eq!(
+        row.1,
+        neural_network_tag.get(),
+        "target_tag_id should match"
+    );
+    assert_eq!(row.2, 0.85, "confidence should match");
+    assert_eq!(row.3, "generic", "hierarchy_type should be generic");
+    assert_eq!(row.4, "llm", "source should be llm");
+    assert_eq!(row.5, 0, "verified should be 0");
+    assert_eq!(row.6, None, "valid_from should be NULL");
+    assert_eq!(row.7, None, "valid_until should be NULL");
+}
+
+#[test]
+fn create_edge_respects_insert_or_ignore_for_duplicates() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let transformer_tag = service
+        .get_or_create_tag("transformer")
+        .expect("failed to create transformer tag");
+    let neural_network_tag = service
+        .get_or_create_tag("neural-network")
+        .expect("failed to create neural-network tag");
+
+    // Create edge first time
+    service
+        .create_edge(
+            transformer_tag,
+            neural_network_tag,
+            0.85,
+            "generic",
+            Some("deepseek-r1:8b"),
+        )
+        .expect("first edge creation should succeed");
+
+    // Create same edge again (should not error due to INSERT OR IGNORE)
+    service
+        .create_edge(
+            transformer_tag,
+            neural_network_tag,
+            0.90,
+            "generic",
+            Some("deepseek-r1:8b"),
+        )
+        .expect("duplicate edge creation should succeed (idempotent)");
+
+    // Verify only one edge exists
+    let conn = service.database().connection();
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [transformer_tag.get(), neural_network_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to count edges");
+
+    assert_eq!(count, 1, "should have only 1 edge (duplicate ignored)");
+
+    // Verify original confidence is preserved (first insert wins)
+    let confidence: f64 = conn
+        .query_row(
+            "SELECT confidence FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [transformer_tag.get(), neural_network_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query confidence");
+
+    assert_eq!(confidence, 0.85, "original confidence should be preserved");
+}
+
+#[test]
+fn list_edges_returns_named_edges_ordered_by_broader_tag() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let rust = service.get_or_create_tag("rust").unwrap();
+    let tokio = service.get_or_create_tag("tokio").unwrap();
+    let programming = service.get_or_create_tag("programming").unwrap();
+    let async_tag = service.get_or_create_tag("async").unwrap();
+
+    service
+        .create_edge(tokio, rust, 0.8, "partitive", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(rust, programming, 0.9, "generic", None)
+        .expect("failed to create edge");
+    service
+        .create_edge(async_tag, programming, 0.7, "generic", None)
+        .expect("failed to create edge");
+
+    let edges = service.list_edges().expect("failed to list edges");
+    let pairs: Vec<(&str, &str)> = edges
+        .iter()
+        .map(|e| (e.source_name(), e.target_name()))
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![
+            ("async", "programming"),
+            ("rust", "programming"),
+            ("tokio", "rust"),
+        ]
+    );
+
+    let tokio_edge = &edges[2];
+    assert_eq!(tokio_edge.source_tag_id(), tokio);
+    assert_eq!(tokio_edge.target_tag_id(), rust);
+    assert_eq!(tokio_edge.hierarchy_type(), "partitive");
+    assert_eq!(tokio_edge.confidence(), 0.8);
+    assert_eq!(tokio_edge.source(), "llm");
+}
+
+#[test]
+fn create_edge_stores_correct_hierarchy_type() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let attention_tag = service
+        .get_or_create_tag("attention")
+        .expect("failed to create attention tag");
+    let transformer_tag = service
+        .get_or_create_tag("transformer")
+        .expect("failed to create transformer tag");
+    let neural_network_tag = service
+        .get_or_create_tag("neural-network")
+        .expect("failed to create neural-network tag");
+
+    // Create partitive edge: attention (part) -> transformer (whole)
+    service
+        .create_edge(
+            attention_tag,
+            transformer_tag,
+            0.95,
+            "partitive",
+            Some("deepseek-r1:8b"),
+        )
+        .expect("failed to create partitive edge");
+
+    // Create generic edge: transformer (narrower) -> neural-network (broader)
+    service
+        .create_edge(
+            transformer_tag,
+            neural_network_tag,
+            0.90,
+            "generic",
+            Some("deepseek-r1:8b"),
+        )
+        .expect("failed to create generic edge");
+
+    // Verify hierarchy types
+    let conn = service.database().connection();
+
+    let partitive_type: String = conn
+        .query_row(
+            "SELECT hierarchy_type FROM edges WHERE source_tag_id = ?1",
+            [attention_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query partitive edge");
+    assert_eq!(partitive_type, "partitive");
+
+    let generic_type: String = conn
+        .query_row(
+            "SELECT hierarchy_type FROM edges WHERE source_tag_id = ?1",
+            [transformer_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query generic edge");
+    assert_eq!(generic_type, "generic");
+}
+
+#[test]
+fn create_edges_batch_uses_transaction_for_atomicity() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+    let tag3 = service
+        .get_or_create_tag("tag3")
+        .expect("failed to create tag3");
+
+    // Prepare edges
+    let edges = vec![
+        (tag1, tag2, 0.9, "generic", Some("deepseek-r1:8b")),
+        (tag2, tag3, 0.85, "partitive", Some("deepseek-r1:8b")),
+    ];
+
+    // Create edges in batch
+    let count = service
+        .create_edges_batch(&edges)
+        .expect("failed to create edges batch");
+
+    assert_eq!(count, 2, "should create 2 edges");
+
+    // Verify both edges exist
+    let conn = service.database().connection();
+    let total: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+
+    assert_eq!(total, 2, "should have 2 edges in database");
+}
+
+#[test]
+fn create_edges_batch_returns_zero_for_empty_input() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create edges batch with empty vec
+    let count = service
+        .create_edges_batch(&[])
+        .expect("failed to create empty batch");
+
+    assert_eq!(count, 0, "should return 0 for empty batch");
+}
+
+#[test]
+fn expand_search_term_with_special_characters_normalized() {
+    // Tests expansion with special characters in alias names
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag
+    let cpp_tag = service
+        .get_or_create_tag("cpp")
+        .expect("failed to create tag");
+
+    // Create alias with special characters (will be normalized)
+    // "c++" normalizes to "c" due to TagNormalizer stripping non-alphanumeric
+    service
+        .create_alias("cplusplus", cpp_tag, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Expand "cpp" should find the canonical tag and its aliases
+    let expanded = service
+        .expand_search_term("cpp")
+        .expect("expansion should succeed");
+
+    assert!(
+        expanded.contains(&"cpp".to_string()),
+        "should contain canonical tag"
+    );
+    assert!(
+        expanded.contains(&"cplusplus".to_string()),
+        "should contain cplusplus alias"
+    );
+}
+
+#[test]
+fn search_alias_in_enhanced_content() {
+    // Tests integration with enhanced content search via alias expansion
+    use time::OffsetDateTime;
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+    service
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Create note with original content
+    let note = service
+        .create_note("Quick note", Some(&["machine-learning"]))
+        .expect("failed to create note");
+
+    // Add enhanced content mentioning the canonical term
+    let now = OffsetDateTime::now_utc();
+    service
+        .update_note_enhancement(
+            note.id(),
+            "This is about machine-learning algorithms and neural networks",
+            "deepseek-r1:8b",
+            0.9,
+            now,
+            None,
+        )
+        .expect("failed to update enhancement");
+
+    // Search using alias "ml" should find note via expansion to "machine-learning"
+    let results = service
+        .search_notes("ml", None)
+        .expect("search should succeed");
+
+    assert_eq!(
+        results.len(),
+        1,
+        "alias search should find note via enhanced content expansion"
+    );
+    assert_eq!(results[0].note.id(), note.id());
+}
+
+#[test]
+fn expand_search_term_exact_confidence_boundary() {
+    // Tests LLM alias at exactly 0.8 confidence threshold
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+
+    // Create LLM alias with exactly 0.8 confidence (should be included)
+    service
+        .create_alias("ml", ml_tag, "llm", 0.8, Some("deepseek-r1:8b"))
+        .expect("failed to create alias");
+
+    // Expand from canonical - should include the alias at exactly 0.8
+    let expanded = service
+        .expand_search_term("machine-learning")
+        .expect("expansion should succeed");
+
+    assert!(
+        expanded.contains(&"ml".to_string()),
+        "LLM alias with confidence exactly 0.8 should be included"
+    );
+}
+
+// --- Hierarchy Population Integration Tests (Task Group 4) ---
+
+#[test]
+fn hierarchy_population_full_end_to_end_workflow() {
+    // Integration test: Full workflow from tags to edges creation
+    use crate::hierarchy::HierarchySuggesterBuilder;
+    use crate::ollama::OllamaClientTrait;
+    use std::sync::Arc;
+
+    struct MockHierarchyClient;
+
+    impl OllamaClientTrait for MockHierarchyClient {
+        fn generate(
+            &self,
+            _model: &str,
+            _prompt: &str,
+        ) -> Result<String, crate::ollama::OllamaError> {
+            Ok(r#"[
+                {"source_tag": "transformer", "target_tag": "neural-network", "hierarchy_type": "generic", "confidence": 0.95},
+                {"source_tag": "attention", "target_tag": "transformer", "hierarchy_type": "partitive", "confidence": 0.85}
+            ]"#.to_string())
+        }
+    }
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create notes with tags to populate tags table
+    service
+        .create_note("About transformers", Some(&["transformer"]))
+        .expect("failed to create note 1");
+    service
+        .create_note("About neural networks", Some(&["neural-network"]))
+        .expect("failed to create note 2");
+    service
+        .create_note("About attention mechanism", Some(&["attention"]))
+        .expect("failed to create note 3");
+
+    // Step 1: Get tags with notes
+    let tags_with_notes = service
+        .get_tags_with_notes()
+        .expect("failed to get tags with notes");
+    assert_eq!(tags_with_notes.len(), 3, "should have 3 tags with notes");
+
+    // Step 2: Call HierarchySuggester
+    let suggester = HierarchySuggesterBuilder::new()
+        .client(Arc::new(MockHierarchyClient))
+        .build();
+
+    let tag_names: Vec<String> = tags_with_notes
+        .iter()
+        .map(|(_, name)| name.clone())
+        .collect();
+
+    let suggestions = suggester
+        .suggest_relationships("test-model", tag_names)
+        .expect("failed to suggest relationships");
+
+    assert_eq!(suggestions.len(), 2, "should get 2 suggestions");
+
+    // Step 3: Create edges from suggestions
+    let mut edges = Vec::new();
+    for suggestion in &suggestions {
+        let source_id = service
+            .get_or_create_tag(&suggestion.source_tag)
+            .expect("failed to resolve source tag");
+        let target_id = service
+            .get_or_create_tag(&suggestion.target_tag)
+            .expect("failed to resolve target tag");
+
+        edges.push((
+            source_id,
+            target_id,
+            suggestion.confidence,
+            suggestion.hierarchy_type.as_str(),
+            Some("test-model"),
+        ));
+    }
+
+    let created_count = service
+        .create_edges_batch(&edges)
+        .expect("failed to create edges");
+
+    assert_eq!(created_count, 2, "should create 2 edges");
+
+    // Step 4: Verify edges in database
+    let conn = service.database().connection();
+    let edge_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+
+    assert_eq!(edge_count, 2, "should have 2 edges in database");
+
+    // Verify edge direction: source = narrower, target = broader
+    let generic_edge: (String, String) = conn
+        .query_row(
+            "SELECT st.name, tt.name FROM edges e
+             JOIN tags st ON e.source_tag_id = st.id
+             JOIN tags tt ON e.target_tag_id = tt.id
+             WHERE e.hierarchy_type = 'generic'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .expect("failed to query generic edge");
+
+    assert_eq!(
+        generic_edge,
+        ("transformer".to_string(), "neural-network".to_string()),
+        "transformer (narrower) should point to neural-network (broader)"
+    );
+}
+
+#[test]
+fn edge_direction_convention_narrower_to_broader() {
+    // Test that edges follow the direction convention: source=narrower, target=broader
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let python_tag = service
+        .get_or_create_tag("python")
+        .expect("failed to create python tag");
+    let programming_language_tag = service
+        .get_or_create_tag("programming-language")
+        .expect("failed to create programming-language tag");
+
+    // Create edge: python (specific/narrower) -> programming-language (general/broader)
+    service
+        .create_edge(
+            python_tag,
+            programming_language_tag,
+            0.95,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+
+    // Verify edge direction in database
+    let conn = service.database().connection();
+    let (source_name, target_name): (String, String) = conn
+        .query_row(
+            "SELECT st.name, tt.name FROM edges e
+             JOIN tags st ON e.source_tag_id = st.id
+             JOIN tags tt ON e.target_tag_id = tt.id
+             WHERE st.id = ?1 AND tt.id = ?2",
+            [python_tag.get(), programming_language_tag.get()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .expect("failed to query edge");
+
+    assert_eq!(
+        source_name, "python",
+        "source should be narrower/specific concept"
+    );
+    assert_eq!(
+        target_name, "programming-language",
+        "target should be broader/general concept"
+    );
+
+    // Verify no reverse edge exists
+    let reverse_count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [programming_language_tag.get(), python_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to count reverse edges");
+
+    assert_eq!(
+        reverse_count, 0,
+        "should not have reverse edge (broader -> narrower)"
+    );
+}
+
+#[test]
+fn hierarchy_suggest_idempotency_no_duplicate_edges() {
+    // Test that running suggest twice doesn't duplicate edges
+    use crate::hierarchy::HierarchySuggesterBuilder;
+    use crate::ollama::OllamaClientTrait;
+    use std::sync::Arc;
+
+    struct MockIdempotentClient;
+
+    impl OllamaClientTrait for MockIdempotentClient {
+        fn generate(
+            &self,
+            _model: &str,
+            _prompt: &str,
+        ) -> Result<String, crate::ollama::OllamaError> {
+            Ok(r#"[
+                {"source_tag": "rust", "target_tag": "programming-language", "hierarchy_type": "generic", "confidence": 0.9}
+            ]"#.to_string())
+        }
+    }
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create notes with tags
+    service
+        .create_note("Rust programming", Some(&["rust", "programming-language"]))
+        .expect("failed to create note");
+
+    let suggester = HierarchySuggesterBuilder::new()
+        .client(Arc::new(MockIdempotentClient))
+        .build();
+
+    // Run suggest first time
+    let tags_with_notes = service.get_tags_with_notes().expect("failed to get tags");
+    let tag_names: Vec<String> = tags_with_notes
+        .iter()
+        .map(|(_, name)| name.clone())
+        .collect();
+
+    let _suggestions1 = suggester
+        .suggest_relationships("test-model", tag_names.clone())
+        .expect("failed to suggest relationships");
+
+    let rust_id = service
+        .get_or_create_tag("rust")
+        .expect("failed to get rust");
+    let pl_id = service
+        .get_or_create_tag("programming-language")
+        .expect("failed to get pl");
+
+    let edges1 = vec![(rust_id, pl_id, 0.9, "generic", Some("test-model"))];
+    service
+        .create_edges_batch(&edges1)
+        .expect("failed to create edges first time");
+
+    // Verify one edge exists
+    let conn = service.database().connection();
+    let count_after_first: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+    assert_eq!(count_after_first, 1, "should have 1 edge after first run");
+
+    // Run suggest second time (same suggestions)
+    let _suggestions2 = suggester
+        .suggest_relationships("test-model", tag_names)
+        .expect("failed to suggest relationships second time");
+
+    let edges2 = vec![(rust_id, pl_id, 0.9, "generic", Some("test-model"))];
+    service
+        .create_edges_batch(&edges2)
+        .expect("failed to create edges second time");
+
+    // Verify still only one edge (INSERT OR IGNORE prevents duplicates)
+    let count_after_second: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+    assert_eq!(
+        count_after_second, 1,
+        "should still have 1 edge after second run (no duplicates)"
+    );
+
+    // Verify original edge metadata is preserved
+    let (confidence, hierarchy_type): (f64, String) = conn
+        .query_row(
+            "SELECT confidence, hierarchy_type FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [rust_id.get(), pl_id.get()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .expect("failed to query edge metadata");
+
+    assert_eq!(confidence, 0.9, "original confidence should be preserved");
+    assert_eq!(
+        hierarchy_type, "generic",
+        "original hierarchy type should be preserved"
+    );
+}
+
+#[test]
+fn mixed_hierarchy_types_in_single_batch() {
+    // Test creating both generic and partitive edges in a single batch
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let attention_tag = service
+        .get_or_create_tag("attention")
+        .expect("failed to create attention");
+    let transformer_tag = service
+        .get_or_create_tag("transformer")
+        .expect("failed to create transformer");
+    let neural_network_tag = service
+        .get_or_create_tag("neural-network")
+        .expect("failed to create neural-network");
+
+    // Create batch with both hierarchy types
+    let edges = vec![
+        // Partitive: attention is part of transformer
+        (
+            attention_tag,
+            transformer_tag,
+            0.9,
+            "partitive",
+            Some("test-model"),
+        ),
+        // Generic: transformer is a type of neural-network
+        (
+            transformer_tag,
+            neural_network_tag,
+            0.95,
+            "generic",
+            Some("test-model"),
+        ),
+    ];
+
+    let created_count = service
+        .create_edges_batch(&edges)
+        .expect("failed to create mixed batch");
+
+    assert_eq!(created_count, 2, "should create 2 edges");
+
+    // Verify both hierarchy types stored correctly
+    let conn = service.database().connection();
+
+    let partitive_count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM edges WHERE hierarchy_type = 'partitive'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("failed to count partitive edges");
+    assert_eq!(partitive_count, 1, "should have 1 partitive edge");
+
+    let generic_count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM edges WHERE hierarchy_type = 'generic'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("failed to count generic edges");
+    assert_eq!(generic_count, 1, "should have 1 generic edge");
+
+    // Verify edge metadata
+    let partitive_edge: (String, String, f64) = conn
+        .query_row(
+            "SELECT st.name, tt.name, e.confidence FROM edges e
+             JOIN tags st ON e.source_tag_id = st.id
+             JOIN tags tt ON e.target_tag_id = tt.id
+             WHERE e.hierarchy_type = 'partitive'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .expect("failed to query partitive edge");
+
+    assert_eq!(
+        partitive_edge,
+        ("attention".to_string(), "transformer".to_string(), 0.9),
+        "partitive edge should be attention -> transformer"
+    );
+}
+
+#[test]
+fn tag_name_resolution_before_edge_creation() {
+    // Test that tag names are properly resolved to IDs before edge creation
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create only one of the two tags initially
+    let existing_tag = service
+        .get_or_create_tag("existing-tag")
+        .expect("failed to create existing tag");
+
+    // Attempt to create edge with non-existent target tag (should fail validation)
+    let non_existent_id = TagId::new(99999);
+
+    let result = service.create_edge(
+        existing_tag,
+        non_existent_id,
+        0.9,
+        "generic",
+        Some("test-model"),
+    );
+
+    // Should fail because target tag doesn't exist
+    assert!(result.is_err(), "should fail when target tag doesn't exist");
+
+    // Now create both tags and verify edge creation works
+    let source_tag = service
+        .get_or_create_tag("python")
+        .expect("failed to create python");
+    let target_tag = service
+        .get_or_create_tag("programming-language")
+        .expect("failed to create programming-language");
+
+    let result = service.create_edge(source_tag, target_tag, 0.95, "generic", Some("test-model"));
+
+    assert!(
+        result.is_ok(),
+        "should succeed when both tags exist: {:?}",
+        result
+    );
+
+    // Verify edge was created
+    let conn = service.database().connection();
+    let edge_count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM edges WHERE source_tag_id = ?1 AND target_tag_id = ?2",
+            [source_tag.get(), target_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to count edges");
+
+    assert_eq!(edge_count, 1, "should have created 1 edge");
+}
+
+#[test]
+fn create_edges_batch_rollback_on_failure() {
+    // Test that batch edge creation rolls back on failure (transaction atomicity)
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create valid tags
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+
+    // Create batch with one invalid edge (non-existent tag)
+    let invalid_tag_id = TagId::new(99999);
+    let edges = vec![
+        (tag1, tag2, 0.9, "generic", Some("test-model")), // Valid
+        (tag1, invalid_tag_id, 0.85, "generic", Some("test-model")), // Invalid - should cause rollback
+    ];
+
+    let result = service.create_edges_batch(&edges);
+
+    // Should fail due to invalid tag
+    assert!(
+        result.is_err(),
+        "batch should fail when one edge is invalid"
+    );
+
+    // Verify NO edges were created (transaction rolled back)
+    let conn = service.database().connection();
+    let edge_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+
+    assert_eq!(
+        edge_count, 0,
+        "no edges should exist after rollback (atomicity)"
+    );
+}
+
+// --- Hierarchy Cycle Detection Tests ---
+
+#[test]
+fn create_edge_rejects_direct_cycle() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let rust = service.get_or_create_tag("rust").unwrap();
+    let programming = service.get_or_create_tag("programming").unwrap();
+    service
+        .create_edge(rust, programming, 0.9, "generic", None)
+        .expect("failed to create edge");
+
+    let err = service
+        .create_edge(programming, rust, 0.9, "generic", None)
+        .expect_err("reverse edge should be rejected");
+    assert_eq!(
+        err.to_string(),
+        "Cannot add edge: it would create a cycle: programming -> rust -> programming"
+    );
+
+    let self_err = service
+        .create_edge(rust, rust, 0.9, "generic", None)
+        .expect_err("self edge should be rejected");
+    assert!(self_err.to_string().contains("rust -> rust"));
+}
+
+#[test]
+fn create_edge_rejects_transitive_cycle() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let a = service.get_or_create_tag("a").unwrap();
+    let b = service.get_or_create_tag("b").unwrap();
+    let c = service.get_or_create_tag("c").unwrap();
+    service.create_edge(a, b, 0.9, "generic", None).unwrap();
+    service.create_edge(b, c, 0.9, "generic", None).unwrap();
+
+    let err = service
+        .create_edge(c, a, 0.9, "generic", None)
+        .expect_err("closing the loop should be rejected");
+    assert_eq!(
+        err.to_string(),
+        "Cannot add edge: it would create a cycle: c -> a -> b -> c"
+    );
+
+    // Degree centrality is untouched by the rejected edge
+    let degree: i64 = service
+        .database()
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [a.get()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(degree, 1);
+
+    // A diamond (two paths to the same broader tag) is not a cycle
+    service
+        .create_edge(a, c, 0.9, "generic", None)
+        .expect("shortcut edge is allowed");
+}
+
+#[test]
+fn create_edge_ignores_temporal_edges_when_checking_for_cycles() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let rust = service.get_or_create_tag("rust").unwrap();
+    let programming = service.get_or_create_tag("programming").unwrap();
+    // An expired temporal edge is not part of the hierarchy
+    service
+        .database()
+        .connection()
+        .execute(
+            "INSERT INTO edges
+             (source_tag_id, target_tag_id, confidence, hierarchy_type, valid_from, valid_until)
+             VALUES (?1, ?2, 0.9, 'generic', 0, 1)",
+            [rust.get(), programming.get()],
+        )
+        .unwrap();
+
+    service
+        .create_edge(programming, rust, 0.9, "generic", None)
+        .expect("a temporal edge should not count as a cycle");
+}
+
+#[test]
+fn create_edges_batch_rolls_back_when_batch_forms_cycle() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let a = service.get_or_create_tag("a").unwrap();
+    let b = service.get_or_create_tag("b").unwrap();
+    let c = service.get_or_create_tag("c").unwrap();
+    let edges = vec![
+        (a, b, 0.9, "generic", Some("test-model")),
+        (b, c, 0.9, "generic", Some("test-model")),
+        (c, a, 0.9, "generic", Some("test-model")),
+    ];
+
+    let err = service
+        .create_edges_batch(&edges)
+        .expect_err("cyclic batch should fail");
+    assert!(err.to_string().contains("would create a cycle"));
+
+    let edge_count: i64 = service
+        .database()
+        .connection()
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(edge_count, 0, "whole batch should roll back");
+}
+
+// --- Degree Centrality Edge Operations Tests (Task Group 2: Degree Centrality) ---
+
+#[test]
+fn create_edge_increments_degree_centrality_for_both_tags() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let programming_tag = service
+        .get_or_create_tag("programming")
+        .expect("failed to create programming tag");
+
+    // Verify both tags start with degree_centrality = 0
+    let conn = service.database().connection();
+    let rust_centrality_before: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [rust_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query rust centrality");
+    let programming_centrality_before: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [programming_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query programming centrality");
+
+    assert_eq!(
+        rust_centrality_before, 0,
+        "rust tag should start with centrality 0"
+    );
+    assert_eq!(
+        programming_centrality_before, 0,
+        "programming tag should start with centrality 0"
+    );
+
+    // Create edge: rust -> programming
+    service
+        .create_edge(
+            rust_tag,
+            programming_tag,
+            0.9,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+
+    // Verify both tags now have degree_centrality = 1
+    let rust_centrality_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [rust_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query rust centrality after");
+    let programming_centrality_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [programming_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query programming centrality after");
+
+    assert_eq!(
+        rust_centrality_after, 1,
+        "rust tag should have centrality 1 after edge creation"
+    );
+    assert_eq!(
+        programming_centrality_after, 1,
+        "programming tag should have centrality 1 after edge creation"
+    );
+}
+
+#[test]
+fn create_edge_idempotent_does_not_double_increment_centrality() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+
+    // Create edge first time
+    service
+        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge first time");
+
+    let conn = service.database().connection();
+    let tag1_centrality_first: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality");
+    let tag2_centrality_first: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality");
+
+    assert_eq!(tag1_centrality_first, 1, "tag1 should have centrality 1");
+    assert_eq!(tag2_centrality_first, 1, "tag2 should have centrality 1");
+
+    // Create same edge again (should be idempotent)
+    service
+        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge second time");
+
+    // Verify centrality is still 1 (not incremented again)
+    let tag1_centrality_second: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality after second create");
+    let tag2_centrality_second: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality after second create");
+
+    assert_eq!(
+        tag1_centrality_second, 1,
+        "tag1 centrality should still be 1 (no double increment)"
+    );
+    assert_eq!(
+        tag2_centrality_second, 1,
+        "tag2 centrality should still be 1 (no double increment)"
+    );
+}
+
+#[test]
+fn delete_edge_decrements_degree_centrality_for_both_tags() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+
+    // Create edge
+    service
+        .create_edge(tag1, tag2, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    let conn = service.database().connection();
+    let tag1_centrality_before: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality before delete");
+    let tag2_centrality_before: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality before delete");
+
+    assert_eq!(tag1_centrality_before, 1);
+    assert_eq!(tag2_centrality_before, 1);
+
+    // Delete edge
+    service
+        .delete_edge(tag1, tag2)
+        .expect("failed to delete edge");
+
+    // Verify centrality decremented to 0
+    let tag1_centrality_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality after delete");
+    let tag2_centrality_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality after delete");
+
+    assert_eq!(
+        tag1_centrality_after, 0,
+        "tag1 centrality should be decremented to 0"
+    );
+    assert_eq!(
+        tag2_centrality_after, 0,
+        "tag2 centrality should be decremented to 0"
+    );
+}
+
+#[test]
+fn prune_edges_removes_weak_edges_and_reduces_centrality() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let hub = service
+        .get_or_create_tag("hub")
+        .expect("failed to create tag");
+    let strong = service
+        .get_or_create_tag("strong")
+        .expect("failed to create tag");
+    let weak1 = service
+        .get_or_create_tag("weak1")
+        .expect("failed to create tag");
+    let weak2 = service
+        .get_or_create_tag("weak2")
+        .expect("failed to create tag");
+
+    service
+        .create_edge(strong, hub, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(weak1, hub, 0.5, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(weak2, hub, 0.69, "partitive", Some("test-model"))
+        .expect("failed to create edge");
+
+    let removed = service.prune_edges(0.7).expect("prune should succeed");
+    assert_eq!(removed, 2);
+
+    let conn = service.database().connection();
+    let centrality = |tag: TagId| -> i32 {
+        conn.query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query centrality")
+    };
+    assert_eq!(centrality(hub), 1, "hub keeps only the strong edge");
+    assert_eq!(centrality(strong), 1);
+    assert_eq!(centrality(weak1), 0);
+    assert_eq!(centrality(weak2), 0);
+
+    let edges = service.list_edges().expect("failed to list edges");
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].source_tag_id(), strong);
+}
+
+#[test]
+fn prune_edges_keeps_edges_at_threshold_and_is_repeatable() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag");
+    service
+        .create_edge(tag1, tag2, 0.7, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    assert_eq!(service.prune_edges(0.7).expect("prune should succeed"), 0);
+    assert_eq!(service.list_edges().expect("failed to list edges").len(), 1);
+
+    assert_eq!(service.prune_edges(0.8).expect("prune should succeed"), 1);
+    assert_eq!(service.prune_edges(0.8).expect("prune should succeed"), 0);
+}
+
+#[test]
+fn verify_edge_marks_edge_and_errors_when_missing() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    let tokio = service
+        .get_or_create_tag("tokio")
+        .expect("failed to create tag");
+    let rust = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    service
+        .create_edge(tokio, rust, 0.8, "partitive", Some("test-model"))
+        .expect("failed to create edge");
+
+    assert!(!service.list_edges().expect("failed to list edges")[0].verified());
+
+    service
+        .verify_edge(tokio, rust)
+        .expect("verify should succeed");
+    service
+        .verify_edge(tokio, rust)
+        .expect("verifying twice should be a no-op");
+    assert!(service.list_edges().expect("failed to list edges")[0].verified());
+
+    let err = service
+        .verify_edge(rust, tokio)
+        .expect_err("reversed edge does not exist");
+    assert_eq!(err.to_string(), "Edge 'rust' -> 'tokio' does not exist");
+}
+
+#[test]
+fn delete_edge_on_non_existent_edge_is_no_op() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags but no edge
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+
+    // Delete non-existent edge (should be idempotent/no-op)
+    let result = service.delete_edge(tag1, tag2);
+
+    assert!(
+        result.is_ok(),
+        "delete of non-existent edge should succeed (no-op)"
+    );
+
+    // Verify centrality remains 0
+    let conn = service.database().connection();
+    let tag1_centrality: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality");
+    let tag2_centrality: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality");
+
+    assert_eq!(tag1_centrality, 0, "tag1 centrality should remain 0");
+    assert_eq!(tag2_centrality, 0, "tag2 centrality should remain 0");
+}
+
+#[test]
+fn degree_centrality_never_goes_negative() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+
+    // Verify both start at 0
+    let conn = service.database().connection();
+    let tag1_start: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality");
+    assert_eq!(tag1_start, 0);
+
+    // Try to delete edge that doesn't exist multiple times
+    service
+        .delete_edge(tag1, tag2)
+        .expect("first delete should succeed");
+    service
+        .delete_edge(tag1, tag2)
+        .expect("second delete should succeed");
+
+    // Verify centrality is still 0 (not negative)
+    let tag1_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality after deletes");
+    let tag2_after: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality after deletes");
+
+    assert_eq!(
+        tag1_after, 0,
+        "tag1 centrality should never go negative (remain 0)"
+    );
+    assert_eq!(
+        tag2_after, 0,
+        "tag2 centrality should never go negative (remain 0)"
+    );
+}
+
+#[test]
+fn edge_and_centrality_update_atomic_transaction() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create one valid tag and use invalid tag ID to force failure
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let invalid_tag = TagId::new(99999);
+
+    // Try to create edge with invalid tag (should fail)
+    let result = service.create_edge(tag1, invalid_tag, 0.9, "generic", Some("test-model"));
+
+    assert!(
+        result.is_err(),
+        "creating edge with invalid tag should fail"
+    );
+
+    // Verify no edge was created
+    let conn = service.database().connection();
+    let edge_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+    assert_eq!(edge_count, 0, "no edge should be created on failure");
+
+    // Verify centrality was NOT incremented (transaction rolled back)
+    let tag1_centrality: i32 = conn
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality");
+
+    assert_eq!(
+        tag1_centrality, 0,
+        "centrality should remain 0 on failed edge creation (transaction atomicity)"
+    );
+}
+
+// --- Graph Search Tests (Task Group 2) ---
+
+#[test]
+fn graph_search_returns_search_results_with_normalized_scores() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags with hierarchy: rust -> programming
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let programming_tag = service
+        .get_or_create_tag("programming")
+        .expect("failed to create programming tag");
+
+    // Create edge: rust specializes programming
+    service
+        .create_edge(
+            rust_tag,
+            programming_tag,
+            0.9,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+
+    // Create note tagged with rust
+    let note1 = service
+        .create_note("Learning Rust", Some(&["rust"]))
+        .expect("failed to create note");
+
+    // Create note tagged with programming
+    let _note2 = service
+        .create_note("General programming concepts", Some(&["programming"]))
+        .expect("failed to create note");
+
+    // Search for "rust" should find both notes via graph spreading
+    let results = service
+        .graph_search("rust", None)
+        .expect("graph search should succeed");
+
+    assert!(!results.is_empty(), "should find notes via graph search");
+
+    // Verify SearchResult structure
+    for result in &results {
+        assert!(
+            result.relevance_score >= 0.0 && result.relevance_score <= 1.0,
+            "relevance score should be normalized to 0.0-1.0 range"
+        );
+        assert!(result.note.id().get() > 0, "note should have valid ID");
+    }
+
+    // Note tagged with rust should score higher (seed tag)
+    let note1_result = results
+        .iter()
+        .find(|r| r.note.id() == note1.id())
+        .expect("note1 should be in results");
+
+    assert!(
+        note1_result.relevance_score > 0.0,
+        "note with seed tag should have positive score"
+    );
+}
+
+#[test]
+fn graph_search_parses_query_into_seed_tags_via_expand_search_term() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create ml tag");
+    service
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Create note with canonical tag
+    service
+        .create_note("ML tutorial", Some(&["machine-learning"]))
+        .expect("failed to create note");
+
+    // Search using alias should expand and find note
+    let results = service
+        .graph_search("ml", None)
+        .expect("graph search should succeed");
+
+    assert!(
+        !results.is_empty(),
+        "alias should expand to canonical tag and find notes"
+    );
+}
+
+#[test]
+fn graph_search_from_note_seeds_from_note_tags_with_confidence_weighting() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let systems_tag = service
+        .get_or_create_tag("systems")
+        .expect("failed to create systems tag");
+
+    // Create edge: rust -> systems
+    service
+        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    // Create seed note with rust tag
+    let seed_note = service
+        .create_note("Rust memory safety", Some(&["rust"]))
+        .expect("failed to create seed note");
+
+    // Create related note with systems tag
+    let related_note = service
+        .create_note("Systems programming", Some(&["systems"]))
+        .expect("failed to create related note");
+
+    // Find notes related to seed note
+    let results = service
+        .graph_search_from_note(seed_note.id(), None)
+        .expect("graph search from note should succeed");
+
+    assert!(
+        !results.is_empty(),
+        "should find related notes via tag graph"
+    );
+
+    // Verify related note is in results
+    let found_related = results.iter().any(|r| r.note.id() == related_note.id());
+    assert!(found_related, "should find note with related tag");
+}
+
+#[test]
+fn graph_search_cold_start_returns_empty_when_no_matching_tags() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create note with tag
+    service
+        .create_note("Some note", Some(&["unrelated"]))
+        .expect("failed to create note");
+
+    // Search for non-existent tag
+    let results = service
+        .graph_search("nonexistent", None)
+        .expect("graph search should succeed");
+
+    assert!(
+        results.is_empty(),
+        "cold start should return empty results when no matching tags"
+    );
+}
+
+#[test]
+fn graph_search_note_scoring_uses_sum_of_tag_activation_times_confidence() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags with hierarchy
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let programming_tag = service
+        .get_or_create_tag("programming")
+        .expect("failed to create programming tag");
+    let systems_tag = service
+        .get_or_create_tag("systems")
+        .expect("failed to create systems tag");
+
+    // Create edges: rust -> programming, rust -> systems
+    service
+        .create_edge(
+            rust_tag,
+            programming_tag,
+            0.9,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+    service
+        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    // Create hub note with multiple activated tags
+    let hub_note = service
+        .create_note(
+            "Rust programming systems",
+            Some(&["programming", "systems"]),
+        )
+        .expect("failed to create hub note");
+
+    // Create single-tag note
+    let single_note = service
+        .create_note("Programming basics", Some(&["programming"]))
+        .expect("failed to create single note");
+
+    // Search for rust - both programming and systems should activate
+    let results = service
+        .graph_search("rust", Some(10))
+        .expect("graph search should succeed");
+
+    assert!(!results.is_empty(), "should find notes");
+
+    // Hub note with 2 activated tags should score higher than single-tag note
+    let hub_result = results
+        .iter()
+        .find(|r| r.note.id() == hub_note.id())
+        .expect("hub note should be in results");
+
+    let single_result = results
+        .iter()
+        .find(|r| r.note.id() == single_note.id())
+        .expect("single note should be in results");
+
+    assert!(
+        hub_result.relevance_score >= single_result.relevance_score,
+        "hub note with multiple activated tags should score higher or equal"
+    );
+}
+
+#[test]
+fn graph_search_from_note_excludes_seed_note_from_results() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags with hierarchy
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let programming_tag = service
+        .get_or_create_tag("programming")
+        .expect("failed to create programming tag");
+
+    service
+        .create_edge(
+            rust_tag,
+            programming_tag,
+            0.9,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+
+    // Create seed note
+    let seed_note = service
+        .create_note("Rust note", Some(&["rust"]))
+        .expect("failed to create seed note");
+
+    // Create related note
+    service
+        .create_note("Programming note", Some(&["programming"]))
+        .expect("failed to create related note");
+
+    // Find notes related to seed note
+    let results = service
+        .graph_search_from_note(seed_note.id(), None)
+        .expect("graph search from note should succeed");
+
+    // Verify seed note is NOT in results
+    let found_seed = results.iter().any(|r| r.note.id() == seed_note.id());
+    assert!(!found_seed, "seed note should be excluded from results");
+}
+
+// --- Similar Notes Tests ---
+
+#[test]
+fn find_similar_notes_ranks_by_jaccard_overlap() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let seed = service
+        .create_note("seed", Some(&["rust", "async", "tokio"]))
+        .expect("failed to create seed");
+    // 3 shared of 3 total -> 1.0
+    let identical = service
+        .create_note("identical", Some(&["rust", "async", "tokio"]))
+        .expect("failed to create note");
+    // 2 shared of 4 total -> 0.5
+    let half = service
+        .create_note("half", Some(&["rust", "async", "cli"]))
+        .expect("failed to create note");
+    // 1 shared of 5 total -> 0.2
+    let fifth = service
+        .create_note("fifth", Some(&["rust", "web", "http"]))
+        .expect("failed to create note");
+    service
+        .create_note("unrelated", Some(&["cooking"]))
+        .expect("failed to create note");
+    service
+        .create_note("untagged", None)
+        .expect("failed to create note");
+
+    let similar = service
+        .find_similar_notes(seed.id(), 10)
+        .expect("find_similar_notes should succeed");
+
+    let ranked: Vec<(NoteId, f64)> = similar.iter().map(|(n, s)| (n.id(), *s)).collect();
+    assert_eq!(
+        ranked,
+        vec![(identical.id(), 1.0), (half.id(), 0.5), (fifth.id(), 0.2)],
+        "seed and notes without shared tags are excluded"
+    );
+}
+
+#[test]
+fn find_similar_notes_applies_limit_and_breaks_ties_by_id() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let seed = service
+        .create_note("seed", Some(&["rust", "async"]))
+        .expect("failed to create seed");
+    let first = service
+        .create_note("first", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .create_note("second", Some(&["async"]))
+        .expect("failed to create note");
+
+    let similar = service
+        .find_similar_notes(seed.id(), 1)
+        .expect("find_similar_notes should succeed");
+    assert_eq!(similar.len(), 1);
+    assert_eq!(similar[0].0.id(), first.id());
+    assert_eq!(similar[0].1, 0.5);
+}
+
+#[test]
+fn find_similar_notes_returns_empty_for_untagged_seed() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let seed = service
+        .create_note("seed", None)
+        .expect("failed to create seed");
+    service
+        .create_note("other", Some(&["rust"]))
+        .expect("failed to create note");
+
+    assert!(
+        service
+            .find_similar_notes(seed.id(), 10)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        service
+            .find_similar_notes(NoteId::new(999), 10)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+// --- Task Group 4: Strategic Integration Tests ---
+
+#[test]
+fn graph_search_multi_hop_traversal_finds_distantly_related_notes() {
+    // Test end-to-end: query -> 3-hop graph traversal -> distantly related notes
+    // Validates: multi-hop spreading, decay application, distant semantic discovery
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create linear chain: rust -> systems-programming -> operating-systems -> kernel
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let systems_tag = service
+        .get_or_create_tag("systems-programming")
+        .expect("failed to create systems tag");
+    let os_tag = service
+        .get_or_create_tag("operating-systems")
+        .expect("failed to create os tag");
+    let kernel_tag = service
+        .get_or_create_tag("kernel")
+        .expect("failed to create kernel tag");
+
+    // Create edges with high confidence (0.9) to ensure propagation
+    service
+        .create_edge(rust_tag, systems_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(systems_tag, os_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(os_tag, kernel_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    // Create notes at different distances from query term "rust"
+    let rust_note = service
+        .create_note("Rust ownership model", Some(&["rust"]))
+        .expect("failed to create note");
+
+    let systems_note = service
+        .create_note(
+            "Systems programming patterns",
+            Some(&["systems-programming"]),
+        )
+        .expect("failed to create note");
+
+    let kernel_note = service
+        .create_note("Kernel development", Some(&["kernel"]))
+        .expect("failed to create note");
+
+    // Search for "rust" - should find notes 3 hops away (kernel)
+    let results = service
+        .graph_search("rust", Some(10))
+        .expect("graph search should succeed");
+
+    assert!(
+        !results.is_empty(),
+        "should find notes via multi-hop spreading"
+    );
+
+    // Verify all notes are found
+    let found_rust = results.iter().any(|r| r.note.id() == rust_note.id());
+    let found_systems = results.iter().any(|r| r.note.id() == systems_note.id());
+    let found_kernel = results.iter().any(|r| r.note.id() == kernel_note.id());
+
+    assert!(found_rust, "should find note with seed tag");
+    assert!(found_systems, "should find note 1 hop away");
+    assert!(
+        found_kernel,
+        "should find note 3 hops away (distant relation)"
+    );
+
+    // Verify score decay: rust > systems > kernel
+    let rust_score = results
+        .iter()
+        .find(|r| r.note.id() == rust_note.id())
+        .unwrap()
+        .relevance_score;
+    let systems_score = results
+        .iter()
+        .find(|r| r.note.id() == systems_note.id())
+        .unwrap()
+        .relevance_score;
+    let kernel_score = results
+        .iter()
+        .find(|r| r.note.id() == kernel_note.id())
+        .unwrap()
+        .relevance_score;
+
+    assert!(
+        rust_score > systems_score,
+        "seed tag note should score higher than 1-hop note"
+    );
+    assert!(
+        systems_score > kernel_score,
+        "1-hop note should score higher than 3-hop note"
+    );
+}
+
+#[test]
+fn graph_search_hub_note_with_multiple_activated_tags_scores_highest() {
+    // Test hub note discovery: query activates multiple tags -> note with ALL tags scores highest
+    // Validates: SUM aggregation, tag convergence scoring
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tag hierarchy:
+    //      rust
+    //     /    \
+    //  memory  concurrency
+    let rust_tag = service
+        .get_or_create_tag("rust")
+        .expect("failed to create rust tag");
+    let memory_tag = service
+        .get_or_create_tag("memory-safety")
+        .expect("failed to create memory tag");
+    let concurrency_tag = service
+        .get_or_create_tag("concurrency")
+        .expect("failed to create concurrency tag");
+
+    service
+        .create_edge(rust_tag, memory_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(
+            rust_tag,
+            concurrency_tag,
+            0.9,
+            "generic",
+            Some("test-model"),
+        )
+        .expect("failed to create edge");
+
+    // Create hub note with BOTH activated tags
+    let hub_note = service
+        .create_note(
+            "Rust safe concurrency",
+            Some(&["memory-safety", "concurrency"]),
+        )
+        .expect("failed to create hub note");
+
+    // Create single-tag notes
+    let memory_note = service
+        .create_note("Memory safety basics", Some(&["memory-safety"]))
+        .expect("failed to create memory note");
+
+    let concurrency_note = service
+        .create_note("Concurrency patterns", Some(&["concurrency"]))
+        .expect("failed to create concurrency note");
+
+    // Search for "rust" - activates both memory-safety and concurrency
+    let results = service
+        .graph_search("rust", Some(10))
+        .expect("graph search should succeed");
+
+    assert!(!results.is_empty(), "should find notes");
+
+    // Find scores
+    let hub_score = results
+        .iter()
+        .find(|r| r.note.id() == hub_note.id())
+        .expect("hub note should be in results")
+        .relevance_score;
+
+    let memory_score = results
+        .iter()
+        .find(|r| r.note.id() == memory_note.id())
+        .expect("memory note should be in results")
+        .relevance_score;
+
+    let concurrency_score = results
+        .iter()
+        .find(|r| r.note.id() == concurrency_note.id())
+        .expect("concurrency note should be in results")
+        .relevance_score;
+
+    // Hub note should score highest (SUM of both tag activations)
+    assert!(
+        hub_score > memory_score,
+        "hub note with 2 activated tags should score higher than single-tag note (got hub={}, memory={})",
+        hub_score,
+        memory_score
+    );
+    assert!(
+        hub_score > concurrency_score,
+        "hub note with 2 activated tags should score higher than single-tag note (got hub={}, concurrency={})",
+        hub_score,
+        concurrency_score
+    );
+
+    // Verify hub score is approximately the sum of individual activations
+    // (allowing for bidirectional traversal effects)
+    assert!(
+        hub_score >= memory_score && hub_score >= concurrency_score,
+        "hub note should benefit from multiple activated tags"
+    );
+}
+
+#[test]
+fn graph_search_environment_variable_override_affects_results() {
+    // Test CONS_DECAY override changes final results
+    // Validates: environment variable configuration, runtime config parsing
+    // NOTE: This test uses serial execution marker to avoid test interference
+
+    // Save original CONS_DECAY value to restore later
+    let original_decay = std::env::var("CONS_DECAY").ok();
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create linear chain: tag1 -> tag2 -> tag3
+    let tag1 = service
+        .get_or_create_tag("tag1")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("tag2")
+        .expect("failed to create tag2");
+    let tag3 = service
+        .get_or_create_tag("tag3")
+        .expect("failed to create tag3");
+
+    service
+        .create_edge(tag1, tag2, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(tag2, tag3, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    // Create note 2 hops away
+    let distant_note = service
+        .create_note("Tag3 note", Some(&["tag3"]))
+        .expect("failed to create note");
+
+    // Test 1: Default decay (0.7) - distant note should be found
+    unsafe { std::env::remove_var("CONS_DECAY") };
+    let service = service.with_config(Config::load());
+    let results_default = service
+        .graph_search("tag1", Some(10))
+        .expect("graph search should succeed");
+
+    let found_default = results_default
+        .iter()
+        .any(|r| r.note.id() == distant_note.id());
+
+    // Test 2: Low decay (0.2) - activation drops quickly, may not reach tag3
+    unsafe { std::env::set_var("CONS_DECAY", "0.2") };
+    let service = service.with_config(Config::load());
+    let results_low_decay = service
+        .graph_search("tag1", Some(10))
+        .expect("graph search should succeed");
+
+    let found_low_decay = results_low_decay
+        .iter()
+        .any(|r| r.note.id() == distant_note.id());
+
+    // Test 3: No decay (1.0) - activation preserved, should definitely find tag3
+    unsafe { std::env::set_var("CONS_DECAY", "1.0") };
+    let service = service.with_config(Config::load());
+    let results_high_decay = service
+        .graph_search("tag1", Some(10))
+        .expect("graph search should succeed");
+
+    let found_high_decay = results_high_decay
+        .iter()
+        .any(|r| r.note.id() == distant_note.id());
+
+    // Restore original environment variable state
+    unsafe {
+        match original_decay {
+            Some(val) => std::env::set_var("CONS_DECAY", val),
+            None => std::env::remove_var("CONS_DECAY"),
+        }
+    }
+
+    // Verify CONS_DECAY affects results
+    // With decay=1.0, we should definitely find the distant note
+    assert!(
+        found_high_decay,
+        "with CONS_DECAY=1.0, should find 2-hop distant note"
+    );
+
+    // With decay=0.2, activation decays rapidly (1.0 -> 0.2 -> 0.04)
+    // Threshold is 0.1, so 0.04 gets pruned
+    assert!(
+        !found_low_decay,
+        "with CONS_DECAY=0.2, should NOT find 2-hop note (0.04 < threshold 0.1)"
+    );
+
+    // Verify default behavior
+    assert!(
+        found_default,
+        "with default CONS_DECAY=0.7, should find 2-hop note"
+    );
+}
+
+#[test]
+fn graph_search_partitive_weight_override_equalizes_edge_types() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // seed <- kind (generic), seed <- part (partitive); both one hop from the seed
+    let seed = service.get_or_create_tag("engine").unwrap();
+    let kind = service.get_or_create_tag("diesel-engine").unwrap();
+    let part = service.get_or_create_tag("piston").unwrap();
+    service
+        .create_edge(kind, seed, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge");
+    service
+        .create_edge(part, seed, 1.0, "partitive", Some("test-model"))
+        .expect("failed to create edge");
+
+    let seed_note = service
+        .create_note("Engine note", Some(&["engine"]))
+        .expect("failed to create note");
+    let generic_note = service
+        .create_note("Diesel note", Some(&["diesel-engine"]))
+        .expect("failed to create note");
+    let partitive_note = service
+        .create_note("Piston note", Some(&["piston"]))
+        .expect("failed to create note");
+
+    let scores = |service: &NoteService| {
+        let results = service
+            .graph_search_from_note(seed_note.id(), None)
+            .expect("graph search should succeed");
+        let score = |id: NoteId| {
+            results
+                .iter()
+                .find(|r| r.note.id() == id)
+                .map(|r| r.relevance_score)
+                .expect("note should be found")
+        };
+        (score(generic_note.id()), score(partitive_note.id()))
+    };
+
+    let service = service.with_config(Config::default());
+    let (generic_default, partitive_default) = scores(&service);
+
+    let service = service.with_config(Config::from_lookup(|name| {
+        (name == "CONS_PARTITIVE_WEIGHT").then(|| "1.0".into())
+    }));
+    let (generic_equal, partitive_equal) = scores(&service);
+
+    assert!(
+        partitive_default < generic_default,
+        "by default partitive edges propagate less ({partitive_default} vs {generic_default})"
+    );
+    assert!(
+        (partitive_equal - generic_equal).abs() < 1e-9,
+        "with CONS_PARTITIVE_WEIGHT=1.0 both edge types score alike ({partitive_equal} vs {generic_equal})"
+    );
+    assert_eq!(service.config().partitive_weight, 1.0);
+}
+
+#[test]
+#[serial_test::serial]
+fn graph_search_uses_config_captured_by_service() {
+    let mut config = Config::default();
+    config.decay = 1.0;
+    config.max_hops = 1;
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db).with_config(config);
+
+    let tags: Vec<TagId> = ["tag1", "tag2", "tag3"]
+        .iter()
+        .map(|name| service.get_or_create_tag(name).unwrap())
+        .collect();
+    for pair in tags.windows(2) {
+        service
+            .create_edge(pair[0], pair[1], 1.0, "generic", Some("test-model"))
+            .expect("failed to create edge");
+    }
+    let distant_note = service
+        .create_note("Tag3 note", Some(&["tag3"]))
+        .expect("failed to create note");
+
+    // Changing the environment after construction must not affect search
+    let original = std::env::var("CONS_MAX_HOPS").ok();
+    unsafe { std::env::set_var("CONS_MAX_HOPS", "6") };
+    let results = service
+        .graph_search("tag1", Some(10))
+        .expect("graph search should succeed");
+    unsafe {
+        match original {
+            Some(val) => std::env::set_var("CONS_MAX_HOPS", val),
+            None => std::env::remove_var("CONS_MAX_HOPS"),
+        }
+    }
+
+    assert_eq!(service.config().max_hops, 1);
+    assert!(
+        !results.iter().any(|r| r.note.id() == distant_note.id()),
+        "max_hops=1 from the service config should stop before the 2-hop note"
+    );
+}
+
+#[test]
+fn graph_search_max_hops_override_limits_traversal_depth() {
+    // CONS_MAX_HOPS=1 stops activation after one hop, even with no decay
+    let config_with = |max_hops: &'static str| {
+        Config::from_lookup(move |name| match name {
+            "CONS_DECAY" => Some("1.0".into()),
+            "CONS_MAX_HOPS" => Some(max_hops.into()),
+            _ => None,
+        })
+    };
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create linear chain: tag1 -> tag2 -> tag3 -> tag4
+    let tags: Vec<TagId> = ["tag1", "tag2", "tag3", "tag4"]
+        .iter()
+        .map(|name| {
+            service
+                .get_or_create_tag(name)
+                .expect("failed to create tag")
+        })
+        .collect();
+    for pair in tags.windows(2) {
+        service
+            .create_edge(pair[0], pair[1], 1.0, "generic", Some("test-model"))
+            .expect("failed to create edge");
+    }
+
+    let near_note = service
+        .create_note("Tag2 note", Some(&["tag2"]))
+        .expect("failed to create note");
+    let distant_note = service
+        .create_note("Tag4 note", Some(&["tag4"]))
+        .expect("failed to create note");
+
+    let service = service.with_config(config_with("1"));
+    let results = service
+        .graph_search("tag1", Some(10))
+        .expect("graph search should succeed");
+    let config_max_hops = DualSearchConfig::from(&config_with("1")).max_hops;
+    let clamped_max_hops = DualSearchConfig::from(&config_with("99")).max_hops;
+
+    assert!(
+        results.iter().any(|r| r.note.id() == near_note.id()),
+        "with CONS_MAX_HOPS=1, should find 1-hop note"
+    );
+    assert!(
+        !results.iter().any(|r| r.note.id() == distant_note.id()),
+        "with CONS_MAX_HOPS=1, should NOT find 3-hop note even with CONS_DECAY=1.0"
+    );
+    assert_eq!(config_max_hops, 1);
+    assert_eq!(clamped_max_hops, 6, "CONS_MAX_HOPS should be clamped to 6");
+}
+
+#[test]
+fn graph_search_alias_expansion_then_spreading_activation() {
+    // Test integration: query uses alias -> resolves to canonical -> spreads through edges
+    // Validates: alias resolution + graph spreading pipeline
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag and alias
+    let ml_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create ml tag");
+    service
+        .create_alias("ml", ml_tag, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Create related tag via edge
+    let nn_tag = service
+        .get_or_create_tag("neural-network")
+        .expect("failed to create nn tag");
+    service
+        .create_edge(ml_tag, nn_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create edge");
+
+    // Create notes
+    let ml_note = service
+        .create_note("ML tutorial", Some(&["machine-learning"]))
+        .expect("failed to create note");
+
+    let nn_note = service
+        .create_note("Neural network basics", Some(&["neural-network"]))
+        .expect("failed to create note");
+
+    // Search using ALIAS "ml" (not canonical "machine-learning")
+    let results = service
+        .graph_search("ml", Some(10))
+        .expect("graph search should succeed");
+
+    assert!(!results.is_empty(), "alias query should find notes");
+
+    // Verify both notes found: alias resolves -> spreads to related tag
+    let found_ml = results.iter().any(|r| r.note.id() == ml_note.id());
+    let found_nn = results.iter().any(|r| r.note.id() == nn_note.id());
+
+    assert!(
+        found_ml,
+        "should find note with canonical tag via alias resolution"
+    );
+    assert!(
+        found_nn,
+        "should find note with related tag via spreading activation after alias resolution"
+    );
+}
+
+#[test]
+fn graph_search_edge_confidence_affects_activation_propagation() {
+    // Test edge confidence weighting: low-confidence edge (0.3) vs high-confidence (0.9)
+    // Validates: confidence multiplier in spreading formula
+    // Clear any environment variables that might affect this test
+    unsafe {
+        std::env::remove_var("CONS_DECAY");
+        std::env::remove_var("CONS_THRESHOLD");
+        std::env::remove_var("CONS_MAX_HOPS");
+    }
+
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create parallel paths with different edge confidences
+    let seed_tag = service
+        .get_or_create_tag("seed")
+        .expect("failed to create seed tag");
+
+    let high_conf_tag = service
+        .get_or_create_tag("high-confidence-target")
+        .expect("failed to create high conf tag");
+
+    let low_conf_tag = service
+        .get_or_create_tag("low-confidence-target")
+        .expect("failed to create low conf tag");
+
+    // High confidence edge (0.9)
+    service
+        .create_edge(seed_tag, high_conf_tag, 0.9, "generic", Some("test-model"))
+        .expect("failed to create high conf edge");
+
+    // Low confidence edge (0.3)
+    service
+        .create_edge(seed_tag, low_conf_tag, 0.3, "generic", Some("test-model"))
+        .expect("failed to create low conf edge");
+
+    // Create notes with each target tag
+    let high_conf_note = service
+        .create_note("High confidence note", Some(&["high-confidence-target"]))
+        .expect("failed to create note");
+
+    let low_conf_note = service
+        .create_note("Low confidence note", Some(&["low-confidence-target"]))
+        .expect("failed to create note");
+
+    // Search for seed tag
+    let results = service
+        .graph_search("seed", Some(10))
+        .expect("graph search should succeed");
+
+    assert!(!results.is_empty(), "should find notes");
+
+    // Get scores
+    let high_conf_score = results
+        .iter()
+        .find(|r| r.note.id() == high_conf_note.id())
+        .expect("high conf note should be in results")
+        .relevance_score;
+
+    let low_conf_score = results
+        .iter()
+        .find(|r| r.note.id() == low_conf_note.id())
+        .expect("low conf note should be in results")
+        .relevance_score;
+
+    // High confidence edge should produce higher activation
+    // Formula: activation = 1.0 * confidence * decay * edge_type_multiplier
+    // High: 1.0 * 0.9 * 0.7 * 1.0 = 0.63
+    // Low:  1.0 * 0.3 * 0.7 * 1.0 = 0.21
+    assert!(
+        high_conf_score > low_conf_score,
+        "high confidence edge (0.9) should produce higher activation than low confidence (0.3), got high={}, low={}",
+        high_conf_score,
+        low_conf_score
+    );
+
+    // Verify rough ratio (allowing for bidirectional and normalization effects)
+    let ratio = high_conf_score / low_conf_score;
+    assert!(
+        ratio > 1.5,
+        "activation ratio should reflect confidence difference (0.9/0.3 = 3.0), got ratio={}",
+        ratio
+    );
+}
+
+#[test]
+fn graph_search_mixed_edge_types_in_path_applies_both_multipliers() {
+    // Test path with both generic (1.0) and partitive (0.5) edges
+    // Validates: edge type multiplier composition
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create chain: seed -> generic_tag -> partitive_tag
+    let seed_tag = service
         .get_or_create_tag("seed")
         .expect("failed to create seed tag");
     let generic_tag = service
@@ -4350,6 +7081,7 @@ fn dual_search_result_struct_instantiation() {
         fts_score: Some(0.7),
         graph_score: Some(0.5),
         found_by_both: true,
+        snippet: None,
     };
 
     // Verify all fields
@@ -4950,6 +7682,7 @@ fn dual_search_custom_config_weights_affect_final_score() {
         std::env::remove_var("CONS_GRAPH_WEIGHT");
         std::env::remove_var("CONS_INTERSECTION_BONUS");
     }
+    let service = service.with_config(Config::load());
 
     let (results_default, metadata_default) = service
         .dual_search("rust", Some(10))
@@ -4961,6 +7694,7 @@ fn dual_search_custom_config_weights_affect_final_score() {
         std::env::set_var("CONS_GRAPH_WEIGHT", "0.5");
         std::env::set_var("CONS_INTERSECTION_BONUS", "0.2");
     }
+    let service = service.with_config(Config::load());
 
     let (results_custom, metadata_custom) = service
         .dual_search("rust", Some(10))
@@ -5156,6 +7890,7 @@ fn dual_search_intersection_bonus_independent_of_weights() {
         std::env::set_var("CONS_GRAPH_WEIGHT", "1.5");
         std::env::set_var("CONS_INTERSECTION_BONUS", "0.7");
     }
+    let service = service.with_config(Config::load());
 
     let (results, metadata) = service
         .dual_search("rust", Some(10))
@@ -5228,974 +7963,1617 @@ fn expand_search_term_with_broader_enforces_term_limit_preferring_aliases() {
 
     // Should be limited to 10 terms
     assert!(
-        expanded.len() <= 10,
-        "should not exceed max_expansion_terms, got {} terms",
-        expanded.len()
+        expanded.len() <= 10,
+        "should not exceed max_expansion_terms, got {} terms",
+        expanded.len()
+    );
+
+    // Should include original term
+    assert!(
+        expanded.contains(&"rust".to_string()),
+        "should include original term"
+    );
+
+    // Aliases should be preferred - count how many aliases made it
+    let alias_count = expanded
+        .iter()
+        .filter(|term| term.starts_with("rust-alias-"))
+        .count();
+
+    // We should have most/all aliases since they're preferred
+    assert!(
+        alias_count >= 7,
+        "should prefer aliases over broader concepts, got {} aliases",
+        alias_count
+    );
+}
+
+#[test]
+fn should_expand_broader_returns_true_for_single_term_query() {
+    assert!(
+        super::should_expand_broader("rust"),
+        "single-term query should expand broader"
+    );
+}
+
+#[test]
+fn should_expand_broader_returns_true_for_two_term_query() {
+    assert!(
+        super::should_expand_broader("rust programming"),
+        "two-term query should expand broader"
+    );
+}
+
+#[test]
+fn should_expand_broader_returns_false_for_three_term_query() {
+    assert!(
+        !super::should_expand_broader("rust programming language"),
+        "three-term query should NOT expand broader"
+    );
+}
+
+#[test]
+fn should_expand_broader_returns_false_for_four_term_query() {
+    assert!(
+        !super::should_expand_broader("rust programming language tutorial"),
+        "four-term query should NOT expand broader"
+    );
+}
+
+#[test]
+fn should_expand_broader_handles_extra_whitespace() {
+    assert!(
+        super::should_expand_broader("  rust  programming  "),
+        "should handle extra whitespace correctly"
+    );
+    assert!(
+        !super::should_expand_broader("  rust  programming  language  "),
+        "should handle extra whitespace correctly for 3+ terms"
+    );
+}
+
+// --- Task Group 4: Enhanced FTS Query Building Tests ---
+
+#[test]
+fn build_expanded_fts_term_includes_alias_and_broader_in_or_expression() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create canonical tag "rust"
+    let rust_tag_id = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+
+    // Create alias "rustlang" -> "rust"
+    service
+        .create_alias("rustlang", rust_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Create broader concept "programming"
+    let programming_tag_id = service
+        .get_or_create_tag("programming")
+        .expect("failed to create tag");
+
+    // Create generic hierarchy edge: rust (narrower) -> programming (broader)
+    let conn = service.database().connection();
+    conn.execute(
+        "INSERT INTO edges (source_tag_id, target_tag_id, hierarchy_type, confidence, source)
+         VALUES (?1, ?2, 'generic', 0.9, 'user')",
+        [rust_tag_id.get(), programming_tag_id.get()],
+    )
+    .expect("failed to create edge");
+
+    // Build FTS term - should include rust, rustlang, and programming in OR expression
+    let config = QueryExpansionConfig::default();
+    let fts_term = service
+        .build_expanded_fts_term_with_config("rust", &config)
+        .expect("failed to build FTS term");
+
+    // Should be formatted as: ("rust" OR "rustlang" OR "programming")
+    assert!(
+        fts_term.contains("rust"),
+        "should include original term 'rust'"
+    );
+    assert!(
+        fts_term.contains("rustlang"),
+        "should include alias 'rustlang'"
+    );
+    assert!(
+        fts_term.contains("programming"),
+        "should include broader concept 'programming'"
+    );
+    assert!(
+        fts_term.contains(" OR "),
+        "should use OR logic between expanded terms"
+    );
+    assert!(fts_term.starts_with('('), "should start with parenthesis");
+    assert!(fts_term.ends_with(')'), "should end with parenthesis");
+}
+
+#[test]
+fn build_expanded_fts_term_maintains_and_between_multi_term_queries() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create two tags with aliases
+    let rust_tag_id = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    service
+        .create_alias("rustlang", rust_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    let tutorial_tag_id = service
+        .get_or_create_tag("tutorial")
+        .expect("failed to create tag");
+    service
+        .create_alias("guide", tutorial_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Build FTS terms for multi-term query
+    let config = QueryExpansionConfig::default();
+    let rust_fts = service
+        .build_expanded_fts_term_with_config("rust", &config)
+        .expect("failed to build rust FTS term");
+    let tutorial_fts = service
+        .build_expanded_fts_term_with_config("tutorial", &config)
+        .expect("failed to build tutorial FTS term");
+
+    // Simulate joining with AND (as done in search_notes)
+    let full_query = format!("{} AND {}", rust_fts, tutorial_fts);
+
+    // Should maintain AND logic between original terms
+    assert!(
+        full_query.contains(" AND "),
+        "should maintain AND between original query terms"
     );
 
-    // Should include original term
+    // Each term should have OR within its expansions
     assert!(
-        expanded.contains(&"rust".to_string()),
-        "should include original term"
+        rust_fts.contains(" OR "),
+        "rust term should have OR within expansions"
     );
-
-    // Aliases should be preferred - count how many aliases made it
-    let alias_count = expanded
-        .iter()
-        .filter(|term| term.starts_with("rust-alias-"))
-        .count();
-
-    // We should have most/all aliases since they're preferred
     assert!(
-        alias_count >= 7,
-        "should prefer aliases over broader concepts, got {} aliases",
-        alias_count
+        tutorial_fts.contains(" OR "),
+        "tutorial term should have OR within expansions"
     );
 }
 
 #[test]
-fn should_expand_broader_returns_true_for_single_term_query() {
+fn build_expanded_fts_term_properly_quotes_and_escapes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create a tag with special characters in name
+    let ml_tag_id = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+
+    // Create alias with hyphen (similar special character)
+    service
+        .create_alias("ml-model", ml_tag_id, "user", 1.0, None)
+        .expect("failed to create alias");
+
+    // Build FTS term
+    let config = QueryExpansionConfig::default();
+    let fts_term = service
+        .build_expanded_fts_term_with_config("machine-learning", &config)
+        .expect("failed to build FTS term");
+
+    // Each term should be quoted for FTS5
     assert!(
-        super::should_expand_broader("rust"),
-        "single-term query should expand broader"
+        fts_term.contains("\"machine-learning\""),
+        "should quote term with hyphen"
+    );
+    assert!(
+        fts_term.contains("\"ml-model\""),
+        "should quote alias with hyphen"
     );
-}
 
-#[test]
-fn should_expand_broader_returns_true_for_two_term_query() {
+    // Should use FTS5 syntax with parentheses and OR
+    assert!(fts_term.starts_with('('), "should start with parenthesis");
+    assert!(fts_term.ends_with(')'), "should end with parenthesis");
     assert!(
-        super::should_expand_broader("rust programming"),
-        "two-term query should expand broader"
+        fts_term.contains(" OR "),
+        "should use OR between quoted terms"
     );
-}
 
-#[test]
-fn should_expand_broader_returns_false_for_three_term_query() {
+    // Verify the full structure is correct
+    // Expected: ("machine-learning" OR "ml-model") or ("ml-model" OR "machine-learning")
+    let contains_both = fts_term.contains("machine-learning") && fts_term.contains("ml-model");
     assert!(
-        !super::should_expand_broader("rust programming language"),
-        "three-term query should NOT expand broader"
+        contains_both,
+        "should include both original and alias terms"
     );
 }
 
+// --- Task Group 5: Search Method Integration Tests ---
+
 #[test]
-fn should_expand_broader_returns_false_for_four_term_query() {
+fn search_notes_returns_notes_tagged_with_broader_concept() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tag hierarchy: rust -> programming
+    let rust = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    let programming = service
+        .get_or_create_tag("programming")
+        .expect("failed to create tag");
+    service
+        .create_edge(rust, programming, 0.9, "generic", Some("test"))
+        .expect("failed to create edge");
+
+    // Create a note tagged with "rust"
+    let note = service
+        .create_note("Learning Rust programming", Some(&["rust"]))
+        .expect("failed to create note");
+    let note_id = note.id();
+
+    // Search for "programming" - should find the note via broader concept expansion
+    let results = service
+        .search_notes("programming", None)
+        .expect("failed to search notes");
+
+    // Should find the note because rust has broader concept "programming"
     assert!(
-        !super::should_expand_broader("rust programming language tutorial"),
-        "four-term query should NOT expand broader"
+        results.iter().any(|r| r.note.id() == note_id),
+        "should find note tagged with narrower concept (rust) when searching broader concept (programming)"
     );
 }
 
 #[test]
-fn should_expand_broader_handles_extra_whitespace() {
-    assert!(
-        super::should_expand_broader("  rust  programming  "),
-        "should handle extra whitespace correctly"
-    );
+fn dual_search_applies_expansion_correctly_to_fts_channel() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tag hierarchy: rust -> programming
+    let rust = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    let programming = service
+        .get_or_create_tag("programming")
+        .expect("failed to create tag");
+    service
+        .create_edge(rust, programming, 0.8, "generic", Some("test"))
+        .expect("failed to create edge");
+
+    // Create a note tagged with "rust"
+    let note = service
+        .create_note("Learning Rust systems programming", Some(&["rust"]))
+        .expect("failed to create note");
+    let note_id = note.id();
+
+    // dual_search calls search_notes internally, which should apply expansion
+    let (results, _metadata) = service
+        .dual_search("programming", None)
+        .expect("failed to dual search");
+
+    // Should find the note via FTS channel expansion
     assert!(
-        !super::should_expand_broader("  rust  programming  language  "),
-        "should handle extra whitespace correctly for 3+ terms"
+        results.iter().any(|r| r.note.id() == note_id),
+        "dual_search should find note via FTS channel with broader expansion"
     );
 }
 
-// --- Task Group 4: Enhanced FTS Query Building Tests ---
-
 #[test]
-fn build_expanded_fts_term_includes_alias_and_broader_in_or_expression() {
+fn graph_search_does_not_apply_broader_expansion() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create canonical tag "rust"
-    let rust_tag_id = service
+    // Create tag hierarchy: rust -> programming
+    let rust = service
         .get_or_create_tag("rust")
         .expect("failed to create tag");
+    let programming = service
+        .get_or_create_tag("programming")
+        .expect("failed to create tag");
+    service
+        .create_edge(rust, programming, 0.9, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Create alias "rustlang" -> "rust"
+    // Create notes to ensure graph has connectivity
     service
-        .create_alias("rustlang", rust_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
+        .create_note("Rust systems programming", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .create_note("General programming concepts", Some(&["programming"]))
+        .expect("failed to create note");
 
-    // Create broader concept "programming"
-    let programming_tag_id = service
+    // graph_search should use spreading activation, not broader expansion
+    // The implementation uses expand_search_term (alias only) for seed tags
+    let results = service
+        .graph_search("rust", None)
+        .expect("failed to graph search");
+
+    // This test verifies graph_search exists and runs without errors
+    // Spreading activation handles hierarchy traversal internally
+    // We just verify it doesn't break with the broader expansion feature
+    assert!(
+        results.len() >= 1,
+        "graph_search should return results using spreading activation"
+    );
+}
+
+#[test]
+fn end_to_end_note_tagged_rust_search_transformer_find_via_hierarchy() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tag hierarchy chain: rust -> programming -> transformer
+    // This simulates a scenario where "rust" is a narrower concept under "programming",
+    // and "programming" is narrower under "transformer" (architecture/paradigm)
+    let rust = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    let programming = service
         .get_or_create_tag("programming")
         .expect("failed to create tag");
+    let transformer = service
+        .get_or_create_tag("transformer")
+        .expect("failed to create tag");
 
-    // Create generic hierarchy edge: rust (narrower) -> programming (broader)
-    let conn = service.database().connection();
-    conn.execute(
-        "INSERT INTO edges (source_tag_id, target_tag_id, hierarchy_type, confidence, source)
-         VALUES (?1, ?2, 'generic', 0.9, 'user')",
-        [rust_tag_id.get(), programming_tag_id.get()],
-    )
-    .expect("failed to create edge");
+    // rust -> programming (depth 1)
+    service
+        .create_edge(rust, programming, 0.85, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Build FTS term - should include rust, rustlang, and programming in OR expression
-    let config = QueryExpansionConfig::default();
-    let fts_term = service
-        .build_expanded_fts_term_with_config("rust", &config)
-        .expect("failed to build FTS term");
+    // programming -> transformer (depth 2 from rust)
+    service
+        .create_edge(programming, transformer, 0.80, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Should be formatted as: ("rust" OR "rustlang" OR "programming")
-    assert!(
-        fts_term.contains("rust"),
-        "should include original term 'rust'"
-    );
-    assert!(
-        fts_term.contains("rustlang"),
-        "should include alias 'rustlang'"
-    );
+    // Create a note tagged with "rust"
+    let note = service
+        .create_note("Advanced Rust programming techniques", Some(&["rust"]))
+        .expect("failed to create note");
+    let note_id = note.id();
+
+    // Search for "transformer"
+    // With depth=1 (default), searching "transformer" should expand to include notes
+    // tagged with "programming" (direct child). But the note is tagged with "rust",
+    // which is 2 levels down, so it should NOT be found with depth=1.
+    let results = service
+        .search_notes("transformer", None)
+        .expect("failed to search notes");
+
+    // Should NOT find the rust note because it's 2 levels deep
+    // and default expansion_depth is 1
     assert!(
-        fts_term.contains("programming"),
-        "should include broader concept 'programming'"
+        !results.iter().any(|r| r.note.id() == note_id),
+        "should NOT find note tagged with rust when searching transformer (2 levels deep with depth=1)"
     );
+
+    // Now search for "programming" - should find the rust note (1 level down)
+    let results_programming = service
+        .search_notes("programming", None)
+        .expect("failed to search notes");
+
     assert!(
-        fts_term.contains(" OR "),
-        "should use OR logic between expanded terms"
+        results_programming.iter().any(|r| r.note.id() == note_id),
+        "should find note tagged with rust when searching programming (1 level deep)"
     );
-    assert!(fts_term.starts_with('('), "should start with parenthesis");
-    assert!(fts_term.ends_with(')'), "should end with parenthesis");
 }
 
+// --- Task Group 6: Additional Strategic Tests for Edge Cases ---
+
 #[test]
-fn build_expanded_fts_term_maintains_and_between_multi_term_queries() {
+fn get_broader_concepts_exact_confidence_threshold_included() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create two tags with aliases
-    let rust_tag_id = service
+    // Create tags
+    let rust = service
         .get_or_create_tag("rust")
         .expect("failed to create tag");
-    service
-        .create_alias("rustlang", rust_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    let tutorial_tag_id = service
-        .get_or_create_tag("tutorial")
+    let programming = service
+        .get_or_create_tag("programming")
         .expect("failed to create tag");
-    service
-        .create_alias("guide", tutorial_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
-
-    // Build FTS terms for multi-term query
-    let config = QueryExpansionConfig::default();
-    let rust_fts = service
-        .build_expanded_fts_term_with_config("rust", &config)
-        .expect("failed to build rust FTS term");
-    let tutorial_fts = service
-        .build_expanded_fts_term_with_config("tutorial", &config)
-        .expect("failed to build tutorial FTS term");
 
-    // Simulate joining with AND (as done in search_notes)
-    let full_query = format!("{} AND {}", rust_fts, tutorial_fts);
+    // Create edge with confidence exactly at threshold (0.7)
+    service
+        .create_edge(rust, programming, 0.7, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Should maintain AND logic between original terms
-    assert!(
-        full_query.contains(" AND "),
-        "should maintain AND between original query terms"
-    );
+    // Query with threshold 0.7 - should include edge with exactly 0.7 confidence
+    let broader = service
+        .get_broader_concepts(rust, 0.7)
+        .expect("failed to get broader concepts");
 
-    // Each term should have OR within its expansions
-    assert!(
-        rust_fts.contains(" OR "),
-        "rust term should have OR within expansions"
+    assert_eq!(
+        broader.len(),
+        1,
+        "should include concepts with confidence exactly at threshold (>=)"
     );
-    assert!(
-        tutorial_fts.contains(" OR "),
-        "tutorial term should have OR within expansions"
+    assert_eq!(
+        broader[0], programming,
+        "should find programming with confidence=0.7"
     );
 }
 
 #[test]
-fn build_expanded_fts_term_properly_quotes_and_escapes() {
+fn expand_search_term_with_broader_exactly_ten_terms_no_truncation() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a tag with special characters in name
-    let ml_tag_id = service
-        .get_or_create_tag("machine-learning")
+    // Create a tag with 8 aliases (9 terms total with original)
+    let rust = service
+        .get_or_create_tag("rust")
         .expect("failed to create tag");
 
-    // Create alias with hyphen (similar special character)
+    for i in 1..=8 {
+        service
+            .create_alias(&format!("rust-alias-{}", i), rust, "user", 1.0, None)
+            .expect("failed to create alias");
+    }
+
+    // Add exactly 1 broader concept to bring total to exactly 10 terms
+    let programming = service
+        .get_or_create_tag("programming")
+        .expect("failed to create tag");
     service
-        .create_alias("ml-model", ml_tag_id, "user", 1.0, None)
-        .expect("failed to create alias");
+        .create_edge(rust, programming, 0.9, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Build FTS term
-    let config = QueryExpansionConfig::default();
-    let fts_term = service
-        .build_expanded_fts_term_with_config("machine-learning", &config)
-        .expect("failed to build FTS term");
+    // Expand with max_expansion_terms = 10
+    let config = QueryExpansionConfig {
+        max_expansion_terms: 10,
+        broader_min_confidence: 0.7,
+        expansion_depth: 1,
+    };
 
-    // Each term should be quoted for FTS5
-    assert!(
-        fts_term.contains("\"machine-learning\""),
-        "should quote term with hyphen"
-    );
-    assert!(
-        fts_term.contains("\"ml-model\""),
-        "should quote alias with hyphen"
+    let expanded = service
+        .expand_search_term_with_broader("rust", &config)
+        .expect("failed to expand term");
+
+    // Should include all 10 terms without truncation
+    assert_eq!(
+        expanded.len(),
+        10,
+        "should include exactly 10 terms without truncation"
     );
 
-    // Should use FTS5 syntax with parentheses and OR
-    assert!(fts_term.starts_with('('), "should start with parenthesis");
-    assert!(fts_term.ends_with(')'), "should end with parenthesis");
+    // Should include original term
     assert!(
-        fts_term.contains(" OR "),
-        "should use OR between quoted terms"
+        expanded.contains(&"rust".to_string()),
+        "should include original term"
     );
 
-    // Verify the full structure is correct
-    // Expected: ("machine-learning" OR "ml-model") or ("ml-model" OR "machine-learning")
-    let contains_both = fts_term.contains("machine-learning") && fts_term.contains("ml-model");
+    // Should include broader concept
     assert!(
-        contains_both,
-        "should include both original and alias terms"
+        expanded.contains(&"programming".to_string()),
+        "should include broader concept when total is exactly at limit"
     );
 }
 
-// --- Task Group 5: Search Method Integration Tests ---
-
 #[test]
-fn search_notes_returns_notes_tagged_with_broader_concept() {
+fn expand_search_term_with_broader_eleven_terms_truncates_broader_first() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tag hierarchy: rust -> programming
+    // Create a tag with 8 aliases (9 terms total with original)
     let rust = service
         .get_or_create_tag("rust")
         .expect("failed to create tag");
+
+    for i in 1..=8 {
+        service
+            .create_alias(&format!("rust-alias-{}", i), rust, "user", 1.0, None)
+            .expect("failed to create alias");
+    }
+
+    // Add 2 broader concepts to bring total to 11 terms
     let programming = service
         .get_or_create_tag("programming")
         .expect("failed to create tag");
+    let language = service
+        .get_or_create_tag("language")
+        .expect("failed to create tag");
+
     service
         .create_edge(rust, programming, 0.9, "generic", Some("test"))
         .expect("failed to create edge");
+    service
+        .create_edge(rust, language, 0.85, "generic", Some("test"))
+        .expect("failed to create edge");
 
-    // Create a note tagged with "rust"
-    let note = service
-        .create_note("Learning Rust programming", Some(&["rust"]))
-        .expect("failed to create note");
-    let note_id = note.id();
+    // Expand with max_expansion_terms = 10
+    let config = QueryExpansionConfig {
+        max_expansion_terms: 10,
+        broader_min_confidence: 0.7,
+        expansion_depth: 1,
+    };
 
-    // Search for "programming" - should find the note via broader concept expansion
-    let results = service
-        .search_notes("programming", None)
-        .expect("failed to search notes");
+    let expanded = service
+        .expand_search_term_with_broader("rust", &config)
+        .expect("failed to expand term");
 
-    // Should find the note because rust has broader concept "programming"
+    // Should be truncated to 10 terms
+    assert_eq!(
+        expanded.len(),
+        10,
+        "should truncate to max_expansion_terms when exceeded"
+    );
+
+    // Should include original term (alias)
     assert!(
-        results.iter().any(|r| r.note.id() == note_id),
-        "should find note tagged with narrower concept (rust) when searching broader concept (programming)"
+        expanded.contains(&"rust".to_string()),
+        "should include original term"
+    );
+
+    // All aliases should be preserved
+    let alias_count = expanded
+        .iter()
+        .filter(|term| term.starts_with("rust-alias-"))
+        .count();
+    assert_eq!(
+        alias_count, 8,
+        "should preserve all 8 aliases when truncating"
+    );
+
+    // At least one broader concept should be excluded due to truncation
+    let broader_count = expanded
+        .iter()
+        .filter(|term| term == &"programming" || term == &"language")
+        .count();
+    assert!(
+        broader_count < 2,
+        "should exclude at least one broader concept when over limit"
     );
 }
 
 #[test]
-fn dual_search_applies_expansion_correctly_to_fts_channel() {
+fn expand_search_term_with_broader_multiple_broader_concepts_all_included() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tag hierarchy: rust -> programming
+    // Create a tag with multiple broader concepts
     let rust = service
         .get_or_create_tag("rust")
         .expect("failed to create tag");
     let programming = service
-        .get_or_create_tag("programming")
+        .get_or_create_tag("programming-language")
         .expect("failed to create tag");
+    let systems = service
+        .get_or_create_tag("systems-programming")
+        .expect("failed to create tag");
+    let compiled = service
+        .get_or_create_tag("compiled-language")
+        .expect("failed to create tag");
+
+    // Create multiple generic edges: rust -> programming, systems, compiled
     service
-        .create_edge(rust, programming, 0.8, "generic", Some("test"))
+        .create_edge(rust, programming, 0.9, "generic", Some("test"))
+        .expect("failed to create edge");
+    service
+        .create_edge(rust, systems, 0.85, "generic", Some("test"))
+        .expect("failed to create edge");
+    service
+        .create_edge(rust, compiled, 0.8, "generic", Some("test"))
         .expect("failed to create edge");
 
-    // Create a note tagged with "rust"
-    let note = service
-        .create_note("Learning Rust systems programming", Some(&["rust"]))
-        .expect("failed to create note");
-    let note_id = note.id();
+    // Expand with default config
+    let config = QueryExpansionConfig::default();
+    let expanded = service
+        .expand_search_term_with_broader("rust", &config)
+        .expect("failed to expand term");
 
-    // dual_search calls search_notes internally, which should apply expansion
-    let (results, _metadata) = service
-        .dual_search("programming", None)
-        .expect("failed to dual search");
+    // Should include all three broader concepts
+    assert!(
+        expanded.contains(&"programming-language".to_string()),
+        "should include first broader concept"
+    );
+    assert!(
+        expanded.contains(&"systems-programming".to_string()),
+        "should include second broader concept"
+    );
+    assert!(
+        expanded.contains(&"compiled-language".to_string()),
+        "should include third broader concept"
+    );
 
-    // Should find the note via FTS channel expansion
+    // Should have at least 4 terms: original + 3 broader concepts
     assert!(
-        results.iter().any(|r| r.note.id() == note_id),
-        "dual_search should find note via FTS channel with broader expansion"
+        expanded.len() >= 4,
+        "should include original term plus all broader concepts, got {} terms",
+        expanded.len()
     );
 }
 
 #[test]
-fn graph_search_does_not_apply_broader_expansion() {
+fn expand_search_term_with_broader_no_broader_but_expansion_enabled() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tag hierarchy: rust -> programming
+    // Create a tag with no broader concepts but with an alias
     let rust = service
         .get_or_create_tag("rust")
         .expect("failed to create tag");
-    let programming = service
-        .get_or_create_tag("programming")
-        .expect("failed to create tag");
-    service
-        .create_edge(rust, programming, 0.9, "generic", Some("test"))
-        .expect("failed to create edge");
-
-    // Create notes to ensure graph has connectivity
-    service
-        .create_note("Rust systems programming", Some(&["rust"]))
-        .expect("failed to create note");
     service
-        .create_note("General programming concepts", Some(&["programming"]))
-        .expect("failed to create note");
+        .create_alias("rustlang", rust, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // graph_search should use spreading activation, not broader expansion
-    // The implementation uses expand_search_term (alias only) for seed tags
-    let results = service
-        .graph_search("rust", None)
-        .expect("failed to graph search");
+    // Expand with broader expansion enabled (single-term query)
+    let config = QueryExpansionConfig::default();
+    let expanded = service
+        .expand_search_term_with_broader("rust", &config)
+        .expect("failed to expand term");
 
-    // This test verifies graph_search exists and runs without errors
-    // Spreading activation handles hierarchy traversal internally
-    // We just verify it doesn't break with the broader expansion feature
+    // Should still get alias expansion even though no broader concepts exist
     assert!(
-        results.len() >= 1,
-        "graph_search should return results using spreading activation"
+        expanded.contains(&"rust".to_string()),
+        "should include original term"
+    );
+    assert!(
+        expanded.contains(&"rustlang".to_string()),
+        "should include alias even when no broader concepts exist"
+    );
+
+    // Should have exactly 2 terms (original + alias, no broader)
+    assert_eq!(
+        expanded.len(),
+        2,
+        "should gracefully handle missing broader concepts"
     );
 }
 
+// ========== Degree Centrality Integration Tests ==========
+
 #[test]
-fn end_to_end_note_tagged_rust_search_transformer_find_via_hierarchy() {
+fn graph_search_high_degree_tag_receives_centrality_boost() {
+    // Integration test: Verify degree centrality boost is applied in end-to-end graph search
+    // Creates a hub tag with high degree centrality and verifies boosted activation
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tag hierarchy chain: rust -> programming -> transformer
-    // This simulates a scenario where "rust" is a narrower concept under "programming",
-    // and "programming" is narrower under "transformer" (architecture/paradigm)
-    let rust = service
+    // Create a hub tag connected to many tags (high degree centrality)
+    let hub_tag = service
         .get_or_create_tag("rust")
-        .expect("failed to create tag");
-    let programming = service
+        .expect("failed to create hub tag");
+
+    // Create 4 connected tags to make hub_tag have degree_centrality = 4
+    let tag1 = service
         .get_or_create_tag("programming")
-        .expect("failed to create tag");
-    let transformer = service
-        .get_or_create_tag("transformer")
-        .expect("failed to create tag");
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("systems")
+        .expect("failed to create tag2");
+    let tag3 = service
+        .get_or_create_tag("memory-safety")
+        .expect("failed to create tag3");
+    let tag4 = service
+        .get_or_create_tag("performance")
+        .expect("failed to create tag4");
 
-    // rust -> programming (depth 1)
+    // Create edges from hub to all tags
     service
-        .create_edge(rust, programming, 0.85, "generic", Some("test"))
-        .expect("failed to create edge");
-
-    // programming -> transformer (depth 2 from rust)
+        .create_edge(hub_tag, tag1, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 1");
     service
-        .create_edge(programming, transformer, 0.80, "generic", Some("test"))
-        .expect("failed to create edge");
+        .create_edge(hub_tag, tag2, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 2");
+    service
+        .create_edge(hub_tag, tag3, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 3");
+    service
+        .create_edge(hub_tag, tag4, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 4");
 
-    // Create a note tagged with "rust"
-    let note = service
-        .create_note("Advanced Rust programming techniques", Some(&["rust"]))
-        .expect("failed to create note");
-    let note_id = note.id();
+    // Verify hub_tag has degree_centrality = 4
+    let hub_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [hub_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query degree_centrality");
+    assert_eq!(
+        hub_centrality, 4,
+        "hub tag should have degree_centrality = 4"
+    );
 
-    // Search for "transformer"
-    // With depth=1 (default), searching "transformer" should expand to include notes
-    // tagged with "programming" (direct child). But the note is tagged with "rust",
-    // which is 2 levels down, so it should NOT be found with depth=1.
+    // Create an isolated tag with degree_centrality = 0 for comparison
+    let _isolated_tag = service
+        .get_or_create_tag("isolated")
+        .expect("failed to create isolated tag");
+
+    // Create notes tagged with hub_tag and isolated_tag respectively
+    let hub_note = service
+        .create_note("Rust programming guide", Some(&["rust"]))
+        .expect("failed to create hub note");
+
+    let _isolated_note = service
+        .create_note("Isolated concept", Some(&["isolated"]))
+        .expect("failed to create isolated note");
+
+    // Search using a tag that connects to hub_tag
+    // This will activate hub_tag with spreading activation
     let results = service
-        .search_notes("transformer", None)
-        .expect("failed to search notes");
+        .graph_search("programming", Some(10))
+        .expect("graph search should succeed");
 
-    // Should NOT find the rust note because it's 2 levels deep
-    // and default expansion_depth is 1
+    // Both notes should be found (rust via edge, isolated not connected but might have seed)
+    // Focus on verifying hub_note benefits from centrality boost
+    let hub_result = results
+        .iter()
+        .find(|r| r.note.id() == hub_note.id())
+        .expect("hub note should be found");
+
+    // The hub tag should receive activation boost due to degree_centrality = 4
+    // With max_degree = 4, boost = 1.0 + (4/4) * 0.3 = 1.3
+    // We can't directly check activation, but we can verify the note was found
+    // and has a reasonable score
     assert!(
-        !results.iter().any(|r| r.note.id() == note_id),
-        "should NOT find note tagged with rust when searching transformer (2 levels deep with depth=1)"
+        hub_result.relevance_score > 0.0,
+        "hub note should have positive relevance due to centrality boost"
+    );
+
+    // For a more precise test, we can compare with expected boost behavior:
+    // If we seed from "programming", it activates hub_tag (rust) via the edge
+    // Hub tag gets boosted by its centrality
+    // The activation is then used to score the hub_note
+    println!(
+        "Hub note score: {} (with centrality boost)",
+        hub_result.relevance_score
+    );
+}
+
+#[test]
+fn create_edges_batch_updates_degree_centrality_for_all_affected_tags() {
+    // Integration test: Verify batch edge creation correctly updates centrality
+    // Covers cross-layer workflow: Service -> Database with transaction atomicity
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    // Create tags for batch edge creation
+    let tag1 = service
+        .get_or_create_tag("neural-networks")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("deep-learning")
+        .expect("failed to create tag2");
+    let tag3 = service
+        .get_or_create_tag("transformers")
+        .expect("failed to create tag3");
+    let tag4 = service
+        .get_or_create_tag("attention")
+        .expect("failed to create tag4");
+
+    // Verify all tags start with degree_centrality = 0
+    for tag_id in [tag1, tag2, tag3, tag4] {
+        let centrality: i32 = service
+            .db
+            .connection()
+            .query_row(
+                "SELECT degree_centrality FROM tags WHERE id = ?1",
+                [tag_id.get()],
+                |row| row.get(0),
+            )
+            .expect("failed to query centrality");
+        assert_eq!(centrality, 0, "tag should start with centrality 0");
+    }
+
+    // Create batch of edges:
+    // tag1 -> tag2 (tag1: 1, tag2: 1)
+    // tag2 -> tag3 (tag1: 1, tag2: 2, tag3: 1)
+    // tag3 -> tag4 (tag1: 1, tag2: 2, tag3: 2, tag4: 1)
+    let edges = vec![
+        (tag1, tag2, 0.9, "generic", Some("test-model")),
+        (tag2, tag3, 0.8, "generic", Some("test-model")),
+        (tag3, tag4, 0.85, "partitive", Some("test-model")),
+    ];
+
+    let count = service
+        .create_edges_batch(&edges)
+        .expect("batch edge creation should succeed");
+
+    assert_eq!(count, 3, "should create 3 edges");
+
+    // Verify degree_centrality was updated correctly for all tags
+    let tag1_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag1.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag1 centrality");
+    assert_eq!(
+        tag1_centrality, 1,
+        "tag1 has 1 edge (tag1->tag2), centrality should be 1"
+    );
+
+    let tag2_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag2.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag2 centrality");
+    assert_eq!(
+        tag2_centrality, 2,
+        "tag2 has 2 edges (tag1->tag2, tag2->tag3), centrality should be 2"
+    );
+
+    let tag3_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag3.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag3 centrality");
+    assert_eq!(
+        tag3_centrality, 2,
+        "tag3 has 2 edges (tag2->tag3, tag3->tag4), centrality should be 2"
     );
 
-    // Now search for "programming" - should find the rust note (1 level down)
-    let results_programming = service
-        .search_notes("programming", None)
-        .expect("failed to search notes");
-
-    assert!(
-        results_programming.iter().any(|r| r.note.id() == note_id),
-        "should find note tagged with rust when searching programming (1 level deep)"
+    let tag4_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag4.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query tag4 centrality");
+    assert_eq!(
+        tag4_centrality, 1,
+        "tag4 has 1 edge (tag3->tag4), centrality should be 1"
     );
 }
 
-// --- Task Group 6: Additional Strategic Tests for Edge Cases ---
-
 #[test]
-fn get_broader_concepts_exact_confidence_threshold_included() {
+fn dual_search_centrality_boost_affects_final_ranking() {
+    // Integration test: Verify degree centrality boost affects dual search results
+    // Tests full end-to-end workflow: Notes -> Tags -> Edges -> Graph Search -> Dual Search
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create tags
-    let rust = service
-        .get_or_create_tag("rust")
-        .expect("failed to create tag");
-    let programming = service
-        .get_or_create_tag("programming")
-        .expect("failed to create tag");
+    // Create a hub tag with high degree centrality
+    let hub_tag = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create hub tag");
 
-    // Create edge with confidence exactly at threshold (0.7)
-    service
-        .create_edge(rust, programming, 0.7, "generic", Some("test"))
-        .expect("failed to create edge");
+    // Create connected tags to establish high centrality for hub_tag
+    let tag1 = service
+        .get_or_create_tag("neural-networks")
+        .expect("failed to create tag1");
+    let tag2 = service
+        .get_or_create_tag("deep-learning")
+        .expect("failed to create tag2");
+    let tag3 = service
+        .get_or_create_tag("supervised-learning")
+        .expect("failed to create tag3");
 
-    // Query with threshold 0.7 - should include edge with exactly 0.7 confidence
-    let broader = service
-        .get_broader_concepts(rust, 0.7)
-        .expect("failed to get broader concepts");
+    // Create edges to make hub_tag have degree_centrality = 3
+    service
+        .create_edge(hub_tag, tag1, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 1");
+    service
+        .create_edge(hub_tag, tag2, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 2");
+    service
+        .create_edge(hub_tag, tag3, 1.0, "generic", Some("test-model"))
+        .expect("failed to create edge 3");
 
-    assert_eq!(
-        broader.len(),
-        1,
-        "should include concepts with confidence exactly at threshold (>=)"
-    );
-    assert_eq!(
-        broader[0], programming,
-        "should find programming with confidence=0.7"
-    );
-}
+    // Verify centrality
+    let hub_centrality: i32 = service
+        .db
+        .connection()
+        .query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [hub_tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query centrality");
+    assert_eq!(hub_centrality, 3);
 
-#[test]
-fn expand_search_term_with_broader_exactly_ten_terms_no_truncation() {
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
+    // Create notes that will be found via different channels
+    let hub_note = service
+        .create_note(
+            "Machine learning fundamentals with neural networks",
+            Some(&["machine-learning", "neural-networks"]),
+        )
+        .expect("failed to create hub note");
 
-    // Create a tag with 8 aliases (9 terms total with original)
-    let rust = service
-        .get_or_create_tag("rust")
-        .expect("failed to create tag");
+    let _other_note = service
+        .create_note("Introduction to algorithms", Some(&["supervised-learning"]))
+        .expect("failed to create other note");
 
-    for i in 1..=8 {
-        service
-            .create_alias(&format!("rust-alias-{}", i), rust, "user", 1.0, None)
-            .expect("failed to create alias");
-    }
+    // Run dual search for "machine learning"
+    // This should:
+    // 1. Find hub_note via FTS (content match)
+    // 2. Find hub_note via graph search (tag match with centrality boost)
+    // 3. Find other_note via graph search (connected via edges)
+    let (results, _metadata) = service
+        .dual_search("machine learning", Some(10))
+        .expect("dual search should succeed");
 
-    // Add exactly 1 broader concept to bring total to exactly 10 terms
-    let programming = service
-        .get_or_create_tag("programming")
-        .expect("failed to create tag");
-    service
-        .create_edge(rust, programming, 0.9, "generic", Some("test"))
-        .expect("failed to create edge");
+    assert!(!results.is_empty(), "should find notes");
 
-    // Expand with max_expansion_terms = 10
-    let config = QueryExpansionConfig {
-        max_expansion_terms: 10,
-        broader_min_confidence: 0.7,
-        expansion_depth: 1,
-    };
+    // Verify hub_note benefits from centrality boost in graph scoring
+    let hub_result = results.iter().find(|r| r.note.id() == hub_note.id());
 
-    let expanded = service
-        .expand_search_term_with_broader("rust", &config)
-        .expect("failed to expand term");
+    if let Some(hub_result) = hub_result {
+        // Hub note should be found
+        println!(
+            "Hub note - FTS: {:?}, Graph: {:?}, Final: {}",
+            hub_result.fts_score, hub_result.graph_score, hub_result.final_score
+        );
 
-    // Should include all 10 terms without truncation
-    assert_eq!(
-        expanded.len(),
-        10,
-        "should include exactly 10 terms without truncation"
-    );
+        // If found by graph channel, verify it has a graph score
+        if let Some(graph_score) = hub_result.graph_score {
+            assert!(
+                graph_score > 0.0,
+                "hub note should have positive graph score due to centrality boost"
+            );
+        }
 
-    // Should include original term
-    assert!(
-        expanded.contains(&"rust".to_string()),
-        "should include original term"
-    );
+        // The centrality boost should contribute to higher final ranking
+        assert!(
+            hub_result.final_score > 0.0,
+            "hub note should have positive final score"
+        );
+    } else {
+        // If not found, that's acceptable as dual search may filter differently
+        println!("Hub note not in top results (this is acceptable)");
+    }
 
-    // Should include broader concept
+    // Main assertion: verify that the dual search completed successfully
+    // and integrated centrality boost into the scoring pipeline
     assert!(
-        expanded.contains(&"programming".to_string()),
-        "should include broader concept when total is exactly at limit"
+        results.len() > 0,
+        "dual search should return results with centrality-boosted graph scores"
     );
 }
 
+// --- Tag Rename Tests ---
+
 #[test]
-fn expand_search_term_with_broader_eleven_terms_truncates_broader_first() {
+fn rename_tag_to_new_name_renames_in_place() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a tag with 8 aliases (9 terms total with original)
-    let rust = service
-        .get_or_create_tag("rust")
-        .expect("failed to create tag");
-
-    for i in 1..=8 {
-        service
-            .create_alias(&format!("rust-alias-{}", i), rust, "user", 1.0, None)
-            .expect("failed to create alias");
-    }
-
-    // Add 2 broader concepts to bring total to 11 terms
-    let programming = service
-        .get_or_create_tag("programming")
-        .expect("failed to create tag");
-    let language = service
-        .get_or_create_tag("language")
-        .expect("failed to create tag");
+    let note = service
+        .create_note("Borrow checker notes", Some(&["rust-lang"]))
+        .expect("failed to create note");
+    let old_id = note.tags()[0].tag_id();
 
-    service
-        .create_edge(rust, programming, 0.9, "generic", Some("test"))
-        .expect("failed to create edge");
-    service
-        .create_edge(rust, language, 0.85, "generic", Some("test"))
-        .expect("failed to create edge");
+    let renamed = service
+        .rename_tag("rust-lang", "Rust")
+        .expect("failed to rename tag");
+    assert_eq!(renamed, old_id, "in-place rename should keep the tag id");
 
-    // Expand with max_expansion_terms = 10
-    let config = QueryExpansionConfig {
-        max_expansion_terms: 10,
-        broader_min_confidence: 0.7,
-        expansion_depth: 1,
-    };
+    let retrieved = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(retrieved.tags()[0].name(), "rust");
 
-    let expanded = service
-        .expand_search_term_with_broader("rust", &config)
-        .expect("failed to expand term");
+    // FTS index should reflect the new tag name
+    let results = service
+        .search_notes("rust", None)
+        .expect("search should succeed");
+    assert_eq!(results.len(), 1);
+}
 
-    // Should be truncated to 10 terms
-    assert_eq!(
-        expanded.len(),
-        10,
-        "should truncate to max_expansion_terms when exceeded"
-    );
+#[test]
+fn rename_tag_merges_into_existing_tag() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    // Should include original term (alias)
-    assert!(
-        expanded.contains(&"rust".to_string()),
-        "should include original term"
-    );
+    let note1 = service
+        .create_note("Note with both tags", Some(&["ml", "machine-learning"]))
+        .expect("failed to create note");
+    let note2 = service
+        .create_note("Note with abbreviation", Some(&["ml"]))
+        .expect("failed to create note");
 
-    // All aliases should be preserved
-    let alias_count = expanded
-        .iter()
-        .filter(|term| term.starts_with("rust-alias-"))
-        .count();
-    assert_eq!(
-        alias_count, 8,
-        "should preserve all 8 aliases when truncating"
-    );
+    let merged = service
+        .rename_tag("ml", "machine-learning")
+        .expect("failed to merge tags");
+
+    for note_id in [note1.id(), note2.id()] {
+        let note = service
+            .get_note(note_id)
+            .expect("failed to get note")
+            .expect("note should exist");
+        let names: Vec<&str> = note.tags().iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["machine-learning"]);
+        assert_eq!(note.tags()[0].tag_id(), merged);
+    }
 
-    // At least one broader concept should be excluded due to truncation
-    let broader_count = expanded
-        .iter()
-        .filter(|term| term == &"programming" || term == &"language")
-        .count();
-    assert!(
-        broader_count < 2,
-        "should exclude at least one broader concept when over limit"
-    );
+    let old_exists: bool = service
+        .database()
+        .connection()
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM tags WHERE name = 'ml')",
+            [],
+            |row| row.get(0),
+        )
+        .expect("failed to query tags");
+    assert!(!old_exists, "old tag should be deleted after merge");
 }
 
 #[test]
-fn expand_search_term_with_broader_multiple_broader_concepts_all_included() {
+fn rename_tag_merge_migrates_edges_aliases_and_centrality() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a tag with multiple broader concepts
-    let rust = service
-        .get_or_create_tag("rust")
-        .expect("failed to create tag");
-    let programming = service
-        .get_or_create_tag("programming-language")
+    let ml = service
+        .get_or_create_tag("ml")
         .expect("failed to create tag");
-    let systems = service
-        .get_or_create_tag("systems-programming")
+    let machine_learning = service
+        .get_or_create_tag("machine-learning")
         .expect("failed to create tag");
-    let compiled = service
-        .get_or_create_tag("compiled-language")
+    let ai = service
+        .get_or_create_tag("ai")
         .expect("failed to create tag");
 
-    // Create multiple generic edges: rust -> programming, systems, compiled
+    // Both tags point at the same broader concept, plus an edge between them
     service
-        .create_edge(rust, programming, 0.9, "generic", Some("test"))
+        .create_edge(ml, ai, 0.9, "generic", None)
         .expect("failed to create edge");
     service
-        .create_edge(rust, systems, 0.85, "generic", Some("test"))
+        .create_edge(machine_learning, ai, 0.9, "generic", None)
         .expect("failed to create edge");
     service
-        .create_edge(rust, compiled, 0.8, "generic", Some("test"))
+        .create_edge(ml, machine_learning, 0.8, "generic", None)
         .expect("failed to create edge");
+    service
+        .create_alias("m-l", ml, "user", 1.0, None)
+        .expect("failed to create alias");
 
-    // Expand with default config
-    let config = QueryExpansionConfig::default();
-    let expanded = service
-        .expand_search_term_with_broader("rust", &config)
-        .expect("failed to expand term");
+    service
+        .rename_tag("ml", "machine-learning")
+        .expect("failed to merge tags");
 
-    // Should include all three broader concepts
-    assert!(
-        expanded.contains(&"programming-language".to_string()),
-        "should include first broader concept"
-    );
-    assert!(
-        expanded.contains(&"systems-programming".to_string()),
-        "should include second broader concept"
-    );
-    assert!(
-        expanded.contains(&"compiled-language".to_string()),
-        "should include third broader concept"
+    let conn = service.database().connection();
+    let edge_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM edges", [], |row| row.get(0))
+        .expect("failed to count edges");
+    assert_eq!(
+        edge_count, 1,
+        "duplicate and self-loop edges should be removed"
     );
 
-    // Should have at least 4 terms: original + 3 broader concepts
-    assert!(
-        expanded.len() >= 4,
-        "should include original term plus all broader concepts, got {} terms",
-        expanded.len()
+    let centrality = |tag: TagId| -> i64 {
+        conn.query_row(
+            "SELECT degree_centrality FROM tags WHERE id = ?1",
+            [tag.get()],
+            |row| row.get(0),
+        )
+        .expect("failed to query centrality")
+    };
+    assert_eq!(centrality(machine_learning), 1);
+    assert_eq!(centrality(ai), 1);
+
+    assert_eq!(
+        service
+            .resolve_alias("m-l")
+            .expect("failed to resolve alias"),
+        Some(machine_learning)
     );
 }
 
 #[test]
-fn expand_search_term_with_broader_no_broader_but_expansion_enabled() {
+fn rename_tag_fails_for_non_existent_tag() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
 
-    // Create a tag with no broader concepts but with an alias
-    let rust = service
-        .get_or_create_tag("rust")
-        .expect("failed to create tag");
+    let result = service.rename_tag("missing", "other");
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("does not exist"));
+}
+
+// --- Merge Notes Tests ---
+
+#[test]
+fn merge_notes_unions_tags_and_deletes_merged_note() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let keep = service
+        .create_note("Tokio runs futures", Some(&["async"]))
+        .expect("failed to create note");
     service
-        .create_alias("rustlang", rust, "user", 1.0, None)
-        .expect("failed to create alias");
+        .add_tags_to_note(keep.id(), &["rust"], TagSource::llm("test-model", 60))
+        .expect("failed to tag note");
+    let merge = service
+        .create_note("Executors poll tasks", Some(&["rust"]))
+        .expect("failed to create note");
+    service
+        .add_tags_to_note(merge.id(), &["tokio"], TagSource::llm("test-model", 90))
+        .expect("failed to tag note");
 
-    // Expand with broader expansion enabled (single-term query)
-    let config = QueryExpansionConfig::default();
-    let expanded = service
-        .expand_search_term_with_broader("rust", &config)
-        .expect("failed to expand term");
+    let merged = service
+        .merge_notes(keep.id(), merge.id())
+        .expect("merge should succeed");
 
-    // Should still get alias expansion even though no broader concepts exist
+    assert_eq!(merged.id(), keep.id());
+    assert_eq!(
+        merged.content(),
+        "Tokio runs futures\n\nExecutors poll tasks"
+    );
+    assert_eq!(service.get_note(merge.id()).unwrap(), None);
+
+    let tag = |name: &str| {
+        merged
+            .tags()
+            .iter()
+            .find(|t| t.name() == name)
+            .unwrap_or_else(|| panic!("{name} should be on the kept note"))
+    };
+    assert_eq!(merged.tags().len(), 3);
+    assert!(tag("async").source().is_user());
     assert!(
-        expanded.contains(&"rust".to_string()),
-        "should include original term"
+        tag("rust").source().is_user(),
+        "the user assignment from the merged note beats the LLM one"
+    );
+    assert_eq!(tag("tokio").confidence(), 90);
+
+    let results = service
+        .search_notes("executors", None)
+        .expect("search should succeed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id(), keep.id());
+}
+
+#[test]
+fn merge_notes_keeps_the_stronger_llm_assignment() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let keep = service.create_note("Kept", None).unwrap();
+    service
+        .add_tags_to_note(keep.id(), &["rust"], TagSource::llm("test-model", 95))
+        .unwrap();
+    let merge = service.create_note("Merged", None).unwrap();
+    service
+        .add_tags_to_note(merge.id(), &["rust"], TagSource::llm("test-model", 40))
+        .unwrap();
+
+    let merged = service.merge_notes(keep.id(), merge.id()).unwrap();
+
+    assert_eq!(merged.tags().len(), 1);
+    assert_eq!(merged.tags()[0].confidence(), 95);
+}
+
+#[test]
+fn merge_notes_repoints_links_to_the_kept_note() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let keep = service.create_note("Ownership", None).unwrap();
+    let merge = service.create_note("Borrowing", None).unwrap();
+    let referrer = service
+        .create_note(&format!("See [[{}]] for details", merge.id()), None)
+        .unwrap();
+    service
+        .database()
+        .connection()
+        .execute(
+            "UPDATE notes SET updated_at = 0 WHERE id = ?1",
+            [referrer.id().get()],
+        )
+        .unwrap();
+
+    service.merge_notes(keep.id(), merge.id()).unwrap();
+
+    let referrer = service.get_note(referrer.id()).unwrap().unwrap();
+    assert_eq!(
+        referrer.content(),
+        format!("See [[{}]] for details", keep.id())
     );
     assert!(
-        expanded.contains(&"rustlang".to_string()),
-        "should include alias even when no broader concepts exist"
+        referrer.updated_at().unix_timestamp() > 0,
+        "rewrite bumps updated_at"
     );
+    let backlinks = service.get_backlinks(keep.id()).unwrap();
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0].id(), referrer.id());
+}
+
+#[test]
+fn merge_notes_rewrites_links_to_the_merged_note_in_the_kept_note() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+
+    let merge = service.create_note("Borrowing", None).unwrap();
+    let keep = service
+        .create_note(&format!("Ownership, see [[{}]]", merge.id()), None)
+        .unwrap();
+
+    let merged = service.merge_notes(keep.id(), merge.id()).unwrap();
 
-    // Should have exactly 2 terms (original + alias, no broader)
     assert_eq!(
-        expanded.len(),
-        2,
-        "should gracefully handle missing broader concepts"
+        merged.content(),
+        format!("Ownership, see [[{}]]\n\nBorrowing", keep.id())
     );
+    assert!(!merged.content().contains(&format!("[[{}]]", merge.id())));
+    assert!(service.get_links(keep.id()).unwrap().is_empty());
 }
 
-// ========== Degree Centrality Integration Tests ==========
+#[test]
+fn merge_notes_rejects_missing_or_identical_notes() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+    let note = service.create_note("Only note", None).unwrap();
+
+    let err = service
+        .merge_notes(note.id(), NoteId::new(999))
+        .unwrap_err();
+    assert!(err.to_string().contains("does not exist"));
+
+    let err = service.merge_notes(note.id(), note.id()).unwrap_err();
+    assert!(err.to_string().contains("into itself"));
+    assert!(service.get_note(note.id()).unwrap().is_some());
+}
+
+// --- Bulk Tagging Tests ---
 
 #[test]
-fn graph_search_high_degree_tag_receives_centrality_boost() {
-    // Integration test: Verify degree centrality boost is applied in end-to-end graph search
-    // Creates a hub tag with high degree centrality and verifies boosted activation
-    let db = Database::in_memory().expect("failed to create in-memory database");
-    let service = NoteService::new(db);
+fn add_tag_to_notes_with_tag_tags_only_matching_notes() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
 
-    // Create a hub tag connected to many tags (high degree centrality)
-    let hub_tag = service
-        .get_or_create_tag("rust")
-        .expect("failed to create hub tag");
+    let first = service.create_note("Ownership", Some(&["rust"])).unwrap();
+    let second = service
+        .create_note("Traits", Some(&["rust", "programming"]))
+        .unwrap();
+    let unrelated = service.create_note("Sourdough", Some(&["baking"])).unwrap();
 
-    // Create 4 connected tags to make hub_tag have degree_centrality = 4
-    let tag1 = service
-        .get_or_create_tag("programming")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("systems")
-        .expect("failed to create tag2");
-    let tag3 = service
-        .get_or_create_tag("memory-safety")
-        .expect("failed to create tag3");
-    let tag4 = service
-        .get_or_create_tag("performance")
-        .expect("failed to create tag4");
+    let added = service
+        .add_tag_to_notes_with_tag("Programming", "RUST", TagSource::llm("test-model", 70))
+        .expect("bulk tagging should succeed");
 
-    // Create edges from hub to all tags
-    service
-        .create_edge(hub_tag, tag1, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 1");
-    service
-        .create_edge(hub_tag, tag2, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 2");
+    assert_eq!(added, 1, "the note already tagged programming is skipped");
+
+    let first = service.get_note(first.id()).unwrap().unwrap();
+    let programming = first
+        .tags()
+        .iter()
+        .find(|t| t.name() == "programming")
+        .expect("first note gains the tag");
+    assert_eq!(programming.confidence(), 70);
+
+    let second = service.get_note(second.id()).unwrap().unwrap();
+    let existing = second
+        .tags()
+        .iter()
+        .find(|t| t.name() == "programming")
+        .unwrap();
+    assert!(existing.source().is_user(), "existing assignment is kept");
+
+    let unrelated = service.get_note(unrelated.id()).unwrap().unwrap();
+    assert_eq!(unrelated.tags().len(), 1);
+
+    let results = service.search_notes("programming", None).unwrap();
+    assert_eq!(results.len(), 2, "FTS sees the new tag");
+}
+
+#[test]
+fn add_tag_to_notes_with_tag_resolves_filter_alias() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
     service
-        .create_edge(hub_tag, tag3, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 3");
+        .create_note("Gradient descent", Some(&["machine-learning"]))
+        .unwrap();
+    let canonical = service.get_or_create_tag("machine-learning").unwrap();
     service
-        .create_edge(hub_tag, tag4, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 4");
+        .create_alias("ml", canonical, "user", 1.0, None)
+        .unwrap();
 
-    // Verify hub_tag has degree_centrality = 4
-    let hub_centrality: i32 = service
-        .db
-        .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [hub_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query degree_centrality");
-    assert_eq!(
-        hub_centrality, 4,
-        "hub tag should have degree_centrality = 4"
-    );
+    let added = service
+        .add_tag_to_notes_with_tag("ai", "ml", TagSource::User)
+        .unwrap();
 
-    // Create an isolated tag with degree_centrality = 0 for comparison
-    let _isolated_tag = service
-        .get_or_create_tag("isolated")
-        .expect("failed to create isolated tag");
+    assert_eq!(added, 1);
+}
 
-    // Create notes tagged with hub_tag and isolated_tag respectively
-    let hub_note = service
-        .create_note("Rust programming guide", Some(&["rust"]))
-        .expect("failed to create hub note");
+#[test]
+fn add_tag_to_notes_with_tag_rejects_missing_filter_or_empty_tag() {
+    let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+    service.create_note("Ownership", Some(&["rust"])).unwrap();
 
-    let _isolated_note = service
-        .create_note("Isolated concept", Some(&["isolated"]))
-        .expect("failed to create isolated note");
+    let err = service
+        .add_tag_to_notes_with_tag("programming", "python", TagSource::User)
+        .unwrap_err();
+    assert!(err.to_string().contains("does not exist"));
 
-    // Search using a tag that connects to hub_tag
-    // This will activate hub_tag with spreading activation
-    let results = service
-        .graph_search("programming", Some(10))
-        .expect("graph search should succeed");
+    let err = service
+        .add_tag_to_notes_with_tag("  ", "rust", TagSource::User)
+        .unwrap_err();
+    assert!(err.to_string().contains("cannot be empty"));
+}
 
-    // Both notes should be found (rust via edge, isolated not connected but might have seed)
-    // Focus on verifying hub_note benefits from centrality boost
-    let hub_result = results
-        .iter()
-        .find(|r| r.note.id() == hub_note.id())
-        .expect("hub note should be found");
+// --- Alias Detection Tests ---
 
-    // The hub tag should receive activation boost due to degree_centrality = 4
-    // With max_degree = 4, boost = 1.0 + (4/4) * 0.3 = 1.3
-    // We can't directly check activation, but we can verify the note was found
-    // and has a reasonable score
-    assert!(
-        hub_result.relevance_score > 0.0,
-        "hub note should have positive relevance due to centrality boost"
+#[test]
+fn detect_alias_opportunity_matches_word_initials() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let canonical = service
+        .get_or_create_tag("machine-learning")
+        .expect("failed to create tag");
+
+    assert_eq!(
+        service.detect_alias_opportunity("ml").unwrap(),
+        Some(canonical)
+    );
+    assert_eq!(
+        service.detect_alias_opportunity("ML").unwrap(),
+        Some(canonical)
     );
+}
 
-    // For a more precise test, we can compare with expected boost behavior:
-    // If we seed from "programming", it activates hub_tag (rust) via the edge
-    // Hub tag gets boosted by its centrality
-    // The activation is then used to score the hub_note
-    println!(
-        "Hub note score: {} (with centrality boost)",
-        hub_result.relevance_score
+#[test]
+fn detect_alias_opportunity_requires_word_boundary_acronym() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    service
+        .get_or_create_tag("aimodel")
+        .expect("failed to create single-word tag");
+    assert_eq!(
+        service.detect_alias_opportunity("ai").unwrap(),
+        None,
+        "'ai' is a prefix of 'aimodel', not an acronym"
     );
+
+    let canonical = service
+        .get_or_create_tag("artificial-intelligence")
+        .expect("failed to create canonical tag");
+    assert_eq!(
+        service.detect_alias_opportunity("ai").unwrap(),
+        Some(canonical)
+    );
+
+    // Word count must match the acronym length
+    service
+        .get_or_create_tag("api-design")
+        .expect("failed to create tag");
+    assert_eq!(service.detect_alias_opportunity("api").unwrap(), None);
 }
 
 #[test]
-fn create_edges_batch_updates_degree_centrality_for_all_affected_tags() {
-    // Integration test: Verify batch edge creation correctly updates centrality
-    // Covers cross-layer workflow: Service -> Database with transaction atomicity
+fn detect_alias_opportunity_returns_none_without_match() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
 
-    // Create tags for batch edge creation
-    let tag1 = service
-        .get_or_create_tag("neural-networks")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("deep-learning")
-        .expect("failed to create tag2");
-    let tag3 = service
-        .get_or_create_tag("transformers")
-        .expect("failed to create tag3");
-    let tag4 = service
-        .get_or_create_tag("attention")
-        .expect("failed to create tag4");
+    assert_eq!(service.detect_alias_opportunity("ml").unwrap(), None);
+    assert_eq!(service.detect_alias_opportunity("rust").unwrap(), None);
+    assert_eq!(service.detect_alias_opportunity("").unwrap(), None);
+}
 
-    // Verify all tags start with degree_centrality = 0
-    for tag_id in [tag1, tag2, tag3, tag4] {
-        let centrality: i32 = service
-            .db
-            .connection()
-            .query_row(
-                "SELECT degree_centrality FROM tags WHERE id = ?1",
-                [tag_id.get()],
-                |row| row.get(0),
-            )
-            .expect("failed to query centrality");
-        assert_eq!(centrality, 0, "tag should start with centrality 0");
-    }
+// --- Graph Search Explanation Tests ---
 
-    // Create batch of edges:
-    // tag1 -> tag2 (tag1: 1, tag2: 1)
-    // tag2 -> tag3 (tag1: 1, tag2: 2, tag3: 1)
-    // tag3 -> tag4 (tag1: 1, tag2: 2, tag3: 2, tag4: 1)
-    let edges = vec![
-        (tag1, tag2, 0.9, "generic", Some("test-model")),
-        (tag2, tag3, 0.8, "generic", Some("test-model")),
-        (tag3, tag4, 0.85, "partitive", Some("test-model")),
-    ];
+#[test]
+fn graph_search_explained_reports_paths_to_note_tags() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-    let count = service
-        .create_edges_batch(&edges)
-        .expect("batch edge creation should succeed");
+    let tokio = service
+        .get_or_create_tag("tokio")
+        .expect("failed to create tag");
+    let rust = service
+        .get_or_create_tag("rust")
+        .expect("failed to create tag");
+    service
+        .create_edge(tokio, rust, 0.9, "generic", None)
+        .expect("failed to create edge");
+    let note = service
+        .create_note("Ownership and borrowing", Some(&["rust"]))
+        .expect("failed to create note");
 
-    assert_eq!(count, 3, "should create 3 edges");
+    let plain = service.graph_search("tokio", None).expect("search failed");
+    assert_eq!(plain.len(), 1);
+    assert!(plain[0].explanation.is_none(), "only explained on request");
 
-    // Verify degree_centrality was updated correctly for all tags
-    let tag1_centrality: i32 = service
-        .db
-        .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag1.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag1 centrality");
-    assert_eq!(
-        tag1_centrality, 1,
-        "tag1 has 1 edge (tag1->tag2), centrality should be 1"
+    let results = service
+        .graph_search_explained("tokio", None)
+        .expect("search failed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].note.id(), note.id());
+    assert_eq!(results[0].relevance_score, plain[0].relevance_score);
+
+    let paths = results[0]
+        .explanation
+        .as_ref()
+        .expect("explanation should be set");
+    assert!(!paths.is_empty() && paths.len() <= 3);
+    let best = &paths[0];
+    assert_eq!(best.steps.first().unwrap().tag_name, "tokio");
+    assert_eq!(best.steps.last().unwrap().tag_id, rust);
+    assert!((best.activation() - 0.9 * 0.7).abs() < 1e-9);
+    assert!(
+        paths
+            .iter()
+            .all(|path| path.steps.last().unwrap().tag_id == rust),
+        "paths end at a tag on the note"
     );
+}
 
-    let tag2_centrality: i32 = service
-        .db
-        .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag2.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag2 centrality");
-    assert_eq!(
-        tag2_centrality, 2,
-        "tag2 has 2 edges (tag1->tag2, tag2->tag3), centrality should be 2"
-    );
+// --- Tag Co-occurrence Tests ---
 
-    let tag3_centrality: i32 = service
-        .db
-        .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag3.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag3 centrality");
+#[test]
+fn tag_cooccurrence_counts_pairs_and_filters_by_min_count() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+
+    for tags in [
+        &["rust", "tokio", "async"][..],
+        &["tokio", "rust"][..],
+        &["rust", "tokio"][..],
+        &["async", "python"][..],
+        &["async", "rust"][..],
+        &["solo"][..],
+    ] {
+        service
+            .create_note("note", Some(tags))
+            .expect("failed to create note");
+    }
+
+    let pairs = service.tag_cooccurrence(1).expect("cooccurrence failed");
     assert_eq!(
-        tag3_centrality, 2,
-        "tag3 has 2 edges (tag2->tag3, tag3->tag4), centrality should be 2"
+        pairs,
+        vec![
+            ("rust".to_string(), "tokio".to_string(), 3),
+            ("async".to_string(), "rust".to_string(), 2),
+            ("async".to_string(), "python".to_string(), 1),
+            ("async".to_string(), "tokio".to_string(), 1),
+        ]
     );
 
-    let tag4_centrality: i32 = service
-        .db
-        .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [tag4.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query tag4 centrality");
-    assert_eq!(
-        tag4_centrality, 1,
-        "tag4 has 1 edge (tag3->tag4), centrality should be 1"
+    let frequent = service.tag_cooccurrence(2).expect("cooccurrence failed");
+    assert_eq!(frequent.len(), 2);
+    assert!(frequent.iter().all(|(_, _, count)| *count >= 2));
+
+    assert!(
+        service
+            .tag_cooccurrence(4)
+            .expect("cooccurrence failed")
+            .is_empty()
     );
 }
 
+// --- Enhancement Prompt Tests ---
+
 #[test]
-fn dual_search_centrality_boost_affects_final_ranking() {
-    // Integration test: Verify degree centrality boost affects dual search results
-    // Tests full end-to-end workflow: Notes -> Tags -> Edges -> Graph Search -> Dual Search
+fn enhancement_prompt_round_trips_and_is_cleared_on_edit() {
     let db = Database::in_memory().expect("failed to create in-memory database");
     let service = NoteService::new(db);
+    let note = service
+        .create_note("buy milk", None)
+        .expect("failed to create note");
 
-    // Create a hub tag with high degree centrality
-    let hub_tag = service
-        .get_or_create_tag("machine-learning")
-        .expect("failed to create hub tag");
+    let prompt = "Expand this note.\n\nNOTE:\nbuy milk";
+    service
+        .update_note_enhancement(
+            note.id(),
+            "Buy milk from the store.",
+            "deepseek-r1:8b",
+            0.85,
+            OffsetDateTime::now_utc(),
+            Some(prompt),
+        )
+        .expect("failed to update note enhancement");
 
-    // Create connected tags to establish high centrality for hub_tag
-    let tag1 = service
-        .get_or_create_tag("neural-networks")
-        .expect("failed to create tag1");
-    let tag2 = service
-        .get_or_create_tag("deep-learning")
-        .expect("failed to create tag2");
-    let tag3 = service
-        .get_or_create_tag("supervised-learning")
-        .expect("failed to create tag3");
+    let stored = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(stored.enhancement_prompt(), Some(prompt));
 
-    // Create edges to make hub_tag have degree_centrality = 3
-    service
-        .create_edge(hub_tag, tag1, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 1");
     service
-        .create_edge(hub_tag, tag2, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 2");
+        .update_note_content(note.id(), "buy oat milk")
+        .expect("failed to update content");
+    let edited = service
+        .get_note(note.id())
+        .expect("failed to get note")
+        .expect("note should exist");
+    assert_eq!(edited.enhancement_prompt(), None);
+}
+
+// --- FTS Consistency Tests ---
+
+#[test]
+fn fts_is_consistent_detects_missing_rows_until_rebuilt() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let note = service
+        .create_note("Rust ownership rules", Some(&["rust"]))
+        .expect("failed to create note");
     service
-        .create_edge(hub_tag, tag3, 1.0, "generic", Some("test-model"))
-        .expect("failed to create edge 3");
+        .create_note("Borrow checker", None)
+        .expect("failed to create note");
+    assert!(service.fts_is_consistent().expect("check failed"));
 
-    // Verify centrality
-    let hub_centrality: i32 = service
-        .db
+    service
+        .database()
         .connection()
-        .query_row(
-            "SELECT degree_centrality FROM tags WHERE id = ?1",
-            [hub_tag.get()],
-            |row| row.get(0),
-        )
-        .expect("failed to query centrality");
-    assert_eq!(hub_centrality, 3);
-
-    // Create notes that will be found via different channels
-    let hub_note = service
-        .create_note(
-            "Machine learning fundamentals with neural networks",
-            Some(&["machine-learning", "neural-networks"]),
+        .execute(
+            "DELETE FROM notes_fts WHERE note_id = ?1",
+            [note.id().get()],
         )
-        .expect("failed to create hub note");
-
-    let _other_note = service
-        .create_note("Introduction to algorithms", Some(&["supervised-learning"]))
-        .expect("failed to create other note");
+        .expect("failed to delete FTS row");
+    assert!(!service.fts_is_consistent().expect("check failed"));
 
-    // Run dual search for "machine learning"
-    // This should:
-    // 1. Find hub_note via FTS (content match)
-    // 2. Find hub_note via graph search (tag match with centrality boost)
-    // 3. Find other_note via graph search (connected via edges)
-    let (results, _metadata) = service
-        .dual_search("machine learning", Some(10))
-        .expect("dual search should succeed");
+    service.rebuild_fts().expect("rebuild failed");
+    assert!(service.fts_is_consistent().expect("check failed"));
+}
 
-    assert!(!results.is_empty(), "should find notes");
+#[test]
+fn fts_is_consistent_detects_rows_for_the_wrong_note() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
+    let note = service
+        .create_note("Rust ownership rules", None)
+        .expect("failed to create note");
 
-    // Verify hub_note benefits from centrality boost in graph scoring
-    let hub_result = results.iter().find(|r| r.note.id() == hub_note.id());
+    // Same row count, but the search row no longer points at the note
+    service
+        .database()
+        .connection()
+        .execute(
+            "UPDATE notes_fts SET note_id = ?1 WHERE note_id = ?2",
+            [note.id().get() + 100, note.id().get()],
+        )
+        .expect("failed to update FTS row");
+    assert!(!service.fts_is_consistent().expect("check failed"));
+}
 
-    if let Some(hub_result) = hub_result {
-        // Hub note should be found
-        println!(
-            "Hub note - FTS: {:?}, Graph: {:?}, Final: {}",
-            hub_result.fts_score, hub_result.graph_score, hub_result.final_score
-        );
+// --- Untagged Notes Tests ---
 
-        // If found by graph channel, verify it has a graph score
-        if let Some(graph_score) = hub_result.graph_score {
-            assert!(
-                graph_score > 0.0,
-                "hub note should have positive graph score due to centrality boost"
-            );
-        }
+#[test]
+fn notes_without_tags_returns_only_untagged_notes() {
+    let db = Database::in_memory().expect("failed to create in-memory database");
+    let service = NoteService::new(db);
 
-        // The centrality boost should contribute to higher final ranking
-        assert!(
-            hub_result.final_score > 0.0,
-            "hub note should have positive final score"
-        );
-    } else {
-        // If not found, that's acceptable as dual search may filter differently
-        println!("Hub note not in top results (this is acceptable)");
-    }
+    service
+        .create_note("Tagged note", Some(&["rust"]))
+        .expect("failed to create note");
+    let first = service
+        .create_note("First stray note", None)
+        .expect("failed to create note");
+    let second = service
+        .create_note("Second stray note", None)
+        .expect("failed to create note");
+    let detagged = service
+        .create_note("Tag removed later", Some(&["temp"]))
+        .expect("failed to create note");
+    service
+        .remove_tag_from_note(detagged.id(), "temp")
+        .expect("failed to remove tag");
 
-    // Main assertion: verify that the dual search completed successfully
-    // and integrated centrality boost into the scoring pipeline
-    assert!(
-        results.len() > 0,
-        "dual search should return results with centrality-boosted graph scores"
-    );
+    let untagged = service
+        .notes_without_tags(ListNotesOptions {
+            order: SortOrder::Ascending,
+            ..Default::default()
+        })
+        .expect("failed to list untagged notes");
+    let ids: Vec<NoteId> = untagged.iter().map(|note| note.id()).collect();
+    assert_eq!(ids, vec![first.id(), second.id(), detagged.id()]);
+    assert!(untagged.iter().all(|note| note.tags().is_empty()));
+
+    let limited = service
+        .notes_without_tags(ListNotesOptions {
+            limit: Some(1),
+            ..Default::default()
+        })
+        .expect("failed to list untagged notes");
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].id(), detagged.id());
+
+    let counted = service
+        .count_notes(&ListNotesOptions {
+            untagged_only: true,
+            ..Default::default()
+        })
+        .expect("failed to count notes");
+    assert_eq!(counted, 3);
 }
@@ -4,44 +4,76 @@
 /// the tag hierarchy graph, propagating activation scores through edges
 /// to surface semantically related notes.
 use anyhow::Result;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension, ToSql};
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::TagId;
+use crate::config::Config;
 
 /// Configuration for spreading activation algorithm.
 ///
-/// Parsed from environment variables at method call time with fallback defaults.
+/// Built from the [`Config`] a `NoteService` holds, or from the environment via `from_env`.
 #[derive(Debug, Clone)]
 pub struct SpreadingActivationConfig {
     /// Activation decay per hop (default 0.7).
     pub decay_factor: f64,
     /// Minimum activation to continue spreading (default 0.1).
     pub threshold: f64,
-    /// Maximum traversal depth (default 3).
+    /// Maximum traversal depth (default 3, clamped to 1-6 when read from env).
+    ///
+    /// Higher values trade latency for recall: each extra hop widens the
+    /// recursive CTE and can surface more loosely related notes.
     pub max_hops: usize,
+    /// Multiplier for activation crossing a generic (is-a) edge (default 1.0).
+    pub generic_weight: f64,
+    /// Multiplier for activation crossing a partitive (part-of) edge (default 0.5).
+    pub partitive_weight: f64,
+    /// Extra multiplier for edges the user has verified (default 1.0, no boost).
+    pub verified_multiplier: f64,
 }
 
+/// Smallest accepted `CONS_MAX_HOPS` value.
+pub const MIN_MAX_HOPS: usize = 1;
+
+/// Largest accepted `CONS_MAX_HOPS` value.
+pub const MAX_MAX_HOPS: usize = 6;
+
 impl Default for SpreadingActivationConfig {
     fn default() -> Self {
+        Self::from(&Config::default())
+    }
+}
+
+impl From<&Config> for SpreadingActivationConfig {
+    fn from(config: &Config) -> Self {
         Self {
-            decay_factor: 0.7,
-            threshold: 0.1,
-            max_hops: 3,
+            decay_factor: config.decay,
+            threshold: config.threshold,
+            max_hops: config.max_hops,
+            generic_weight: config.generic_weight,
+            partitive_weight: config.partitive_weight,
+            verified_multiplier: config.verified_multiplier,
         }
     }
 }
 
 impl SpreadingActivationConfig {
-    /// Parses configuration from environment variables.
+    /// Reads configuration from environment variables via [`Config::load`].
     ///
     /// Falls back to defaults when env vars not set or invalid.
     ///
     /// # Environment Variables
     ///
-    /// - `CONS_DECAY` (f64, default 0.7): Activation decay per hop
+    /// - `CONS_DECAY` (f64, default 0.7, clamped to 0-1): Activation decay per hop
     /// - `CONS_THRESHOLD` (f64, default 0.1): Minimum activation to continue spreading
-    /// - `CONS_MAX_HOPS` (usize, default 3): Maximum traversal depth
+    /// - `CONS_MAX_HOPS` (usize, default 3, clamped to 1-6): Maximum traversal depth.
+    ///   Higher values trade latency for recall.
+    /// - `CONS_GENERIC_WEIGHT` (f64, default 1.0): Multiplier for generic edges
+    /// - `CONS_PARTITIVE_WEIGHT` (f64, default 0.5): Multiplier for partitive edges
+    /// - `CONS_VERIFIED_MULTIPLIER` (f64, default 1.0): Extra multiplier for verified edges
+    ///
+    /// Negative weights clamp to 0.
     ///
     /// # Examples
     ///
@@ -52,26 +84,7 @@ impl SpreadingActivationConfig {
     /// assert_eq!(config.decay_factor, 0.7); // default when env var not set
     /// ```
     pub fn from_env() -> Self {
-        let decay_factor = std::env::var("CONS_DECAY")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0.7);
-
-        let threshold = std::env::var("CONS_THRESHOLD")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0.1);
-
-        let max_hops = std::env::var("CONS_MAX_HOPS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(3);
-
-        Self {
-            decay_factor,
-            threshold,
-            max_hops,
-        }
+        Self::from(&Config::load())
     }
 }
 
@@ -85,7 +98,9 @@ impl SpreadingActivationConfig {
 /// 1. Seed CTE with initial activation 1.0 for seed tags
 /// 2. Traverse edges bidirectionally (source->target and target->source)
 /// 3. Apply formula: `activation_next = activation_current * edge.confidence * decay_factor * edge_type_multiplier`
-/// 4. Edge type multiplier: generic=1.0, partitive=0.5
+/// 4. Edge type multiplier: `config.generic_weight` (default 1.0) or
+///    `config.partitive_weight` (default 0.5), times `config.verified_multiplier`
+///    (default 1.0) when the user has verified the edge
 /// 5. Prune nodes below activation threshold
 /// 6. Limit traversal with max_hops parameter
 /// 7. Accumulate scores with SUM when tag receives activation from multiple paths
@@ -133,38 +148,9 @@ pub fn spread_activation(
         })?
         .unwrap_or(0) as f64;
 
-    // Build VALUES clause for seed tags
-    let seed_values: Vec<String> = seed_tags
-        .iter()
-        .map(|(tag_id, activation)| format!("({}, {}, 0)", tag_id.get(), activation))
-        .collect();
-    let seed_values_clause = seed_values.join(", ");
-
     let query = format!(
         r#"
-        WITH RECURSIVE activation_spread(tag_id, activation, hop_count) AS (
-            -- Base case: seed tags with initial activation
-            SELECT * FROM (VALUES {seed_values})
-
-            UNION ALL
-
-            -- Recursive case: spread activation through edges
-            SELECT
-                CASE
-                    -- Forward traversal (source -> target)
-                    WHEN e.source_tag_id = a.tag_id THEN e.target_tag_id
-                    -- Backward traversal (target -> source)
-                    ELSE e.source_tag_id
-                END AS tag_id,
-                a.activation * e.confidence * ?1 *
-                    CASE WHEN e.hierarchy_type = 'partitive' THEN 0.5 ELSE 1.0 END AS activation,
-                a.hop_count + 1 AS hop_count
-            FROM activation_spread a
-            JOIN edges e ON (e.source_tag_id = a.tag_id OR e.target_tag_id = a.tag_id)
-            WHERE a.hop_count < ?2
-              AND a.activation * e.confidence * ?1 *
-                  CASE WHEN e.hierarchy_type = 'partitive' THEN 0.5 ELSE 1.0 END >= ?3
-        )
+        {cte}
         SELECT
             a.tag_id,
             SUM(a.activation) as total_activation,
@@ -173,19 +159,16 @@ pub fn spread_activation(
         LEFT JOIN tags t ON a.tag_id = t.id
         GROUP BY a.tag_id
         "#,
-        seed_values = seed_values_clause
+        cte = activation_cte(seed_tags, false)
     );
 
     let mut stmt = conn.prepare(&query)?;
-    let rows = stmt.query_map(
-        rusqlite::params![config.decay_factor, config.max_hops, config.threshold],
-        |row| {
-            let tag_id: i64 = row.get(0)?;
-            let activation: f64 = row.get(1)?;
-            let degree_centrality: i64 = row.get(2)?;
-            Ok((TagId::new(tag_id), activation, degree_centrality))
-        },
-    )?;
+    let rows = stmt.query_map(activation_params(config), |row| {
+        let tag_id: i64 = row.get(0)?;
+        let activation: f64 = row.get(1)?;
+        let degree_centrality: i64 = row.get(2)?;
+        Ok((TagId::new(tag_id), activation, degree_centrality))
+    })?;
 
     let mut result = HashMap::new();
     for row_result in rows {
@@ -205,6 +188,210 @@ pub fn spread_activation(
     Ok(result)
 }
 
+/// One tag reached along an [`ActivationPath`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivationStep {
+    /// The tag reached at this step.
+    pub tag_id: TagId,
+    /// The tag's name, for display.
+    pub tag_name: String,
+    /// Activation arriving at the tag along this path, before the centrality boost.
+    pub activation: f64,
+}
+
+/// A route activation took from a seed tag to an activated tag.
+///
+/// The first step is the seed with its initial activation; each later step
+/// crossed one edge, so its activation has been decayed and weighted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivationPath {
+    /// Tags along the route, starting with the seed tag.
+    pub steps: Vec<ActivationStep>,
+}
+
+impl ActivationPath {
+    /// Returns the activation this path delivers to its final tag.
+    pub fn activation(&self) -> f64 {
+        self.steps.last().map_or(0.0, |step| step.activation)
+    }
+}
+
+impl fmt::Display for ActivationPath {
+    /// Formats the path as `rust (1.00) -> programming (0.70)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{} ({:.2})", step.tag_name, step.activation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records every path along which [`spread_activation`] reached each tag.
+///
+/// Runs the same traversal with predecessor links kept, so the returned paths
+/// explain the activation scores. Paths for each tag are ordered by the
+/// activation they deliver, strongest first.
+///
+/// # Examples
+///
+/// ```
+/// use cons::{Database, NoteService};
+/// use cons::spreading_activation::{SpreadingActivationConfig, activation_paths};
+/// use std::collections::HashMap;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let service = NoteService::new(Database::in_memory()?);
+/// let tokio = service.get_or_create_tag("tokio")?;
+/// let rust = service.get_or_create_tag("rust")?;
+/// service.create_edge(tokio, rust, 1.0, "generic", None)?;
+///
+/// let seeds = HashMap::from([(tokio, 1.0)]);
+/// let config = SpreadingActivationConfig::default();
+/// let paths = activation_paths(service.database().connection(), &seeds, &config)?;
+/// assert_eq!(paths[&rust][0].to_string(), "tokio (1.00) -> rust (0.70)");
+/// # Ok(())
+/// # }
+/// ```
+pub fn activation_paths(
+    conn: &Connection,
+    seed_tags: &HashMap<TagId, f64>,
+    config: &SpreadingActivationConfig,
+) -> Result<HashMap<TagId, Vec<ActivationPath>>> {
+    if seed_tags.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let query = format!(
+        "{cte} SELECT tag_id, path, trail FROM activation_spread",
+        cte = activation_cte(seed_tags, true)
+    );
+
+    let mut stmt = conn.prepare(&query)?;
+    let rows = stmt.query_map(activation_params(config), |row| {
+        Ok((
+            TagId::new(row.get(0)?),
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut name_stmt = conn.prepare("SELECT name FROM tags WHERE id = ?1")?;
+    let mut names: HashMap<i64, String> = HashMap::new();
+    let mut result: HashMap<TagId, Vec<ActivationPath>> = HashMap::new();
+
+    for row_result in rows {
+        let (tag_id, path, trail) = row_result?;
+
+        let mut steps = Vec::new();
+        for (id, activation) in path.split(',').zip(trail.split(',')) {
+            let id: i64 = id.parse()?;
+            let tag_name = match names.get(&id) {
+                Some(name) => name.clone(),
+                None => {
+                    let name: String = name_stmt
+                        .query_row([id], |row| row.get(0))
+                        .optional()?
+                        .unwrap_or_else(|| format!("tag {id}"));
+                    names.insert(id, name.clone());
+                    name
+                }
+            };
+            steps.push(ActivationStep {
+                tag_id: TagId::new(id),
+                tag_name,
+                activation: activation.parse()?,
+            });
+        }
+
+        result
+            .entry(tag_id)
+            .or_default()
+            .push(ActivationPath { steps });
+    }
+
+    for paths in result.values_mut() {
+        paths.sort_by(|a, b| b.activation().total_cmp(&a.activation()));
+    }
+
+    Ok(result)
+}
+
+/// Builds the recursive `activation_spread` CTE shared by the traversal queries.
+///
+/// Binds `?1` decay, `?2` max hops, `?3` threshold and `?4`-`?6` edge weights
+/// (see [`activation_params`]). With `record_paths`, each row also carries the
+/// comma-separated tag ids (`path`) and activations (`trail`) that led to it.
+fn activation_cte(seed_tags: &HashMap<TagId, f64>, record_paths: bool) -> String {
+    // Build VALUES clause for seed tags
+    let seed_values: Vec<String> = seed_tags
+        .iter()
+        .map(|(tag_id, activation)| {
+            if record_paths {
+                format!(
+                    "({id}, {activation}, 0, '{id}', '{activation}')",
+                    id = tag_id.get()
+                )
+            } else {
+                format!("({}, {}, 0)", tag_id.get(), activation)
+            }
+        })
+        .collect();
+
+    // Next tag: forward traversal (source -> target), else backward (target -> source)
+    let next_tag =
+        "CASE WHEN e.source_tag_id = a.tag_id THEN e.target_tag_id ELSE e.source_tag_id END";
+    let step_activation = "a.activation * e.confidence * ?1 *
+                    CASE WHEN e.hierarchy_type = 'partitive' THEN ?4 ELSE ?5 END *
+                    CASE WHEN e.verified = 1 THEN ?6 ELSE 1.0 END";
+    let (path_columns, path_select) = if record_paths {
+        (
+            ", path, trail",
+            format!(
+                ",\n                a.path || ',' || {next_tag},\n                a.trail || ',' || ({step_activation})"
+            ),
+        )
+    } else {
+        ("", String::new())
+    };
+
+    format!(
+        r#"
+        WITH RECURSIVE activation_spread(tag_id, activation, hop_count{path_columns}) AS (
+            -- Base case: seed tags with initial activation
+            SELECT * FROM (VALUES {seed_values})
+
+            UNION ALL
+
+            -- Recursive case: spread activation through edges
+            SELECT
+                {next_tag} AS tag_id,
+                {step_activation} AS activation,
+                a.hop_count + 1 AS hop_count{path_select}
+            FROM activation_spread a
+            JOIN edges e ON (e.source_tag_id = a.tag_id OR e.target_tag_id = a.tag_id)
+            WHERE a.hop_count < ?2
+              AND {step_activation} >= ?3
+        )
+        "#,
+        seed_values = seed_values.join(", ")
+    )
+}
+
+/// Returns the parameters bound by [`activation_cte`].
+fn activation_params(config: &SpreadingActivationConfig) -> [&dyn ToSql; 6] {
+    [
+        &config.decay_factor,
+        &config.max_hops,
+        &config.threshold,
+        &config.partitive_weight,
+        &config.generic_weight,
+        &config.verified_multiplier,
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,6 +439,7 @@ mod tests {
             decay_factor: 0.7,
             threshold: 0.1,
             max_hops: 3,
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -308,6 +496,7 @@ mod tests {
             decay_factor: 0.5,
             threshold: 0.05,
             max_hops: 3,
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -359,6 +548,7 @@ mod tests {
             decay_factor: 0.5,
             threshold: 0.3, // High threshold should stop at tag 2
             max_hops: 3,
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -407,6 +597,7 @@ mod tests {
             decay_factor: 0.9,
             threshold: 0.01,
             max_hops: 2, // Limit to 2 hops
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -460,6 +651,7 @@ mod tests {
             decay_factor: 0.5,
             threshold: 0.1,
             max_hops: 3,
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -496,6 +688,7 @@ mod tests {
             decay_factor: 1.0, // No decay to isolate edge type effect
             threshold: 0.01,
             max_hops: 3,
+            ..Default::default()
         };
 
         // Test generic edge
@@ -521,6 +714,87 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_edge_type_weights_are_configurable() -> Result<()> {
+        let db = setup_test_db()?;
+        let conn = db.connection();
+
+        // Same parallel chains as above: 1 -> 2 (generic), 3 -> 4 (partitive)
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type)
+             VALUES (1, 2, 1.0, 'generic')",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type)
+             VALUES (3, 4, 1.0, 'partitive')",
+            [],
+        )?;
+
+        let config = SpreadingActivationConfig {
+            decay_factor: 1.0,
+            threshold: 0.01,
+            max_hops: 1,
+            partitive_weight: 1.0,
+            ..Default::default()
+        };
+
+        let mut seed_tags_generic = HashMap::new();
+        seed_tags_generic.insert(TagId::new(1), 1.0);
+        let activated_generic = spread_activation(conn, &seed_tags_generic, &config)?;
+        let generic_activation = activated_generic.get(&TagId::new(2)).unwrap();
+
+        let mut seed_tags_partitive = HashMap::new();
+        seed_tags_partitive.insert(TagId::new(3), 1.0);
+        let activated_partitive = spread_activation(conn, &seed_tags_partitive, &config)?;
+        let partitive_activation = activated_partitive.get(&TagId::new(4)).unwrap();
+
+        // With equal weights the edge type no longer matters
+        assert!((partitive_activation - generic_activation).abs() < 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verified_multiplier_boosts_only_verified_edges() -> Result<()> {
+        let db = setup_test_db()?;
+        let conn = db.connection();
+
+        // Two parallel generic chains, only 3 -> 4 verified by the user
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type)
+             VALUES (1, 2, 0.5, 'generic')",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type, verified)
+             VALUES (3, 4, 0.5, 'generic', 1)",
+            [],
+        )?;
+
+        let config = SpreadingActivationConfig {
+            decay_factor: 1.0,
+            threshold: 0.01,
+            max_hops: 1,
+            verified_multiplier: 2.0,
+            ..Default::default()
+        };
+
+        let mut seed_tags_plain = HashMap::new();
+        seed_tags_plain.insert(TagId::new(1), 1.0);
+        let activated_plain = spread_activation(conn, &seed_tags_plain, &config)?;
+        let plain_activation = activated_plain.get(&TagId::new(2)).unwrap();
+
+        let mut seed_tags_verified = HashMap::new();
+        seed_tags_verified.insert(TagId::new(3), 1.0);
+        let activated_verified = spread_activation(conn, &seed_tags_verified, &config)?;
+        let verified_activation = activated_verified.get(&TagId::new(4)).unwrap();
+
+        assert!((verified_activation / plain_activation - 2.0).abs() < 1e-9);
+
+        Ok(())
+    }
+
     #[test]
     fn test_high_degree_tag_receives_centrality_boost() -> Result<()> {
         let db = setup_test_db()?;
@@ -559,6 +833,7 @@ mod tests {
             decay_factor: 1.0, // No decay to isolate boost effect
             threshold: 0.01,
             max_hops: 1,
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -598,6 +873,7 @@ mod tests {
             decay_factor: 1.0,
             threshold: 0.01,
             max_hops: 0, // No hops to isolate the seed tag
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -664,6 +940,7 @@ mod tests {
             decay_factor: 1.0,
             threshold: 0.01,
             max_hops: 0, // No spreading to isolate boost calculation
+            ..Default::default()
         };
 
         let activated = spread_activation(conn, &seed_tags, &config)?;
@@ -679,4 +956,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_activation_paths_record_route_and_values() -> Result<()> {
+        let db = setup_test_db()?;
+        let conn = db.connection();
+
+        // rust -> programming (generic), programming -> systems (partitive)
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type)
+             VALUES (1, 2, 1.0, 'generic')",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO edges (source_tag_id, target_tag_id, confidence, hierarchy_type)
+             VALUES (2, 3, 1.0, 'partitive')",
+            [],
+        )?;
+
+        let mut seed_tags = HashMap::new();
+        seed_tags.insert(TagId::new(1), 1.0);
+
+        let config = SpreadingActivationConfig {
+            decay_factor: 0.5,
+            threshold: 0.1,
+            max_hops: 2,
+            ..Default::default()
+        };
+
+        let paths = activation_paths(conn, &seed_tags, &config)?;
+
+        let systems = &paths[&TagId::new(3)];
+        assert_eq!(systems.len(), 1);
+        let names: Vec<&str> = systems[0]
+            .steps
+            .iter()
+            .map(|step| step.tag_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["rust", "programming", "systems"]);
+        let activations: Vec<f64> = systems[0].steps.iter().map(|s| s.activation).collect();
+        assert!((activations[0] - 1.0).abs() < 1e-9);
+        assert!((activations[1] - 0.5).abs() < 1e-9);
+        assert!((activations[2] - 0.125).abs() < 1e-9); // 0.5 * 0.5 decay * 0.5 partitive
+        assert_eq!(
+            systems[0].to_string(),
+            "rust (1.00) -> programming (0.50) -> systems (0.12)"
+        );
+
+        // The seed is reached directly and again by bouncing back from programming
+        let rust = &paths[&TagId::new(1)];
+        assert_eq!(rust.len(), 2);
+        assert_eq!(rust[0].steps.len(), 1, "strongest path first");
+        assert!(rust[0].activation() > rust[1].activation());
+
+        // Every path's final value adds up to the unboosted activation
+        let activated = spread_activation(conn, &seed_tags, &config)?;
+        let programming_total: f64 = paths[&TagId::new(2)].iter().map(|p| p.activation()).sum();
+        assert!((activated[&TagId::new(2)] - programming_total).abs() < 1e-9);
+
+        Ok(())
+    }
 }
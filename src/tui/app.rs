@@ -1,6 +1,12 @@
 use std::time::Instant;
 
-use crate::models::Note;
+use anyhow::{Context, Result};
+
+use crate::models::{Note, NoteId};
+use crate::service::{ListNotesOptions, NoteService, SortOrder};
+
+/// Number of notes fetched per page, for the initial load and each [`App::load_more`].
+pub(crate) const NOTE_PAGE_SIZE: usize = 50;
 
 /// Application state for the TUI.
 ///
@@ -21,8 +27,36 @@ pub struct App {
     search_changed_at: Option<Instant>,
     /// Whether we need to run a search (filter changed but not yet searched)
     search_pending: bool,
+    /// Whether the search was submitted with Enter (runs without waiting for the debounce)
+    search_submitted: bool,
+    /// Whether recent notes should be reloaded from the database (query was cleared)
+    reload_requested: bool,
     /// Scroll offset for detail view
     detail_scroll: u16,
+    /// Largest detail scroll offset for the rendered note (None until first measured)
+    detail_max_scroll: Option<u16>,
+    /// Tags with their note counts, shown in the tag overlay
+    tags: Vec<(String, usize)>,
+    /// Currently selected tag index in the tag overlay (None if no selection)
+    selected_tag_index: Option<usize>,
+    /// Whether the tag overlay is shown (toggled with `t`)
+    tag_panel_open: bool,
+    /// Tag chosen in the overlay whose notes the event loop should load
+    tag_filter_requested: Option<String>,
+    /// Whether the keybinding help overlay is shown (toggled with `?`)
+    show_help: bool,
+    /// Note awaiting a yes/no delete confirmation (modal prompt is open)
+    delete_prompt: Option<NoteId>,
+    /// Note whose deletion was confirmed but not yet carried out by the event loop
+    delete_requested: Option<NoteId>,
+    /// Whether the displayed list is the unfiltered recent notes (older ones can be loaded)
+    showing_all_notes: bool,
+    /// Whether older notes may exist in the database (false once a fetch comes back empty)
+    has_more_notes: bool,
+    /// Whether the event loop should load older notes (selection moved past the top)
+    load_more_requested: bool,
+    /// Error shown in place of the shortcut bar until the next key press
+    status_message: Option<String>,
 }
 
 /// Panel focus state for keyboard navigation.
@@ -36,6 +70,8 @@ pub enum Focus {
     NoteList,
     /// Detail view panel is focused (for future scrolling support)
     DetailView,
+    /// Tag overlay is focused (j/k navigation, Enter to filter notes by tag)
+    TagList,
 }
 
 impl App {
@@ -62,7 +98,21 @@ impl App {
             focus: Focus::SearchInput,
             search_changed_at: None,
             search_pending: false,
+            search_submitted: false,
+            reload_requested: false,
             detail_scroll: 0,
+            detail_max_scroll: None,
+            tags: Vec::new(),
+            selected_tag_index: None,
+            tag_panel_open: false,
+            tag_filter_requested: None,
+            show_help: false,
+            delete_prompt: None,
+            delete_requested: None,
+            showing_all_notes: true,
+            has_more_notes: false,
+            load_more_requested: false,
+            status_message: None,
         }
     }
 
@@ -94,11 +144,16 @@ impl App {
     /// Sets the notes list and resets selection to None.
     ///
     /// Used when loading notes from database. Stores notes in both
-    /// `all_notes` (for filtering) and `notes` (for display).
+    /// `all_notes` (for filtering) and `notes` (for display). A full page of
+    /// notes means older ones may exist, so [`load_more`](Self::load_more) is enabled.
     pub fn set_notes(&mut self, notes: Vec<Note>) {
+        self.has_more_notes = notes.len() >= NOTE_PAGE_SIZE;
+        self.load_more_requested = false;
         self.all_notes = notes.clone();
         self.notes = notes;
+        self.showing_all_notes = true;
         self.selected_index = None;
+        self.detail_scroll = 0;
         // Apply current filter if any
         if !self.search_input.is_empty() {
             self.apply_filter();
@@ -112,6 +167,7 @@ impl App {
     pub fn apply_filter(&mut self) {
         let query = self.search_input.to_lowercase();
 
+        self.showing_all_notes = query.is_empty();
         if query.is_empty() {
             // No filter - show all notes
             self.notes = self.all_notes.clone();
@@ -127,6 +183,7 @@ impl App {
 
         // Reset selection when filter changes
         self.selected_index = None;
+        self.detail_scroll = 0;
     }
 
     /// Returns the currently selected note, if any.
@@ -154,7 +211,8 @@ impl App {
 
     /// Cycles focus to the next panel in Tab order.
     ///
-    /// Order: `SearchInput` -> `NoteList` -> `DetailView` -> `SearchInput`
+    /// Order: `SearchInput` -> `NoteList` -> `DetailView` -> `SearchInput`,
+    /// with `TagList` after `DetailView` while the tag overlay is open.
     ///
     /// # Examples
     ///
@@ -177,14 +235,16 @@ impl App {
         self.focus = match self.focus {
             Focus::SearchInput => Focus::NoteList,
             Focus::NoteList => Focus::DetailView,
-            Focus::DetailView => Focus::SearchInput,
+            Focus::DetailView if self.tag_panel_open => Focus::TagList,
+            Focus::DetailView | Focus::TagList => Focus::SearchInput,
         };
         self.auto_select_on_note_list_focus();
     }
 
     /// Cycles focus to the previous panel in reverse Tab order.
     ///
-    /// Order: `SearchInput` -> `DetailView` -> `NoteList` -> `SearchInput`
+    /// Order: `SearchInput` -> `DetailView` -> `NoteList` -> `SearchInput`,
+    /// with `TagList` before `DetailView` while the tag overlay is open.
     ///
     /// # Examples
     ///
@@ -205,7 +265,8 @@ impl App {
     /// ```
     pub fn prev_focus(&mut self) {
         self.focus = match self.focus {
-            Focus::SearchInput => Focus::DetailView,
+            Focus::SearchInput if self.tag_panel_open => Focus::TagList,
+            Focus::SearchInput | Focus::TagList => Focus::DetailView,
             Focus::NoteList => Focus::SearchInput,
             Focus::DetailView => Focus::NoteList,
         };
@@ -217,6 +278,7 @@ impl App {
         if self.focus == Focus::NoteList && self.selected_index.is_none() && !self.notes.is_empty()
         {
             self.selected_index = Some(0);
+            self.detail_scroll = 0;
         }
     }
 
@@ -271,7 +333,9 @@ impl App {
     /// Moves selection up in the notes list (k key navigation).
     ///
     /// If no selection, selects last note.
-    /// If at beginning of list, wraps to end.
+    /// If at beginning of list, wraps to end, unless older notes may exist:
+    /// then the selection stays put and the event loop is asked to
+    /// [`load_more`](Self::load_more) (see [`take_load_more_request`](Self::take_load_more_request)).
     ///
     /// # Examples
     ///
@@ -303,6 +367,11 @@ impl App {
             return;
         }
 
+        if self.selected_index == Some(0) && self.can_load_more() {
+            self.load_more_requested = true;
+            return;
+        }
+
         self.selected_index = Some(match self.selected_index {
             None => self.notes.len() - 1,
             Some(0) => self.notes.len() - 1,
@@ -317,8 +386,34 @@ impl App {
     }
 
     /// Scrolls the detail view down by the specified amount.
+    ///
+    /// Stops at the end of the note once the renderer has reported how far
+    /// it can scroll (see [`set_detail_max_scroll`](Self::set_detail_max_scroll)).
     pub fn scroll_detail_down(&mut self, amount: u16) {
-        self.detail_scroll = self.detail_scroll.saturating_add(amount);
+        let scroll = self.detail_scroll.saturating_add(amount);
+        self.detail_scroll = self.detail_max_scroll.map_or(scroll, |max| scroll.min(max));
+    }
+
+    /// Sets the largest detail scroll offset for the current note, clamping the
+    /// current offset to it.
+    ///
+    /// The event loop calls this before each frame with the number of wrapped
+    /// lines that do not fit in the detail panel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::tui::App;
+    ///
+    /// let mut app = App::new();
+    /// app.set_detail_max_scroll(3);
+    ///
+    /// app.scroll_detail_down(10);
+    /// assert_eq!(app.detail_scroll(), 3);
+    /// ```
+    pub fn set_detail_max_scroll(&mut self, max: u16) {
+        self.detail_max_scroll = Some(max);
+        self.detail_scroll = self.detail_scroll.min(max);
     }
 
     /// Scrolls the detail view up by the specified amount.
@@ -357,11 +452,14 @@ impl App {
     /// Returns whether a search is pending and enough time has passed (debounce).
     ///
     /// Returns `true` if filter changed and at least `debounce_ms` milliseconds
-    /// have passed since the last change.
+    /// have passed since the last change, or immediately after [`submit_search`](Self::submit_search).
     pub fn should_search(&self, debounce_ms: u64) -> bool {
         if !self.search_pending {
             return false;
         }
+        if self.search_submitted {
+            return true;
+        }
         match self.search_changed_at {
             Some(changed_at) => changed_at.elapsed().as_millis() >= u128::from(debounce_ms),
             None => false,
@@ -371,6 +469,41 @@ impl App {
     /// Clears the search pending flag after a search is executed.
     pub fn clear_search_pending(&mut self) {
         self.search_pending = false;
+        self.search_submitted = false;
+    }
+
+    /// Requests an immediate search for the current filter (Enter key behavior).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::tui::App;
+    ///
+    /// let mut app = App::new();
+    /// app.push_search_char('r');
+    /// assert!(!app.should_search(1000));
+    ///
+    /// app.submit_search();
+    /// assert!(app.should_search(1000));
+    /// ```
+    pub fn submit_search(&mut self) {
+        self.search_pending = true;
+        self.search_submitted = true;
+    }
+
+    /// Clears the filter and requests a reload of recent notes (Esc key behavior in search).
+    ///
+    /// Any pending search is dropped so the cleared query is not searched.
+    pub fn clear_search(&mut self) {
+        self.search_input.clear();
+        self.search_changed_at = None;
+        self.clear_search_pending();
+        self.reload_requested = true;
+    }
+
+    /// Returns and clears whether recent notes should be reloaded.
+    pub fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
     }
 
     /// Returns whether the filter is empty.
@@ -378,24 +511,289 @@ impl App {
         self.search_input.is_empty()
     }
 
+    /// Returns whether older notes can be loaded into the displayed list.
+    ///
+    /// Only the unfiltered recent notes are paged; search and tag results are not.
+    pub fn can_load_more(&self) -> bool {
+        self.showing_all_notes && self.has_more_notes
+    }
+
+    /// Returns the error message to show in the shortcut bar, if any.
+    pub fn status_message(&self) -> Option<&str> {
+        self.status_message.as_deref()
+    }
+
+    /// Shows `message` in the shortcut bar until [`clear_status_message`](Self::clear_status_message).
+    pub fn set_status_message(&mut self, message: impl Into<String>) {
+        self.status_message = Some(message.into());
+    }
+
+    /// Clears the status message, restoring the shortcut bar.
+    pub fn clear_status_message(&mut self) {
+        self.status_message = None;
+    }
+
+    /// Returns and clears whether older notes should be loaded.
+    pub fn take_load_more_request(&mut self) -> bool {
+        std::mem::take(&mut self.load_more_requested)
+    }
+
+    /// Loads the next page of notes older than the oldest loaded note.
+    ///
+    /// Uses the first (oldest) loaded note as the `before` cursor on
+    /// [`ListNotesOptions`], so notes added in the meantime cannot shift the page.
+    /// The cursor follows creation time, matching the initial load's order. Older notes are placed at the top of the
+    /// oldest-first list; notes already loaded are skipped, and the selection
+    /// stays on the same note. When a fetch comes back empty, further attempts
+    /// are disabled until notes are reloaded.
+    ///
+    /// Returns the number of notes added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if note loading fails.
+    pub fn load_more(&mut self, service: &NoteService) -> Result<usize> {
+        if !self.can_load_more() {
+            return Ok(0);
+        }
+        let Some(oldest) = self.all_notes.first().map(|note| note.id().get()) else {
+            self.has_more_notes = false;
+            return Ok(0);
+        };
+
+        let options = ListNotesOptions {
+            limit: Some(NOTE_PAGE_SIZE),
+            order: SortOrder::Descending,
+            before: Some(oldest),
+            ..Default::default()
+        };
+        let mut older = service
+            .list_notes(options)
+            .context("Failed to load older notes")?;
+
+        if older.is_empty() {
+            self.has_more_notes = false;
+            return Ok(0);
+        }
+
+        // Oldest-first, like the initial load, without notes already shown
+        older.reverse();
+        older.retain(|note| !self.all_notes.iter().any(|loaded| loaded.id() == note.id()));
+        let added = older.len();
+
+        self.all_notes.splice(0..0, older.iter().cloned());
+        self.notes.splice(0..0, older);
+        self.selected_index = self.selected_index.map(|i| i + added);
+
+        Ok(added)
+    }
+
     /// Sets the filtered/displayed notes without updating all_notes.
     ///
     /// Used when search results come from NoteService.dual_search().
     /// Resets selection when notes change.
     pub fn set_filtered_notes(&mut self, notes: Vec<Note>) {
+        self.showing_all_notes = false;
         self.notes = notes;
         self.selected_index = None;
+        self.detail_scroll = 0;
     }
 
     /// Clears the selection (Esc key behavior).
     pub fn clear_selection(&mut self) {
         self.selected_index = None;
+        self.detail_scroll = 0;
     }
 
     /// Returns focus to `SearchInput` (Esc key behavior).
     pub fn reset_focus(&mut self) {
         self.focus = Focus::SearchInput;
     }
+
+    /// Returns the tags shown in the tag overlay with their note counts.
+    pub fn tags(&self) -> &[(String, usize)] {
+        &self.tags
+    }
+
+    /// Returns the currently selected tag index in the tag overlay.
+    pub fn selected_tag_index(&self) -> Option<usize> {
+        self.selected_tag_index
+    }
+
+    /// Returns whether the tag overlay is shown.
+    pub fn tag_panel_open(&self) -> bool {
+        self.tag_panel_open
+    }
+
+    /// Sets the tags shown in the tag overlay, keeping the selection in range.
+    pub fn set_tags(&mut self, tags: Vec<(String, usize)>) {
+        self.tags = tags;
+        self.selected_tag_index = match self.selected_tag_index {
+            _ if self.tags.is_empty() => None,
+            Some(i) => Some(i.min(self.tags.len() - 1)),
+            None => None,
+        };
+    }
+
+    /// Shows or hides the tag overlay (t key behavior).
+    ///
+    /// Opening focuses the overlay and selects the first tag; closing returns
+    /// focus to the note list if the overlay had it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::tui::{App, Focus};
+    ///
+    /// let mut app = App::new();
+    /// app.set_tags(vec![("rust".to_string(), 3)]);
+    ///
+    /// app.toggle_tag_panel();
+    /// assert_eq!(app.focus(), Focus::TagList);
+    /// assert_eq!(app.selected_tag_index(), Some(0));
+    ///
+    /// app.toggle_tag_panel();
+    /// assert!(!app.tag_panel_open());
+    /// assert_eq!(app.focus(), Focus::NoteList);
+    /// ```
+    pub fn toggle_tag_panel(&mut self) {
+        if self.tag_panel_open {
+            self.close_tag_panel();
+        } else {
+            self.tag_panel_open = true;
+            self.focus = Focus::TagList;
+            if self.selected_tag_index.is_none() && !self.tags.is_empty() {
+                self.selected_tag_index = Some(0);
+            }
+        }
+    }
+
+    /// Hides the tag overlay, moving focus to the note list if the overlay had it.
+    pub fn close_tag_panel(&mut self) {
+        self.tag_panel_open = false;
+        if self.focus == Focus::TagList {
+            self.focus = Focus::NoteList;
+        }
+    }
+
+    /// Moves tag selection down in the tag overlay, wrapping to the top.
+    pub fn select_next_tag(&mut self) {
+        if self.tags.is_empty() {
+            self.selected_tag_index = None;
+            return;
+        }
+
+        self.selected_tag_index = Some(match self.selected_tag_index {
+            Some(i) if i + 1 < self.tags.len() => i + 1,
+            _ => 0,
+        });
+    }
+
+    /// Moves tag selection up in the tag overlay, wrapping to the bottom.
+    pub fn select_previous_tag(&mut self) {
+        if self.tags.is_empty() {
+            self.selected_tag_index = None;
+            return;
+        }
+
+        self.selected_tag_index = Some(match self.selected_tag_index {
+            Some(i) if i > 0 => i - 1,
+            _ => self.tags.len() - 1,
+        });
+    }
+
+    /// Requests the notes for the selected tag and closes the overlay (Enter key behavior).
+    ///
+    /// The event loop picks the tag up with [`take_tag_filter_request`](Self::take_tag_filter_request).
+    pub fn filter_by_selected_tag(&mut self) {
+        let Some((name, _)) = self.selected_tag_index.and_then(|i| self.tags.get(i)) else {
+            return;
+        };
+        self.tag_filter_requested = Some(name.clone());
+        self.close_tag_panel();
+    }
+
+    /// Returns and clears the tag whose notes should be loaded, if any.
+    pub fn take_tag_filter_request(&mut self) -> Option<String> {
+        self.tag_filter_requested.take()
+    }
+
+    /// Returns whether the keybinding help overlay is shown.
+    pub fn show_help(&self) -> bool {
+        self.show_help
+    }
+
+    /// Shows or hides the keybinding help overlay (? key behavior).
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    /// Returns the note awaiting delete confirmation, if the prompt is open.
+    pub fn delete_prompt(&self) -> Option<NoteId> {
+        self.delete_prompt
+    }
+
+    /// Opens the delete confirmation prompt for the selected note (d key behavior).
+    ///
+    /// Does nothing when no note is selected.
+    pub fn request_delete(&mut self) {
+        self.delete_prompt = self.selected_note().map(Note::id);
+    }
+
+    /// Confirms the open delete prompt, queueing the note for deletion.
+    ///
+    /// The event loop picks the note up with [`take_delete_request`](Self::take_delete_request).
+    pub fn confirm_delete(&mut self) {
+        if let Some(id) = self.delete_prompt.take() {
+            self.delete_requested = Some(id);
+        }
+    }
+
+    /// Closes the delete prompt without deleting anything.
+    pub fn cancel_delete(&mut self) {
+        self.delete_prompt = None;
+    }
+
+    /// Returns and clears the note whose deletion was confirmed, if any.
+    pub fn take_delete_request(&mut self) -> Option<NoteId> {
+        self.delete_requested.take()
+    }
+
+    /// Removes a deleted note from the loaded and displayed notes.
+    ///
+    /// Keeps the selection on the same position (or the new last note) so
+    /// navigation continues naturally; clears it when no notes remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cons::tui::App;
+    /// use cons::{NoteBuilder, NoteId};
+    ///
+    /// let mut app = App::new();
+    /// app.set_notes(vec![NoteBuilder::new().id(NoteId::new(1)).content("Only note").build()]);
+    /// app.select_next();
+    ///
+    /// app.remove_note(NoteId::new(1));
+    /// assert!(app.notes().is_empty());
+    /// assert_eq!(app.selected_index(), None);
+    /// ```
+    pub fn remove_note(&mut self, id: NoteId) {
+        self.all_notes.retain(|note| note.id() != id);
+
+        let Some(position) = self.notes.iter().position(|note| note.id() == id) else {
+            return;
+        };
+        self.notes.remove(position);
+
+        self.selected_index = match self.selected_index {
+            _ if self.notes.is_empty() => None,
+            Some(i) if i > position => Some(i - 1),
+            Some(i) => Some(i.min(self.notes.len() - 1)),
+            None => None,
+        };
+        self.detail_scroll = 0;
+    }
 }
 
 impl Default for App {
@@ -444,6 +842,7 @@ mod tests {
             limit: Some(50),
             order: SortOrder::Descending,
             tags: None,
+            ..Default::default()
         };
         let notes = service.list_notes(options).expect("failed to list notes");
 
@@ -469,6 +868,7 @@ mod tests {
             limit: Some(50),
             order: SortOrder::Descending,
             tags: None,
+            ..Default::default()
         };
         let notes = service.list_notes(options).expect("failed to list notes");
 
@@ -505,6 +905,7 @@ mod tests {
             limit: Some(50),
             order: SortOrder::Descending,
             tags: None,
+            ..Default::default()
         };
         let mut notes = service.list_notes(options).expect("failed to list notes");
 
@@ -530,6 +931,7 @@ mod tests {
             limit: Some(50),
             order: SortOrder::Descending,
             tags: None,
+            ..Default::default()
         };
         let notes = service.list_notes(options).expect("failed to list notes");
 
@@ -726,6 +1128,263 @@ mod tests {
         assert_eq!(app.selected_index(), None);
     }
 
+    // --- Tag Overlay Tests ---
+
+    fn app_with_tags() -> App {
+        let mut app = App::new();
+        app.set_tags(vec![
+            ("async".to_string(), 2),
+            ("rust".to_string(), 5),
+            ("writing".to_string(), 1),
+        ]);
+        app
+    }
+
+    #[test]
+    fn focus_rotation_includes_tag_list_only_when_open() {
+        let mut app = app_with_tags();
+        app.toggle_tag_panel();
+        assert_eq!(app.focus(), Focus::TagList);
+
+        app.next_focus();
+        assert_eq!(app.focus(), Focus::SearchInput);
+        app.next_focus();
+        assert_eq!(app.focus(), Focus::NoteList);
+        app.next_focus();
+        assert_eq!(app.focus(), Focus::DetailView);
+        app.next_focus();
+        assert_eq!(app.focus(), Focus::TagList);
+
+        app.prev_focus();
+        assert_eq!(app.focus(), Focus::DetailView);
+        app.prev_focus();
+        app.prev_focus();
+        assert_eq!(app.focus(), Focus::SearchInput);
+        app.prev_focus();
+        assert_eq!(app.focus(), Focus::TagList);
+
+        // Closed overlay drops out of the rotation
+        app.toggle_tag_panel();
+        assert_eq!(app.focus(), Focus::NoteList);
+        app.next_focus();
+        app.next_focus();
+        assert_eq!(app.focus(), Focus::SearchInput);
+        app.prev_focus();
+        assert_eq!(app.focus(), Focus::DetailView);
+    }
+
+    #[test]
+    fn tag_selection_wraps_in_both_directions() {
+        let mut app = app_with_tags();
+        app.toggle_tag_panel();
+        assert_eq!(app.selected_tag_index(), Some(0));
+
+        app.select_previous_tag();
+        assert_eq!(app.selected_tag_index(), Some(2));
+        app.select_next_tag();
+        assert_eq!(app.selected_tag_index(), Some(0));
+        app.select_next_tag();
+        assert_eq!(app.selected_tag_index(), Some(1));
+    }
+
+    #[test]
+    fn filter_by_selected_tag_requests_tag_and_closes_overlay() {
+        let mut app = app_with_tags();
+        app.toggle_tag_panel();
+        app.select_next_tag();
+
+        app.filter_by_selected_tag();
+        assert!(!app.tag_panel_open());
+        assert_eq!(app.focus(), Focus::NoteList);
+        assert_eq!(app.take_tag_filter_request().as_deref(), Some("rust"));
+        assert_eq!(app.take_tag_filter_request(), None);
+    }
+
+    #[test]
+    fn filter_without_tags_does_nothing() {
+        let mut app = App::new();
+        app.toggle_tag_panel();
+        assert_eq!(app.selected_tag_index(), None);
+
+        app.filter_by_selected_tag();
+        assert!(app.tag_panel_open());
+        assert_eq!(app.take_tag_filter_request(), None);
+    }
+
+    #[test]
+    fn set_tags_keeps_selection_in_range() {
+        let mut app = app_with_tags();
+        app.toggle_tag_panel();
+        app.select_previous_tag();
+        assert_eq!(app.selected_tag_index(), Some(2));
+
+        app.set_tags(vec![("rust".to_string(), 5)]);
+        assert_eq!(app.selected_tag_index(), Some(0));
+
+        app.set_tags(Vec::new());
+        assert_eq!(app.selected_tag_index(), None);
+    }
+
+    #[test]
+    fn toggle_help_flips_flag() {
+        let mut app = App::new();
+        assert!(!app.show_help());
+
+        app.toggle_help();
+        assert!(app.show_help());
+
+        app.toggle_help();
+        assert!(!app.show_help());
+    }
+
+    // --- Delete Confirmation Tests ---
+
+    fn app_with_notes(count: i64) -> App {
+        let mut app = App::new();
+        app.set_notes(
+            (1..=count)
+                .map(|i| {
+                    NoteBuilder::new()
+                        .id(NoteId::new(i))
+                        .content(format!("Note {i}"))
+                        .build()
+                })
+                .collect(),
+        );
+        app
+    }
+
+    #[test]
+    fn request_delete_without_selection_does_not_prompt() {
+        let mut app = app_with_notes(2);
+
+        app.request_delete();
+        assert_eq!(app.delete_prompt(), None);
+    }
+
+    #[test]
+    fn confirm_delete_queues_selected_note() {
+        let mut app = app_with_notes(2);
+        app.select_next();
+        app.select_next();
+
+        app.request_delete();
+        assert_eq!(app.delete_prompt(), Some(NoteId::new(2)));
+
+        app.confirm_delete();
+        assert_eq!(app.delete_prompt(), None, "prompt closes on confirm");
+        assert_eq!(app.take_delete_request(), Some(NoteId::new(2)));
+        assert_eq!(app.take_delete_request(), None, "request is taken once");
+    }
+
+    #[test]
+    fn cancel_delete_closes_prompt_without_queueing() {
+        let mut app = app_with_notes(2);
+        app.select_next();
+
+        app.request_delete();
+        app.cancel_delete();
+
+        assert_eq!(app.delete_prompt(), None);
+        assert_eq!(app.take_delete_request(), None);
+        assert_eq!(app.notes().len(), 2);
+    }
+
+    #[test]
+    fn remove_note_keeps_selection_in_range() {
+        let mut app = app_with_notes(3);
+        app.select_previous(); // last note (index 2)
+
+        app.remove_note(NoteId::new(3));
+        assert_eq!(app.notes().len(), 2);
+        assert_eq!(app.all_notes().len(), 2);
+        assert_eq!(app.selected_index(), Some(1));
+        assert_eq!(app.selected_note().unwrap().id(), NoteId::new(2));
+
+        // Removing a note above the selection keeps the same note selected
+        app.remove_note(NoteId::new(1));
+        assert_eq!(app.selected_index(), Some(0));
+        assert_eq!(app.selected_note().unwrap().id(), NoteId::new(2));
+    }
+
+    #[test]
+    fn remove_last_note_clears_selection_and_detail() {
+        let mut app = app_with_notes(1);
+        app.select_next();
+
+        app.remove_note(NoteId::new(1));
+        assert!(app.notes().is_empty());
+        assert_eq!(app.selected_index(), None);
+        assert!(
+            app.selected_note().is_none(),
+            "detail pane has nothing to show"
+        );
+    }
+
+    // --- Detail Scroll Tests ---
+
+    #[test]
+    fn detail_scroll_cannot_pass_end_of_note() {
+        let mut app = app_with_notes(1);
+        app.select_next();
+        app.set_detail_max_scroll(2);
+
+        app.scroll_detail_down(1);
+        app.scroll_detail_down(1);
+        app.scroll_detail_down(1);
+        assert_eq!(app.detail_scroll(), 2);
+
+        app.scroll_detail_down(u16::MAX);
+        assert_eq!(app.detail_scroll(), 2);
+    }
+
+    #[test]
+    fn detail_scroll_cannot_go_negative() {
+        let mut app = app_with_notes(1);
+        app.select_next();
+        app.set_detail_max_scroll(5);
+
+        app.scroll_detail_down(1);
+        app.scroll_detail_up(3);
+        assert_eq!(app.detail_scroll(), 0);
+    }
+
+    #[test]
+    fn shrinking_max_scroll_clamps_current_offset() {
+        let mut app = app_with_notes(1);
+        app.select_next();
+        app.set_detail_max_scroll(10);
+        app.scroll_detail_down(8);
+
+        // e.g. the terminal grew and more of the note fits
+        app.set_detail_max_scroll(4);
+        assert_eq!(app.detail_scroll(), 4);
+
+        app.set_detail_max_scroll(0);
+        app.scroll_detail_down(1);
+        assert_eq!(app.detail_scroll(), 0, "short notes do not scroll");
+    }
+
+    #[test]
+    fn changing_selection_resets_detail_scroll() {
+        let mut app = app_with_notes(2);
+        app.select_next();
+        app.set_detail_max_scroll(10);
+
+        app.scroll_detail_down(4);
+        app.select_next();
+        assert_eq!(app.detail_scroll(), 0);
+
+        app.scroll_detail_down(4);
+        app.set_filtered_notes(app.notes().to_vec());
+        assert_eq!(app.detail_scroll(), 0);
+
+        app.select_next();
+        app.scroll_detail_down(4);
+        app.clear_selection();
+        assert_eq!(app.detail_scroll(), 0);
+    }
+
     // --- Debounced Search Tests ---
 
     #[test]
@@ -789,6 +1448,45 @@ mod tests {
         assert!(app.search_is_empty());
     }
 
+    #[test]
+    fn submit_search_skips_debounce_until_searched() {
+        let mut app = App::new();
+        app.push_search_char('r');
+        assert!(!app.should_search(1000));
+
+        app.submit_search();
+        assert!(app.should_search(1000));
+
+        app.clear_search_pending();
+        assert!(!app.should_search(0));
+    }
+
+    #[test]
+    fn search_with_no_matches_shows_empty_list() {
+        let mut app = app_with_notes(2);
+        app.select_next();
+        app.push_search_char('x');
+
+        app.set_filtered_notes(Vec::new());
+        assert!(app.notes().is_empty());
+        assert_eq!(app.selected_index(), None);
+        assert!(app.selected_note().is_none());
+        assert_eq!(app.all_notes().len(), 2, "loaded notes are kept");
+    }
+
+    #[test]
+    fn clear_search_empties_query_and_requests_reload() {
+        let mut app = app_with_notes(2);
+        app.push_search_char('x');
+        app.set_filtered_notes(Vec::new());
+
+        app.clear_search();
+        assert!(app.search_is_empty());
+        assert!(!app.should_search(0), "cleared query is not searched");
+        assert!(app.take_reload_request());
+        assert!(!app.take_reload_request(), "reload is requested once");
+    }
+
     #[test]
     fn set_filtered_notes_updates_displayed_notes() {
         let mut app = App::new();
@@ -886,4 +1584,138 @@ mod tests {
         assert!(app.notes()[0].content().to_lowercase().contains("hello"));
         assert!(app.notes()[1].content().to_lowercase().contains("hello"));
     }
+
+    /// Loads the most recent page of notes into a new App, like the TUI does on start.
+    fn app_with_recent_page(service: &NoteService) -> App {
+        let mut notes = service
+            .list_notes(ListNotesOptions {
+                limit: Some(NOTE_PAGE_SIZE),
+                order: SortOrder::Descending,
+                ..Default::default()
+            })
+            .expect("failed to list notes");
+        notes.reverse();
+
+        let mut app = App::new();
+        app.set_notes(notes);
+        app
+    }
+
+    #[test]
+    fn load_more_adds_older_notes_without_duplicates() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        for i in 1..=120 {
+            service
+                .create_note(&format!("Note {i}"), None)
+                .expect("failed to create note");
+        }
+
+        let mut app = app_with_recent_page(&service);
+        assert!(app.can_load_more());
+        app.select_next(); // oldest loaded note, at the top
+        let selected = app.selected_note().map(Note::id);
+
+        // Moving past the top asks for older notes instead of wrapping
+        app.select_previous();
+        assert_eq!(app.selected_index(), Some(0));
+        assert!(app.take_load_more_request());
+        assert!(!app.take_load_more_request());
+
+        let added = app.load_more(&service).expect("load more should succeed");
+        assert_eq!(added, NOTE_PAGE_SIZE);
+        assert_eq!(app.notes().len(), 2 * NOTE_PAGE_SIZE);
+        assert_eq!(app.all_notes().len(), 2 * NOTE_PAGE_SIZE);
+        assert_eq!(app.selected_note().map(Note::id), selected);
+
+        let ids: Vec<i64> = app.notes().iter().map(|note| note.id().get()).collect();
+        let mut sorted = ids.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(ids, sorted, "notes stay oldest-first with no duplicates");
+        assert_eq!(ids.first(), Some(&21));
+    }
+
+    #[test]
+    fn load_more_pages_by_creation_time_not_id() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        for i in 1..=60 {
+            service
+                .create_note(&format!("Note {i}"), None)
+                .expect("failed to create note");
+        }
+        // Lower ids are newer, e.g. after an import of older notes
+        service
+            .database()
+            .connection()
+            .execute("UPDATE notes SET created_at = 1000 - id", [])
+            .expect("failed to set created_at");
+
+        let mut app = app_with_recent_page(&service);
+        assert_eq!(app.all_notes().first().map(|n| n.id().get()), Some(50));
+
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            10
+        );
+        let ids: Vec<i64> = app.notes().iter().map(|note| note.id().get()).collect();
+        assert_eq!(ids, (1..=60).rev().collect::<Vec<_>>());
+
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            0
+        );
+        assert!(!app.can_load_more());
+    }
+
+    #[test]
+    fn load_more_stops_after_empty_fetch() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        for i in 1..=60 {
+            service
+                .create_note(&format!("Note {i}"), None)
+                .expect("failed to create note");
+        }
+
+        let mut app = app_with_recent_page(&service);
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            10
+        );
+        assert!(app.can_load_more(), "a short page does not prove the end");
+
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            0
+        );
+        assert!(!app.can_load_more());
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            0
+        );
+        assert_eq!(app.notes().len(), 60);
+
+        // With nothing older, moving past the top wraps as usual
+        app.select_next();
+        app.select_previous();
+        assert!(!app.take_load_more_request());
+        assert_eq!(app.selected_index(), Some(59));
+    }
+
+    #[test]
+    fn load_more_is_disabled_for_filtered_results() {
+        let service = NoteService::new(Database::in_memory().expect("failed to create database"));
+        for i in 1..=60 {
+            service
+                .create_note(&format!("Note {i}"), None)
+                .expect("failed to create note");
+        }
+
+        let mut app = app_with_recent_page(&service);
+        app.set_filtered_notes(app.all_notes()[..2].to_vec());
+        assert!(!app.can_load_more());
+        assert_eq!(
+            app.load_more(&service).expect("load more should succeed"),
+            0
+        );
+    }
 }
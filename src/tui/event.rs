@@ -14,10 +14,14 @@ use super::app::{App, Focus};
 /// # Event Handling
 ///
 /// - `q`: Quit application (from any focus state)
+/// - `?`: Toggle the keybinding help (while open, only `?` and Esc are handled)
 /// - `Tab`: Cycle focus between panels
-/// - `Esc`: Return to search input focus
-/// - When `SearchInput` focused: character input updates filter buffer
-/// - When `NoteList` focused: j/k navigation, Enter to select
+/// - `Esc`: Return to search input focus (clears the query if already there)
+/// - When `SearchInput` focused: character input updates filter buffer, Enter searches now
+/// - When `NoteList` focused: j/k navigation, Enter to select, d to delete, t for tags
+/// - When `DetailView` focused: j/k or Up/Down scrolling, t for tags
+/// - When `TagList` focused: j/k navigation, Enter to filter notes by tag, t to close
+/// - While the delete prompt is open: y confirms, n or Esc cancels, other keys are ignored
 ///
 /// # Examples
 ///
@@ -31,11 +35,31 @@ use super::app::{App, Focus};
 /// assert!(should_quit);
 /// ```
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
+    // The help overlay is modal: only ? and Esc (both close it) are handled
+    if app.show_help() {
+        if matches!(key.code, KeyCode::Char('?') | KeyCode::Esc) {
+            app.toggle_help();
+        }
+        return false;
+    }
+
+    // The delete confirmation prompt is modal and captures all keys
+    if app.delete_prompt().is_some() {
+        handle_delete_prompt(app, key);
+        return false;
+    }
+
     // Global quit key - works from any focus state
     if key.code == KeyCode::Char('q') && key.modifiers.is_empty() {
         return true;
     }
 
+    // Global help key - works from any focus state
+    if key.code == KeyCode::Char('?') {
+        app.toggle_help();
+        return false;
+    }
+
     // Global focus cycling with Tab / Shift+Tab (BackTab)
     if key.code == KeyCode::Tab {
         app.next_focus();
@@ -46,8 +70,12 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
         return false;
     }
 
-    // Global Esc - return to search input
+    // Global Esc - return to search input, or clear the query when already there
     if key.code == KeyCode::Esc {
+        if app.focus() == Focus::SearchInput {
+            app.clear_search();
+        }
+        app.close_tag_panel();
         app.reset_focus();
         app.clear_selection();
         return false;
@@ -60,6 +88,7 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
         Focus::DetailView => {
             handle_detail_view(app, key);
         }
+        Focus::TagList => handle_tag_list(app, key),
     }
 
     false
@@ -68,7 +97,8 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
 /// Handles keyboard input when search input is focused.
 ///
 /// Accepts character input and backspace for editing the filter buffer.
-/// Changes mark the filter as pending search (debounced in event loop).
+/// Changes mark the filter as pending search (debounced in event loop);
+/// Enter runs the search without waiting for the debounce.
 fn handle_search_input(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Char(c) if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT => {
@@ -77,6 +107,9 @@ fn handle_search_input(app: &mut App, key: KeyEvent) {
         KeyCode::Backspace => {
             app.pop_search_char();
         }
+        KeyCode::Enter => {
+            app.submit_search();
+        }
         _ => {
             // Ignore other keys when in search input
         }
@@ -85,7 +118,8 @@ fn handle_search_input(app: &mut App, key: KeyEvent) {
 
 /// Handles keyboard input when note list is focused.
 ///
-/// Supports Vim-style navigation (j/k) and Enter to select.
+/// Supports Vim-style navigation (j/k), Enter to select, d to delete, and t
+/// to open the tag overlay.
 fn handle_note_list(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Char('j') if key.modifiers.is_empty() => {
@@ -94,6 +128,12 @@ fn handle_note_list(app: &mut App, key: KeyEvent) {
         KeyCode::Char('k') if key.modifiers.is_empty() => {
             app.select_previous();
         }
+        KeyCode::Char('d') if key.modifiers.is_empty() => {
+            app.request_delete();
+        }
+        KeyCode::Char('t') if key.modifiers.is_empty() => {
+            app.toggle_tag_panel();
+        }
         KeyCode::Enter => {
             // Enter in note list maintains current selection
             // (selection is already set by j/k navigation)
@@ -105,17 +145,61 @@ fn handle_note_list(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Handles keyboard input when the tag overlay is focused.
+///
+/// Supports Vim-style navigation (j/k), Enter to filter notes by the
+/// selected tag, and t to close the overlay.
+fn handle_tag_list(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('j') if key.modifiers.is_empty() => {
+            app.select_next_tag();
+        }
+        KeyCode::Char('k') if key.modifiers.is_empty() => {
+            app.select_previous_tag();
+        }
+        KeyCode::Char('t') if key.modifiers.is_empty() => {
+            app.toggle_tag_panel();
+        }
+        KeyCode::Enter => {
+            app.filter_by_selected_tag();
+        }
+        _ => {
+            // Ignore other keys when in tag list
+        }
+    }
+}
+
+/// Handles keyboard input while the delete confirmation prompt is open.
+fn handle_delete_prompt(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Char('y' | 'Y') => app.confirm_delete(),
+        KeyCode::Char('n' | 'N') | KeyCode::Esc => app.cancel_delete(),
+        _ => {
+            // Ignore other keys until the prompt is answered
+        }
+    }
+}
+
 /// Handles keyboard input when detail view is focused.
 ///
-/// Supports Vim-style scrolling (j/k).
+/// Supports Vim-style scrolling (j/k, or the arrow keys) and t to open the tag overlay.
 fn handle_detail_view(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Char('j') if key.modifiers.is_empty() => {
             app.scroll_detail_down(1);
         }
+        KeyCode::Down => {
+            app.scroll_detail_down(1);
+        }
         KeyCode::Char('k') if key.modifiers.is_empty() => {
             app.scroll_detail_up(1);
         }
+        KeyCode::Up => {
+            app.scroll_detail_up(1);
+        }
+        KeyCode::Char('t') if key.modifiers.is_empty() => {
+            app.toggle_tag_panel();
+        }
         _ => {
             // Ignore other keys when in detail view
         }
@@ -331,6 +415,243 @@ mod tests {
         assert_eq!(app.search_input(), "A");
     }
 
+    #[test]
+    fn enter_in_search_input_submits_search() {
+        let mut app = App::new();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+        );
+        assert!(!app.should_search(1000));
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.should_search(1000));
+    }
+
+    #[test]
+    fn esc_in_search_input_clears_query() {
+        let mut app = App::new();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+        );
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.search_input(), "");
+        assert!(app.take_reload_request());
+    }
+
+    #[test]
+    fn esc_from_note_list_keeps_query() {
+        let mut app = App::new();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+        );
+        app.next_focus();
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.focus(), Focus::SearchInput);
+        assert_eq!(app.search_input(), "r");
+        assert!(!app.take_reload_request());
+    }
+
+    // --- Tag Overlay Tests ---
+
+    #[test]
+    fn t_key_toggles_tag_overlay_and_enter_filters() {
+        let mut app = App::new();
+        app.set_tags(vec![("async".to_string(), 2), ("rust".to_string(), 5)]);
+        app.next_focus(); // -> NoteList
+
+        let key_t = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        handle_key_event(&mut app, key_t);
+        assert!(app.tag_panel_open());
+        assert_eq!(app.focus(), Focus::TagList);
+
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.selected_tag_index(), Some(1));
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!app.tag_panel_open());
+        assert_eq!(app.take_tag_filter_request().as_deref(), Some("rust"));
+
+        // t toggles the overlay closed again without filtering
+        handle_key_event(&mut app, key_t);
+        handle_key_event(&mut app, key_t);
+        assert!(!app.tag_panel_open());
+        assert_eq!(app.take_tag_filter_request(), None);
+    }
+
+    #[test]
+    fn esc_closes_tag_overlay() {
+        let mut app = App::new();
+        app.next_focus(); // -> NoteList
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE),
+        );
+        assert!(app.tag_panel_open());
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.tag_panel_open());
+        assert_eq!(app.focus(), Focus::SearchInput);
+    }
+
+    // --- Help Overlay Tests ---
+
+    #[test]
+    fn question_mark_toggles_help_from_any_focus() {
+        let mut app = App::new();
+        let key_help = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+
+        handle_key_event(&mut app, key_help);
+        assert!(app.show_help());
+        assert_eq!(app.search_input(), "", "? is not typed into the search box");
+
+        handle_key_event(&mut app, key_help);
+        assert!(!app.show_help());
+
+        app.next_focus(); // -> NoteList
+        handle_key_event(&mut app, key_help);
+        assert!(app.show_help());
+    }
+
+    #[test]
+    fn esc_closes_help_without_resetting_focus() {
+        let mut app = note_list_app();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        );
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.show_help());
+        assert_eq!(app.focus(), Focus::NoteList);
+        assert_eq!(app.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn keys_are_swallowed_while_help_is_open() {
+        let mut app = note_list_app();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        );
+
+        for code in [
+            KeyCode::Char('j'),
+            KeyCode::Tab,
+            KeyCode::Char('d'),
+            KeyCode::Char('t'),
+            KeyCode::Enter,
+        ] {
+            handle_key_event(&mut app, KeyEvent::new(code, KeyModifiers::NONE));
+        }
+        let should_quit = handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
+        );
+
+        assert!(!should_quit, "q does not quit while help is open");
+        assert!(app.show_help());
+        assert_eq!(app.focus(), Focus::NoteList);
+        assert_eq!(app.selected_index(), Some(0));
+        assert_eq!(app.delete_prompt(), None);
+        assert!(!app.tag_panel_open());
+    }
+
+    // --- Delete Confirmation Tests ---
+
+    fn note_list_app() -> App {
+        let mut app = App::new();
+        app.set_notes(vec![
+            NoteBuilder::new()
+                .id(NoteId::new(1))
+                .content("Note 1")
+                .build(),
+            NoteBuilder::new()
+                .id(NoteId::new(2))
+                .content("Note 2")
+                .build(),
+        ]);
+        app.next_focus(); // -> NoteList (auto-selects index 0)
+        app
+    }
+
+    #[test]
+    fn d_key_opens_delete_prompt_and_y_confirms() {
+        let mut app = note_list_app();
+
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.delete_prompt(), Some(NoteId::new(1)));
+
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+        );
+        assert_eq!(app.delete_prompt(), None);
+        assert_eq!(app.take_delete_request(), Some(NoteId::new(1)));
+    }
+
+    #[test]
+    fn delete_prompt_cancels_with_n_or_esc() {
+        for cancel in [KeyCode::Char('n'), KeyCode::Esc] {
+            let mut app = note_list_app();
+
+            handle_key_event(
+                &mut app,
+                KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+            );
+            handle_key_event(&mut app, KeyEvent::new(cancel, KeyModifiers::NONE));
+
+            assert_eq!(app.delete_prompt(), None);
+            assert_eq!(app.take_delete_request(), None);
+            assert_eq!(app.focus(), Focus::NoteList, "cancel keeps focus");
+            assert_eq!(app.selected_index(), Some(0), "cancel keeps selection");
+        }
+    }
+
+    #[test]
+    fn delete_prompt_captures_other_keys() {
+        let mut app = note_list_app();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        );
+
+        let should_quit = handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE),
+        );
+        assert!(!should_quit, "q does not quit while the prompt is open");
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+        );
+
+        assert_eq!(app.delete_prompt(), Some(NoteId::new(1)));
+        assert_eq!(app.selected_index(), Some(0));
+    }
+
+    #[test]
+    fn d_key_in_search_input_is_typed() {
+        let mut app = App::new();
+        handle_key_event(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE),
+        );
+
+        assert_eq!(app.search_input(), "d");
+        assert_eq!(app.delete_prompt(), None);
+    }
+
     // --- Task Group 6: Additional Strategic Tests ---
 
     #[test]
@@ -400,6 +721,28 @@ mod tests {
         assert_eq!(app.detail_scroll(), 0, "scroll should not go below 0");
     }
 
+    #[test]
+    fn arrow_keys_scroll_in_detail_view() {
+        let mut app = App::new();
+        app.set_notes(vec![
+            NoteBuilder::new()
+                .id(NoteId::new(1))
+                .content("Note 1")
+                .build(),
+        ]);
+        app.next_focus(); // -> NoteList (auto-selects index 0)
+        app.next_focus(); // -> DetailView
+        app.set_detail_max_scroll(1);
+
+        let down = KeyEvent::new(KeyCode::Down, KeyModifiers::NONE);
+        handle_key_event(&mut app, down);
+        handle_key_event(&mut app, down);
+        assert_eq!(app.detail_scroll(), 1, "scroll stops at the end");
+
+        handle_key_event(&mut app, KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(app.detail_scroll(), 0);
+    }
+
     #[test]
     fn selection_persists_across_focus_changes() {
         // Test that selection is maintained when cycling focus
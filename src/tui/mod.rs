@@ -18,6 +18,7 @@ mod app;
 pub mod event;
 mod ui;
 
+use app::NOTE_PAGE_SIZE;
 pub use app::{App, Focus};
 
 /// Initializes the terminal for TUI rendering.
@@ -120,8 +121,30 @@ fn run_event_loop_internal(
             execute_search(app, service);
         }
 
-        // Render the current state
+        // Reload recent notes (and tag counts) after the query was cleared
+        if app.take_reload_request() {
+            if load_notes(app, service).is_err() {
+                app.apply_filter();
+            }
+            let _ = load_tags(app, service);
+        }
+
+        // Load older notes when the selection moved past the top of the list
+        if app.take_load_more_request() {
+            execute_load_more(app, service);
+        }
+
+        // Show the notes for a tag chosen in the tag overlay
+        if let Some(tag) = app.take_tag_filter_request() {
+            execute_tag_filter(app, service, &tag);
+        }
+
+        // Carry out a confirmed delete
+        execute_pending_delete(app, service);
+
+        // Render the current state, clamping detail scroll to the wrapped note height
         terminal.draw(|frame| {
+            app.set_detail_max_scroll(ui::detail_max_scroll(frame.area(), app));
             ui::draw(frame, app);
         })?;
 
@@ -129,7 +152,8 @@ fn run_event_loop_internal(
         if crossterm_event::poll(std::time::Duration::from_millis(50))?
             && let Event::Key(key) = crossterm_event::read()?
         {
-            // Handle the key event
+            // Handle the key event; any key dismisses an error message
+            app.clear_status_message();
             let should_quit = event::handle_key_event(app, key);
             if should_quit {
                 break;
@@ -169,10 +193,75 @@ fn execute_search(app: &mut App, service: &crate::service::NoteService) {
     }
 }
 
+/// Loads older notes above the list and moves the selection onto the newest of them.
+///
+/// A failed load is shown in the shortcut bar and leaves the list unchanged.
+fn execute_load_more(app: &mut App, service: &crate::service::NoteService) {
+    match app.load_more(service) {
+        Ok(0) => {}
+        Ok(_) => app.select_previous(),
+        Err(e) => app.set_status_message(format!("{e:#}")),
+    }
+}
+
+/// Deletes the note the user confirmed in the delete prompt, if any.
+///
+/// The note is only removed from the App once the database delete succeeds,
+/// so a failed delete leaves it visible.
+fn execute_pending_delete(app: &mut App, service: &crate::service::NoteService) {
+    if let Some(id) = app.take_delete_request()
+        && service.delete_note(id).is_ok()
+    {
+        app.remove_note(id);
+        let _ = load_tags(app, service);
+    }
+}
+
+/// Replaces the displayed notes with the recent notes carrying `tag`.
+///
+/// Uses the same limit and oldest-first display order as [`load_notes`].
+/// On error the displayed notes are left unchanged.
+fn execute_tag_filter(app: &mut App, service: &crate::service::NoteService, tag: &str) {
+    use crate::service::{ListNotesOptions, SortOrder};
+
+    let options = ListNotesOptions {
+        limit: Some(NOTE_PAGE_SIZE),
+        order: SortOrder::Descending,
+        tags: Some(vec![tag.to_string()]),
+        ..Default::default()
+    };
+
+    if let Ok(mut notes) = service.list_notes(options) {
+        notes.reverse();
+        app.set_filtered_notes(notes);
+    }
+}
+
+/// Loads tags with their note counts into the App's tag overlay.
+///
+/// Tags without notes are skipped since filtering by them shows nothing.
+///
+/// # Errors
+///
+/// Returns an error if tag loading fails.
+fn load_tags(app: &mut App, service: &crate::service::NoteService) -> Result<()> {
+    let tags = service
+        .list_tags_with_counts()
+        .context("Failed to load tags")?
+        .into_iter()
+        .filter(|(_, _, count)| *count > 0)
+        .map(|(_, name, count)| (name, count))
+        .collect();
+
+    app.set_tags(tags);
+
+    Ok(())
+}
+
 /// Loads recent notes from the database into the App.
 ///
 /// Uses `NoteService::list_notes()` with:
-/// - limit: Some(50)
+/// - limit: Some(50) (older notes are paged in by `App::load_more`)
 /// - order: SortOrder::Descending
 /// - tags: None
 ///
@@ -186,9 +275,10 @@ fn load_notes(app: &mut App, service: &crate::service::NoteService) -> Result<()
 
     // Load recent notes with descending order (newest first)
     let options = ListNotesOptions {
-        limit: Some(50),
+        limit: Some(NOTE_PAGE_SIZE),
         order: SortOrder::Descending,
         tags: None,
+        ..Default::default()
     };
 
     let mut notes = service
@@ -206,34 +296,25 @@ fn load_notes(app: &mut App, service: &crate::service::NoteService) -> Result<()
 
 /// Entry point for the TUI application.
 ///
-/// Initializes the database connection, loads notes, and starts the event loop.
+/// Loads notes from `db` and starts the event loop.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - Database path cannot be determined
-/// - Database directory creation fails
-/// - Database connection fails
 /// - Note loading fails
+/// - Tag loading fails
 /// - Terminal initialization or event loop fails
-pub fn run() -> Result<()> {
+pub fn run(db: crate::Database) -> Result<()> {
     // Install panic hook to restore terminal on panic
     init_panic_hook();
 
-    // Get database path and ensure directory exists (reusing shared utilities)
-    let db_path = crate::utils::get_database_path().context("Failed to get database path")?;
-    crate::utils::ensure_database_directory(&db_path)
-        .context("Failed to ensure database directory")?;
-
-    // Open database connection
-    let db = crate::Database::open(&db_path).context("Failed to open database")?;
-
     // Create NoteService
     let service = crate::service::NoteService::new(db);
 
     // Create App and load notes
     let mut app = App::new();
     load_notes(&mut app, &service).context("Failed to load notes from database")?;
+    load_tags(&mut app, &service).context("Failed to load tags from database")?;
 
     // Start the TUI event loop with NoteService for debounced search
     run_event_loop(&mut app, &service).context("TUI event loop failed")?;
@@ -318,6 +399,141 @@ mod tests {
         assert_eq!(app.notes().len(), 50);
     }
 
+    #[test]
+    fn execute_search_replaces_notes_with_matches() {
+        use crate::service::NoteService;
+
+        let db = crate::Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        service
+            .create_note("Rust ownership", None)
+            .expect("failed to create note");
+        service
+            .create_note("Grocery list", None)
+            .expect("failed to create note");
+
+        let mut app = App::new();
+        load_notes(&mut app, &service).expect("failed to load notes");
+        for c in "ownership".chars() {
+            app.push_search_char(c);
+        }
+        app.submit_search();
+        execute_search(&mut app, &service);
+        assert_eq!(app.notes().len(), 1);
+        assert_eq!(app.notes()[0].content(), "Rust ownership");
+
+        app.clear_search();
+        for c in "zebra".chars() {
+            app.push_search_char(c);
+        }
+        execute_search(&mut app, &service);
+        assert!(app.notes().is_empty(), "no matches shows an empty list");
+
+        // Clearing the query reloads recent notes, including ones added since
+        service
+            .create_note("Added later", None)
+            .expect("failed to create note");
+        app.clear_search();
+        assert!(app.take_reload_request());
+        load_notes(&mut app, &service).expect("failed to load notes");
+        assert_eq!(app.notes().len(), 3);
+    }
+
+    #[test]
+    fn tag_overlay_filters_notes_by_chosen_tag() {
+        use crate::service::NoteService;
+
+        let db = crate::Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        service
+            .create_note("Ownership", Some(&["rust"]))
+            .expect("failed to create note");
+        service
+            .create_note("Tokio select", Some(&["rust", "async"]))
+            .expect("failed to create note");
+        service
+            .create_note("Grocery list", None)
+            .expect("failed to create note");
+        service
+            .get_or_create_tag("unused")
+            .expect("failed to create tag");
+
+        let mut app = App::new();
+        load_notes(&mut app, &service).expect("failed to load notes");
+        load_tags(&mut app, &service).expect("failed to load tags");
+        assert_eq!(
+            app.tags(),
+            &[("async".to_string(), 1), ("rust".to_string(), 2)],
+            "tags without notes are skipped"
+        );
+
+        app.toggle_tag_panel();
+        app.select_next_tag(); // rust
+        app.filter_by_selected_tag();
+        let tag = app
+            .take_tag_filter_request()
+            .expect("tag should be requested");
+        execute_tag_filter(&mut app, &service, &tag);
+
+        let contents: Vec<&str> = app.notes().iter().map(|n| n.content()).collect();
+        assert_eq!(contents, vec!["Ownership", "Tokio select"]);
+        assert_eq!(app.all_notes().len(), 3, "loaded notes are kept");
+    }
+
+    #[test]
+    fn execute_pending_delete_removes_note_from_database_and_app() {
+        use crate::service::NoteService;
+
+        let db = crate::Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        let note = service
+            .create_note("Delete me", None)
+            .expect("failed to create note");
+
+        let mut app = App::new();
+        load_notes(&mut app, &service).expect("failed to load notes");
+        app.next_focus(); // -> NoteList (auto-selects the note)
+        app.request_delete();
+        app.confirm_delete();
+
+        execute_pending_delete(&mut app, &service);
+
+        assert!(app.notes().is_empty());
+        assert!(app.selected_note().is_none());
+        assert_eq!(
+            service.get_note(note.id()).expect("failed to get note"),
+            None
+        );
+    }
+
+    #[test]
+    fn execute_load_more_reports_errors_in_status_line() {
+        use crate::service::NoteService;
+
+        let db = crate::Database::in_memory().expect("failed to create in-memory database");
+        let service = NoteService::new(db);
+        for i in 1..=NOTE_PAGE_SIZE {
+            service
+                .create_note(&format!("Note {i}"), None)
+                .expect("failed to create note");
+        }
+
+        let mut app = App::new();
+        load_notes(&mut app, &service).expect("failed to load notes");
+        assert!(app.can_load_more());
+        service
+            .database()
+            .connection()
+            .execute_batch("ALTER TABLE notes RENAME TO notes_gone")
+            .expect("failed to rename table");
+
+        execute_load_more(&mut app, &service);
+
+        let message = app.status_message().expect("error should be shown");
+        assert!(message.contains("Failed to load older notes"), "{message}");
+        assert_eq!(app.notes().len(), NOTE_PAGE_SIZE);
+    }
+
     // --- Task Group 6: Additional Strategic Tests ---
 
     #[test]
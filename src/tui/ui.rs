@@ -8,11 +8,12 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 use time::format_description;
 
 use super::app::{App, Focus};
+use crate::models::{Note, NoteId};
 
 /// Main rendering function for the TUI.
 ///
@@ -25,7 +26,37 @@ use super::app::{App, Focus};
 /// * `app` - The application state containing notes, selection, and focus
 pub fn draw(frame: &mut Frame, app: &App) {
     let size = frame.area();
+    let areas = panel_areas(size);
 
+    // Render each panel
+    render_search_input(frame, app, areas.search);
+    render_note_list(frame, app, areas.note_list);
+    render_detail_view(frame, app, areas.detail);
+    render_shortcut_bar(frame, app, areas.shortcuts);
+
+    // Overlays draw on top of the panels, with modal prompts topmost
+    if app.tag_panel_open() {
+        render_tag_list(frame, app, areas.content);
+    }
+    if let Some(id) = app.delete_prompt() {
+        render_delete_prompt(frame, id, size);
+    }
+    if app.show_help() {
+        render_help(frame, size);
+    }
+}
+
+/// Screen regions of the three-panel layout.
+struct PanelAreas {
+    search: Rect,
+    content: Rect,
+    note_list: Rect,
+    detail: Rect,
+    shortcuts: Rect,
+}
+
+/// Splits the screen into the three-panel layout.
+fn panel_areas(area: Rect) -> PanelAreas {
     // Create main layout: search input at top, content in middle, shortcuts at bottom
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -34,7 +65,7 @@ pub fn draw(frame: &mut Frame, app: &App) {
             Constraint::Min(0),    // Content area
             Constraint::Length(1), // Shortcut bar
         ])
-        .split(size);
+        .split(area);
 
     // Split content area horizontally: note list (30%) | detail view (70%)
     let content_chunks = Layout::default()
@@ -45,11 +76,13 @@ pub fn draw(frame: &mut Frame, app: &App) {
         ])
         .split(main_chunks[1]);
 
-    // Render each panel
-    render_search_input(frame, app, main_chunks[0]);
-    render_note_list(frame, app, content_chunks[0]);
-    render_detail_view(frame, app, content_chunks[1]);
-    render_shortcut_bar(frame, app, main_chunks[2]);
+    PanelAreas {
+        search: main_chunks[0],
+        content: main_chunks[1],
+        note_list: content_chunks[0],
+        detail: content_chunks[1],
+        shortcuts: main_chunks[2],
+    }
 }
 
 /// Renders the search input panel at the top of the screen.
@@ -177,121 +210,160 @@ fn render_detail_view(frame: &mut Frame, app: &App, area: Rect) {
         .border_style(border_style);
 
     // Build content based on selected note
-    let content = if let Some(note) = app.selected_note() {
-        let mut text = Text::default();
+    let content = app
+        .selected_note()
+        .map_or_else(|| Text::from("No note selected"), detail_text);
 
-        // Original content section (rendered as markdown)
-        text.lines.push(Line::from(vec![Span::styled(
-            "Content:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]));
-        let content_md = tui_markdown::from_str(note.content());
-        text.lines.extend(content_md.lines);
-
-        // Enhanced content section (if available)
-        if let Some(enhanced) = note.content_enhanced() {
-            text.lines.push(Line::from(""));
-            text.lines
-                .push(Line::from("───────────────────────────────"));
-            text.lines.push(Line::from(""));
-
-            // Enhanced label with confidence
-            if let Some(confidence) = note.enhancement_confidence() {
-                #[allow(clippy::cast_possible_truncation)]
-                let confidence_pct = (confidence * 100.0) as i32;
-                text.lines.push(Line::from(vec![
-                    Span::styled("Enhanced:", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(format!(" ({confidence_pct}% confidence)")),
-                ]));
-            } else {
-                text.lines.push(Line::from(vec![Span::styled(
-                    "Enhanced:",
-                    Style::default().add_modifier(Modifier::BOLD),
-                )]));
-            }
-
-            // Render enhanced content as markdown
-            let enhanced_md = tui_markdown::from_str(enhanced);
-            text.lines.extend(enhanced_md.lines);
-        }
+    let paragraph = Paragraph::new(content)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_scroll(), 0));
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Builds the detail view text for a note.
+///
+/// Shared by rendering and [`detail_max_scroll`] so scrolling is clamped to
+/// exactly what is drawn.
+fn detail_text(note: &Note) -> Text<'_> {
+    let mut text = Text::default();
+
+    // Original content section (rendered as markdown)
+    text.lines.push(Line::from(vec![Span::styled(
+        "Content:",
+        Style::default().add_modifier(Modifier::BOLD),
+    )]));
+    let content_md = tui_markdown::from_str(note.content());
+    text.lines.extend(content_md.lines);
+
+    // Enhanced content section (if available)
+    if let Some(enhanced) = note.content_enhanced() {
+        text.lines.push(Line::from(""));
+        text.lines
+            .push(Line::from("───────────────────────────────"));
+        text.lines.push(Line::from(""));
 
-        // Tags section
-        if !note.tags().is_empty() {
-            text.lines.push(Line::from(""));
+        // Enhanced label with confidence
+        if let Some(confidence) = note.enhancement_confidence() {
+            #[allow(clippy::cast_possible_truncation)]
+            let confidence_pct = (confidence * 100.0) as i32;
+            text.lines.push(Line::from(vec![
+                Span::styled("Enhanced:", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!(" ({confidence_pct}% confidence)")),
+            ]));
+        } else {
             text.lines.push(Line::from(vec![Span::styled(
-                "Tags:",
+                "Enhanced:",
                 Style::default().add_modifier(Modifier::BOLD),
             )]));
-
-            for tag in note.tags() {
-                let source_indicator = if tag.source().is_user() {
-                    "user".to_string()
-                } else {
-                    format!("llm {}%", tag.confidence())
-                };
-
-                text.lines.push(Line::from(vec![
-                    Span::raw("  - "),
-                    Span::styled(tag.name().to_string(), Style::default().fg(Color::Cyan)),
-                    Span::raw(" "),
-                    Span::styled(
-                        format!("({source_indicator})"),
-                        Style::default()
-                            .fg(Color::DarkGray)
-                            .add_modifier(Modifier::ITALIC),
-                    ),
-                ]));
-            }
         }
 
-        // Timestamps section
+        // Render enhanced content as markdown
+        let enhanced_md = tui_markdown::from_str(enhanced);
+        text.lines.extend(enhanced_md.lines);
+    }
+
+    // Tags section
+    if !note.tags().is_empty() {
         text.lines.push(Line::from(""));
-        let date_format =
-            format_description::parse("[year]-[month]-[day] [hour]:[minute]:[second]")
-                .expect("valid datetime format");
+        text.lines.push(Line::from(vec![Span::styled(
+            "Tags:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
+
+        for tag in note.tags() {
+            let source_indicator = if tag.source().is_user() {
+                "user".to_string()
+            } else if tag.source().is_import() {
+                "import".to_string()
+            } else {
+                format!("llm {}%", tag.confidence())
+            };
 
+            text.lines.push(Line::from(vec![
+                Span::raw("  - "),
+                Span::styled(tag.name().to_string(), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("({source_indicator})"),
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+            ]));
+        }
+    }
+
+    // Timestamps section
+    text.lines.push(Line::from(""));
+    let date_format = format_description::parse("[year]-[month]-[day] [hour]:[minute]:[second]")
+        .expect("valid datetime format");
+
+    text.lines.push(Line::from(vec![
+        Span::styled("Created:", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" "),
+        Span::styled(
+            note.created_at()
+                .format(&date_format)
+                .unwrap_or_else(|_| "????-??-?? ??:??:??".to_string()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+
+    if let Some(enhanced_at) = note.enhanced_at() {
         text.lines.push(Line::from(vec![
-            Span::styled("Created:", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled("Enhanced:", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(" "),
             Span::styled(
-                note.created_at()
+                enhanced_at
                     .format(&date_format)
                     .unwrap_or_else(|_| "????-??-?? ??:??:??".to_string()),
                 Style::default().fg(Color::DarkGray),
             ),
         ]));
+    }
 
-        if let Some(enhanced_at) = note.enhanced_at() {
-            text.lines.push(Line::from(vec![
-                Span::styled("Enhanced:", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" "),
-                Span::styled(
-                    enhanced_at
-                        .format(&date_format)
-                        .unwrap_or_else(|_| "????-??-?? ??:??:??".to_string()),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]));
-        }
+    text
+}
 
-        text
-    } else {
-        Text::from("No note selected")
+/// Returns the largest useful detail view scroll offset for the selected note.
+///
+/// Counts the rows the detail text occupies once wrapped to the detail panel
+/// inside `area` (the whole screen), minus the rows that fit on screen.
+/// Returns 0 when nothing is selected or the note fits.
+pub fn detail_max_scroll(area: Rect, app: &App) -> u16 {
+    let Some(note) = app.selected_note() else {
+        return 0;
     };
 
-    let paragraph = Paragraph::new(content)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.detail_scroll(), 0));
+    let detail = panel_areas(area).detail;
+    // Borders take one row/column on each side
+    let width = usize::from(detail.width.saturating_sub(2)).max(1);
+    let height = usize::from(detail.height.saturating_sub(2));
 
-    frame.render_widget(paragraph, area);
+    let rows: usize = detail_text(note)
+        .lines
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+
+    u16::try_from(rows.saturating_sub(height)).unwrap_or(u16::MAX)
 }
 
 /// Renders the shortcut bar at the bottom of the screen.
 ///
-/// Shows context-aware keyboard shortcuts based on current focus state.
+/// Shows context-aware keyboard shortcuts based on current focus state, or the
+/// app's status message in red when one is set.
 /// Format: `Key: action | Key: action` with keys highlighted in cyan.
 fn render_shortcut_bar(frame: &mut Frame, app: &App, area: Rect) {
+    // An error takes the bar's place until the next key press
+    if let Some(message) = app.status_message() {
+        let paragraph = Paragraph::new(message).style(Style::default().fg(Color::Red));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
     let key_style = Style::default().fg(Color::Cyan);
     let sep_style = Style::default().fg(Color::DarkGray);
 
@@ -308,6 +380,9 @@ fn render_shortcut_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(" | ", sep_style),
         Span::styled("Esc", key_style),
         Span::raw(": reset"),
+        Span::styled(" | ", sep_style),
+        Span::styled("?", key_style),
+        Span::raw(": help"),
     ];
 
     // Add focus-specific shortcuts
@@ -316,14 +391,36 @@ fn render_shortcut_bar(frame: &mut Frame, app: &App, area: Rect) {
             spans.push(Span::styled(" | ", sep_style));
             spans.push(Span::styled("j/k", key_style));
             spans.push(Span::raw(": navigate"));
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("d", key_style));
+            spans.push(Span::raw(": delete"));
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("t", key_style));
+            spans.push(Span::raw(": tags"));
         }
         Focus::DetailView => {
             spans.push(Span::styled(" | ", sep_style));
             spans.push(Span::styled("j/k", key_style));
             spans.push(Span::raw(": scroll"));
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("t", key_style));
+            spans.push(Span::raw(": tags"));
+        }
+        Focus::TagList => {
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("j/k", key_style));
+            spans.push(Span::raw(": navigate"));
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("Enter", key_style));
+            spans.push(Span::raw(": filter"));
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("t", key_style));
+            spans.push(Span::raw(": close"));
         }
         Focus::SearchInput => {
-            // No additional shortcuts for search input
+            spans.push(Span::styled(" | ", sep_style));
+            spans.push(Span::styled("Enter", key_style));
+            spans.push(Span::raw(": search"));
         }
     }
 
@@ -333,6 +430,141 @@ fn render_shortcut_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Renders the tag overlay over the right side of the content area.
+///
+/// Lists each tag with its note count and highlights the selected tag.
+fn render_tag_list(frame: &mut Frame, app: &App, area: Rect) {
+    let is_focused = matches!(app.focus(), Focus::TagList);
+
+    // Create block with focus-dependent border style
+    let border_style = if is_focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Tags")
+        .border_style(border_style);
+
+    let items: Vec<ListItem> = app
+        .tags()
+        .iter()
+        .map(|(name, count)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(name.clone(), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("({count})"),
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::REVERSED),
+    );
+
+    let mut list_state = ListState::default();
+    list_state.select(app.selected_tag_index());
+
+    // Overlay the right third of the content area, covering part of the detail view
+    let width = area.width / 3;
+    let overlay = Rect::new(area.x + area.width - width, area.y, width, area.height);
+
+    frame.render_widget(Clear, overlay);
+    frame.render_stateful_widget(list, overlay, &mut list_state);
+}
+
+/// Keybindings listed in the help overlay, as (keys, description).
+const HELP_KEYS: &[(&str, &str)] = &[
+    ("Tab / Shift+Tab", "cycle focus between panels"),
+    (
+        "j/k, Up/Down",
+        "navigate the list (k at the top loads older notes) or scroll the detail view",
+    ),
+    ("Enter", "search now (search box) or filter by tag (tags)"),
+    ("Esc", "back to search; clear the query when already there"),
+    ("d", "delete the selected note"),
+    ("t", "show or hide the tag list"),
+    ("?", "show or hide this help"),
+    ("q", "quit"),
+];
+
+/// Renders the keybinding help centered over the screen.
+fn render_help(frame: &mut Frame, area: Rect) {
+    let key_style = Style::default().fg(Color::Cyan);
+    let key_width = HELP_KEYS
+        .iter()
+        .map(|(keys, _)| keys.len())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = HELP_KEYS
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(format!("{keys:<key_width$}"), key_style),
+                Span::raw("  "),
+                Span::raw(*description),
+            ])
+        })
+        .collect();
+
+    // Borders take one row/column on each side
+    let width = area.width.min(72);
+    let height = area
+        .height
+        .min(u16::try_from(lines.len() + 2).unwrap_or(u16::MAX));
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Help (? or Esc to close)")
+        .border_style(key_style);
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// Renders the delete confirmation prompt centered over the screen.
+fn render_delete_prompt(frame: &mut Frame, id: NoteId, area: Rect) {
+    let key_style = Style::default().fg(Color::Cyan);
+    let width = area.width.min(40);
+    let height = area.height.min(3);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Confirm")
+        .border_style(Style::default().fg(Color::Red));
+    let line = Line::from(vec![
+        Span::raw(format!("Delete note #{id}? ")),
+        Span::styled("y", key_style),
+        Span::raw("/"),
+        Span::styled("n", key_style),
+    ]);
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(line).block(block), popup);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -568,4 +800,128 @@ mod tests {
         // The detail view should only show Content section, no separator or Enhanced section
         // This is tested implicitly by the render function handling None cases
     }
+
+    #[test]
+    fn tag_overlay_renders_tags_with_counts() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = create_test_app();
+        app.set_tags(vec![("async".to_string(), 1), ("rust".to_string(), 2)]);
+        app.toggle_tag_panel();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 24)).expect("test terminal");
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .expect("draw should succeed");
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Tags"));
+        assert!(screen.contains("async (1)"));
+        assert!(screen.contains("rust (2)"));
+        assert!(screen.contains("Enter: filter"));
+    }
+
+    #[test]
+    fn detail_max_scroll_counts_wrapped_lines_beyond_panel() {
+        let mut app = App::new();
+        let long_content = (1..=40)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        app.set_notes(vec![
+            NoteBuilder::new()
+                .id(NoteId::new(1))
+                .content(long_content)
+                .created_at(OffsetDateTime::now_utc())
+                .build(),
+            NoteBuilder::new()
+                .id(NoteId::new(2))
+                .content("Short")
+                .created_at(OffsetDateTime::now_utc())
+                .build(),
+        ]);
+        let area = Rect::new(0, 0, 100, 30);
+
+        assert_eq!(detail_max_scroll(area, &app), 0, "nothing selected");
+
+        app.select_next();
+        let max = detail_max_scroll(area, &app);
+        // 30 rows minus search (3), shortcuts (1) and borders (2) leaves 24 visible
+        let rows = u16::try_from(detail_text(app.selected_note().unwrap()).lines.len()).unwrap();
+        assert_eq!(max, rows - 24);
+
+        app.select_next();
+        assert_eq!(detail_max_scroll(area, &app), 0, "short notes fit");
+    }
+
+    #[test]
+    fn help_overlay_lists_keybindings() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = create_test_app();
+        app.toggle_help();
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).expect("test terminal");
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .expect("draw should succeed");
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Help (? or Esc to close)"));
+        assert!(screen.contains("delete the selected note"));
+        assert!(screen.contains("quit"));
+    }
+
+    #[test]
+    fn status_message_replaces_shortcut_bar() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = create_test_app();
+        app.set_status_message("Failed to load older notes");
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).expect("test terminal");
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .expect("draw should succeed");
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Failed to load older notes"));
+        assert!(!screen.contains(": quit"));
+    }
+
+    #[test]
+    fn delete_prompt_renders_over_panels() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = create_test_app();
+        app.select_next();
+        app.request_delete();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("test terminal");
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .expect("draw should succeed");
+
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Delete note #1? y/n"));
+    }
 }
@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
+use crate::config::Config;
 use crate::{Database, TagAssignment};
 
 /// Gets the cross-platform database path.
@@ -25,6 +26,32 @@ pub fn get_database_path() -> Result<PathBuf> {
     Ok(data_dir.join("cons").join("notes.db"))
 }
 
+/// Resolves the database path for this invocation.
+///
+/// Precedence is the `--db` flag, then the `CONS_DB_PATH` environment
+/// variable, then the default from [`get_database_path`]. An empty
+/// `CONS_DB_PATH` is treated as unset.
+///
+/// # Errors
+///
+/// Returns an error if the default is needed and the data directory cannot
+/// be determined.
+pub fn resolve_database_path(flag: Option<&Path>) -> Result<PathBuf> {
+    resolve_database_path_with(flag, Config::load())
+}
+
+/// Resolves the database path, reading `CONS_DB_PATH` from `config`.
+fn resolve_database_path_with(flag: Option<&Path>, config: Config) -> Result<PathBuf> {
+    if let Some(path) = flag {
+        return Ok(path.to_path_buf());
+    }
+
+    match config.db_path {
+        Some(path) => Ok(path),
+        None => get_database_path(),
+    }
+}
+
 /// Ensures the parent directory of the database file exists.
 ///
 /// Creates the directory structure if it doesn't exist using `create_dir_all`.
@@ -41,6 +68,25 @@ pub fn ensure_database_directory(db_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Returns the hash used to detect notes with identical content.
+///
+/// The hash is the lowercase hex SHA-256 digest of the content with
+/// surrounding whitespace trimmed, so it is stable across runs and versions.
+///
+/// # Examples
+///
+/// ```
+/// use cons::content_hash;
+///
+/// assert_eq!(content_hash("Learning Rust\n"), content_hash("  Learning Rust"));
+/// assert_eq!(content_hash("").len(), 64);
+/// ```
+pub fn content_hash(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(content.trim().as_bytes()))
+}
+
 /// Gets tag names from the database for the given tag assignments.
 ///
 /// Uses a single batch query with IN clause for efficiency.
@@ -94,6 +140,25 @@ mod tests {
         assert!(path.to_string_lossy().contains("notes.db"));
     }
 
+    #[test]
+    fn resolve_database_path_prefers_flag_then_env_then_default() {
+        // Read through a fixed lookup so parallel tests never see CONS_DB_PATH change
+        let config = |value: &'static str| {
+            Config::from_lookup(move |name| (name == "CONS_DB_PATH").then(|| value.into()))
+        };
+
+        let from_flag = resolve_database_path_with(
+            Some(Path::new("/tmp/flag/notes.db")),
+            config("/tmp/env/notes.db"),
+        );
+        let from_env = resolve_database_path_with(None, config("/tmp/env/notes.db"));
+        let from_empty_env = resolve_database_path_with(None, config(""));
+
+        assert_eq!(from_flag.unwrap(), PathBuf::from("/tmp/flag/notes.db"));
+        assert_eq!(from_env.unwrap(), PathBuf::from("/tmp/env/notes.db"));
+        assert_eq!(from_empty_env.unwrap(), get_database_path().unwrap());
+    }
+
     #[test]
     fn get_tag_names_resolves_tag_ids_to_display_names() {
         let db = Database::in_memory().expect("failed to create in-memory database");
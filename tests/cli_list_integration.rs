@@ -28,6 +28,7 @@ fn list_notes(
         limit: Some(limit),
         tags: parsed_tags,
         order: SortOrder::Descending,
+        ..Default::default()
     };
 
     // Retrieve notes (newest first from DB)
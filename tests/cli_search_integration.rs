@@ -165,6 +165,7 @@ fn search_across_all_indexed_fields() -> Result<()> {
         "test-model",
         0.85,
         now,
+        None,
     )?;
 
     // Search for "quantum" - should find all 3 via different indexed fields
@@ -300,6 +301,7 @@ fn search_enhanced_content_with_real_ollama() {
             &model,
             enhancement.confidence(),
             now,
+            enhancement.prompt(),
         )
         .expect("Failed to store enhancement");
 
@@ -377,6 +379,7 @@ fn end_to_end_search_workflow_with_ollama() {
                         &model,
                         enhancement.confidence(),
                         now,
+                        enhancement.prompt(),
                     )
                     .expect("Failed to store enhancement");
             }